@@ -6,7 +6,28 @@
 /**
  * Defines the current ABI version
  */
-#define MUN_ABI_VERSION 300
+#define MUN_ABI_VERSION 1200
+
+/**
+ * Represents the privacy level of modules, functions, or variables.
+ */
+enum MunPrivacy
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * Publicly (and privately) accessible
+     */
+    Public = 0,
+    /**
+     * Privately accessible
+     */
+    Private = 1,
+};
+#ifndef __cplusplus
+typedef uint8_t MunPrivacy;
+#endif // __cplusplus
 
 /**
  * Represents the kind of memory management a struct uses.
@@ -148,7 +169,19 @@ typedef struct MunFunctionPrototype {
  * Represents a function definition. A function definition contains the name,
  * type signature, and a pointer to the implementation.
  *
- * `fn_ptr` can be used to call the declared function.
+ * `fn_ptr` can be used to call the declared function. Value struct arguments
+ * are passed as a handle to a heap-allocated copy of the struct. A value
+ * struct is not returned directly; instead the function receives a pointer to
+ * a caller-allocated buffer as its first argument, to which it writes the
+ * return value. The buffer must have the size and alignment of the struct's
+ * `TypeDefinition`.
+ *
+ * `trampoline_ptr` calls the same function without knowing its signature at
+ * compile time. It has the signature
+ * `void trampoline(const void* const* args, void* ret)`, where `args` points
+ * to a pointer to every argument, each passed as for `fn_ptr`. The value that
+ * `fn_ptr` returns, or writes to its return slot, is written to `ret`, which
+ * is ignored if the function doesn't return a value.
  */
 typedef struct MunFunctionDefinition {
     /**
@@ -159,6 +192,21 @@ typedef struct MunFunctionDefinition {
      * Function pointer
      */
     const void *fn_ptr;
+    /**
+     * Pointer to a trampoline that calls the function with arguments read
+     * from an array, or null if the function can only be called through
+     * `fn_ptr`
+     */
+    const void *trampoline_ptr;
+    /**
+     * Documentation of the function, or null if it has none
+     */
+    const char *docs;
+    /**
+     * Whether the function can be called by the host. A private function is
+     * only exposed to link other assemblies of the same package.
+     */
+    MunPrivacy privacy;
 } MunFunctionDefinition;
 
 /**
@@ -181,6 +229,10 @@ typedef struct MunStructDefinition {
      * Struct fields' offsets
      */
     const uint16_t *field_offsets;
+    /**
+     * Documentation of the struct, or null if it has none
+     */
+    const char *docs;
     /**
      * Number of fields
      */
@@ -242,15 +294,37 @@ typedef struct MunTypeDefinition {
      */
     uint32_t size_in_bits;
     /**
-     * The alignment of the type
+     * The alignment of the type in bytes
      */
-    uint8_t alignment;
+    uint16_t alignment;
     /**
      * Type group
      */
     union MunTypeDefinitionData data;
 } MunTypeDefinition;
 
+/**
+ * Represents a global variable (a `static` item) of a module. The storage of
+ * the global lives in the assembly and is pointed to by `value_ptr`.
+ *
+ * When an assembly is reloaded the runtime copies the value of each global
+ * from the old assembly into the storage of the new assembly.
+ */
+typedef struct MunGlobalDefinition {
+    /**
+     * Fully qualified name of the global
+     */
+    const char *name;
+    /**
+     * The type of the global
+     */
+    union MunTypeId type_id;
+    /**
+     * Pointer to the storage of the global
+     */
+    void *value_ptr;
+} MunGlobalDefinition;
+
 /**
  * Represents a module declaration.
  */
@@ -267,6 +341,10 @@ typedef struct MunModuleInfo {
      * Module types
      */
     const struct MunTypeDefinition *types;
+    /**
+     * Module globals
+     */
+    const struct MunGlobalDefinition *globals;
     /**
      * Number of module functions
      */
@@ -275,6 +353,10 @@ typedef struct MunModuleInfo {
      * Number of module types
      */
     uint32_t num_types;
+    /**
+     * Number of module globals
+     */
+    uint32_t num_globals;
 } MunModuleInfo;
 
 /**
@@ -322,6 +404,21 @@ typedef struct MunTypeLut {
     uint32_t num_entries;
 } MunTypeLut;
 
+/**
+ * Represents a function that is marked with the `#[test]` attribute. Test
+ * functions take no arguments and return nothing.
+ */
+typedef struct MunTestDefinition {
+    /**
+     * Fully qualified name of the test
+     */
+    const char *name;
+    /**
+     * Pointer to the test function
+     */
+    const void *fn_ptr;
+} MunTestDefinition;
+
 /**
  * Represents an assembly declaration.
  */
@@ -346,6 +443,32 @@ typedef struct MunAssemblyInfo {
      * Number of dependencies
      */
     uint32_t num_dependencies;
+    /**
+     * Pointer to the `init` function, or null if the assembly has none
+     */
+    const void *init_fn;
+    /**
+     * Pointer to the `shutdown` function, or null if the assembly has none
+     */
+    const void *shutdown_fn;
+    /**
+     * Functions that are marked with the `#[test]` attribute
+     */
+    const struct MunTestDefinition *tests;
+    /**
+     * Number of tests
+     */
+    uint32_t num_tests;
+    /**
+     * Version of the package from which the assembly was compiled, or null if
+     * it was not compiled from a package
+     */
+    const char *version;
+    /**
+     * Version requirements of the assembly dependencies, in the same order as
+     * `dependencies`. An entry is null if the dependency has no version.
+     */
+    const char *const *dependency_versions;
 } MunAssemblyInfo;
 
 #endif /* MUN_ABI_H_ */
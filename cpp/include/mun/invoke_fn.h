@@ -1,7 +1,9 @@
 #ifndef MUN_INVOKE_FN_H_
 #define MUN_INVOKE_FN_H_
 
+#include <cstddef>
 #include <iostream>
+#include <new>
 #include <optional>
 #include <string_view>
 
@@ -9,9 +11,42 @@
 #include "mun/marshal.h"
 #include "mun/reflection.h"
 #include "mun/runtime.h"
+#include "mun/struct_ref.h"
+#include "mun/struct_type.h"
 #include "mun/util.h"
 
 namespace mun {
+namespace details {
+/** A buffer, with the size and alignment of a value struct, to which a Mun
+ * function writes its return value.
+ */
+class ReturnSlot {
+public:
+    /** Allocates an uninitialized buffer for a value of type `type_info`.
+     *
+     * \param type_info the type of the value struct
+     */
+    explicit ReturnSlot(const Type& type_info) noexcept
+        : m_alignment(type_info.alignment()),
+          m_data(::operator new(type_info.size(), std::align_val_t(m_alignment))) {}
+
+    ~ReturnSlot() noexcept { ::operator delete(m_data, std::align_val_t(m_alignment)); }
+
+    ReturnSlot(const ReturnSlot&) = delete;
+    ReturnSlot& operator=(const ReturnSlot&) = delete;
+
+    /** Retrieves a pointer to the buffer.
+     *
+     * \return a pointer to the buffer
+     */
+    [[nodiscard]] void* data() const noexcept { return m_data; }
+
+private:
+    std::size_t m_alignment;
+    void* m_data;
+};
+}  // namespace details
+
 /** Invokes the runtime function corresponding to `fn_name` with arguments
  * `args`.
  *
@@ -73,6 +108,23 @@ InvokeResult<Output, Args...> invoke_fn(Runtime& runtime, std::string_view fn_na
             return make_error(runtime, fn_name, args...);
         }
 
+        if constexpr (std::is_same_v<Output, StructRef>) {
+            // A value struct is written to a buffer provided by the caller, which is
+            // passed as the first argument.
+            const auto return_type = fn_info->return_type();
+            if (auto struct_type = StructType::try_cast(return_type);
+                struct_type && struct_type->memory_kind() == MUN_STRUCT_MEMORY_KIND_VALUE) {
+                auto fn = reinterpret_cast<void(MUN_CALLTYPE*)(
+                    void*, typename Marshal<Args>::type...)>(
+                    const_cast<void*>(fn_info->function_pointer()));
+
+                details::ReturnSlot return_slot(return_type);
+                fn(return_slot.data(), Marshal<Args>::to(args)...);
+                return InvokeResult<Output, Args...>(Marshal<StructRef>::copy_from(
+                    static_cast<const MunGcPtr*>(return_slot.data()), runtime, return_type));
+            }
+        }
+
         auto fn = reinterpret_cast<typename Marshal<Output>::type(MUN_CALLTYPE*)(
             typename Marshal<Args>::type...)>(const_cast<void*>(fn_info->function_pointer()));
         if constexpr (std::is_same_v<Output, void>) {
@@ -3,6 +3,7 @@
 
 #include <cassert>
 #include <optional>
+#include <string>
 #include <string_view>
 
 #include "mun/error.h"
@@ -151,6 +152,38 @@ public:
         return updated;
     }
 
+    /** Retrieves the number of times the runtime successfully reloaded its assemblies.
+     *
+     * \return the generation of the runtime
+     */
+    uint64_t generation() const noexcept {
+        uint64_t generation;
+        MUN_ASSERT(mun_runtime_generation(m_handle, &generation));
+        return generation;
+    }
+
+    /** Retrieves the version of the assembly corresponding to the library at `library_path`.
+     *
+     * \param library_path the path to a loaded Mun library
+     * \param out_error a pointer that will optionally return an error
+     * \return possibly, the version of the assembly
+     */
+    std::optional<MunAssemblyVersion> assembly_version(const std::string& library_path,
+                                                       Error* out_error = nullptr) const noexcept {
+        bool has_assembly;
+        MunAssemblyVersion version;
+        if (auto error = Error(mun_runtime_get_assembly_version(m_handle, library_path.c_str(),
+                                                                &has_assembly, &version));
+            error.is_error()) {
+            if (out_error) {
+                *out_error = std::move(error);
+            }
+            return std::nullopt;
+        }
+
+        return has_assembly ? std::make_optional(version) : std::nullopt;
+    }
+
 private:
     MunRuntime m_handle;
 };
@@ -244,6 +244,21 @@ typedef union MunTypeId {
     };
 } MunTypeId;
 
+/**
+ * Identifies the version of an assembly that is loaded by a runtime.
+ */
+typedef struct MunAssemblyVersion {
+    /**
+     * The time at which the assembly was loaded, in milliseconds since the
+     * UNIX epoch
+     */
+    uint64_t loaded_at_ms;
+    /**
+     * The MD5 hash of the contents of the assembly's library
+     */
+    uint8_t content_hash[16];
+} MunAssemblyVersion;
+
 /**
  * An array of [`Type`]s.
  *
@@ -542,6 +557,41 @@ struct MunErrorHandle mun_runtime_get_type_info_by_id(struct MunRuntime runtime,
  */
 struct MunErrorHandle mun_runtime_update(struct MunRuntime runtime, bool *updated);
 
+/**
+ * Retrieves the number of times the runtime successfully reloaded its
+ * assemblies. If successful, `generation` is set, otherwise a non-zero error
+ * handle is returned.
+ *
+ * If a non-zero error handle is returned, it must be manually destructed using
+ * [`mun_error_destroy`].
+ *
+ * # Safety
+ *
+ * This function receives raw pointers as parameters. If any of the arguments
+ * is a null pointer, an error will be returned. Passing pointers to invalid
+ * data, will lead to undefined behavior.
+ */
+struct MunErrorHandle mun_runtime_generation(struct MunRuntime runtime, uint64_t *generation);
+
+/**
+ * Retrieves the version of the assembly corresponding to the library at
+ * `library_path`. If successful, `has_assembly` and `version` are set,
+ * otherwise a non-zero error handle is returned.
+ *
+ * If a non-zero error handle is returned, it must be manually destructed using
+ * [`mun_error_destroy`].
+ *
+ * # Safety
+ *
+ * This function receives raw pointers as parameters. If any of the arguments
+ * is a null pointer, an error will be returned. Passing pointers to invalid
+ * data, will lead to undefined behavior.
+ */
+struct MunErrorHandle mun_runtime_get_assembly_version(struct MunRuntime runtime,
+                                                       const char *library_path,
+                                                       bool *has_assembly,
+                                                       struct MunAssemblyVersion *version);
+
 /**
  * Notifies the runtime an additional references exists to the function. This
  * ensures that the data is kept alive even if [`mun_function_release`] is
@@ -228,6 +228,38 @@ TEST_CASE("struct can get, set, and replace struct", "[marshal]") {
     }
 }
 
+TEST_CASE("value struct can be passed and returned", "[marshal]") {
+    mun::Error err;
+    if (auto runtime = mun::make_runtime(get_munlib_path("mun-marshal/target/mod.munlib"), {}, &err)) {
+        REQUIRE(err.is_ok());
+
+        float a = -3.14f, b = 6.28f;
+        auto value_struct = mun::invoke_fn<mun::StructRef>(*runtime, "new_value_struct", a, b).unwrap();
+
+        // The value struct is returned through a return slot
+        auto swapped_res =
+            mun::invoke_fn<mun::StructRef>(*runtime, "swap_value_struct", value_struct);
+        REQUIRE(swapped_res.is_ok());
+        auto swapped = swapped_res.unwrap();
+
+        const auto swapped_0 = swapped.get<float>("0");
+        REQUIRE(swapped_0.has_value());
+        REQUIRE(*swapped_0 == b);
+
+        const auto swapped_1 = swapped.get<float>("1");
+        REQUIRE(swapped_1.has_value());
+        REQUIRE(*swapped_1 == a);
+
+        // The argument is passed as a copy
+        const auto value_0 = value_struct.get<float>("0");
+        REQUIRE(value_0.has_value());
+        REQUIRE(*value_0 == a);
+    } else {
+        REQUIRE(err.is_error());
+        FAIL(err.message().value());
+    }
+}
+
 TEST_CASE("can fetch array type", "[marshal]") {
     mun::Error err;
     if (auto runtime = mun::make_runtime(get_munlib_path("marshal/target/mod.munlib"), {}, &err)) {
@@ -58,6 +58,23 @@ TEST_CASE("runtime can update", "[runtime]") {
     }
 }
 
+TEST_CASE("runtime exposes assembly versions", "[runtime]") {
+    mun::Error err;
+    const auto library_path = get_munlib_path("fibonacci/mun/target/mod.munlib");
+    if (auto runtime = mun::make_runtime(library_path, {}, &err)) {
+        REQUIRE(err.is_ok());
+        REQUIRE(runtime->generation() == 0);
+
+        auto version = runtime->assembly_version(library_path, &err);
+        REQUIRE(err.is_ok());
+        REQUIRE(version.has_value());
+        REQUIRE(version->loaded_at_ms > 0);
+    } else {
+        REQUIRE(err.is_error());
+        FAIL(err.message().value());
+    }
+}
+
 TEST_CASE("runtime can garbage collect", "[runtime]") {
     mun::Error err;
     if (auto runtime = mun::make_runtime(get_munlib_path("mun-marshal/target/mod.munlib"), {}, &err)) {
@@ -3,7 +3,9 @@ mod ops;
 use std::ffi::OsString;
 
 use clap::{Parser, Subcommand};
-use ops::{build, init, language_server, new, start};
+use ops::{
+    build, check, diff, explain, fmt, init, inspect, language_server, new, start, test, verify,
+};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -21,6 +23,15 @@ enum Command {
     /// Compiles a local Mun file into a module
     Build(build::Args),
 
+    /// Reports the errors and warnings of a Mun project without generating code
+    Check(check::Args),
+
+    /// Print the extended explanation of an error code, e.g. `E0008`
+    Explain(explain::Args),
+
+    /// Formats the source files of a Mun project
+    Fmt(fmt::Args),
+
     /// Create a new Mun project at the specified location
     New(new::Args),
 
@@ -29,6 +40,18 @@ enum Command {
 
     /// Invoke a function from a munlib
     Start(start::Args),
+
+    /// Build a Mun project and run its functions that are marked with `#[test]`
+    Test(test::Args),
+
+    /// Verify that a munlib and its dependencies can be loaded by the runtime
+    Verify(verify::Args),
+
+    /// Print the functions, types, and dependencies of a munlib
+    Inspect(inspect::Args),
+
+    /// Print the differences in API and memory layout between two munlibs
+    Diff(diff::Args),
 }
 
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
@@ -55,9 +78,16 @@ where
     let args = Args::parse_from(args);
     match args.command {
         Command::Build(args) => build::build(args),
+        Command::Check(args) => check::check(args),
+        Command::Explain(args) => explain::explain(args),
+        Command::Fmt(args) => fmt::fmt(args),
         Command::LanguageServer(args) => language_server::language_server(args),
         Command::New(args) => new::new(args),
         Command::Init(args) => init::init(args),
         Command::Start(args) => start::start(args),
+        Command::Test(args) => test::test(args),
+        Command::Verify(args) => verify::verify(args),
+        Command::Inspect(args) => inspect::inspect(args),
+        Command::Diff(args) => diff::diff(args),
     }
 }
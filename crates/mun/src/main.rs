@@ -3,6 +3,8 @@ use mun::{run_with_args, ExitStatus};
 /// Main entry point for the `mun` executable.
 fn main() -> Result<(), anyhow::Error> {
     pretty_env_logger::try_init()?;
+    mun_crash_report::install("mun", env!("CARGO_PKG_VERSION"));
+    mun_crash_report::set_context("command", std::env::args().collect::<Vec<_>>().join(" "));
     let status = run_with_args(std::env::args_os()).unwrap();
     match status {
         ExitStatus::Success => {}
@@ -1,5 +1,12 @@
 pub mod build;
+pub mod check;
+pub mod diff;
+pub mod explain;
+pub mod fmt;
 pub mod init;
+pub mod inspect;
 pub mod language_server;
 pub mod new;
 pub mod start;
+pub mod test;
+pub mod verify;
@@ -1,10 +1,12 @@
 use std::{
     env,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use anyhow::anyhow;
-use mun_compiler::{Config, DisplayColor, Target};
+use mun_compiler::{Backend, Config, DisplayColor, LruCapacities, Target};
+use mun_compiler_daemon::{BuildTrigger, WatchConfig};
 use mun_project::MANIFEST_FILENAME;
 
 use crate::ExitStatus;
@@ -16,32 +18,143 @@ pub enum UseColor {
     Auto,
 }
 
+/// The format in which diagnostics are emitted
+#[derive(Copy, Clone, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// Human-readable source snippets, written to stderr
+    #[default]
+    Human,
+    /// A JSON object per diagnostic on a single line, written to stdout
+    Json,
+}
+
+/// Additional outputs that can be written to the output directory
+#[derive(Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum Emit {
+    /// A `GraphViz` file that describes how modules are grouped into assemblies
+    /// and which calls go through a dispatch table
+    #[value(name = "modules.dot")]
+    ModulesDot,
+}
+
+/// The backend that generates machine code
+#[derive(Copy, Clone, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BackendArg {
+    /// Optimized machine code generated by LLVM
+    #[default]
+    Llvm,
+    /// Unoptimized machine code that compiles faster, for hot reloading
+    /// during development
+    Cranelift,
+}
+
 #[derive(clap::Args)]
 pub struct Args {
     /// Path to the manifest of the project
     #[clap(long)]
     manifest_path: Option<PathBuf>,
 
+    /// The backend that generates machine code
+    #[clap(long, value_enum, default_value_t)]
+    backend: BackendArg,
+
     /// Optimization level [0,3]
     #[clap(long, short = 'O', default_value_t = 2)]
     opt_level: u8,
 
+    /// Panic when integer arithmetic overflows instead of wrapping around.
+    /// Defaults to `true` for `-O0` and to `false` otherwise.
+    #[clap(long, value_name = "BOOL")]
+    overflow_checks: Option<bool>,
+
+    /// Generate trampolines that allow a host to invoke public functions with
+    /// dynamic values
+    #[clap(long)]
+    dynamic_invocation: bool,
+
     /// Use color in output
     #[clap(long, value_enum)]
     color: Option<UseColor>,
 
+    /// The format in which diagnostics are emitted
+    #[clap(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+
     /// Emits IR instead of a *.munlib
     #[clap(long)]
     emit_ir: bool,
 
+    /// Comma separated list of additional outputs to write to the output
+    /// directory
+    #[clap(long, value_enum, value_delimiter = ',')]
+    emit: Vec<Emit>,
+
+    /// Write a report of how long the phases of the compilation took to
+    /// `timings.html` and `timings.json` in the output directory
+    #[clap(long, conflicts_with = "watch")]
+    timings: bool,
+
+    /// Print how many results of the queries that take up the most memory are
+    /// stored in the compiler database after the build
+    #[clap(long, conflicts_with = "watch")]
+    memory_report: bool,
+
+    /// The maximum number of results to keep in memory for the queries that
+    /// take up the most memory, e.g. `parse=128,infer=512`. The capacity of
+    /// `parse`, `item_tree`, `body`, and `infer` can be set; by default all
+    /// results are kept.
+    #[clap(long, value_name = "CAPACITIES", default_value = "")]
+    lru_capacities: LruCapacities,
+
+    /// Only read source files inside the source directories of the package
+    /// and its workspace members, don't reuse previous outputs, and write
+    /// the hashes of all inputs to `inputs.json` in the output directory
+    #[clap(long, conflicts_with = "watch")]
+    hermetic: bool,
+
+    /// A directory in which assemblies are cached by a hash of all of their
+    /// inputs, which can be shared between builds, e.g. on CI. Defaults to
+    /// the `MUN_ARTIFACT_CACHE` environment variable.
+    #[clap(long, value_name = "DIR", conflicts_with = "hermetic")]
+    artifact_cache: Option<PathBuf>,
+
     /// Run the compiler in watch mode. Watch input files and trigger
     /// recompilation on changes.
     #[clap(long)]
     watch: bool,
 
+    /// In watch mode, the number of milliseconds to wait for more changes
+    /// before rebuilding
+    #[clap(long, value_name = "MILLISECONDS", requires = "watch")]
+    debounce: Option<u64>,
+
+    /// In watch mode, only rebuild when the specified marker file is created
+    /// or written to
+    #[clap(long, value_name = "MARKER", requires = "watch")]
+    on_save: Option<PathBuf>,
+
+    /// In watch mode, poll the filesystem with the specified interval in
+    /// milliseconds instead of relying on filesystem notifications
+    #[clap(
+        long,
+        value_name = "MILLISECONDS",
+        num_args = 0..=1,
+        default_missing_value = "500",
+        requires = "watch"
+    )]
+    poll: Option<u64>,
+
     /// Target for machine code
     #[clap(long, value_parser=parse_target_triple)]
     target: Option<Target>,
+
+    /// Space or comma separated list of features to enable
+    #[clap(long, short = 'F', value_delimiter = ',', num_args = 1..)]
+    features: Vec<String>,
+
+    /// Do not enable the `default` feature of the package
+    #[clap(long)]
+    no_default_features: bool,
 }
 
 fn parse_target_triple(target_triple: &str) -> Result<Target, String> {
@@ -63,8 +176,82 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
         _ => return Err(anyhow!("Only optimization levels 0-3 are supported")),
     };
 
-    let display_colors = args
-        .color
+    let message_format = message_format(args.message_format, args.color);
+
+    let manifest_path = locate_manifest(args.manifest_path.as_deref())?;
+
+    log::info!("located build manifest at: {}", manifest_path.display());
+
+    let compiler_options = Config {
+        target: args
+            .target
+            .unwrap_or_else(|| Target::host_target().expect("unable to determine host target")),
+        backend: match args.backend {
+            BackendArg::Llvm => Backend::Llvm,
+            BackendArg::Cranelift => Backend::Cranelift,
+        },
+        optimization_lvl,
+        overflow_checks: args.overflow_checks.unwrap_or(args.opt_level == 0),
+        debug_info: false,
+        dynamic_invocation: args.dynamic_invocation,
+        out_dir: None,
+        emit_ir: args.emit_ir,
+        emit_module_graph: args.emit.contains(&Emit::ModulesDot),
+        timings: args.timings,
+        memory_report: args.memory_report,
+        lru_capacities: args.lru_capacities,
+        features: args
+            .features
+            .iter()
+            .flat_map(|features| features.split_whitespace())
+            .map(ToOwned::to_owned)
+            .collect(),
+        default_features: !args.no_default_features,
+        hermetic: args.hermetic,
+        artifact_cache: args
+            .artifact_cache
+            .or_else(|| env::var_os("MUN_ARTIFACT_CACHE").map(PathBuf::from)),
+    };
+
+    if args.watch {
+        let default_watch_config = WatchConfig::default();
+        let watch_config = WatchConfig {
+            debounce: args
+                .debounce
+                .map_or(default_watch_config.debounce, Duration::from_millis),
+            trigger: args
+                .on_save
+                .map_or(BuildTrigger::OnChange, BuildTrigger::OnSaveMarker),
+            poll_interval: args.poll.map(Duration::from_millis),
+        };
+        mun_compiler_daemon::compile_and_watch_manifest(
+            &manifest_path,
+            compiler_options,
+            watch_config,
+            message_format,
+        )
+    } else {
+        mun_compiler::compile_manifest(&manifest_path, compiler_options, message_format)
+    }
+    .map(Into::into)
+}
+
+/// Returns the format in which diagnostics are emitted, based on the
+/// `--message-format` and `--color` arguments.
+pub(crate) fn message_format(
+    format: MessageFormat,
+    color: Option<UseColor>,
+) -> mun_compiler::MessageFormat {
+    match format {
+        MessageFormat::Human => mun_compiler::MessageFormat::Human(display_colors(color)),
+        MessageFormat::Json => mun_compiler::MessageFormat::Json,
+    }
+}
+
+/// Returns whether diagnostics are displayed with colors, based on the `--color`
+/// argument or the `MUN_TERMINAL_COLOR` environment variable.
+pub(crate) fn display_colors(color: Option<UseColor>) -> DisplayColor {
+    color
         .map(|clr| match clr {
             UseColor::Disable => DisplayColor::Disable,
             UseColor::Enable => DisplayColor::Enable,
@@ -79,10 +266,13 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
                 })
                 .ok()
         })
-        .unwrap_or(DisplayColor::Auto);
+        .unwrap_or(DisplayColor::Auto)
+}
 
-    // Locate the manifest
-    let manifest_path = match &args.manifest_path {
+/// Returns the path of the manifest at `manifest_path`, or of the manifest in the
+/// current directory or one of its parents if no path is specified.
+pub(crate) fn locate_manifest(manifest_path: Option<&Path>) -> anyhow::Result<PathBuf> {
+    match manifest_path {
         None => {
             let current_dir =
                 std::env::current_dir().expect("could not determine current working directory");
@@ -92,37 +282,15 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
                     MANIFEST_FILENAME,
                     current_dir.display()
                 )
-            })?
+            })
         }
-        Some(path) => std::fs::canonicalize(Path::new(&path)).map_err(|_error| {
+        Some(path) => std::fs::canonicalize(path).map_err(|_error| {
             anyhow::anyhow!(
                 "'{}' does not refer to a valid manifest path",
                 path.display()
             )
-        })?,
-    };
-
-    log::info!("located build manifest at: {}", manifest_path.display());
-
-    let compiler_options = Config {
-        target: args
-            .target
-            .unwrap_or_else(|| Target::host_target().expect("unable to determine host target")),
-        optimization_lvl,
-        out_dir: None,
-        emit_ir: args.emit_ir,
-    };
-
-    if args.watch {
-        mun_compiler_daemon::compile_and_watch_manifest(
-            &manifest_path,
-            compiler_options,
-            display_colors,
-        )
-    } else {
-        mun_compiler::compile_manifest(&manifest_path, compiler_options, display_colors)
+        }),
     }
-    .map(Into::into)
 }
 
 /// Find a Mun manifest file in the specified directory or one of its parents.
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use mun_compiler::Config;
+
+use super::build::{locate_manifest, message_format, MessageFormat, UseColor};
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the manifest of the project
+    #[clap(long)]
+    manifest_path: Option<PathBuf>,
+
+    /// Use color in output
+    #[clap(long, value_enum)]
+    color: Option<UseColor>,
+
+    /// The format in which diagnostics are emitted
+    #[clap(long, value_enum, default_value_t)]
+    message_format: MessageFormat,
+
+    /// Space or comma separated list of features to enable
+    #[clap(long, short = 'F', value_delimiter = ',', num_args = 1..)]
+    features: Vec<String>,
+
+    /// Do not enable the `default` feature of the package
+    #[clap(long)]
+    no_default_features: bool,
+}
+
+/// This method is invoked when the executable is run with the `check` argument
+/// indicating that a user requested us to report the errors and warnings of a
+/// project, without generating any code.
+pub fn check(args: Args) -> anyhow::Result<ExitStatus> {
+    log::trace!("starting check");
+
+    let manifest_path = locate_manifest(args.manifest_path.as_deref())?;
+
+    log::info!("located manifest at: {}", manifest_path.display());
+
+    let config = Config {
+        features: args
+            .features
+            .iter()
+            .flat_map(|features| features.split_whitespace())
+            .map(ToOwned::to_owned)
+            .collect(),
+        default_features: !args.no_default_features,
+        ..Config::default()
+    };
+
+    mun_compiler::check_manifest(
+        &manifest_path,
+        config,
+        message_format(args.message_format, args.color),
+    )
+    .map(Into::into)
+}
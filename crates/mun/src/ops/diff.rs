@@ -0,0 +1,237 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use mun_memory::{
+    diff::{compute_struct_diff, FieldDiff, FieldEditKind, StructDiff},
+    Type,
+};
+
+use super::inspect::{function_signature, type_name, LibraryInfo};
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// The original library
+    old: PathBuf,
+
+    /// The modified library
+    new: PathBuf,
+}
+
+/// Prints the differences between the API and memory layout of two munlibs.
+/// This shows how the runtime will map the old state onto the new state when
+/// the old library is hot reloaded with the new library.
+pub fn diff(args: Args) -> anyhow::Result<ExitStatus> {
+    let old = LibraryInfo::load(&args.old)?;
+    let new = LibraryInfo::load(&args.new)?;
+
+    let mut stdout = io::stdout().lock();
+    let has_changes = write_diff(&mut stdout, &old, &new)?;
+    if !has_changes {
+        writeln!(stdout, "No changes")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Writes the differences between two libraries. Returns whether any
+/// differences were found.
+fn write_diff(out: &mut impl Write, old: &LibraryInfo, new: &LibraryInfo) -> io::Result<bool> {
+    let (old_type_table, old_types) = old.types();
+    let (new_type_table, new_types) = new.types();
+
+    let old_functions = old
+        .info
+        .symbols
+        .functions()
+        .iter()
+        .map(|f| {
+            (
+                f.prototype.name(),
+                function_signature(&old_type_table, &f.prototype),
+            )
+        })
+        .collect();
+    let new_functions = new
+        .info
+        .symbols
+        .functions()
+        .iter()
+        .map(|f| {
+            (
+                f.prototype.name(),
+                function_signature(&new_type_table, &f.prototype),
+            )
+        })
+        .collect();
+
+    let old_globals = old
+        .info
+        .symbols
+        .globals()
+        .iter()
+        .map(|g| (g.name(), type_name(&old_type_table, &g.type_id)))
+        .collect();
+    let new_globals = new
+        .info
+        .symbols
+        .globals()
+        .iter()
+        .map(|g| (g.name(), type_name(&new_type_table, &g.type_id)))
+        .collect();
+
+    let mut has_changes = write_symbol_diff(out, "Functions", &old_functions, &new_functions)?;
+    has_changes |= write_type_diff(out, &old_types, &new_types)?;
+    has_changes |= write_symbol_diff(out, "Globals", &old_globals, &new_globals)?;
+
+    Ok(has_changes)
+}
+
+/// Writes the added, removed, and changed symbols in a section with the
+/// specified title. Symbols are identified by their name and described by
+/// their signature.
+fn write_symbol_diff(
+    out: &mut impl Write,
+    title: &str,
+    old: &BTreeMap<&str, String>,
+    new: &BTreeMap<&str, String>,
+) -> io::Result<bool> {
+    let mut lines = Vec::new();
+    for (name, old_signature) in old {
+        match new.get(name) {
+            None => lines.push(format!("  - {old_signature}")),
+            Some(new_signature) if new_signature != old_signature => {
+                lines.push(format!("  ~ {new_signature}"));
+                lines.push(format!("      was: {old_signature}"));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, new_signature) in new {
+        if !old.contains_key(name) {
+            lines.push(format!("  + {new_signature}"));
+        }
+    }
+
+    write_section(out, title, &lines)
+}
+
+/// Writes the differences between the struct types of two libraries, as
+/// computed by the runtime when hot reloading.
+fn write_type_diff(out: &mut impl Write, old: &[Type], new: &[Type]) -> io::Result<bool> {
+    let mut lines = Vec::new();
+    for diff in compute_struct_diff(old, new) {
+        match diff {
+            StructDiff::Insert { ty, .. } => lines.push(format!("  + struct {}", ty.name())),
+            StructDiff::Delete { ty, .. } => lines.push(format!("  - struct {}", ty.name())),
+            StructDiff::Edit {
+                diff,
+                old_ty,
+                new_ty,
+                ..
+            } => {
+                if old_ty.name() == new_ty.name() {
+                    lines.push(format!("  ~ struct {}", new_ty.name()));
+                } else {
+                    lines.push(format!(
+                        "  ~ struct {} (renamed from {})",
+                        new_ty.name(),
+                        old_ty.name()
+                    ));
+                }
+                lines.extend(
+                    diff.iter()
+                        .map(|diff| field_diff_line(diff, &old_ty, &new_ty)),
+                );
+            }
+            StructDiff::Move { old_ty, new_ty, .. } => {
+                if old_ty.name() != new_ty.name() {
+                    lines.push(format!(
+                        "  ~ struct {} (renamed from {})",
+                        new_ty.name(),
+                        old_ty.name()
+                    ));
+                }
+            }
+        }
+    }
+
+    write_section(out, "Types", &lines)
+}
+
+/// Describes a change to a field of a struct.
+fn field_diff_line(diff: &FieldDiff, old_ty: &Type, new_ty: &Type) -> String {
+    let field_name = |ty: &Type, index: usize| {
+        ty.as_struct()
+            .and_then(|s| s.fields().get(index))
+            .map_or_else(String::new, |field| field.name().to_owned())
+    };
+
+    match diff {
+        FieldDiff::Insert { index, new_type } => {
+            format!(
+                "      + {}: {}",
+                field_name(new_ty, *index),
+                new_type.name()
+            )
+        }
+        FieldDiff::Delete { index } => format!("      - {}", field_name(old_ty, *index)),
+        FieldDiff::Edit {
+            old_type,
+            new_type,
+            old_index,
+            new_index,
+            kind,
+        } => {
+            let old_index = old_index.unwrap_or(*new_index);
+            let new_name = field_name(new_ty, *new_index);
+            let mut line = match kind {
+                FieldEditKind::ChangedTyped => {
+                    format!(
+                        "      ~ {new_name}: {} -> {}",
+                        old_type.name(),
+                        new_type.name()
+                    )
+                }
+                FieldEditKind::RenamedField => format!(
+                    "      ~ {new_name}: {} (renamed from {})",
+                    new_type.name(),
+                    field_name(old_ty, old_index)
+                ),
+            };
+            if old_index != *new_index {
+                line.push_str(&format!(
+                    " (moved from position {old_index} to {new_index})"
+                ));
+            }
+            line
+        }
+        FieldDiff::Move {
+            ty,
+            old_index,
+            new_index,
+        } => format!(
+            "      ~ {}: {} (moved from position {old_index} to {new_index})",
+            field_name(new_ty, *new_index),
+            ty.name()
+        ),
+    }
+}
+
+/// Writes a section with the specified title if it contains any lines. Returns
+/// whether the section was written.
+fn write_section(out: &mut impl Write, title: &str, lines: &[String]) -> io::Result<bool> {
+    if lines.is_empty() {
+        return Ok(false);
+    }
+
+    writeln!(out, "{title}:")?;
+    for line in lines {
+        writeln!(out, "{line}")?;
+    }
+    writeln!(out)?;
+    Ok(true)
+}
@@ -0,0 +1,18 @@
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// The error code to explain, e.g. `E0008`
+    code: String,
+}
+
+/// This method is invoked when the executable is run with the `explain`
+/// argument indicating that a user requested the extended explanation of an
+/// error code.
+pub fn explain(args: Args) -> anyhow::Result<ExitStatus> {
+    let explanation = mun_diagnostics::explain(&args.code)
+        .ok_or_else(|| anyhow::anyhow!("'{}' is not a valid error code", args.code))?;
+
+    print!("{explanation}");
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use mun_project::Package;
+
+use super::build::locate_manifest;
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// The files to format. If no files are specified, all source files of the
+    /// project are formatted.
+    files: Vec<PathBuf>,
+
+    /// Path to the manifest of the project
+    #[clap(long, conflicts_with = "files")]
+    manifest_path: Option<PathBuf>,
+
+    /// Do not write the formatted files, instead exit with an error if any of
+    /// the files is not formatted. Useful to check the formatting in CI.
+    #[clap(long)]
+    check: bool,
+}
+
+/// This method is invoked when the executable is run with the `fmt` argument
+/// indicating that a user requested us to format the source files of a project
+/// or the specified files.
+pub fn fmt(args: Args) -> anyhow::Result<ExitStatus> {
+    let files = if args.files.is_empty() {
+        let manifest_path = locate_manifest(args.manifest_path.as_deref())?;
+        let package = Package::from_file(&manifest_path)?;
+        mun_compiler::iter_source_files(&package.source_directory()).collect()
+    } else {
+        args.files
+    };
+
+    let mut success = true;
+    for path in &files {
+        match format_file(path, args.check) {
+            Ok(false) if args.check => {
+                println!("{} is not formatted", path.display());
+                success = false;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("error: {e}");
+                success = false;
+            }
+        }
+    }
+
+    Ok(success.into())
+}
+
+/// Formats the file at `path`. If `check` is true, the file is not written.
+/// Returns whether the file was already formatted.
+fn format_file(path: &Path, check: bool) -> anyhow::Result<bool> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("could not read '{}': {}", path.display(), e))?;
+
+    let formatted = mun_fmt::format(&text).map_err(|errors| {
+        let errors = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::anyhow!("could not format '{}': {}", path.display(), errors)
+    })?;
+    if formatted == text {
+        return Ok(true);
+    }
+
+    if !check {
+        std::fs::write(path, formatted)
+            .map_err(|e| anyhow::anyhow!("could not write '{}': {}", path.display(), e))?;
+    }
+    Ok(false)
+}
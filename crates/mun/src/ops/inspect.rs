@@ -0,0 +1,171 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use mun_abi as abi;
+use mun_libloader::MunLibrary;
+use mun_memory::{type_table::TypeTable, Type};
+
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// The library to inspect
+    library: PathBuf,
+
+    /// Print the metadata as JSON instead of in a human-readable form
+    #[clap(long)]
+    json: bool,
+}
+
+/// The ABI of a munlib that was loaded without executing any Mun code.
+pub(crate) struct LibraryInfo {
+    pub info: abi::AssemblyInfo<'static>,
+
+    /// Keeps the memory that `info` refers to alive
+    _library: MunLibrary,
+}
+
+impl LibraryInfo {
+    /// Loads the ABI of the munlib at the specified path.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        // Safety: loading a munlib does not execute any Mun code, and the
+        // functions that retrieve the ABI only return static data.
+        let library = unsafe { MunLibrary::new(path) }
+            .map_err(|e| anyhow::anyhow!("could not load '{}': {}", path.display(), e))?;
+
+        let abi_version = unsafe { library.get_abi_version() };
+        if abi_version != abi::ABI_VERSION {
+            anyhow::bail!(
+                "ABI version mismatch. '{}' is `{abi_version}` but runtime is `{}`",
+                path.display(),
+                abi::ABI_VERSION
+            );
+        }
+
+        Ok(Self {
+            info: unsafe { library.get_info() },
+            _library: library,
+        })
+    }
+
+    /// Loads the types that are defined by the library. If the library
+    /// references types of its dependencies, only the primitive types are
+    /// returned.
+    pub fn types(&self) -> (TypeTable, Vec<Type>) {
+        Type::try_from_abi(self.info.symbols.types(), TypeTable::default())
+            .unwrap_or_else(|_| (TypeTable::default(), Vec::new()))
+    }
+}
+
+/// Returns the name of the type with the specified id, or the id itself if the
+/// type is unknown.
+pub(crate) fn type_name(type_table: &TypeTable, type_id: &abi::TypeId<'_>) -> String {
+    type_table
+        .find_type_info_by_id(type_id)
+        .map_or_else(|| type_id.to_string(), |ty| ty.name().to_owned())
+}
+
+/// Returns the signature of a function in Mun syntax.
+pub(crate) fn function_signature(
+    type_table: &TypeTable,
+    prototype: &abi::FunctionPrototype<'_>,
+) -> String {
+    let signature = &prototype.signature;
+    let mut result = format!(
+        "fn {}({})",
+        prototype.name(),
+        signature
+            .arg_types()
+            .iter()
+            .map(|ty| type_name(type_table, ty))
+            .join(", ")
+    );
+    if let Some(return_type) = signature.return_type() {
+        result.push_str(" -> ");
+        result.push_str(&type_name(type_table, &return_type));
+    }
+    result
+}
+
+/// Prints the metadata of a munlib: its ABI version, dependencies, and the
+/// functions, types, and globals that it exports. No Mun code is executed.
+pub fn inspect(args: Args) -> anyhow::Result<ExitStatus> {
+    let library = LibraryInfo::load(&args.library)?;
+
+    let mut stdout = io::stdout().lock();
+    if args.json {
+        let metadata = serde_json::json!({
+            "abi_version": abi::ABI_VERSION,
+            "assembly": library.info,
+        });
+        serde_json::to_writer_pretty(&mut stdout, &metadata)?;
+        writeln!(stdout)?;
+    } else {
+        write_metadata(&mut stdout, &library)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Writes the metadata of a library in a human-readable form.
+fn write_metadata(out: &mut impl Write, library: &LibraryInfo) -> io::Result<()> {
+    let info = &library.info;
+    let (type_table, _) = library.types();
+
+    writeln!(out, "ABI version: {}", abi::ABI_VERSION)?;
+
+    writeln!(out, "\nDependencies:")?;
+    for dependency in info.dependencies() {
+        writeln!(out, "  {dependency}")?;
+    }
+
+    writeln!(out, "\nFunctions:")?;
+    for function in info.symbols.functions() {
+        let visibility = match function.privacy {
+            abi::Privacy::Public => "pub ",
+            abi::Privacy::Private => "",
+        };
+        writeln!(
+            out,
+            "  {visibility}{}",
+            function_signature(&type_table, &function.prototype)
+        )?;
+    }
+
+    writeln!(out, "\nTypes:")?;
+    for ty in info.symbols.types() {
+        let abi::TypeDefinitionData::Struct(s) = &ty.data;
+        writeln!(
+            out,
+            "  struct {} ({:?}, size: {}, alignment: {})",
+            ty.name(),
+            s.memory_kind,
+            ty.size_in_bytes(),
+            ty.alignment()
+        )?;
+        for ((name, field_type), offset) in
+            s.field_names().zip(s.field_types()).zip(s.field_offsets())
+        {
+            writeln!(
+                out,
+                "    {offset:>4}: {name}: {}",
+                type_name(&type_table, field_type)
+            )?;
+        }
+    }
+
+    writeln!(out, "\nGlobals:")?;
+    for global in info.symbols.globals() {
+        writeln!(
+            out,
+            "  {}: {}",
+            global.name(),
+            type_name(&type_table, &global.type_id)
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use mun_compiler::{Config, Target};
+use mun_runtime::Runtime;
+
+use super::build::{display_colors, locate_manifest, UseColor};
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Only run the tests whose name contains this string
+    filter: Option<String>,
+
+    /// Path to the manifest of the project
+    #[clap(long)]
+    manifest_path: Option<PathBuf>,
+
+    /// Use color in output
+    #[clap(long, value_enum)]
+    color: Option<UseColor>,
+
+    /// Space or comma separated list of features to enable
+    #[clap(long, short = 'F', value_delimiter = ',', num_args = 1..)]
+    features: Vec<String>,
+
+    /// Do not enable the `default` feature of the package
+    #[clap(long)]
+    no_default_features: bool,
+}
+
+/// A test that failed, together with the reason it failed.
+struct Failure {
+    name: String,
+    message: String,
+}
+
+/// This method is invoked when the executable is run with the `test` argument
+/// indicating that a user requested us to build a project and run all of its
+/// functions that are marked with `#[test]`.
+pub fn test(args: Args) -> anyhow::Result<ExitStatus> {
+    log::trace!("starting test");
+
+    let manifest_path = locate_manifest(args.manifest_path.as_deref())?;
+
+    log::info!("located manifest at: {}", manifest_path.display());
+
+    // Build into a separate directory, so a runtime that watches the regular output
+    // directory doesn't pick up the test build.
+    let out_dir = manifest_path
+        .parent()
+        .expect("manifest has no parent directory")
+        .join("target")
+        .join("test");
+    let config = Config {
+        target: Target::host_target().expect("unable to determine host target"),
+        optimization_lvl: mun_compiler::OptimizationLevel::None,
+        overflow_checks: true,
+        out_dir: Some(out_dir),
+        features: args
+            .features
+            .iter()
+            .flat_map(|features| features.split_whitespace())
+            .map(ToOwned::to_owned)
+            .collect(),
+        default_features: !args.no_default_features,
+        ..Config::default()
+    };
+
+    let Some(assembly_paths) = mun_compiler::compile_manifest_assemblies(
+        &manifest_path,
+        config,
+        display_colors(args.color),
+    )?
+    else {
+        return Ok(ExitStatus::Error);
+    };
+
+    let mut passed = 0;
+    let mut failures = Vec::new();
+    let mut filtered_out = 0;
+    for assembly_path in assembly_paths {
+        // Every assembly is loaded by its own runtime, so assemblies that no other
+        // assembly depends on are tested as well.
+        // Safety: the assembly was just built by the Mun compiler
+        let runtime = unsafe { Runtime::builder(&assembly_path).finish() }
+            .map_err(|e| anyhow::anyhow!("could not load '{}': {}", assembly_path.display(), e))?;
+        let assembly = runtime
+            .get_assembly(&assembly_path)
+            .expect("the runtime did not load its entry assembly");
+
+        for test in assembly.tests() {
+            if args
+                .filter
+                .as_deref()
+                .is_some_and(|filter| !test.name().contains(filter))
+            {
+                filtered_out += 1;
+                continue;
+            }
+
+            match test.run() {
+                Ok(()) => {
+                    println!("test {} ... ok", test.name());
+                    passed += 1;
+                }
+                Err(error) => {
+                    println!("test {} ... FAILED", test.name());
+                    failures.push(Failure {
+                        name: test.name().to_owned(),
+                        message: error.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for failure in &failures {
+            println!("\n---- {} ----\n{}", failure.name, failure.message);
+        }
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} filtered out",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failures.len(),
+        filtered_out
+    );
+
+    Ok(failures.is_empty().into())
+}
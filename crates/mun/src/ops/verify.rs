@@ -0,0 +1,261 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+use mun_abi as abi;
+use mun_libloader::MunLibrary;
+use mun_memory::{type_table::TypeTable, Type};
+
+use crate::ExitStatus;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// The library to verify
+    library: PathBuf,
+}
+
+/// A library of which the ABI was loaded.
+struct LoadedLibrary {
+    path: PathBuf,
+    info: abi::AssemblyInfo<'static>,
+
+    /// Keeps the memory that `info` refers to alive
+    _library: MunLibrary,
+}
+
+/// Collects the problems found while verifying libraries.
+#[derive(Default)]
+struct Report {
+    problems: Vec<String>,
+    host_functions: Vec<String>,
+}
+
+impl Report {
+    fn error(&mut self, library: &Path, message: impl AsRef<str>) {
+        self.problems
+            .push(format!("{}: {}", library.display(), message.as_ref()));
+    }
+}
+
+/// Verifies that the specified library and all of its dependencies can be
+/// loaded by the runtime, without executing any Mun code. Prints a report of
+/// all problems that were found.
+pub fn verify(args: Args) -> anyhow::Result<ExitStatus> {
+    let library_path = args
+        .library
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("could not open '{}': {}", args.library.display(), e))?;
+
+    let mut report = Report::default();
+    let libraries = load_with_dependencies(library_path, &mut report);
+    for library in &libraries {
+        println!(
+            "Loaded {} ({} functions, {} types, {} globals)",
+            library.path.display(),
+            library.info.symbols.functions().len(),
+            library.info.symbols.types().len(),
+            library.info.symbols.globals().len()
+        );
+    }
+
+    verify_dependency_versions(&libraries, &mut report);
+    verify_types(&libraries, &mut report);
+    verify_functions(&libraries, &mut report);
+
+    for name in &report.host_functions {
+        println!("Requires host function `{name}`");
+    }
+
+    if report.problems.is_empty() {
+        println!("No problems found");
+        Ok(ExitStatus::Success)
+    } else {
+        for problem in &report.problems {
+            eprintln!("error: {problem}");
+        }
+        eprintln!("{} problem(s) found", report.problems.len());
+        Ok(ExitStatus::Error)
+    }
+}
+
+/// Loads the ABI of the library at `library_path` and of all its dependencies.
+fn load_with_dependencies(library_path: PathBuf, report: &mut Report) -> Vec<LoadedLibrary> {
+    let mut libraries = Vec::new();
+    let mut visited = HashSet::new();
+    let mut to_load = VecDeque::from([library_path]);
+    while let Some(library_path) = to_load.pop_front() {
+        if !visited.insert(library_path.clone()) {
+            continue;
+        }
+
+        // Safety: loading a munlib does not execute any Mun code, and the
+        // functions that retrieve the ABI only return static data.
+        let library = match unsafe { MunLibrary::new(&library_path) } {
+            Ok(library) => library,
+            Err(e) => {
+                report.error(&library_path, format!("could not load library: {e}"));
+                continue;
+            }
+        };
+
+        let abi_version = unsafe { library.get_abi_version() };
+        if abi_version != abi::ABI_VERSION {
+            report.error(
+                &library_path,
+                format!(
+                    "ABI version mismatch. munlib is `{abi_version}` but runtime is `{}`",
+                    abi::ABI_VERSION
+                ),
+            );
+            continue;
+        }
+
+        let info = unsafe { library.get_info() };
+
+        for dependency in info.dependencies() {
+            let dependency_path = mun_runtime::resolve_dependency_path(&library_path, dependency);
+            if dependency_path.is_file() {
+                to_load.push_back(dependency_path);
+            } else {
+                report.error(
+                    &library_path,
+                    format!(
+                        "missing dependency `{dependency}`, expected it at '{}'",
+                        dependency_path.display()
+                    ),
+                );
+            }
+        }
+
+        libraries.push(LoadedLibrary {
+            path: library_path,
+            info,
+            _library: library,
+        });
+    }
+    libraries
+}
+
+/// Verifies that the version of every dependency satisfies the requirement
+/// that the dependent library was compiled against.
+fn verify_dependency_versions(libraries: &[LoadedLibrary], report: &mut Report) {
+    let versions: HashMap<&Path, Option<&str>> = libraries
+        .iter()
+        .map(|library| (library.path.as_path(), library.info.version()))
+        .collect();
+
+    for library in libraries {
+        for (dependency, requirement) in library.info.dependency_requirements() {
+            let Some(requirement) = requirement else {
+                continue;
+            };
+
+            let dependency_path = mun_runtime::resolve_dependency_path(&library.path, dependency);
+            let Some(&version) = versions.get(dependency_path.as_path()) else {
+                continue;
+            };
+
+            if !mun_runtime::is_compatible_version(requirement, version) {
+                report.error(
+                    &library.path,
+                    format!(
+                        "dependency `{dependency}` has version `{}`, but `{requirement}` is required",
+                        version.unwrap_or("unknown")
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Verifies that all types referenced by the libraries are either primitives
+/// or defined by one of the libraries.
+fn verify_types(libraries: &[LoadedLibrary], report: &mut Report) {
+    let definitions = libraries
+        .iter()
+        .flat_map(|library| library.info.symbols.types());
+    let type_table = match Type::try_from_abi(definitions, TypeTable::default()) {
+        Ok((type_table, _)) => type_table,
+        Err(e) => {
+            let path = libraries
+                .first()
+                .map_or(Path::new(""), |l| l.path.as_path());
+            report.error(path, format!("invalid type definitions: {e}"));
+            return;
+        }
+    };
+
+    for library in libraries {
+        let symbols = &library.info.symbols;
+        let referenced_types = library
+            .info
+            .type_lut
+            .type_ids()
+            .iter()
+            .chain(symbols.globals().iter().map(|global| &global.type_id))
+            .chain(
+                symbols
+                    .functions()
+                    .iter()
+                    .map(|function| &function.prototype.signature)
+                    .chain(
+                        library
+                            .info
+                            .dispatch_table
+                            .prototypes()
+                            .iter()
+                            .map(|p| &p.signature),
+                    )
+                    .flat_map(|signature| {
+                        signature
+                            .arg_types()
+                            .iter()
+                            .chain(std::iter::once(&signature.return_type))
+                    }),
+            );
+
+        let mut reported = HashSet::new();
+        for type_id in referenced_types {
+            if type_table.find_type_info_by_id(type_id).is_none() && reported.insert(type_id) {
+                report.error(&library.path, format!("unknown type `{type_id}`"));
+            }
+        }
+    }
+}
+
+/// Verifies that every function that a library imports and that is defined by
+/// one of the libraries has the same signature. Functions that are not defined
+/// by any library must be provided by the host.
+fn verify_functions(libraries: &[LoadedLibrary], report: &mut Report) {
+    let definitions: HashMap<&str, &abi::FunctionPrototype<'_>> = libraries
+        .iter()
+        .flat_map(|library| library.info.symbols.functions())
+        .map(|function| (function.prototype.name(), &function.prototype))
+        .collect();
+
+    for library in libraries {
+        // Functions that are not yet linked, have to be provided by another library
+        let imports = library
+            .info
+            .dispatch_table
+            .iter()
+            .filter(|(ptr, _)| ptr.is_null())
+            .map(|(_, prototype)| prototype);
+
+        for prototype in imports {
+            match definitions.get(prototype.name()) {
+                // The host has to provide functions that are not defined by any library
+                None => report.host_functions.push(prototype.name().to_owned()),
+                Some(definition) if definition.signature != prototype.signature => report.error(
+                    &library.path,
+                    format!(
+                        "the signature of function `{}` does not match its definition",
+                        prototype.name()
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+}
@@ -53,6 +53,408 @@ fn mun_emit_ir() {
     assert!(ir_path.is_file());
 }
 
+/// Verifies that a newly created project can be used to emit a module graph.
+#[test]
+fn mun_emit_modules_dot() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &["--emit=modules.dot"]);
+
+    let graph = std::fs::read_to_string(project_path.join("target/modules.dot")).unwrap();
+    assert!(graph.starts_with("digraph modules {"));
+    assert!(graph.contains("label = \"mod\";"));
+}
+
+/// Verifies that a newly built project passes `mun verify`.
+#[test]
+fn mun_verify() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &[]);
+
+    let library_path = project_path.join("target/mod.munlib");
+    let args: Vec<OsString> = vec!["mun".into(), "verify".into(), library_path.into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    // A library that does not exist cannot be verified
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "verify".into(),
+        project_path.join("target/missing.munlib").into(),
+    ];
+    assert!(run_with_args(args).is_err());
+}
+
+/// Verifies that `mun verify` reports dependencies that are missing or that
+/// don't match the library that depends on them.
+#[test]
+fn mun_verify_dependencies() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let source_path = project_path.join("src");
+    std::fs::write(
+        source_path.join("mod.mun"),
+        "pub fn main() -> i32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(source_path.join("foo.mun"), "pub fn bar() -> i32 { 5 }").unwrap();
+    build(&project_path, &[]);
+
+    let library_path = project_path.join("target/mod.munlib");
+    let verify = || {
+        let args: Vec<OsString> = vec!["mun".into(), "verify".into(), library_path.clone().into()];
+        run_with_args(args).unwrap()
+    };
+    assert_eq!(verify(), mun::ExitStatus::Success);
+
+    // Keep the library that was compiled against the original dependency
+    let library = std::fs::read(&library_path).unwrap();
+
+    // The version of the dependency is not compatible with the requirement of the
+    // library
+    let manifest_path = project_path.join("mun.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+    let new_manifest = manifest.replace("version=\"0.1.0\"", "version=\"1.0.0\"");
+    assert_ne!(new_manifest, manifest);
+    std::fs::write(&manifest_path, new_manifest).unwrap();
+    build(&project_path, &[]);
+    std::fs::write(&library_path, &library).unwrap();
+    assert_eq!(verify(), mun::ExitStatus::Error);
+
+    // The signature of a function in the dependency changed. The library itself
+    // is changed as well to keep the package compiling.
+    std::fs::write(&manifest_path, manifest).unwrap();
+    std::fs::write(
+        source_path.join("mod.mun"),
+        "pub fn main() -> f32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(source_path.join("foo.mun"), "pub fn bar() -> f32 { 5.0 }").unwrap();
+    build(&project_path, &[]);
+    std::fs::write(&library_path, &library).unwrap();
+    assert_eq!(verify(), mun::ExitStatus::Error);
+
+    // The dependency is missing
+    std::fs::remove_file(project_path.join("target/foo.munlib")).unwrap();
+    assert_eq!(verify(), mun::ExitStatus::Error);
+}
+
+/// Verifies that the metadata of a newly built project can be inspected.
+#[test]
+fn mun_inspect() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &[]);
+
+    let library_path = project_path.join("target/mod.munlib");
+    let args: Vec<OsString> = vec!["mun".into(), "inspect".into(), library_path.clone().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "inspect".into(),
+        "--json".into(),
+        library_path.into(),
+    ];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+}
+
+/// Verifies that two builds of a project can be compared.
+#[test]
+fn mun_diff() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &[]);
+    let old_path = project_dir.path().join("old.munlib");
+    std::fs::copy(project_path.join("target/mod.munlib"), &old_path).unwrap();
+
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        "pub struct Foo { a: i32 }\npub fn main() -> Foo { Foo { a: 5 } }\n",
+    )
+    .unwrap();
+    build(&project_path, &[]);
+
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "diff".into(),
+        old_path.into(),
+        project_path.join("target/mod.munlib").into(),
+    ];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+}
+
+#[test]
+fn mun_explain() {
+    let args: Vec<OsString> = vec!["mun".into(), "explain".into(), "E0008".into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let args: Vec<OsString> = vec!["mun".into(), "explain".into(), "E9999".into()];
+    assert!(run_with_args(args).is_err());
+}
+
+/// Verifies that features declared in the manifest drive conditional
+/// compilation.
+#[test]
+fn mun_build_features() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let manifest_path = project_path.join("mun.toml");
+    let mut manifest = std::fs::read_to_string(&manifest_path).unwrap();
+    manifest.push_str("\n[features]\ndefault = [\"debug_draw\"]\ndebug_draw = []\n");
+    std::fs::write(&manifest_path, manifest).unwrap();
+
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        r#"
+        #[cfg(feature = "debug_draw")]
+        pub fn main() -> i32 { 1 }
+
+        #[cfg(not(feature = "debug_draw"))]
+        pub fn main() -> i32 { 2 }
+        "#,
+    )
+    .unwrap();
+
+    let library_path = project_path.join("target/mod.munlib");
+    let invoke_main = || {
+        // Safety: since we compiled the code ourselves, loading the library should be
+        // safe
+        let runtime = unsafe { Runtime::builder(&library_path).finish() }.unwrap();
+        runtime.invoke::<i32, ()>("main", ()).unwrap()
+    };
+
+    build(&project_path, &[]);
+    assert_eq!(invoke_main(), 1);
+
+    build(&project_path, &["--no-default-features"]);
+    assert_eq!(invoke_main(), 2);
+
+    build(
+        &project_path,
+        &["--no-default-features", "--features", "debug_draw"],
+    );
+    assert_eq!(invoke_main(), 1);
+
+    // Features that are not declared in the manifest are rejected
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "build".into(),
+        "--manifest-path".into(),
+        manifest_path.into(),
+        "--features".into(),
+        "unknown".into(),
+    ];
+    assert!(run_with_args(args).is_err());
+}
+
+/// Verifies that the backend that generates machine code can be selected.
+#[test]
+fn mun_build_backend() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &["--backend", "llvm"]);
+    assert!(project_path.join("target/mod.munlib").is_file());
+
+    // The Cranelift backend is not available yet
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "build".into(),
+        "--manifest-path".into(),
+        project_path.join("mun.toml").into(),
+        "--backend".into(),
+        "cranelift".into(),
+    ];
+    assert!(run_with_args(args).is_err());
+}
+
+/// Verifies that a build reuses the assemblies of modules that did not change
+/// since a previous build.
+#[test]
+fn mun_build_incremental() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        "use foo::value;\npub fn main() -> i32 { value() }\n",
+    )
+    .unwrap();
+    let foo_path = project_path.join("src/foo.mun");
+    std::fs::write(&foo_path, "pub fn value() -> i32 { 1 }\n").unwrap();
+
+    let library_path = project_path.join("target/mod.munlib");
+    let modified = || {
+        std::fs::metadata(&library_path)
+            .unwrap()
+            .modified()
+            .unwrap()
+    };
+
+    build(&project_path, &[]);
+    assert!(project_path
+        .join("target/incremental/mod.fingerprint")
+        .is_file());
+    assert!(project_path
+        .join("target/incremental/foo.fingerprint")
+        .is_file());
+    let first_build = modified();
+
+    // Changing the body of a function only rebuilds the assembly of its module
+    std::fs::write(&foo_path, "pub fn value() -> i32 { 2 }\n").unwrap();
+    build(&project_path, &[]);
+    assert_eq!(modified(), first_build);
+
+    // Safety: since we compiled the code ourselves, loading the library should be
+    // safe
+    let runtime = unsafe { Runtime::builder(&library_path).finish() }.unwrap();
+    assert_eq!(runtime.invoke::<i32, ()>("main", ()).unwrap(), 2);
+}
+
+/// Verifies that the members of a workspace are built together with the root
+/// package.
+#[test]
+fn mun_build_workspace() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+    let member_path = project_path.join("game");
+    for path in [&project_path, &member_path] {
+        let args: Vec<OsString> = vec!["mun".into(), "new".into(), path.as_path().into()];
+        assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+    }
+
+    let manifest_path = project_path.join("mun.toml");
+    let mut manifest = std::fs::read_to_string(&manifest_path).unwrap();
+    manifest.push_str("\n[workspace]\nmembers = [\"game\"]\n");
+    std::fs::write(&manifest_path, manifest).unwrap();
+    std::fs::write(
+        member_path.join("src/mod.mun"),
+        "pub fn main() -> i32 { 42 }\n",
+    )
+    .unwrap();
+
+    build(&project_path, &[]);
+    assert!(project_path.join("target/mod.munlib").is_file());
+
+    let library_path = project_path.join("target/game/mod.munlib");
+    // Safety: since we compiled the code ourselves, loading the library should be
+    // safe
+    let runtime = unsafe { Runtime::builder(&library_path).finish() }.unwrap();
+    assert_eq!(runtime.invoke::<i32, ()>("main", ()).unwrap(), 42);
+}
+
+/// Verifies that a report of the timings of a build can be written.
+#[test]
+fn mun_build_timings() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &["--timings"]);
+    assert!(project_path.join("target/timings.html").is_file());
+
+    let report: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(project_path.join("target/timings.json")).unwrap(),
+    )
+    .unwrap();
+    let phases: Vec<_> = report["phases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|phase| phase["phase"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        phases,
+        vec![
+            "loading",
+            "parsing",
+            "analysis",
+            "code generation",
+            "linking",
+            "writing"
+        ]
+    );
+    assert_eq!(report["assemblies"][0]["name"], "mod");
+    assert!(report["queries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|query| query["name"] == "target_assembly"));
+}
+
 fn build(project: &Path, args: &[&str]) {
     let args: Vec<OsString> = vec![
         OsString::from("mun"),
@@ -1,6 +1,10 @@
-use std::{ffi::CStr, os::raw::c_char, slice, str};
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    slice, str,
+};
 
-use crate::{DispatchTable, ModuleInfo, TypeLut};
+use crate::{DispatchTable, ModuleInfo, TestDefinition, TypeLut};
 
 /// Represents an assembly declaration.
 #[repr(C)]
@@ -15,6 +19,20 @@ pub struct AssemblyInfo<'a> {
     pub(crate) dependencies: *const *const c_char,
     /// Number of dependencies
     pub num_dependencies: u32,
+    /// Pointer to the `init` function, or null if the assembly has none
+    pub(crate) init_fn: *const c_void,
+    /// Pointer to the `shutdown` function, or null if the assembly has none
+    pub(crate) shutdown_fn: *const c_void,
+    /// Functions that are marked with the `#[test]` attribute
+    pub(crate) tests: *const TestDefinition,
+    /// Number of tests
+    pub num_tests: u32,
+    /// Version of the package from which the assembly was compiled, or null if
+    /// it was not compiled from a package
+    pub(crate) version: *const c_char,
+    /// Version requirements of the assembly dependencies, in the same order as
+    /// `dependencies`. An entry is null if the dependency has no version.
+    pub(crate) dependency_versions: *const *const c_char,
 }
 
 impl AssemblyInfo<'_> {
@@ -30,6 +48,65 @@ impl AssemblyInfo<'_> {
             .iter()
             .map(|d| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*d).to_bytes()) })
     }
+
+    /// Returns the version of the package from which the assembly was
+    /// compiled, if it was compiled from a package.
+    pub fn version(&self) -> Option<&str> {
+        (!self.version.is_null())
+            .then(|| unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.version).to_bytes()) })
+    }
+
+    /// Returns an iterator over the assembly's dependencies together with the
+    /// version requirement that was recorded for each dependency when the
+    /// assembly was compiled, if any.
+    pub fn dependency_requirements(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        let requirements = if self.num_dependencies == 0 || self.dependency_versions.is_null() {
+            &[]
+        } else {
+            unsafe {
+                slice::from_raw_parts(self.dependency_versions, self.num_dependencies as usize)
+            }
+        };
+
+        self.dependencies()
+            .zip(
+                requirements
+                    .iter()
+                    .copied()
+                    .chain(std::iter::repeat(std::ptr::null())),
+            )
+            .map(|(dependency, requirement)| {
+                (
+                    dependency,
+                    (!requirement.is_null()).then(|| unsafe {
+                        str::from_utf8_unchecked(CStr::from_ptr(requirement).to_bytes())
+                    }),
+                )
+            })
+    }
+
+    /// Returns a pointer to the function that must be invoked after the
+    /// assembly has been loaded and linked, if the assembly defines one. The
+    /// function takes no arguments and returns nothing.
+    pub fn init_fn(&self) -> Option<*const c_void> {
+        (!self.init_fn.is_null()).then_some(self.init_fn)
+    }
+
+    /// Returns a pointer to the function that must be invoked before the
+    /// assembly is unloaded, if the assembly defines one. The function takes
+    /// no arguments and returns nothing.
+    pub fn shutdown_fn(&self) -> Option<*const c_void> {
+        (!self.shutdown_fn.is_null()).then_some(self.shutdown_fn)
+    }
+
+    /// Returns the assembly's test functions.
+    pub fn tests(&self) -> &[TestDefinition] {
+        if self.num_tests == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.tests, self.num_tests as usize) }
+        }
+    }
 }
 
 unsafe impl Send for AssemblyInfo<'_> {}
@@ -44,28 +121,40 @@ impl serde::Serialize for AssemblyInfo<'_> {
         use itertools::Itertools;
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("AssemblyInfo", 4)?;
+        let mut s = serializer.serialize_struct("AssemblyInfo", 7)?;
         s.serialize_field("symbols", &self.symbols)?;
         s.serialize_field("dispatch_table", &self.dispatch_table)?;
         s.serialize_field("type_lut", &self.type_lut)?;
         s.serialize_field("dependencies", &self.dependencies().collect_vec())?;
+        s.serialize_field(
+            "dependency_versions",
+            &self
+                .dependency_requirements()
+                .map(|(_, requirement)| requirement)
+                .collect_vec(),
+        )?;
+        s.serialize_field("tests", self.tests())?;
+        s.serialize_field("version", &self.version())?;
         s.end()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{
+        ffi::{c_void, CString},
+        ptr,
+    };
 
     use crate::test_utils::{
-        fake_assembly_info, fake_dispatch_table, fake_module_info, fake_type_lut, FAKE_DEPENDENCY,
-        FAKE_MODULE_PATH,
+        fake_assembly_info, fake_dispatch_table, fake_module_info, fake_test_definition,
+        fake_type_lut, FAKE_DEPENDENCY, FAKE_MODULE_PATH, FAKE_TEST_NAME,
     };
 
     #[test]
     fn test_assembly_info_dependencies() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[]);
 
         let dispatch_table = fake_dispatch_table(&[], &mut []);
         let type_lut = fake_type_lut(&[], &mut [], &[]);
@@ -79,4 +168,71 @@ mod tests {
             assert_eq!(lhs, *rhs);
         }
     }
+
+    #[test]
+    fn test_assembly_info_versions() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &[]);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = fake_type_lut(&[], &mut [], &[]);
+
+        let dependency = CString::new(FAKE_DEPENDENCY).expect("Invalid fake dependency.");
+        let dependencies = &[dependency.as_ptr(), dependency.as_ptr()];
+        let mut assembly = fake_assembly_info(module, dispatch_table, type_lut, dependencies);
+        assert_eq!(assembly.version(), None);
+        assert_eq!(
+            assembly.dependency_requirements().collect::<Vec<_>>(),
+            vec![(FAKE_DEPENDENCY, None), (FAKE_DEPENDENCY, None)]
+        );
+
+        let version = CString::new("0.2.0").expect("Invalid version.");
+        let requirement = CString::new("^0.2.0").expect("Invalid version requirement.");
+        let dependency_versions = &[requirement.as_ptr(), ptr::null()];
+        assembly.version = version.as_ptr();
+        assembly.dependency_versions = dependency_versions.as_ptr();
+        assert_eq!(assembly.version(), Some("0.2.0"));
+        assert_eq!(
+            assembly.dependency_requirements().collect::<Vec<_>>(),
+            vec![(FAKE_DEPENDENCY, Some("^0.2.0")), (FAKE_DEPENDENCY, None)]
+        );
+    }
+
+    #[test]
+    fn test_assembly_info_lifecycle_fns() {
+        extern "C" fn init() {}
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &[]);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = fake_type_lut(&[], &mut [], &[]);
+
+        let mut assembly = fake_assembly_info(module, dispatch_table, type_lut, &[]);
+        assert_eq!(assembly.init_fn(), None);
+        assert_eq!(assembly.shutdown_fn(), None);
+
+        assembly.init_fn = init as *const c_void;
+        assert_eq!(assembly.init_fn(), Some(init as *const c_void));
+        assert_eq!(assembly.shutdown_fn(), None);
+    }
+
+    #[test]
+    fn test_assembly_info_tests() {
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &[]);
+
+        let dispatch_table = fake_dispatch_table(&[], &mut []);
+        let type_lut = fake_type_lut(&[], &mut [], &[]);
+
+        let mut assembly = fake_assembly_info(module, dispatch_table, type_lut, &[]);
+        assert!(assembly.tests().is_empty());
+
+        let test_name = CString::new(FAKE_TEST_NAME).expect("Invalid fake test name.");
+        let tests = &[fake_test_definition(&test_name)];
+        assembly.tests = tests.as_ptr();
+        assembly.num_tests = tests.len() as u32;
+        assert_eq!(assembly.tests().len(), 1);
+        assert_eq!(assembly.tests()[0].name(), FAKE_TEST_NAME);
+    }
 }
@@ -4,12 +4,27 @@ use std::{
     slice, str,
 };
 
-use crate::type_id::{HasStaticTypeId, TypeId};
+use crate::{
+    type_id::{HasStaticTypeId, TypeId},
+    Privacy,
+};
 
 /// Represents a function definition. A function definition contains the name,
 /// type signature, and a pointer to the implementation.
 ///
-/// `fn_ptr` can be used to call the declared function.
+/// `fn_ptr` can be used to call the declared function. Value struct arguments
+/// are passed as a handle to a heap-allocated copy of the struct. A value
+/// struct is not returned directly; instead the function receives a pointer to
+/// a caller-allocated buffer as its first argument, to which it writes the
+/// return value. The buffer must have the size and alignment of the struct's
+/// `TypeDefinition`.
+///
+/// `trampoline_ptr` calls the same function without knowing its signature at
+/// compile time. It has the signature
+/// `void trampoline(const void* const* args, void* ret)`, where `args` points
+/// to a pointer to every argument, each passed as for `fn_ptr`. The value that
+/// `fn_ptr` returns, or writes to its return slot, is written to `ret`, which
+/// is ignored if the function doesn't return a value.
 #[repr(C)]
 #[derive(Clone)]
 pub struct FunctionDefinition<'a> {
@@ -17,6 +32,15 @@ pub struct FunctionDefinition<'a> {
     pub prototype: FunctionPrototype<'a>,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Pointer to a trampoline that calls the function with arguments read
+    /// from an array, or null if the function can only be called through
+    /// `fn_ptr`
+    pub trampoline_ptr: *const c_void,
+    /// Documentation of the function, or null if it has none
+    pub docs: *const c_char,
+    /// Whether the function can be called by the host. A private function is
+    /// only exposed to link other assemblies of the same package.
+    pub privacy: Privacy,
 }
 
 /// Represents a function prototype. A function prototype contains the name,
@@ -42,6 +66,17 @@ pub struct FunctionSignature<'a> {
     pub num_arg_types: u16,
 }
 
+impl FunctionDefinition<'_> {
+    /// Returns the function's documentation, if any.
+    pub fn docs(&self) -> Option<&str> {
+        if self.docs.is_null() {
+            None
+        } else {
+            Some(unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.docs).to_bytes()) })
+        }
+    }
+}
+
 unsafe impl Send for FunctionDefinition<'_> {}
 unsafe impl Sync for FunctionDefinition<'_> {}
 
@@ -94,9 +129,12 @@ impl serde::Serialize for FunctionDefinition<'_> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("FunctionDefinition", 1)?;
+        let mut s = serializer.serialize_struct("FunctionDefinition", 3)?;
         s.serialize_field("prototype", &self.prototype)?;
         s.skip_field("fn_ptr")?;
+        s.skip_field("trampoline_ptr")?;
+        s.serialize_field("docs", &self.docs())?;
+        s.serialize_field("privacy", &self.privacy)?;
         s.end()
     }
 }
@@ -133,11 +171,13 @@ impl serde::Serialize for FunctionSignature<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{ffi::CString, ptr};
 
+    use super::FunctionDefinition;
     use crate::{
         test_utils::{fake_fn_prototype, fake_fn_signature, FAKE_FN_NAME},
         type_id::HasStaticTypeId,
+        Privacy,
     };
 
     #[test]
@@ -148,6 +188,23 @@ mod tests {
         assert_eq!(fn_signature.name(), FAKE_FN_NAME);
     }
 
+    #[test]
+    fn test_fn_definition_docs() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let docs = CString::new("Adds two numbers").expect("Invalid docs.");
+        let mut fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: ptr::null(),
+            trampoline_ptr: ptr::null(),
+            docs: ptr::null(),
+            privacy: Privacy::Public,
+        };
+        assert_eq!(fn_definition.docs(), None);
+
+        fn_definition.docs = docs.as_ptr();
+        assert_eq!(fn_definition.docs(), Some("Adds two numbers"));
+    }
+
     #[test]
     fn test_fn_signature_arg_types_none() {
         let arg_types = &[];
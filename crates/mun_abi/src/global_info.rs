@@ -0,0 +1,69 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    str,
+};
+
+use crate::type_id::TypeId;
+
+/// Represents a global variable (a `static` item) of a module. The storage of
+/// the global lives in the assembly and is pointed to by `value_ptr`.
+///
+/// When an assembly is reloaded the runtime copies the value of each global
+/// from the old assembly into the storage of the new assembly.
+#[repr(C)]
+#[derive(Clone)]
+pub struct GlobalDefinition<'a> {
+    /// Fully qualified name of the global
+    pub name: *const c_char,
+    /// The type of the global
+    pub type_id: TypeId<'a>,
+    /// Pointer to the storage of the global
+    pub value_ptr: *mut c_void,
+}
+
+impl GlobalDefinition<'_> {
+    /// Returns the global's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+}
+
+unsafe impl Send for GlobalDefinition<'_> {}
+unsafe impl Sync for GlobalDefinition<'_> {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GlobalDefinition<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("GlobalDefinition", 2)?;
+        s.serialize_field("name", self.name())?;
+        s.serialize_field("type_id", &self.type_id)?;
+        s.skip_field("value_ptr")?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use crate::{
+        test_utils::{fake_global_definition, FAKE_GLOBAL_NAME},
+        type_id::HasStaticTypeId,
+    };
+
+    #[test]
+    fn test_global_definition_name() {
+        let name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let mut value = 0u32;
+        let global = fake_global_definition(&name, u32::type_id().clone(), &mut value);
+
+        assert_eq!(global.name(), FAKE_GLOBAL_NAME);
+        assert_eq!(&global.type_id, u32::type_id());
+    }
+}
@@ -9,9 +9,11 @@ use std::{ffi::CStr, fmt};
 pub use assembly_info::AssemblyInfo;
 pub use dispatch_table::DispatchTable;
 pub use function_info::{FunctionDefinition, FunctionPrototype, FunctionSignature};
+pub use global_info::GlobalDefinition;
 pub use module_info::ModuleInfo;
 pub use primitive::PrimitiveType;
 pub use struct_info::{StructDefinition, StructMemoryKind};
+pub use test_info::TestDefinition;
 pub use type_id::{ArrayTypeId, HasStaticTypeId, PointerTypeId, TypeId};
 pub use type_info::{HasStaticTypeName, TypeDefinition, TypeDefinitionData};
 pub use type_lut::TypeLut;
@@ -20,10 +22,12 @@ pub use type_lut::TypeLut;
 mod assembly_info;
 mod dispatch_table;
 mod function_info;
+mod global_info;
 mod module_info;
 mod primitive;
 pub mod static_type_map;
 mod struct_info;
+mod test_info;
 mod type_id;
 mod type_info;
 mod type_lut;
@@ -33,7 +37,7 @@ mod test_utils;
 
 /// Defines the current ABI version
 #[allow(clippy::zero_prefixed_literal)]
-pub const ABI_VERSION: u32 = 00_03_00;
+pub const ABI_VERSION: u32 = 00_12_00;
 /// Defines the name for the `get_info` function
 pub const GET_INFO_FN_NAME: &str = "get_info";
 /// Defines the name for the `get_version` function
@@ -1,6 +1,6 @@
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
-use crate::{FunctionDefinition, TypeDefinition};
+use crate::{FunctionDefinition, GlobalDefinition, TypeDefinition};
 
 /// Represents a module declaration.
 #[repr(C)]
@@ -11,10 +11,14 @@ pub struct ModuleInfo<'a> {
     pub(crate) functions: *const FunctionDefinition<'a>,
     /// Module types
     pub(crate) types: *const TypeDefinition<'a>,
+    /// Module globals
+    pub(crate) globals: *const GlobalDefinition<'a>,
     /// Number of module functions
     pub num_functions: u32,
     /// Number of module types
     pub num_types: u32,
+    /// Number of module globals
+    pub num_globals: u32,
 }
 
 impl<'a> ModuleInfo<'a> {
@@ -55,6 +59,15 @@ impl<'a> ModuleInfo<'a> {
             unsafe { slice::from_raw_parts(self.types, self.num_types as usize) }
         }
     }
+
+    /// Returns the module's globals.
+    pub fn globals(&self) -> &[GlobalDefinition<'a>] {
+        if self.num_globals == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.globals, self.num_globals as usize) }
+        }
+    }
 }
 
 unsafe impl Send for ModuleInfo<'_> {}
@@ -68,10 +81,11 @@ impl serde::Serialize for ModuleInfo<'_> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("ModuleInfo", 3)?;
+        let mut s = serializer.serialize_struct("ModuleInfo", 4)?;
         s.serialize_field("path", self.path())?;
         s.serialize_field("functions", self.functions())?;
         s.serialize_field("types", self.types())?;
+        s.serialize_field("globals", self.globals())?;
         s.end()
     }
 }
@@ -82,17 +96,18 @@ mod tests {
 
     use crate::{
         test_utils::{
-            fake_fn_prototype, fake_module_info, fake_struct_definition, fake_type_definition,
-            FAKE_FN_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME,
+            fake_fn_prototype, fake_global_definition, fake_module_info, fake_struct_definition,
+            fake_type_definition, FAKE_FN_NAME, FAKE_GLOBAL_NAME, FAKE_MODULE_PATH,
+            FAKE_STRUCT_NAME,
         },
         type_id::HasStaticTypeId,
-        FunctionDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData,
+        FunctionDefinition, Privacy, StructMemoryKind, TypeDefinition, TypeDefinitionData,
     };
 
     #[test]
     fn test_module_info_path() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[]);
 
         assert_eq!(module.path(), FAKE_MODULE_PATH);
     }
@@ -102,10 +117,31 @@ mod tests {
         let functions = &[];
         let types = &[];
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[]);
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
+        assert_eq!(module.globals().len(), 0);
+    }
+
+    #[test]
+    fn test_module_info_globals_some() {
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let mut value = 5i64;
+        let globals = [fake_global_definition(
+            &global_name,
+            i64::type_id().clone(),
+            &mut value,
+        )];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &globals);
+
+        let result_globals = module.globals();
+        assert_eq!(result_globals.len(), 1);
+        assert_eq!(result_globals[0].name(), FAKE_GLOBAL_NAME);
+        assert_eq!(&result_globals[0].type_id, i64::type_id());
+        assert_eq!(unsafe { *result_globals[0].value_ptr.cast::<i64>() }, 5);
     }
 
     #[test]
@@ -117,6 +153,9 @@ mod tests {
         let fn_info = FunctionDefinition {
             prototype: fn_prototype,
             fn_ptr: ptr::null(),
+            trampoline_ptr: ptr::null(),
+            docs: ptr::null(),
+            privacy: Privacy::Public,
         };
         let functions = &[fn_info];
 
@@ -128,7 +167,7 @@ mod tests {
         let types = [type_info];
 
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, &types);
+        let module = fake_module_info(&module_path, functions, &types, &[]);
 
         let result_functions = module.functions();
         assert_eq!(result_functions.len(), functions.len());
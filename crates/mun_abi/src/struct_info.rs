@@ -14,6 +14,8 @@ pub struct StructDefinition<'a> {
     pub(crate) field_types: *const TypeId<'a>,
     /// Struct fields' offsets
     pub(crate) field_offsets: *const u16,
+    /// Documentation of the struct, or null if it has none
+    pub docs: *const c_char,
     // TODO: Field accessibility levels
     // const MunPrivacy_t *field_privacies,
     /// Number of fields
@@ -78,6 +80,15 @@ impl<'a> StructDefinition<'a> {
     pub fn num_fields(&self) -> usize {
         self.num_fields.into()
     }
+
+    /// Returns the struct's documentation, if any.
+    pub fn docs(&self) -> Option<&str> {
+        if self.docs.is_null() {
+            None
+        } else {
+            Some(unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.docs).to_bytes()) })
+        }
+    }
 }
 
 impl PartialEq for StructDefinition<'_> {
@@ -104,7 +115,7 @@ impl serde::Serialize for StructDefinition<'_> {
             offset: &'a u16,
         }
 
-        let mut s = serializer.serialize_struct("StructInfo", 3)?;
+        let mut s = serializer.serialize_struct("StructInfo", 4)?;
 
         s.serialize_field("guid", &self.guid)?;
         s.serialize_field(
@@ -121,6 +132,7 @@ impl serde::Serialize for StructDefinition<'_> {
                 .collect_vec(),
         )?;
         s.serialize_field("memory_kind", &self.memory_kind)?;
+        s.serialize_field("docs", &self.docs())?;
         s.end()
     }
 }
@@ -195,4 +207,16 @@ mod tests {
 
         assert_eq!(struct_info.memory_kind, struct_memory_kind);
     }
+
+    #[test]
+    fn test_struct_info_docs() {
+        let struct_name = CString::new(FAKE_STRUCT_NAME).expect("Invalid fake struct name.");
+        let mut struct_info =
+            fake_struct_definition(&struct_name, &[], &[], &[], StructMemoryKind::default());
+        assert_eq!(struct_info.docs(), None);
+
+        let docs = CString::new("A struct").expect("Invalid docs.");
+        struct_info.docs = docs.as_ptr();
+        assert_eq!(struct_info.docs(), Some("A struct"));
+    }
 }
@@ -0,0 +1,56 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    str,
+};
+
+/// Represents a function that is marked with the `#[test]` attribute. Test
+/// functions take no arguments and return nothing.
+#[repr(C)]
+#[derive(Clone)]
+pub struct TestDefinition {
+    /// Fully qualified name of the test
+    pub name: *const c_char,
+    /// Pointer to the test function
+    pub fn_ptr: *const c_void,
+}
+
+impl TestDefinition {
+    /// Returns the test's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+}
+
+unsafe impl Send for TestDefinition {}
+unsafe impl Sync for TestDefinition {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TestDefinition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("TestDefinition", 1)?;
+        s.serialize_field("name", self.name())?;
+        s.skip_field("fn_ptr")?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use crate::test_utils::{fake_test_definition, FAKE_TEST_NAME};
+
+    #[test]
+    fn test_test_definition_name() {
+        let name = CString::new(FAKE_TEST_NAME).expect("Invalid fake test name.");
+        let test = fake_test_definition(&name);
+
+        assert_eq!(test.name(), FAKE_TEST_NAME);
+    }
+}
@@ -1,12 +1,14 @@
 use std::{
     ffi::{self, CStr},
     os::raw::c_char,
+    ptr,
 };
 
 use crate::{
     type_id::{HasStaticTypeId, TypeId},
-    AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype, FunctionSignature, Guid,
-    ModuleInfo, StructDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData, TypeLut,
+    AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype, FunctionSignature,
+    GlobalDefinition, Guid, ModuleInfo, StructDefinition, StructMemoryKind, TestDefinition,
+    TypeDefinition, TypeDefinitionData, TypeLut,
 };
 
 pub(crate) const FAKE_TYPE_GUID: Guid =
@@ -15,8 +17,10 @@ pub(crate) const FAKE_TYPE_ID: TypeId<'static> = TypeId::Concrete(FAKE_TYPE_GUID
 pub(crate) const FAKE_DEPENDENCY: &str = "path/to/dependency.munlib";
 pub(crate) const FAKE_FIELD_NAME: &str = "field_name";
 pub(crate) const FAKE_FN_NAME: &str = "fn_name";
+pub(crate) const FAKE_GLOBAL_NAME: &str = "GLOBAL_NAME";
 pub(crate) const FAKE_MODULE_PATH: &str = "path::to::module";
 pub(crate) const FAKE_STRUCT_NAME: &str = "StructName";
+pub(crate) const FAKE_TEST_NAME: &str = "test_name";
 pub(crate) const FAKE_TYPE_NAME: &str = "TypeName";
 
 pub(crate) fn fake_assembly_info<'a>(
@@ -31,6 +35,12 @@ pub(crate) fn fake_assembly_info<'a>(
         type_lut,
         dependencies: dependencies.as_ptr(),
         num_dependencies: dependencies.len() as u32,
+        init_fn: ptr::null(),
+        shutdown_fn: ptr::null(),
+        tests: ptr::null(),
+        num_tests: 0,
+        version: ptr::null(),
+        dependency_versions: ptr::null(),
     }
 }
 
@@ -84,10 +94,23 @@ pub(crate) fn fake_fn_prototype<'a>(
     }
 }
 
+pub(crate) fn fake_global_definition<'a, T>(
+    name: &CStr,
+    type_id: TypeId<'a>,
+    value: &mut T,
+) -> GlobalDefinition<'a> {
+    GlobalDefinition {
+        name: name.as_ptr(),
+        type_id,
+        value_ptr: (value as *mut T).cast(),
+    }
+}
+
 pub(crate) fn fake_module_info<'a>(
     path: &CStr,
     functions: &[FunctionDefinition<'a>],
     types: &[TypeDefinition<'a>],
+    globals: &[GlobalDefinition<'a>],
 ) -> ModuleInfo<'a> {
     ModuleInfo {
         path: path.as_ptr(),
@@ -95,6 +118,8 @@ pub(crate) fn fake_module_info<'a>(
         num_functions: functions.len() as u32,
         types: types.as_ptr(),
         num_types: types.len() as u32,
+        globals: globals.as_ptr(),
+        num_globals: globals.len() as u32,
     }
 }
 
@@ -113,6 +138,7 @@ pub(crate) fn fake_struct_definition<'a>(
         field_names: field_names.as_ptr(),
         field_types: field_types.as_ptr(),
         field_offsets: field_offsets.as_ptr(),
+        docs: ptr::null(),
         num_fields: field_names.len() as u16,
         memory_kind,
     }
@@ -121,7 +147,7 @@ pub(crate) fn fake_struct_definition<'a>(
 pub(crate) fn fake_type_definition<'a>(
     name: &CStr,
     size: u32,
-    alignment: u8,
+    alignment: u16,
     data: TypeDefinitionData<'a>,
 ) -> TypeDefinition<'a> {
     TypeDefinition {
@@ -131,3 +157,12 @@ pub(crate) fn fake_type_definition<'a>(
         data,
     }
 }
+
+pub(crate) fn fake_test_definition(name: &CStr) -> TestDefinition {
+    extern "C" fn test() {}
+
+    TestDefinition {
+        name: name.as_ptr(),
+        fn_ptr: test as *const ffi::c_void,
+    }
+}
@@ -27,8 +27,8 @@ pub struct TypeDefinition<'a> {
     pub name: *const c_char,
     /// The exact size of the type in bits without any padding
     pub(crate) size_in_bits: u32,
-    /// The alignment of the type
-    pub(crate) alignment: u8,
+    /// The alignment of the type in bytes
+    pub(crate) alignment: u16,
     /// Type group
     pub data: TypeDefinitionData<'a>,
 }
@@ -3,6 +3,7 @@
 use std::{
     ffi::{CStr, CString},
     os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
     ptr,
 };
 
@@ -60,6 +61,27 @@ impl<T: Into<Vec<u8>>> From<T> for ErrorHandle {
     }
 }
 
+/// Runs `f` and converts a panic into an [`ErrorHandle`] that contains the
+/// panic message.
+///
+/// Unwinding across the C ABI is undefined behavior, so every function that is
+/// exposed through the C ABI should run its body inside this function.
+pub fn catch_panic<F: FnOnce() -> ErrorHandle>(f: F) -> ErrorHandle {
+    // The caller receives an error and must not rely on any of the state that
+    // `f` was modifying, so it's fine to assume unwind safety.
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(error) => error,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic payload");
+            ErrorHandle::new(format!("panicked: {}", message.replace('\0', "\\0")))
+        }
+    }
+}
+
 /// Destructs the error message corresponding to the specified handle.
 ///
 /// # Safety
@@ -144,3 +166,20 @@ macro_rules! assert_error_snapshot {
         );
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{catch_panic, mun_error_destroy, ErrorHandle};
+
+    #[test]
+    fn catch_panic_message() {
+        assert!(catch_panic(ErrorHandle::default).is_ok());
+
+        let error = catch_panic(|| panic!("invalid {}", "state"));
+        assert_eq!(
+            unsafe { error.err() }.unwrap().to_str(),
+            Ok("panicked: invalid state")
+        );
+        unsafe { mun_error_destroy(error) };
+    }
+}
@@ -1,66 +1,28 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use anyhow::anyhow;
 use apple_codesign::{SigningSettings, UnifiedSigner};
-use inkwell::context::Context;
 use tempfile::NamedTempFile;
 
-use crate::{
-    code_gen::{AssemblyBuilder, CodeGenContext, ObjectFile},
-    db::CodeGenDatabase,
-    ModuleGroupId,
-};
-
-/// An `Assembly` is a successfully linked module of code from one or more
-/// files.
-pub struct Assembly<'db, 'ink, 'ctx> {
-    code_gen: &'ctx CodeGenContext<'db, 'ink>,
-    module: inkwell::module::Module<'ink>,
-}
-
-impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
-    /// Constructs an assembly
-    pub fn new(
-        code_gen: &'ctx CodeGenContext<'db, 'ink>,
-        module: inkwell::module::Module<'ink>,
-    ) -> Self {
-        Self { code_gen, module }
-    }
-
-    /// Tries to convert the assembly into an `ObjectFile`.
-    pub fn into_object_file(self) -> Result<ObjectFile, anyhow::Error> {
-        ObjectFile::new(
-            &self.code_gen.db.target(),
-            &self.code_gen.target_machine,
-            &self.module,
-        )
-    }
-
-    /// Tries to write the `Assembly`'s IR to file.
-    pub fn write_ir_to_file(self, output_path: &Path) -> Result<(), anyhow::Error> {
-        self.module
-            .print_to_file(output_path)
-            .map_err(|e| anyhow!("{}", e))
-    }
-}
-
-/// Builds an assembly for the specified file
-fn build_assembly<'db, 'ink, 'ctx>(
-    db: &'db dyn CodeGenDatabase,
-    code_gen: &'ctx CodeGenContext<'db, 'ink>,
-    module_group_id: ModuleGroupId,
-) -> Assembly<'db, 'ink, 'ctx> {
-    // Setup the code generation context
-    let module_partition = db.module_partition();
-
-    let module_builder = AssemblyBuilder::new(code_gen, &module_partition, module_group_id);
-    module_builder.build().expect("unable to create assembly")
-}
+use crate::{backend::CodeGenerator, db::CodeGenDatabase, ModuleGroupId};
 
 /// A `TargetAssembly` is a reference to a Mun library stored on disk.
 #[derive(Debug)]
 pub struct TargetAssembly {
     file: NamedTempFile,
+    timings: AssemblyTimings,
+}
+
+/// The time it took to build a [`TargetAssembly`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssemblyTimings {
+    /// The time it took to generate the machine code of the assembly
+    pub code_generation: Duration,
+    /// The time it took to link the machine code into a shared object
+    pub linking: Duration,
 }
 
 impl PartialEq for TargetAssembly {
@@ -83,6 +45,11 @@ impl TargetAssembly {
     pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
         std::fs::copy(self.path(), destination).map(|_| ())
     }
+
+    /// Returns the time it took to build the assembly
+    pub fn timings(&self) -> AssemblyTimings {
+        self.timings
+    }
 }
 
 /// Builds an assembly for the specified module.
@@ -90,17 +57,14 @@ pub(crate) fn build_target_assembly(
     db: &dyn CodeGenDatabase,
     module_group: ModuleGroupId,
 ) -> Arc<TargetAssembly> {
-    // Setup the code generation context
-    let inkwell_context = Context::create();
-    let code_gen_context = CodeGenContext::new(&inkwell_context, db);
+    let start = Instant::now();
 
-    // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
-
-    // Convert the assembly into an object file
-    let obj_file = assembly
-        .into_object_file()
+    // Generate the machine code of the module with the selected backend
+    let obj_file = code_generator(db)
+        .generate_object_file(db, module_group)
         .expect("unable to create object file");
+    let code_generation = start.elapsed();
+    let start = Instant::now();
 
     // Construct a temporary file for the assembly
     let file = NamedTempFile::new().expect("could not create temp file for shared object");
@@ -118,7 +82,13 @@ pub(crate) fn build_target_assembly(
             .expect("Failed to sign shared object");
     }
 
-    Arc::new(TargetAssembly { file })
+    Arc::new(TargetAssembly {
+        file,
+        timings: AssemblyTimings {
+            code_generation,
+            linking: start.elapsed(),
+        },
+    })
 }
 
 /// An `AssemblyIr` is a reference to an IR file stored on disk.
@@ -154,20 +124,21 @@ pub(crate) fn build_assembly_ir(
     db: &dyn CodeGenDatabase,
     module_group: ModuleGroupId,
 ) -> Arc<AssemblyIr> {
-    // Setup the code generation context
-    let inkwell_context = Context::create();
-    let code_gen_context = CodeGenContext::new(&inkwell_context, db);
-
-    // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
-
     // Construct a temporary file for the assembly
     let file = NamedTempFile::new().expect("could not create temp file for shared object");
 
     // Write the assembly's IR to disk
-    assembly
-        .write_ir_to_file(file.path())
+    code_generator(db)
+        .write_ir(db, module_group, file.path())
         .expect("could not write to temp file");
 
     Arc::new(AssemblyIr { file })
 }
+
+/// Returns the code generator of the backend that is selected in the database.
+fn code_generator(db: &dyn CodeGenDatabase) -> &'static dyn CodeGenerator {
+    let backend = db.backend();
+    backend
+        .code_generator()
+        .unwrap_or_else(|| panic!("the {backend} backend is not available"))
+}
@@ -0,0 +1,67 @@
+use std::{fmt, path::Path};
+
+pub(crate) use self::llvm::{Assembly, LlvmBackend};
+use crate::{db::CodeGenDatabase, object_file::ObjectFile, ModuleGroupId};
+
+mod llvm;
+
+/// The backend that generates machine code for the assemblies of a package.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Generates optimized machine code using LLVM.
+    #[default]
+    Llvm,
+
+    /// Generates unoptimized machine code using Cranelift, which trades
+    /// runtime performance for shorter compile times during hot reloading.
+    Cranelift,
+}
+
+impl Backend {
+    /// Returns whether this build of the compiler is able to generate code
+    /// with the backend.
+    pub fn is_available(self) -> bool {
+        self.code_generator().is_some()
+    }
+
+    /// Returns the code generator that implements the backend, or `None` if
+    /// the backend is not available in this build of the compiler.
+    pub(crate) fn code_generator(self) -> Option<&'static dyn CodeGenerator> {
+        match self {
+            Backend::Llvm => Some(&LlvmBackend),
+            Backend::Cranelift => None,
+        }
+    }
+}
+
+/// Generates machine code for the module groups of a package.
+///
+/// A code generator only lowers HIR to machine code. The steps that follow,
+/// like linking the object file into a munlib and signing it, are shared by all
+/// backends.
+pub(crate) trait CodeGenerator {
+    /// Generates an object file with the machine code of `module_group`.
+    fn generate_object_file(
+        &self,
+        db: &dyn CodeGenDatabase,
+        module_group: ModuleGroupId,
+    ) -> Result<ObjectFile, anyhow::Error>;
+
+    /// Writes the intermediate representation of `module_group` in a human
+    /// readable form to `output_path`.
+    fn write_ir(
+        &self,
+        db: &dyn CodeGenDatabase,
+        module_group: ModuleGroupId,
+        output_path: &Path,
+    ) -> Result<(), anyhow::Error>;
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Backend::Llvm => "LLVM",
+            Backend::Cranelift => "Cranelift",
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use anyhow::anyhow;
+use inkwell::{context::Context, targets::FileType};
+
+use super::CodeGenerator;
+use crate::{
+    code_gen::{AssemblyBuilder, CodeGenContext, CodeGenerationError},
+    db::CodeGenDatabase,
+    object_file::ObjectFile,
+    ModuleGroupId,
+};
+
+/// Generates optimized machine code using LLVM, through inkwell.
+pub(crate) struct LlvmBackend;
+
+impl CodeGenerator for LlvmBackend {
+    fn generate_object_file(
+        &self,
+        db: &dyn CodeGenDatabase,
+        module_group: ModuleGroupId,
+    ) -> Result<ObjectFile, anyhow::Error> {
+        let inkwell_context = Context::create();
+        let code_gen = CodeGenContext::new(&inkwell_context, db);
+        let assembly = build_assembly(db, &code_gen, module_group);
+
+        let obj = code_gen
+            .target_machine
+            .write_to_memory_buffer(&assembly.module, FileType::Object)
+            .map_err(|e| CodeGenerationError::MachineCodeError(e.to_string()))?;
+        ObjectFile::new(&db.target(), obj.as_slice())
+    }
+
+    fn write_ir(
+        &self,
+        db: &dyn CodeGenDatabase,
+        module_group: ModuleGroupId,
+        output_path: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let inkwell_context = Context::create();
+        let code_gen = CodeGenContext::new(&inkwell_context, db);
+        let assembly = build_assembly(db, &code_gen, module_group);
+
+        assembly
+            .module
+            .print_to_file(output_path)
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// An `Assembly` is a successfully linked LLVM module of code from one or more
+/// files.
+pub struct Assembly<'db, 'ink, 'ctx> {
+    _code_gen: &'ctx CodeGenContext<'db, 'ink>,
+    module: inkwell::module::Module<'ink>,
+}
+
+impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
+    /// Constructs an assembly
+    pub fn new(
+        code_gen: &'ctx CodeGenContext<'db, 'ink>,
+        module: inkwell::module::Module<'ink>,
+    ) -> Self {
+        Self {
+            _code_gen: code_gen,
+            module,
+        }
+    }
+}
+
+/// Builds an assembly for the specified module group
+fn build_assembly<'db, 'ink, 'ctx>(
+    db: &'db dyn CodeGenDatabase,
+    code_gen: &'ctx CodeGenContext<'db, 'ink>,
+    module_group_id: ModuleGroupId,
+) -> Assembly<'db, 'ink, 'ctx> {
+    let module_partition = db.module_partition();
+
+    let module_builder = AssemblyBuilder::new(code_gen, &module_partition, module_group_id);
+    module_builder.build().expect("unable to create assembly")
+}
@@ -6,12 +6,10 @@ use inkwell::{
     passes::{PassManager, PassManagerBuilder},
     OptimizationLevel,
 };
-pub(crate) use object_file::ObjectFile;
 
 mod assembly_builder;
 mod context;
 mod error;
-mod object_file;
 pub mod symbols;
 
 /// Optimizes the specified LLVM `Module` using the default passes for the given
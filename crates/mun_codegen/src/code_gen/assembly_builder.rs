@@ -1,12 +1,13 @@
+use std::collections::BTreeSet;
+
 use inkwell::module::{Linkage, Module};
-use rustc_hash::FxHashSet;
 
 use crate::{
-    assembly::Assembly,
+    backend::Assembly,
     code_gen::{optimize_module, symbols, CodeGenContext, CodeGenerationError},
     ir::{file::gen_file_ir, file_group::gen_file_group_ir},
     value::{IrTypeContext, IrValueContext},
-    ModuleGroupId, ModulePartition,
+    ModuleGroup, ModuleGroupId, ModulePartition,
 };
 
 /// A struct that can be used to build an `Assembly<'db, 'ink', ctx>`
@@ -75,7 +76,8 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             module: &self.assembly_module,
         };
 
-        // Build the set of dependencies
+        // Build the set of dependencies, together with the version of the package that
+        // each dependency is part of
         let direct_children = module_group
             .iter()
             .flat_map(|module| module.children(self.code_gen.db))
@@ -84,13 +86,15 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             .referenced_modules
             .union(&direct_children)
             .filter_map(|&module| self.module_group_partition.group_for_module(module))
-            .collect::<FxHashSet<_>>()
-            .into_iter()
             .map(|group_id| {
-                self.module_group_partition[group_id]
-                    .relative_file_path()
-                    .to_string()
+                let dependency = &self.module_group_partition[group_id];
+                (
+                    module_group.relative_path_to(dependency).to_string(),
+                    package_version(self.code_gen.db, dependency),
+                )
             })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
             .collect();
 
         // Generate the `get_info` method.
@@ -98,8 +102,13 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             self.code_gen.db,
             &value_context,
             &module_group.name,
+            package_version(self.code_gen.db, module_group),
             &file.function_definitions,
             &file.type_definitions,
+            &file.static_definitions,
+            file.init_fn,
+            file.shutdown_fn,
+            &file.tests,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             &self.code_gen.hir_types,
@@ -116,3 +125,13 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
         Ok(Assembly::new(self.code_gen, self.assembly_module))
     }
 }
+
+/// Returns the version of the package that contains the modules of the
+/// specified module group, if the package has a version.
+fn package_version(db: &dyn mun_hir::HirDatabase, module_group: &ModuleGroup) -> Option<String> {
+    module_group
+        .iter()
+        .next()
+        .and_then(|module| module.package().version(db))
+        .map(|version| version.to_string())
+}
@@ -20,6 +20,15 @@ pub struct CodeGenContext<'db, 'ink> {
     /// The optimization level
     pub optimization_level: inkwell::OptimizationLevel,
 
+    /// Whether integer arithmetic that overflows panics
+    pub overflow_checks: bool,
+
+    /// Whether debug information is generated
+    pub debug_info: bool,
+
+    /// Whether trampolines are generated for public functions
+    pub dynamic_invocation: bool,
+
     /// The target to generate code for
     pub target_machine: Rc<TargetMachine>,
 }
@@ -34,6 +43,9 @@ impl<'db, 'ink> CodeGenContext<'db, 'ink> {
             rust_types: RefCell::new(HashMap::default()),
             hir_types: HirTypeCache::new(context, db.upcast(), target_machine.get_target_data()),
             optimization_level: db.optimization_level(),
+            overflow_checks: db.overflow_checks(),
+            debug_info: db.debug_info(),
+            dynamic_invocation: db.dynamic_invocation(),
             target_machine,
             db: db.upcast(),
         }
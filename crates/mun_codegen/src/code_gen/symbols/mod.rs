@@ -4,11 +4,12 @@ use inkwell::{attributes::Attribute, module::Linkage, types::AnyType};
 use ir_type_builder::TypeIdBuilder;
 use itertools::Itertools;
 use mun_abi as abi;
-use mun_hir::{HirDatabase, TyKind};
+use mun_hir::{HasVisibility, HirDatabase, TyKind};
 
 use crate::{
     ir::{
         dispatch_table::{DispatchTable, DispatchableFunction},
+        file::static_global_name,
         function,
         ty::{guid_from_struct, HirTypeCache},
         type_table::TypeTable,
@@ -30,7 +31,7 @@ fn gen_prototype_from_function<'ink>(
     hir_types: &HirTypeCache<'_, 'ink>,
     ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
 ) -> ir::FunctionPrototype<'ink> {
-    let name = function.full_name(db);
+    let name = function.export_name(db);
 
     // Internalize the name of the function prototype
     let name_str = CString::new(name.clone())
@@ -121,21 +122,26 @@ fn get_type_definition_array<'ink>(
             TyKind::Struct(s) => {
                 let inkwell_type = hir_types.get_struct_type(*s);
                 let struct_name = s.full_name(db);
+
+                // An `#[align(N)]` attribute can raise the alignment of a struct above the
+                // alignment of its fields. The size is padded to a multiple of the alignment.
+                let target_data = &context.type_context.target_data;
+                let alignment = target_data
+                    .get_abi_alignment(&inkwell_type)
+                    .max(s.data(db.upcast()).attrs().align().unwrap_or(1));
+                let size_in_bits = target_data
+                    .get_bit_size(&inkwell_type)
+                    .next_multiple_of(u64::from(alignment) * 8);
+
                 ir::TypeDefinition {
                     name: CString::new(struct_name.clone())
                         .expect("typename is not a valid CString")
                         .intern(format!("type_info::<{struct_name}>::name"), context)
                         .as_value(context),
-                    size_in_bits: context
-                        .type_context
-                        .target_data
-                        .get_bit_size(&inkwell_type)
+                    size_in_bits: size_in_bits
                         .try_into()
                         .expect("could not convert size in bits to smaller size"),
-                    alignment: context
-                        .type_context
-                        .target_data
-                        .get_abi_alignment(&inkwell_type)
+                    alignment: alignment
                         .try_into()
                         .expect("could not convert alignment to smaller size"),
                     data: ir::TypeDefinitionData::Struct(gen_struct_info(
@@ -152,6 +158,22 @@ fn get_type_definition_array<'ink>(
         .into_const_private_pointer_or_null("fn.get_info.types", context)
 }
 
+/// Interns the documentation of an item as a global string, or returns a null
+/// pointer if the item is not documented.
+fn gen_docs<'ink>(
+    docs: Option<String>,
+    global_name: String,
+    context: &IrValueContext<'ink, '_, '_>,
+) -> Value<'ink, *const u8> {
+    match docs {
+        Some(docs) => CString::new(docs.replace('\0', ""))
+            .expect("docs are not a valid CString")
+            .intern(global_name, context)
+            .as_value(context),
+        None => Value::null(context),
+    }
+}
+
 fn gen_struct_info<'ink>(
     db: &dyn HirDatabase,
     hir_struct: mun_hir::Struct,
@@ -205,6 +227,11 @@ fn gen_struct_info<'ink>(
         field_names,
         field_types,
         field_offsets,
+        docs: gen_docs(
+            hir_struct.docs(db),
+            format!("struct_info::<{name}>::docs"),
+            context,
+        ),
         num_fields: fields
             .len()
             .try_into()
@@ -238,6 +265,20 @@ fn get_function_definition_array<'ink, 'a>(
                 .unwrap();
             value.set_linkage(Linkage::Private);
 
+            // Only functions that are exposed to the host have a trampoline
+            let trampoline_ptr = module
+                .get_function(&format!("{name}_trampoline"))
+                .map_or_else(
+                    || Value::null(context),
+                    |trampoline| {
+                        trampoline.set_linkage(Linkage::Private);
+                        Value::<*const fn()>::with_cast(
+                            trampoline.as_global_value().as_pointer_value(),
+                            context,
+                        )
+                    },
+                );
+
             // Generate the signature from the function
             let prototype =
                 gen_prototype_from_function(db, context, *f, hir_types, ir_type_builder);
@@ -247,12 +288,62 @@ fn get_function_definition_array<'ink, 'a>(
                     value.as_global_value().as_pointer_value(),
                     context,
                 ),
+                trampoline_ptr,
+                docs: gen_docs(
+                    f.docs(db),
+                    format!("fn_def::<{}>::docs", f.full_name(db)),
+                    context,
+                ),
+                // Functions that are not `pub` are only exported to link other assemblies
+                // of the package, they are hidden from the host.
+                privacy: if f.visibility(db).is_externally_visible() {
+                    abi::Privacy::Public
+                } else {
+                    abi::Privacy::Private
+                },
             }
         })
         .into_value(context)
         .into_const_private_global("fn.get_info.functions", context)
 }
 
+/// Construct a global that holds a reference to all statics. e.g.:
+/// `MunGlobalDefinition[] definitions = { ... }`
+fn get_global_definition_array<'ink, 'a>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    statics: impl Iterator<Item = &'a mun_hir::Static>,
+    hir_types: &HirTypeCache<'_, 'ink>,
+    ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
+) -> Value<'ink, *const ir::GlobalDefinition<'ink>> {
+    let module = context.module;
+    statics
+        .sorted_by_cached_key(|s| s.full_name(db))
+        .map(|s| {
+            let name = s.full_name(db);
+
+            // Get the storage of the static from the cloned module and modify its linkage.
+            let value = module
+                .get_global(&static_global_name(db, *s))
+                .expect("missing storage for static");
+            value.set_linkage(Linkage::Private);
+
+            let name_str = CString::new(name.clone())
+                .expect("static name is not a valid CString")
+                .intern(format!("static::<{name}>::name"), context);
+
+            ir::GlobalDefinition {
+                name: name_str.as_value(context),
+                type_id: ir_type_builder.construct_from_type_id(&hir_types.type_id(&s.ty(db))),
+                value_ptr: Value::<*mut std::ffi::c_void>::with_cast(
+                    value.as_pointer_value(),
+                    context,
+                ),
+            }
+        })
+        .into_const_private_pointer_or_null("fn.get_info.globals", context)
+}
+
 /// Generate the type lookup table information. e.g.:
 /// ```c
 /// MunTypeLut typeLut = { ... }
@@ -353,13 +444,18 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     db: &'db dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     module_name: &str,
+    version: Option<String>,
     function_definitions: &HashSet<mun_hir::Function>,
     type_definitions: &HashSet<mun_hir::Ty>,
+    static_definitions: &HashSet<mun_hir::Static>,
+    init_fn: Option<mun_hir::Function>,
+    shutdown_fn: Option<mun_hir::Function>,
+    tests: &[mun_hir::Function],
     dispatch_table: &DispatchTable<'ink>,
     type_table: &TypeTable<'ink>,
     hir_types: &HirTypeCache<'db, 'ink>,
     optimization_level: inkwell::OptimizationLevel,
-    dependencies: Vec<String>,
+    dependencies: Vec<(String, Option<String>)>,
 ) {
     let ir_type_builder = TypeIdBuilder::new(context);
 
@@ -382,6 +478,15 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         &ir_type_builder,
     );
 
+    let num_globals = static_definitions.len() as u32;
+    let globals = get_global_definition_array(
+        db,
+        context,
+        static_definitions.iter(),
+        hir_types,
+        &ir_type_builder,
+    );
+
     // Construct the module info struct
     let module_info = ir::ModuleInfo {
         path: CString::new(module_name)
@@ -392,6 +497,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         num_functions,
         types,
         num_types,
+        globals,
+        num_globals,
     };
 
     // Construct the dispatch table struct
@@ -399,6 +506,13 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
 
     let type_lut = gen_type_lut(context, type_table, &ir_type_builder);
 
+    // Construct pointers to the lifecycle hooks, or null if they are not defined
+    let init_fn = gen_lifecycle_fn_ptr(db, context, init_fn);
+    let shutdown_fn = gen_lifecycle_fn_ptr(db, context, shutdown_fn);
+
+    let num_tests = tests.len() as u32;
+    let tests = get_test_definition_array(db, context, tests);
+
     // Construct the actual `get_info` function
     gen_get_info_fn(
         db,
@@ -407,13 +521,72 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         dispatch_table,
         type_lut,
         optimization_level,
+        version,
         dependencies,
+        init_fn,
+        shutdown_fn,
+        tests,
+        num_tests,
     );
     gen_set_allocator_handle_fn(context);
     gen_get_version_fn(context);
 }
 
+/// Returns a pointer to the specified lifecycle hook, or a null pointer if the
+/// hook is not defined.
+fn gen_lifecycle_fn_ptr<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    function: Option<mun_hir::Function>,
+) -> Value<'ink, *const fn()> {
+    let Some(function) = function else {
+        return Value::null(context);
+    };
+
+    let value = context
+        .module
+        .get_function(&function.name(db).to_string())
+        .expect("could not find lifecycle hook in module");
+
+    // Make sure the pointer doesn't resolve to a symbol with the same name in
+    // another library (e.g. `shutdown` in libc)
+    value.set_linkage(Linkage::Private);
+    Value::<*const fn()>::with_cast(value.as_global_value().as_pointer_value(), context)
+}
+
+/// Construct a global that holds a reference to all test functions. e.g.:
+/// `MunTestDefinition[] tests = { ... }`
+fn get_test_definition_array<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    tests: &[mun_hir::Function],
+) -> Value<'ink, *const ir::TestDefinition<'ink>> {
+    tests
+        .iter()
+        .map(|f| {
+            let name = f.full_name(db);
+            let name_str = CString::new(name.clone())
+                .expect("test name is not a valid CString")
+                .intern(format!("test::<{name}>::name"), context);
+
+            let value = context
+                .module
+                .get_function(&f.name(db).to_string())
+                .expect("could not find test function in module");
+
+            ir::TestDefinition {
+                name: name_str.as_value(context),
+                fn_ptr: Value::<*const fn()>::with_cast(
+                    value.as_global_value().as_pointer_value(),
+                    context,
+                ),
+            }
+        })
+        .into_const_private_pointer_or_null("fn.get_info.tests", context)
+}
+
 /// Construct the actual `get_info` function.
+#[allow(clippy::too_many_arguments)]
 fn gen_get_info_fn<'ink>(
     db: &dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
@@ -421,7 +594,12 @@ fn gen_get_info_fn<'ink>(
     dispatch_table: ir::DispatchTable<'ink>,
     type_lut: ir::TypeLut<'ink>,
     optimization_level: inkwell::OptimizationLevel,
-    dependencies: Vec<String>,
+    version: Option<String>,
+    dependencies: Vec<(String, Option<String>)>,
+    init_fn: Value<'ink, *const fn()>,
+    shutdown_fn: Value<'ink, *const fn()>,
+    tests: Value<'ink, *const ir::TestDefinition<'ink>>,
+    num_tests: u32,
 ) {
     let target = db.target();
 
@@ -490,6 +668,24 @@ fn gen_get_info_fn<'ink>(
     let num_dependencies_addr = builder
         .build_struct_gep(result_ptr, 9, "num_dependencies")
         .expect("could not retrieve `num_dependencies` from result struct");
+    let init_fn_addr = builder
+        .build_struct_gep(result_ptr, 11, "init_fn")
+        .expect("could not retrieve `init_fn` from result struct");
+    let shutdown_fn_addr = builder
+        .build_struct_gep(result_ptr, 13, "shutdown_fn")
+        .expect("could not retrieve `shutdown_fn` from result struct");
+    let tests_addr = builder
+        .build_struct_gep(result_ptr, 15, "tests")
+        .expect("could not retrieve `tests` from result struct");
+    let num_tests_addr = builder
+        .build_struct_gep(result_ptr, 17, "num_tests")
+        .expect("could not retrieve `num_tests` from result struct");
+    let version_addr = builder
+        .build_struct_gep(result_ptr, 19, "version")
+        .expect("could not retrieve `version` from result struct");
+    let dependency_versions_addr = builder
+        .build_struct_gep(result_ptr, 21, "dependency_versions")
+        .expect("could not retrieve `dependency_versions` from result struct");
 
     // Assign the struct values one by one.
     builder.build_store(symbols_addr, module_info.as_value(context).value);
@@ -500,7 +696,7 @@ fn gen_get_info_fn<'ink>(
         dependencies
             .iter()
             .enumerate()
-            .map(|(idx, name)| {
+            .map(|(idx, (name, _))| {
                 CString::new(name.as_str())
                     .expect("could not convert dependency name to string")
                     .intern(format!("dependency{idx}"), context)
@@ -518,6 +714,45 @@ fn gen_get_info_fn<'ink>(
             false,
         ),
     );
+    builder.build_store(init_fn_addr, init_fn.value);
+    builder.build_store(shutdown_fn_addr, shutdown_fn.value);
+    builder.build_store(tests_addr, tests.value);
+    builder.build_store(
+        num_tests_addr,
+        context
+            .context
+            .i32_type()
+            .const_int(num_tests.into(), false),
+    );
+    builder.build_store(
+        version_addr,
+        version
+            .map_or(Value::null(context), |version| {
+                CString::new(version)
+                    .expect("could not convert version to string")
+                    .intern("module_info::version", context)
+                    .as_value(context)
+            })
+            .value,
+    );
+    // A dependency is compatible with the assembly if its version is semver compatible
+    // with the version it had when the assembly was compiled
+    builder.build_store(
+        dependency_versions_addr,
+        dependencies
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, version))| {
+                version.as_ref().map_or(Value::null(context), |version| {
+                    CString::new(format!("^{version}"))
+                        .expect("could not convert dependency version requirement to string")
+                        .intern(format!("dependency_version{idx}"), context)
+                        .as_value(context)
+                })
+            })
+            .into_const_private_pointer_or_null("dependency_versions", context)
+            .value,
+    );
 
     // Construct the return statement of the function.
     if target.options.is_like_windows {
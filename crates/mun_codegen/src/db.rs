@@ -3,7 +3,7 @@ use std::{rc::Rc, sync::Arc};
 use by_address::ByAddress;
 use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetTriple};
 
-use crate::{AssemblyIr, ModuleGroupId, ModulePartition, TargetAssembly};
+use crate::{AssemblyIr, Backend, ModuleGroupId, ModulePartition, TargetAssembly};
 
 /// The `CodeGenDatabase` enables caching of code generation stages.
 /// Inkwell/LLVM objects are not stored in the cache because they are not
@@ -19,6 +19,25 @@ pub trait CodeGenDatabase: mun_hir::HirDatabase + mun_db::Upcast<dyn mun_hir::Hi
     #[salsa::input]
     fn optimization_level(&self) -> inkwell::OptimizationLevel;
 
+    /// Set whether integer arithmetic that overflows panics instead of
+    /// wrapping around
+    #[salsa::input]
+    fn overflow_checks(&self) -> bool;
+
+    /// Set whether debug information is generated, which enables native
+    /// debuggers to step through Mun source code
+    #[salsa::input]
+    fn debug_info(&self) -> bool;
+
+    /// Set whether a trampoline is generated for every public function, which
+    /// enables the runtime to invoke the function with dynamic values
+    #[salsa::input]
+    fn dynamic_invocation(&self) -> bool;
+
+    /// Set the backend that is used to generate machine code
+    #[salsa::input]
+    fn backend(&self) -> Backend;
+
     /// Returns the current module partition
     #[salsa::invoke(crate::module_partition::build_partition)]
     fn module_partition(&self) -> Arc<ModulePartition>;
@@ -26,4 +26,11 @@ intrinsics! {
     ///
     /// Note that the elements in the array are left uninitialized.
     pub fn new_array(type_handle: *const ffi::c_void, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Aborts the execution of the current Mun function with the specified null-terminated
+    /// `message`. The `file`, `line`, and `column` describe the location in the source code
+    /// that panicked.
+    ///
+    /// This function never returns; the runtime unwinds the stack to the host.
+    pub fn panic(message: *const u8, file: *const u8, line: u32, column: u32) -> ();
 }
@@ -65,6 +65,18 @@ impl<'ink> RuntimeArrayValue<'ink> {
             .expect("could not get `length` from array struct")
     }
 
+    /// Generate code to fetch the length of the array.
+    pub fn get_length(&self, builder: &Builder<'ink>) -> IntValue<'ink> {
+        let array_ptr = self.get_array_ptr(builder);
+        let value_name = array_ptr.get_name().to_string_lossy();
+        let length_ptr = builder
+            .build_struct_gep(array_ptr, 0, &format!("{}->length", &value_name))
+            .expect("could not get `length` from array struct");
+        builder
+            .build_load(length_ptr, &format!("{}.length", &value_name))
+            .into_int_value()
+    }
+
     /// Generate code to fetch the capacity of the array.
     pub fn get_capacity(&self, builder: &Builder<'ink>) -> IntValue<'ink> {
         let array_ptr = self.get_array_ptr(builder);
@@ -87,7 +99,7 @@ impl<'ink> RuntimeArrayValue<'ink> {
     }
 
     /// Returns the type of the `length` field
-    pub fn length_ty(&self) -> IntType<'_> {
+    pub fn length_ty(&self) -> IntType<'ink> {
         self.array_data_ty()
             .get_field_type_at_index(0)
             .expect("an array must have a second field")
@@ -95,7 +107,7 @@ impl<'ink> RuntimeArrayValue<'ink> {
     }
 
     /// Returns the type of the `length` field
-    pub fn capacity_ty(&self) -> IntType<'_> {
+    pub fn capacity_ty(&self) -> IntType<'ink> {
         self.array_data_ty()
             .get_field_type_at_index(1)
             .expect("an array must have a second field")
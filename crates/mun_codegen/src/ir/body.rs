@@ -1,9 +1,15 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
+    intrinsics::Intrinsic,
+    module::Module,
+    types::{BasicType, BasicTypeEnum},
     values::{
         AggregateValueEnum, BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, FloatValue,
         FunctionValue, GlobalValue, IntValue, PointerValue, StructValue,
@@ -12,16 +18,19 @@ use inkwell::{
 };
 use mun_abi as abi;
 use mun_hir::{
-    ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, HirDatabase, HirDisplay, InferenceResult,
-    Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness, Resolver, Statement,
-    TyKind, UnaryOp, ValueNs,
+    ArithOp, BinaryOp, Body, BuiltinFunction, CmpOp, ConstValue, Expr, ExprId, HirDatabase,
+    HirDisplay, InferenceResult, Literal, LogicOp, Name, Ordering, Pat, PatId, Path, RangeOp,
+    ResolveBitness, Resolver, Statement, TyKind, UnaryOp, ValueNs,
 };
 
 use crate::{
     intrinsics,
     ir::{
-        dispatch_table::DispatchTable, ty::HirTypeCache, type_table::TypeTable, RuntimeArrayValue,
-        RuntimeReferenceValue,
+        debug_info::{DebugInfo, FunctionDebugInfo},
+        dispatch_table::DispatchTable,
+        ty::HirTypeCache,
+        type_table::TypeTable,
+        RuntimeArrayValue, RuntimeReferenceValue,
     },
     module_group::ModuleGroup,
     value::Global,
@@ -30,8 +39,10 @@ use crate::{
 type BreakSources<'ink> = Vec<Option<(BasicValueEnum<'ink>, BasicBlock<'ink>)>>;
 
 struct LoopInfo<'ink> {
+    label: Option<Name>,
     break_values: BreakSources<'ink>,
     exit_block: BasicBlock<'ink>,
+    continue_block: BasicBlock<'ink>,
 }
 
 #[derive(Clone)]
@@ -39,6 +50,7 @@ pub(crate) struct ExternalGlobals<'ink> {
     pub alloc_handle: Option<GlobalValue<'ink>>,
     pub dispatch_table: Option<GlobalValue<'ink>>,
     pub type_table: Option<Global<'ink, [*const std::ffi::c_void]>>,
+    pub statics: HashMap<mun_hir::Static, GlobalValue<'ink>>,
 }
 
 pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
@@ -55,10 +67,22 @@ pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     dispatch_table: &'t DispatchTable<'ink>,
     type_table: &'t TypeTable<'ink>,
     hir_types: &'t HirTypeCache<'db, 'ink>,
-    active_loop: Option<LoopInfo<'ink>>,
+    /// The loops that are currently being generated, from the outermost to the
+    /// innermost loop
+    active_loops: Vec<LoopInfo<'ink>>,
     hir_function: mun_hir::Function,
     external_globals: ExternalGlobals<'ink>,
     module_group: &'t ModuleGroup,
+    module: &'t Module<'ink>,
+    overflow_checks: bool,
+    /// The self-recursive calls in tail position, which are lowered to a jump
+    tail_calls: HashSet<ExprId>,
+    /// The block to jump to for a tail call, and the slots that store the
+    /// parameters of the function
+    tail_call_target: Option<(BasicBlock<'ink>, Vec<PointerValue<'ink>>)>,
+    /// The debug information of the function, if debug information is
+    /// generated
+    debug_info: Option<FunctionDebugInfo<'t, 'db, 'ink>>,
 }
 
 impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
@@ -73,6 +97,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         external_globals: ExternalGlobals<'ink>,
         hir_types: &'t HirTypeCache<'db, 'ink>,
         module_group: &'t ModuleGroup,
+        module: &'t Module<'ink>,
+        overflow_checks: bool,
+        debug_info: Option<&'t DebugInfo<'db, 'ink>>,
     ) -> Self {
         let (hir_function, ir_function) = function;
 
@@ -85,6 +112,14 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let body_ir = context.append_basic_block(ir_function, "body");
         builder.position_at_end(body_ir);
 
+        // Attribute the instructions that are not part of a statement, e.g. the
+        // prologue of the function, to the start of its body
+        let debug_info = debug_info.map(|debug_info| {
+            let debug_info = debug_info.gen_function(hir_function, ir_function);
+            debug_info.set_expr_location(&builder, body.body_expr());
+            debug_info
+        });
+
         BodyIrGenerator {
             context,
             db,
@@ -98,11 +133,16 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             function_map,
             dispatch_table,
             type_table,
-            active_loop: None,
+            active_loops: Vec::new(),
             hir_function,
             external_globals,
             hir_types,
             module_group,
+            module,
+            overflow_checks,
+            tail_calls: HashSet::new(),
+            tail_call_target: None,
+            debug_info,
         }
     }
 
@@ -110,19 +150,32 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     pub fn gen_fn_body(&mut self) {
         // Iterate over all parameters and their type and store them so we can reference
         // them later in code.
-        for (i, (pat, _ty)) in self.body.params().iter().enumerate() {
-            let body = self.body.clone(); // Avoid borrow issues
-
+        let body = self.body.clone(); // Avoid borrow issues
+        let params = self.gen_tail_call_target();
+        for (idx, ((pat, _ty), param)) in body.params().iter().zip(params).enumerate() {
             match &body[*pat] {
-                Pat::Bind { name } => {
+                Pat::Bind { name, .. } => {
                     let name = name.to_string();
-                    let param = self.fn_value.get_nth_param(i as u32).unwrap();
                     let builder = self.new_alloca_builder();
                     let param_ptr = builder.build_alloca(param.get_type(), &name);
-                    builder.build_store(param_ptr, param);
+                    self.builder.build_store(param_ptr, param);
+                    if let Some(debug_info) = &self.debug_info {
+                        debug_info.declare_variable(
+                            &self.builder,
+                            *pat,
+                            &name,
+                            &self.infer[*pat],
+                            param.get_type(),
+                            param_ptr,
+                            Some(idx as u32 + 1),
+                        );
+                    }
                     self.pat_to_local.insert(*pat, param_ptr);
                     self.pat_to_name.insert(*pat, name);
                 }
+                Pat::Record { .. } | Pat::TupleStruct { .. } | Pat::Tuple { .. } => {
+                    self.gen_pat_binding(*pat, Some(param));
+                }
                 Pat::Wild => {
                     // Wildcard patterns cannot be referenced from code. So
                     // nothing to do.
@@ -159,14 +212,77 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Lowers self-recursive calls in tail position to a loop, if the function
+    /// contains any. The arguments of a tail call are stored in the parameter
+    /// slots, after which the function jumps back to the start of its body.
+    ///
+    /// Returns the values of the parameters of the function.
+    fn gen_tail_call_target(&mut self) -> Vec<BasicValueEnum<'ink>> {
+        self.tail_calls = tail_calls(&self.body, &self.infer, self.hir_function);
+        if self.tail_calls.is_empty() {
+            return self.fn_value.get_param_iter().collect();
+        }
+
+        let builder = self.new_alloca_builder();
+        let param_slots: Vec<_> = self
+            .fn_value
+            .get_param_iter()
+            .map(|param| {
+                let slot = builder.build_alloca(param.get_type(), "param");
+                self.builder.build_store(slot, param);
+                slot
+            })
+            .collect();
+
+        let tail_call_block = self.context.append_basic_block(self.fn_value, "tail_call");
+        self.builder.build_unconditional_branch(tail_call_block);
+        self.builder.position_at_end(tail_call_block);
+
+        let params = param_slots
+            .iter()
+            .map(|slot| self.builder.build_load(*slot, "param"))
+            .collect();
+        self.tail_call_target = Some((tail_call_block, param_slots));
+        params
+    }
+
+    /// Generates IR for a self-recursive call in tail position, by storing the
+    /// `args` in the parameter slots and jumping back to the start of the
+    /// function.
+    fn gen_tail_call(&mut self, args: &[BasicValueEnum<'ink>]) -> Option<BasicValueEnum<'ink>> {
+        let (tail_call_block, param_slots) = self
+            .tail_call_target
+            .as_ref()
+            .expect("a tail call requires a tail call target");
+        for (slot, arg) in param_slots.iter().zip(args) {
+            self.builder.build_store(*slot, *arg);
+        }
+        self.builder.build_unconditional_branch(*tail_call_block);
+        None
+    }
+
     pub fn gen_fn_wrapper(&mut self) {
         let fn_sig = self.hir_function.ty(self.db).callable_sig(self.db).unwrap();
+
+        // A value struct is returned through a pointer to a buffer provided by the
+        // caller, which is passed as the first parameter.
+        let return_slot = fn_sig.returns_value_struct(self.db).then(|| {
+            self.fn_value
+                .get_first_param()
+                .expect("missing return slot parameter")
+                .into_pointer_value()
+        });
+        let param_offset = u32::from(return_slot.is_some());
+
         let args: Vec<BasicMetadataValueEnum<'_>> = fn_sig
             .params()
             .iter()
             .enumerate()
             .map(|(idx, ty)| {
-                let param = self.fn_value.get_nth_param(idx as u32).unwrap();
+                let param = self
+                    .fn_value
+                    .get_nth_param(idx as u32 + param_offset)
+                    .unwrap();
                 if let Some(s) = ty.as_struct() {
                     if s.data(self.db.upcast()).memory_kind == abi::StructMemoryKind::Value {
                         deref_heap_value(&self.builder, param)
@@ -198,22 +314,84 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             if fn_ret_type.is_empty() {
                 self.builder.build_return(None);
             } else if let Some(value) = ret_value {
-                let ret_value = if let Some(hir_struct) = fn_ret_type.as_struct() {
-                    if hir_struct.data(self.db.upcast()).memory_kind
-                        == mun_hir::StructMemoryKind::Value
-                    {
-                        self.gen_struct_alloc_on_heap(hir_struct, value.into_struct_value())
-                    } else {
-                        value
-                    }
+                if let Some(return_slot) = return_slot {
+                    self.builder.build_store(return_slot, value);
+                    self.builder.build_return(None);
                 } else {
-                    value
-                };
-                self.builder.build_return(Some(&ret_value));
+                    self.builder.build_return(Some(&value));
+                }
             }
         }
     }
 
+    /// Generates the body of a trampoline, which calls `target` with the
+    /// arguments that are read from the array of pointers passed as its first
+    /// parameter, and writes the return value to the pointer passed as its
+    /// second parameter. `target` is the function that is exposed to the host,
+    /// i.e. either the function itself or its wrapper.
+    pub fn gen_fn_trampoline(&mut self, target: FunctionValue<'ink>) {
+        let args_ptr = self
+            .fn_value
+            .get_nth_param(0)
+            .expect("missing arguments parameter")
+            .into_pointer_value();
+        let ret_ptr = self
+            .fn_value
+            .get_nth_param(1)
+            .expect("missing return value parameter")
+            .into_pointer_value();
+
+        // A value struct is written to the return slot of the target function, which
+        // is passed as its first parameter.
+        let fn_sig = self.hir_function.ty(self.db).callable_sig(self.db).unwrap();
+        let param_offset = usize::from(fn_sig.returns_value_struct(self.db));
+
+        let mut args: Vec<BasicMetadataValueEnum<'ink>> = Vec::new();
+        for (idx, param) in target.get_param_iter().enumerate() {
+            if idx < param_offset {
+                let return_slot_type = param.get_type().into_pointer_type();
+                args.push(
+                    self.builder
+                        .build_pointer_cast(ret_ptr, return_slot_type, "return_slot")
+                        .into(),
+                );
+                continue;
+            }
+
+            let index = self
+                .context
+                .i64_type()
+                .const_int((idx - param_offset) as u64, false);
+            // Safety: the caller passes a pointer to every argument
+            let arg_ptr_ptr = unsafe { self.builder.build_gep(args_ptr, &[index], "arg_ptr") };
+            let arg_ptr = self
+                .builder
+                .build_load(arg_ptr_ptr, "arg_ptr")
+                .into_pointer_value();
+            let arg_ptr = self.builder.build_pointer_cast(
+                arg_ptr,
+                param.get_type().ptr_type(AddressSpace::default()),
+                "arg_ptr",
+            );
+            args.push(self.builder.build_load(arg_ptr, "arg").into());
+        }
+
+        let ret_value = self
+            .builder
+            .build_call(target, &args, "ret")
+            .try_as_basic_value()
+            .left();
+        if let Some(value) = ret_value {
+            let ret_ptr = self.builder.build_pointer_cast(
+                ret_ptr,
+                value.get_type().ptr_type(AddressSpace::default()),
+                "ret_ptr",
+            );
+            self.builder.build_store(ret_ptr, value);
+        }
+        self.builder.build_return(None);
+    }
+
     /// Generates IR for the specified expression. Dependending on the type of
     /// expression an IR value is returned.
     fn gen_expr(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum<'ink>> {
@@ -241,15 +419,26 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 ref callee,
                 ref args,
             } => {
+                if let Some(builtin) = self.infer.builtin_call(expr) {
+                    return self.gen_builtin_call(expr, builtin, args);
+                }
+
                 // Get the callable definition from the map
                 match self.infer[*callee].as_callable_def() {
                     Some(mun_hir::CallableDef::Function(def)) => {
                         // Get all the arguments
-                        let args: Vec<BasicMetadataValueEnum<'_>> = args
+                        let args: Vec<BasicValueEnum<'_>> = args
                             .iter()
-                            .map(|expr| self.gen_expr(*expr).expect("expected a value").into())
+                            .map(|expr| self.gen_expr(*expr).expect("expected a value"))
                             .collect();
 
+                        if self.tail_calls.contains(&expr) {
+                            return self.gen_tail_call(&args);
+                        }
+
+                        let args: Vec<BasicMetadataValueEnum<'_>> =
+                            args.into_iter().map(Into::into).collect();
+
                         self.gen_call(def, &args)
                             .try_as_basic_value()
                             .left()
@@ -277,15 +466,33 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 else_branch,
             } => self.gen_if(expr, *condition, *then_branch, *else_branch),
             Expr::Return { expr: ret_expr } => self.gen_return(expr, *ret_expr),
-            Expr::Loop { body } => self.gen_loop(expr, *body),
-            Expr::While { condition, body } => self.gen_while(expr, *condition, *body),
-            Expr::Break { expr: break_expr } => self.gen_break(expr, *break_expr),
+            Expr::Loop { body, label } => self.gen_loop(expr, *body, label.clone()),
+            Expr::While {
+                condition,
+                body,
+                label,
+            } => self.gen_while(expr, *condition, *body, label.clone()),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            } => self.gen_for(expr, *pat, *iterable, *body, label.clone()),
+            Expr::Break {
+                expr: break_expr,
+                label,
+            } => self.gen_break(expr, *break_expr, label.as_ref()),
+            Expr::Continue { label } => self.gen_continue(label.as_ref()),
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_field(expr, *receiver_expr, name),
             Expr::Array(exprs) => self.gen_array(expr, exprs).map(Into::into),
+            Expr::Tuple(exprs) => self.gen_tuple(expr, exprs),
             Expr::Index { base, index } => self.gen_index(expr, *base, *index),
+            Expr::Range { .. } => {
+                unreachable!("range expressions can only occur as the iterable of a `for` loop")
+            }
             Expr::Missing => unimplemented!("unimplemented expr type {:?}", &body[expr]),
         }
     }
@@ -474,6 +681,29 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         self.gen_struct_alloc(hir_struct, args)
     }
 
+    /// Generates IR for a tuple, e.g. `(1.23, 4)`. Tuples are stored as
+    /// anonymous value structs.
+    fn gen_tuple(&mut self, tgt_expr: ExprId, exprs: &[ExprId]) -> Option<BasicValueEnum<'ink>> {
+        let elems = exprs
+            .iter()
+            .map(|expr| self.gen_expr(*expr))
+            .collect::<Option<Vec<_>>>()?;
+
+        let tuple_ty = self
+            .hir_types
+            .get_basic_type(&self.infer[tgt_expr])
+            .expect("expected a basic type")
+            .into_struct_type();
+        let mut value: AggregateValueEnum<'_> = tuple_ty.get_undef().into();
+        for (i, elem) in elems.into_iter().enumerate() {
+            value = self
+                .builder
+                .build_insert_value(value, elem, i as u32, "init")
+                .expect("Failed to initialize tuple element.");
+        }
+        Some(value.into_struct_value().into())
+    }
+
     /// Generates IR for a unit struct literal, e.g `Foo`
     fn gen_unit_struct_lit(&mut self, type_expr: ExprId) -> BasicValueEnum<'ink> {
         let struct_ty = self.infer[type_expr].clone();
@@ -489,6 +719,13 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         tail: Option<ExprId>,
     ) -> Option<BasicValueEnum<'ink>> {
         for statement in statements.iter() {
+            if let Some(debug_info) = &self.debug_info {
+                match statement {
+                    Statement::Let { pat, .. } => debug_info.set_pat_location(&self.builder, *pat),
+                    Statement::Expr(expr) => debug_info.set_expr_location(&self.builder, *expr),
+                }
+            }
+
             match statement {
                 Statement::Let {
                     pat, initializer, ..
@@ -506,6 +743,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
 
         if let Some(tail) = tail {
+            if let Some(debug_info) = &self.debug_info {
+                debug_info.set_expr_location(&self.builder, tail);
+            }
             self.gen_expr(tail)
         } else {
             Some(self.gen_empty())
@@ -542,8 +782,17 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             None => None,
         };
 
-        match &self.body[pat] {
-            Pat::Bind { name } => {
+        self.gen_pat_binding(pat, initializer);
+        true
+    }
+
+    /// Generates IR to bind the specified pattern to a local variable and
+    /// optionally initialize it with `value`. Struct patterns bind each of
+    /// their fields to the respective sub-pattern.
+    fn gen_pat_binding(&mut self, pat: PatId, value: Option<BasicValueEnum<'ink>>) {
+        let body = self.body.clone(); // Avoid borrow issues
+        match &body[pat] {
+            Pat::Bind { name, .. } => {
                 let builder = self.new_alloca_builder();
                 let pat_ty = self.infer[pat].clone();
                 let ty = self
@@ -551,18 +800,88 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     .get_basic_type(&pat_ty)
                     .expect("expected basic type");
                 let ptr = builder.build_alloca(ty, &name.to_string());
+                if let Some(debug_info) = &self.debug_info {
+                    debug_info.declare_variable(
+                        &self.builder,
+                        pat,
+                        &name.to_string(),
+                        &pat_ty,
+                        ty,
+                        ptr,
+                        None,
+                    );
+                }
                 self.pat_to_local.insert(pat, ptr);
                 self.pat_to_name.insert(pat, name.to_string());
                 if !(pat_ty.is_empty() || pat_ty.is_never()) {
-                    if let Some(value) = initializer {
+                    if let Some(value) = value {
                         self.builder.build_store(ptr, value);
                     };
                 }
             }
+            Pat::Record { args, .. } => {
+                let hir_struct = self.infer[pat].as_struct().expect("expected a struct");
+                for field in args {
+                    let hir_field = hir_struct
+                        .field(self.db, &field.name)
+                        .expect("expected a struct field");
+                    let field_value = value.map(|value| self.gen_pat_field(pat, value, hir_field));
+                    self.gen_pat_binding(field.pat, field_value);
+                }
+            }
+            Pat::TupleStruct { args, .. } => {
+                let hir_struct = self.infer[pat].as_struct().expect("expected a struct");
+                for (arg, hir_field) in args.iter().zip(hir_struct.fields(self.db)) {
+                    let field_value = value.map(|value| self.gen_pat_field(pat, value, hir_field));
+                    self.gen_pat_binding(*arg, field_value);
+                }
+            }
+            Pat::Tuple { args, .. } => {
+                for (idx, arg) in args.iter().enumerate() {
+                    let elem_value = value.map(|value| {
+                        self.builder
+                            .build_extract_value(value.into_struct_value(), idx as u32, "elem")
+                            .expect("could not extract tuple element")
+                    });
+                    self.gen_pat_binding(*arg, elem_value);
+                }
+            }
             Pat::Wild => {}
             Pat::Missing | Pat::Path(_) => unreachable!(),
         }
-        true
+    }
+
+    /// Extracts the value of a field from the struct `value` that is
+    /// destructured by the pattern `pat`.
+    fn gen_pat_field(
+        &mut self,
+        pat: PatId,
+        value: BasicValueEnum<'ink>,
+        field: mun_hir::Field,
+    ) -> BasicValueEnum<'ink> {
+        let hir_struct = self.infer[pat].as_struct().expect("expected a struct");
+        let hir_struct_name = hir_struct.name(self.db);
+        let name = field.name(self.db);
+        let field_idx = field.index(self.db);
+
+        let value =
+            if hir_struct.data(self.db.upcast()).memory_kind == mun_hir::StructMemoryKind::Gc {
+                deref_heap_value(&self.builder, value)
+            } else {
+                value
+            };
+
+        self.builder
+            .build_extract_value(
+                value.into_struct_value(),
+                field_idx,
+                &format!("{hir_struct_name}.{name}"),
+            )
+            .unwrap_or_else(|| {
+                panic!(
+                    "could not extract field {name} (index: {field_idx}) from struct {hir_struct_name}"
+                )
+            })
     }
 
     /// Generates IR for looking up a certain path expression.
@@ -578,6 +897,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .0
         {
             ValueNs::ImplSelf(_) => unimplemented!("no support for self types"),
+            ValueNs::BuiltinFunction(_) => unreachable!("builtin functions can only be called"),
             ValueNs::LocalBinding(pat) => {
                 if let Some(param) = self.pat_to_param.get(&pat) {
                     *param
@@ -589,10 +909,42 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 }
             }
             ValueNs::StructId(_) => self.gen_unit_struct_lit(expr),
+            ValueNs::ConstId(id) => match self.db.const_eval(id) {
+                Ok(value) => gen_const_value(self.context, value),
+                // The evaluation error is reported as a diagnostic, which prevents
+                // the assembly from being emitted.
+                Err(_) => self
+                    .hir_types
+                    .get_basic_type(&self.infer[expr])
+                    .expect("expected basic type")
+                    .const_zero(),
+            },
+            ValueNs::StaticId(id) => {
+                let static_def = mun_hir::Static::from(id);
+                self.builder.build_load(
+                    self.static_global(static_def).as_pointer_value(),
+                    &static_def.name(self.db).to_string(),
+                )
+            }
             ValueNs::FunctionId(_) => panic!("unable to generate path expression from a function"),
         }
     }
 
+    /// Returns the global that holds the storage of the specified static.
+    fn static_global(&self, static_def: mun_hir::Static) -> GlobalValue<'ink> {
+        *self
+            .external_globals
+            .statics
+            .get(&static_def)
+            .unwrap_or_else(|| {
+                // Accessing a static of another module is rejected by type inference.
+                panic!(
+                    "static '{}' is not defined in the current assembly",
+                    static_def.full_name(self.db)
+                )
+            })
+    }
+
     /// Given an expression and its value optionally dereference the value to
     /// get to the actual value. This is useful if we need to do an
     /// indirection to get to the actual value.
@@ -623,11 +975,15 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .0
         {
             ValueNs::ImplSelf(_) => unimplemented!("no support for self types"),
+            ValueNs::BuiltinFunction(_) => unreachable!("builtin functions are not places"),
             ValueNs::LocalBinding(pat) => *self
                 .pat_to_local
                 .get(&pat)
                 .expect("unresolved local binding"),
-            ValueNs::FunctionId(_) | ValueNs::StructId(_) => {
+            ValueNs::StaticId(id) => self
+                .static_global(mun_hir::Static::from(id))
+                .as_pointer_value(),
+            ValueNs::FunctionId(_) | ValueNs::StructId(_) | ValueNs::ConstId(_) => {
                 panic!("no support for module definitions")
             }
         }
@@ -636,7 +992,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// Generates IR to calculate a binary operation between two expressions.
     fn gen_binary_op(
         &mut self,
-        _tgt_expr: ExprId,
+        tgt_expr: ExprId,
         lhs: ExprId,
         rhs: ExprId,
         op: BinaryOp,
@@ -645,7 +1001,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         match lhs_type.interned() {
             TyKind::Bool => self.gen_binary_op_bool(lhs, rhs, op),
             TyKind::Float(_) => self.gen_binary_op_float(lhs, rhs, op),
-            TyKind::Int(ty) => self.gen_binary_op_int(lhs, rhs, op, ty.signedness),
+            TyKind::Int(ty) => self.gen_binary_op_int(tgt_expr, lhs, rhs, op, ty.signedness),
             TyKind::Struct(s) => {
                 if s.data(self.db.upcast()).memory_kind == mun_hir::StructMemoryKind::Value {
                     self.gen_binary_op_value_struct(lhs, rhs, op)
@@ -825,6 +1181,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// Generates IR to calculate a binary operation between two integer values.
     fn gen_binary_op_int(
         &mut self,
+        expr: ExprId,
         lhs_expr: ExprId,
         rhs_expr: ExprId,
         op: BinaryOp,
@@ -841,13 +1198,14 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .expect("no rhs value")
             .into_int_value();
         match op {
-            BinaryOp::ArithOp(op) => {
-                Some(self.gen_arith_bin_op_int(lhs, rhs, op, signedness).into())
-            }
+            BinaryOp::ArithOp(op) => Some(
+                self.gen_arith_bin_op_int(expr, lhs, rhs, op, signedness)
+                    .into(),
+            ),
             BinaryOp::CmpOp(op) => Some(self.gen_cmp_bin_op_int(lhs, rhs, op, signedness).into()),
             BinaryOp::Assignment { op } => {
                 let rhs = match op {
-                    Some(op) => self.gen_arith_bin_op_int(lhs, rhs, op, signedness),
+                    Some(op) => self.gen_arith_bin_op_int(expr, lhs, rhs, op, signedness),
                     None => rhs,
                 };
                 let place = self.gen_place_expr(lhs_expr)?;
@@ -992,12 +1350,16 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
     fn gen_arith_bin_op_int(
         &mut self,
+        expr: ExprId,
         lhs: IntValue<'ink>,
         rhs: IntValue<'ink>,
         op: ArithOp,
         signedness: mun_hir::Signedness,
     ) -> IntValue<'ink> {
         match op {
+            ArithOp::Add | ArithOp::Subtract | ArithOp::Multiply if self.overflow_checks => {
+                self.gen_checked_arith_bin_op_int(expr, lhs, rhs, op, signedness)
+            }
             ArithOp::Add => self.builder.build_int_add(lhs, rhs, "add"),
             ArithOp::Subtract => self.builder.build_int_sub(lhs, rhs, "sub"),
             ArithOp::Divide => match signedness {
@@ -1024,6 +1386,62 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR to add, subtract, or multiply two integer values. If the
+    /// result overflows, the generated code panics.
+    fn gen_checked_arith_bin_op_int(
+        &mut self,
+        expr: ExprId,
+        lhs: IntValue<'ink>,
+        rhs: IntValue<'ink>,
+        op: ArithOp,
+        signedness: mun_hir::Signedness,
+    ) -> IntValue<'ink> {
+        let (name, message) = match op {
+            ArithOp::Add => ("add", "attempt to add with overflow"),
+            ArithOp::Subtract => ("sub", "attempt to subtract with overflow"),
+            ArithOp::Multiply => ("mul", "attempt to multiply with overflow"),
+            _ => unreachable!("operator {:?} cannot overflow", op),
+        };
+
+        let intrinsic_name = format!(
+            "llvm.{}{name}.with.overflow",
+            if signedness.is_signed() { 's' } else { 'u' }
+        );
+
+        // The intrinsic returns both the wrapped result and whether it overflowed
+        let result = self
+            .gen_llvm_intrinsic_call(
+                &intrinsic_name,
+                &[lhs.get_type().into()],
+                &[lhs.into(), rhs.into()],
+                name,
+            )
+            .into_struct_value();
+        let value = self
+            .builder
+            .build_extract_value(result, 0, name)
+            .expect("missing result")
+            .into_int_value();
+        let overflowed = self
+            .builder
+            .build_extract_value(result, 1, "overflowed")
+            .expect("missing overflow flag")
+            .into_int_value();
+
+        let overflow_block = self.context.append_basic_block(self.fn_value, "overflow");
+        let continue_block = self
+            .context
+            .append_basic_block(self.fn_value, "no_overflow");
+        self.builder
+            .build_conditional_branch(overflowed, overflow_block, continue_block);
+
+        self.builder.position_at_end(overflow_block);
+        self.gen_panic(expr, message);
+
+        self.builder.position_at_end(continue_block);
+        value
+    }
+
     fn gen_arith_bin_op_float(
         &mut self,
         lhs: FloatValue<'ink>,
@@ -1124,6 +1542,163 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR for a call to a builtin function.
+    fn gen_builtin_call(
+        &mut self,
+        expr: ExprId,
+        builtin: BuiltinFunction,
+        args: &[ExprId],
+    ) -> Option<inkwell::values::BasicValueEnum<'ink>> {
+        // The message of a panic is always a string literal
+        let body = self.body.clone();
+        let message = |arg: Option<&ExprId>, default: &str| match arg.map(|arg| &body[*arg]) {
+            Some(Expr::Literal(Literal::String(message))) => message.clone(),
+            _ => default.to_owned(),
+        };
+
+        match builtin {
+            BuiltinFunction::Panic => {
+                self.gen_panic(expr, &message(args.first(), "explicit panic"));
+                None
+            }
+            BuiltinFunction::Assert => {
+                let condition_ir = self
+                    .gen_expr(args[0])
+                    .map(|value| self.opt_deref_value(args[0], value))?
+                    .into_int_value();
+
+                let panic_block = self
+                    .context
+                    .append_basic_block(self.fn_value, "assert_failed");
+                let continue_block = self.context.append_basic_block(self.fn_value, "assert_ok");
+                self.builder
+                    .build_conditional_branch(condition_ir, continue_block, panic_block);
+
+                self.builder.position_at_end(panic_block);
+                self.gen_panic(expr, &message(args.get(1), "assertion failed"));
+
+                self.builder.position_at_end(continue_block);
+                Some(self.gen_empty())
+            }
+            BuiltinFunction::Sin
+            | BuiltinFunction::Cos
+            | BuiltinFunction::Sqrt
+            | BuiltinFunction::Abs
+            | BuiltinFunction::Min
+            | BuiltinFunction::Max => {
+                let mut arg_values = Vec::with_capacity(args.len());
+                for &arg in args {
+                    let value = self
+                        .gen_expr(arg)
+                        .map(|value| self.opt_deref_value(arg, value))?;
+                    arg_values.push(value);
+                }
+                Some(self.gen_math_builtin_call(expr, builtin, arg_values))
+            }
+        }
+    }
+
+    /// Generates IR for a call to a builtin math function by lowering it to the
+    /// equivalent LLVM intrinsic.
+    fn gen_math_builtin_call(
+        &mut self,
+        expr: ExprId,
+        builtin: BuiltinFunction,
+        mut args: Vec<BasicValueEnum<'ink>>,
+    ) -> BasicValueEnum<'ink> {
+        let signedness = match self.infer[expr].interned() {
+            TyKind::Int(int_ty) => Some(int_ty.signedness),
+            TyKind::Float(_) => None,
+            _ => unreachable!("math builtins are only applied to numbers"),
+        };
+        let intrinsic_name = match (builtin, signedness) {
+            (BuiltinFunction::Sin, None) => "llvm.sin",
+            (BuiltinFunction::Cos, None) => "llvm.cos",
+            (BuiltinFunction::Sqrt, None) => "llvm.sqrt",
+            (BuiltinFunction::Abs, None) => "llvm.fabs",
+            (BuiltinFunction::Abs, Some(_)) => {
+                // The absolute value of the minimum value wraps around instead of resulting in
+                // a poison value
+                args.push(self.context.bool_type().const_zero().into());
+                "llvm.abs"
+            }
+            (BuiltinFunction::Min, None) => "llvm.minnum",
+            (BuiltinFunction::Max, None) => "llvm.maxnum",
+            (BuiltinFunction::Min, Some(signedness)) if signedness.is_signed() => "llvm.smin",
+            (BuiltinFunction::Min, Some(_)) => "llvm.umin",
+            (BuiltinFunction::Max, Some(signedness)) if signedness.is_signed() => "llvm.smax",
+            (BuiltinFunction::Max, Some(_)) => "llvm.umax",
+            _ => unreachable!("invalid argument type for builtin function `{builtin}`"),
+        };
+
+        let ty = args[0].get_type();
+        let args: Vec<BasicMetadataValueEnum<'ink>> = args.into_iter().map(Into::into).collect();
+        self.gen_llvm_intrinsic_call(intrinsic_name, &[ty], &args, &builtin.to_string())
+    }
+
+    /// Generates a call to the LLVM intrinsic with the specified `name`,
+    /// overloaded for the specified `types`.
+    fn gen_llvm_intrinsic_call(
+        &mut self,
+        name: &str,
+        types: &[BasicTypeEnum<'ink>],
+        args: &[BasicMetadataValueEnum<'ink>],
+        value_name: &str,
+    ) -> BasicValueEnum<'ink> {
+        let intrinsic = Intrinsic::find(name)
+            .and_then(|intrinsic| intrinsic.get_declaration(self.module, types))
+            .unwrap_or_else(|| panic!("could not find intrinsic '{name}'"));
+
+        self.builder
+            .build_call(intrinsic, args, value_name)
+            .try_as_basic_value()
+            .left()
+            .unwrap_or_else(|| panic!("intrinsic '{name}' does not return a value"))
+    }
+
+    /// Generates IR that calls the `panic` intrinsic with the specified
+    /// `message`. The location of `expr` in the source code is passed along
+    /// with the message.
+    fn gen_panic(&mut self, expr: ExprId, message: &str) {
+        let file_id = self.hir_function.file_id(self.db);
+        let file = self.db.file_relative_path(file_id);
+        let line_col = self
+            .hir_function
+            .expr_text_range(self.db, expr)
+            .map(|range| self.db.line_index(file_id).line_col(range.start()));
+        let (line, column) = line_col.map_or((0, 0), |line_col| {
+            (line_col.line + 1, line_col.col_utf16 + 1)
+        });
+
+        let panic_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::panic,
+        );
+
+        let message_ptr = self
+            .builder
+            .build_global_string_ptr(message, "panic_message")
+            .as_pointer_value();
+        let file_ptr = self
+            .builder
+            .build_global_string_ptr(file.as_str(), "panic_file")
+            .as_pointer_value();
+        let i32_type = self.context.i32_type();
+
+        self.builder.build_call(
+            panic_fn_ptr,
+            &[
+                message_ptr.into(),
+                file_ptr.into(),
+                i32_type.const_int(u64::from(line), false).into(),
+                i32_type.const_int(u64::from(column), false).into(),
+            ],
+            "",
+        );
+        self.builder.build_unreachable();
+    }
+
     /// Generates IR for an if statement.
     fn gen_if(
         &mut self,
@@ -1157,7 +1732,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         // Fill the then block
         self.builder.position_at_end(then_block);
         let then_block_ir = self.gen_expr(then_branch);
-        if !self.infer[then_branch].is_never() {
+        if !self.infer[then_branch].is_never() && !self.is_block_terminated() {
             self.builder.build_unconditional_branch(merge_block);
         }
         then_block = self.builder.get_insert_block().unwrap();
@@ -1213,6 +1788,11 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     ) -> Option<BasicValueEnum<'ink>> {
         let ret_value = ret_expr.and_then(|expr| self.gen_expr(expr));
 
+        // The returned expression might not return, e.g. because it is a tail call
+        if self.is_block_terminated() {
+            return None;
+        }
+
         // Construct a return statement from the returned value of the body
         if let Some(value) = ret_value {
             self.builder.build_return(Some(&value));
@@ -1223,11 +1803,28 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         None
     }
 
+    /// Returns the index in `active_loops` of the loop with the given label, or
+    /// of the innermost loop if no label is specified.
+    fn find_active_loop(&self, label: Option<&Name>) -> usize {
+        // The type checker ensures that the targeted loop exists
+        match label {
+            Some(label) => self
+                .active_loops
+                .iter()
+                .rposition(|loop_info| loop_info.label.as_ref() == Some(label)),
+            None => self.active_loops.len().checked_sub(1),
+        }
+        .expect("break or continue outside of a loop")
+    }
+
     fn gen_break(
         &mut self,
         _expr: ExprId,
         break_expr: Option<ExprId>,
+        label: Option<&Name>,
     ) -> Option<BasicValueEnum<'ink>> {
+        let target = self.find_active_loop(label);
+
         if let Some(expr) = break_expr {
             // There is an expression
             // e.g. break x;
@@ -1236,7 +1833,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
             // If the expression never returns, we can stop what we're doing.
             if let Some(break_value) = break_value {
-                let loop_info = self.active_loop.as_mut().unwrap();
+                let loop_info = &mut self.active_loops[target];
                 loop_info.break_values.push(Some((
                     break_value,
                     self.builder.get_insert_block().unwrap(),
@@ -1247,7 +1844,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         } else {
             // If the break expression doesnt contain a break statement. Add a none to the
             // break values.
-            let loop_info = self.active_loop.as_mut().unwrap();
+            let loop_info = &mut self.active_loops[target];
             loop_info.break_values.push(None);
             self.builder
                 .build_unconditional_branch(loop_info.exit_block);
@@ -1256,23 +1853,34 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         None
     }
 
+    fn gen_continue(&mut self, label: Option<&Name>) -> Option<BasicValueEnum<'ink>> {
+        let target = self.find_active_loop(label);
+        self.builder
+            .build_unconditional_branch(self.active_loops[target].continue_block);
+
+        None
+    }
+
+    /// Generates the body of a loop. A `break` jumps to `exit_block`, a
+    /// `continue` jumps to `continue_block`.
     fn gen_loop_block_expr(
         &mut self,
         block: ExprId,
+        label: Option<Name>,
         exit_block: BasicBlock<'ink>,
+        continue_block: BasicBlock<'ink>,
     ) -> (
         BasicBlock<'ink>,
         BreakSources<'ink>,
         Option<BasicValueEnum<'ink>>,
     ) {
-        // Build a new loop info struct
-        let loop_info = LoopInfo {
+        // Push a new loop info struct
+        self.active_loops.push(LoopInfo {
+            label,
             exit_block,
+            continue_block,
             break_values: Vec::new(),
-        };
-
-        // Replace previous loop info
-        let prev_loop = std::mem::replace(&mut self.active_loop, Some(loop_info));
+        });
 
         // Start generating code inside the loop
         let value = self.gen_expr(block);
@@ -1280,7 +1888,11 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let LoopInfo {
             exit_block,
             break_values,
-        } = std::mem::replace(&mut self.active_loop, prev_loop).unwrap();
+            ..
+        } = self
+            .active_loops
+            .pop()
+            .expect("the active loop must still be on the stack");
 
         (exit_block, break_values, value)
     }
@@ -1290,6 +1902,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         _expr: ExprId,
         condition_expr: ExprId,
         body_expr: ExprId,
+        label: Option<Name>,
     ) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let cond_block = context.append_basic_block(self.fn_value, "whilecond");
@@ -1318,7 +1931,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate loop block
         self.builder.position_at_end(loop_block);
-        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, _, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, cond_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(cond_block);
         }
@@ -1329,7 +1943,191 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         Some(self.gen_empty())
     }
 
-    fn gen_loop(&mut self, _expr: ExprId, body_expr: ExprId) -> Option<BasicValueEnum<'ink>> {
+    fn gen_for(
+        &mut self,
+        _expr: ExprId,
+        pat: PatId,
+        iterable_expr: ExprId,
+        body_expr: ExprId,
+        label: Option<Name>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        let body = self.body.clone();
+        match &body[iterable_expr] {
+            Expr::Range { start, end, op } => {
+                self.gen_for_range(iterable_expr, pat, *start, *end, *op, body_expr, label)
+            }
+            _ => self.gen_for_array(pat, iterable_expr, body_expr, label),
+        }
+    }
+
+    /// Generates IR for a `for` loop that iterates over a range of integers:
+    /// `for i in 0..10 { .. }`.
+    #[allow(clippy::too_many_arguments)]
+    fn gen_for_range(
+        &mut self,
+        range_expr: ExprId,
+        pat: PatId,
+        start_expr: ExprId,
+        end_expr: ExprId,
+        op: RangeOp,
+        body_expr: ExprId,
+        label: Option<Name>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        let start = self.gen_expr(start_expr)?.into_int_value();
+        let end = self.gen_expr(end_expr)?.into_int_value();
+
+        // The type of the range expression is the type of its elements
+        let signedness = match self.infer[range_expr].interned() {
+            TyKind::Int(ty) => ty.signedness,
+            _ => unreachable!("the elements of a range must be integers"),
+        };
+        let (lt, le) = if signedness == mun_hir::Signedness::Signed {
+            (IntPredicate::SLT, IntPredicate::SLE)
+        } else {
+            (IntPredicate::ULT, IntPredicate::ULE)
+        };
+
+        let counter = self
+            .new_alloca_builder()
+            .build_alloca(start.get_type(), "for.counter");
+        self.builder.build_store(counter, start);
+
+        let context = self.context;
+        let cond_block = context.append_basic_block(self.fn_value, "forcond");
+        let loop_block = context.append_basic_block(self.fn_value, "for");
+        let step_block = context.append_basic_block(self.fn_value, "forstep");
+        let exit_block = context.append_basic_block(self.fn_value, "afterfor");
+
+        // Insert an explicit fall through from the current block to the condition check
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate condition block
+        self.builder.position_at_end(cond_block);
+        let current = self
+            .builder
+            .build_load(counter, "for.current")
+            .into_int_value();
+        let predicate = match op {
+            RangeOp::Exclusive => lt,
+            RangeOp::Inclusive => le,
+        };
+        let condition_ir = self
+            .builder
+            .build_int_compare(predicate, current, end, "for.cond");
+        self.builder
+            .build_conditional_branch(condition_ir, loop_block, exit_block);
+
+        // Generate loop block
+        self.builder.position_at_end(loop_block);
+        let current = self.builder.build_load(counter, "for.current");
+        self.gen_pat_binding(pat, Some(current));
+        let (exit_block, _, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, step_block);
+        if value.is_some() {
+            self.builder.build_unconditional_branch(step_block);
+        }
+
+        // Generate the step block which increments the counter. For inclusive ranges the
+        // loop is exited before incrementing past the end to prevent an overflow when the
+        // end of the range is the maximum value of its type.
+        self.builder.position_at_end(step_block);
+        let current = self
+            .builder
+            .build_load(counter, "for.current")
+            .into_int_value();
+        if op == RangeOp::Inclusive {
+            let increment_block = context.append_basic_block(self.fn_value, "forincrement");
+            let is_last =
+                self.builder
+                    .build_int_compare(IntPredicate::EQ, current, end, "for.islast");
+            self.builder
+                .build_conditional_branch(is_last, exit_block, increment_block);
+            self.builder.position_at_end(increment_block);
+        }
+        let next =
+            self.builder
+                .build_int_add(current, current.get_type().const_int(1, false), "for.next");
+        self.builder.build_store(counter, next);
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate exit block
+        self.builder.position_at_end(exit_block);
+
+        Some(self.gen_empty())
+    }
+
+    /// Generates IR for a `for` loop that iterates over the elements of an
+    /// array: `for x in [1, 2, 3] { .. }`.
+    fn gen_for_array(
+        &mut self,
+        pat: PatId,
+        array_expr: ExprId,
+        body_expr: ExprId,
+        label: Option<Name>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        // Safety: the type checker only allows arrays and ranges to be iterated over.
+        let array = unsafe {
+            RuntimeArrayValue::from_ptr_unchecked(self.gen_expr(array_expr)?.into_pointer_value())
+        };
+        let index_ty = array.length_ty();
+        let index = self
+            .new_alloca_builder()
+            .build_alloca(index_ty, "for.index");
+        self.builder.build_store(index, index_ty.const_zero());
+
+        let context = self.context;
+        let cond_block = context.append_basic_block(self.fn_value, "forcond");
+        let loop_block = context.append_basic_block(self.fn_value, "for");
+        let exit_block = context.append_basic_block(self.fn_value, "afterfor");
+
+        // Insert an explicit fall through from the current block to the condition check
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate condition block
+        self.builder.position_at_end(cond_block);
+        let current = self.builder.build_load(index, "for.index").into_int_value();
+        let length = self
+            .builder
+            .build_load(array.get_length_ptr(&self.builder), "for.length")
+            .into_int_value();
+        let condition_ir =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, current, length, "for.cond");
+        self.builder
+            .build_conditional_branch(condition_ir, loop_block, exit_block);
+
+        // Generate loop block. The index is incremented before the body is executed,
+        // since the body is the only thing that can jump back to the condition.
+        self.builder.position_at_end(loop_block);
+        let elements = array.get_elements(&self.builder);
+        let element_ptr = unsafe {
+            self.builder
+                .build_gep(elements, &[current], "for.element_ptr")
+        };
+        let element = self.builder.build_load(element_ptr, "for.element");
+        let next = self
+            .builder
+            .build_int_add(current, index_ty.const_int(1, false), "for.next");
+        self.builder.build_store(index, next);
+        self.gen_pat_binding(pat, Some(element));
+        let (exit_block, _, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, cond_block);
+        if value.is_some() {
+            self.builder.build_unconditional_branch(cond_block);
+        }
+
+        // Generate exit block
+        self.builder.position_at_end(exit_block);
+
+        Some(self.gen_empty())
+    }
+
+    fn gen_loop(
+        &mut self,
+        _expr: ExprId,
+        body_expr: ExprId,
+        label: Option<Name>,
+    ) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let loop_block = context.append_basic_block(self.fn_value, "loop");
         let exit_block = context.append_basic_block(self.fn_value, "exit");
@@ -1339,7 +2137,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate the body of the loop
         self.builder.position_at_end(loop_block);
-        let (exit_block, break_values, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, break_values, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, loop_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(loop_block);
         }
@@ -1565,19 +2364,22 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         Some(self.builder.build_load(element_ptr, ""))
     }
 
-    /// Generates an index into an array
+    /// Generates an index into an array. If the index is out of bounds, the
+    /// generated code panics.
     fn gen_place_index(
         &mut self,
-        _expr: ExprId,
+        expr: ExprId,
         base: ExprId,
-        index: ExprId,
+        index_expr: ExprId,
     ) -> Option<PointerValue<'ink>> {
         // Safety: place expression can only be generated if the base expression is an
         // array.
         let base = unsafe {
             RuntimeArrayValue::from_ptr_unchecked(self.gen_expr(base)?.into_pointer_value())
         };
-        let index = self.gen_expr(index)?.into_int_value();
+        let index = self.gen_expr(index_expr)?.into_int_value();
+
+        self.gen_bounds_check(expr, &base, index_expr, index);
 
         let elements = base.get_elements(&self.builder);
         Some(unsafe {
@@ -1589,6 +2391,69 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         })
     }
 
+    /// Generates IR that panics if `index` is out of the bounds of `array`.
+    fn gen_bounds_check(
+        &mut self,
+        expr: ExprId,
+        array: &RuntimeArrayValue<'ink>,
+        index_expr: ExprId,
+        index: IntValue<'ink>,
+    ) {
+        let is_signed = matches!(
+            self.infer[index_expr].interned(),
+            TyKind::Int(int_ty) if int_ty.signedness == mun_hir::Signedness::Signed
+        );
+
+        // Bring the index and the length to the same width. A negative index is
+        // sign-extended, which makes it out of bounds when compared unsigned.
+        let length = array.get_length(&self.builder);
+        let index_width = index.get_type().get_bit_width();
+        let length_width = length.get_type().get_bit_width();
+        let (index, length) = match index_width.cmp(&length_width) {
+            std::cmp::Ordering::Less if is_signed => (
+                self.builder
+                    .build_int_s_extend(index, length.get_type(), "index"),
+                length,
+            ),
+            std::cmp::Ordering::Less => (
+                self.builder
+                    .build_int_z_extend(index, length.get_type(), "index"),
+                length,
+            ),
+            std::cmp::Ordering::Greater => (
+                index,
+                self.builder
+                    .build_int_z_extend(length, index.get_type(), "length"),
+            ),
+            std::cmp::Ordering::Equal => (index, length),
+        };
+
+        let in_bounds =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, index, length, "in_bounds");
+
+        let panic_block = self
+            .context
+            .append_basic_block(self.fn_value, "out_of_bounds");
+        let continue_block = self.context.append_basic_block(self.fn_value, "in_bounds");
+        self.builder
+            .build_conditional_branch(in_bounds, continue_block, panic_block);
+
+        self.builder.position_at_end(panic_block);
+        self.gen_panic(expr, "index out of bounds");
+
+        self.builder.position_at_end(continue_block);
+    }
+
+    /// Returns true if the block that is currently being generated already
+    /// ends with a terminator instruction.
+    fn is_block_terminated(&self) -> bool {
+        self.builder
+            .get_insert_block()
+            .and_then(BasicBlock::get_terminator)
+            .is_some()
+    }
+
     /// Returns a pointer to the allocator handle
     fn get_allocator_handle_ptr(&self) -> PointerValue<'ink> {
         self.builder
@@ -1603,6 +2468,57 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     }
 }
 
+/// Returns all self-recursive calls of `function` in its `body` that are in
+/// tail position. The value of such a call is directly returned by the
+/// function.
+fn tail_calls(
+    body: &Body,
+    infer: &InferenceResult,
+    function: mun_hir::Function,
+) -> HashSet<ExprId> {
+    fn collect(
+        body: &Body,
+        infer: &InferenceResult,
+        function: mun_hir::Function,
+        expr: ExprId,
+        tail_calls: &mut HashSet<ExprId>,
+    ) {
+        match &body[expr] {
+            Expr::Block {
+                tail: Some(tail), ..
+            } => collect(body, infer, function, *tail, tail_calls),
+            Expr::If {
+                then_branch,
+                else_branch: Some(else_branch),
+                ..
+            } => {
+                collect(body, infer, function, *then_branch, tail_calls);
+                collect(body, infer, function, *else_branch, tail_calls);
+            }
+            Expr::Call { callee, .. }
+                if infer.builtin_call(expr).is_none()
+                    && infer[*callee].as_callable_def()
+                        == Some(mun_hir::CallableDef::Function(function)) =>
+            {
+                tail_calls.insert(expr);
+            }
+            _ => (),
+        }
+    }
+
+    let mut tail_calls = HashSet::new();
+    collect(body, infer, function, body.body_expr(), &mut tail_calls);
+    for (_, expr) in body.exprs() {
+        if let Expr::Return {
+            expr: Some(ret_expr),
+        } = expr
+        {
+            collect(body, infer, function, *ret_expr, &mut tail_calls);
+        }
+    }
+    tail_calls
+}
+
 /// Derefs a heap-allocated value. As we introduce a layer of indirection for
 /// hot reloading, we need to first load the pointer that points to the memory
 /// block.
@@ -1616,3 +2532,33 @@ fn deref_heap_value<'ink>(
 
     builder.build_load(mem_ptr, "deref")
 }
+
+/// Generates an IR constant for the value of a `const` item or the initial
+/// value of a `static`. Constants are inlined at every use site.
+pub(crate) fn gen_const_value(context: &Context, value: ConstValue) -> BasicValueEnum<'_> {
+    match value {
+        ConstValue::Bool(value) => context.bool_type().const_int(value.into(), false).into(),
+        ConstValue::Int { bits, ty } => {
+            let ir_ty = match ty.bitness {
+                mun_hir::IntBitness::X8 => context.i8_type(),
+                mun_hir::IntBitness::X16 => context.i16_type(),
+                mun_hir::IntBitness::X32 => context.i32_type(),
+                mun_hir::IntBitness::X64 => context.i64_type(),
+                mun_hir::IntBitness::X128 => context.i128_type(),
+                mun_hir::IntBitness::Xsize => {
+                    unreachable!("unresolved bitness in code generation")
+                }
+            };
+            ir_ty
+                .const_int_arbitrary_precision(&[bits as u64, (bits >> 64) as u64])
+                .into()
+        }
+        ConstValue::Float { bits, ty } => {
+            let value = f64::from_bits(bits);
+            match ty.bitness {
+                mun_hir::FloatBitness::X32 => context.f32_type().const_float(value).into(),
+                mun_hir::FloatBitness::X64 => context.f64_type().const_float(value).into(),
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+
+use inkwell::{
+    builder::Builder,
+    context::Context,
+    debug_info::{
+        debug_metadata_version, AsDIScope, DICompileUnit, DIFile, DIFlags, DIFlagsConstants,
+        DILocation, DIScope, DISubprogram, DIType, DWARFEmissionKind, DWARFSourceLanguage,
+        DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Module},
+    types::BasicTypeEnum,
+    values::{FunctionValue, PointerValue},
+};
+use mun_hir::{
+    FloatBitness, HasVisibility, HirDatabase, HirDisplay, PatId, Signedness, Ty, TyKind,
+};
+use mun_hir_input::{FileId, LineCol, LineIndex};
+
+/// The version of DWARF that is emitted on targets that don't use `CodeView`
+const DWARF_VERSION: u64 = 4;
+
+/// Encodings of basic types as defined by the DWARF standard (`DW_ATE_*`)
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x08;
+
+/// Generates the debug information of an LLVM module, which enables native
+/// debuggers to map machine code back to Mun source lines and to inspect
+/// local variables of primitive types.
+pub(crate) struct DebugInfo<'db, 'ink> {
+    db: &'db dyn HirDatabase,
+    context: &'ink Context,
+    builder: DebugInfoBuilder<'ink>,
+    compile_unit: DICompileUnit<'ink>,
+    files: RefCell<HashMap<FileId, (DIFile<'ink>, Arc<LineIndex>)>>,
+    is_optimized: bool,
+}
+
+impl<'db, 'ink> DebugInfo<'db, 'ink> {
+    /// Constructs the debug information for `module`, of which the compile unit
+    /// is described by `file_id`.
+    pub fn new(
+        db: &'db dyn HirDatabase,
+        context: &'ink Context,
+        module: &Module<'ink>,
+        file_id: FileId,
+        is_optimized: bool,
+    ) -> Self {
+        let target = db.target();
+        if target.options.is_like_msvc {
+            module.add_basic_value_flag(
+                "CodeView",
+                FlagBehavior::Warning,
+                context.i32_type().const_int(1, false),
+            );
+        } else {
+            module.add_basic_value_flag(
+                "Dwarf Version",
+                FlagBehavior::Warning,
+                context.i32_type().const_int(DWARF_VERSION, false),
+            );
+        }
+        module.add_basic_value_flag(
+            "Debug Info Version",
+            FlagBehavior::Warning,
+            context
+                .i32_type()
+                .const_int(debug_metadata_version().into(), false),
+        );
+
+        let (directory, filename) = split_file_path(db, file_id);
+        let (builder, compile_unit) = module.create_debug_info_builder(
+            true,
+            // There is no DWARF language code for Mun, C is what debuggers support best
+            DWARFSourceLanguage::C,
+            &filename,
+            &directory,
+            "mun",
+            is_optimized,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+
+        Self {
+            db,
+            context,
+            builder,
+            compile_unit,
+            files: RefCell::default(),
+            is_optimized,
+        }
+    }
+
+    /// Returns the debug information and line index of the specified file.
+    fn file(&self, file_id: FileId) -> (DIFile<'ink>, Arc<LineIndex>) {
+        self.files
+            .borrow_mut()
+            .entry(file_id)
+            .or_insert_with(|| {
+                let (directory, filename) = split_file_path(self.db, file_id);
+                (
+                    self.builder.create_file(&filename, &directory),
+                    self.db.line_index(file_id),
+                )
+            })
+            .clone()
+    }
+
+    /// Attaches the debug information of `hir_function` to `fn_value` and
+    /// returns the scope in which the locations of its body are described.
+    pub fn gen_function(
+        &self,
+        hir_function: mun_hir::Function,
+        fn_value: FunctionValue<'ink>,
+    ) -> FunctionDebugInfo<'_, 'db, 'ink> {
+        let (file, line_index) = self.file(hir_function.file_id(self.db));
+        let body = hir_function.body(self.db);
+        let line = hir_function
+            .expr_text_range(self.db, body.body_expr())
+            .map_or(0, |range| line_index.line_col(range.start()).line + 1);
+
+        let subroutine_type = self
+            .builder
+            .create_subroutine_type(file, None, &[], DIFlags::PUBLIC);
+        let subprogram = self.builder.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            &hir_function.name(self.db).to_string(),
+            fn_value.get_name().to_str().ok(),
+            file,
+            line,
+            subroutine_type,
+            !hir_function.visibility(self.db).is_externally_visible(),
+            true,
+            line,
+            DIFlags::PROTOTYPED,
+            self.is_optimized,
+        );
+        fn_value.set_subprogram(subprogram);
+
+        FunctionDebugInfo {
+            debug_info: self,
+            hir_function,
+            subprogram,
+            file,
+            line_index,
+        }
+    }
+
+    /// Returns the debug type of a primitive type, or `None` if `ty` is not a
+    /// primitive type.
+    fn basic_type(&self, ty: &Ty, llvm_type: BasicTypeEnum<'ink>) -> Option<DIType<'ink>> {
+        let (size_in_bits, encoding) = match ty.interned() {
+            TyKind::Bool => (8, DW_ATE_BOOLEAN),
+            TyKind::Int(int_ty) => {
+                let encoding = match int_ty.signedness {
+                    Signedness::Signed => DW_ATE_SIGNED,
+                    Signedness::Unsigned => DW_ATE_UNSIGNED,
+                };
+                (
+                    u64::from(llvm_type.into_int_type().get_bit_width()),
+                    encoding,
+                )
+            }
+            TyKind::Float(float_ty) => match float_ty.bitness {
+                FloatBitness::X32 => (32, DW_ATE_FLOAT),
+                FloatBitness::X64 => (64, DW_ATE_FLOAT),
+            },
+            _ => return None,
+        };
+
+        self.builder
+            .create_basic_type(
+                &ty.display(self.db).to_string(),
+                size_in_bits,
+                encoding,
+                DIFlags::PUBLIC,
+            )
+            .ok()
+            .map(|ty| ty.as_type())
+    }
+
+    /// Resolves all debug information. Must be called before the module is
+    /// verified or compiled.
+    pub fn finalize(&self) {
+        self.builder.finalize();
+    }
+}
+
+/// The debug information of a single function.
+pub(crate) struct FunctionDebugInfo<'d, 'db, 'ink> {
+    debug_info: &'d DebugInfo<'db, 'ink>,
+    hir_function: mun_hir::Function,
+    subprogram: DISubprogram<'ink>,
+    file: DIFile<'ink>,
+    line_index: Arc<LineIndex>,
+}
+
+impl<'ink> FunctionDebugInfo<'_, '_, 'ink> {
+    /// Returns the debug location of `line_col` in the function. Debug
+    /// locations are one-based, zero denotes an unknown location.
+    fn location(&self, line_col: Option<LineCol>) -> DILocation<'ink> {
+        let (line, column) = line_col.map_or((0, 0), |line_col| {
+            (line_col.line + 1, line_col.col_utf16 + 1)
+        });
+        self.debug_info.builder.create_debug_location(
+            self.debug_info.context,
+            line,
+            column,
+            self.scope(),
+            None,
+        )
+    }
+
+    /// Returns the debug location of `pat` in the function.
+    fn pat_location(&self, pat: PatId) -> DILocation<'ink> {
+        let line_col = self
+            .hir_function
+            .pat_text_range(self.debug_info.db, pat)
+            .map(|range| self.line_index.line_col(range.start()));
+        self.location(line_col)
+    }
+
+    fn scope(&self) -> DIScope<'ink> {
+        self.subprogram.as_debug_info_scope()
+    }
+
+    /// Attributes the instructions that `builder` emits from now on to the
+    /// source location of `expr`.
+    pub fn set_expr_location(&self, builder: &Builder<'ink>, expr: mun_hir::ExprId) {
+        let line_col = self
+            .hir_function
+            .expr_text_range(self.debug_info.db, expr)
+            .map(|range| self.line_index.line_col(range.start()));
+        builder.set_current_debug_location(self.location(line_col));
+    }
+
+    /// Attributes the instructions that `builder` emits from now on to the
+    /// source location of `pat`.
+    pub fn set_pat_location(&self, builder: &Builder<'ink>, pat: PatId) {
+        builder.set_current_debug_location(self.pat_location(pat));
+    }
+
+    /// Describes the local variable or parameter `name`, bound by `pat`, that
+    /// is stored at `storage`. `arg_no` is the one-based index of a parameter.
+    /// Variables of which the type is not primitive are not described.
+    #[allow(clippy::too_many_arguments)]
+    pub fn declare_variable(
+        &self,
+        builder: &Builder<'ink>,
+        pat: PatId,
+        name: &str,
+        ty: &Ty,
+        llvm_type: BasicTypeEnum<'ink>,
+        storage: PointerValue<'ink>,
+        arg_no: Option<u32>,
+    ) {
+        let Some(di_type) = self.debug_info.basic_type(ty, llvm_type) else {
+            return;
+        };
+        let Some(block) = builder.get_insert_block() else {
+            return;
+        };
+
+        let location = self.pat_location(pat);
+        let di_builder = &self.debug_info.builder;
+        let variable = match arg_no {
+            Some(arg_no) => di_builder.create_parameter_variable(
+                self.scope(),
+                name,
+                arg_no,
+                self.file,
+                location.get_line(),
+                di_type,
+                true,
+                DIFlags::ZERO,
+            ),
+            None => di_builder.create_auto_variable(
+                self.scope(),
+                name,
+                self.file,
+                location.get_line(),
+                di_type,
+                true,
+                DIFlags::ZERO,
+                0,
+            ),
+        };
+        di_builder.insert_declare_at_end(storage, Some(variable), None, location, block);
+    }
+}
+
+/// Splits the path of the specified file into a directory and a filename, as
+/// they are stored in the debug information. The path is relative to the
+/// source directory of the package.
+fn split_file_path(db: &dyn HirDatabase, file_id: FileId) -> (String, String) {
+    let path = db.file_relative_path(file_id);
+    let filename = path.file_name().unwrap_or_default().to_owned();
+    let directory = path.parent().map(ToString::to_string).unwrap_or_default();
+    (directory, filename)
+}
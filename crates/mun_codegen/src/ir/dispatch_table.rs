@@ -255,9 +255,11 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
     fn collect_expr(&mut self, expr_id: ExprId, body: &Arc<Body>, infer: &InferenceResult) {
         let expr = &body[expr_id];
 
-        // If this expression is a call, store it in the dispatch table
+        // If this expression is a call, store it in the dispatch table. Calls to
+        // builtin functions are implemented through intrinsics.
         if let Expr::Call { callee, .. } = expr {
             match infer[*callee].as_callable_def() {
+                _ if infer.builtin_call(expr_id).is_some() => (),
                 Some(mun_hir::CallableDef::Function(def)) => {
                     if self.module_group.should_runtime_link_fn(self.db, def) {
                         let fn_module = def.module(self.db);
@@ -283,7 +285,7 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
 
         // If the function is not yet contained in the table, add it
         if !self.function_to_idx.contains_key(&function) {
-            let name = function.full_name(self.db);
+            let name = function.export_name(self.db);
             let hir_type = function.ty(self.db);
             let sig = hir_type.callable_sig(self.db).unwrap();
             let ir_type = self.hir_types.get_function_type(function);
@@ -331,7 +333,28 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
     ///   IR values.
     ///
     /// Returns the `DispatchTable` and a set of dependencies for the module.
-    pub fn build(self) -> (DispatchTable<'ink>, FxHashSet<mun_hir::Module>) {
+    pub fn build(mut self) -> (DispatchTable<'ink>, FxHashSet<mun_hir::Module>) {
+        // The order in which calls are encountered depends on the order of the
+        // function bodies. Sort the entries by name to guarantee that the
+        // layout of the table is the same for identical inputs. Intrinsics
+        // are kept in front of the Mun functions.
+        self.entries.sort_by(|a, b| {
+            (a.function.mun_hir.is_some(), &a.function.prototype.name)
+                .cmp(&(b.function.mun_hir.is_some(), &b.function.prototype.name))
+        });
+        self.prototype_to_idx = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| (entry.function.prototype.clone(), idx))
+            .collect();
+        self.function_to_idx = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| entry.function.mun_hir.map(|f| (f, idx)))
+            .collect();
+
         // Construct the table body from all the entries in the dispatch table
         let table_body: Vec<BasicTypeEnum<'ink>> = self
             .entries
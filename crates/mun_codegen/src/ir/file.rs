@@ -1,12 +1,16 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use inkwell::module::Module;
-use mun_hir::{HasVisibility, ModuleDef};
+use itertools::Itertools;
+use mun_hir::{HasVisibility, LifecycleHook, ModuleDef};
 
-use super::body::ExternalGlobals;
+use super::body::{gen_const_value, ExternalGlobals};
 use crate::{
     code_gen::CodeGenContext,
-    ir::{body::BodyIrGenerator, file_group::FileGroupIr, function, type_table::TypeTable},
+    ir::{
+        body::BodyIrGenerator, debug_info::DebugInfo, file_group::FileGroupIr, function,
+        type_table::TypeTable,
+    },
     module_group::ModuleGroup,
     value::Global,
 };
@@ -20,6 +24,19 @@ pub struct FileIr<'ink> {
     pub function_definitions: HashSet<mun_hir::Function>,
     /// The types defined in this file
     pub type_definitions: HashSet<mun_hir::Ty>,
+    /// The statics defined in this file
+    pub static_definitions: HashSet<mun_hir::Static>,
+    /// The function that is invoked after the assembly has been loaded
+    pub init_fn: Option<mun_hir::Function>,
+    /// The function that is invoked before the assembly is unloaded
+    pub shutdown_fn: Option<mun_hir::Function>,
+    /// The functions marked with `#[test]`, in a deterministic order
+    pub tests: Vec<mun_hir::Function>,
+}
+
+/// Returns the name of the LLVM global that holds the storage of a static.
+pub(crate) fn static_global_name(db: &dyn mun_hir::HirDatabase, s: mun_hir::Static) -> String {
+    format!("static::<{}>", s.full_name(db))
 }
 
 /// Generates IR for the specified file.
@@ -33,10 +50,12 @@ pub(crate) fn gen_file_ir<'ink>(
     let hir_types = &code_gen.hir_types;
 
     // Generate all exposed function and wrapper function signatures.
-    // Use a `BTreeMap` to guarantee deterministically ordered output.ures
+    // Use a `BTreeMap` to guarantee deterministically ordered output.
     let mut functions = HashMap::new();
     let mut type_definitions = HashSet::new();
     let mut wrapper_functions = BTreeMap::new();
+    let mut trampoline_functions = BTreeMap::new();
+    let mut statics = HashMap::new();
     for def in module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
@@ -47,24 +66,45 @@ pub(crate) fn gen_file_ir<'ink>(
                 functions.insert(f, fun);
 
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
-                if f.visibility(code_gen.db).is_externally_visible()
-                    && !fn_sig.marshallable(code_gen.db)
-                {
-                    let wrapper_fun = function::gen_public_prototype(
-                        code_gen.db,
-                        &code_gen.hir_types,
-                        f,
-                        &llvm_module,
-                    );
-                    wrapper_functions.insert(f, wrapper_fun);
+                if f.visibility(code_gen.db).is_externally_visible() {
+                    if !fn_sig.marshallable(code_gen.db) {
+                        let wrapper_fun = function::gen_public_prototype(
+                            code_gen.db,
+                            &code_gen.hir_types,
+                            f,
+                            &llvm_module,
+                        );
+                        wrapper_functions.insert(f, wrapper_fun);
+                    }
+
+                    if code_gen.dynamic_invocation {
+                        let trampoline_fun =
+                            function::gen_trampoline_prototype(code_gen.db, f, &llvm_module);
+                        trampoline_functions.insert(f, trampoline_fun);
+                    }
                 }
             }
         }
         if let ModuleDef::Struct(s) = def {
             type_definitions.insert(s.ty(code_gen.db));
         }
+        if let ModuleDef::Static(s) = def {
+            // Allocate storage for the static, initialized with its initial value
+            let value = s
+                .initializer(code_gen.db)
+                .expect("cannot generate code for a static that failed to evaluate");
+            let initial_value = gen_const_value(code_gen.context, value);
+            let global = llvm_module.add_global(
+                initial_value.get_type(),
+                None,
+                &static_global_name(code_gen.db, s),
+            );
+            global.set_initializer(&initial_value);
+            statics.insert(s, global);
+        }
     }
 
+    let static_definitions = statics.keys().copied().collect();
     let external_globals = {
         let alloc_handle = group_ir
             .allocator_handle_type
@@ -82,14 +122,33 @@ pub(crate) fn gen_file_ir<'ink>(
             alloc_handle,
             dispatch_table,
             type_table: type_table.map(|g| unsafe { Global::from_raw(g) }),
+            statics,
         }
     };
 
     // Construct requirements for generating the bodies
     let fn_pass_manager = function::create_pass_manager(&llvm_module, code_gen.optimization_level);
+    let debug_info = code_gen
+        .debug_info
+        .then(|| {
+            module_group
+                .iter()
+                .find_map(|module| module.file_id(code_gen.db))
+        })
+        .flatten()
+        .map(|file_id| {
+            DebugInfo::new(
+                code_gen.db,
+                code_gen.context,
+                &llvm_module,
+                file_id,
+                code_gen.optimization_level != inkwell::OptimizationLevel::None,
+            )
+        });
 
-    // Generate the function bodies
-    for (hir_function, llvm_function) in functions.iter() {
+    // Generate the function bodies. Sort them to guarantee deterministically
+    // ordered output.
+    for (hir_function, llvm_function) in functions.iter().sorted_by_key(|(f, _)| **f) {
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
             code_gen.db,
@@ -100,10 +159,12 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            &llvm_module,
+            code_gen.overflow_checks,
+            debug_info.as_ref(),
         );
 
         code_gen.gen_fn_body();
-        fn_pass_manager.run_on(llvm_function);
     }
 
     for (hir_function, llvm_function) in wrapper_functions.iter() {
@@ -117,9 +178,47 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            &llvm_module,
+            code_gen.overflow_checks,
+            None,
         );
 
         code_gen.gen_fn_wrapper();
+    }
+
+    // Generate the trampolines, which call the function that is exposed to the host
+    for (hir_function, llvm_function) in trampoline_functions.iter() {
+        let target = wrapper_functions
+            .get(hir_function)
+            .unwrap_or_else(|| &functions[hir_function]);
+        let mut code_gen = BodyIrGenerator::new(
+            code_gen.context,
+            code_gen.db,
+            (*hir_function, *llvm_function),
+            &functions,
+            &group_ir.dispatch_table,
+            &group_ir.type_table,
+            external_globals.clone(),
+            &code_gen.hir_types,
+            module_group,
+            &llvm_module,
+            code_gen.overflow_checks,
+            None,
+        );
+
+        code_gen.gen_fn_trampoline(*target);
+    }
+
+    // The debug information must be complete before the functions are optimized
+    if let Some(debug_info) = &debug_info {
+        debug_info.finalize();
+    }
+    for (_, llvm_function) in functions
+        .iter()
+        .sorted_by_key(|(f, _)| **f)
+        .chain(wrapper_functions.iter())
+        .chain(trampoline_functions.iter())
+    {
         fn_pass_manager.run_on(llvm_function);
     }
 
@@ -130,9 +229,32 @@ pub(crate) fn gen_file_ir<'ink>(
         .filter(|&f| module_group.should_export_fn(code_gen.db, f))
         .collect();
 
+    // Find the lifecycle hooks that the runtime invokes on load and unload
+    let lifecycle_hook = |hook| {
+        functions
+            .keys()
+            .copied()
+            .filter(|f| f.lifecycle_hook(code_gen.db) == Some(hook))
+            .min()
+    };
+    let init_fn = lifecycle_hook(LifecycleHook::Init);
+    let shutdown_fn = lifecycle_hook(LifecycleHook::Shutdown);
+
+    // Find the tests that `mun test` invokes
+    let tests = functions
+        .keys()
+        .copied()
+        .filter(|f| f.is_test(code_gen.db))
+        .sorted()
+        .collect();
+
     FileIr {
         llvm_module,
         function_definitions,
         type_definitions,
+        static_definitions,
+        init_fn,
+        shutdown_fn,
+        tests,
     }
 }
@@ -57,6 +57,7 @@ pub(crate) fn gen_file_group_ir<'ink>(
                     code_gen.db,
                     &mut intrinsics_map,
                     &mut needs_alloc,
+                    code_gen.overflow_checks,
                     &f.body(code_gen.db),
                     &f.infer(code_gen.db),
                 );
@@ -78,6 +79,9 @@ pub(crate) fn gen_file_group_ir<'ink>(
             | ModuleDef::Struct(_)
             | ModuleDef::PrimitiveType(_)
             | ModuleDef::TypeAlias(_)
+            | ModuleDef::Const(_)
+            | ModuleDef::Static(_)
+            | ModuleDef::BuiltinFunction(_)
             | ModuleDef::Function(_) => (),
         }
     }
@@ -140,7 +144,12 @@ pub(crate) fn gen_file_group_ir<'ink>(
             ModuleDef::Function(f) => {
                 type_table_builder.collect_fn(f);
             }
-            ModuleDef::PrimitiveType(_) | ModuleDef::TypeAlias(_) | ModuleDef::Module(_) => (),
+            ModuleDef::PrimitiveType(_)
+            | ModuleDef::TypeAlias(_)
+            | ModuleDef::Const(_)
+            | ModuleDef::Static(_)
+            | ModuleDef::BuiltinFunction(_)
+            | ModuleDef::Module(_) => (),
         }
     }
 
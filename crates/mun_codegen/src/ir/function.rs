@@ -1,8 +1,10 @@
 use inkwell::{
+    attributes::{Attribute, AttributeLoc},
     passes::{PassManager, PassManagerBuilder},
     values::FunctionValue,
+    AddressSpace,
 };
-use mun_hir::HirDatabase;
+use mun_hir::{HirDatabase, InlineAttr};
 
 use crate::{ir::ty::HirTypeCache, Module, OptimizationLevel};
 
@@ -35,7 +37,22 @@ pub(crate) fn gen_prototype<'db, 'ink>(
 ) -> FunctionValue<'ink> {
     let name = func.name(db).to_string();
     let ir_ty = types.get_function_type(func);
-    module.add_function(&name, ir_ty, None)
+    let value = module.add_function(&name, ir_ty, None);
+
+    // Pass the inlining hint of the function on to LLVM
+    if let Some(inline) = func.data(db.upcast()).attrs().inline() {
+        let attribute_name = match inline {
+            InlineAttr::Hint => "inlinehint",
+            InlineAttr::Always => "alwaysinline",
+            InlineAttr::Never => "noinline",
+        };
+        let attribute = module
+            .get_context()
+            .create_enum_attribute(Attribute::get_named_enum_kind_id(attribute_name), 0);
+        value.add_attribute(AttributeLoc::Function, attribute);
+    }
+
+    value
 }
 
 /// Generates a `FunctionValue` for a `mun_hir::Function` that is usable from
@@ -54,3 +71,25 @@ pub(crate) fn gen_public_prototype<'db, 'ink>(
     let ir_ty = types.get_public_function_type(func);
     module.add_function(&name, ir_ty, None)
 }
+
+/// Generates a `FunctionValue` for the trampoline of a `mun_hir::Function`,
+/// which enables calling the function without knowing its signature at compile
+/// time. The trampoline receives a pointer to an array of pointers to the
+/// arguments and a pointer to which the return value is written. Its body is
+/// generated by `gen_fn_trampoline`.
+pub(crate) fn gen_trampoline_prototype<'ink>(
+    db: &dyn HirDatabase,
+    func: mun_hir::Function,
+    module: &Module<'ink>,
+) -> FunctionValue<'ink> {
+    let context = module.get_context();
+    let ptr_type = context.i8_type().ptr_type(AddressSpace::default());
+    let ir_ty = context.void_type().fn_type(
+        &[
+            ptr_type.ptr_type(AddressSpace::default()).into(),
+            ptr_type.into(),
+        ],
+        false,
+    );
+    module.add_function(&format!("{}_trampoline", func.name(db)), ir_ty, None)
+}
@@ -1,7 +1,10 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use inkwell::{context::Context, targets::TargetData, types::FunctionType};
-use mun_hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, ValueNs};
+use mun_hir::{
+    ArithOp, BinaryOp, Body, BuiltinFunction, Expr, ExprId, HirDatabase, InferenceResult, TyKind,
+    ValueNs,
+};
 
 use crate::{
     intrinsics::{self, Intrinsic},
@@ -24,6 +27,17 @@ fn collect_intrinsic<'ink>(
         .or_insert_with(|| intrinsic.ir_type(context, target));
 }
 
+/// Returns true if the integer operation `op` can overflow.
+fn can_overflow(op: BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::ArithOp(ArithOp::Add | ArithOp::Subtract | ArithOp::Multiply)
+            | BinaryOp::Assignment {
+                op: Some(ArithOp::Add | ArithOp::Subtract | ArithOp::Multiply)
+            }
+    )
+}
+
 /// Iterates over all expressions and stores information on which intrinsics
 /// they use in `entries`.
 #[allow(clippy::too_many_arguments)]
@@ -33,14 +47,35 @@ fn collect_expr<'ink>(
     db: &'_ dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    overflow_checks: bool,
     expr_id: ExprId,
     body: &Arc<Body>,
     infer: &InferenceResult,
 ) {
     let expr = &body[expr_id];
 
+    // Integer arithmetic that overflows panics if overflow checks are enabled
+    if overflow_checks {
+        if let Expr::BinaryOp {
+            lhs, op: Some(op), ..
+        } = expr
+        {
+            let is_int = matches!(infer[*lhs].interned(), TyKind::Int(_));
+            if is_int && can_overflow(*op) {
+                collect_intrinsic(context, target, &intrinsics::panic, intrinsics);
+            }
+        }
+    }
+
+    // Builtin functions are implemented by the runtime through intrinsics, except
+    // for the math functions which are lowered to LLVM intrinsics
+    if let Some(builtin) = infer.builtin_call(expr_id) {
+        if matches!(builtin, BuiltinFunction::Panic | BuiltinFunction::Assert) {
+            collect_intrinsic(context, target, &intrinsics::panic, intrinsics);
+        }
+    }
     // If this expression is a call, store it in the dispatch table
-    if let Expr::Call { callee, .. } = expr {
+    else if let Expr::Call { callee, .. } = expr {
         match infer[*callee].as_callable_def() {
             Some(mun_hir::CallableDef::Struct(_)) => {
                 collect_intrinsic(context, target, &intrinsics::new, intrinsics);
@@ -69,6 +104,11 @@ fn collect_expr<'ink>(
         }
     }
 
+    // Indexing into an array panics if the index is out of bounds
+    if let Expr::Index { .. } = expr {
+        collect_intrinsic(context, target, &intrinsics::panic, intrinsics);
+    }
+
     if let Expr::Array(_) = expr {
         collect_intrinsic(context, target, &intrinsics::new_array, intrinsics);
         *needs_alloc = true;
@@ -82,6 +122,7 @@ fn collect_expr<'ink>(
             db,
             intrinsics,
             needs_alloc,
+            overflow_checks,
             expr_id,
             body,
             infer,
@@ -90,12 +131,14 @@ fn collect_expr<'ink>(
 }
 
 /// Collects all intrinsics from the specified `body`.
+#[allow(clippy::too_many_arguments)]
 pub fn collect_fn_body<'ink>(
     context: &'ink Context,
     target: TargetData,
     db: &dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    overflow_checks: bool,
     body: &Arc<Body>,
     infer: &InferenceResult,
 ) {
@@ -105,6 +148,7 @@ pub fn collect_fn_body<'ink>(
         db,
         intrinsics,
         needs_alloc,
+        overflow_checks,
         body.body_expr(),
         body,
         infer,
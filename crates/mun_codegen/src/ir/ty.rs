@@ -237,7 +237,7 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
     /// compatible.
     pub fn get_public_function_type(&self, ty: mun_hir::Function) -> FunctionType<'ink> {
         let ty = self.db.callable_sig(ty.into());
-        let param_tys: Vec<_> = ty
+        let mut param_tys: Vec<_> = ty
             .params()
             .iter()
             .map(|p| {
@@ -247,7 +247,21 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             })
             .collect();
 
+        // A value struct is returned by writing it to a buffer provided by the
+        // caller, instead of relying on the platform specific conventions for
+        // returning aggregates.
         let return_type = ty.ret();
+        if ty.returns_value_struct(self.db) {
+            let hir_struct = return_type
+                .as_struct()
+                .expect("expected a struct return type");
+            let return_slot_type = self
+                .get_struct_type(hir_struct)
+                .ptr_type(AddressSpace::default());
+            param_tys.insert(0, return_slot_type.into());
+            return self.context.void_type().fn_type(&param_tys, false);
+        }
+
         match return_type.interned() {
             TyKind::Tuple(0, _) => self.context.void_type().fn_type(&param_tys, false),
             _ => self
@@ -150,6 +150,7 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
         // If this expression is a call, store it in the dispatch table
         if let mun_hir::Expr::Call { callee, .. } = expr {
             match infer[*callee].as_callable_def() {
+                _ if infer.builtin_call(expr_id).is_some() => (),
                 Some(mun_hir::CallableDef::Function(hir_fn)) => {
                     self.maybe_collect_fn_signature(hir_fn);
                 }
@@ -1,13 +1,11 @@
 #[cfg(test)]
 mod test;
 
-use itertools::Itertools;
 use mun_abi as abi;
 use mun_codegen_macros::AsValue;
 
 use crate::value::{
-    AsValue, BytesOrPtr, Global, IrTypeContext, IrValueContext, SizedValueType, TransparentValue,
-    Value,
+    AsValue, BytesOrPtr, Global, IrTypeContext, IrValueContext, TransparentValue, Value,
 };
 
 impl<'ink> TransparentValue<'ink> for abi::Guid {
@@ -69,7 +67,7 @@ pub struct ArrayTypeId<'ink> {
 pub struct TypeDefinition<'ink> {
     pub name: Value<'ink, *const u8>,
     pub size_in_bits: u32,
-    pub alignment: u8,
+    pub alignment: u16,
     pub data: TypeDefinitionData<'ink>,
 }
 
@@ -96,6 +94,22 @@ pub struct FunctionPrototype<'ink> {
 pub struct FunctionDefinition<'ink> {
     pub prototype: FunctionPrototype<'ink>,
     pub fn_ptr: Value<'ink, *const fn()>,
+    pub trampoline_ptr: Value<'ink, *const fn()>,
+    pub docs: Value<'ink, *const u8>,
+    pub privacy: abi::Privacy,
+}
+
+#[derive(AsValue)]
+pub struct GlobalDefinition<'ink> {
+    pub name: Value<'ink, *const u8>,
+    pub type_id: TypeId<'ink>,
+    pub value_ptr: Value<'ink, *mut std::ffi::c_void>,
+}
+
+#[derive(AsValue)]
+pub struct TestDefinition<'ink> {
+    pub name: Value<'ink, *const u8>,
+    pub fn_ptr: Value<'ink, *const fn()>,
 }
 
 #[derive(AsValue)]
@@ -104,6 +118,7 @@ pub struct StructDefinition<'ink> {
     pub field_names: Value<'ink, *const *const u8>,
     pub field_types: Value<'ink, *const TypeId<'ink>>,
     pub field_offsets: Value<'ink, *const u16>,
+    pub docs: Value<'ink, *const u8>,
     pub num_fields: u16,
     pub memory_kind: abi::StructMemoryKind,
 }
@@ -113,8 +128,10 @@ pub struct ModuleInfo<'ink> {
     pub path: Value<'ink, *const u8>,
     pub functions: Value<'ink, *const FunctionDefinition<'ink>>,
     pub types: Value<'ink, *const TypeDefinition<'ink>>,
+    pub globals: Value<'ink, *const GlobalDefinition<'ink>>,
     pub num_functions: u32,
     pub num_types: u32,
+    pub num_globals: u32,
 }
 
 #[derive(AsValue)]
@@ -139,4 +156,10 @@ pub struct AssemblyInfo<'ink> {
     pub type_lut: TypeLut<'ink>,
     pub dependencies: Value<'ink, *const *const u8>,
     pub num_dependencies: u32,
+    pub init_fn: Value<'ink, *const fn()>,
+    pub shutdown_fn: Value<'ink, *const fn()>,
+    pub tests: Value<'ink, *const TestDefinition<'ink>>,
+    pub num_tests: u32,
+    pub version: Value<'ink, *const u8>,
+    pub dependency_versions: Value<'ink, *const *const u8>,
 }
@@ -1,7 +1,8 @@
 pub use inkwell::{builder::Builder, context::Context, module::Module, OptimizationLevel};
 
 pub use crate::{
-    assembly::{AssemblyIr, TargetAssembly},
+    assembly::{AssemblyIr, AssemblyTimings, TargetAssembly},
+    backend::Backend,
     code_gen::AssemblyBuilder,
     db::{CodeGenDatabase, CodeGenDatabaseStorage},
     module_group::ModuleGroup,
@@ -15,9 +16,10 @@ mod db;
 #[macro_use]
 mod ir;
 mod assembly;
+mod backend;
 
-#[cfg(test)]
-mod mock;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
 #[cfg(test)]
 mod test;
 
@@ -28,4 +30,5 @@ pub(crate) mod intrinsics;
 mod linker;
 mod module_group;
 mod module_partition;
+mod object_file;
 pub(crate) mod type_info;
@@ -88,6 +88,9 @@ impl Linker for LdLinker {
         // Link as dynamic library
         self.args.push("--shared".to_owned());
 
+        // Allows the runtime to unwind the stack through Mun code when it panics
+        self.args.push("--eh-frame-hdr".to_owned());
+
         // Specify output path
         self.args.push("-o".to_owned());
         self.args.push(path_str.to_owned());
@@ -238,6 +241,8 @@ impl Linker for MsvcLinker {
 
         self.args.push("/DLL".to_owned());
         self.args.push("/NOENTRY".to_owned());
+        // Don't embed a timestamp so identical inputs produce identical output
+        self.args.push("/Brepro".to_owned());
         self.args.push(format!("/EXPORT:{}", abi::GET_INFO_FN_NAME));
         self.args
             .push(format!("/EXPORT:{}", abi::GET_VERSION_FN_NAME));
@@ -1,6 +1,29 @@
+//! A mock database that implements all the queries of this crate. It is
+//! available to other crates through the `test-support` feature, which makes it
+//! possible to unit test compiler extensions against the real query
+//! infrastructure.
+//!
+//! Databases are most easily constructed from a fixture through the
+//! [`WithFixture`] trait:
+//!
+//! ```
+//! use mun_codegen::mock::{MockDatabase, WithFixture};
+//!
+//! let db = MockDatabase::with_files(
+//!     r#"
+//! //- /foo.mun
+//! pub fn foo() -> i32 { 5 }
+//!
+//! //- /mod.mun
+//! fn main() -> i32 { foo::foo() }
+//! "#,
+//! );
+//! ```
+
 use std::sync::Arc;
 
-use mun_hir::HirDatabase;
+use mun_hir::{DefDatabase, HirDatabase, LintLevels};
+pub use mun_hir_input::WithFixture;
 use mun_hir_input::{FileId, PackageSet, SourceDatabase, SourceRoot, SourceRootId};
 use mun_paths::RelativePathBuf;
 use mun_target::spec::Target;
@@ -8,7 +31,7 @@ use parking_lot::Mutex;
 
 use crate::{
     db::{CodeGenDatabase, CodeGenDatabaseStorage},
-    OptimizationLevel,
+    Backend, OptimizationLevel,
 };
 
 /// A mock implementation of the IR database. It can be used to set up a simple
@@ -21,7 +44,7 @@ use crate::{
     mun_hir::HirDatabaseStorage,
     CodeGenDatabaseStorage
 )]
-pub(crate) struct MockDatabase {
+pub struct MockDatabase {
     storage: salsa::Storage<Self>,
     events: Mutex<Option<Vec<salsa::Event>>>,
 }
@@ -72,13 +95,21 @@ impl Default for MockDatabase {
             events: Mutex::default(),
         };
         db.set_optimization_level(OptimizationLevel::Default);
+        db.set_overflow_checks(false);
+        db.set_debug_info(false);
+        db.set_dynamic_invocation(false);
+        db.set_backend(Backend::default());
         db.set_target(Target::host_target().unwrap());
+        db.set_cfg_options(Arc::default());
+        // Lints don't affect code generation
+        db.set_lint_levels(Arc::new(LintLevels::allow_all()));
         db
     }
 }
 
 impl MockDatabase {
-    /// Creates a database from the given text.
+    /// Creates a database from the given text with a single file and without
+    /// optimizations.
     pub fn with_single_file(text: &str) -> (MockDatabase, FileId) {
         let mut db = MockDatabase::default();
 
@@ -102,12 +133,16 @@ impl MockDatabase {
         (db, file_id)
     }
 
+    /// Runs `f` and returns all the salsa events that occurred while it ran.
     pub fn log(&self, f: impl FnOnce()) -> Vec<salsa::Event> {
         *self.events.lock() = Some(Vec::new());
         f();
         self.events.lock().take().unwrap()
     }
 
+    /// Runs `f` and returns a description of every query that was executed
+    /// while it ran. This is useful to verify that a change does or doesn't
+    /// trigger recomputation.
     pub fn log_executed(&self, f: impl FnOnce()) -> Vec<String> {
         let events = self.log(f);
         events
@@ -125,8 +125,23 @@ impl ModuleGroup {
             .filter_map(move |module| module.file_id(db))
     }
 
-    /// Returns the filename for this module group
+    /// Returns the path of the assembly of this module group relative to the
+    /// output directory, without an extension. The layout mirrors the module
+    /// tree: the assembly of `a::b` is stored as `a/b` and the assembly of the
+    /// root module as `mod`. Since `mod` is never a valid module name, these
+    /// paths cannot collide.
     pub fn relative_file_path(&self) -> mun_paths::RelativePathBuf {
-        mun_paths::RelativePathBuf::from(self.name.replace("::", "$"))
+        mun_paths::RelativePathBuf::from(self.name.replace("::", "/"))
+    }
+
+    /// Returns the path of the assembly of `other` relative to the directory
+    /// that contains the assembly of this module group, without an extension.
+    /// This is how assemblies refer to their dependencies.
+    pub fn relative_path_to(&self, other: &ModuleGroup) -> mun_paths::RelativePathBuf {
+        let file_path = self.relative_file_path();
+        let directory = file_path
+            .parent()
+            .unwrap_or_else(|| mun_paths::RelativePath::new(""));
+        directory.relative(other.relative_file_path())
     }
 }
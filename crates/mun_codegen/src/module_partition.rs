@@ -5,6 +5,8 @@ use rustc_hash::FxHashMap;
 
 use crate::{CodeGenDatabase, ModuleGroup};
 
+mod graphviz;
+
 /// A `ModuleGroupId` refers to a single [`ModuleGroup`] in a
 /// [`ModulePartition`]
 #[derive(Default, PartialEq, Eq, Clone, Debug, Hash, PartialOrd, Ord, Copy)]
@@ -0,0 +1,108 @@
+//! Renders a [`ModulePartition`] as a graph in the DOT language, which shows how
+//! functions are grouped into assemblies and which calls cross the boundary of
+//! an assembly.
+
+use std::{collections::BTreeSet, fmt::Write};
+
+use mun_hir::{Body, CallableDef, Expr, ExprId, HirDatabase, InferenceResult, ModuleDef};
+
+use super::ModulePartition;
+
+impl ModulePartition {
+    /// Renders the partition in the DOT language of `GraphViz`.
+    ///
+    /// Every module group is drawn as a cluster that contains the functions
+    /// defined in the group. Calls between functions are drawn as edges. Calls
+    /// that are linked at runtime through the dispatch table of the calling
+    /// group, because the callee is defined in another group or by the host,
+    /// are dashed. Extern functions are drawn with a dashed border.
+    pub fn to_dot(&self, db: &dyn HirDatabase) -> String {
+        let mut dot = String::from("digraph modules {\n    node [shape = box];\n");
+
+        // Calls are collected as `(caller, callee, dispatched)` and written after all
+        // clusters, so an edge never causes a node to be placed in the wrong cluster.
+        let mut calls = BTreeSet::new();
+        for (group_id, group) in self.iter() {
+            writeln!(dot, "\n    subgraph cluster_{} {{", group_id.0).unwrap();
+            writeln!(dot, "        label = {};", quote(&group.name)).unwrap();
+
+            let mut functions: Vec<_> = group
+                .iter()
+                .flat_map(|module| module.declarations(db))
+                .filter_map(|def| match def {
+                    ModuleDef::Function(function) => Some(function),
+                    _ => None,
+                })
+                .map(|function| (function.full_name(db), function))
+                .collect();
+            functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            for (name, function) in functions {
+                if function.is_extern(db) {
+                    writeln!(dot, "        {} [style = dashed];", quote(&name)).unwrap();
+                    continue;
+                }
+                writeln!(dot, "        {};", quote(&name)).unwrap();
+
+                let body = function.body(db);
+                let infer = function.infer(db);
+                collect_calls(&body, &infer, body.body_expr(), &mut |callee| {
+                    calls.insert((
+                        name.clone(),
+                        callee.full_name(db),
+                        group.should_runtime_link_fn(db, callee),
+                    ));
+                });
+            }
+
+            dot.push_str("    }\n");
+        }
+
+        if !calls.is_empty() {
+            dot.push('\n');
+        }
+        for (caller, callee, dispatched) in calls {
+            let attributes = if dispatched {
+                " [style = dashed, label = \"dispatch\"]"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    {} -> {}{};",
+                quote(&caller),
+                quote(&callee),
+                attributes
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Calls `f` for every function that is called from `expr` or one of its sub
+/// expressions. Calls to builtin functions and struct constructors are
+/// skipped, since those are never linked.
+fn collect_calls(
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+    f: &mut impl FnMut(mun_hir::Function),
+) {
+    if let Expr::Call { callee, .. } = &body[expr] {
+        if infer.builtin_call(expr).is_none() {
+            if let Some(CallableDef::Function(function)) = infer[*callee].as_callable_def() {
+                f(function);
+            }
+        }
+    }
+
+    body[expr].walk_child_exprs(|child| collect_calls(body, infer, child, f));
+}
+
+/// Returns `id` as a quoted DOT identifier.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -1,31 +1,24 @@
 use std::{io::Write, path::Path};
 
-use inkwell::targets::{FileType, TargetMachine};
 use mun_target::spec;
 use tempfile::NamedTempFile;
 
 use crate::{code_gen::CodeGenerationError, linker};
 
+/// An object file with the machine code of a module group, generated by one of
+/// the backends.
 pub struct ObjectFile {
     target: spec::Target,
     obj_file: NamedTempFile,
 }
 
 impl ObjectFile {
-    /// Constructs a new object file from the specified `module` for `target`
-    pub fn new(
-        target: &spec::Target,
-        target_machine: &TargetMachine,
-        module: &inkwell::module::Module<'_>,
-    ) -> Result<Self, anyhow::Error> {
-        let obj = target_machine
-            .write_to_memory_buffer(module, FileType::Object)
-            .map_err(|e| CodeGenerationError::MachineCodeError(e.to_string()))?;
-
+    /// Constructs a new object file for `target` with the specified contents
+    pub fn new(target: &spec::Target, contents: &[u8]) -> Result<Self, anyhow::Error> {
         let mut obj_file = tempfile::NamedTempFile::new()
             .map_err(CodeGenerationError::CouldNotCreateObjectFile)?;
         obj_file
-            .write(obj.as_slice())
+            .write_all(contents)
             .map_err(CodeGenerationError::CouldNotCreateObjectFile)?;
 
         Ok(Self {
@@ -19,7 +19,7 @@ fn array_index_assign() {
         "array_index_assign",
         r"
     pub fn main() {
-        let a = [1,2,3,4,]
+        let mut a = [1,2,3,4,]
         a[1] = 100
     }
     ",
@@ -69,6 +69,56 @@ fn multi_file() {
     );
 }
 
+#[test]
+fn module_partition_dot() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        foo::get_value() + local()
+    }
+
+    fn local() -> i32 {
+        extern_value()
+    }
+
+    extern fn extern_value() -> i32;
+
+    //- /foo.mun
+    pub(super) fn get_value() -> i32 {
+        3
+    }
+    ",
+    );
+
+    let dot = db.module_partition().to_dot(db.upcast());
+    assert!(dot.starts_with("digraph modules {\n"));
+    assert!(dot.contains(
+        r#"
+        label = "mod";
+        "extern_value" [style = dashed];
+        "local";
+        "main";
+    }
+"#
+    ));
+    assert!(dot.contains(
+        r#"
+        label = "foo";
+        "foo::get_value";
+    }
+"#
+    ));
+    assert!(dot.ends_with(
+        r#"
+    "local" -> "extern_value" [style = dashed, label = "dispatch"];
+    "main" -> "foo::get_value" [style = dashed, label = "dispatch"];
+    "main" -> "local";
+}
+"#
+    ));
+}
+
 #[test]
 fn issue_262() {
     test_snapshot(
@@ -240,7 +290,7 @@ fn assignment_op_bool() {
     test_snapshot(
         "assignment_op_bool",
         r#"
-    pub fn assign(a: bool, b: bool) -> bool {
+    pub fn assign(mut a: bool, b: bool) -> bool {
         a = b;
         a
     }
@@ -277,12 +327,12 @@ fn assignment_op_struct() {
     pub struct(value) Value(i32, i32);
     pub struct(gc) Heap(f64, f64);
 
-    pub fn assign_value(a: Value, b: Value) -> Value {
+    pub fn assign_value(mut a: Value, b: Value) -> Value {
         a = b;
         a
     }
 
-    pub fn assign_heap(a: Heap, b: Heap) -> Heap {
+    pub fn assign_heap(mut a: Heap, b: Heap) -> Heap {
         a = b;
         a
     }
@@ -307,27 +357,27 @@ macro_rules! test_number_operator_types {
                     test_snapshot(
                         &format!("assignment_op_{ty}", ty = stringify!($ty)),
                         &format!(r#"
-    pub fn assign(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign(mut a: {ty}, b: {ty}) -> {ty} {{
         a = b;
         a
     }}
-    pub fn assign_add(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_add(mut a: {ty}, b: {ty}) -> {ty} {{
         a += b;
         a
     }}
-    pub fn assign_subtract(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_subtract(mut a: {ty}, b: {ty}) -> {ty} {{
         a -= b;
         a
     }}
-    pub fn assign_multiply(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_multiply(mut a: {ty}, b: {ty}) -> {ty} {{
         a *= b;
         a
     }}
-    pub fn assign_divide(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_divide(mut a: {ty}, b: {ty}) -> {ty} {{
         a /= b;
         a
     }}
-    pub fn assign_remainder(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_remainder(mut a: {ty}, b: {ty}) -> {ty} {{
         a %= b;
         a
     }}
@@ -415,15 +465,15 @@ macro_rules! test_bit_operator_types  {
                     test_snapshot(
                         &format!("assign_bit_op_{ty}", ty = stringify!($ty)),
                         &format!(r#"
-    pub fn assign_bitand(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_bitand(mut a: {ty}, b: {ty}) -> {ty} {{
         a &= b;
         a
     }}
-    pub fn assign_bitor(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_bitor(mut a: {ty}, b: {ty}) -> {ty} {{
         a |= b;
         a
     }}
-    pub fn assign_bitxor(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_bitxor(mut a: {ty}, b: {ty}) -> {ty} {{
         a ^= b;
         a
     }}
@@ -461,11 +511,11 @@ macro_rules! test_shift_operator_types  {
                     test_snapshot(
                         &format!("assign_shift_op_{ty}", ty = stringify!($ty)),
                         &format!(r#"
-    pub fn assign_leftshift(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_leftshift(mut a: {ty}, b: {ty}) -> {ty} {{
         a <<= b;
         a
     }}
-    pub fn assign_rightshift(a: {ty}, b: {ty}) -> {ty} {{
+    pub fn assign_rightshift(mut a: {ty}, b: {ty}) -> {ty} {{
         a >>= b;
         a
     }}
@@ -503,6 +553,30 @@ fn let_statement() {
     );
 }
 
+#[test]
+fn destructuring() {
+    test_snapshot(
+        "destructuring",
+        r#"
+    pub struct(value) Vec2 { x: f32, y: f32 }
+    pub struct Pair(i32, i32);
+
+    pub fn length_squared(Vec2 { x, y: y_coord }: Vec2) -> f32 {
+        x * x + y_coord * y_coord
+    }
+
+    pub fn sum(pair: Pair) -> i32 {
+        let Pair(a, b) = pair;
+        a + b
+    }
+
+    pub fn first(Pair(a, _): Pair) -> i32 {
+        a
+    }
+    "#,
+    );
+}
+
 #[test]
 fn invalid_binary_ops() {
     test_snapshot(
@@ -522,31 +596,31 @@ fn update_operators() {
         "update_operators",
         r#"
     pub fn add(a:i32, b:i32) -> i32 {
-      let result = a
+      let mut result = a
       result += b
       result
     }
 
     pub fn subtract(a:i32, b:i32) -> i32 {
-      let result = a
+      let mut result = a
       result -= b
       result
     }
 
     pub fn multiply(a:i32, b:i32) -> i32 {
-      let result = a
+      let mut result = a
       result *= b
       result
     }
 
     pub fn divide(a:i32, b:i32) -> i32 {
-      let result = a
+      let mut result = a
       result /= b
       result
     }
 
     pub fn remainder(a:i32, b:i32) -> i32 {
-      let result = a
+      let mut result = a
       result %= b
       result
     }
@@ -559,7 +633,7 @@ fn update_parameter() {
     test_snapshot(
         "update_parameter",
         r#"
-    pub fn add_three(a:i32) -> i32 {
+    pub fn add_three(mut a:i32) -> i32 {
       a += 3;
       a
     }
@@ -648,9 +722,9 @@ fn fibonacci_loop() {
         "fibonacci_loop",
         r#"
     pub fn fibonacci(n:i32) -> i32 {
-        let a = 0;
-        let b = 1;
-        let i = 1;
+        let mut a = 0;
+        let mut b = 1;
+        let mut i = 1;
         loop {
             if i > n {
                 return a
@@ -665,6 +739,61 @@ fn fibonacci_loop() {
     );
 }
 
+#[test]
+fn attributes() {
+    test_snapshot(
+        "attributes",
+        r#"
+    #[inline]
+    fn add(a: i32, b: i32) -> i32 { a + b }
+
+    #[inline(always)]
+    fn sub(a: i32, b: i32) -> i32 { a - b }
+
+    #[inline(never)]
+    fn mul(a: i32, b: i32) -> i32 { a * b }
+
+    #[export_name = "calculate"]
+    pub fn main(a: i32, b: i32) -> i32 {
+        mul(add(a, b), sub(a, b))
+    }
+    "#,
+    );
+}
+
+#[test]
+fn tail_call() {
+    test_snapshot(
+        "tail_call",
+        r#"
+    pub struct Foo { a: i32 }
+
+    pub fn fibonacci(n: i32, a: i32, b: i32) -> i32 {
+        if n == 0 {
+            return a;
+        }
+        fibonacci(n - 1, b, a + b)
+    }
+
+    pub fn sum(n: i32, acc: i32) -> i32 {
+        if n == 0 {
+            acc
+        } else {
+            return sum(n - 1, acc + n)
+        }
+    }
+
+    pub fn count(Foo { a }: Foo) -> i32 {
+        if a == 0 { 0 } else { count(Foo { a: a - 1 }) }
+    }
+
+    pub fn not_a_tail_call(n: i32) -> i32 {
+        if n == 0 { 0 } else { 1 + not_a_tail_call(n - 1) }
+    }
+    "#,
+    );
+}
+
 #[test]
 fn loop_issue_llvm13() {
     // A bug was surfaced by switching to LLVM13. When using a loop in code an exit
@@ -782,7 +911,7 @@ fn loop_break_expr() {
     test_snapshot(
         "loop_break_expr",
         r#"
-    pub fn foo(n:i32) -> i32 {
+    pub fn foo(mut n:i32) -> i32 {
         loop {
             if n > 5 {
                 break n;
@@ -797,12 +926,61 @@ fn loop_break_expr() {
     );
 }
 
+#[test]
+fn builtin_panic_and_assert() {
+    test_snapshot(
+        "builtin_panic_and_assert",
+        r#"
+    pub fn foo(n: i32) -> i32 {
+        assert(n > 0, "n must be positive");
+        if n > 10 {
+            panic("n is too large")
+        } else {
+            n
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn builtin_math() {
+    test_snapshot(
+        "builtin_math",
+        r#"
+    pub fn trig(a: f32) -> f32 { sin(a) * cos(a) }
+    pub fn length(x: f64, y: f64) -> f64 { sqrt(x * x + y * y) }
+    pub fn abs_int(a: i32) -> i32 { abs(a) }
+    pub fn abs_float(a: f64) -> f64 { abs(a) }
+    pub fn clamp(a: u8, lo: u8, hi: u8) -> u8 { min(max(a, lo), hi) }
+    pub fn smallest(a: i64, b: i64) -> i64 { min(a, b) }
+    pub fn largest(a: f32, b: f32) -> f32 { max(a, b) }
+    "#,
+    );
+}
+
+#[test]
+fn overflow_checks() {
+    test_snapshot_with_overflow_checks(
+        "overflow_checks",
+        r#"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn sub(a: u8, b: u8) -> u8 { a - b }
+    pub fn mul_assign(mut a: i64, b: i64) -> i64 {
+        a *= b;
+        a
+    }
+    pub fn div(a: i32, b: i32) -> i32 { a / b }
+    "#,
+    );
+}
+
 #[test]
 fn while_expr() {
     test_snapshot(
         "while_expr",
         r#"
-    pub fn foo(n:i32) {
+    pub fn foo(mut n:i32) {
         while n<3 {
             n += 1;
         };
@@ -887,7 +1065,7 @@ fn gc_struct() {
     struct(gc) Foo { a: i32, b: i32 };
 
     pub fn foo() {
-        let a = Foo { a: 3, b: 4 };
+        let mut a = Foo { a: 3, b: 4 };
         a.b += 3;
         let b = a;
     }
@@ -908,6 +1086,46 @@ fn extern_fn() {
     );
 }
 
+#[test]
+fn const_use() {
+    test_snapshot(
+        "const_use",
+        r#"
+    const SCALE: i32 = 3 * 4;
+    const HALF: f32 = 1.0 / 2.0;
+    pub fn scale(a: i32) -> i32 {
+        a * SCALE
+    }
+    pub fn half() -> f32 {
+        HALF
+    }
+    "#,
+    );
+}
+
+#[test]
+fn const_eval_error_does_not_panic() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    const OVERFLOW: i8 = 100 * 2;
+    pub fn main() -> i8 {
+        OVERFLOW
+    }
+    "#,
+    );
+
+    // The evaluation error is reported as a diagnostic, code generation must
+    // not panic on it.
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let module_partition = db.module_partition();
+    for (_, module_group) in module_partition.iter() {
+        let group_ir = gen_file_group_ir(&code_gen, module_group);
+        gen_file_ir(&code_gen, &group_ir, module_group);
+    }
+}
+
 #[test]
 fn private_fn_only() {
     test_snapshot(
@@ -1070,6 +1288,91 @@ fn nested_private_recursive_fn_with_args() {
     );
 }
 
+#[test]
+fn debug_info() {
+    let mut db = MockDatabase::with_files(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 {
+        let sum = a + b;
+        sum
+    }
+    ",
+    );
+    db.set_optimization_level(OptimizationLevel::None);
+    db.set_debug_info(true);
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group);
+
+    file_ir
+        .llvm_module
+        .verify()
+        .expect("the debug information should be valid");
+    let ir = file_ir.llvm_module.print_to_string().to_string();
+    for expected in [
+        "!\"Debug Info Version\"",
+        "!DIFile(filename: \"mod.mun\"",
+        "!DISubprogram(name: \"add\"",
+        "!DILocalVariable(name: \"a\", arg: 1",
+        "!DILocalVariable(name: \"b\", arg: 2",
+        "!DILocalVariable(name: \"sum\"",
+        "!DIBasicType(name: \"i32\", size: 32, encoding: DW_ATE_signed",
+        "!DILocation(line: 3,",
+    ] {
+        assert!(ir.contains(expected), "expected `{expected}` in:\n{ir}");
+    }
+}
+
+#[test]
+fn dynamic_invocation() {
+    let mut db = MockDatabase::with_files(
+        r"
+    pub struct(value) Vec2 { x: f32, y: f32 }
+
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn scale(v: Vec2, s: f32) -> Vec2 { Vec2 { x: v.x * s, y: v.y * s } }
+    fn private(a: i32) -> i32 { a }
+    ",
+    );
+
+    let generate_ir = |db: &MockDatabase| {
+        let llvm_context = Context::create();
+        let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+        let module_partition = db.module_partition();
+        let (_, module_group) = module_partition.iter().next().unwrap();
+        let group_ir = gen_file_group_ir(&code_gen, module_group);
+        let file_ir = gen_file_ir(&code_gen, &group_ir, module_group);
+        file_ir
+            .llvm_module
+            .verify()
+            .expect("the trampolines should be valid");
+        file_ir.llvm_module.print_to_string().to_string()
+    };
+
+    let ir = generate_ir(&db);
+    assert!(
+        !ir.contains("_trampoline"),
+        "unexpected trampoline in:\n{ir}"
+    );
+
+    db.set_dynamic_invocation(true);
+    let ir = generate_ir(&db);
+    for expected in [
+        "define void @add_trampoline(i8** %0, i8* %1)",
+        "define void @scale_trampoline(i8** %0, i8* %1)",
+    ] {
+        assert!(ir.contains(expected), "expected `{expected}` in:\n{ir}");
+    }
+    assert!(
+        !ir.contains("@private_trampoline"),
+        "unexpected trampoline for a private function in:\n{ir}"
+    );
+}
+
 fn test_snapshot(name: &str, text: &str) {
     test_snapshot_with_optimization(name, text, OptimizationLevel::Default);
 }
@@ -1079,8 +1382,22 @@ fn test_snapshot_unoptimized(name: &str, text: &str) {
 }
 
 fn test_snapshot_with_optimization(name: &str, text: &str, opt: OptimizationLevel) {
+    test_snapshot_with_config(name, text, opt, false);
+}
+
+fn test_snapshot_with_overflow_checks(name: &str, text: &str) {
+    test_snapshot_with_config(name, text, OptimizationLevel::Default, true);
+}
+
+fn test_snapshot_with_config(
+    name: &str,
+    text: &str,
+    opt: OptimizationLevel,
+    overflow_checks: bool,
+) {
     let mut db = MockDatabase::with_files(text);
     db.set_optimization_level(opt);
+    db.set_overflow_checks(overflow_checks);
     db.set_target(Target::host_target().unwrap());
 
     // Build and extra diagnostics
@@ -297,8 +297,10 @@ impl<'ink, T: ConcreteValueType<'ink> + ?Sized> Value<'ink, T> {
         <T::Value as ValueType>::get_type(&self.value)
     }
 
-    /// Constructs a `Value<T>` from an inkwell value.
-    pub(super) fn from_raw(value: T::Value) -> Value<'ink, T> {
+    /// Constructs a `Value<T>` from an inkwell value. This is public because
+    /// the code generated by the `AsValue` derive uses it.
+    #[doc(hidden)]
+    pub fn from_raw(value: T::Value) -> Value<'ink, T> {
         Value { value }
     }
 }
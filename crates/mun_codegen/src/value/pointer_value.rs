@@ -95,3 +95,43 @@ where
         vec![BytesOrPtr::UntypedPtr(self.value)]
     }
 }
+
+impl<'ink> ConcreteValueType<'ink> for *mut std::ffi::c_void {
+    type Value = inkwell::values::PointerValue<'ink>;
+}
+
+impl<'ink> SizedValueType<'ink> for *mut std::ffi::c_void {
+    fn get_ir_type(
+        context: &IrTypeContext<'ink, '_>,
+    ) -> <<Self as ConcreteValueType<'ink>>::Value as ValueType<'ink>>::Type {
+        context
+            .context
+            .ptr_sized_int_type(context.target_data, None)
+            .ptr_type(AddressSpace::default())
+    }
+}
+
+impl<'ink> PointerValueType<'ink> for *mut std::ffi::c_void {
+    fn get_ptr_type(
+        context: &IrTypeContext<'ink, '_>,
+        address_space: Option<AddressSpace>,
+    ) -> PointerType<'ink> {
+        Self::get_ir_type(context).ptr_type(address_space.unwrap_or_default())
+    }
+}
+
+impl<'ink> Value<'ink, *mut std::ffi::c_void> {
+    /// Constructs a value by casting the specified pointer value to an untyped
+    /// pointer
+    pub fn with_cast(
+        value: inkwell::values::PointerValue<'ink>,
+        context: &IrValueContext<'ink, '_, '_>,
+    ) -> Self {
+        let target_type = <*mut std::ffi::c_void>::get_ir_type(context.type_context);
+        Value::from_raw(if value.get_type() == target_type {
+            value
+        } else {
+            value.const_cast(target_type)
+        })
+    }
+}
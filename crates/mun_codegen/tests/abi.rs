@@ -9,16 +9,22 @@ fn test_abi_compatibility() {
     let fn_name2 = "bar";
     let struct_name = "Foo";
     let struct_name2 = "Bar";
+    let static_name = "COUNTER";
     let driver = CompileTestDriver::from_file(&format!(
         r#"
+    /// Always returns zero.
     pub fn {fn_name}(_: f64) -> i32 {{ 0 }}
     pub fn {fn_name2}() {{
         let a = {struct_name}(1.0, 2.0);
         let b = [1,2,3]
     }}
 
+    /// A pair of floats.
+    ///
+    /// Used to test documentation.
     pub struct {struct_name}(f64, f64);
     pub struct(value) {struct_name2} {{ a: i32, b: i32 }};
+    static {static_name}: i32 = 5;
     "#,
     ));
 
@@ -42,6 +48,8 @@ fn test_abi_compatibility() {
                 return_type: None,
               ),
             ),
+            docs: None,
+            privacy: Public,
           ),
           FunctionDefinition(
             prototype: FunctionPrototype(
@@ -53,6 +61,8 @@ fn test_abi_compatibility() {
                 return_type: Some(Concrete("17797a74-19d6-3217-d235-954317885bfa")),
               ),
             ),
+            docs: Some("Always returns zero."),
+            privacy: Public,
           ),
         ],
         types: [
@@ -75,6 +85,7 @@ fn test_abi_compatibility() {
                 ),
               ],
               memory_kind: Value,
+              docs: None,
             )),
           ),
           TypeDefinition(
@@ -96,9 +107,16 @@ fn test_abi_compatibility() {
                 ),
               ],
               memory_kind: Gc,
+              docs: Some("A pair of floats.\n\nUsed to test documentation."),
             )),
           ),
         ],
+        globals: [
+          GlobalDefinition(
+            name: "COUNTER",
+            type_id: Concrete("17797a74-19d6-3217-d235-954317885bfa"),
+          ),
+        ],
       ),
       dispatch_table: DispatchTable(
         prototypes: [
@@ -174,6 +192,9 @@ fn test_abi_compatibility() {
         ),
       ],
       dependencies: [],
+      dependency_versions: [],
+      tests: [],
+      version: None,
     )
     "#);
 }
@@ -1,25 +1,49 @@
 #![cfg(not(tarpaulin_include))]
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Ident, Index};
+use proc_macro2::{Span, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Generics, Ident, Index, Lifetime, Type,
+};
 
 /// This procedural macro implements the `AsValue` trait as well as several
 /// required other traits. All of these traits enable creating an
 /// `inkwell::values::StructValue` from a generic struct, as long as all fields
 /// of the struct also implement `AsValue`.
+///
+/// The type may have generic parameters. The implementations are generic over
+/// an `'ink` lifetime, which is shared with the type if it declares one itself.
+/// Every field whose type depends on a type parameter is required to implement
+/// the value traits.
+///
+/// Enums are stored as a tag followed by the fields of the variant. The type of
+/// the tag is specified with a `repr` attribute and defaults to `u32`. The tag
+/// value of a variant is its discriminant.
 #[proc_macro_derive(AsValue)]
 pub fn as_value_derive(input: TokenStream) -> TokenStream {
     // Parse Phase
     let derive_input = parse_macro_input!(input as DeriveInput);
 
+    // Determine the generics of the implementations
+    let impl_generics = impl_generics(&derive_input);
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+    // The traits and types used by the generated code, so it does not depend on
+    // the imports of the module that contains the derived type
+    let prelude = quote! {
+        #[allow(unused_imports)]
+        use crate::value::{AsBytesAndPtrs, BytesOrPtr, HasConstValue, SizedValueType};
+        #[allow(unused_imports)]
+        use itertools::Itertools;
+    };
+
     // Get the typename of the struct we're working with
+    let (_, ty_generics, _) = derive_input.generics.split_for_impl();
     let ident = {
         let ident = &derive_input.ident;
-        let generics = derive_input.generics;
         quote! {
-            #ident #generics
+            #ident #ty_generics
         }
     };
 
@@ -142,12 +166,13 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
 
             // Generate Phase
             (quote! {
-                impl<'ink> crate::value::ConcreteValueType<'ink> for #ident {
+                impl #impl_generics crate::value::ConcreteValueType<'ink> for #ident #where_clause {
                     type Value = inkwell::values::StructValue<'ink>;
                 }
 
-                impl<'ink> crate::value::SizedValueType<'ink> for #ident {
+                impl #impl_generics crate::value::SizedValueType<'ink> for #ident #where_clause {
                     fn get_ir_type(context: &crate::value::IrTypeContext<'ink, '_>) -> inkwell::types::StructType<'ink> {
+                        #prelude
                         // Check whether the IR struct type exists
                         let key = std::any::type_name::<#ident>();
                         match context.struct_types.borrow().get(&key) {
@@ -200,26 +225,27 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                impl<'ink> crate::value::PointerValueType<'ink> for #ident {
+                impl #impl_generics crate::value::PointerValueType<'ink> for #ident #where_clause {
                     fn get_ptr_type(context: &crate::value::IrTypeContext<'ink, '_>, address_space: Option<inkwell::AddressSpace>) -> inkwell::types::PointerType<'ink> {
+                        #prelude
                         Self::get_ir_type(context).ptr_type(address_space.unwrap_or(inkwell::AddressSpace::default()))
                     }
                 }
 
-                impl<'ink> crate::value::HasConstValue for #ident {
+                impl #impl_generics crate::value::HasConstValue for #ident #where_clause {
                     fn has_const_value() -> bool {
-                        use crate::value::HasConstValue;
+                        #prelude
                         #(#field_has_const_values)*
                         true
                     }
                 }
 
-                impl<'ink> crate::value::AsBytesAndPtrs<'ink> for #ident {
+                impl #impl_generics crate::value::AsBytesAndPtrs<'ink> for #ident #where_clause {
                     fn as_bytes_and_ptrs(
                         &self,
                         context: &crate::value::IrTypeContext<'ink, '_>
                     ) -> Vec<crate::value::BytesOrPtr<'ink>> {
-                        use crate::value::AsBytesAndPtrs;
+                        #prelude
 
                         fn padded_size(align: usize, data_size: usize) -> usize {
                             data_size.div_ceil(align) * align
@@ -234,9 +260,9 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                impl<'ink> crate::value::AsValue<'ink, #ident> for #ident {
+                impl #impl_generics crate::value::AsValue<'ink, #ident> for #ident #where_clause {
                     fn as_value(&self, context: &crate::value::IrValueContext<'ink, '_, '_>) -> crate::value::Value<'ink, Self> {
-                        use crate::value::HasConstValue;
+                        #prelude
 
                         /// Calculates the size of data after padding has been appended to its end,
                         /// based on its alignment.
@@ -285,7 +311,6 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                             // eprintln!("Done");
                             crate::value::Value::from_raw(value)
                         } else {
-                            use crate::value::{AsBytesAndPtrs, BytesOrPtr};
                             use inkwell::values::BasicValueEnum;
 
                             // construct an anonymous struct type consisting of bytes and pointers
@@ -326,65 +351,57 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                                 .collect();
 
                             let value = context.context.const_struct(&field_values, true);
-                            Value::from_raw(value)
+                            crate::value::Value::from_raw(value)
                         }
                     }
                 }
 
-                impl<'ink> crate::value::AddressableType<'ink, #ident> for #ident {}
+                impl #impl_generics crate::value::AddressableType<'ink, #ident> for #ident #where_clause {}
             }).into()
         }
-        Data::Union(_) => {
-            unimplemented!("#[derive(AsValue)] is not defined for unions!");
-        }
+        Data::Union(union_data) => syn::Error::new_spanned(
+            union_data.union_token,
+            "#[derive(AsValue)] is not defined for unions",
+        )
+        .to_compile_error()
+        .into(),
         Data::Enum(enum_data) => {
-            // Only allow these types in the `repr` attribute
-            const SUPPORTED_TAG_SIZES: &[&str] =
-                &["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
-
-            let repr_ty = derive_input.attrs.iter().find_map(|attr| {
-                let mut repr_ty = None::<proc_macro2::TokenStream>;
-
-                // Check whether the enum has a `repr` attribute
-                if attr.path().is_ident("repr") {
-                    attr.parse_nested_meta(|meta| {
-                        // Use the `repr` attribute as tag type.
-                        if let Some(segment) = meta.path.segments.iter().next() {
-                            let ident = segment.ident.clone();
-                            let tag_name = ident.to_string();
-                            if !SUPPORTED_TAG_SIZES.contains(&tag_name.as_str()) {
-                                return Err(
-                                    meta.error(format!("unrecognised repr type: ${tag_name}"))
-                                );
-                            }
+            let repr_ty = match enum_repr_ty(&derive_input.attrs) {
+                Ok(repr_ty) => repr_ty,
+                Err(err) => return err.to_compile_error().into(),
+            };
 
-                            repr_ty = Some(quote! {
-                                #ident
-                            });
+            if enum_data.variants.is_empty() {
+                return syn::Error::new_spanned(
+                    &derive_input.ident,
+                    "enums with no variants are not supported by #[derive(AsValue)]",
+                )
+                .to_compile_error()
+                .into();
+            }
 
-                            Ok(())
-                        } else {
-                            Err(meta.error("repr missing type. E.g. repr(u8)"))
+            // The tag value of each variant. Variants without an explicit discriminant
+            // are one larger than the previous variant, starting at zero.
+            let variant_tags = {
+                let mut base = None;
+                let mut offset = 0usize;
+                enum_data
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        if let Some((_, discriminant)) = &v.discriminant {
+                            base = Some(discriminant.clone());
+                            offset = 0;
                         }
+                        let offset_lit = Index::from(offset);
+                        offset += 1;
+                        base.as_ref().map_or_else(
+                            || quote! { #offset_lit },
+                            |discriminant| quote! { (#discriminant) + #offset_lit },
+                        )
                     })
-                    .unwrap_or_else(|err| {
-                        eprintln!("{err}");
-                    });
-                }
-
-                repr_ty
-            });
-
-            let repr_ty = repr_ty.unwrap_or_else(|| {
-                // Default to u32
-                quote! {
-                    u32
-                }
-            });
-
-            if enum_data.variants.is_empty() {
-                eprintln!("Enums with no variants are not supported by the `AsValue` macro.");
-            }
+                    .collect::<Vec<_>>()
+            };
 
             let enum_name = &derive_input.ident;
 
@@ -414,8 +431,8 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     // Start with the tag's size (same as chunk_size)
                     let mut total_size = chunk_size;
 
-                    let field_sizes = [ #(#field_sizes),* ];
-                    let field_alignments = [ #(#field_alignments),* ];
+                    let field_sizes: &[usize] = &[ #(#field_sizes),* ];
+                    let field_alignments: &[usize] = &[ #(#field_alignments),* ];
 
                     // Calculate the padding required to align each field
                     let field_paddings: Vec<usize> = field_sizes
@@ -449,7 +466,7 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                 });
 
                 let variant_align = quote! {{
-                    let field_alignments = [#(#field_alignments),*];
+                    let field_alignments: &[usize] = &[#(#field_alignments),*];
                     field_alignments.iter().max().cloned().unwrap_or(1)
                 }};
 
@@ -468,9 +485,10 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                 let variant_bytes_and_ptrs_mapping = enum_data
                     .variants
                     .iter()
+                    .zip(variant_tags.iter())
                     .enumerate()
-                    .map(|(tag, v)| {
-                        let tag = Index::from(tag);
+                    .map(|(idx, (v, tag))| {
+                        let idx = Index::from(idx);
                         let field_mappings = v.fields.iter().enumerate().map(|(idx, f)| {
                             let name = f.ident.as_ref().map_or_else(|| {
                                 // If this is a tuple struct, map the index to an alias (e.g. 0: t0)
@@ -500,7 +518,7 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                         quote! {
                             #enum_name :: #ident { #(#field_mappings),* } => {
                                 let (variant_field_paddings, variant_size) =
-                                    variant_field_paddings_and_sizes.get(#tag).expect(
+                                    variant_field_paddings_and_sizes.get(#idx).expect(
                                         "Number of `variant_field_paddings_and_sizes` does not match the number of variants."
                                     );
 
@@ -508,10 +526,11 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                                     .iter()
                                     .map(|p| vec![0u8; *p].into());
 
+                                let tag: #repr_ty = #tag;
                                 let field_bytes_and_ptrs = vec![
                                     // Convert the tag to bytes
                                     vec![BytesOrPtr::Bytes(
-                                        bytemuck::cast_ref::<#repr_ty, [u8; std::mem::size_of::<#repr_ty>()]>(&#tag)
+                                        bytemuck::cast_ref::<#repr_ty, [u8; std::mem::size_of::<#repr_ty>()]>(&tag)
                                             .to_vec()
                                     )],
                                     // Converts all other fields to bytes and pointers
@@ -545,14 +564,15 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
 
             // Generate Phase
             (quote! {
-                impl<'ink> crate::value::ConcreteValueType<'ink> for #ident {
+                impl #impl_generics crate::value::ConcreteValueType<'ink> for #ident #where_clause {
                     type Value = inkwell::values::StructValue<'ink>;
                 }
 
-                impl<'ink> crate::value::SizedValueType<'ink> for #ident {
+                impl #impl_generics crate::value::SizedValueType<'ink> for #ident #where_clause {
                     fn get_ir_type(
                         context: &crate::value::IrTypeContext<'ink, '_>
                     ) -> inkwell::types::StructType<'ink> {
+                        #prelude
                         use std::convert::TryFrom;
                         use inkwell::types::AnyType;
 
@@ -609,24 +629,26 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                impl<'ink> crate::value::PointerValueType<'ink> for #ident {
+                impl #impl_generics crate::value::PointerValueType<'ink> for #ident #where_clause {
                     fn get_ptr_type(context: &crate::value::IrTypeContext<'ink, '_>, address_space: Option<inkwell::AddressSpace>) -> inkwell::types::PointerType<'ink> {
+                        #prelude
                         Self::get_ir_type(context).ptr_type(address_space.unwrap_or(inkwell::AddressSpace::default()))
                     }
                 }
 
-                impl<'ink> crate::value::HasConstValue for #ident {
+                impl #impl_generics crate::value::HasConstValue for #ident #where_clause {
                     fn has_const_value() -> bool {
+                        #prelude
                         false
                     }
                 }
 
-                impl<'ink> crate::value::AsBytesAndPtrs<'ink> for #ident {
+                impl #impl_generics crate::value::AsBytesAndPtrs<'ink> for #ident #where_clause {
                     fn as_bytes_and_ptrs(
                         &self,
                         context: &crate::value::IrTypeContext<'ink, '_>
                     ) -> Vec<crate::value::BytesOrPtr<'ink>> {
-                        use crate::value::{AsBytesAndPtrs, BytesOrPtr};
+                        #prelude
                         use inkwell::types::AnyType;
 
                         // Aliasing to make sure that all procedurally generated macros can use the
@@ -662,9 +684,9 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                impl<'ink> crate::value::AsValue<'ink, #ident> for #ident {
+                impl #impl_generics crate::value::AsValue<'ink, #ident> for #ident #where_clause {
                     fn as_value(&self, context: &crate::value::IrValueContext<'ink, '_, '_>) -> crate::value::Value<'ink, Self> {
-                        use crate::value::{AsBytesAndPtrs, BytesOrPtr};
+                        #prelude
                         use inkwell::values::BasicValueEnum;
                         use inkwell::types::AnyType;
 
@@ -705,12 +727,266 @@ pub fn as_value_derive(input: TokenStream) -> TokenStream {
                             .collect();
 
                         let value = context.context.const_struct(&field_values, true);
-                        Value::from_raw(value)
+                        crate::value::Value::from_raw(value)
                     }
                 }
 
-                impl<'ink> crate::value::AddressableType<'ink, #ident> for #ident {}
+                impl #impl_generics crate::value::AddressableType<'ink, #ident> for #ident #where_clause {}
             }).into()
         }
     }
 }
+
+/// This procedural macro implements the `HasStaticType`, `ReturnTypeReflection`
+/// and `Marshal` traits of `mun_runtime` for a `#[repr(C)]` struct. This
+/// enables reading a Mun value struct with the same name and fields directly
+/// into the Rust struct, e.g. when a function returns it or when it is stored
+/// in the field of another struct.
+///
+/// Every field of the struct is required to implement the same traits. Value
+/// structs are passed to public Mun functions by reference, so the derived type
+/// does not implement `ArgumentReflection`.
+#[proc_macro_derive(TestAbiType)]
+pub fn test_abi_type_derive(input: TokenStream) -> TokenStream {
+    // Parse Phase
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    let ident = &derive_input.ident;
+
+    let struct_data = match &derive_input.data {
+        Data::Struct(struct_data) => struct_data,
+        Data::Enum(enum_data) => {
+            return syn::Error::new_spanned(
+                enum_data.enum_token,
+                "#[derive(TestAbiType)] is only defined for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+        Data::Union(union_data) => {
+            return syn::Error::new_spanned(
+                union_data.union_token,
+                "#[derive(TestAbiType)] is only defined for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // The type information is stored in a static, which would be shared by all
+    // instantiations of a generic type
+    if !derive_input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &derive_input.generics,
+            "generic types are not supported by #[derive(TestAbiType)]",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // The memory of the Rust struct is copied as is, so it must have the same
+    // layout as the Mun struct
+    match has_repr_c(&derive_input.attrs) {
+        Ok(true) => {}
+        Ok(false) => {
+            return syn::Error::new_spanned(
+                ident,
+                "#[derive(TestAbiType)] requires the struct to be `#[repr(C)]`",
+            )
+            .to_compile_error()
+            .into();
+        }
+        Err(err) => return err.to_compile_error().into(),
+    }
+
+    // Mun uses the index of a tuple struct's field as its name
+    let fields = struct_data.fields.iter().enumerate().map(|(idx, field)| {
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| idx.to_string(), ToString::to_string);
+        let ty = &field.ty;
+        quote! {
+            (#name, <#ty as mun_runtime::HasStaticType>::type_info().clone())
+        }
+    });
+
+    let name = ident.to_string();
+    (quote! {
+        impl mun_runtime::HasStaticType for #ident {
+            fn type_info() -> &'static mun_runtime::Type {
+                static TYPE_INFO: std::sync::OnceLock<mun_runtime::Type> = std::sync::OnceLock::new();
+                TYPE_INFO.get_or_init(|| {
+                    let ty = mun_runtime::StructTypeBuilder::new(#name)
+                        .set_memory_kind(mun_runtime::StructMemoryKind::Value)
+                        .add_fields([#(#fields),*])
+                        .finish();
+
+                    assert_eq!(
+                        ty.value_layout(),
+                        std::alloc::Layout::new::<#ident>(),
+                        "the layout of `{}` does not match its Mun type",
+                        #name,
+                    );
+                    ty
+                })
+            }
+        }
+
+        impl mun_runtime::ReturnTypeReflection for #ident {
+            fn accepts_type(ty: &mun_runtime::Type) -> bool {
+                ty == <Self as mun_runtime::HasStaticType>::type_info()
+            }
+
+            fn type_hint() -> &'static str {
+                #name
+            }
+        }
+
+        impl<'t> mun_runtime::Marshal<'t> for #ident {
+            type MunType = #ident;
+
+            fn marshal_from<'r>(value: Self::MunType, _runtime: &'r mun_runtime::Runtime) -> Self
+            where
+                Self: 't,
+                'r: 't,
+            {
+                value
+            }
+
+            fn marshal_into(self) -> Self::MunType {
+                self
+            }
+
+            fn marshal_from_ptr<'r>(
+                ptr: std::ptr::NonNull<Self::MunType>,
+                _runtime: &'r mun_runtime::Runtime,
+                _type_info: &mun_runtime::Type,
+            ) -> Self
+            where
+                Self: 't,
+                'r: 't,
+            {
+                // SAFETY: A value struct is stored inline, with the same layout as `Self`
+                unsafe { ptr.as_ptr().read_unaligned() }
+            }
+
+            fn marshal_to_ptr(
+                value: Self,
+                ptr: std::ptr::NonNull<Self::MunType>,
+                _type_info: &mun_runtime::Type,
+            ) {
+                // SAFETY: A value struct is stored inline, with the same layout as `Self`
+                unsafe { ptr.as_ptr().write_unaligned(value) };
+            }
+        }
+    })
+    .into()
+}
+
+/// Returns the generics used for the implementations of a derived type. These
+/// are the generics of the type itself, extended with an `'ink` lifetime if the
+/// type does not declare it, and bounds requiring every field type that
+/// depends on a type parameter to implement the value traits.
+fn impl_generics(derive_input: &DeriveInput) -> Generics {
+    let mut generics = derive_input.generics.clone();
+
+    let ink = Lifetime::new("'ink", Span::call_site());
+    if !generics.lifetimes().any(|param| param.lifetime == ink) {
+        generics.params.insert(0, parse_quote!(#ink));
+    }
+
+    let type_params: Vec<Ident> = derive_input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    if type_params.is_empty() {
+        return generics;
+    }
+
+    let field_types: Vec<&Type> = match &derive_input.data {
+        Data::Struct(struct_data) => struct_data.fields.iter().map(|f| &f.ty).collect(),
+        Data::Enum(enum_data) => enum_data
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| &f.ty))
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+
+    let where_clause = generics.make_where_clause();
+    for ty in field_types {
+        if !references_any(ty.to_token_stream(), &type_params) {
+            continue;
+        }
+
+        where_clause.predicates.push(parse_quote! {
+            #ty: crate::value::SizedValueType<#ink>
+                + crate::value::HasConstValue
+                + crate::value::AsBytesAndPtrs<#ink>
+                + crate::value::AsValueInto<#ink, inkwell::values::BasicValueEnum<#ink>>
+        });
+        where_clause.predicates.push(parse_quote! {
+            <<#ty as crate::value::ConcreteValueType<#ink>>::Value as crate::value::ValueType<#ink>>::Type:
+                Into<inkwell::types::BasicTypeEnum<#ink>>
+        });
+    }
+
+    generics
+}
+
+/// Returns the tag type of an enum, which is specified by its `repr` attribute
+/// and defaults to `u32`.
+fn enum_repr_ty(attrs: &[syn::Attribute]) -> syn::Result<proc_macro2::TokenStream> {
+    // Only allow these types in the `repr` attribute
+    const SUPPORTED_TAG_SIZES: &[&str] = &["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
+
+    let mut repr_ty = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            let Some(ident) = meta.path.get_ident() else {
+                return Err(meta.error("expected a repr type, e.g. `repr(u8)`"));
+            };
+
+            if !SUPPORTED_TAG_SIZES.contains(&ident.to_string().as_str()) {
+                return Err(meta.error(format!(
+                    "unsupported repr type `{ident}`, expected one of: {}",
+                    SUPPORTED_TAG_SIZES.join(", ")
+                )));
+            }
+
+            repr_ty = Some(quote! { #ident });
+            Ok(())
+        })?;
+    }
+
+    Ok(repr_ty.unwrap_or_else(|| quote! { u32 }))
+}
+
+/// Returns true if the `repr` attributes of a type include `C`.
+fn has_repr_c(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut is_repr_c = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                is_repr_c = true;
+            } else if meta.input.peek(syn::token::Paren) {
+                // Skip the arguments of e.g. `align(8)`
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(is_repr_c)
+}
+
+/// Returns true if the `tokens` contain any of the specified identifiers.
+fn references_any(tokens: proc_macro2::TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => references_any(group.stream(), idents),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+}
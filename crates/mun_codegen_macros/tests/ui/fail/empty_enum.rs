@@ -0,0 +1,6 @@
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+enum Never {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mun_codegen::value::{self, AsValue, SizedValueType};
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+struct Wrapper<T> {
+    value: T,
+}
+
+fn assert_as_value<'ink, T: SizedValueType<'ink> + AsValue<'ink, T>>() {}
+
+fn main() {
+    assert_as_value::<Wrapper<String>>();
+}
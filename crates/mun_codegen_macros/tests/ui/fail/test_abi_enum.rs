@@ -0,0 +1,10 @@
+use mun_codegen_macros::TestAbiType;
+
+#[repr(C)]
+#[derive(TestAbiType)]
+enum Direction {
+    Left,
+    Right,
+}
+
+fn main() {}
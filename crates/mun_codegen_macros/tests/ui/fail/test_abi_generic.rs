@@ -0,0 +1,10 @@
+use mun_codegen_macros::TestAbiType;
+
+#[repr(C)]
+#[derive(TestAbiType)]
+struct Pair<T> {
+    a: T,
+    b: T,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use mun_codegen_macros::TestAbiType;
+
+#[derive(TestAbiType)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+union IntOrFloat {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+#[repr(usize)]
+enum Tag {
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use mun_codegen::value::{self, AsValue, SizedValueType};
+use mun_codegen_macros::AsValue;
+
+const BASE: i16 = -3;
+
+#[derive(AsValue)]
+#[repr(i16)]
+enum Discriminants {
+    First = BASE,
+    Second,
+    Third = 10,
+    Fourth(u32),
+}
+
+fn assert_as_value<'ink, T: SizedValueType<'ink> + AsValue<'ink, T>>() {}
+
+fn main() {
+    assert_as_value::<Discriminants>();
+}
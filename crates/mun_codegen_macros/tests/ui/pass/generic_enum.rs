@@ -0,0 +1,15 @@
+use mun_codegen::value::{self, AsValue, SizedValueType};
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+#[repr(u8)]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+fn assert_as_value<'ink, T: SizedValueType<'ink> + AsValue<'ink, T>>() {}
+
+fn main() {
+    assert_as_value::<Either<u32, f32>>();
+}
@@ -0,0 +1,26 @@
+use mun_codegen::value::{self, AsValue, SizedValueType};
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+#[derive(AsValue)]
+struct Tagged<T, U>
+where
+    U: Copy,
+{
+    tag: u8,
+    value: T,
+    other: U,
+}
+
+fn assert_as_value<'ink, T: SizedValueType<'ink> + AsValue<'ink, T>>() {}
+
+fn main() {
+    assert_as_value::<Pair<u32>>();
+    assert_as_value::<Pair<Pair<f64>>>();
+    assert_as_value::<Tagged<i16, u64>>();
+}
@@ -0,0 +1,21 @@
+use mun_codegen::value::{self, AsValue, SizedValueType, Value};
+use mun_codegen_macros::AsValue;
+
+#[derive(AsValue)]
+struct Named<'ink> {
+    name: Value<'ink, *const u8>,
+    size: u32,
+}
+
+#[derive(AsValue)]
+struct NamedValue<'ink, T> {
+    name: Value<'ink, *const u8>,
+    value: T,
+}
+
+fn assert_as_value<'ink, T: SizedValueType<'ink> + AsValue<'ink, T>>() {}
+
+fn main() {
+    assert_as_value::<Named<'_>>();
+    assert_as_value::<NamedValue<'_, Named<'_>>>();
+}
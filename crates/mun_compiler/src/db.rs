@@ -1,9 +1,14 @@
+use std::sync::Arc;
+
 use mun_codegen::{CodeGenDatabase, CodeGenDatabaseStorage};
 use mun_db::Upcast;
-use mun_hir::{salsa, HirDatabase};
+use mun_hir::{salsa, CfgOptions, DefDatabase, HirDatabase, QueryMemoryUsage};
 use mun_hir_input::SourceDatabase;
 
-use crate::Config;
+use crate::{
+    timings::{Profiler, Timings},
+    Config,
+};
 
 /// A compiler database is a salsa database that enables increment compilation.
 #[salsa::database(
@@ -16,6 +21,10 @@ use crate::Config;
 )]
 pub struct CompilerDatabase {
     storage: salsa::Storage<Self>,
+
+    /// Records the timings of the compilation if [`Config::timings`] is
+    /// enabled
+    profiler: Option<Profiler>,
 }
 
 impl Upcast<dyn mun_hir::AstDatabase> for CompilerDatabase {
@@ -53,10 +62,12 @@ impl CompilerDatabase {
     pub fn new(config: &Config) -> Self {
         let mut db = CompilerDatabase {
             storage: salsa::Storage::default(),
+            profiler: config.timings.then(Profiler::default),
         };
 
         // Set the initial configuration
         db.set_config(config);
+        db.set_lint_levels(Arc::default());
 
         db
     }
@@ -65,7 +76,43 @@ impl CompilerDatabase {
     pub fn set_config(&mut self, config: &Config) {
         self.set_target(config.target.clone());
         self.set_optimization_level(config.optimization_lvl);
+        self.set_overflow_checks(config.overflow_checks);
+        self.set_debug_info(config.debug_info);
+        self.set_dynamic_invocation(config.dynamic_invocation);
+        self.set_backend(config.backend);
+        self.set_cfg_options(Arc::new(CfgOptions::with_features(
+            config.features.iter().map(String::as_str),
+        )));
+        config.lru_capacities.apply(self);
+    }
+
+    /// Returns the profiler that records the timings of the compilation, if
+    /// [`Config::timings`] is enabled.
+    pub(crate) fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    /// Returns the timings that were recorded so far, if [`Config::timings`]
+    /// is enabled.
+    pub fn timings(&self) -> Option<Timings> {
+        self.profiler
+            .as_ref()
+            .map(|profiler| profiler.timings(self))
+    }
+
+    /// Returns how many results of the queries that take up the most memory
+    /// are stored in the database.
+    pub fn memory_usage(&self) -> Vec<QueryMemoryUsage> {
+        mun_hir::memory_usage(self)
     }
 }
 
-impl salsa::Database for CompilerDatabase {}
+impl salsa::Database for CompilerDatabase {
+    fn salsa_event(&self, event: salsa::Event) {
+        if let (Some(profiler), salsa::EventKind::WillExecute { database_key }) =
+            (&self.profiler, event.kind)
+        {
+            profiler.record_query(database_key);
+        }
+    }
+}
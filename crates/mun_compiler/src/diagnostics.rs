@@ -2,10 +2,16 @@
 mod tests {
     use std::io::Cursor;
 
-    use crate::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+    use crate::{Config, DisplayColor, Driver, MessageFormat, PathOrInline, RelativePathBuf};
 
     /// Compile passed source code and return all compilation errors
     fn compilation_errors(source_code: &str) -> String {
+        compilation_errors_with_format(source_code, DisplayColor::Disable.into())
+    }
+
+    /// Compile passed source code and return all compilation errors in the
+    /// specified format
+    fn compilation_errors_with_format(source_code: &str, message_format: MessageFormat) -> String {
         let config = Config::default();
 
         let input = PathOrInline::Inline {
@@ -18,10 +24,7 @@ mod tests {
         let mut compilation_errors = Vec::<u8>::new();
 
         let _ = driver
-            .emit_diagnostics(
-                &mut Cursor::new(&mut compilation_errors),
-                DisplayColor::Disable,
-            )
+            .emit_diagnostics(&mut Cursor::new(&mut compilation_errors), message_format)
             .unwrap();
 
         String::from_utf8(compilation_errors).unwrap()
@@ -46,6 +49,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_unresolved_similar_name_error() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nstruct Velocity(f32);\n\npub fn main() {\nlet velocity = Velocity(1.0);\nlet a: Velocty = velocty;\n}"
+        ));
+    }
+
     #[test]
     fn test_leaked_private_type_error_function() {
         insta::assert_snapshot!(compilation_errors(
@@ -88,6 +98,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_unknown_similar_field_error() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nstruct Foo {\nvelocity: f32\n}\n\npub fn main() {\nlet a = Foo { velocty: 1.0 };\nlet b = a.velocty;\n}"
+        ));
+    }
+
     #[test]
     fn test_free_type_alias_error() {
         insta::assert_snapshot!(compilation_errors("\n\ntype Foo;"));
@@ -107,4 +124,31 @@ mod tests {
     fn test_expected_function() {
         insta::assert_snapshot!(compilation_errors("\n\nfn foo() { let a = 3; a(); }"));
     }
+
+    #[test]
+    fn test_deprecated_call_warning() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\n#[deprecated = \"use `bar` instead\"]\nfn foo() {}\n\nfn main() { foo(); }"
+        ));
+    }
+
+    #[test]
+    fn test_unused_code_warning() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nfn foo() {}\n\n#[deny(unreachable_code)]\npub fn main() {\nlet a = 5;\nreturn;\nfoo();\n}"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_attribute_error() {
+        insta::assert_snapshot!(compilation_errors("\n\n#[foo]\nfn main() {}"));
+    }
+
+    #[test]
+    fn test_json_message_format() {
+        insta::assert_snapshot!(compilation_errors_with_format(
+            "\n\nfn foo() {}\n\n#[deny(unreachable_code)]\npub fn main() {\nlet a = 5;\nreturn;\nfoo();\n}",
+            MessageFormat::Json
+        ));
+    }
 }
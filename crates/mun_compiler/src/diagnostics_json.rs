@@ -0,0 +1,85 @@
+use mun_diagnostics::DiagnosticForWith;
+use mun_hir::{diagnostics::Severity, HirDatabase};
+use mun_hir_input::{FileId, LineIndex};
+use mun_syntax::{SyntaxError, TextRange};
+use serde_json::json;
+
+use crate::driver::file_display_path;
+
+/// Writes the specified syntax error to the output stream as a single line of
+/// JSON.
+pub(crate) fn emit_syntax_error_json(
+    syntax_error: &SyntaxError,
+    relative_file_path: &str,
+    line_index: &LineIndex,
+    writer: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let location = syntax_error.location();
+    let range = TextRange::new(location.offset(), location.end_offset());
+    write_json_diagnostic(
+        relative_file_path,
+        range,
+        line_index,
+        Severity::Error,
+        &syntax_error.to_string(),
+        None,
+        writer,
+    )
+}
+
+/// Writes the specified HIR diagnostic to the output stream as a single line of
+/// JSON.
+pub(crate) fn emit_hir_diagnostic_json(
+    diagnostic: &dyn mun_hir::Diagnostic,
+    db: &impl HirDatabase,
+    file_id: FileId,
+    writer: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let severity = diagnostic.severity();
+    diagnostic.with_diagnostic(db, |diagnostic| {
+        write_json_diagnostic(
+            file_display_path(db, file_id).as_str(),
+            diagnostic.range(),
+            &db.line_index(file_id),
+            severity,
+            &diagnostic.title(),
+            diagnostic.code().as_deref(),
+            writer,
+        )
+    })
+}
+
+/// Writes a diagnostic as a JSON object on a single line. Lines and columns in
+/// the range are one-based, columns are counted in UTF-16 code units.
+fn write_json_diagnostic(
+    relative_file_path: &str,
+    range: TextRange,
+    line_index: &LineIndex,
+    severity: Severity,
+    message: &str,
+    code: Option<&str>,
+    writer: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let position = |offset| {
+        let line_col = line_index.line_col(offset);
+        json!({
+            "line": line_col.line + 1,
+            "column": line_col.col_utf16 + 1,
+        })
+    };
+
+    let diagnostic = json!({
+        "file": relative_file_path,
+        "range": {
+            "start": position(range.start()),
+            "end": position(range.end()),
+        },
+        "severity": match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        },
+        "message": message,
+        "code": code,
+    });
+    writeln!(writer, "{diagnostic}")
+}
@@ -2,11 +2,13 @@ use std::{collections::HashMap, sync::Arc};
 
 use annotate_snippets::{Annotation, AnnotationType, Renderer, Slice, Snippet, SourceAnnotation};
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::HirDatabase;
+use mun_hir::{diagnostics::Severity, HirDatabase};
 use mun_hir_input::{FileId, LineIndex};
 use mun_paths::RelativePathBuf;
 use mun_syntax::SyntaxError;
 
+use crate::driver::file_display_path;
+
 /// Writes the specified syntax error to the output stream.
 pub(crate) fn emit_syntax_error(
     syntax_error: &SyntaxError,
@@ -61,14 +63,26 @@ pub(crate) fn emit_hir_diagnostic(
     display_colors: bool,
     writer: &mut dyn std::io::Write,
 ) -> std::io::Result<()> {
+    let annotation_type = match diagnostic.severity() {
+        Severity::Error => AnnotationType::Error,
+        Severity::Warning => AnnotationType::Warning,
+    };
     diagnostic.with_diagnostic(db, |diagnostic| {
-        emit_diagnostic(diagnostic, db, file_id, display_colors, writer)
+        emit_diagnostic(
+            diagnostic,
+            annotation_type,
+            db,
+            file_id,
+            display_colors,
+            writer,
+        )
     })
 }
 
 /// Emits a diagnostic by writting a snippet to the specified `writer`.
 fn emit_diagnostic(
     diagnostic: &dyn mun_diagnostics::Diagnostic,
+    annotation_type: AnnotationType,
     db: &impl HirDatabase,
     file_id: FileId,
     display_colors: bool,
@@ -92,7 +106,7 @@ fn emit_diagnostic(
 
         // Add primary annotations
         annotations.push(AnnotationFile {
-            relative_file_path: db.file_relative_path(file_id).to_relative_path_buf(),
+            relative_file_path: file_display_path(db, file_id),
             source_code: db.file_text(file_id),
             line_index: db.line_index(file_id),
             annotations: vec![match diagnostic.primary_annotation() {
@@ -114,7 +128,7 @@ fn emit_diagnostic(
                 None => {
                     // Doesn't exist yet, add it
                     annotations.push(AnnotationFile {
-                        relative_file_path: db.file_relative_path(file_id),
+                        relative_file_path: file_display_path(db, file_id),
                         source_code: db.file_text(file_id),
                         line_index: db.line_index(file_id),
                         annotations: Vec::new(),
@@ -135,12 +149,18 @@ fn emit_diagnostic(
 
     let footer = diagnostic.footer();
 
+    // Only codes that can be explained with `mun explain` are shown, lint names are
+    // already mentioned in the footer.
+    let code = diagnostic
+        .code()
+        .filter(|code| mun_diagnostics::explain(code).is_some());
+
     // Construct an annotation snippet to be able to emit it.
     let snippet = Snippet {
         title: Some(Annotation {
-            id: None,
+            id: code.as_deref(),
             label: Some(&title),
-            annotation_type: AnnotationType::Error,
+            annotation_type,
         }),
         slices: annotations
             .iter()
@@ -170,7 +190,7 @@ fn emit_diagnostic(
                                 usize::from(annotation.range.end()) - line_offset,
                             ),
                             label: annotation.message.as_str(),
-                            annotation_type: AnnotationType::Error,
+                            annotation_type,
                         })
                         .collect(),
                     fold: true,
@@ -1,37 +1,86 @@
 //! `Driver` is a stateful compiler frontend that enables incremental
 //! compilation by retaining state from previous compilation.
 
-use mun_codegen::{AssemblyIr, CodeGenDatabase, ModuleGroup, TargetAssembly};
-use mun_hir::{AstDatabase, DiagnosticSink, Module};
+use mun_codegen::{
+    AssemblyIr, Backend, CodeGenDatabase, ModuleGroup, ModuleGroupId, TargetAssembly,
+};
+use mun_hir::{
+    diagnostics::Severity, AstDatabase, CfgOptions, DefDatabase, DiagnosticSink, HirDatabase, Lint,
+    LintLevel, LintLevels, Module,
+};
 use mun_hir_input::{FileId, PackageSet, SourceDatabase, SourceRoot, SourceRootId};
 use mun_paths::RelativePathBuf;
 
 use crate::{
     compute_source_relative_path, db::CompilerDatabase, ensure_package_output_dir, is_source_file,
-    PathOrInline, RelativePath,
+    progress::Progress, timings::Phase, PathOrInline, RelativePath,
 };
 
+mod artifact_cache;
 mod config;
 mod display_color;
+mod hermetic;
+mod incremental;
+mod message_format;
 
 use std::{
     collections::HashMap,
     convert::TryInto,
+    hash::{DefaultHasher, Hash, Hasher},
     io::Cursor,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use mun_db::Upcast;
 use mun_project::{Package, LOCKFILE_NAME};
+use mun_vfs::MonitorDirectories;
 use walkdir::WalkDir;
 
-pub use self::{config::Config, display_color::DisplayColor};
-use crate::diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error};
+use self::{
+    artifact_cache::ArtifactCache,
+    hermetic::{ensure_allowed, source_allowlist, InputManifest},
+    incremental::IncrementalCache,
+};
+pub use self::{
+    config::{Config, LruCapacities},
+    display_color::DisplayColor,
+    message_format::MessageFormat,
+};
+use crate::{
+    diagnostics_json::{emit_hir_diagnostic_json, emit_syntax_error_json},
+    diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error},
+};
 
 pub const WORKSPACE: SourceRootId = SourceRootId(0);
 
+/// The source root that holds the virtual files of a driver, see
+/// [`Driver::set_virtual_file`]. The virtual files form a separate package.
+pub const VIRTUAL: SourceRootId = SourceRootId(u32::MAX);
+
+/// The directory that precedes the path of a virtual file when it is displayed,
+/// e.g. in diagnostics, to distinguish it from files on disk
+pub const VIRTUAL_PATH_PREFIX: &str = "<generated>";
+
+/// The name of the directory in the output directory in which the assemblies of
+/// the virtual files are written
+pub const VIRTUAL_OUTPUT_DIRNAME: &str = "generated";
+
+/// The name of the file in the output directory that describes the module
+/// partition when [`Config::emit_module_graph`] is enabled
+pub const MODULE_GRAPH_FILENAME: &str = "modules.dot";
+
+/// The name of the directory in the output directory of a package in which the
+/// fingerprints of the inputs of its assemblies are stored, which enables
+/// reusing the assemblies of unchanged modules across invocations of the
+/// compiler
+pub const INCREMENTAL_DIRNAME: &str = "incremental";
+
+/// The name of the file in the output directory that lists the SHA-256 hashes
+/// of all files that were read by a hermetic build, see [`Config::hermetic`]
+pub const INPUTS_FILENAME: &str = "inputs.json";
+
 pub struct Driver {
     db: CompilerDatabase,
     out_dir: PathBuf,
@@ -41,11 +90,45 @@ pub struct Driver {
     file_id_to_path: HashMap<FileId, RelativePathBuf>,
     next_file_id: usize,
 
-    module_to_temp_assembly_path: HashMap<Module, PathBuf>,
+    /// The source files that are not stored on disk, see
+    /// [`Driver::set_virtual_file`]
+    virtual_source_root: SourceRoot,
+    /// The ids of all virtual files that were ever added, keyed by their path.
+    /// Ids are not forgotten when a file is removed, so a file that is added
+    /// again keeps its id.
+    virtual_file_ids: HashMap<RelativePathBuf, FileId>,
+
+    /// The directories, relative to the output directory, in which the
+    /// assemblies of the members of a workspace are written, keyed by the
+    /// source root of the member
+    member_output_dirs: HashMap<SourceRootId, RelativePathBuf>,
+
+    /// Fingerprints of the contents of the assemblies last written to the
+    /// output directory, keyed by their output path
+    written_fingerprints: HashMap<PathBuf, u64>,
+
+    /// Fingerprints of the inputs of the assemblies that were written by
+    /// previous invocations of the compiler, only available when compiling a
+    /// package
+    incremental: Option<IncrementalCache>,
+
+    /// Assemblies shared between builds, keyed by a fingerprint of their
+    /// inputs. Only used when compiling a package.
+    artifact_cache: Option<ArtifactCache>,
+
+    /// The inputs of the build, only available for hermetic builds
+    inputs: Option<InputManifest>,
 
     emit_ir: bool,
+    emit_module_graph: bool,
+
+    /// Receives notifications about the progress of a compilation
+    progress_handler: Option<Box<ProgressHandler>>,
 }
 
+/// A function that receives notifications about the progress of a compilation
+type ProgressHandler = dyn Fn(Progress<'_>) + Send + Sync;
+
 impl Driver {
     /// Constructs a driver with a specific configuration.
     pub fn with_config(config: Config, out_dir: PathBuf) -> Self {
@@ -56,13 +139,30 @@ impl Driver {
             path_to_file_id: HashMap::default(),
             file_id_to_path: HashMap::default(),
             next_file_id: 0,
-            module_to_temp_assembly_path: HashMap::default(),
+            virtual_source_root: SourceRoot::default(),
+            virtual_file_ids: HashMap::default(),
+            member_output_dirs: HashMap::default(),
+            written_fingerprints: HashMap::default(),
+            incremental: None,
+            artifact_cache: config.artifact_cache.clone().map(ArtifactCache::new),
+            inputs: None,
             emit_ir: config.emit_ir,
+            emit_module_graph: config.emit_module_graph,
+            progress_handler: None,
         }
     }
 
+    /// Registers a function that is notified about the progress of parsing,
+    /// analyzing, and building the package, e.g. to render a progress bar.
+    /// Replaces any previously registered handler.
+    pub fn set_progress_handler(&mut self, handler: impl Fn(Progress<'_>) + Send + Sync + 'static) {
+        self.progress_handler = Some(Box::new(handler));
+    }
+
     /// Constructs a driver with a configuration and a single file.
     pub fn with_file(config: Config, path: PathOrInline) -> anyhow::Result<(Driver, FileId)> {
+        ensure_backend_available(config.backend)?;
+
         let out_dir = config.out_dir.clone().unwrap_or_else(|| {
             std::env::current_dir().expect("could not determine current working directory")
         });
@@ -97,46 +197,90 @@ impl Driver {
         Ok((driver, file_id))
     }
 
-    /// Constructs a driver with a package manifest directory
+    /// Returns the database that holds the state of the compiler, e.g. to
+    /// query the diagnostics of a module.
+    pub fn database(&self) -> &CompilerDatabase {
+        &self.db
+    }
+
+    /// Constructs a driver with a package manifest directory. If the package
+    /// defines a workspace, the members of the workspace are built together
+    /// with the package. They are compiled with the features and lints of the
+    /// package, and their assemblies are written to a subdirectory of the
+    /// output directory that is named after the member.
     pub fn with_package_path<P: AsRef<Path>>(
         package_path: P,
         config: Config,
     ) -> Result<(Package, Driver), anyhow::Error> {
-        // Load the manifest file as a package
+        ensure_backend_available(config.backend)?;
+
+        // Load the manifest file as a package, together with the members of its workspace
         let package = Package::from_file(package_path)?;
+        let members = package.workspace_members()?;
 
         // Determine output directory
         let output_dir = ensure_package_output_dir(&package, &config)
             .map_err(|e| anyhow::anyhow!("could not create package output directory: {}", e))?;
 
+        // Determine the features that are enabled for the package
+        let features = package
+            .manifest()
+            .resolve_features(&config.features, config.default_features)?;
+
+        // Determine the levels of the lints that are configured for the package
+        let mut lint_levels = LintLevels::default();
+        for (name, level) in package.manifest().lints() {
+            let lint = Lint::from_name(name).ok_or_else(|| {
+                anyhow::anyhow!("unknown lint `{name}` in the `[lints]` section of the manifest")
+            })?;
+            lint_levels.set(
+                lint,
+                match level {
+                    mun_project::LintLevel::Allow => LintLevel::Allow,
+                    mun_project::LintLevel::Warn => LintLevel::Warn,
+                    mun_project::LintLevel::Deny => LintLevel::Deny,
+                },
+            );
+        }
+
         // Construct the driver
+        let config = Config {
+            debug_info: config.debug_info || package.manifest().profile().debug,
+            ..config
+        };
+        // A hermetic build only reads the source files of the package and the members
+        // of its workspace
+        let allowlist = if config.hermetic {
+            Some(source_allowlist(std::iter::once(&package).chain(&members))?)
+        } else {
+            None
+        };
+        let incremental =
+            IncrementalCache::new(output_dir.join(INCREMENTAL_DIRNAME), &config, &features);
         let mut driver = Driver::with_config(config, output_dir);
+        if allowlist.is_some() {
+            // A hermetic build does not reuse the outputs of previous builds
+            let mut inputs = InputManifest::default();
+            inputs.add_package(WORKSPACE, &package)?;
+            driver.inputs = Some(inputs);
+        } else {
+            driver.incremental = Some(incremental);
+        }
+        driver
+            .db
+            .set_cfg_options(Arc::new(CfgOptions::with_features(features)));
+        driver.db.set_lint_levels(Arc::new(lint_levels));
 
         // Iterate over all files in the source directory of the package and store their
         // information in the database
-        let source_directory = package.source_directory();
-        if !source_directory.is_dir() {
-            anyhow::bail!("the source directory does not exist")
-        }
-
-        for source_file_path in iter_source_files(&source_directory) {
-            let relative_path = compute_source_relative_path(&source_directory, &source_file_path)?;
-
-            // Load the contents of the file
-            let file_contents = std::fs::read_to_string(&source_file_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "could not read contents of '{}': {}",
-                    source_file_path.display(),
-                    e
-                )
-            })?;
-
+        let start = Instant::now();
+        for (relative_path, file_contents) in
+            read_source_files(&package.source_directory(), allowlist.as_ref())?
+        {
             let file_id = driver.alloc_file_id(&relative_path)?;
             driver.db.set_file_text(file_id, Arc::from(file_contents));
             driver.db.set_file_source_root(file_id, WORKSPACE);
-            driver
-                .source_root
-                .insert_file(file_id, relative_path.clone());
+            driver.source_root.insert_file(file_id, relative_path);
         }
 
         // Store the source root in the database
@@ -145,7 +289,41 @@ impl Driver {
             .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
 
         let mut package_set = PackageSet::default();
-        package_set.add_package(WORKSPACE);
+        let package_id = package_set.add_package(WORKSPACE);
+        package_set.set_version(package_id, package.version().clone());
+
+        // Every member of the workspace is a separate package with its own source root.
+        // The files of members are not tracked by path, so they cannot be updated through
+        // the driver.
+        for (idx, member) in members.iter().enumerate() {
+            let source_root_id = SourceRootId(
+                (idx + 1)
+                    .try_into()
+                    .map_err(|_e| anyhow::anyhow!("too many workspace members"))?,
+            );
+            let mut source_root = SourceRoot::default();
+            for (relative_path, file_contents) in
+                read_source_files(&member.source_directory(), allowlist.as_ref())?
+            {
+                let file_id = driver.alloc_untracked_file_id()?;
+                driver.db.set_file_text(file_id, Arc::from(file_contents));
+                driver.db.set_file_source_root(file_id, source_root_id);
+                source_root.insert_file(file_id, relative_path);
+            }
+            driver
+                .db
+                .set_source_root(source_root_id, Arc::new(source_root));
+            if let Some(inputs) = &mut driver.inputs {
+                inputs.add_package(source_root_id, member)?;
+            }
+            let member_id = package_set.add_package(source_root_id);
+            package_set.set_version(member_id, member.version().clone());
+            driver
+                .member_output_dirs
+                .insert(source_root_id, RelativePathBuf::from(member.name()));
+        }
+        driver.record_phase(Phase::Loading, start);
+
         driver.db.set_packages(Arc::new(package_set));
 
         Ok((package, driver))
@@ -169,6 +347,19 @@ impl Driver {
         }
 
         // Allocate a new id
+        let id = self.alloc_untracked_file_id()?;
+
+        // Update bookkeeping
+        self.path_to_file_id
+            .insert(relative_path.as_ref().to_relative_path_buf(), id);
+        self.file_id_to_path
+            .insert(id, relative_path.as_ref().to_relative_path_buf());
+
+        Ok(id)
+    }
+
+    /// Allocates a new file id that is not associated with a path.
+    fn alloc_untracked_file_id(&mut self) -> Result<FileId, anyhow::Error> {
         // TODO: See if we can figure out if the compiler cleared the cache of a certain
         // file, at  which point we can sort of reset the `next_file_id`
         let id = FileId(
@@ -177,13 +368,6 @@ impl Driver {
                 .map_err(|_e| anyhow::anyhow!("too many active source files"))?,
         );
         self.next_file_id += 1;
-
-        // Update bookkeeping
-        self.path_to_file_id
-            .insert(relative_path.as_ref().to_relative_path_buf(), id);
-        self.file_id_to_path
-            .insert(id, relative_path.as_ref().to_relative_path_buf());
-
         Ok(id)
     }
 }
@@ -211,58 +395,143 @@ impl Driver {
     pub fn emit_diagnostics(
         &self,
         writer: &mut dyn std::io::Write,
-        display_color: DisplayColor,
+        message_format: impl Into<MessageFormat>,
     ) -> Result<bool, anyhow::Error> {
-        let emit_colors = display_color.should_enable();
+        let message_format = message_format.into();
         let mut has_error = false;
 
-        for package in mun_hir::Package::all(self.db.upcast()) {
-            for module in package.modules(self.db.upcast()) {
-                if let Some(file_id) = module.file_id(self.db.upcast()) {
-                    let parse = self.db.parse(file_id);
-                    let source_code = self.db.file_text(file_id);
-                    let relative_file_path = self.db.file_relative_path(file_id);
-                    let line_index = self.db.line_index(file_id);
-
-                    // Emit all syntax diagnostics
-                    for syntax_error in parse.errors().iter() {
-                        emit_syntax_error(
-                            syntax_error,
-                            relative_file_path.as_str(),
-                            &source_code,
-                            &line_index,
-                            emit_colors,
-                            writer,
-                        )?;
-                        has_error = true;
-                    }
+        let modules: Vec<(Module, FileId)> = mun_hir::Package::all(self.db.upcast())
+            .into_iter()
+            .flat_map(|package| package.modules(self.db.upcast()))
+            .filter_map(|module| Some((module, module.file_id(self.db.upcast())?)))
+            .collect();
 
-                    // Emit all HIR diagnostics
-                    let mut error = None;
-                    module.diagnostics(
-                        self.db.upcast(),
-                        &mut DiagnosticSink::new(|d| {
-                            has_error = true;
-                            if let Err(e) =
-                                emit_hir_diagnostic(d, &self.db, file_id, emit_colors, writer)
-                            {
-                                error = Some(e);
-                            };
-                        }),
-                    );
-
-                    // If an error occurred when emitting HIR diagnostics, return early with the
-                    // error.
-                    if let Some(e) = error {
-                        return Err(e.into());
-                    }
+        // Parse all files up front, so parsing can be reported as a separate phase
+        self.report_progress(Progress::Started {
+            phase: Phase::Parsing,
+            total: modules.len(),
+        });
+        for (completed, (_, file_id)) in modules.iter().enumerate() {
+            let start = Instant::now();
+            self.db.parse(*file_id);
+            self.record_phase(Phase::Parsing, start);
+            self.report_progress(Progress::Step {
+                phase: Phase::Parsing,
+                name: file_display_path(&self.db, *file_id).as_str(),
+                completed: completed + 1,
+                total: modules.len(),
+            });
+        }
+        self.report_progress(Progress::Finished {
+            phase: Phase::Parsing,
+        });
+
+        // While progress is reported, diagnostics are buffered and written after the
+        // analysis finished, so they are not interleaved with the progress.
+        let mut buffer = Vec::new();
+        let output: &mut dyn std::io::Write = if self.progress_handler.is_some() {
+            &mut buffer
+        } else {
+            &mut *writer
+        };
+
+        self.report_progress(Progress::Started {
+            phase: Phase::Analysis,
+            total: modules.len(),
+        });
+        for (completed, &(module, file_id)) in modules.iter().enumerate() {
+            let parse = self.db.parse(file_id);
+
+            let source_code = self.db.file_text(file_id);
+            let relative_file_path = file_display_path(&self.db, file_id);
+            let line_index = self.db.line_index(file_id);
+
+            // Emit all syntax diagnostics
+            for syntax_error in parse.errors().iter() {
+                match message_format {
+                    MessageFormat::Human(display_color) => emit_syntax_error(
+                        syntax_error,
+                        relative_file_path.as_str(),
+                        &source_code,
+                        &line_index,
+                        display_color.should_enable(),
+                        output,
+                    )?,
+                    MessageFormat::Json => emit_syntax_error_json(
+                        syntax_error,
+                        relative_file_path.as_str(),
+                        &line_index,
+                        output,
+                    )?,
                 }
+                has_error = true;
             }
+
+            // Emit all HIR diagnostics
+            let start = Instant::now();
+            let mut error = None;
+            module.diagnostics(
+                self.db.upcast(),
+                &mut DiagnosticSink::new(|d| {
+                    if d.severity() == Severity::Error {
+                        has_error = true;
+                    }
+                    let result = match message_format {
+                        MessageFormat::Human(display_color) => emit_hir_diagnostic(
+                            d,
+                            &self.db,
+                            file_id,
+                            display_color.should_enable(),
+                            output,
+                        ),
+                        MessageFormat::Json => {
+                            emit_hir_diagnostic_json(d, &self.db, file_id, output)
+                        }
+                    };
+                    if let Err(e) = result {
+                        error = Some(e);
+                    };
+                }),
+            );
+            self.record_phase(Phase::Analysis, start);
+
+            // If an error occurred when emitting HIR diagnostics, return early with the
+            // error.
+            if let Some(e) = error {
+                return Err(e.into());
+            }
+            self.report_progress(Progress::Step {
+                phase: Phase::Analysis,
+                name: relative_file_path.as_str(),
+                completed: completed + 1,
+                total: modules.len(),
+            });
         }
+        self.report_progress(Progress::Finished {
+            phase: Phase::Analysis,
+        });
+        writer.write_all(&buffer)?;
 
         Ok(has_error)
     }
 
+    /// Emits all diagnostic messages currently in the database to the console;
+    /// returns true if errors were emitted. Human-readable diagnostics are
+    /// written to stderr, JSON diagnostics to stdout so they can be piped into
+    /// other tools.
+    pub fn emit_diagnostics_to_console(
+        &self,
+        message_format: impl Into<MessageFormat>,
+    ) -> Result<bool, anyhow::Error> {
+        let message_format = message_format.into();
+        match message_format {
+            MessageFormat::Human(_) => {
+                self.emit_diagnostics(&mut std::io::stderr(), message_format)
+            }
+            MessageFormat::Json => self.emit_diagnostics(&mut std::io::stdout(), message_format),
+        }
+    }
+
     /// Returns all diagnostics as a human readable string
     pub fn emit_diagnostics_to_string(
         &self,
@@ -326,31 +595,314 @@ impl Driver {
             .with_extension(AssemblyIr::EXTENSION)
     }
 
+    /// Get the paths where the driver will write the assemblies of all modules.
+    pub fn assembly_output_paths(&self) -> Vec<PathBuf> {
+        self.db
+            .module_partition()
+            .iter()
+            .map(|(_, module_group)| {
+                self.path_for_module_group(module_group)
+                    .with_extension(TargetAssembly::EXTENSION)
+            })
+            .collect()
+    }
+
     /// Returns the output path for the specified module group without an
     /// extension
     fn path_for_module_group(&self, module_group: &ModuleGroup) -> PathBuf {
-        module_group.relative_file_path().to_path(&self.out_dir)
+        self.relative_path_for_module_group(module_group)
+            .to_path(&self.out_dir)
     }
 
-    /// Writes all assemblies. If `force` is false, the binary will not be
-    /// written if there are no changes since last time it was written.
-    pub fn write_all_assemblies(&mut self, force: bool) -> Result<(), anyhow::Error> {
-        let _lock = self.acquire_filesystem_output_lock();
+    /// Returns the output path for the specified module group relative to the
+    /// output directory without an extension. The assemblies of the members
+    /// of a workspace are stored in a subdirectory.
+    fn relative_path_for_module_group(&self, module_group: &ModuleGroup) -> RelativePathBuf {
+        let file_path = module_group.relative_file_path();
+        match module_group
+            .iter()
+            .find_map(|module| module.file_id(self.db.upcast()))
+            .and_then(|file_id| {
+                self.member_output_dirs
+                    .get(&self.db.file_source_root(file_id))
+            }) {
+            Some(member_dir) => member_dir.join(file_path),
+            None => file_path,
+        }
+    }
 
-        // Create a copy of all current files
-        for package in mun_hir::Package::all(self.db.upcast()) {
-            for module in package.modules(self.db.upcast()) {
-                if self.emit_ir {
-                    self.write_assembly_ir(module)?;
+    /// Builds the assemblies of all module groups and returns their contents,
+    /// without writing anything to the output directory. The contents are an
+    /// IR file if [`Config::emit_ir`] is enabled and a munlib otherwise.
+    pub fn emit_assemblies_to_memory(
+        &self,
+    ) -> Result<Vec<(ModuleGroupId, Vec<u8>)>, std::io::Error> {
+        let module_partition = self.db.module_partition();
+        let total = module_partition.iter().count();
+        self.report_progress(Progress::Started {
+            phase: Phase::CodeGeneration,
+            total,
+        });
+        let assemblies = module_partition
+            .iter()
+            .enumerate()
+            .map(|(completed, (module_group_id, module_group))| {
+                let start = Instant::now();
+                let assembly = if self.emit_ir {
+                    let assembly = self.db.assembly_ir(module_group_id);
+                    self.record_phase(Phase::CodeGeneration, start);
+                    BuiltAssembly::Ir(assembly)
                 } else {
-                    self.write_target_assembly(module, force)?;
+                    let assembly = self.db.target_assembly(module_group_id);
+                    self.record_assembly(&module_group.name, &assembly, start);
+                    BuiltAssembly::Target(assembly)
+                };
+                let contents = std::fs::read(assembly.path())?;
+                self.report_progress(Progress::Step {
+                    phase: Phase::CodeGeneration,
+                    name: self.relative_path_for_module_group(module_group).as_str(),
+                    completed: completed + 1,
+                    total,
+                });
+                Ok((module_group_id, contents))
+            })
+            .collect();
+        self.report_progress(Progress::Finished {
+            phase: Phase::CodeGeneration,
+        });
+        assemblies
+    }
+
+    /// Writes all assemblies. If `force` is false, assemblies whose contents
+    /// did not change since the last time they were written are skipped, as
+    /// are assemblies whose inputs did not change since a previous invocation
+    /// of the compiler wrote them. If no assembly changed, the output directory
+    /// is not touched at all.
+    pub fn write_all_assemblies(&mut self, force: bool) -> Result<(), anyhow::Error> {
+        let module_partition = self.db.module_partition();
+        let declarations_fingerprint = self
+            .incremental
+            .as_ref()
+            .map(|_| IncrementalCache::declarations_fingerprint(&self.db));
+
+        // Determine which assemblies changed since they were last written
+        let mut changed_assemblies = Vec::new();
+        let mut input_fingerprints = Vec::new();
+        let mut output_names: HashMap<String, &str> = HashMap::new();
+        let total = module_partition.iter().count();
+        self.report_progress(Progress::Started {
+            phase: Phase::CodeGeneration,
+            total,
+        });
+        for (completed, (module_group_id, module_group)) in module_partition.iter().enumerate() {
+            // Module names are case-sensitive but filesystems might not be
+            let relative_path = self.relative_path_for_module_group(module_group);
+            let name = relative_path.to_string();
+            let report_step = || {
+                self.report_progress(Progress::Step {
+                    phase: Phase::CodeGeneration,
+                    name: &name,
+                    completed: completed + 1,
+                    total,
+                });
+            };
+            let output_name = relative_path.as_str().to_lowercase();
+            if let Some(other) = output_names.insert(output_name, &module_group.name) {
+                anyhow::bail!(
+                    "the assemblies of modules `{}` and `{}` would be written to the same file on case-insensitive filesystems",
+                    other,
+                    module_group.name
+                );
+            }
+
+            let extension = if self.emit_ir {
+                AssemblyIr::EXTENSION
+            } else {
+                TargetAssembly::EXTENSION
+            };
+            let output_path = relative_path
+                .to_path(&self.out_dir)
+                .with_extension(extension);
+
+            // Reuse the assembly written by a previous invocation of the compiler if its
+            // inputs did not change
+            let mut input_fingerprint = None;
+            if let (Some(incremental), Some(declarations_fingerprint)) =
+                (&self.incremental, declarations_fingerprint)
+            {
+                let fingerprint =
+                    incremental.input_fingerprint(&self.db, module_group, declarations_fingerprint);
+                if !force
+                    && output_path.is_file()
+                    && incremental.stored_fingerprint(&relative_path) == Some(fingerprint)
+                {
+                    report_step();
+                    continue;
+                }
+                input_fingerprints.push((relative_path, fingerprint));
+                input_fingerprint = Some(fingerprint);
+            }
+
+            // Restore the assembly from the artifact cache if another build already
+            // generated it from the same inputs
+            let cached_path = input_fingerprint
+                .zip(self.artifact_cache.as_ref())
+                .and_then(|(fingerprint, cache)| cache.get(fingerprint, extension));
+
+            let start = Instant::now();
+            let assembly = if let Some(cached_path) = cached_path {
+                log::trace!(
+                    "restoring assembly {} from the artifact cache",
+                    output_path.display()
+                );
+                BuiltAssembly::Cached(cached_path)
+            } else if self.emit_ir {
+                let assembly = self.db.assembly_ir(module_group_id);
+                self.record_phase(Phase::CodeGeneration, start);
+                BuiltAssembly::Ir(assembly)
+            } else {
+                let assembly = self.db.target_assembly(module_group_id);
+                self.record_assembly(&module_group.name, &assembly, start);
+                BuiltAssembly::Target(assembly)
+            };
+            if let (Some(cache), Some(input_fingerprint)) =
+                (&self.artifact_cache, input_fingerprint)
+            {
+                if !matches!(assembly, BuiltAssembly::Cached(_)) {
+                    // Failing to share the assembly should not fail the build
+                    if let Err(e) = cache.store(input_fingerprint, extension, assembly.path()) {
+                        log::warn!("could not store assembly in the artifact cache: {e}");
+                    }
                 }
             }
+            let fingerprint = fingerprint_file(assembly.path())?;
+
+            if force
+                || !output_path.is_file()
+                || self.written_fingerprints.get(&output_path) != Some(&fingerprint)
+            {
+                changed_assemblies.push((name.clone(), output_path, assembly, fingerprint));
+            }
+            report_step();
+        }
+        self.report_progress(Progress::Finished {
+            phase: Phase::CodeGeneration,
+        });
+
+        // The module graph only changes if the partition or the calls between functions
+        // change, both of which also change an assembly.
+        let graph_path = self.out_dir.join(MODULE_GRAPH_FILENAME);
+        let write_module_graph =
+            self.emit_module_graph && (!changed_assemblies.is_empty() || !graph_path.is_file());
+
+        // A hermetic build always describes its inputs
+        if changed_assemblies.is_empty()
+            && input_fingerprints.is_empty()
+            && !write_module_graph
+            && self.inputs.is_none()
+        {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let _lock = self.acquire_filesystem_output_lock();
+
+        // Forget the inputs of the assemblies that are about to be replaced, so an
+        // interrupted build never pairs an assembly with the wrong inputs.
+        if let Some(incremental) = &self.incremental {
+            for (relative_path, _) in &input_fingerprints {
+                incremental.remove_fingerprint(relative_path)?;
+            }
         }
 
+        let total = changed_assemblies.len();
+        self.report_progress(Progress::Started {
+            phase: Phase::Writing,
+            total,
+        });
+        for (completed, (name, output_path, assembly, fingerprint)) in
+            changed_assemblies.into_iter().enumerate()
+        {
+            log::trace!("writing assembly {}", output_path.display());
+            if let Some(parent) = output_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            assembly.copy_to(&output_path)?;
+
+            // Store the fingerprint so we maybe don't have to write it next time
+            self.written_fingerprints.insert(output_path, fingerprint);
+            self.report_progress(Progress::Step {
+                phase: Phase::Writing,
+                name: &name,
+                completed: completed + 1,
+                total,
+            });
+        }
+        self.report_progress(Progress::Finished {
+            phase: Phase::Writing,
+        });
+
+        if let Some(incremental) = &self.incremental {
+            for (relative_path, input_fingerprint) in input_fingerprints {
+                incremental.store_fingerprint(&relative_path, input_fingerprint)?;
+            }
+        }
+
+        if write_module_graph {
+            log::trace!("writing module graph {}", graph_path.display());
+            std::fs::write(graph_path, module_partition.to_dot(self.db.upcast()))?;
+        }
+
+        if let Some(inputs) = &self.inputs {
+            let inputs_path = self.out_dir.join(INPUTS_FILENAME);
+            log::trace!("writing input manifest {}", inputs_path.display());
+            std::fs::write(
+                inputs_path,
+                serde_json::to_string_pretty(&inputs.to_json(&self.db))?,
+            )?;
+        }
+        self.record_phase(Phase::Writing, start);
+
         Ok(())
     }
 
+    /// Writes a report of the timings that were recorded so far to the output
+    /// directory, if [`Config::timings`] is enabled. Returns the path of the
+    /// HTML report.
+    pub fn write_timings_report(&self) -> Result<Option<PathBuf>, std::io::Error> {
+        self.db
+            .timings()
+            .map(|timings| timings.write_report(&self.out_dir))
+            .transpose()
+    }
+
+    /// Notifies the progress handler, if one is registered.
+    fn report_progress(&self, progress: Progress<'_>) {
+        if let Some(handler) = &self.progress_handler {
+            handler(progress);
+        }
+    }
+
+    /// Adds the time since `start` to the time spent in a phase, if timings are
+    /// recorded.
+    fn record_phase(&self, phase: Phase, start: Instant) {
+        if let Some(profiler) = self.db.profiler() {
+            profiler.record_phase(phase, start.elapsed());
+        }
+    }
+
+    /// Records the time it took to build the assembly of a module group, if
+    /// timings are recorded and the assembly was built after `start` instead
+    /// of being retrieved from the cache.
+    fn record_assembly(&self, name: &str, assembly: &TargetAssembly, start: Instant) {
+        if let Some(profiler) = self.db.profiler() {
+            let timings = assembly.timings();
+            if start.elapsed() >= timings.code_generation + timings.linking {
+                profiler.record_assembly(name, timings);
+            }
+        }
+    }
+
     /// Acquires a filesystem lock on the output directory. This ensures that
     /// multiple instances cannot write to the same output directory and
     /// that the runtime does not start reading before we finished writing.
@@ -375,81 +927,6 @@ impl Driver {
             };
         }
     }
-
-    /// Generates an assembly for the target machine and specified module and
-    /// stores it in the output location. If `force` is false, the binary
-    /// will not be written if there are no changes since last time it was
-    /// written. Returns `true` if the assembly was written, `false`
-    /// if it was up to date.
-    fn write_target_assembly(
-        &mut self,
-        module: Module,
-        force: bool,
-    ) -> Result<bool, anyhow::Error> {
-        log::trace!("writing target assembly for {:?}", module);
-
-        // Find the module group to which the module belongs
-        let module_partition = self.db.module_partition();
-        let module_group_id = module_partition
-            .group_for_module(module)
-            .expect("could not find the module in the module partition");
-        let module_group = &module_partition[module_group_id];
-
-        // Get the compiled assembly
-        let assembly = self.db.target_assembly(module_group_id);
-
-        // Determine the filename of the group
-        let assembly_path = self
-            .path_for_module_group(module_group)
-            .with_extension(TargetAssembly::EXTENSION);
-
-        // Did the assembly change since last time?
-        if !force
-            && assembly_path.is_file()
-            && self
-                .module_to_temp_assembly_path
-                .get(&module)
-                .map(AsRef::as_ref)
-                == Some(assembly.path())
-        {
-            return Ok(false);
-        }
-
-        // It did change or we are forced, so write it to disk
-        assembly.copy_to(&assembly_path)?;
-
-        // Store the information so we maybe don't have to write it next time
-        self.module_to_temp_assembly_path
-            .insert(module, assembly.path().to_path_buf());
-
-        Ok(true)
-    }
-
-    /// Generates IR for the specified module and stores it in the output
-    /// location.
-    fn write_assembly_ir(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
-        log::trace!("writing assembly IR for {:?}", module);
-
-        // Find the module group to which the module belongs
-        let module_partition = self.db.module_partition();
-        let module_group_id = module_partition
-            .group_for_module(module)
-            .expect("could not find the module in the module partition");
-        let module_group = &module_partition[module_group_id];
-
-        // Get the compiled assembly
-        let assembly_ir = self.db.assembly_ir(module_group_id);
-
-        // Determine the filename of the group
-        let assembly_path = self
-            .path_for_module_group(module_group)
-            .with_extension(AssemblyIr::EXTENSION);
-
-        // Write to disk
-        assembly_ir.copy_to(assembly_path)?;
-
-        Ok(())
-    }
 }
 
 impl Driver {
@@ -519,6 +996,8 @@ impl Driver {
         self.file_id_to_path
             .insert(file_id, to.as_ref().to_relative_path_buf());
         self.path_to_file_id.remove(from.as_ref()); // FileId now belongs to to
+        self.path_to_file_id
+            .insert(to.as_ref().to_relative_path_buf(), file_id);
 
         self.source_root.remove_file(file_id);
         self.source_root
@@ -530,6 +1009,187 @@ impl Driver {
     }
 }
 
+impl Driver {
+    /// Sets the contents of the virtual file at the specified `path`, adding
+    /// the file if it does not exist yet. Returns the `FileId` of the file,
+    /// which stays the same for as long as the driver exists, even if the file
+    /// is removed and added again.
+    ///
+    /// Virtual files are never read from or written to disk, which enables
+    /// tools that embed the compiler to inject generated source code, e.g.
+    /// bindings generated from reflection data. The `path` is relative to a
+    /// separate source root whose files form a separate package.
+    /// Its assemblies are written to the [`VIRTUAL_OUTPUT_DIRNAME`]
+    /// subdirectory of the output directory and diagnostics refer to its files
+    /// by their path prefixed with [`VIRTUAL_PATH_PREFIX`].
+    pub fn set_virtual_file<P: AsRef<RelativePath>>(
+        &mut self,
+        path: P,
+        contents: String,
+    ) -> anyhow::Result<FileId> {
+        let path = path.as_ref();
+        if !is_source_file(path.as_str()) {
+            return Err(anyhow::anyhow!(
+                "the virtual file '{path}' is not a Mun source file"
+            ));
+        }
+
+        let file_id = if let Some(file_id) = self.virtual_file_ids.get(path) {
+            *file_id
+        } else {
+            let file_id = self.alloc_untracked_file_id()?;
+            self.virtual_file_ids
+                .insert(path.to_relative_path_buf(), file_id);
+            file_id
+        };
+        self.db.set_file_text(file_id, Arc::from(contents));
+
+        if !self.virtual_source_root.contains(file_id) {
+            self.db.set_file_source_root(file_id, VIRTUAL);
+            self.virtual_source_root
+                .insert_file(file_id, path.to_relative_path_buf());
+            self.db
+                .set_source_root(VIRTUAL, Arc::new(self.virtual_source_root.clone()));
+            self.ensure_virtual_package();
+        }
+
+        Ok(file_id)
+    }
+
+    /// Removes the virtual file at the specified `path`. Returns the `FileId`
+    /// of the file, or `None` if there is no virtual file at the path.
+    pub fn remove_virtual_file<P: AsRef<RelativePath>>(&mut self, path: P) -> Option<FileId> {
+        let file_id = *self.virtual_file_ids.get(path.as_ref())?;
+        if !self.virtual_source_root.remove_file(file_id) {
+            return None;
+        }
+        self.db
+            .set_source_root(VIRTUAL, Arc::new(self.virtual_source_root.clone()));
+
+        Some(file_id)
+    }
+
+    /// Returns the `FileId` of the virtual file at the specified `path`
+    pub fn get_file_id_for_virtual_path<P: AsRef<RelativePath>>(&self, path: P) -> Option<FileId> {
+        let file_id = *self.virtual_file_ids.get(path.as_ref())?;
+        self.virtual_source_root
+            .contains(file_id)
+            .then_some(file_id)
+    }
+
+    /// Adds the package of the virtual files to the package set, if it was not
+    /// added before. The package is not removed when all virtual files are
+    /// removed, because that would change the ids of other packages.
+    fn ensure_virtual_package(&mut self) {
+        let packages = self.db.packages();
+        if packages
+            .iter()
+            .any(|package| packages[package].source_root == VIRTUAL)
+        {
+            return;
+        }
+
+        let mut package_set = PackageSet::clone(&packages);
+        package_set.add_package(VIRTUAL);
+        self.db.set_packages(Arc::new(package_set));
+        self.member_output_dirs
+            .insert(VIRTUAL, RelativePathBuf::from(VIRTUAL_OUTPUT_DIRNAME));
+    }
+}
+
+/// Returns the path by which the file with the specified `file_id` is referred
+/// to, e.g. in diagnostics. This is the path of the file relative to its source
+/// root, prefixed with [`VIRTUAL_PATH_PREFIX`] if it is a virtual file.
+pub(crate) fn file_display_path(
+    db: &(impl SourceDatabase + ?Sized),
+    file_id: FileId,
+) -> RelativePathBuf {
+    let relative_path = db.file_relative_path(file_id);
+    if db.file_source_root(file_id) == VIRTUAL {
+        RelativePath::new(VIRTUAL_PATH_PREFIX).join(relative_path)
+    } else {
+        relative_path
+    }
+}
+
+/// An assembly that was built by the compiler and stored in a temporary
+/// location.
+enum BuiltAssembly {
+    Target(Arc<TargetAssembly>),
+    Ir(Arc<AssemblyIr>),
+    /// An assembly that is stored in the artifact cache
+    Cached(PathBuf),
+}
+
+impl BuiltAssembly {
+    /// Returns the current location of the assembly
+    fn path(&self) -> &Path {
+        match self {
+            BuiltAssembly::Target(assembly) => assembly.path(),
+            BuiltAssembly::Ir(assembly) => assembly.path(),
+            BuiltAssembly::Cached(path) => path,
+        }
+    }
+
+    /// Copies the assembly to the specified location
+    fn copy_to(&self, destination: &Path) -> Result<(), std::io::Error> {
+        match self {
+            BuiltAssembly::Target(assembly) => assembly.copy_to(destination),
+            BuiltAssembly::Ir(assembly) => assembly.copy_to(destination),
+            BuiltAssembly::Cached(path) => std::fs::copy(path, destination).map(|_| ()),
+        }
+    }
+}
+
+/// Returns an error if this build of the compiler cannot generate code with
+/// the specified backend.
+fn ensure_backend_available(backend: Backend) -> anyhow::Result<()> {
+    if backend.is_available() {
+        Ok(())
+    } else {
+        anyhow::bail!("the {backend} backend is not available in this build of the compiler")
+    }
+}
+
+/// Reads all source files in the source directory of a package. Returns the
+/// paths of the files relative to the source directory together with their
+/// contents. If an `allowlist` is specified, reading a file that is not part
+/// of it fails.
+fn read_source_files(
+    source_directory: &Path,
+    allowlist: Option<&MonitorDirectories>,
+) -> anyhow::Result<Vec<(RelativePathBuf, String)>> {
+    if !source_directory.is_dir() {
+        anyhow::bail!("the source directory does not exist")
+    }
+
+    iter_source_files(source_directory)
+        .map(|source_file_path| {
+            let relative_path = compute_source_relative_path(source_directory, &source_file_path)?;
+            if let Some(allowlist) = allowlist {
+                ensure_allowed(allowlist, &source_file_path)?;
+            }
+
+            // Load the contents of the file
+            let file_contents = std::fs::read_to_string(&source_file_path).map_err(|e| {
+                anyhow::anyhow!(
+                    "could not read contents of '{}': {}",
+                    source_file_path.display(),
+                    e
+                )
+            })?;
+            Ok((relative_path, file_contents))
+        })
+        .collect()
+}
+
+/// Computes a fingerprint of the contents of the file at the specified path.
+fn fingerprint_file(path: &Path) -> Result<u64, std::io::Error> {
+    let mut hasher = DefaultHasher::new();
+    std::fs::read(path)?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
 pub fn iter_source_files(source_dir: &Path) -> impl Iterator<Item = PathBuf> {
     WalkDir::new(source_dir)
         .into_iter()
@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+
+/// A directory that stores assemblies keyed by a fingerprint of all of their
+/// inputs: their sources, the declarations and versions of all packages, the
+/// version of the compiler, and the configuration of the build.
+///
+/// Unlike the incremental cache, which only remembers the inputs of the
+/// assemblies in the output directory of a single package, the artifact cache
+/// can be shared between checkouts and machines, e.g. by pointing every CI
+/// job at the same (network) directory. An assembly whose inputs are found in
+/// the cache is restored instead of generated.
+pub(super) struct ArtifactCache {
+    dir: PathBuf,
+}
+
+impl ArtifactCache {
+    /// Constructs a cache that stores its assemblies in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the path of the cached assembly with the specified input
+    /// fingerprint and extension, if the cache contains one.
+    pub fn get(&self, fingerprint: u64, extension: &str) -> Option<PathBuf> {
+        let path = self.entry_path(fingerprint, extension);
+        path.is_file().then_some(path)
+    }
+
+    /// Stores a copy of the assembly at `path` with the specified input
+    /// fingerprint. The assembly is copied to a temporary file first, so
+    /// concurrent builds never restore a partially written assembly.
+    pub fn store(&self, fingerprint: u64, extension: &str, path: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let entry_path = self.entry_path(fingerprint, extension);
+        let temp_path = entry_path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::copy(path, &temp_path)?;
+        std::fs::rename(&temp_path, entry_path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp_path);
+        })
+    }
+
+    /// Returns the path of the assembly with the specified input fingerprint
+    /// and extension in the cache.
+    fn entry_path(&self, fingerprint: u64, extension: &str) -> PathBuf {
+        self.dir.join(format!("{fingerprint:016x}.{extension}"))
+    }
+}
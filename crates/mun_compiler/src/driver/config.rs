@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{Backend, OptimizationLevel};
+pub use mun_hir::LruCapacities;
 use mun_target::spec::Target;
 
 /// Describes all the permanent settings that are used during compilations.
@@ -9,15 +10,76 @@ pub struct Config {
     /// The target triple to compile the code for.
     pub target: Target,
 
+    /// The backend that generates the machine code of the assemblies.
+    pub backend: Backend,
+
     /// The optimization level to use for the IR generation.
     pub optimization_lvl: OptimizationLevel,
 
+    /// Whether integer arithmetic that overflows panics instead of wrapping
+    /// around. Enabled by default for unoptimized builds.
+    pub overflow_checks: bool,
+
+    /// Whether to generate debug information, which enables native debuggers
+    /// to step through Mun source code. Also enabled by `debug = true` in the
+    /// `[profile]` section of a package manifest.
+    pub debug_info: bool,
+
+    /// Whether to generate a trampoline for every public function, which
+    /// enables a host to call the function with `Runtime::invoke_dynamic`.
+    pub dynamic_invocation: bool,
+
     /// The optional output directory to store all outputs. If no directory is
     /// specified all output is stored in a temporary directory.
     pub out_dir: Option<PathBuf>,
 
     /// Whether or not to emit an IR file instead of a munlib.
     pub emit_ir: bool,
+
+    /// Whether or not to also write a `GraphViz` file (`modules.dot`) to the
+    /// output directory that describes how modules are grouped into
+    /// assemblies and which calls go through a dispatch table.
+    pub emit_module_graph: bool,
+
+    /// Whether to record how long the phases of the compilation take and how
+    /// often the queries of the compiler are executed, see
+    /// [`CompilerDatabase::timings`].
+    ///
+    /// [`CompilerDatabase::timings`]: crate::CompilerDatabase::timings
+    pub timings: bool,
+
+    /// Whether to print how many results of the queries that take up the most
+    /// memory are stored in the compiler database after a build, see
+    /// [`CompilerDatabase::memory_usage`].
+    ///
+    /// [`CompilerDatabase::memory_usage`]: crate::CompilerDatabase::memory_usage
+    pub memory_report: bool,
+
+    /// The maximum number of results that are kept in memory for the queries
+    /// whose results take up the most memory. Limiting them keeps the memory
+    /// usage of long running compilations, e.g. in watch mode, bounded.
+    pub lru_capacities: LruCapacities,
+
+    /// The features that are enabled for `#[cfg(feature = "...")]`
+    /// attributes. When compiling a package, these must be declared in the
+    /// `[features]` section of its manifest.
+    pub features: Vec<String>,
+
+    /// Whether the `default` feature of a package is enabled.
+    pub default_features: bool,
+
+    /// Whether to build a package hermetically. A hermetic build refuses to
+    /// read source files that resolve to a path outside of the source
+    /// directories of the package and the members of its workspace, does not
+    /// reuse the outputs of previous builds, and writes the SHA-256 hash of
+    /// every file it read to `inputs.json` in the output directory.
+    pub hermetic: bool,
+
+    /// An optional directory in which assemblies are stored keyed by a
+    /// fingerprint of all of their inputs, so builds of unchanged packages
+    /// can restore them instead of generating them. The directory can be
+    /// shared between checkouts and machines. Not used by hermetic builds.
+    pub artifact_cache: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -27,9 +89,21 @@ impl Default for Config {
             // This unwrap is safe because we only compile for targets that have an implemented host
             // triple.
             target: target.unwrap(),
+            backend: Backend::default(),
             optimization_lvl: OptimizationLevel::Default,
+            overflow_checks: false,
+            debug_info: false,
+            dynamic_invocation: false,
             out_dir: None,
             emit_ir: false,
+            emit_module_graph: false,
+            timings: false,
+            memory_report: false,
+            lru_capacities: LruCapacities::default(),
+            features: Vec::new(),
+            default_features: true,
+            hermetic: false,
+            artifact_cache: None,
         }
     }
 }
@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+use mun_hir_input::{SourceDatabase, SourceRootId};
+use mun_paths::{AbsPathBuf, RelativePathBuf};
+use mun_project::Package;
+use mun_vfs::MonitorDirectories;
+
+use crate::db::CompilerDatabase;
+
+/// Records the inputs of a hermetic build, from which a manifest of the
+/// hashes of every file that was used by the build is written to
+/// [`super::INPUTS_FILENAME`].
+///
+/// The hashes of source files are computed from the contents stored in the
+/// database when the manifest is written, so they always describe the
+/// sources that the assemblies were built from.
+#[derive(Default)]
+pub(super) struct InputManifest {
+    /// The name of the package of every source root, together with the path
+    /// of its source directory relative to the root of the package
+    packages: BTreeMap<SourceRootId, (String, RelativePathBuf)>,
+
+    /// The file name and SHA-256 hash of the manifest of every package, keyed
+    /// by the name of the package
+    manifests: BTreeMap<String, (String, String)>,
+}
+
+impl InputManifest {
+    /// Records the manifest of `package`, whose source files are stored in
+    /// `source_root`.
+    pub fn add_package(
+        &mut self,
+        source_root: SourceRootId,
+        package: &Package,
+    ) -> Result<(), std::io::Error> {
+        let manifest_path = package.manifest_path();
+        let file_name = manifest_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let hash = sha256(&std::fs::read(manifest_path)?);
+        let source_directory = package
+            .source_directory()
+            .strip_prefix(package.root())
+            .ok()
+            .and_then(|path| RelativePathBuf::from_path(path).ok())
+            .unwrap_or_default();
+        self.packages
+            .insert(source_root, (package.name().to_owned(), source_directory));
+        self.manifests
+            .insert(package.name().to_owned(), (file_name, hash));
+        Ok(())
+    }
+
+    /// Returns the manifest as a JSON value. Every file is identified by the
+    /// name of its package and its path relative to the root of the package.
+    pub fn to_json(&self, db: &CompilerDatabase) -> serde_json::Value {
+        let mut files = BTreeMap::new();
+        for (source_root_id, (package, source_directory)) in &self.packages {
+            let (manifest_file_name, manifest_hash) = &self.manifests[package];
+            files.insert(
+                (package.as_str(), manifest_file_name.clone()),
+                manifest_hash.clone(),
+            );
+
+            let source_root = db.source_root(*source_root_id);
+            for file_id in source_root.files() {
+                let path = source_directory.join(source_root.relative_path(file_id));
+                files.insert(
+                    (package.as_str(), path.to_string()),
+                    sha256(db.file_text(file_id).as_bytes()),
+                );
+            }
+        }
+
+        serde_json::json!({
+            "files": files
+                .into_iter()
+                .map(|((package, path), hash)| serde_json::json!({
+                    "package": package,
+                    "path": path,
+                    "sha256": hash,
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Returns the set of files that a hermetic build of `packages` is allowed to
+/// read, which are the source files in their source directories.
+pub(super) fn source_allowlist<'p>(
+    packages: impl IntoIterator<Item = &'p Package>,
+) -> Result<MonitorDirectories, std::io::Error> {
+    let include = packages
+        .into_iter()
+        .map(|package| {
+            let source_directory = std::path::absolute(package.source_directory())?;
+            // `std::path::absolute` always returns an absolute path
+            Ok(AbsPathBuf::try_from(source_directory).unwrap())
+        })
+        .collect::<Result<_, std::io::Error>>()?;
+    Ok(MonitorDirectories {
+        extensions: vec![String::from("mun")],
+        include,
+        exclude: Vec::new(),
+    })
+}
+
+/// Ensures that the file at `path` is part of `allowlist` once all symbolic
+/// links have been resolved. A hermetic build refuses to read files through
+/// symbolic links that point outside of the source directories of its
+/// packages.
+pub(super) fn ensure_allowed(allowlist: &MonitorDirectories, path: &Path) -> anyhow::Result<()> {
+    let absolute_path = AbsPathBuf::try_from(std::path::absolute(path)?).unwrap();
+    if !allowlist.contains_resolved_file(&absolute_path)? {
+        anyhow::bail!(
+            "hermetic build refuses to read '{}', which resolves to '{}' outside of the source directories of the packages",
+            path.display(),
+            absolute_path.canonicalize()?.display(),
+        );
+    }
+    Ok(())
+}
+
+/// Returns the SHA-256 hash of `bytes` as a hexadecimal string.
+fn sha256(bytes: &[u8]) -> String {
+    extendhash::sha256::compute_hash(bytes)
+        .iter()
+        .fold(String::new(), |mut hash, byte| {
+            let _ = write!(hash, "{byte:02x}");
+            hash
+        })
+}
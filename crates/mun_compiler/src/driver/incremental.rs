@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeSet,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+};
+
+use mun_codegen::ModuleGroup;
+use mun_hir::{HirDisplay, ModuleDef, Package};
+use mun_hir_input::SourceDatabase;
+use mun_paths::RelativePath;
+
+use super::Config;
+use crate::db::CompilerDatabase;
+
+/// The extension of the files that store the fingerprint of the inputs of an
+/// assembly
+const FINGERPRINT_EXTENSION: &str = "fingerprint";
+
+/// Persists fingerprints of the inputs of assemblies to disk, which enables
+/// reusing assemblies that were written by a previous invocation of the
+/// compiler without generating their code again.
+///
+/// The assembly of a module group only depends on the source of its own
+/// files and on the declarations of other modules, because calls to functions
+/// of other module groups go through a dispatch table. Changing the body of a
+/// function therefore only invalidates the assembly that contains it.
+pub(super) struct IncrementalCache {
+    dir: PathBuf,
+    config_fingerprint: u64,
+}
+
+impl IncrementalCache {
+    /// Constructs a cache that stores its fingerprints in `dir` for assemblies
+    /// that are compiled with the specified `config` and `features`.
+    pub fn new(dir: PathBuf, config: &Config, features: &BTreeSet<String>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        config.target.llvm_target.hash(&mut hasher);
+        config.backend.hash(&mut hasher);
+        format!("{:?}", config.optimization_lvl).hash(&mut hasher);
+        config.overflow_checks.hash(&mut hasher);
+        config.debug_info.hash(&mut hasher);
+        config.dynamic_invocation.hash(&mut hasher);
+        config.emit_ir.hash(&mut hasher);
+        features.hash(&mut hasher);
+
+        Self {
+            dir,
+            config_fingerprint: hasher.finish(),
+        }
+    }
+
+    /// Computes a fingerprint of the declarations of all modules and the
+    /// versions of all packages, which the assemblies of all module groups
+    /// depend on.
+    pub fn declarations_fingerprint(db: &CompilerDatabase) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let packages = Package::all(db);
+        for package in &packages {
+            // The versions of packages are embedded in the assemblies that depend on them
+            package.version(db).map(|v| v.to_string()).hash(&mut hasher);
+        }
+        for module in packages.into_iter().flat_map(|package| package.modules(db)) {
+            module.full_name(db).hash(&mut hasher);
+            for declaration in module.declarations(db) {
+                match declaration {
+                    ModuleDef::Function(function) => {
+                        function.export_name(db).hash(&mut hasher);
+                        format!("{:?}", function.data(db).visibility()).hash(&mut hasher);
+                        function.ty(db).display(db).to_string().hash(&mut hasher);
+                    }
+                    ModuleDef::Struct(strukt) => {
+                        strukt.full_name(db).hash(&mut hasher);
+                        format!("{:?}", strukt.data(db).memory_kind).hash(&mut hasher);
+                        for field in strukt.fields(db) {
+                            field.name(db).to_string().hash(&mut hasher);
+                            field.ty(db).display(db).to_string().hash(&mut hasher);
+                        }
+                    }
+                    ModuleDef::TypeAlias(type_alias) => {
+                        type_alias.name(db).to_string().hash(&mut hasher);
+                        type_alias
+                            .target_type(db)
+                            .display(db)
+                            .to_string()
+                            .hash(&mut hasher);
+                    }
+                    ModuleDef::Const(konst) => {
+                        konst.name(db).to_string().hash(&mut hasher);
+                        format!("{:?}", konst.eval(db)).hash(&mut hasher);
+                    }
+                    ModuleDef::Static(statik) => {
+                        statik.full_name(db).hash(&mut hasher);
+                        statik.ty(db).display(db).to_string().hash(&mut hasher);
+                    }
+                    ModuleDef::Module(_)
+                    | ModuleDef::PrimitiveType(_)
+                    | ModuleDef::BuiltinFunction(_) => {}
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Computes a fingerprint of the inputs of the assembly of the specified
+    /// module group.
+    pub fn input_fingerprint(
+        &self,
+        db: &CompilerDatabase,
+        module_group: &ModuleGroup,
+        declarations_fingerprint: u64,
+    ) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.config_fingerprint.hash(&mut hasher);
+        declarations_fingerprint.hash(&mut hasher);
+        module_group.name.hash(&mut hasher);
+        for file_id in module_group.files(db) {
+            db.file_text(file_id).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Returns the fingerprint that was stored for the assembly with the
+    /// specified output path, relative to the output directory, if any.
+    pub fn stored_fingerprint(&self, relative_path: &RelativePath) -> Option<u64> {
+        let contents = std::fs::read_to_string(self.fingerprint_path(relative_path)).ok()?;
+        u64::from_str_radix(contents.trim(), 16).ok()
+    }
+
+    /// Stores the fingerprint of the inputs of the assembly with the specified
+    /// output path, relative to the output directory.
+    pub fn store_fingerprint(
+        &self,
+        relative_path: &RelativePath,
+        fingerprint: u64,
+    ) -> Result<(), std::io::Error> {
+        let path = self.fingerprint_path(relative_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, format!("{fingerprint:016x}"))
+    }
+
+    /// Removes the stored fingerprint of the assembly with the specified
+    /// output path, relative to the output directory, if any.
+    pub fn remove_fingerprint(&self, relative_path: &RelativePath) -> Result<(), std::io::Error> {
+        match std::fs::remove_file(self.fingerprint_path(relative_path)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the path of the file that stores the fingerprint of the
+    /// assembly with the specified output path
+    fn fingerprint_path(&self, relative_path: &RelativePath) -> PathBuf {
+        relative_path
+            .to_path(&self.dir)
+            .with_extension(FINGERPRINT_EXTENSION)
+    }
+}
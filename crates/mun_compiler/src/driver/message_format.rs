@@ -0,0 +1,18 @@
+use crate::DisplayColor;
+
+/// The format in which the [`crate::Driver`] emits diagnostics.
+#[derive(Debug, Clone, Copy)]
+pub enum MessageFormat {
+    /// Human-readable source snippets, optionally with colors
+    Human(DisplayColor),
+
+    /// A JSON object per line for every diagnostic, which can be parsed by
+    /// editors and CI
+    Json,
+}
+
+impl From<DisplayColor> for MessageFormat {
+    fn from(display_color: DisplayColor) -> Self {
+        MessageFormat::Human(display_color)
+    }
+}
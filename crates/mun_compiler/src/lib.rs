@@ -3,17 +3,19 @@
 
 mod db;
 pub mod diagnostics;
+mod diagnostics_json;
 mod diagnostics_snippets;
 mod driver;
+mod progress;
+mod timings;
 
 use std::{
     ffi::OsStr,
-    io::stderr,
     path::{Path, PathBuf},
 };
 
 pub use annotate_snippets::AnnotationType;
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{AssemblyTimings, Backend, ModuleGroupId, OptimizationLevel};
 pub use mun_hir_input::FileId;
 pub use mun_paths::{RelativePath, RelativePathBuf};
 use mun_project::Package;
@@ -21,7 +23,12 @@ pub use mun_target::spec::Target;
 
 pub use crate::{
     db::CompilerDatabase,
-    driver::{Config, DisplayColor, Driver},
+    driver::{
+        iter_source_files, Config, DisplayColor, Driver, LruCapacities, MessageFormat,
+        INPUTS_FILENAME, VIRTUAL_OUTPUT_DIRNAME, VIRTUAL_PATH_PREFIX,
+    },
+    progress::{console_progress_handler, Progress},
+    timings::{Phase, Timings, TIMINGS_HTML_FILENAME, TIMINGS_JSON_FILENAME},
 };
 
 #[derive(Debug, Clone)]
@@ -90,19 +97,69 @@ pub fn ensure_package_output_dir(
 pub fn compile_manifest(
     manifest_path: &Path,
     config: Config,
-    emit_colors: DisplayColor,
+    message_format: impl Into<MessageFormat>,
 ) -> Result<bool, anyhow::Error> {
+    let memory_report = config.memory_report;
     let (_package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    if let Some(handler) = console_progress_handler() {
+        driver.set_progress_handler(handler);
+    }
 
     // Emit diagnostics. If one of the snippets is an error, abort gracefully.
-    if driver.emit_diagnostics(&mut stderr(), emit_colors)? {
-        return Ok(false);
-    };
+    let success = !driver.emit_diagnostics_to_console(message_format)?;
 
     // Write out all assemblies
+    if success {
+        driver.write_all_assemblies(false)?;
+    }
+
+    if let Some(report_path) = driver.write_timings_report()? {
+        eprintln!("Timing report saved to {}", report_path.display());
+    }
+
+    if memory_report {
+        eprintln!("Memory report:");
+        eprintln!("{:<24}{:>10}{:>10}", "query", "entries", "retained");
+        for usage in driver.database().memory_usage() {
+            eprintln!("{usage}");
+        }
+    }
+
+    Ok(success)
+}
+
+/// Compiles the package of the specified manifest like [`compile_manifest`]
+/// and returns the paths of all assemblies of the package, or `None` if errors
+/// were emitted.
+pub fn compile_manifest_assemblies(
+    manifest_path: &Path,
+    config: Config,
+    message_format: impl Into<MessageFormat>,
+) -> Result<Option<Vec<PathBuf>>, anyhow::Error> {
+    let (_package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    if let Some(handler) = console_progress_handler() {
+        driver.set_progress_handler(handler);
+    }
+    if driver.emit_diagnostics_to_console(message_format)? {
+        return Ok(None);
+    };
+
     driver.write_all_assemblies(false)?;
+    Ok(Some(driver.assembly_output_paths()))
+}
 
-    Ok(true)
+/// Analyzes the package of the specified manifest and emits its diagnostics,
+/// without generating any code. Returns false if errors were emitted.
+pub fn check_manifest(
+    manifest_path: &Path,
+    config: Config,
+    message_format: impl Into<MessageFormat>,
+) -> Result<bool, anyhow::Error> {
+    let (_package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    if let Some(handler) = console_progress_handler() {
+        driver.set_progress_handler(handler);
+    }
+    Ok(!driver.emit_diagnostics_to_console(message_format)?)
 }
 
 /// Determines the relative path of a file to the source directory.
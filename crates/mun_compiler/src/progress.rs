@@ -0,0 +1,126 @@
+use std::{
+    io::{IsTerminal, Write},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::timings::Phase;
+
+/// The minimum time between two redraws of the progress of a compilation on
+/// the console
+const CONSOLE_REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The width of the progress bar rendered on the console, in characters
+const CONSOLE_BAR_WIDTH: usize = 25;
+
+/// A notification about the progress of a compilation, passed to the handler
+/// registered with [`crate::Driver::set_progress_handler`].
+///
+/// Every phase that reports progress starts with [`Progress::Started`],
+/// followed by a [`Progress::Step`] for every completed step and ends with
+/// [`Progress::Finished`]. The driver does not write any output while a phase
+/// is in progress.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Progress<'a> {
+    /// A phase of the compilation started, which consists of `total` steps
+    Started { phase: Phase, total: usize },
+    /// A step of a phase was completed, e.g. a file was parsed or the assembly
+    /// of a module group was built
+    Step {
+        phase: Phase,
+        /// The name of the file or module group that was processed
+        name: &'a str,
+        /// The number of steps that were completed so far, including this one
+        completed: usize,
+        total: usize,
+    },
+    /// A phase of the compilation finished
+    Finished { phase: Phase },
+}
+
+/// Returns a progress handler that renders the progress of a compilation as a
+/// progress bar on a single line of stderr, or `None` if stderr is not a
+/// terminal. To keep the overhead low, the bar is redrawn at most every 50
+/// milliseconds.
+pub fn console_progress_handler() -> Option<impl Fn(Progress<'_>) + Send + Sync + 'static> {
+    if !std::io::stderr().is_terminal() {
+        return None;
+    }
+
+    let last_redraw: Mutex<Option<Instant>> = Mutex::new(None);
+    Some(move |progress: Progress<'_>| {
+        let mut last_redraw = last_redraw.lock().expect("progress lock is poisoned");
+        let mut stderr = std::io::stderr().lock();
+
+        // Failing to render the progress should never fail the compilation
+        let _ = match progress {
+            Progress::Started { phase, total } => {
+                *last_redraw = Some(Instant::now());
+                write!(stderr, "\r\x1b[2K{}", render_bar(phase, 0, total, ""))
+            }
+            Progress::Step {
+                phase,
+                name,
+                completed,
+                total,
+            } => {
+                if completed < total
+                    && last_redraw.is_some_and(|last| last.elapsed() < CONSOLE_REDRAW_INTERVAL)
+                {
+                    return;
+                }
+                *last_redraw = Some(Instant::now());
+                write!(
+                    stderr,
+                    "\r\x1b[2K{}",
+                    render_bar(phase, completed, total, name)
+                )
+            }
+            Progress::Finished { .. } => {
+                *last_redraw = None;
+                write!(stderr, "\r\x1b[2K")
+            }
+        };
+        let _ = stderr.flush();
+    })
+}
+
+/// Renders a single line that describes the progress of a phase.
+fn render_bar(phase: Phase, completed: usize, total: usize, name: &str) -> String {
+    let filled = (completed * CONSOLE_BAR_WIDTH)
+        .checked_div(total)
+        .unwrap_or(CONSOLE_BAR_WIDTH)
+        .min(CONSOLE_BAR_WIDTH);
+    format!(
+        "{:>15} [{}{}] {completed}/{total} {name}",
+        capitalize(phase.name()),
+        "=".repeat(filled),
+        " ".repeat(CONSOLE_BAR_WIDTH - filled),
+    )
+}
+
+/// Returns `text` with its first character in uppercase.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_bar;
+    use crate::Phase;
+
+    #[test]
+    fn render_progress_bar() {
+        assert_eq!(
+            render_bar(Phase::CodeGeneration, 2, 5, "foo::bar"),
+            "Code generation [==========               ] 2/5 foo::bar"
+        );
+        assert_eq!(
+            render_bar(Phase::Parsing, 0, 0, ""),
+            "        Parsing [=========================] 0/0 "
+        );
+    }
+}
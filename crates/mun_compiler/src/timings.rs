@@ -0,0 +1,243 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use mun_codegen::AssemblyTimings;
+use mun_hir::salsa::{self, plumbing::DatabaseOps};
+
+/// The name of the file in the output directory that contains the HTML report
+/// of the timings of a compilation
+pub const TIMINGS_HTML_FILENAME: &str = "timings.html";
+
+/// The name of the file in the output directory that contains the JSON report
+/// of the timings of a compilation
+pub const TIMINGS_JSON_FILENAME: &str = "timings.json";
+
+/// A phase of the compilation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    /// Reading the source files of a package from disk
+    Loading,
+    /// Parsing the source files
+    Parsing,
+    /// Name resolution, type inference, and the other analyses that produce
+    /// diagnostics
+    Analysis,
+    /// Generating machine code or IR
+    CodeGeneration,
+    /// Linking the generated machine code into shared objects
+    Linking,
+    /// Writing the assemblies to the output directory
+    Writing,
+}
+
+impl Phase {
+    /// Returns a human-readable name of the phase
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Loading => "loading",
+            Phase::Parsing => "parsing",
+            Phase::Analysis => "analysis",
+            Phase::CodeGeneration => "code generation",
+            Phase::Linking => "linking",
+            Phase::Writing => "writing",
+        }
+    }
+}
+
+/// How long the phases of a compilation took and how often the queries of the
+/// compiler were executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    /// The total time spent in each phase
+    pub phases: BTreeMap<Phase, Duration>,
+
+    /// The time it took to build the assembly of each module group, keyed by
+    /// the name of the module group
+    pub assemblies: BTreeMap<String, AssemblyTimings>,
+
+    /// The number of times each query was executed, keyed by the name of the
+    /// query. A query that is executed often is a hint that its results are
+    /// invalidated often.
+    pub queries: BTreeMap<String, u64>,
+}
+
+impl Timings {
+    /// Returns the total time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.phases.values().sum()
+    }
+
+    /// Returns the timings as a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "total": self.total().as_secs_f64(),
+            "phases": self
+                .phases
+                .iter()
+                .map(|(phase, duration)| serde_json::json!({
+                    "phase": phase.name(),
+                    "duration": duration.as_secs_f64(),
+                }))
+                .collect::<Vec<_>>(),
+            "assemblies": self
+                .assemblies
+                .iter()
+                .map(|(name, timings)| serde_json::json!({
+                    "name": name,
+                    "code_generation": timings.code_generation.as_secs_f64(),
+                    "linking": timings.linking.as_secs_f64(),
+                }))
+                .collect::<Vec<_>>(),
+            "queries": self
+                .queries
+                .iter()
+                .map(|(name, executions)| serde_json::json!({
+                    "name": name,
+                    "executions": executions,
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Returns the timings as a self-contained HTML page.
+    pub fn to_html(&self) -> String {
+        let total = self.total().as_secs_f64();
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Mun build timings</title>\n\
+             <style>\nbody { font-family: sans-serif; }\ntable { border-collapse: collapse; margin-bottom: 2em; }\n\
+             th, td { padding: 0.25em 1em; text-align: left; }\n\
+             .bar { background: #4a90d9; height: 1em; }\n</style>\n</head>\n<body>\n",
+        );
+        let _ = writeln!(
+            html,
+            "<h1>Mun build timings</h1>\n<p>Total: {total:.3}s</p>"
+        );
+
+        html.push_str(
+            "<h2>Phases</h2>\n<table>\n<tr><th>Phase</th><th>Duration</th><th></th></tr>\n",
+        );
+        for (phase, duration) in &self.phases {
+            let duration = duration.as_secs_f64();
+            // The bar of a phase that takes all the time is 300 pixels wide
+            let width = if total > 0.0 {
+                duration / total * 300.0
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{duration:.3}s</td><td><div class=\"bar\" style=\"width: {width:.0}px\"></div></td></tr>",
+                phase.name()
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Assemblies</h2>\n<table>\n<tr><th>Assembly</th><th>Code generation</th><th>Linking</th></tr>\n");
+        for (name, timings) in &self.assemblies {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{:.3}s</td><td>{:.3}s</td></tr>",
+                escape_html(name),
+                timings.code_generation.as_secs_f64(),
+                timings.linking.as_secs_f64()
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Queries</h2>\n<table>\n<tr><th>Query</th><th>Executions</th></tr>\n");
+        for (name, executions) in &self.queries {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{executions}</td></tr>",
+                escape_html(name)
+            );
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Writes the timings as an HTML and a JSON report to the output
+    /// directory. Returns the path of the HTML report.
+    pub fn write_report(&self, out_dir: &Path) -> Result<PathBuf, std::io::Error> {
+        std::fs::write(
+            out_dir.join(TIMINGS_JSON_FILENAME),
+            serde_json::to_string_pretty(&self.to_json())?,
+        )?;
+        let html_path = out_dir.join(TIMINGS_HTML_FILENAME);
+        std::fs::write(&html_path, self.to_html())?;
+        Ok(html_path)
+    }
+}
+
+/// Escapes the characters of a string that have a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Records [`Timings`] during a compilation.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    phases: Mutex<BTreeMap<Phase, Duration>>,
+    assemblies: Mutex<BTreeMap<String, AssemblyTimings>>,
+
+    /// The number of executions of each query, keyed by the group and the
+    /// index of the query. The key of one execution is stored to be able to
+    /// retrieve the name of the query.
+    queries: Mutex<HashMap<(u16, u16), (salsa::DatabaseKeyIndex, u64)>>,
+}
+
+impl Profiler {
+    /// Adds the specified duration to the time spent in a phase.
+    pub fn record_phase(&self, phase: Phase, duration: Duration) {
+        *self.phases.lock().unwrap().entry(phase).or_default() += duration;
+    }
+
+    /// Records the time it took to build the assembly of a module group.
+    pub fn record_assembly(&self, name: &str, timings: AssemblyTimings) {
+        self.record_phase(Phase::CodeGeneration, timings.code_generation);
+        self.record_phase(Phase::Linking, timings.linking);
+        self.assemblies
+            .lock()
+            .unwrap()
+            .insert(name.to_owned(), timings);
+    }
+
+    /// Records that a query is about to be executed.
+    pub fn record_query(&self, database_key: salsa::DatabaseKeyIndex) {
+        self.queries
+            .lock()
+            .unwrap()
+            .entry((database_key.group_index(), database_key.query_index()))
+            .or_insert((database_key, 0))
+            .1 += 1;
+    }
+
+    /// Returns the timings that were recorded so far. The database is used to
+    /// look up the names of the executed queries.
+    pub fn timings(&self, db: &dyn DatabaseOps) -> Timings {
+        let queries = self
+            .queries
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(database_key, executions)| {
+                let key = format!("{:?}", database_key.debug(db));
+                let name = key.split('(').next().unwrap_or(&key).to_owned();
+                (name, *executions)
+            })
+            .collect();
+
+        Timings {
+            phases: self.phases.lock().unwrap().clone(),
+            assemblies: self.assemblies.lock().unwrap().clone(),
+            queries,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use mun_compiler::{Config, Driver};
+
+const MANIFEST: &str = r#"[package]
+name="cached"
+authors=[]
+version="0.1.0"
+"#;
+
+/// Writes a package to `dir` and builds it with the artifact cache in
+/// `cache_dir`. Returns the paths of the assemblies of the package.
+fn build_package(dir: &Path, cache_dir: &Path) -> Vec<PathBuf> {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(
+        src_dir.join("mod.mun"),
+        "pub fn main() -> i32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(src_dir.join("foo.mun"), "pub fn bar() -> i32 { 5 }").unwrap();
+
+    let config = Config {
+        artifact_cache: Some(cache_dir.to_path_buf()),
+        ..Config::default()
+    };
+    let (_package, mut driver) = Driver::with_package_path(dir.join("mun.toml"), config).unwrap();
+    driver.write_all_assemblies(false).unwrap();
+    driver.assembly_output_paths()
+}
+
+/// Returns the paths of all entries in the cache.
+fn cache_entries(cache_dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<_> = std::fs::read_dir(cache_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+#[test]
+fn assemblies_are_restored_from_artifact_cache() {
+    let cache_dir = tempfile::tempdir().unwrap();
+
+    // The first build stores its assemblies in the cache
+    let first_dir = tempfile::tempdir().unwrap();
+    build_package(first_dir.path(), cache_dir.path());
+    let entries = cache_entries(cache_dir.path());
+    assert_eq!(entries.len(), 2);
+
+    // Mark the cached assemblies, so we can tell that they are restored
+    for entry in &entries {
+        std::fs::write(entry, "cached").unwrap();
+    }
+
+    // A build of the same package in another checkout restores the assemblies
+    let second_dir = tempfile::tempdir().unwrap();
+    let assembly_paths = build_package(second_dir.path(), cache_dir.path());
+    assert_eq!(assembly_paths.len(), 2);
+    for assembly_path in assembly_paths {
+        assert_eq!(std::fs::read_to_string(assembly_path).unwrap(), "cached");
+    }
+    assert_eq!(cache_entries(cache_dir.path()), entries);
+}
@@ -0,0 +1,100 @@
+use std::{fmt::Write, path::Path};
+
+use mun_compiler::{Config, Driver, INPUTS_FILENAME};
+
+const MANIFEST: &str = r#"[package]
+name="hermetic"
+authors=[]
+version="0.1.0"
+"#;
+
+/// Writes a package with a `mod` and a `foo` module to `dir`.
+fn write_package(dir: &Path) {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(
+        src_dir.join("mod.mun"),
+        "pub fn main() -> i32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(src_dir.join("foo.mun"), "pub fn bar() -> i32 { 5 }").unwrap();
+}
+
+/// Returns the SHA-256 hash of `text` as a hexadecimal string.
+fn sha256(text: &str) -> String {
+    extendhash::sha256::compute_hash(text.as_bytes())
+        .iter()
+        .fold(String::new(), |mut hash, byte| {
+            let _ = write!(hash, "{byte:02x}");
+            hash
+        })
+}
+
+fn hermetic_config() -> Config {
+    Config {
+        hermetic: true,
+        ..Config::default()
+    }
+}
+
+#[test]
+fn hermetic_build_writes_input_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    write_package(dir.path());
+
+    let (package, mut driver) =
+        Driver::with_package_path(dir.path().join("mun.toml"), hermetic_config()).unwrap();
+    driver.write_all_assemblies(false).unwrap();
+
+    let out_dir = package.root().join("target");
+    let inputs: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(out_dir.join(INPUTS_FILENAME)).unwrap())
+            .unwrap();
+    assert_eq!(
+        inputs,
+        serde_json::json!({
+            "files": [
+                { "package": "hermetic", "path": "mun.toml", "sha256": sha256(MANIFEST) },
+                {
+                    "package": "hermetic",
+                    "path": "src/foo.mun",
+                    "sha256": sha256("pub fn bar() -> i32 { 5 }"),
+                },
+                {
+                    "package": "hermetic",
+                    "path": "src/mod.mun",
+                    "sha256": sha256("pub fn main() -> i32 { foo::bar() }"),
+                },
+            ]
+        })
+    );
+
+    // Previous outputs are never reused
+    assert!(!out_dir.join("incremental").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn hermetic_build_refuses_files_outside_source_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write_package(dir.path());
+
+    let outside_dir = tempfile::tempdir().unwrap();
+    let outside_path = outside_dir.path().join("outside.mun");
+    std::fs::write(&outside_path, "pub fn outside() -> i32 { 1 }").unwrap();
+    std::os::unix::fs::symlink(&outside_path, dir.path().join("src/outside.mun")).unwrap();
+
+    // A regular build follows the link
+    assert!(Driver::with_package_path(dir.path().join("mun.toml"), Config::default()).is_ok());
+
+    let error = Driver::with_package_path(dir.path().join("mun.toml"), hermetic_config())
+        .err()
+        .expect("a hermetic build must not read files outside the source directory");
+    assert!(
+        error
+            .to_string()
+            .contains("outside of the source directories"),
+        "{error}"
+    );
+}
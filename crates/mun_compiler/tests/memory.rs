@@ -0,0 +1,35 @@
+use mun_compiler::{Config, Driver};
+
+const MANIFEST: &str = r#"[package]
+name="memory"
+authors=[]
+version="0.1.0"
+"#;
+
+#[test]
+fn emit_assemblies_to_memory() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.path().join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(
+        src_dir.join("mod.mun"),
+        "pub fn main() -> i32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(src_dir.join("foo.mun"), "pub fn bar() -> i32 { 5 }").unwrap();
+
+    let (_package, mut driver) =
+        Driver::with_package_path(dir.path().join("mun.toml"), Config::default()).unwrap();
+
+    let assemblies = driver.emit_assemblies_to_memory().unwrap();
+    let output_paths = driver.assembly_output_paths();
+    assert_eq!(assemblies.len(), 2);
+    assert!(output_paths.iter().all(|path| !path.exists()));
+
+    // The assemblies in memory are identical to the ones written to disk
+    driver.write_all_assemblies(true).unwrap();
+    for ((_, contents), output_path) in assemblies.into_iter().zip(output_paths) {
+        assert_eq!(contents, std::fs::read(output_path).unwrap());
+    }
+}
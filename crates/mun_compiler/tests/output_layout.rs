@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use mun_compiler::{Config, Driver};
+
+const MANIFEST: &str = r#"[package]
+name="layout"
+authors=[]
+version="0.1.0"
+"#;
+
+/// Writes a package with the specified source files to `dir` and returns a
+/// driver for it.
+fn package(dir: &Path, files: &[(&str, &str)]) -> Driver {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    for (path, contents) in files {
+        let path = src_dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    let (_package, driver) =
+        Driver::with_package_path(dir.join("mun.toml"), Config::default()).unwrap();
+    driver
+}
+
+#[test]
+fn assemblies_mirror_module_tree() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut driver = package(
+        dir.path(),
+        &[
+            ("mod.mun", "pub fn main() -> i32 { foo::bar::baz() }"),
+            ("foo.mun", "pub fn foo() {}"),
+            ("foo/bar.mun", "pub fn baz() -> i32 { 5 }"),
+        ],
+    );
+    driver.write_all_assemblies(false).unwrap();
+
+    let out_dir = dir.path().join("target");
+    let mut paths: Vec<_> = driver
+        .assembly_output_paths()
+        .into_iter()
+        .map(|path| path.strip_prefix(&out_dir).unwrap().to_path_buf())
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("foo").join("bar.munlib"),
+            Path::new("foo.munlib").to_path_buf(),
+            Path::new("mod.munlib").to_path_buf(),
+        ]
+    );
+    for path in paths {
+        assert!(out_dir.join(path).is_file());
+    }
+}
+
+#[test]
+fn modules_that_differ_in_case_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut driver = package(
+        dir.path(),
+        &[
+            ("mod.mun", "pub fn main() {}"),
+            ("foo.mun", "pub fn foo() {}"),
+        ],
+    );
+
+    // Added in memory, so the test also works on case-insensitive filesystems
+    driver.add_file("Foo.mun", String::from("pub fn foo() {}"));
+
+    let error = driver.write_all_assemblies(false).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("would be written to the same file on case-insensitive filesystems"),
+        "unexpected error: {error}"
+    );
+    assert!(!dir.path().join("target").join("foo.munlib").exists());
+}
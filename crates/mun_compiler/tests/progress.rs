@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeSet,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use mun_compiler::{Config, DisplayColor, Driver, Progress};
+
+const MANIFEST: &str = r#"[package]
+name="progress"
+authors=[]
+version="0.1.0"
+"#;
+
+/// The progress reported by a driver. The order in which modules are processed
+/// is unspecified, so the names of the steps are stored separately.
+#[derive(Default)]
+struct RecordedProgress {
+    events: Vec<String>,
+    names: BTreeSet<String>,
+}
+
+/// Writes a package with the specified `main` module and `child` module `foo`
+/// to `dir` and returns a driver for it that records all progress it reports.
+fn driver_for_package(
+    dir: &Path,
+    main: &str,
+    child: &str,
+) -> (Driver, Arc<Mutex<RecordedProgress>>) {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(src_dir.join("mod.mun"), main).unwrap();
+    std::fs::write(src_dir.join("foo.mun"), child).unwrap();
+
+    let (_package, mut driver) =
+        Driver::with_package_path(dir.join("mun.toml"), Config::default()).unwrap();
+
+    let recorded = Arc::new(Mutex::new(RecordedProgress::default()));
+    let recorder = recorded.clone();
+    driver.set_progress_handler(move |progress| {
+        let mut recorder = recorder.lock().unwrap();
+        let event = match progress {
+            Progress::Started { phase, total } => format!("started {} ({total})", phase.name()),
+            Progress::Step {
+                phase,
+                name,
+                completed,
+                total,
+            } => {
+                recorder.names.insert(format!("{} {name}", phase.name()));
+                format!("{} {completed}/{total}", phase.name())
+            }
+            Progress::Finished { phase } => format!("finished {}", phase.name()),
+        };
+        recorder.events.push(event);
+    });
+
+    (driver, recorded)
+}
+
+#[test]
+fn reports_progress_of_all_phases() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut driver, recorded) = driver_for_package(
+        dir.path(),
+        "pub fn main() -> i32 { foo::bar() }",
+        "pub fn bar() -> i32 { 5 }",
+    );
+
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+    driver.write_all_assemblies(true).unwrap();
+
+    let recorded = recorded.lock().unwrap();
+    assert_eq!(
+        recorded.events,
+        vec![
+            "started parsing (2)",
+            "parsing 1/2",
+            "parsing 2/2",
+            "finished parsing",
+            "started analysis (2)",
+            "analysis 1/2",
+            "analysis 2/2",
+            "finished analysis",
+            "started code generation (2)",
+            "code generation 1/2",
+            "code generation 2/2",
+            "finished code generation",
+            "started writing (2)",
+            "writing 1/2",
+            "writing 2/2",
+            "finished writing",
+        ]
+    );
+    assert_eq!(
+        recorded.names,
+        BTreeSet::from(
+            [
+                "parsing foo.mun",
+                "parsing mod.mun",
+                "analysis foo.mun",
+                "analysis mod.mun",
+                "code generation foo",
+                "code generation mod",
+                "writing foo",
+                "writing mod",
+            ]
+            .map(String::from)
+        )
+    );
+}
+
+#[test]
+fn diagnostics_are_emitted_after_progress() {
+    let dir = tempfile::tempdir().unwrap();
+    let (driver, recorded) = driver_for_package(
+        dir.path(),
+        "pub fn main() -> i32 { foo::baz() }",
+        "pub fn bar() -> i32 { 5 }",
+    );
+
+    let diagnostics = driver
+        .emit_diagnostics_to_string(DisplayColor::Disable)
+        .unwrap()
+        .expect("expected an error");
+    assert!(diagnostics.contains("baz"), "{diagnostics}");
+    assert_eq!(
+        recorded.lock().unwrap().events.last().map(String::as_str),
+        Some("finished analysis")
+    );
+}
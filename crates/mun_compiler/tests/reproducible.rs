@@ -0,0 +1,88 @@
+use std::{
+    collections::BTreeMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+};
+
+use mun_compiler::{Config, Driver};
+
+const MANIFEST: &str = r#"[package]
+name="reproducible"
+authors=[]
+version="0.1.0"
+"#;
+
+const MAIN: &str = r#"
+pub struct Vec2 { x: f32, y: f32 }
+pub struct(value) Color(u8, u8, u8);
+
+pub fn main() -> f32 {
+    let v = foo::scale(Vec2 { x: 1.0, y: 2.0 }, 2.0);
+    bar::length(v) + add(1.0, 2.0)
+}
+
+fn add(a: f32, b: f32) -> f32 { a + b }
+
+pub fn color() -> Color { Color(255, 0, 0) }
+"#;
+
+const FOO: &str = r#"
+use super::Vec2;
+
+pub fn scale(v: Vec2, s: f32) -> Vec2 {
+    Vec2 { x: v.x * s, y: v.y * s }
+}
+"#;
+
+const BAR: &str = r#"
+use super::Vec2;
+
+pub fn length(v: Vec2) -> f32 {
+    super::foo::scale(v, 1.0).x + v.y
+}
+"#;
+
+/// Writes a package to `dir`, builds it, and returns the hashes of the
+/// contents of all files in its output directory, keyed by their path
+/// relative to the output directory.
+fn build_package(dir: &Path) -> BTreeMap<String, u64> {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(src_dir.join("mod.mun"), MAIN).unwrap();
+    std::fs::write(src_dir.join("foo.mun"), FOO).unwrap();
+    std::fs::write(src_dir.join("bar.mun"), BAR).unwrap();
+
+    let config = Config {
+        debug_info: true,
+        ..Config::default()
+    };
+    let (package, mut driver) = Driver::with_package_path(dir.join("mun.toml"), config).unwrap();
+    driver.write_all_assemblies(true).unwrap();
+
+    let out_dir = package.root().join("target");
+    driver
+        .assembly_output_paths()
+        .into_iter()
+        .map(|path| {
+            let mut hasher = DefaultHasher::new();
+            std::fs::read(&path).unwrap().hash(&mut hasher);
+            let relative_path = path.strip_prefix(&out_dir).unwrap();
+            (relative_path.display().to_string(), hasher.finish())
+        })
+        .collect()
+}
+
+#[test]
+fn identical_inputs_produce_identical_assemblies() {
+    // Build the same package in two different directories to ensure that no
+    // absolute paths end up in the assemblies
+    let first_dir = tempfile::tempdir().unwrap();
+    let second_dir = tempfile::tempdir().unwrap();
+
+    let first = build_package(first_dir.path());
+    let second = build_package(second_dir.path());
+
+    assert_eq!(first.len(), 3);
+    assert_eq!(first, second);
+}
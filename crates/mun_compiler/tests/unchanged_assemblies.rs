@@ -0,0 +1,115 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use mun_compiler::{Config, Driver};
+use mun_project::LOCKFILE_NAME;
+
+const MANIFEST: &str = r#"[package]
+name="unchanged"
+authors=[]
+version="0.1.0"
+"#;
+
+/// The modification time and contents of a file, or only the modification
+/// time of a directory.
+#[derive(Debug, PartialEq)]
+struct Entry {
+    modified: SystemTime,
+    contents: Option<Vec<u8>>,
+}
+
+/// Returns the state of `dir` and everything in it, keyed by path.
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, Entry> {
+    let mut entries = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = std::fs::metadata(&path).unwrap();
+        let contents = if metadata.is_dir() {
+            pending.extend(
+                std::fs::read_dir(&path)
+                    .unwrap()
+                    .map(|entry| entry.unwrap().path()),
+            );
+            None
+        } else {
+            Some(std::fs::read(&path).unwrap())
+        };
+        entries.insert(
+            path,
+            Entry {
+                modified: metadata.modified().unwrap(),
+                contents,
+            },
+        );
+    }
+    entries
+}
+
+/// Writes a package with two modules to `dir` and returns a driver for it,
+/// together with its output directory.
+fn package(dir: &Path) -> (Driver, PathBuf) {
+    std::fs::write(dir.join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(
+        src_dir.join("mod.mun"),
+        "pub fn main() -> i32 { foo::bar() }",
+    )
+    .unwrap();
+    std::fs::write(src_dir.join("foo.mun"), "pub fn bar() -> i32 { 5 }").unwrap();
+
+    let (package, driver) =
+        Driver::with_package_path(dir.join("mun.toml"), Config::default()).unwrap();
+    (driver, package.root().join("target"))
+}
+
+/// Waits long enough for a rewritten file to get a different modification
+/// time, even on filesystems with a coarse timestamp resolution.
+fn wait_for_next_mtime() {
+    std::thread::sleep(Duration::from_millis(1100));
+}
+
+#[test]
+fn rebuild_without_changes_does_not_touch_output_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut driver, out_dir) = package(dir.path());
+    driver.write_all_assemblies(false).unwrap();
+    let before = snapshot(&out_dir);
+    assert_eq!(
+        before
+            .keys()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "munlib"))
+            .count(),
+        2
+    );
+    assert!(!out_dir.join(LOCKFILE_NAME).exists());
+
+    wait_for_next_mtime();
+    driver.write_all_assemblies(false).unwrap();
+
+    // The lockfile is created in the output directory while writing, which would
+    // change the modification time of the directory itself.
+    assert_eq!(snapshot(&out_dir), before);
+}
+
+#[test]
+fn rebuild_only_writes_changed_assemblies() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut driver, out_dir) = package(dir.path());
+    driver.write_all_assemblies(false).unwrap();
+    let main_path = out_dir.join("mod.munlib");
+    let foo_path = out_dir.join("foo.munlib");
+    let before = snapshot(&out_dir);
+
+    wait_for_next_mtime();
+    driver.update_file("foo.mun", "pub fn bar() -> i32 { 6 }".to_owned());
+    driver.write_all_assemblies(false).unwrap();
+    let after = snapshot(&out_dir);
+
+    assert_eq!(after[&main_path], before[&main_path]);
+    assert_ne!(after[&foo_path].contents, before[&foo_path].contents);
+    assert!(after[&foo_path].modified > before[&foo_path].modified);
+}
@@ -0,0 +1,61 @@
+use mun_compiler::{Config, DisplayColor, Driver, VIRTUAL_OUTPUT_DIRNAME};
+
+const MANIFEST: &str = r#"[package]
+name="virtual_files"
+authors=[]
+version="0.1.0"
+"#;
+
+#[test]
+fn virtual_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("mun.toml"), MANIFEST).unwrap();
+    let src_dir = dir.path().join("src");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(src_dir.join("mod.mun"), "pub fn main() -> i32 { 5 }").unwrap();
+
+    let (_package, mut driver) =
+        Driver::with_package_path(dir.path().join("mun.toml"), Config::default()).unwrap();
+
+    // Diagnostics of virtual files refer to a synthetic path
+    let file_id = driver
+        .set_virtual_file("mod.mun", String::from("pub fn bindings() -> i32 { true }"))
+        .unwrap();
+    let diagnostics = driver
+        .emit_diagnostics_to_string(DisplayColor::Disable)
+        .unwrap()
+        .expect("expected a type mismatch");
+    assert!(diagnostics.contains("<generated>/mod.mun"));
+
+    // Virtual files keep their id, even when they are removed and added again
+    assert_eq!(
+        driver
+            .set_virtual_file("mod.mun", String::from("pub fn bindings() -> i32 { 1 }"))
+            .unwrap(),
+        file_id
+    );
+    assert_eq!(driver.remove_virtual_file("mod.mun"), Some(file_id));
+    assert_eq!(driver.get_file_id_for_virtual_path("mod.mun"), None);
+    assert_eq!(
+        driver
+            .set_virtual_file("mod.mun", String::from("pub fn bindings() -> i32 { 1 }"))
+            .unwrap(),
+        file_id
+    );
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+
+    // The assemblies of virtual files are written to a subdirectory
+    driver.write_all_assemblies(true).unwrap();
+    assert!(dir.path().join("target/mod.munlib").exists());
+    assert!(dir
+        .path()
+        .join("target")
+        .join(VIRTUAL_OUTPUT_DIRNAME)
+        .join("mod.munlib")
+        .exists());
+}
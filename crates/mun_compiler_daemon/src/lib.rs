@@ -1,33 +1,154 @@
 use std::{
-    io::stderr,
-    path::Path,
-    sync::{mpsc::channel, Arc},
-    time::Duration,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
-use mun_compiler::{compute_source_relative_path, is_source_file, Config, DisplayColor, Driver};
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use mun_compiler::{
+    compute_source_relative_path, console_progress_handler, is_source_file, Config, Driver,
+    MessageFormat,
+};
+use notify::{DebouncedEvent, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// The delay with which the operating system notifies us about changes to a
+/// single file.
+const NOTIFY_DELAY: Duration = Duration::from_millis(10);
+
+/// The maximum number of debounce intervals a batch of events is extended by.
+/// This ensures that a continuous stream of events cannot postpone a build
+/// forever.
+const MAX_BATCH_DEBOUNCES: u32 = 10;
+
+/// Determines when the daemon rebuilds the package after source files changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildTrigger {
+    /// Rebuild after every batch of changes to source files.
+    OnChange,
+
+    /// Keep track of changes to source files, but only rebuild when the
+    /// marker file at the specified path is created or written to. Editors
+    /// can touch the marker on save to avoid rebuilds for intermediate writes.
+    OnSaveMarker(PathBuf),
+}
+
+impl BuildTrigger {
+    /// Returns true if a build should be started, given whether source files
+    /// changed since the last build and whether the save marker was touched in
+    /// the last batch of events.
+    fn should_build(&self, pending_changes: bool, marker_touched: bool) -> bool {
+        match self {
+            BuildTrigger::OnChange => pending_changes,
+            BuildTrigger::OnSaveMarker(_) => pending_changes && marker_touched,
+        }
+    }
+}
+
+/// Configures how the daemon watches the source directory for changes.
+#[derive(Clone, Debug)]
+pub struct WatchConfig {
+    /// File events that arrive within this interval of each other are
+    /// batched into a single build. A batch is closed after at most ten
+    /// intervals, even if events keep arriving.
+    pub debounce: Duration,
+
+    /// Determines when a build is triggered.
+    pub trigger: BuildTrigger,
+
+    /// If set, the filesystem is polled with the specified interval instead
+    /// of relying on notifications from the operating system. This is
+    /// required for filesystems that do not support notifications, like
+    /// some network drives.
+    pub poll_interval: Option<Duration>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(50),
+            trigger: BuildTrigger::OnChange,
+            poll_interval: None,
+        }
+    }
+}
+
+/// A filesystem watcher that either receives notifications from the operating
+/// system or polls the filesystem.
+enum FileWatcher {
+    Notify(RecommendedWatcher),
+    Poll(PollWatcher),
+}
+
+impl FileWatcher {
+    fn new(tx: Sender<DebouncedEvent>, poll_interval: Option<Duration>) -> notify::Result<Self> {
+        match poll_interval {
+            Some(interval) => Ok(FileWatcher::Poll(Watcher::new(tx, interval)?)),
+            None => Ok(FileWatcher::Notify(Watcher::new(tx, NOTIFY_DELAY)?)),
+        }
+    }
+
+    fn watch(&mut self, path: &Path, recursive_mode: RecursiveMode) -> notify::Result<()> {
+        match self {
+            FileWatcher::Notify(watcher) => watcher.watch(path, recursive_mode),
+            FileWatcher::Poll(watcher) => watcher.watch(path, recursive_mode),
+        }
+    }
+}
+
+/// The effect of a single filesystem event.
+#[derive(Default)]
+struct Changes {
+    /// True if the contents or layout of the source files changed
+    sources: bool,
+
+    /// True if the save marker was touched
+    marker: bool,
+}
 
 /// Compiles and watches the package at the specified path. Recompiles changes
 /// that occur.
 pub fn compile_and_watch_manifest(
     manifest_path: &Path,
     config: Config,
-    display_color: DisplayColor,
+    watch_config: WatchConfig,
+    message_format: impl Into<MessageFormat>,
 ) -> Result<bool, anyhow::Error> {
+    let message_format = message_format.into();
+
     // Create the compiler driver
     let (package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    if let Some(handler) = console_progress_handler() {
+        driver.set_progress_handler(handler);
+    }
 
     // Start watching the source directory
     let (watcher_tx, watcher_rx) = channel();
-    let mut watcher: RecommendedWatcher = Watcher::new(watcher_tx, Duration::from_millis(10))?;
+    let mut watcher = FileWatcher::new(watcher_tx, watch_config.poll_interval)?;
     let source_directory = package.source_directory();
 
     watcher.watch(&source_directory, RecursiveMode::Recursive)?;
     println!("Watching: {}", source_directory.display());
 
+    // The marker may not exist yet, so watch the directory that contains it
+    let marker = match &watch_config.trigger {
+        BuildTrigger::OnChange => None,
+        BuildTrigger::OnSaveMarker(marker) => {
+            let marker = package.root().join(marker);
+            if let Some(marker_dir) = marker.parent() {
+                if !marker_dir.starts_with(&source_directory) {
+                    std::fs::create_dir_all(marker_dir)?;
+                    watcher.watch(marker_dir, RecursiveMode::NonRecursive)?;
+                }
+            }
+            println!("Building when {} is touched", marker.display());
+            Some(marker)
+        }
+    };
+
     // Emit all current errors, and write the assemblies if no errors occured
-    if !driver.emit_diagnostics(&mut stderr(), display_color)? {
+    if !driver.emit_diagnostics_to_console(message_format)? {
         driver.write_all_assemblies(false)?;
     }
 
@@ -40,60 +161,390 @@ pub fn compile_and_watch_manifest(
     .expect("error setting ctrl-c handler");
 
     // Start watching filesystem events.
+    let mut pending_changes = false;
     while !should_quit.load(std::sync::atomic::Ordering::SeqCst) {
-        if let Ok(event) = watcher_rx.recv_timeout(Duration::from_millis(1)) {
-            use notify::DebouncedEvent::{Create, Remove, Rename, Write};
-            match event {
-                Write(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
-                    let file_contents = std::fs::read_to_string(path)?;
-                    log::info!("Modifying {}", relative_path);
-                    driver.update_file(relative_path, file_contents);
-                    if !driver.emit_diagnostics(&mut stderr(), display_color)? {
-                        driver.write_all_assemblies(false)?;
-                    }
-                }
-                Create(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
-                    let file_contents = std::fs::read_to_string(path)?;
-                    log::info!("Creating {}", relative_path);
-                    driver.add_file(relative_path, file_contents);
-                    if !driver.emit_diagnostics(&mut stderr(), display_color)? {
-                        driver.write_all_assemblies(false)?;
-                    }
-                }
-                Remove(ref path) if is_source_file(path) => {
-                    // Simply remove the source file from the source root
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
-                    log::info!("Removing {}", relative_path);
-                    // TODO: Remove assembly files if there are no files referencing it.
-                    // let assembly_path =
-                    // driver.assembly_output_path(driver.get_file_id_for_path(&relative_path).
-                    // expect("cannot remove a file that was not part of the compilation in the
-                    // first place")); if assembly_path.is_file() {
-                    //     std::fs::remove_file(assembly_path)?;
-                    // }
-                    driver.remove_file(relative_path);
-                    driver.emit_diagnostics(&mut stderr(), display_color)?;
-                }
-                Rename(ref from, ref to) => {
-                    // Renaming is done by changing the relative path of the original source file
-                    // but not modifying any text. This ensures that most of the
+        let Some(batch) = receive_batch(&watcher_rx, watch_config.debounce) else {
+            continue;
+        };
+
+        let mut marker_touched = false;
+        for event in batch {
+            let changes = apply_event(&mut driver, &source_directory, marker.as_deref(), event)?;
+            pending_changes |= changes.sources;
+            marker_touched |= changes.marker;
+        }
+
+        if watch_config
+            .trigger
+            .should_build(pending_changes, marker_touched)
+        {
+            pending_changes = false;
+            if !driver.emit_diagnostics_to_console(message_format)? {
+                driver.write_all_assemblies(false)?;
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+/// Waits for a filesystem event and collects all events that follow it within
+/// the `debounce` interval. The batch is closed after at most
+/// `MAX_BATCH_DEBOUNCES` intervals. Returns `None` if no event arrived.
+fn receive_batch(
+    watcher_rx: &Receiver<DebouncedEvent>,
+    debounce: Duration,
+) -> Option<Vec<DebouncedEvent>> {
+    let event = watcher_rx.recv_timeout(Duration::from_millis(1)).ok()?;
+    let deadline = Instant::now() + debounce * MAX_BATCH_DEBOUNCES;
+    let mut batch = vec![event];
+    loop {
+        let timeout = debounce.min(deadline.saturating_duration_since(Instant::now()));
+        if timeout.is_zero() {
+            break;
+        }
+        match watcher_rx.recv_timeout(timeout) {
+            Ok(event) => batch.push(event),
+            Err(_) => break,
+        }
+    }
+    Some(batch)
+}
+
+/// Applies the changes described by a filesystem event to the driver.
+fn apply_event(
+    driver: &mut Driver,
+    source_directory: &Path,
+    marker: Option<&Path>,
+    event: DebouncedEvent,
+) -> Result<Changes, anyhow::Error> {
+    use notify::DebouncedEvent::{Create, Remove, Rename, Write};
+    let mut changes = Changes::default();
+    match event {
+        Write(ref path) | Create(ref path) if Some(path.as_path()) == marker => {
+            changes.marker = true;
+        }
+        Write(ref path) | Create(ref path) if is_package_source(source_directory, path) => {
+            write_file(driver, source_directory, path)?;
+            changes.sources = true;
+        }
+        Remove(ref path) if is_package_source(source_directory, path) => {
+            // Simply remove the source file from the source root
+            let relative_path = compute_source_relative_path(source_directory, path)?;
+            if driver.get_file_id_for_path(&relative_path).is_some() {
+                log::info!("Removing {}", relative_path);
+                // TODO: Remove assembly files if there are no files referencing it.
+                // let assembly_path =
+                // driver.assembly_output_path(driver.get_file_id_for_path(&relative_path).
+                // expect("cannot remove a file that was not part of the compilation in the
+                // first place")); if assembly_path.is_file() {
+                //     std::fs::remove_file(assembly_path)?;
+                // }
+                driver.remove_file(relative_path);
+                changes.sources = true;
+            }
+        }
+        Rename(ref from, ref to) => {
+            let known_source = if is_package_source(source_directory, from) {
+                let relative_path = compute_source_relative_path(source_directory, from)?;
+                driver
+                    .get_file_id_for_path(&relative_path)
+                    .map(|_| relative_path)
+            } else {
+                None
+            };
+            match known_source {
+                Some(from_relative_path) if is_package_source(source_directory, to) => {
+                    // Renaming is done by changing the relative path of the original source
+                    // file but not modifying any text. This ensures that most of the
                     // cache for the renamed file stays alive. This is
                     // effectively a rename of the file_id in the database.
-                    let from_relative_path = compute_source_relative_path(&source_directory, from)?;
-                    let to_relative_path = compute_source_relative_path(&source_directory, to)?;
-
-                    log::info!("Renaming {} to {}", from_relative_path, to_relative_path,);
+                    let to_relative_path = compute_source_relative_path(source_directory, to)?;
+                    log::info!("Renaming {} to {}", from_relative_path, to_relative_path);
                     driver.rename(from_relative_path, to_relative_path);
-                    if !driver.emit_diagnostics(&mut stderr(), display_color)? {
-                        driver.write_all_assemblies(false)?;
-                    }
+                    changes.sources = true;
+                }
+                Some(from_relative_path) => {
+                    log::info!("Removing {}", from_relative_path);
+                    driver.remove_file(from_relative_path);
+                    changes.sources = true;
                 }
-                _ => {}
+                None if is_package_source(source_directory, to) => {
+                    // Editors often write to a temporary file and rename it over the original
+                    write_file(driver, source_directory, to)?;
+                    changes.sources = true;
+                }
+                None => {}
             }
+            changes.marker = Some(to.as_path()) == marker;
         }
+        _ => {}
     }
+    Ok(changes)
+}
 
-    Ok(true)
+/// Returns true if `path` refers to a source file in the source directory.
+fn is_package_source(source_directory: &Path, path: &Path) -> bool {
+    is_source_file(path) && path.starts_with(source_directory)
+}
+
+/// Updates or adds the source file at the specified path.
+fn write_file(
+    driver: &mut Driver,
+    source_directory: &Path,
+    path: &Path,
+) -> Result<(), anyhow::Error> {
+    let relative_path = compute_source_relative_path(source_directory, path)?;
+    let file_contents = std::fs::read_to_string(path)?;
+    if driver.get_file_id_for_path(&relative_path).is_some() {
+        log::info!("Modifying {}", relative_path);
+        driver.update_file(relative_path, file_contents);
+    } else {
+        log::info!("Creating {}", relative_path);
+        driver.add_file(relative_path, file_contents);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::PathBuf,
+        sync::mpsc::channel,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use mun_compiler::{Config, Driver};
+    use notify::DebouncedEvent;
+
+    use super::{apply_event, receive_batch, BuildTrigger, MAX_BATCH_DEBOUNCES};
+
+    /// Returns true if the driver would write an assembly with the specified
+    /// name.
+    fn has_assembly(driver: &Driver, name: &str) -> bool {
+        driver
+            .assembly_output_paths()
+            .iter()
+            .any(|path| path.file_stem().is_some_and(|stem| stem == name))
+    }
+
+    /// Creates a package with a single source file and a driver for it.
+    fn package() -> (tempfile::TempDir, PathBuf, Driver) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("mun.toml"),
+            "[package]\nname=\"foo\"\nversion=\"0.0.0\"\n",
+        )
+        .unwrap();
+        let source_directory = dir.path().join("src");
+        std::fs::create_dir(&source_directory).unwrap();
+        std::fs::write(source_directory.join("mod.mun"), "pub fn main() {}").unwrap();
+
+        let (package, driver) =
+            Driver::with_package_path(dir.path().join("mun.toml"), Config::default()).unwrap();
+        (dir, package.source_directory(), driver)
+    }
+
+    #[test]
+    fn no_batch_without_events() {
+        let (_tx, rx) = channel();
+        assert!(receive_batch(&rx, Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn events_within_debounce_are_batched() {
+        let (tx, rx) = channel();
+        tx.send(DebouncedEvent::Write(PathBuf::from("a.mun")))
+            .unwrap();
+        tx.send(DebouncedEvent::Write(PathBuf::from("b.mun")))
+            .unwrap();
+
+        let batch = receive_batch(&rx, Duration::from_millis(10)).unwrap();
+        assert_eq!(
+            batch,
+            vec![
+                DebouncedEvent::Write(PathBuf::from("a.mun")),
+                DebouncedEvent::Write(PathBuf::from("b.mun"))
+            ]
+        );
+        assert!(receive_batch(&rx, Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn continuous_events_do_not_postpone_batch_forever() {
+        let debounce = Duration::from_millis(20);
+        let (tx, rx) = channel();
+        let sender = thread::spawn(move || {
+            // Keep sending events well within the debounce interval for much
+            // longer than the maximum batch duration.
+            let until = Instant::now() + debounce * MAX_BATCH_DEBOUNCES * 5;
+            while Instant::now() < until {
+                if tx
+                    .send(DebouncedEvent::Write(PathBuf::from("a.mun")))
+                    .is_err()
+                {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(2));
+            }
+        });
+
+        let start = Instant::now();
+        let batch = receive_batch(&rx, debounce).unwrap();
+        let elapsed = start.elapsed();
+        drop(rx);
+        sender.join().unwrap();
+
+        assert!(batch.len() > 1);
+        assert!(
+            elapsed < debounce * MAX_BATCH_DEBOUNCES * 3,
+            "batch took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn on_change_builds_on_pending_changes() {
+        let trigger = BuildTrigger::OnChange;
+        assert!(trigger.should_build(true, false));
+        assert!(trigger.should_build(true, true));
+        assert!(!trigger.should_build(false, true));
+        assert!(!trigger.should_build(false, false));
+    }
+
+    #[test]
+    fn on_save_marker_builds_when_marker_is_touched() {
+        let trigger = BuildTrigger::OnSaveMarker(PathBuf::from(".mun-save"));
+        assert!(trigger.should_build(true, true));
+        assert!(!trigger.should_build(true, false));
+        assert!(!trigger.should_build(false, true));
+        assert!(!trigger.should_build(false, false));
+    }
+
+    #[test]
+    fn source_events_change_sources() {
+        let (_dir, source_directory, mut driver) = package();
+
+        // Modifying an existing file
+        let main = source_directory.join("mod.mun");
+        std::fs::write(&main, "pub fn main() -> i32 { 5 }").unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Write(main),
+        )
+        .unwrap();
+        assert!(changes.sources);
+        assert!(!changes.marker);
+
+        // Creating a new file
+        let foo = source_directory.join("foo.mun");
+        std::fs::write(&foo, "pub fn foo() {}").unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Create(foo.clone()),
+        )
+        .unwrap();
+        assert!(changes.sources);
+        assert!(has_assembly(&driver, "foo"));
+
+        // Renaming it
+        let bar = source_directory.join("bar.mun");
+        std::fs::rename(&foo, &bar).unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Rename(foo, bar.clone()),
+        )
+        .unwrap();
+        assert!(changes.sources);
+        assert!(!has_assembly(&driver, "foo"));
+        assert!(has_assembly(&driver, "bar"));
+
+        // And removing it
+        std::fs::remove_file(&bar).unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Remove(bar),
+        )
+        .unwrap();
+        assert!(changes.sources);
+        assert!(!has_assembly(&driver, "bar"));
+    }
+
+    #[test]
+    fn unrelated_events_do_not_change_sources() {
+        let (dir, source_directory, mut driver) = package();
+
+        // A file that is not a source file
+        let readme = source_directory.join("README.md");
+        std::fs::write(&readme, "foo").unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Write(readme),
+        )
+        .unwrap();
+        assert!(!changes.sources);
+
+        // A source file outside of the source directory
+        let outside = dir.path().join("outside.mun");
+        std::fs::write(&outside, "pub fn foo() {}").unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Write(outside),
+        )
+        .unwrap();
+        assert!(!changes.sources);
+
+        // Removing a file that was never part of the package
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            None,
+            DebouncedEvent::Remove(source_directory.join("unknown.mun")),
+        )
+        .unwrap();
+        assert!(!changes.sources);
+    }
+
+    #[test]
+    fn touching_the_marker_does_not_change_sources() {
+        let (dir, source_directory, mut driver) = package();
+        let marker = dir.path().join(".mun-save");
+        std::fs::write(&marker, "").unwrap();
+
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            Some(marker.as_path()),
+            DebouncedEvent::Write(marker.clone()),
+        )
+        .unwrap();
+        assert!(changes.marker);
+        assert!(!changes.sources);
+
+        // Editors may save the marker by renaming a temporary file over it
+        let temp = dir.path().join(".mun-save.tmp");
+        std::fs::write(&temp, "").unwrap();
+        std::fs::rename(&temp, &marker).unwrap();
+        let changes = apply_event(
+            &mut driver,
+            &source_directory,
+            Some(marker.as_path()),
+            DebouncedEvent::Rename(temp, marker.clone()),
+        )
+        .unwrap();
+        assert!(changes.marker);
+        assert!(!changes.sources);
+    }
 }
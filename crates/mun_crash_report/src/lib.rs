@@ -0,0 +1,247 @@
+//! Writes a crash report to a local file when a tool of the Mun toolchain, e.g.
+//! the `mun` CLI or the language server, panics.
+//!
+//! A crash report contains the version of the tool, the panic message and
+//! backtrace, and the context that the tool recorded with [`set_context`],
+//! e.g. the request that was being handled. Source code is never included;
+//! files are identified by their [`content_hash`]. Nothing is sent over the
+//! network: the path of the report is printed, so users can attach it to a bug
+//! report themselves.
+
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    collections::BTreeMap,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    panic::PanicHookInfo,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once, PoisonError,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The environment variable that specifies the directory in which crash
+/// reports are written. Defaults to the temporary directory of the operating
+/// system.
+pub const CRASH_REPORT_DIR_ENV: &str = "MUN_CRASH_REPORT_DIR";
+
+/// The context that is included in crash reports, see [`set_context`].
+static CONTEXT: Mutex<BTreeMap<&'static str, String>> = Mutex::new(BTreeMap::new());
+
+/// Installs a panic hook that writes a crash report for every panic and prints
+/// the path of the report to stderr. The previously installed hook is still
+/// invoked. Calling this function multiple times has no effect.
+pub fn install(tool: &'static str, version: &'static str) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+            previous_hook(info);
+
+            let report = CrashReport::new(tool, version, info);
+            match report.write_to(&report_dir()) {
+                Ok(path) => eprintln!(
+                    "{tool} crashed, a crash report was written to {}\n\
+                    Please attach it when reporting the issue at https://github.com/mun-lang/mun/issues",
+                    path.display()
+                ),
+                Err(err) => eprintln!("{tool} crashed, but the crash report could not be written: {err}"),
+            }
+        }));
+    });
+}
+
+/// Records a value that is included in crash reports, e.g. the request that
+/// is being handled. Replaces the previous value of `key`.
+pub fn set_context(key: &'static str, value: impl Into<String>) {
+    CONTEXT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, value.into());
+}
+
+/// Returns a hash of the contents of a file, which identifies the file in a
+/// crash report without including its contents.
+pub fn content_hash(contents: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Returns the directory in which crash reports are written.
+fn report_dir() -> PathBuf {
+    std::env::var_os(CRASH_REPORT_DIR_ENV).map_or_else(std::env::temp_dir, PathBuf::from)
+}
+
+/// The information about a single panic that is written to a file.
+struct CrashReport {
+    tool: &'static str,
+    version: &'static str,
+    message: String,
+    location: Option<String>,
+    thread: String,
+    context: BTreeMap<&'static str, String>,
+    backtrace: String,
+}
+
+impl CrashReport {
+    fn new(tool: &'static str, version: &'static str, info: &PanicHookInfo<'_>) -> Self {
+        // The panic may have occurred while the context was locked, in which case it is
+        // left out rather than risking a deadlock.
+        let context = CONTEXT
+            .try_lock()
+            .map(|context| context.clone())
+            .unwrap_or_default();
+
+        CrashReport {
+            tool,
+            version,
+            message: panic_message(info.payload()),
+            location: info.location().map(ToString::to_string),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_owned(),
+            context,
+            backtrace: Backtrace::force_capture().to_string(),
+        }
+    }
+
+    /// Writes the report to a new file in `dir` and returns its path.
+    fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        // Multiple panics can occur within the same second, e.g. on different threads
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let path = dir.join(format!(
+            "{}-crash-{timestamp}-{}-{}.txt",
+            self.tool,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# {} crash report", self.tool)?;
+        writeln!(f)?;
+        writeln!(f, "version: {}", self.version)?;
+        writeln!(
+            f,
+            "platform: {}-{}",
+            std::env::consts::ARCH,
+            std::env::consts::OS
+        )?;
+        writeln!(f, "thread: {}", self.thread)?;
+        writeln!(f, "message: {}", self.message)?;
+        if let Some(location) = &self.location {
+            writeln!(f, "location: {location}")?;
+        }
+
+        if !self.context.is_empty() {
+            writeln!(f, "\n## Context\n")?;
+            for (key, value) in &self.context {
+                writeln!(f, "{key}: {value}")?;
+            }
+        }
+
+        writeln!(f, "\n## Backtrace\n")?;
+        write!(f, "{}", self.backtrace)
+    }
+}
+
+/// Extracts a human readable message from the payload of a panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<unknown panic payload>".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{content_hash, install, set_context, CrashReport, CRASH_REPORT_DIR_ENV};
+
+    #[test]
+    fn report_format() {
+        let report = CrashReport {
+            tool: "mun",
+            version: "1.2.3",
+            message: "something went wrong".to_owned(),
+            location: Some("src/lib.rs:1:2".to_owned()),
+            thread: "main".to_owned(),
+            context: BTreeMap::from([("command", "mun build".to_owned())]),
+            backtrace: "<backtrace>".to_owned(),
+        };
+
+        let platform = format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS);
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "# mun crash report\n\
+                \n\
+                version: 1.2.3\n\
+                platform: {platform}\n\
+                thread: main\n\
+                message: something went wrong\n\
+                location: src/lib.rs:1:2\n\
+                \n\
+                ## Context\n\
+                \n\
+                command: mun build\n\
+                \n\
+                ## Backtrace\n\
+                \n\
+                <backtrace>"
+            )
+        );
+    }
+
+    #[test]
+    fn content_hash_identifies_contents() {
+        assert_eq!(
+            content_hash(b"pub fn main() {}"),
+            content_hash(b"pub fn main() {}")
+        );
+        assert_ne!(
+            content_hash(b"pub fn main() {}"),
+            content_hash(b"fn main() {}")
+        );
+    }
+
+    #[test]
+    fn panic_writes_report() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var(CRASH_REPORT_DIR_ENV, dir.path());
+
+        install("mun-test", "1.2.3");
+        set_context("last request", "textDocument/hover");
+        std::thread::spawn(|| panic!("something went wrong"))
+            .join()
+            .unwrap_err();
+
+        let reports: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(reports.len(), 1);
+
+        let report = std::fs::read_to_string(reports[0].as_ref().unwrap().path()).unwrap();
+        assert!(report.starts_with("# mun-test crash report"));
+        assert!(report.contains("message: something went wrong"));
+        assert!(report.contains("last request: textDocument/hover"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Every diagnostic that is not emitted by a lint is identified by a stable
+//! error code, e.g. `E0008`. The extended explanation of a code, with examples
+//! of erroneous code and how to fix it, is stored in a markdown file with the
+//! same name in the `error_codes` directory.
+//!
+//! Codes are never reused. To add a code, append a new entry to the list
+//! below.
+
+macro_rules! error_codes {
+    ($($code:ident: $diagnostic:ident,)*) => {
+        /// All error codes in ascending order, together with their extended
+        /// explanation.
+        pub const ERROR_CODES: &[(&str, &str)] = &[
+            $((stringify!($code), include_str!(concat!("error_codes/", stringify!($code), ".md"))),)*
+        ];
+
+        /// Returns the error code of the specified HIR diagnostic, or `None` if it
+        /// doesn't have one, e.g. because it's emitted by a lint.
+        pub(crate) fn hir_error_code(diagnostic: &dyn mun_hir::Diagnostic) -> Option<&'static str> {
+            $(
+                if diagnostic.downcast_ref::<mun_hir::diagnostics::$diagnostic>().is_some() {
+                    return Some(stringify!($code));
+                }
+            )*
+            None
+        }
+    };
+}
+
+error_codes! {
+    E0001: UnresolvedValue,
+    E0002: UnresolvedType,
+    E0003: CyclicType,
+    E0004: PrivateAccess,
+    E0005: ExpectedFunction,
+    E0006: ExportedPrivate,
+    E0007: ParameterCountMismatch,
+    E0008: MismatchedType,
+    E0009: IncompatibleBranch,
+    E0010: InvalidLhs,
+    E0011: MissingElseBranch,
+    E0012: CannotApplyBinaryOp,
+    E0013: CannotApplyUnaryOp,
+    E0014: DuplicateDefinition,
+    E0015: ReturnMissingExpression,
+    E0016: BreakOutsideLoop,
+    E0017: ContinueOutsideLoop,
+    E0018: UndeclaredLabel,
+    E0019: DuplicateLabel,
+    E0020: ExpectedStringLiteral,
+    E0021: BuiltinFunctionNotCalled,
+    E0022: InvalidBuiltinArgument,
+    E0023: NotIterable,
+    E0024: TupleInArray,
+    E0025: UnsupportedRange,
+    E0026: BreakWithValueOutsideLoop,
+    E0027: AccessUnknownField,
+    E0028: FieldCountMismatch,
+    E0029: MissingFields,
+    E0030: MismatchedStructLit,
+    E0031: MismatchedStructPat,
+    E0032: PatFieldCountMismatch,
+    E0033: TuplePatLengthMismatch,
+    E0034: NoFields,
+    E0035: NoSuchField,
+    E0036: PossiblyUninitializedVariable,
+    E0037: AssignToImmutable,
+    E0038: ExternCannotHaveBody,
+    E0039: ExternNonPrimitiveParam,
+    E0040: InvalidTestFunction,
+    E0041: IntLiteralTooLarge,
+    E0042: LiteralOutOfRange,
+    E0043: InvalidLiteralSuffix,
+    E0044: InvalidFloatingPointLiteral,
+    E0045: FreeTypeAliasWithoutTypeRef,
+    E0046: UnresolvedImport,
+    E0047: ImportDuplicateDefinition,
+    E0048: ImportAmbiguousGlob,
+    E0049: GlobImportNotModule,
+    E0050: PrivateTypeAlias,
+    E0051: ImplForForeignType,
+    E0052: InvalidSelfTyImpl,
+    E0053: MethodNotInScope,
+    E0054: MethodNotFound,
+    E0055: NotConstExpr,
+    E0056: ConstOverflow,
+    E0057: DivisionByZero,
+    E0058: ConstEvalCycle,
+    E0059: InvalidStaticType,
+    E0060: UnknownAttribute,
+    E0061: MalformedAttribute,
+    E0062: AttributeNotOnFunction,
+    E0063: DeprecatedCall,
+    E0064: UnknownLint,
+    E0065: StaticInOtherModule,
+}
+
+/// Returns the extended explanation of the specified error code, e.g. `E0008`.
+/// The code is matched case-insensitively.
+pub fn explain(code: &str) -> Option<&'static str> {
+    ERROR_CODES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{explain, ERROR_CODES};
+
+    #[test]
+    fn codes_are_sequential() {
+        for (index, (code, _)) in ERROR_CODES.iter().enumerate() {
+            assert_eq!(*code, format!("E{:04}", index + 1));
+        }
+    }
+
+    #[test]
+    fn explanations_contain_examples() {
+        for (code, explanation) in ERROR_CODES {
+            assert!(
+                explanation.contains("Erroneous code example:") && explanation.contains("```mun"),
+                "the explanation of {code} has no example"
+            );
+        }
+    }
+
+    #[test]
+    fn explain_is_case_insensitive() {
+        assert_eq!(explain("e0008"), explain("E0008"));
+        assert!(explain("E0008").is_some());
+        assert!(explain("E9999").is_none());
+    }
+}
@@ -2,49 +2,132 @@
 //! `crate::Diagnostics`.
 
 mod access_unknown_field;
+mod assign_to_immutable;
 mod duplicate_definition_error;
 mod expected_function;
 mod exported_private;
+mod incompatible_branch;
+mod lint;
 mod mismatched_type;
+mod missing_else_branch;
 mod missing_fields;
+mod no_such_field;
 mod possibly_unitialized_variable;
+mod similar_name;
 mod unresolved_type;
 mod unresolved_value;
 
 use mun_hir::Diagnostic as HirDiagnostic;
 use mun_syntax::TextRange;
 
-use crate::{Diagnostic, DiagnosticForWith, SourceAnnotation};
+use crate::{
+    error_codes::hir_error_code, Diagnostic, DiagnosticForWith, Fix, SecondaryAnnotation,
+    SourceAnnotation,
+};
 
 // Provides conversion of a mun_hir::Diagnostic to a crate::Diagnostic. This
 // requires a database for most operations.
 impl<DB: mun_hir::HirDatabase> DiagnosticForWith<DB> for dyn mun_hir::Diagnostic {
     fn with_diagnostic<R, F: FnMut(&dyn Diagnostic) -> R>(&self, with: &DB, mut f: F) -> R {
-        if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::UnresolvedValue>() {
-            f(&unresolved_value::UnresolvedValue::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::UnresolvedType>() {
-            f(&unresolved_type::UnresolvedType::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::ExpectedFunction>() {
-            f(&expected_function::ExpectedFunction::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MismatchedType>() {
-            f(&mismatched_type::MismatchedType::new(with, v))
-        } else if let Some(v) =
-            self.downcast_ref::<mun_hir::diagnostics::PossiblyUninitializedVariable>()
-        {
-            f(&possibly_unitialized_variable::PossiblyUninitializedVariable::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::AccessUnknownField>() {
-            f(&access_unknown_field::AccessUnknownField::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::DuplicateDefinition>() {
-            f(&duplicate_definition_error::DuplicateDefinition::new(
-                with, v,
-            ))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MissingFields>() {
-            f(&missing_fields::MissingFields::new(with, v))
-        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::ExportedPrivate>() {
-            f(&exported_private::ExportedPrivate::new(with, v))
-        } else {
-            f(&GenericHirDiagnostic { diagnostic: self })
-        }
+        let error_code = hir_error_code(self);
+        with_specialized_diagnostic(self, with, |diagnostic| {
+            f(&WithErrorCode {
+                diagnostic,
+                error_code,
+            })
+        })
+    }
+}
+
+/// Calls `f` with the specialized implementation of `diagnostic`, or with a
+/// [`GenericHirDiagnostic`] if it doesn't have one.
+fn with_specialized_diagnostic<DB: mun_hir::HirDatabase, R, F: FnMut(&dyn Diagnostic) -> R>(
+    diagnostic: &dyn mun_hir::Diagnostic,
+    with: &DB,
+    mut f: F,
+) -> R {
+    if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnresolvedValue>() {
+        f(&unresolved_value::UnresolvedValue::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnresolvedType>() {
+        f(&unresolved_type::UnresolvedType::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::ExpectedFunction>() {
+        f(&expected_function::ExpectedFunction::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::MismatchedType>() {
+        f(&mismatched_type::MismatchedType::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::IncompatibleBranch>() {
+        f(&incompatible_branch::IncompatibleBranch::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::MissingElseBranch>() {
+        f(&missing_else_branch::MissingElseBranch::new(with, v))
+    } else if let Some(v) =
+        diagnostic.downcast_ref::<mun_hir::diagnostics::PossiblyUninitializedVariable>()
+    {
+        f(&possibly_unitialized_variable::PossiblyUninitializedVariable::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::AssignToImmutable>() {
+        f(&assign_to_immutable::AssignToImmutable::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::AccessUnknownField>() {
+        f(&access_unknown_field::AccessUnknownField::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::NoSuchField>() {
+        f(&no_such_field::NoSuchField::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::DuplicateDefinition>() {
+        f(&duplicate_definition_error::DuplicateDefinition::new(
+            with, v,
+        ))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::MissingFields>() {
+        f(&missing_fields::MissingFields::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::ExportedPrivate>() {
+        f(&exported_private::ExportedPrivate::new(with, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnusedVariable>() {
+        f(&lint::Lint::new(diagnostic, v.lint, v.level, v.source))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnusedFunction>() {
+        f(&lint::Lint::new(diagnostic, v.lint, v.level, v.source))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnreachableCode>() {
+        f(&lint::Lint::new(diagnostic, v.lint, v.level, v.source))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnusedMut>() {
+        f(&lint::Lint::unused_mut(with, diagnostic, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::ShadowedVariable>() {
+        f(&lint::Lint::shadowed_variable(with, diagnostic, v))
+    } else if let Some(v) = diagnostic.downcast_ref::<mun_hir::diagnostics::UnusedLabel>() {
+        f(&lint::Lint::new(diagnostic, v.lint, v.level, v.source))
+    } else {
+        f(&GenericHirDiagnostic { diagnostic })
+    }
+}
+
+/// Adds the error code of a HIR diagnostic to its specialized implementation.
+struct WithErrorCode<'diag> {
+    diagnostic: &'diag dyn Diagnostic,
+    error_code: Option<&'static str>,
+}
+
+impl Diagnostic for WithErrorCode<'_> {
+    fn title(&self) -> String {
+        self.diagnostic.title()
+    }
+
+    fn range(&self) -> TextRange {
+        self.diagnostic.range()
+    }
+
+    fn code(&self) -> Option<String> {
+        self.error_code
+            .map(ToOwned::to_owned)
+            .or_else(|| self.diagnostic.code())
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        self.diagnostic.primary_annotation()
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        self.diagnostic.secondary_annotations()
+    }
+
+    fn footer(&self) -> Vec<String> {
+        self.diagnostic.footer()
+    }
+
+    fn fixes(&self) -> Vec<Fix> {
+        self.diagnostic.fixes()
     }
 }
 
@@ -1,8 +1,11 @@
 use mun_hir::HirDisplay;
 use mun_syntax::{ast, AstNode, TextRange};
 
-use super::HirDiagnostic;
-use crate::{Diagnostic, SourceAnnotation};
+use super::{
+    similar_name::{similar_name_annotations, similar_name_footer},
+    HirDiagnostic,
+};
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
 
 /// An error that is emitted when trying to access a field that doesn't exist.
 ///
@@ -41,6 +44,14 @@ impl<DB: mun_hir::HirDatabase> Diagnostic for AccessUnknownField<'_, '_, DB> {
             message: "unknown field".to_string(),
         })
     }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        similar_name_annotations(self.db, self.diag.similar_name.as_ref(), "field")
+    }
+
+    fn footer(&self) -> Vec<String> {
+        similar_name_footer(self.diag.similar_name.as_ref())
+    }
 }
 
 impl<'db, 'diag, DB: mun_hir::HirDatabase> AccessUnknownField<'db, 'diag, DB> {
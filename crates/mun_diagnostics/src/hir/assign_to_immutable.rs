@@ -0,0 +1,67 @@
+use mun_hir::{diagnostics::ImmutableAssignmentKind, InFile};
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, Fix, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when a binding that is not declared as `mut` is
+/// mutated.
+///
+/// ```mun
+/// # fn main() {
+/// let a = 5;
+/// a = 6;    // cannot assign twice to immutable variable `a`
+/// #}
+/// ```
+pub struct AssignToImmutable<'db, 'diag, DB: mun_hir::HirDatabase> {
+    _db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::AssignToImmutable,
+}
+
+impl<DB: mun_hir::HirDatabase> Diagnostic for AssignToImmutable<'_, '_, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        self.diag.message()
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        let message = match self.diag.kind {
+            ImmutableAssignmentKind::Variable => "cannot assign twice to immutable variable",
+            ImmutableAssignmentKind::Argument => "cannot assign to immutable argument",
+            ImmutableAssignmentKind::Place => "cannot assign",
+        };
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message: message.to_owned(),
+        })
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        vec![SecondaryAnnotation {
+            range: InFile::new(self.diag.file, self.diag.binding.range()),
+            message: format!(
+                "help: consider making this binding mutable: `mut {}`",
+                self.diag.name
+            ),
+        }]
+    }
+
+    fn fixes(&self) -> Vec<Fix> {
+        let start = self.diag.binding.range().start();
+        vec![Fix {
+            label: format!("Make `{}` mutable", self.diag.name),
+            range: InFile::new(self.diag.file, TextRange::empty(start)),
+            replacement: String::from("mut "),
+        }]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> AssignToImmutable<'db, 'diag, DB> {
+    /// Constructs a new instance of `AssignToImmutable`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::AssignToImmutable) -> Self {
+        AssignToImmutable { _db: db, diag }
+    }
+}
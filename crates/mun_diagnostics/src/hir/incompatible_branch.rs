@@ -0,0 +1,53 @@
+use mun_hir::{HirDisplay, InFile};
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when the branches of an `if` expression evaluate
+/// to different types.
+///
+/// ```mun
+/// # fn main() {
+///     let a = if true { 1 } else { 2.0 };   // `if` and `else` have incompatible types
+/// # }
+/// ```
+pub struct IncompatibleBranch<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::IncompatibleBranch,
+}
+
+impl<DB: mun_hir::HirDatabase> Diagnostic for IncompatibleBranch<'_, '_, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        self.diag.message()
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message: format!(
+                "expected `{}`, found `{}`",
+                self.diag.expected.display(self.db),
+                self.diag.found.display(self.db)
+            ),
+        })
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        vec![SecondaryAnnotation {
+            range: InFile::new(self.diag.file, self.diag.then_expr.range()),
+            message: "expected because of this".to_owned(),
+        }]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> IncompatibleBranch<'db, 'diag, DB> {
+    /// Constructs a new instance of `IncompatibleBranch`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::IncompatibleBranch) -> Self {
+        IncompatibleBranch { db, diag }
+    }
+}
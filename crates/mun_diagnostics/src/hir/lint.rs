@@ -0,0 +1,157 @@
+use mun_hir::{HirDisplay, InFile, LintLevel, LintSource};
+use mun_syntax::{ast, AstNode, SyntaxKind, SyntaxNodePtr, TextRange};
+
+use crate::{Diagnostic, Fix, SecondaryAnnotation, SourceAnnotation};
+
+/// A diagnostic that is emitted by a lint, e.g. for an unused variable. The
+/// footer explains where the level of the lint was specified.
+///
+/// ```mun
+/// # pub fn main() {
+/// let a = 5;    // unused variable: `a`
+/// # }
+/// ```
+pub struct Lint<'diag> {
+    diagnostic: &'diag dyn mun_hir::Diagnostic,
+    lint: mun_hir::Lint,
+    level: LintLevel,
+    source: LintSource,
+    secondary_annotations: Vec<SecondaryAnnotation>,
+    fixes: Vec<Fix>,
+}
+
+impl Diagnostic for Lint<'_> {
+    fn range(&self) -> TextRange {
+        self.diagnostic.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        self.diagnostic.message()
+    }
+
+    fn code(&self) -> Option<String> {
+        Some(self.lint.to_string())
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        None
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        self.secondary_annotations.clone()
+    }
+
+    fn footer(&self) -> Vec<String> {
+        match self.source {
+            LintSource::Default => {
+                vec![format!("`#[{}({})]` on by default", self.level, self.lint)]
+            }
+            LintSource::Package => vec![format!(
+                "`#[{}({})]` is configured for the package",
+                self.level, self.lint
+            )],
+            LintSource::Attribute => Vec::new(),
+        }
+    }
+
+    fn fixes(&self) -> Vec<Fix> {
+        self.fixes.clone()
+    }
+}
+
+impl<'diag> Lint<'diag> {
+    /// Constructs a new instance of `Lint`
+    pub fn new(
+        diagnostic: &'diag dyn mun_hir::Diagnostic,
+        lint: mun_hir::Lint,
+        level: LintLevel,
+        source: LintSource,
+    ) -> Self {
+        Lint {
+            diagnostic,
+            lint,
+            level,
+            source,
+            secondary_annotations: Vec::new(),
+            fixes: Vec::new(),
+        }
+    }
+
+    /// Constructs a new instance of `Lint` for a binding that does not need to
+    /// be mutable, with a fix that removes the `mut`.
+    pub fn unused_mut<DB: mun_hir::HirDatabase>(
+        db: &DB,
+        diagnostic: &'diag dyn mun_hir::Diagnostic,
+        unused_mut: &mun_hir::diagnostics::UnusedMut,
+    ) -> Self {
+        let mut lint = Lint::new(
+            diagnostic,
+            unused_mut.lint,
+            unused_mut.level,
+            unused_mut.source,
+        );
+
+        let parse = db.parse(unused_mut.pat.file_id);
+        let binding = unused_mut.pat.value.to_node(&parse.syntax_node());
+        let mut_token = ast::BindPat::cast(binding.clone())
+            .and_then(|it| it.mut_token())
+            .or_else(|| ast::SelfParam::cast(binding).and_then(|it| it.mut_token()));
+        if let Some(mut_token) = mut_token {
+            // Also remove the whitespace between `mut` and the name
+            let end = mut_token
+                .next_token()
+                .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+                .map_or_else(|| mut_token.text_range().end(), |it| it.text_range().end());
+            lint.fixes.push(Fix {
+                label: String::from("Remove unnecessary `mut`"),
+                range: InFile::new(
+                    unused_mut.pat.file_id,
+                    TextRange::new(mut_token.text_range().start(), end),
+                ),
+                replacement: String::new(),
+            });
+        }
+        lint
+    }
+    /// Constructs a new instance of `Lint` for a binding that shadows a
+    /// binding of a different type, which points out the shadowed binding and
+    /// where it is still used.
+    pub fn shadowed_variable<DB: mun_hir::HirDatabase>(
+        db: &DB,
+        diagnostic: &'diag dyn mun_hir::Diagnostic,
+        shadowed_variable: &mun_hir::diagnostics::ShadowedVariable,
+    ) -> Self {
+        let mut lint = Lint::new(
+            diagnostic,
+            shadowed_variable.lint,
+            shadowed_variable.level,
+            shadowed_variable.source,
+        );
+        lint.secondary_annotations = vec![
+            SecondaryAnnotation {
+                range: shadowed_variable
+                    .shadowed
+                    .as_ref()
+                    .map(SyntaxNodePtr::range),
+                message: format!(
+                    "`{}` of type `{}` is declared here",
+                    shadowed_variable.name,
+                    shadowed_variable.shadowed_ty.display(db)
+                ),
+            },
+            SecondaryAnnotation {
+                range: shadowed_variable
+                    .later_use
+                    .as_ref()
+                    .map(SyntaxNodePtr::range),
+                message: format!(
+                    "`{}` is still used here, while the new `{}` has type `{}`",
+                    shadowed_variable.name,
+                    shadowed_variable.name,
+                    shadowed_variable.ty.display(db)
+                ),
+            },
+        ];
+        lint
+    }
+}
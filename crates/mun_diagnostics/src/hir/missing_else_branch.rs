@@ -0,0 +1,52 @@
+use mun_hir::HirDisplay;
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SourceAnnotation};
+
+/// An error that is emitted when the value of an `if` expression without an
+/// `else` branch is used.
+///
+/// ```mun
+/// # fn main() {
+///     let a = if true { 1 };   // `if` may be missing an `else` clause
+/// # }
+/// ```
+pub struct MissingElseBranch<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::MissingElseBranch,
+}
+
+impl<DB: mun_hir::HirDatabase> Diagnostic for MissingElseBranch<'_, '_, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        "`if` may be missing an `else` clause".to_owned()
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message: format!(
+                "expected `()`, found `{}`",
+                self.diag.found.display(self.db)
+            ),
+        })
+    }
+
+    fn footer(&self) -> Vec<String> {
+        vec![
+            "`if` expressions without `else` evaluate to `()`".to_owned(),
+            "consider adding an `else` block that evaluates to the expected type".to_owned(),
+        ]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> MissingElseBranch<'db, 'diag, DB> {
+    /// Constructs a new instance of `MissingElseBranch`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::MissingElseBranch) -> Self {
+        MissingElseBranch { db, diag }
+    }
+}
@@ -0,0 +1,53 @@
+use mun_syntax::TextRange;
+
+use super::{
+    similar_name::{similar_name_annotations, similar_name_footer},
+    HirDiagnostic,
+};
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when a struct literal or pattern refers to a field
+/// that doesn't exist.
+///
+/// ```mun
+/// struct Foo {
+///     b: i32
+/// }
+///
+/// # fn main() {
+/// let a = Foo { c: 3 }; // no field `c`
+/// #}
+/// ```
+pub struct NoSuchField<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::NoSuchField,
+}
+
+impl<DB: mun_hir::HirDatabase> Diagnostic for NoSuchField<'_, '_, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        self.diag.message()
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        None
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        similar_name_annotations(self.db, self.diag.similar_name.as_ref(), "field")
+    }
+
+    fn footer(&self) -> Vec<String> {
+        similar_name_footer(self.diag.similar_name.as_ref())
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> NoSuchField<'db, 'diag, DB> {
+    /// Constructs a new instance of `NoSuchField`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::NoSuchField) -> Self {
+        NoSuchField { db, diag }
+    }
+}
@@ -0,0 +1,39 @@
+use mun_hir::{diagnostics::SimilarName, InFile};
+
+use super::duplicate_definition_error::syntax_node_signature_range;
+use crate::SecondaryAnnotation;
+
+/// Returns an annotation that points to the definition of a name that is
+/// similar to a name that could not be resolved. `kind` describes what is
+/// named, e.g. `field`.
+pub(super) fn similar_name_annotations<DB: mun_hir::HirDatabase>(
+    db: &DB,
+    similar_name: Option<&SimilarName>,
+    kind: &str,
+) -> Vec<SecondaryAnnotation> {
+    let Some(similar_name) = similar_name else {
+        return Vec::new();
+    };
+    let Some(definition) = &similar_name.definition else {
+        return Vec::new();
+    };
+
+    vec![SecondaryAnnotation {
+        range: InFile::new(
+            definition.file_id,
+            syntax_node_signature_range(&definition.value, &db.parse(definition.file_id)),
+        ),
+        message: format!(
+            "similarly named {kind} `{}` defined here",
+            similar_name.name
+        ),
+    }]
+}
+
+/// Returns a footer that suggests to use a similar name instead of a name that
+/// could not be resolved.
+pub(super) fn similar_name_footer(similar_name: Option<&SimilarName>) -> Vec<String> {
+    similar_name
+        .map(|similar_name| vec![format!("did you mean `{}`?", similar_name.name)])
+        .unwrap_or_default()
+}
@@ -1,7 +1,10 @@
 use mun_syntax::{AstNode, TextRange};
 
-use super::HirDiagnostic;
-use crate::{Diagnostic, SourceAnnotation};
+use super::{
+    similar_name::{similar_name_annotations, similar_name_footer},
+    HirDiagnostic,
+};
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
 
 /// An error that is emitted when trying to use a type that doesnt exist within
 /// the scope.
@@ -12,7 +15,7 @@ use crate::{Diagnostic, SourceAnnotation};
 /// #}
 /// ```
 pub struct UnresolvedType<'db, 'diag, DB: mun_hir::HirDatabase> {
-    _db: &'db DB,
+    db: &'db DB,
     diag: &'diag mun_hir::diagnostics::UnresolvedType,
     value_name: String,
 }
@@ -32,6 +35,14 @@ impl<DB: mun_hir::HirDatabase> Diagnostic for UnresolvedType<'_, '_, DB> {
             message: "not found in this scope".to_owned(),
         })
     }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        similar_name_annotations(self.db, self.diag.similar_name.as_ref(), "type")
+    }
+
+    fn footer(&self) -> Vec<String> {
+        similar_name_footer(self.diag.similar_name.as_ref())
+    }
 }
 
 impl<'db, 'diag, DB: mun_hir::HirDatabase> UnresolvedType<'db, 'diag, DB> {
@@ -48,7 +59,7 @@ impl<'db, 'diag, DB: mun_hir::HirDatabase> UnresolvedType<'db, 'diag, DB> {
             .to_string();
 
         UnresolvedType {
-            _db: db,
+            db,
             diag,
             value_name,
         }
@@ -1,7 +1,10 @@
 use mun_syntax::{AstNode, TextRange};
 
-use super::HirDiagnostic;
-use crate::{Diagnostic, SourceAnnotation};
+use super::{
+    similar_name::{similar_name_annotations, similar_name_footer},
+    HirDiagnostic,
+};
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
 
 /// An error that is emitted when trying to use a value that doesnt exist within
 /// the scope.
@@ -12,7 +15,7 @@ use crate::{Diagnostic, SourceAnnotation};
 /// #}
 /// ```
 pub struct UnresolvedValue<'db, 'diag, DB: mun_hir::HirDatabase> {
-    _db: &'db DB,
+    db: &'db DB,
     diag: &'diag mun_hir::diagnostics::UnresolvedValue,
     value_name: String,
 }
@@ -32,6 +35,14 @@ impl<DB: mun_hir::HirDatabase> Diagnostic for UnresolvedValue<'_, '_, DB> {
             message: "not found in this scope".to_owned(),
         })
     }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        similar_name_annotations(self.db, self.diag.similar_name.as_ref(), "value")
+    }
+
+    fn footer(&self) -> Vec<String> {
+        similar_name_footer(self.diag.similar_name.as_ref())
+    }
 }
 
 impl<'db, 'diag, DB: mun_hir::HirDatabase> UnresolvedValue<'db, 'diag, DB> {
@@ -43,7 +54,7 @@ impl<'db, 'diag, DB: mun_hir::HirDatabase> UnresolvedValue<'db, 'diag, DB> {
         let value_name = diag.expr.to_node(parse.tree().syntax()).text().to_string();
 
         UnresolvedValue {
-            _db: db,
+            db,
             diag,
             value_name,
         }
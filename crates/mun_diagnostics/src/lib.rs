@@ -7,11 +7,14 @@
 //! reasons. This enables lazily querying the system for more information only
 //! when required.
 
+mod error_codes;
 mod hir;
 
 use mun_hir::InFile;
 use mun_syntax::TextRange;
 
+pub use crate::error_codes::{explain, ERROR_CODES};
+
 /// An annotation within the source code
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SourceAnnotation {
@@ -32,6 +35,20 @@ pub struct SecondaryAnnotation {
     pub message: String,
 }
 
+/// A change to the source code that resolves a diagnostic, e.g. adding a
+/// missing `mut`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fix {
+    /// A short description of the change, e.g. "Make `a` mutable"
+    pub label: String,
+
+    /// The location in the source that is replaced
+    pub range: InFile<TextRange>,
+
+    /// The text that replaces the range
+    pub replacement: String,
+}
+
 /// The base trait for all diagnostics in this crate.
 pub trait Diagnostic {
     /// Returns the primary message of the diagnostic.
@@ -40,6 +57,12 @@ pub trait Diagnostic {
     /// Returns the location of this diagnostic.
     fn range(&self) -> TextRange;
 
+    /// Returns a code that identifies the kind of diagnostic: its error code
+    /// (e.g. `E0008`), or the name of the lint that emitted it.
+    fn code(&self) -> Option<String> {
+        None
+    }
+
     /// Returns a source annotation that acts as the primary annotation for this
     /// Diagnostic.
     fn primary_annotation(&self) -> Option<SourceAnnotation>;
@@ -54,6 +77,11 @@ pub trait Diagnostic {
     fn footer(&self) -> Vec<String> {
         Vec::new()
     }
+
+    /// Returns changes to the source code that resolve the diagnostic.
+    fn fixes(&self) -> Vec<Fix> {
+        Vec::new()
+    }
 }
 
 /// When implemented enables requesting `Diagnostic`s for the implementer.
@@ -0,0 +1,83 @@
+//! Builds the Mun packages of the examples in the repository, so integration
+//! tests can load them in the runtime and exercise their entry points. This
+//! catches examples that no longer compile or run with the current toolchain.
+
+use std::path::{Path, PathBuf};
+
+use mun_compiler::{Config, DisplayColor, Driver};
+
+/// The name of the manifest file of a Mun package
+const MANIFEST_FILENAME: &str = "mun.toml";
+
+/// A Mun example package that was built into a temporary directory.
+#[derive(Debug)]
+pub struct BuiltExample {
+    _out_dir: tempfile::TempDir,
+    lib_path: PathBuf,
+}
+
+impl BuiltExample {
+    /// Returns the path to the `*.munlib` library of the root module.
+    pub fn lib_path(&self) -> &Path {
+        &self.lib_path
+    }
+}
+
+/// Returns the directory that contains the examples.
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples")
+}
+
+/// Returns the paths to the manifests of all Mun packages in the examples
+/// directory. A package is either located in the directory of an example or in
+/// its `mun` subdirectory.
+pub fn example_manifests() -> anyhow::Result<Vec<PathBuf>> {
+    let mut manifests = Vec::new();
+    for entry in std::fs::read_dir(examples_dir())? {
+        let example_dir = entry?.path();
+        for manifest_path in [
+            example_dir.join(MANIFEST_FILENAME),
+            example_dir.join("mun").join(MANIFEST_FILENAME),
+        ] {
+            if manifest_path.is_file() {
+                manifests.push(manifest_path);
+            }
+        }
+    }
+    manifests.sort();
+    Ok(manifests)
+}
+
+/// Builds the Mun package of the example with the specified `name`, e.g.
+/// `fibonacci`.
+pub fn build_example(name: &str) -> anyhow::Result<BuiltExample> {
+    let example_dir = examples_dir().join(name);
+    let manifest_path = if example_dir.join(MANIFEST_FILENAME).is_file() {
+        example_dir.join(MANIFEST_FILENAME)
+    } else {
+        example_dir.join("mun").join(MANIFEST_FILENAME)
+    };
+    build_package(&manifest_path)
+}
+
+/// Builds the Mun package with the manifest at `manifest_path` into a
+/// temporary directory. Fails if the compiler emits any errors.
+pub fn build_package(manifest_path: &Path) -> anyhow::Result<BuiltExample> {
+    let out_dir = tempfile::tempdir()?;
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+
+    let (_, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    if let Some(errors) = driver.emit_diagnostics_to_string(DisplayColor::Disable)? {
+        anyhow::bail!("could not compile `{}`:\n{errors}", manifest_path.display());
+    }
+    driver.write_all_assemblies(true)?;
+
+    let lib_path = out_dir.path().join("mod.munlib");
+    Ok(BuiltExample {
+        _out_dir: out_dir,
+        lib_path,
+    })
+}
@@ -0,0 +1,116 @@
+use mun_examples::{build_example, build_package, example_manifests, BuiltExample};
+use mun_runtime::{Runtime, RuntimeBuilder, StructRef};
+
+/// Builds the example with the specified `name` and loads it in a runtime that
+/// is configured by `config_fn`. The returned example must outlive the
+/// runtime.
+fn load_example(
+    name: &str,
+    config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+) -> (BuiltExample, Runtime) {
+    let example = build_example(name).unwrap();
+    let builder = config_fn(Runtime::builder(example.lib_path()));
+
+    // Safety: the library was just built by the Mun compiler
+    let runtime = unsafe { builder.finish() }.expect("could not load the example");
+    (example, runtime)
+}
+
+#[test]
+fn all_examples_compile() {
+    let manifests = example_manifests().unwrap();
+    assert!(!manifests.is_empty(), "could not find any examples");
+    for manifest_path in manifests {
+        if let Err(e) = build_package(&manifest_path) {
+            panic!("{e}");
+        }
+    }
+}
+
+#[test]
+fn fibonacci() {
+    let (_example, runtime) = load_example("fibonacci", |builder| builder);
+
+    let result: i64 = runtime.invoke("fibonacci_n", ()).unwrap();
+    assert_eq!(result, 55);
+
+    let result: i64 = runtime.invoke("fibonacci", (20i64,)).unwrap();
+    assert_eq!(result, 6765);
+}
+
+extern "C" fn log_f32(_value: f32) {}
+
+#[test]
+fn buoyancy() {
+    let (_example, runtime) = load_example("buoyancy", |builder| {
+        builder.insert_fn("log_f32", log_f32 as extern "C" fn(f32))
+    });
+
+    let ctx = runtime
+        .invoke::<StructRef<'_>, ()>("new_sim", ())
+        .unwrap()
+        .root();
+    let height = |runtime: &Runtime| {
+        ctx.as_ref(runtime)
+            .get::<StructRef<'_>>("sphere")
+            .unwrap()
+            .get::<f32>("height")
+            .unwrap()
+    };
+    assert_eq!(height(&runtime), 1.0);
+
+    // The sphere is less dense than water, so it falls and bobs back up
+    let mut lowest = f32::MAX;
+    for _ in 0..100 {
+        let _: () = runtime
+            .invoke("sim_update", (ctx.as_ref(&runtime), 0.04f32))
+            .unwrap();
+        let height = height(&runtime);
+        assert!(height.is_finite());
+        lowest = lowest.min(height);
+    }
+    assert!(lowest < 1.0);
+    assert!(height(&runtime) > lowest);
+}
+
+extern "C" fn rand_f32() -> f32 {
+    0.5
+}
+
+#[test]
+fn pong_update_tick() {
+    let (_example, runtime) = load_example("rust-pong", |builder| {
+        builder.insert_fn("rand_f32", rand_f32 as extern "C" fn() -> f32)
+    });
+
+    let radius: f32 = runtime.invoke("ball_radius", ()).unwrap();
+    assert_eq!(radius, 10.0);
+
+    let state = runtime
+        .invoke::<StructRef<'_>, ()>("new_state", ())
+        .unwrap()
+        .root();
+    let mut paddle = state
+        .as_ref(&runtime)
+        .get::<StructRef<'_>>("paddle_left")
+        .unwrap();
+    paddle.set("move_up", true).unwrap();
+
+    let _: () = runtime.invoke("update", (state.as_ref(&runtime),)).unwrap();
+
+    let state = state.as_ref(&runtime);
+    let ball_pos = state
+        .get::<StructRef<'_>>("ball")
+        .unwrap()
+        .get::<StructRef<'_>>("pos")
+        .unwrap();
+    assert_eq!(ball_pos.get::<f32>("x").unwrap(), 399.0);
+    assert_eq!(ball_pos.get::<f32>("y").unwrap(), 297.5);
+
+    let paddle_pos = state
+        .get::<StructRef<'_>>("paddle_left")
+        .unwrap()
+        .get::<StructRef<'_>>("pos")
+        .unwrap();
+    assert_eq!(paddle_pos.get::<f32>("y").unwrap(), 244.0);
+}
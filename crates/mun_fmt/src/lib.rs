@@ -0,0 +1,380 @@
+//! The formatter of the Mun programming language.
+//!
+//! The formatter walks the tokens of a [`mun_syntax`] concrete syntax tree and
+//! replaces all whitespace between them with canonical whitespace: tokens are
+//! separated by at most a single space, items and statements are placed on
+//! lines of their own, nested blocks are indented by four spaces and at most
+//! one empty line is kept between lines. Comments are preserved.
+//!
+//! Whether a delimited list of elements (e.g. the arguments of a call) is
+//! written on a single line or with one element per line is left to the
+//! author: a list is spread over multiple lines if its opening delimiter is
+//! followed by a line break. Function bodies, blocks that contain statements,
+//! struct fields and the items of an `impl` are always spread over multiple
+//! lines.
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use mun_syntax::{
+    AstNode, SourceFile, SyntaxElement, SyntaxError, SyntaxKind, SyntaxNode, SyntaxToken, T,
+};
+
+/// The text that a nested block is indented with.
+const INDENT: &str = "    ";
+
+/// Formats the Mun source code in `text`.
+///
+/// Source code that contains syntax errors is not formatted, instead the
+/// errors are returned.
+pub fn format(text: &str) -> Result<String, Arc<[SyntaxError]>> {
+    let file = SourceFile::parse(text).ok()?;
+    Ok(format_node(file.syntax()))
+}
+
+/// Returns true if formatting `text` leaves it unchanged.
+pub fn is_formatted(text: &str) -> Result<bool, Arc<[SyntaxError]>> {
+    format(text).map(|formatted| formatted == text)
+}
+
+/// A delimiter that was opened but not yet closed.
+struct OpenDelimiter {
+    /// The node that is delimited
+    node: SyntaxNode,
+
+    /// Whether the elements of `node` are placed on lines of their own
+    multiline: bool,
+}
+
+/// The whitespace that is placed between two tokens.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Separator {
+    None,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+/// Formats all tokens of `root`.
+fn format_node(root: &SyntaxNode) -> String {
+    let mut output = String::new();
+
+    // The root behaves like a delimited node whose elements are all placed on lines
+    // of their own, but it is not indented.
+    let mut stack = vec![OpenDelimiter {
+        node: root.clone(),
+        multiline: true,
+    }];
+
+    let mut prev: Option<SyntaxToken> = None;
+    for token in significant_tokens(root) {
+        if token.kind() == T![,] && is_redundant_comma(&token) {
+            continue;
+        }
+
+        let closed = if is_closing_delimiter(&token) && stack.len() > 1 {
+            stack.pop()
+        } else {
+            None
+        };
+
+        if let Some(prev) = &prev {
+            // Multiline comma separated lists end with a trailing comma
+            if closed.as_ref().is_some_and(|it| it.multiline)
+                && is_comma_separated_list(token.parent().as_ref())
+                && !matches!(prev.kind(), T![,] | T![..] | SyntaxKind::COMMENT)
+                && !is_opening_delimiter(prev)
+            {
+                output.push(',');
+            }
+
+            let separator = separator(prev, &token, &stack, closed.as_ref());
+            match separator {
+                Separator::None => {}
+                Separator::Space => output.push(' '),
+                Separator::Newline | Separator::BlankLine => {
+                    if separator == Separator::BlankLine {
+                        output.push('\n');
+                    }
+                    output.push('\n');
+                    let depth = stack.iter().filter(|it| it.multiline).count() - 1;
+                    output.push_str(&INDENT.repeat(depth));
+                }
+            }
+        }
+
+        if token.kind() == SyntaxKind::COMMENT {
+            output.push_str(token.text().trim_end());
+        } else {
+            output.push_str(token.text());
+        }
+
+        if is_opening_delimiter(&token) {
+            if let Some(node) = token.parent() {
+                stack.push(OpenDelimiter {
+                    multiline: is_multiline(&token, &node),
+                    node,
+                });
+            }
+        }
+
+        prev = Some(token);
+    }
+
+    if prev.is_some() {
+        output.push('\n');
+    }
+    output
+}
+
+/// Returns the separator to place between `prev` and `next`. `stack` contains
+/// the delimiters that enclose `next`, `closed` is the delimiter that is closed
+/// by `next`, if any.
+fn separator(
+    prev: &SyntaxToken,
+    next: &SyntaxToken,
+    stack: &[OpenDelimiter],
+    closed: Option<&OpenDelimiter>,
+) -> Separator {
+    // At most a single blank line is preserved, but never directly after an opening
+    // delimiter or before a closing one.
+    let line_break =
+        if newlines_between(prev, next) > 1 && !is_opening_delimiter(prev) && closed.is_none() {
+            Separator::BlankLine
+        } else {
+            Separator::Newline
+        };
+
+    if let Some(closed) = closed {
+        if closed.multiline || is_line_comment(prev) {
+            return Separator::Newline;
+        }
+        if is_opening_delimiter(prev) {
+            return Separator::None;
+        }
+        return if prev.kind() == SyntaxKind::COMMENT || spaced_delimiters(&closed.node) {
+            Separator::Space
+        } else {
+            Separator::None
+        };
+    }
+
+    // Comments stay on the line of the code that precedes them, block comments also
+    // on the line of the code that follows them.
+    let is_block_comment = prev.kind() == SyntaxKind::COMMENT && !is_line_comment(prev);
+    if (next.kind() == SyntaxKind::COMMENT || is_block_comment) && newlines_between(prev, next) == 0
+    {
+        return Separator::Space;
+    }
+    if is_line_comment(prev) || starts_line(next, stack) {
+        return line_break;
+    }
+    if is_opening_delimiter(prev) && stack.last().is_some_and(|it| it.multiline) {
+        return Separator::Newline;
+    }
+    if prev.kind() == T![']'] && is_in(prev, SyntaxKind::ATTR) {
+        return Separator::Newline;
+    }
+    if next.kind() == SyntaxKind::COMMENT || prev.kind() == SyntaxKind::COMMENT {
+        return line_break;
+    }
+
+    if is_opening_delimiter(prev) {
+        return if prev.parent().as_ref().is_some_and(spaced_delimiters) {
+            Separator::Space
+        } else {
+            Separator::None
+        };
+    }
+
+    let is_range_operator = |token: &SyntaxToken| {
+        matches!(token.kind(), T![..] | T![..=]) && is_in(token, SyntaxKind::RANGE_EXPR)
+    };
+    if is_range_operator(prev) || is_range_operator(next) {
+        return Separator::None;
+    }
+
+    let is_prefix_operator = |token: &SyntaxToken| {
+        is_in(token, SyntaxKind::PREFIX_EXPR) && token.prev_sibling_or_token().is_none()
+    };
+    if matches!(prev.kind(), T![.] | T![::] | T![#]) || is_prefix_operator(prev) {
+        return Separator::None;
+    }
+
+    if matches!(
+        next.kind(),
+        T![')'] | T![']'] | T![,] | T![;] | T![:] | T![.] | T![::]
+    ) {
+        return Separator::None;
+    }
+
+    // Argument lists and indices directly follow what they apply to
+    let is_attached_delimiter = match next.kind() {
+        T!['('] => next.parent().is_some_and(|it| {
+            matches!(
+                it.kind(),
+                SyntaxKind::ARG_LIST
+                    | SyntaxKind::PARAM_LIST
+                    | SyntaxKind::TUPLE_FIELD_DEF_LIST
+                    | SyntaxKind::TUPLE_STRUCT_PAT
+                    | SyntaxKind::MEMORY_TYPE_SPECIFIER
+                    | SyntaxKind::VISIBILITY
+                    | SyntaxKind::TOKEN_TREE
+            )
+        }),
+        T!['['] => is_in(next, SyntaxKind::INDEX_EXPR),
+        _ => false,
+    };
+    if is_attached_delimiter {
+        return Separator::None;
+    }
+
+    Separator::Space
+}
+
+/// Returns true if `token` is the first token of a node that is placed on a
+/// line of its own. These are the elements of a multiline delimited node, like
+/// items, statements and fields.
+fn starts_line(token: &SyntaxToken, stack: &[OpenDelimiter]) -> bool {
+    let Some(delimited) = stack.last().filter(|it| it.multiline) else {
+        return false;
+    };
+    token
+        .parent_ancestors()
+        .take_while(|node| first_significant_token(node).as_ref() == Some(token))
+        .any(|node| node.parent().as_ref() == Some(&delimited.node))
+}
+
+/// Returns true if the elements of `node`, which is delimited by `opening`, are
+/// placed on lines of their own.
+fn is_multiline(opening: &SyntaxToken, node: &SyntaxNode) -> bool {
+    let next = next_significant_token(opening);
+    let is_empty = next.as_ref().map_or(true, is_closing_delimiter);
+    if is_empty {
+        return false;
+    }
+
+    let always_multiline = match node.kind() {
+        SyntaxKind::RECORD_FIELD_DEF_LIST | SyntaxKind::ASSOCIATED_ITEM_LIST => true,
+        SyntaxKind::BLOCK_EXPR => {
+            node.parent()
+                .is_some_and(|it| it.kind() == SyntaxKind::FUNCTION_DEF)
+                || node
+                    .children()
+                    .any(|it| matches!(it.kind(), SyntaxKind::LET_STMT | SyntaxKind::EXPR_STMT))
+        }
+        _ => false,
+    };
+
+    always_multiline
+        || next.is_some_and(|next| newlines_between(opening, &next) > 0 || is_line_comment(&next))
+}
+
+/// Returns true if `token` is the trailing comma of a list that is written on a
+/// single line.
+fn is_redundant_comma(token: &SyntaxToken) -> bool {
+    let Some(list) = token
+        .parent()
+        .filter(|it| is_comma_separated_list(Some(it)))
+    else {
+        return false;
+    };
+    let is_last = next_significant_token(token)
+        .is_some_and(|next| is_closing_delimiter(&next) && next.parent().as_ref() == Some(&list));
+    let opening = list
+        .children_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .find(is_opening_delimiter);
+    // The comma of a tuple with a single element distinguishes it from a
+    // parenthesized expression, e.g. `(a,)`
+    let is_single_element_tuple =
+        matches!(list.kind(), SyntaxKind::TUPLE_EXPR | SyntaxKind::TUPLE_PAT)
+            && list
+                .children_with_tokens()
+                .filter(|it| it.kind() == T![,])
+                .count()
+                == 1;
+    is_last
+        && !is_single_element_tuple
+        && opening.is_some_and(|opening| !is_multiline(&opening, &list))
+}
+
+/// Returns true if `node` is a list of elements that are separated by commas.
+fn is_comma_separated_list(node: Option<&SyntaxNode>) -> bool {
+    node.is_some_and(|node| {
+        matches!(
+            node.kind(),
+            SyntaxKind::ARG_LIST
+                | SyntaxKind::PARAM_LIST
+                | SyntaxKind::ARRAY_EXPR
+                | SyntaxKind::RECORD_FIELD_DEF_LIST
+                | SyntaxKind::TUPLE_FIELD_DEF_LIST
+                | SyntaxKind::RECORD_FIELD_LIST
+                | SyntaxKind::RECORD_FIELD_PAT_LIST
+                | SyntaxKind::TUPLE_STRUCT_PAT
+                | SyntaxKind::TUPLE_EXPR
+                | SyntaxKind::TUPLE_PAT
+                | SyntaxKind::USE_TREE_LIST
+        )
+    })
+}
+
+/// Returns true if the contents of `node` are separated from its delimiters by
+/// a space when written on a single line, e.g. `{ a: 1 }` instead of `(a, 1)`.
+fn spaced_delimiters(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::BLOCK_EXPR
+            | SyntaxKind::RECORD_FIELD_DEF_LIST
+            | SyntaxKind::RECORD_FIELD_LIST
+            | SyntaxKind::RECORD_FIELD_PAT_LIST
+            | SyntaxKind::ASSOCIATED_ITEM_LIST
+    )
+}
+
+/// Returns all tokens of `node` except whitespace.
+fn significant_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|it| it.kind() != SyntaxKind::WHITESPACE)
+}
+
+/// Returns the first token of `node` that is not whitespace.
+fn first_significant_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    significant_tokens(node).next()
+}
+
+/// Returns the first token after `token` that is not whitespace.
+fn next_significant_token(token: &SyntaxToken) -> Option<SyntaxToken> {
+    std::iter::successors(token.next_token(), SyntaxToken::next_token)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE)
+}
+
+/// Returns the number of line breaks in the whitespace between `prev` and
+/// `next`.
+fn newlines_between(prev: &SyntaxToken, next: &SyntaxToken) -> usize {
+    std::iter::successors(prev.next_token(), SyntaxToken::next_token)
+        .take_while(|it| it != next)
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+        .map(|it| it.text().matches('\n').count())
+        .sum()
+}
+
+/// Returns true if the parent of `token` is of the specified kind.
+fn is_in(token: &SyntaxToken, kind: SyntaxKind) -> bool {
+    token.parent().is_some_and(|it| it.kind() == kind)
+}
+
+fn is_line_comment(token: &SyntaxToken) -> bool {
+    token.kind() == SyntaxKind::COMMENT && token.text().starts_with("//")
+}
+
+fn is_opening_delimiter(token: &SyntaxToken) -> bool {
+    matches!(token.kind(), T!['('] | T!['['] | T!['{'])
+}
+
+fn is_closing_delimiter(token: &SyntaxToken) -> bool {
+    matches!(token.kind(), T![')'] | T![']'] | T!['}'])
+}
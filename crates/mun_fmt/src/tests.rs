@@ -0,0 +1,260 @@
+use crate::{format, is_formatted};
+
+/// Formats `text` and checks that formatting the result again does not change
+/// it.
+fn fmt(text: &str) -> String {
+    let formatted = format(text).expect("text contains syntax errors");
+    assert_eq!(
+        format(&formatted).unwrap(),
+        formatted,
+        "formatting is not idempotent"
+    );
+    formatted
+}
+
+#[test]
+fn whitespace_between_tokens() {
+    insta::assert_snapshot!(fmt("pub   fn  add(a:i32,b  :i32)->i32{a+b*-a}"), @r###"
+    pub fn add(a: i32, b: i32) -> i32 {
+        a + b * -a
+    }
+    "###);
+}
+
+#[test]
+fn statements_on_own_lines() {
+    insta::assert_snapshot!(fmt("fn main() { let a = 3; let b = a; b }"), @r###"
+    fn main() {
+        let a = 3;
+        let b = a;
+        b
+    }
+    "###);
+}
+
+#[test]
+fn nested_blocks_indentation() {
+    insta::assert_snapshot!(fmt(r#"
+fn main() {
+let mut i = 0;
+      while i < 10 {
+  if i % 2 == 0 { i += 1; } else { i += 2; }
+}
+  loop { break; }
+}
+"#), @r###"
+    fn main() {
+        let mut i = 0;
+        while i < 10 {
+            if i % 2 == 0 {
+                i += 1;
+            } else {
+                i += 2;
+            }
+        }
+        loop {
+            break;
+        }
+    }
+    "###);
+}
+
+#[test]
+fn single_line_blocks_are_kept() {
+    insta::assert_snapshot!(fmt("fn main(a: bool) -> i32 { let b = if a {1} else {  2  }; b }"), @r###"
+    fn main(a: bool) -> i32 {
+        let b = if a { 1 } else { 2 };
+        b
+    }
+    "###);
+}
+
+#[test]
+fn empty_blocks() {
+    insta::assert_snapshot!(fmt("fn main() {\n\n}\nstruct Foo {  }\nimpl Foo {\n}"), @r###"
+    fn main() {}
+    struct Foo {}
+    impl Foo {}
+    "###);
+}
+
+#[test]
+fn struct_fields_get_trailing_comma() {
+    insta::assert_snapshot!(fmt("struct(gc) Foo { a: i32, b: f32 }\nstruct Bar(i32,f32,);"), @r###"
+    struct(gc) Foo {
+        a: i32,
+        b: f32,
+    }
+    struct Bar(i32, f32);
+    "###);
+}
+
+#[test]
+fn multiline_lists() {
+    insta::assert_snapshot!(fmt(r#"
+fn main() {
+    let a = foo(
+    1,
+          2
+    );
+    let b = [
+        1, 2,
+        3];
+    let c = Foo { a: 1, b: 2, };
+}
+"#), @r###"
+    fn main() {
+        let a = foo(
+            1,
+            2,
+        );
+        let b = [
+            1,
+            2,
+            3,
+        ];
+        let c = Foo { a: 1, b: 2 };
+    }
+    "###);
+}
+
+#[test]
+fn single_line_lists_lose_trailing_comma() {
+    insta::assert_snapshot!(fmt("fn main() { foo(1, 2,); let a = [1,2,]; }"), @r###"
+    fn main() {
+        foo(1, 2);
+        let a = [1, 2];
+    }
+    "###);
+}
+
+#[test]
+fn blank_lines() {
+    insta::assert_snapshot!(fmt(r#"
+
+
+fn foo() {
+
+    let a = 1;
+
+
+
+    let b = 2;
+
+}
+
+
+
+fn bar() {}
+fn baz() {}
+"#), @r###"
+    fn foo() {
+        let a = 1;
+
+        let b = 2;
+    }
+
+    fn bar() {}
+    fn baz() {}
+    "###);
+}
+
+#[test]
+fn comments() {
+    insta::assert_snapshot!(fmt(r#"
+// A comment about foo
+/// Documentation of foo
+fn foo() {   // trailing comment
+    let a = 1;    // another trailing comment
+    /* block */ a
+    // comment at the end of a block
+}
+"#), @r###"
+    // A comment about foo
+    /// Documentation of foo
+    fn foo() { // trailing comment
+        let a = 1; // another trailing comment
+        /* block */ a
+        // comment at the end of a block
+    }
+    "###);
+}
+
+#[test]
+fn impl_and_attributes() {
+    insta::assert_snapshot!(fmt(r#"
+#[allow(dead_code)] pub struct Foo { a: i32 }
+impl Foo { pub fn new(a: i32) -> Self { Self { a } } fn get(self) -> i32 { self.a } }
+"#), @r###"
+    #[allow(dead_code)]
+    pub struct Foo {
+        a: i32,
+    }
+    impl Foo {
+        pub fn new(a: i32) -> Self {
+            Self { a }
+        }
+        fn get(self) -> i32 {
+            self.a
+        }
+    }
+    "###);
+}
+
+#[test]
+fn paths_and_ranges() {
+    insta::assert_snapshot!(fmt(r#"
+use package :: foo :: { Bar , baz };
+fn main() { for i in 0 .. 10 { let a = foo::Bar { b: i }; a . b . c ( ) ; } let c = 1 ..= 3; }
+"#), @r###"
+    use package::foo::{Bar, baz};
+    fn main() {
+        for i in 0..10 {
+            let a = foo::Bar { b: i };
+            a.b.c();
+        }
+        let c = 1..=3;
+    }
+    "###);
+}
+
+#[test]
+fn labels_and_patterns() {
+    insta::assert_snapshot!(fmt(r#"
+fn main() {
+    'outer: loop { break 'outer; }
+    let Foo { a, .. } = foo();
+    let Bar(b, _) = bar();
+}
+"#), @r###"
+    fn main() {
+        'outer: loop {
+            break 'outer;
+        }
+        let Foo { a, .. } = foo();
+        let Bar(b, _) = bar();
+    }
+    "###);
+}
+
+#[test]
+fn tuples() {
+    insta::assert_snapshot!(fmt("fn main() { let ( a , b, ) = ( 1,2 ); let (c,) = ( a, ); let d = ( b ); }"), @r#"
+    fn main() {
+        let (a, b) = (1, 2);
+        let (c,) = (a,);
+        let d = (b);
+    }
+    "#);
+}
+
+#[test]
+fn syntax_errors_are_not_formatted() {
+    assert!(format("fn main() { let a = ; }").is_err());
+}
+
+#[test]
+fn check_formatted() {
+    assert_eq!(is_formatted("fn main() {}\n"), Ok(true));
+    assert_eq!(is_formatted("fn main() { }\n"), Ok(false));
+}
@@ -0,0 +1,314 @@
+//! Generates the glue between a host application and the Mun code it runs.
+//!
+//! A [`GlueSchema`] describes the types and functions that a host exposes to
+//! Mun, e.g. generated from the reflection data of a game engine. From a single
+//! schema, this crate generates both sides of the scripting boundary: the Mun
+//! declarations of the structs and `extern` functions, and the Rust code that
+//! registers the host functions with a `mun_runtime::RuntimeBuilder`. Because
+//! both are generated from the same description, they cannot get out of sync.
+//!
+//! The Mun declarations are meant to be added to a compiler driver as a
+//! virtual file, which the `driver` feature enables through
+//! [`GlueSchema::set_virtual_file`].
+
+#[cfg(test)]
+mod tests;
+
+use std::{collections::HashSet, fmt::Write};
+
+use serde_derive::{Deserialize, Serialize};
+
+/// The header of all generated files.
+const HEADER: &str = "// This file is generated by mun_glue_gen, do not edit it manually.\n";
+
+/// The name of the Rust function that registers the host functions.
+pub const INSERT_FN_NAME: &str = "insert_host_functions";
+
+/// The primitive types that can be used in a schema. They are spelled the same
+/// in Mun and Rust.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    "f32", "f64",
+];
+
+/// A description of the types and functions that a host exposes to Mun.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlueSchema {
+    /// The structs that are declared in Mun
+    #[serde(default)]
+    pub structs: Vec<StructSchema>,
+    /// The host functions that Mun code can call
+    #[serde(default)]
+    pub functions: Vec<FunctionSchema>,
+}
+
+/// A description of a struct that is declared in Mun.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StructSchema {
+    /// The name of the struct
+    pub name: String,
+    /// How the struct is stored in memory
+    #[serde(default)]
+    pub memory_kind: MemoryKind,
+    /// The fields of the struct
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+}
+
+/// Describes how a struct is stored in memory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryKind {
+    /// The struct is allocated by the garbage collector and passed by reference
+    #[default]
+    Gc,
+    /// The struct is passed by value
+    Value,
+}
+
+/// A description of a field of a struct or an argument of a function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldSchema {
+    /// The name of the field
+    pub name: String,
+    /// The type of the field, either a primitive type or the name of a struct
+    /// in the schema
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A description of a host function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FunctionSchema {
+    /// The name by which Mun code calls the function
+    pub name: String,
+    /// The namespace in which the function is registered, e.g. `host::math`.
+    /// Without a namespace, the function is registered by its name.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// The arguments of the function, which must be of primitive types
+    #[serde(default)]
+    pub args: Vec<FieldSchema>,
+    /// The return type of the function, which must be a primitive type
+    #[serde(default)]
+    pub return_type: Option<String>,
+    /// The path of the `extern "C"` Rust function that implements the function,
+    /// e.g. `crate::math::add`
+    pub rust_path: String,
+}
+
+/// An error that makes it impossible to generate glue for a schema.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum GlueError {
+    #[error("'{0}' is not a valid identifier")]
+    InvalidIdentifier(String),
+    #[error("'{0}' is declared more than once")]
+    DuplicateName(String),
+    #[error("unknown type '{ty}' in '{item}'")]
+    UnknownType { item: String, ty: String },
+    #[error("the host function '{function}' uses '{ty}', but host functions only support primitive types")]
+    UnsupportedFunctionType { function: String, ty: String },
+}
+
+impl GlueSchema {
+    /// Validates that the schema only refers to known types and that all names
+    /// are valid and unique.
+    pub fn validate(&self) -> Result<(), GlueError> {
+        let mut struct_names = HashSet::new();
+        for strukt in &self.structs {
+            ensure_identifier(&strukt.name)?;
+            if PRIMITIVE_TYPES.contains(&strukt.name.as_str())
+                || !struct_names.insert(strukt.name.as_str())
+            {
+                return Err(GlueError::DuplicateName(strukt.name.clone()));
+            }
+        }
+
+        for strukt in &self.structs {
+            ensure_unique_fields(&strukt.fields)?;
+            for field in &strukt.fields {
+                if !PRIMITIVE_TYPES.contains(&field.ty.as_str())
+                    && !struct_names.contains(field.ty.as_str())
+                {
+                    return Err(GlueError::UnknownType {
+                        item: strukt.name.clone(),
+                        ty: field.ty.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut function_names = HashSet::new();
+        for function in &self.functions {
+            ensure_identifier(&function.name)?;
+            if let Some(namespace) = &function.namespace {
+                namespace.split("::").try_for_each(ensure_identifier)?;
+            }
+            if !function_names.insert((function.namespace.as_deref(), function.name.as_str())) {
+                return Err(GlueError::DuplicateName(function.link_name()));
+            }
+
+            ensure_unique_fields(&function.args)?;
+            for ty in function
+                .args
+                .iter()
+                .map(|arg| &arg.ty)
+                .chain(&function.return_type)
+            {
+                if !PRIMITIVE_TYPES.contains(&ty.as_str()) {
+                    return Err(GlueError::UnsupportedFunctionType {
+                        function: function.name.clone(),
+                        ty: ty.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates the Mun declarations of the structs and host functions.
+    ///
+    /// Host functions without a namespace are linked by their name, so the
+    /// declarations must be placed in the root module of a package.
+    pub fn mun_source(&self) -> Result<String, GlueError> {
+        self.validate()?;
+
+        let mut source = String::from(HEADER);
+        for strukt in &self.structs {
+            let memory_kind = match strukt.memory_kind {
+                MemoryKind::Gc => "",
+                MemoryKind::Value => "(value)",
+            };
+            write!(source, "\npub struct{memory_kind} {} {{", strukt.name).unwrap();
+            if strukt.fields.is_empty() {
+                source.push_str("}\n");
+            } else {
+                source.push('\n');
+                for field in &strukt.fields {
+                    writeln!(source, "    {}: {},", field.name, field.ty).unwrap();
+                }
+                source.push_str("}\n");
+            }
+        }
+
+        for function in &self.functions {
+            source.push('\n');
+            if let Some(namespace) = &function.namespace {
+                writeln!(source, "#[link_namespace = \"{namespace}\"]").unwrap();
+            }
+            let args = function
+                .args
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name, arg.ty))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(source, "extern fn {}({args})", function.name).unwrap();
+            if let Some(return_type) = &function.return_type {
+                write!(source, " -> {return_type}").unwrap();
+            }
+            source.push_str(";\n");
+        }
+
+        Ok(source)
+    }
+
+    /// Generates a Rust function, called [`INSERT_FN_NAME`], that registers
+    /// the host functions with a `mun_runtime::RuntimeBuilder`.
+    pub fn rust_source(&self) -> Result<String, GlueError> {
+        self.validate()?;
+
+        let mut source = String::from(HEADER);
+        writeln!(
+            source,
+            "\n/// Registers the host functions that are declared in the generated Mun glue.\n\
+             pub fn {INSERT_FN_NAME}(\n    \
+                 builder: mun_runtime::RuntimeBuilder,\n\
+             ) -> mun_runtime::RuntimeBuilder {{\n    \
+                 builder"
+        )
+        .unwrap();
+        for function in &self.functions {
+            let arg_types = function
+                .args
+                .iter()
+                .map(|arg| arg.ty.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let return_type = function.return_type.as_deref().unwrap_or("()");
+            let fn_ptr = format!(
+                "{} as extern \"C\" fn({arg_types}) -> {return_type}",
+                function.rust_path
+            );
+            match &function.namespace {
+                Some(namespace) => writeln!(
+                    source,
+                    "        .insert_fn_in(\"{namespace}\", \"{}\", {fn_ptr})",
+                    function.name
+                ),
+                None => writeln!(
+                    source,
+                    "        .insert_fn(\"{}\", {fn_ptr})",
+                    function.name
+                ),
+            }
+            .unwrap();
+        }
+        source.push_str("}\n");
+
+        Ok(source)
+    }
+
+    /// Adds the Mun declarations of the schema to `driver` as the virtual file
+    /// at `path`, replacing the previous declarations at that path. Returns the
+    /// id of the file.
+    #[cfg(feature = "driver")]
+    pub fn set_virtual_file(
+        &self,
+        driver: &mut mun_compiler::Driver,
+        path: impl AsRef<mun_compiler::RelativePath>,
+    ) -> anyhow::Result<mun_compiler::FileId> {
+        driver.set_virtual_file(path, self.mun_source()?)
+    }
+}
+
+impl FunctionSchema {
+    /// Returns the name by which the function is registered with the runtime.
+    pub fn link_name(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{namespace}::{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Returns an error if `name` is not a valid identifier in both Mun and Rust.
+fn ensure_identifier(name: &str) -> Result<(), GlueError> {
+    let mut chars = name.chars();
+    let is_valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_";
+    if is_valid {
+        Ok(())
+    } else {
+        Err(GlueError::InvalidIdentifier(name.to_owned()))
+    }
+}
+
+/// Returns an error if any of the `fields` has an invalid or duplicate name.
+fn ensure_unique_fields(fields: &[FieldSchema]) -> Result<(), GlueError> {
+    let mut names = HashSet::new();
+    for field in fields {
+        ensure_identifier(&field.name)?;
+        if !names.insert(field.name.as_str()) {
+            return Err(GlueError::DuplicateName(field.name.clone()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::{GlueError, GlueSchema};
+
+/// Parses a schema from JSON.
+fn schema(json: &str) -> GlueSchema {
+    serde_json::from_str(json).expect("invalid schema")
+}
+
+const SCHEMA: &str = r#"{
+    "structs": [
+        { "name": "Vec2", "memory_kind": "value", "fields": [
+            { "name": "x", "type": "f32" },
+            { "name": "y", "type": "f32" }
+        ] },
+        { "name": "Entity", "fields": [
+            { "name": "id", "type": "u32" },
+            { "name": "position", "type": "Vec2" }
+        ] },
+        { "name": "Marker" }
+    ],
+    "functions": [
+        { "name": "add", "namespace": "host::math", "args": [
+            { "name": "a", "type": "i32" },
+            { "name": "b", "type": "i32" }
+        ], "return_type": "i32", "rust_path": "crate::math::add" },
+        { "name": "log_frame", "args": [{ "name": "frame", "type": "u64" }], "rust_path": "log_frame" }
+    ]
+}"#;
+
+#[test]
+fn mun_source() {
+    let source = schema(SCHEMA).mun_source().unwrap();
+    assert!(mun_fmt::is_formatted(&source).unwrap(), "{source}");
+    insta::assert_snapshot!(source, @r###"
+    // This file is generated by mun_glue_gen, do not edit it manually.
+
+    pub struct(value) Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    pub struct Entity {
+        id: u32,
+        position: Vec2,
+    }
+
+    pub struct Marker {}
+
+    #[link_namespace = "host::math"]
+    extern fn add(a: i32, b: i32) -> i32;
+
+    extern fn log_frame(frame: u64);
+    "###);
+}
+
+#[test]
+fn rust_source() {
+    insta::assert_snapshot!(schema(SCHEMA).rust_source().unwrap(), @r###"
+    // This file is generated by mun_glue_gen, do not edit it manually.
+
+    /// Registers the host functions that are declared in the generated Mun glue.
+    pub fn insert_host_functions(
+        builder: mun_runtime::RuntimeBuilder,
+    ) -> mun_runtime::RuntimeBuilder {
+        builder
+            .insert_fn_in("host::math", "add", crate::math::add as extern "C" fn(i32, i32) -> i32)
+            .insert_fn("log_frame", log_frame as extern "C" fn(u64) -> ())
+    }
+    "###);
+}
+
+#[test]
+fn invalid_schemas() {
+    assert_eq!(
+        schema(r#"{ "structs": [{ "name": "Foo", "fields": [{ "name": "a", "type": "Bar" }] }] }"#)
+            .validate(),
+        Err(GlueError::UnknownType {
+            item: String::from("Foo"),
+            ty: String::from("Bar")
+        })
+    );
+    assert_eq!(
+        schema(r#"{ "structs": [{ "name": "Foo" }, { "name": "Foo" }] }"#).validate(),
+        Err(GlueError::DuplicateName(String::from("Foo")))
+    );
+    assert_eq!(
+        schema(r#"{ "functions": [{ "name": "foo bar", "rust_path": "foo" }] }"#).validate(),
+        Err(GlueError::InvalidIdentifier(String::from("foo bar")))
+    );
+    assert_eq!(
+        schema(
+            r#"{
+                "structs": [{ "name": "Foo" }],
+                "functions": [{ "name": "foo", "args": [{ "name": "a", "type": "Foo" }], "rust_path": "foo" }]
+            }"#
+        )
+        .validate(),
+        Err(GlueError::UnsupportedFunctionType {
+            function: String::from("foo"),
+            ty: String::from("Foo")
+        })
+    );
+}
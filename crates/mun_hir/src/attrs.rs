@@ -0,0 +1,380 @@
+//! Attributes are annotations on items, e.g. `#[inline]` or
+//! `#[export_name = "foo"]`, that modify how an item is compiled. The doc
+//! comments of an item are stored alongside its attributes.
+
+use std::{ops::Deref, sync::Arc};
+
+use mun_syntax::{ast, AstNode, SmolStr};
+
+use crate::{
+    cfg::{CfgExpr, CfgOptions},
+    lint::{Lint, LintLevel},
+    name,
+    name::AsName,
+    Name,
+};
+
+/// A single attribute attached to an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attr {
+    /// The name of the attribute, e.g. `inline` in `#[inline(always)]`
+    pub name: Name,
+
+    /// The input of the attribute, e.g. `always` in `#[inline(always)]`
+    pub input: Option<AttrInput>,
+}
+
+/// The input of an attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrInput {
+    /// The value of a string literal, e.g. `foo` in `#[export_name = "foo"]`
+    String(SmolStr),
+
+    /// The text of any other literal, e.g. `5` in `#[foo = 5]`
+    Literal(SmolStr),
+
+    /// The text between the parentheses of a token tree, e.g. `always` in
+    /// `#[inline(always)]`
+    TokenTree(SmolStr),
+}
+
+/// The inlining hint of a function, specified with the `#[inline]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineAttr {
+    /// `#[inline]`: the function should be inlined if possible.
+    Hint,
+
+    /// `#[inline(always)]`: the function should always be inlined.
+    Always,
+
+    /// `#[inline(never)]`: the function should never be inlined.
+    Never,
+}
+
+/// The deprecation of an item, specified with the `#[deprecated]` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    /// An optional note that describes why the item is deprecated or what to
+    /// use instead, e.g. `#[deprecated = "use `bar` instead"]`
+    pub note: Option<SmolStr>,
+}
+
+/// The largest alignment, in bytes, that can be requested with the
+/// `#[align(N)]` attribute.
+pub const MAX_ALIGNMENT: u32 = 1 << 12;
+
+/// The kind of item that an attribute is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttrTarget {
+    /// A function
+    Function,
+
+    /// A struct with the specified memory kind
+    Struct(ast::StructMemoryKind),
+
+    /// Any other item
+    Other,
+}
+
+/// All attributes and the documentation attached to an item.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attrs {
+    entries: Option<Arc<[Attr]>>,
+    docs: Option<Arc<str>>,
+}
+
+impl Attrs {
+    /// An empty set of attributes
+    pub(crate) const EMPTY: Attrs = Attrs {
+        entries: None,
+        docs: None,
+    };
+
+    /// Lowers all attributes and doc comments of the specified `owner`.
+    pub(crate) fn from_ast(owner: &(impl ast::AttrsOwner + ast::DocCommentsOwner)) -> Self {
+        let entries: Arc<[Attr]> = owner.attrs().map(|attr| lower_attr(&attr)).collect();
+        Attrs {
+            entries: (!entries.is_empty()).then_some(entries),
+            docs: owner.doc_comment_text().map(Into::into),
+        }
+    }
+
+    /// Returns `true` if there are neither attributes nor doc comments.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_none() && self.docs.is_none()
+    }
+
+    /// Returns the text of the doc comments, without the comment markers.
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// Returns an iterator over all attributes with the specified name.
+    pub fn by_name(&self, name: Name) -> impl Iterator<Item = &Attr> + '_ {
+        self.iter().filter(move |attr| attr.name == name)
+    }
+
+    /// Returns the inlining hint specified with the `#[inline]` attribute.
+    pub fn inline(&self) -> Option<InlineAttr> {
+        self.by_name(name![inline])
+            .find_map(|attr| match &attr.input {
+                None => Some(InlineAttr::Hint),
+                Some(AttrInput::TokenTree(input)) if input == "always" => Some(InlineAttr::Always),
+                Some(AttrInput::TokenTree(input)) if input == "never" => Some(InlineAttr::Never),
+                _ => None,
+            })
+    }
+
+    /// Returns the symbol name specified with the `#[export_name]` attribute.
+    pub fn export_name(&self) -> Option<&str> {
+        self.by_name(name![export_name])
+            .find_map(|attr| match &attr.input {
+                Some(AttrInput::String(name)) if !name.is_empty() => Some(name.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Returns the namespace specified with the `#[link_namespace]` attribute,
+    /// e.g. `host::math` in `#[link_namespace = "host::math"]`.
+    pub fn link_namespace(&self) -> Option<&str> {
+        self.by_name(name![link_namespace])
+            .find_map(|attr| match &attr.input {
+                Some(AttrInput::String(namespace)) if is_namespace(namespace) => {
+                    Some(namespace.as_str())
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the minimum alignment in bytes specified with the `#[align(N)]`
+    /// attribute.
+    pub fn align(&self) -> Option<u32> {
+        self.by_name(name![align]).find_map(Attr::alignment)
+    }
+
+    /// Returns `true` if the item is marked with the `#[test]` attribute.
+    pub fn is_test(&self) -> bool {
+        self.by_name(name![test]).next().is_some()
+    }
+
+    /// Returns `true` if the predicates of all `#[cfg(...)]` attributes hold
+    /// with the specified options. Malformed predicates are ignored, they are
+    /// reported when validating the attributes.
+    pub fn is_cfg_enabled(&self, options: &CfgOptions) -> bool {
+        self.by_name(name![cfg])
+            .filter_map(Attr::cfg)
+            .all(|expr| options.check(&expr))
+    }
+
+    /// Returns the level of the specified lint that is set with an
+    /// `#[allow(...)]`, `#[warn(...)]` or `#[deny(...)]` attribute. If multiple
+    /// attributes mention the lint, the last one takes precedence.
+    pub fn lint_level(&self, lint: Lint) -> Option<LintLevel> {
+        self.iter()
+            .filter_map(|attr| {
+                let level = LintLevel::from_attr_name(&attr.name)?;
+                attr.lint_names()?
+                    .any(|name| name == lint.name())
+                    .then_some(level)
+            })
+            .last()
+    }
+
+    /// Returns the deprecation specified with the `#[deprecated]` attribute.
+    pub fn deprecated(&self) -> Option<Deprecation> {
+        self.by_name(name![deprecated])
+            .find_map(|attr| match &attr.input {
+                None => Some(Deprecation { note: None }),
+                Some(AttrInput::String(note)) => Some(Deprecation {
+                    note: Some(note.clone()),
+                }),
+                _ => None,
+            })
+    }
+}
+
+impl Deref for Attrs {
+    type Target = [Attr];
+
+    fn deref(&self) -> &Self::Target {
+        self.entries.as_deref().unwrap_or(&[])
+    }
+}
+
+/// Lowers a single attribute
+fn lower_attr(attr: &ast::Attr) -> Attr {
+    let name = match attr.path() {
+        Some(path) => match (path.qualifier(), path.segment().and_then(|s| s.name_ref())) {
+            (None, Some(name_ref)) => name_ref.as_name(),
+            _ => Name::new(path.syntax().text().to_string()),
+        },
+        None => Name::missing(),
+    };
+
+    let input = if let Some(literal) = attr.literal() {
+        Some(match literal.kind() {
+            ast::LiteralKind::String(string) => AttrInput::String(string.value().into()),
+            _ => AttrInput::Literal(literal.syntax().text().to_string().into()),
+        })
+    } else {
+        attr.token_tree()
+            .map(|token_tree| AttrInput::TokenTree(token_tree.inner_text().into()))
+    };
+
+    Attr { name, input }
+}
+
+/// An error in the usage of an attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrError {
+    /// The attribute is not known to the compiler
+    Unknown,
+
+    /// The input of the attribute is invalid, `expected` describes the valid
+    /// forms.
+    Malformed { expected: &'static str },
+
+    /// The attribute is applied to an item that it doesn't support
+    NotOnFunction,
+
+    /// The attribute is applied to an item other than a `gc` struct
+    NotOnGcStruct,
+
+    /// A lint attribute, e.g. `#[allow(...)]`, refers to a lint that doesn't
+    /// exist
+    UnknownLint { name: SmolStr },
+}
+
+impl Attr {
+    /// Returns the predicate of a `#[cfg(...)]` attribute, or `None` if the
+    /// attribute is malformed.
+    fn cfg(&self) -> Option<CfgExpr> {
+        match &self.input {
+            Some(AttrInput::TokenTree(input)) => CfgExpr::parse(input),
+            _ => None,
+        }
+    }
+
+    /// Returns the alignment of an `#[align(N)]` attribute, or `None` if the
+    /// attribute is malformed.
+    fn alignment(&self) -> Option<u32> {
+        match &self.input {
+            Some(AttrInput::TokenTree(input)) => input
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|align| align.is_power_of_two() && *align <= MAX_ALIGNMENT),
+            _ => None,
+        }
+    }
+
+    /// Returns the names of the lints of an `#[allow(...)]`, `#[warn(...)]` or
+    /// `#[deny(...)]` attribute, or `None` if the attribute is malformed.
+    fn lint_names(&self) -> Option<impl Iterator<Item = &str>> {
+        match &self.input {
+            Some(AttrInput::TokenTree(input)) => {
+                let names = input.split(',').map(str::trim);
+                if names.clone().any(|name| !is_identifier(name)) {
+                    return None;
+                }
+                Some(names)
+            }
+            _ => None,
+        }
+    }
+
+    /// Validates the attribute, `target` indicates the kind of item that the
+    /// attribute is attached to.
+    pub(crate) fn validate(&self, target: AttrTarget) -> Result<(), AttrError> {
+        // `#[cfg(...)]` is supported on all items
+        if self.name == name![cfg] {
+            return match self.cfg() {
+                Some(_) => Ok(()),
+                None => Err(AttrError::Malformed {
+                    expected: "`#[cfg(feature = \"name\")]`, optionally combined with `all(...)`, `any(...)` or `not(...)`",
+                }),
+            };
+        }
+
+        // Lint attributes are supported on all items
+        if LintLevel::from_attr_name(&self.name).is_some() {
+            let Some(mut names) = self.lint_names() else {
+                return Err(AttrError::Malformed {
+                    expected: "a comma separated list of lints, e.g. `#[allow(unused_variables)]`",
+                });
+            };
+            return match names.find(|name| Lint::from_name(name).is_none()) {
+                Some(name) => Err(AttrError::UnknownLint { name: name.into() }),
+                None => Ok(()),
+            };
+        }
+
+        // `#[align(N)]` is only supported on `gc` structs, whose memory is
+        // allocated by the runtime. Value structs are stored inline and take
+        // the alignment of their fields.
+        if self.name == name![align] {
+            return if self.alignment().is_none() {
+                Err(AttrError::Malformed {
+                    expected: "`#[align(N)]`, where `N` is a power of two no larger than 4096",
+                })
+            } else if target != AttrTarget::Struct(ast::StructMemoryKind::Gc) {
+                Err(AttrError::NotOnGcStruct)
+            } else {
+                Ok(())
+            };
+        }
+
+        let expected = if self.name == name![inline] {
+            match &self.input {
+                None => None,
+                Some(AttrInput::TokenTree(input)) if input == "always" || input == "never" => None,
+                _ => Some("`#[inline]`, `#[inline(always)]` or `#[inline(never)]`"),
+            }
+        } else if self.name == name![export_name] {
+            match &self.input {
+                Some(AttrInput::String(name)) if !name.is_empty() => None,
+                _ => Some("`#[export_name = \"name\"]`"),
+            }
+        } else if self.name == name![link_namespace] {
+            match &self.input {
+                Some(AttrInput::String(namespace)) if is_namespace(namespace) => None,
+                _ => Some("`#[link_namespace = \"path::to::namespace\"]`"),
+            }
+        } else if self.name == name![deprecated] {
+            match &self.input {
+                None | Some(AttrInput::String(_)) => None,
+                _ => Some("`#[deprecated]` or `#[deprecated = \"note\"]`"),
+            }
+        } else if self.name == name![test] {
+            match &self.input {
+                None => None,
+                _ => Some("`#[test]`"),
+            }
+        } else {
+            return Err(AttrError::Unknown);
+        };
+
+        if let Some(expected) = expected {
+            Err(AttrError::Malformed { expected })
+        } else if target != AttrTarget::Function {
+            Err(AttrError::NotOnFunction)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns `true` if `namespace` is a path of identifiers separated by `::`,
+/// e.g. `host::math`.
+fn is_namespace(namespace: &str) -> bool {
+    namespace.split("::").all(is_identifier)
+}
+
+/// Returns `true` if `name` is a valid identifier, e.g. the name of a lint.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
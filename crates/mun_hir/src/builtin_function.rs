@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::name::{name, Name};
+
+/// A function that is built into the language. Builtin functions are visible
+/// from every module, but can be shadowed by items with the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinFunction {
+    /// `panic("message")` stops the execution of the current invocation and
+    /// reports the message to the host.
+    Panic,
+
+    /// `assert(condition)` or `assert(condition, "message")` panics if the
+    /// condition does not hold.
+    Assert,
+
+    /// `sin(x)` returns the sine of a floating point number (in radians).
+    Sin,
+
+    /// `cos(x)` returns the cosine of a floating point number (in radians).
+    Cos,
+
+    /// `sqrt(x)` returns the square root of a floating point number.
+    Sqrt,
+
+    /// `abs(x)` returns the absolute value of a signed integer or floating
+    /// point number.
+    Abs,
+
+    /// `min(a, b)` returns the smallest of two numbers of the same type.
+    Min,
+
+    /// `max(a, b)` returns the largest of two numbers of the same type.
+    Max,
+}
+
+impl BuiltinFunction {
+    pub const ALL: &'static [(Name, BuiltinFunction)] = &[
+        (name![panic], BuiltinFunction::Panic),
+        (name![assert], BuiltinFunction::Assert),
+        (name![sin], BuiltinFunction::Sin),
+        (name![cos], BuiltinFunction::Cos),
+        (name![sqrt], BuiltinFunction::Sqrt),
+        (name![abs], BuiltinFunction::Abs),
+        (name![min], BuiltinFunction::Min),
+        (name![max], BuiltinFunction::Max),
+    ];
+
+    /// Returns true if this is one of the math functions, which operate on
+    /// numbers and return a number of the same type.
+    pub fn is_math(self) -> bool {
+        matches!(
+            self,
+            BuiltinFunction::Sin
+                | BuiltinFunction::Cos
+                | BuiltinFunction::Sqrt
+                | BuiltinFunction::Abs
+                | BuiltinFunction::Min
+                | BuiltinFunction::Max
+        )
+    }
+}
+
+impl fmt::Display for BuiltinFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BuiltinFunction::Panic => "panic",
+            BuiltinFunction::Assert => "assert",
+            BuiltinFunction::Sin => "sin",
+            BuiltinFunction::Cos => "cos",
+            BuiltinFunction::Sqrt => "sqrt",
+            BuiltinFunction::Abs => "abs",
+            BuiltinFunction::Min => "min",
+            BuiltinFunction::Max => "max",
+        })
+    }
+}
@@ -0,0 +1,193 @@
+//! Conditional compilation with the `#[cfg(...)]` attribute. Items whose
+//! predicate evaluates to `false` with the current [`CfgOptions`] are excluded
+//! from compilation.
+
+use std::{iter::Peekable, str::Chars};
+
+use mun_syntax::SmolStr;
+use rustc_hash::FxHashSet;
+
+/// The options against which `#[cfg(...)]` predicates are evaluated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgOptions {
+    features: FxHashSet<SmolStr>,
+}
+
+impl CfgOptions {
+    /// Constructs `CfgOptions` in which the specified features are enabled.
+    pub fn with_features<I: IntoIterator<Item = T>, T: Into<SmolStr>>(features: I) -> Self {
+        CfgOptions {
+            features: features.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns `true` if the feature with the specified name is enabled.
+    pub fn is_feature_enabled(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Returns `true` if the specified predicate holds.
+    pub fn check(&self, expr: &CfgExpr) -> bool {
+        match expr {
+            CfgExpr::Feature(feature) => self.is_feature_enabled(feature),
+            CfgExpr::All(exprs) => exprs.iter().all(|expr| self.check(expr)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|expr| self.check(expr)),
+            CfgExpr::Not(expr) => !self.check(expr),
+        }
+    }
+}
+
+/// A predicate of a `#[cfg(...)]` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    /// `feature = "name"`: holds if the feature is enabled
+    Feature(SmolStr),
+
+    /// `all(...)`: holds if all predicates hold
+    All(Vec<CfgExpr>),
+
+    /// `any(...)`: holds if any of the predicates holds
+    Any(Vec<CfgExpr>),
+
+    /// `not(...)`: holds if the predicate doesn't hold
+    Not(Box<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parses a predicate from the text between the parentheses of a
+    /// `#[cfg(...)]` attribute. Returns `None` if the predicate is malformed.
+    pub fn parse(text: &str) -> Option<CfgExpr> {
+        let mut tokens = tokenize(text)?.into_iter().peekable();
+        let expr = parse_expr(&mut tokens)?;
+        tokens.next().is_none().then_some(expr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(SmolStr),
+    String(SmolStr),
+    Eq,
+    Comma,
+    LParen,
+    RParen,
+}
+
+/// Splits the text of a predicate into tokens. Returns `None` if the text
+/// contains an invalid token.
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '=' => Token::Eq,
+            ',' => Token::Comma,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '"' => Token::String(tokenize_string(&mut chars)?),
+            c if c == '_' || c.is_alphabetic() => {
+                let mut ident = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c != '_' && !c.is_alphanumeric() {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                Token::Ident(ident.into())
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// Reads the remainder of a string literal of which the opening quote has
+/// already been consumed.
+fn tokenize_string(chars: &mut Peekable<Chars<'_>>) -> Option<SmolStr> {
+    let mut string = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(string.into()),
+            '\\' => string.push(chars.next()?),
+            c => string.push(c),
+        }
+    }
+}
+
+fn parse_expr(tokens: &mut Peekable<impl Iterator<Item = Token>>) -> Option<CfgExpr> {
+    let Some(Token::Ident(name)) = tokens.next() else {
+        return None;
+    };
+
+    match tokens.next()? {
+        Token::Eq if name == "feature" => match tokens.next()? {
+            Token::String(feature) => Some(CfgExpr::Feature(feature)),
+            _ => None,
+        },
+        Token::LParen => {
+            let mut exprs = Vec::new();
+            while tokens.next_if_eq(&Token::RParen).is_none() {
+                exprs.push(parse_expr(tokens)?);
+                if tokens.next_if_eq(&Token::Comma).is_none() {
+                    tokens.next_if_eq(&Token::RParen)?;
+                    break;
+                }
+            }
+
+            match name.as_str() {
+                "all" => Some(CfgExpr::All(exprs)),
+                "any" => Some(CfgExpr::Any(exprs)),
+                "not" if exprs.len() == 1 => Some(CfgExpr::Not(Box::new(exprs.pop()?))),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CfgExpr, CfgOptions};
+
+    fn feature(name: &str) -> CfgExpr {
+        CfgExpr::Feature(name.into())
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            CfgExpr::parse(r#"feature = "debug_draw""#),
+            Some(feature("debug_draw"))
+        );
+        assert_eq!(
+            CfgExpr::parse(r#"all(feature = "a", not(feature = "b"),)"#),
+            Some(CfgExpr::All(vec![
+                feature("a"),
+                CfgExpr::Not(Box::new(feature("b")))
+            ]))
+        );
+        assert_eq!(CfgExpr::parse("any()"), Some(CfgExpr::Any(Vec::new())));
+
+        assert_eq!(CfgExpr::parse(""), None);
+        assert_eq!(CfgExpr::parse("feature"), None);
+        assert_eq!(CfgExpr::parse(r#"target = "x86""#), None);
+        assert_eq!(CfgExpr::parse(r#"not(feature = "a", feature = "b")"#), None);
+        assert_eq!(CfgExpr::parse(r#"all(feature = "a""#), None);
+        assert_eq!(CfgExpr::parse(r#"feature = "a" feature = "b""#), None);
+    }
+
+    #[test]
+    fn check() {
+        let options = CfgOptions::with_features(["a"]);
+        assert!(options.check(&feature("a")));
+        assert!(!options.check(&feature("b")));
+        assert!(options.check(&CfgExpr::Any(vec![feature("a"), feature("b")])));
+        assert!(!options.check(&CfgExpr::All(vec![feature("a"), feature("b")])));
+        assert!(options.check(&CfgExpr::Not(Box::new(feature("b")))));
+        assert!(options.check(&CfgExpr::All(Vec::new())));
+        assert!(!options.check(&CfgExpr::Any(Vec::new())));
+    }
+}
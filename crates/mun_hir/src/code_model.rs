@@ -1,41 +1,71 @@
+mod r#const;
 mod function;
 mod r#impl;
 mod module;
 mod package;
 pub(crate) mod src;
+mod r#static;
 pub(crate) mod r#struct;
 mod type_alias;
 
 use std::sync::Arc;
 
+use mun_hir_input::FileId;
+
 pub use self::{
-    function::{Function, FunctionData},
+    function::{Function, FunctionData, LifecycleHook},
     module::{Module, ModuleDef},
     package::Package,
+    r#const::{Const, ConstData},
     r#impl::{AssocItem, ImplData},
+    r#static::{Static, StaticData},
     r#struct::{Field, Struct, StructData, StructKind, StructMemoryKind},
     src::HasSource,
     type_alias::{TypeAlias, TypeAliasData},
 };
-use crate::{expr::BodySourceMap, HirDatabase, Name};
+use crate::{expr::BodySourceMap, ids::DefWithBodyId, HirDatabase, Name};
 
 /// The definitions that have a body.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBody {
     Function(Function),
+    Const(Const),
+    Static(Static),
 }
-impl_froms!(DefWithBody: Function);
+impl_froms!(DefWithBody: Function, Const, Static);
 
 impl DefWithBody {
     pub fn module(self, db: &dyn HirDatabase) -> Module {
         match self {
             DefWithBody::Function(f) => f.module(db),
+            DefWithBody::Const(c) => c.module(db),
+            DefWithBody::Static(s) => s.module(db),
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        match self {
+            DefWithBody::Function(f) => f.file_id(db),
+            DefWithBody::Const(c) => c.file_id(db),
+            DefWithBody::Static(s) => s.file_id(db),
         }
     }
 
     pub fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         match self {
             DefWithBody::Function(f) => f.body_source_map(db),
+            DefWithBody::Const(c) => c.body_source_map(db),
+            DefWithBody::Static(s) => s.body_source_map(db),
+        }
+    }
+}
+
+impl From<DefWithBody> for DefWithBodyId {
+    fn from(def: DefWithBody) -> Self {
+        match def {
+            DefWithBody::Function(f) => f.id.into(),
+            DefWithBody::Const(c) => c.id.into(),
+            DefWithBody::Static(s) => s.id.into(),
         }
     }
 }
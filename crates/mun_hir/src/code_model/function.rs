@@ -1,20 +1,22 @@
 use std::{iter::once, sync::Arc};
 
 use mun_hir_input::FileId;
-use mun_syntax::{ast, ast::TypeAscriptionOwner};
+use mun_syntax::{ast, ast::TypeAscriptionOwner, TextRange};
 
 use super::Module;
 use crate::{
+    attrs::Attrs,
     expr::{validator::ExprValidator, BodySourceMap},
     has_module::HasModule,
-    ids::{FunctionId, Lookup},
+    ids::{FunctionId, ItemContainerId, Lookup},
     item_tree::FunctionFlags,
+    name,
     name_resolution::Namespace,
     resolve::HasResolver,
     type_ref::{LocalTypeRefId, TypeRefMap, TypeRefSourceMap},
     visibility::RawVisibility,
-    Body, DefDatabase, DiagnosticSink, HasSource, HasVisibility, HirDatabase, InFile,
-    InferenceResult, Name, Pat, Ty, Visibility,
+    Body, DefDatabase, DiagnosticSink, ExprId, HasSource, HasVisibility, HirDatabase, InFile,
+    InferenceResult, Name, Pat, PatId, Ty, Visibility,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -28,6 +30,16 @@ impl From<FunctionId> for Function {
     }
 }
 
+/// A well-known function that the runtime invokes when the assembly that
+/// defines it is loaded or unloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleHook {
+    /// `fn init()`, invoked after the assembly has been loaded and linked.
+    Init,
+    /// `fn shutdown()`, invoked before the assembly is unloaded.
+    Shutdown,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct FunctionData {
     name: Name,
@@ -37,6 +49,7 @@ pub struct FunctionData {
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     flags: FunctionFlags,
+    attrs: Attrs,
 }
 
 impl FunctionData {
@@ -72,6 +85,7 @@ impl FunctionData {
             type_ref_source_map,
             flags: func.flags,
             visibility: item_tree[func.visibility].clone(),
+            attrs: item_tree.attrs(loc.id.value.into()).clone(),
         })
     }
 
@@ -104,6 +118,11 @@ impl FunctionData {
         self.flags.is_extern()
     }
 
+    /// Returns the attributes attached to this function.
+    pub fn attrs(&self) -> &Attrs {
+        &self.attrs
+    }
+
     /// Returns true if the first param is `self`. This is relevant to decide
     /// whether this can be called as a method as opposed to an associated
     /// function.
@@ -139,6 +158,26 @@ impl Function {
         .collect()
     }
 
+    /// Returns the name of the function in the ABI of an assembly, by which
+    /// `extern` functions are resolved at runtime. This is the name specified
+    /// with the `#[export_name]` attribute, the name of the function in the
+    /// namespace specified with the `#[link_namespace]` attribute or, if both
+    /// are absent, the full name of the function.
+    pub fn export_name(self, db: &dyn HirDatabase) -> String {
+        let data = self.data(db.upcast());
+        match (data.attrs().export_name(), data.attrs().link_namespace()) {
+            (Some(name), _) => name.to_owned(),
+            (None, Some(namespace)) => format!("{namespace}::{}", self.name(db)),
+            (None, None) => self.full_name(db),
+        }
+    }
+
+    /// Returns the documentation of the function, which is specified with doc
+    /// comments.
+    pub fn docs(self, db: &dyn HirDatabase) -> Option<String> {
+        self.data(db.upcast()).attrs().docs().map(ToOwned::to_owned)
+    }
+
     pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
         self.id.lookup(db.upcast()).id.file_id
     }
@@ -187,17 +226,72 @@ impl Function {
         db.fn_data(self.id).flags.is_extern()
     }
 
+    /// Returns the lifecycle hook this function implements, if any. A lifecycle
+    /// hook is a non-extern function called `init` or `shutdown` that is
+    /// declared at module level, takes no arguments, and returns nothing.
+    pub fn lifecycle_hook(self, db: &dyn HirDatabase) -> Option<LifecycleHook> {
+        if !matches!(
+            self.id.lookup(db.upcast()).container,
+            ItemContainerId::ModuleId(_)
+        ) {
+            return None;
+        }
+
+        let data = self.data(db.upcast());
+        let hook = match data.name() {
+            name if *name == name![init] => LifecycleHook::Init,
+            name if *name == name![shutdown] => LifecycleHook::Shutdown,
+            _ => return None,
+        };
+
+        (!data.is_extern() && data.params().is_empty() && self.ret_type(db).is_empty())
+            .then_some(hook)
+    }
+
+    /// Returns `true` if the function is a test: a function declared at module
+    /// level that is marked with the `#[test]` attribute. Test functions are
+    /// invoked by `mun test`.
+    pub fn is_test(self, db: &dyn HirDatabase) -> bool {
+        matches!(
+            self.id.lookup(db.upcast()).container,
+            ItemContainerId::ModuleId(_)
+        ) && self.data(db.upcast()).attrs().is_test()
+    }
+
     pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
 
+    /// Returns the range in the source file of the specified expression of the
+    /// body of this function.
+    pub fn expr_text_range(self, db: &dyn HirDatabase, expr: ExprId) -> Option<TextRange> {
+        self.body_source_map(db).expr_syntax(expr).map(|source| {
+            source.value.either(
+                |ptr| ptr.syntax_node_ptr().range(),
+                |ptr| ptr.syntax_node_ptr().range(),
+            )
+        })
+    }
+
+    /// Returns the range in the source file of the specified pattern of the
+    /// body of this function.
+    pub fn pat_text_range(self, db: &dyn HirDatabase, pat: PatId) -> Option<TextRange> {
+        self.body_source_map(db).pat_syntax(pat).map(|source| {
+            source.value.either(
+                |ptr| ptr.syntax_node_ptr().range(),
+                |ptr| ptr.syntax_node_ptr().range(),
+            )
+        })
+    }
+
     pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
         let body = self.body(db);
         body.add_diagnostics(db, self.into(), sink);
         let infer = self.infer(db);
-        infer.add_diagnostics(db, self, sink);
-        let validator = ExprValidator::new(self, db);
+        infer.add_diagnostics(db, self.into(), sink);
+        let validator = ExprValidator::new(self.into(), db);
         validator.validate_body(sink);
+        crate::lint::check_function(db, self, sink);
     }
 }
 
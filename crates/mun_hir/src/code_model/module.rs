@@ -1,7 +1,10 @@
 use mun_hir_input::{FileId, ModuleId};
 
-use super::{r#impl::Impl, AssocItem, Function, Package, Struct, TypeAlias};
-use crate::{ids::ItemDefinitionId, primitive_type::PrimitiveType, DiagnosticSink, HirDatabase};
+use super::{r#impl::Impl, AssocItem, Const, Function, Package, Static, Struct, TypeAlias};
+use crate::{
+    ids::ItemDefinitionId, primitive_type::PrimitiveType, BuiltinFunction, DiagnosticSink,
+    HirDatabase,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Module {
@@ -97,6 +100,8 @@ impl Module {
                 ModuleDef::Function(f) => f.diagnostics(db, sink),
                 ModuleDef::Struct(s) => s.diagnostics(db, sink),
                 ModuleDef::TypeAlias(t) => t.diagnostics(db, sink),
+                ModuleDef::Const(c) => c.diagnostics(db, sink),
+                ModuleDef::Static(s) => s.diagnostics(db, sink),
                 _ => (),
             }
         }
@@ -142,6 +147,7 @@ impl Module {
         itertools::Itertools::intersperse(
             self.path_to_root(db)
                 .iter()
+                .rev()
                 .filter_map(|&module| module.name(db)),
             String::from("::"),
         )
@@ -162,8 +168,11 @@ pub enum ModuleDef {
     Module(Module),
     Function(Function),
     PrimitiveType(PrimitiveType),
+    BuiltinFunction(BuiltinFunction),
     Struct(Struct),
     TypeAlias(TypeAlias),
+    Const(Const),
+    Static(Static),
 }
 
 impl From<Function> for ModuleDef {
@@ -178,6 +187,12 @@ impl From<PrimitiveType> for ModuleDef {
     }
 }
 
+impl From<BuiltinFunction> for ModuleDef {
+    fn from(t: BuiltinFunction) -> Self {
+        ModuleDef::BuiltinFunction(t)
+    }
+}
+
 impl From<Struct> for ModuleDef {
     fn from(t: Struct) -> Self {
         ModuleDef::Struct(t)
@@ -190,6 +205,18 @@ impl From<TypeAlias> for ModuleDef {
     }
 }
 
+impl From<Const> for ModuleDef {
+    fn from(c: Const) -> Self {
+        ModuleDef::Const(c)
+    }
+}
+
+impl From<Static> for ModuleDef {
+    fn from(s: Static) -> Self {
+        ModuleDef::Static(s)
+    }
+}
+
 impl From<Module> for ModuleDef {
     fn from(m: Module) -> Self {
         ModuleDef::Module(m)
@@ -203,7 +230,10 @@ impl From<ItemDefinitionId> for ModuleDef {
             ItemDefinitionId::FunctionId(id) => Function { id }.into(),
             ItemDefinitionId::StructId(id) => Struct { id }.into(),
             ItemDefinitionId::TypeAliasId(id) => TypeAlias { id }.into(),
+            ItemDefinitionId::ConstId(id) => Const { id }.into(),
+            ItemDefinitionId::StaticId(id) => Static { id }.into(),
             ItemDefinitionId::PrimitiveType(id) => id.into(),
+            ItemDefinitionId::BuiltinFunction(id) => id.into(),
         }
     }
 }
@@ -27,6 +27,11 @@ impl Package {
         }
     }
 
+    /// Returns the version of the package, if it has one
+    pub fn version(self, db: &dyn HirDatabase) -> Option<semver::Version> {
+        db.packages()[self.id].version.clone()
+    }
+
     /// Returns all the modules in the package
     pub fn modules(self, db: &dyn HirDatabase) -> Vec<Module> {
         let module_tree = db.module_tree(self.id);
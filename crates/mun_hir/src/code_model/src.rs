@@ -1,8 +1,8 @@
-use mun_syntax::ast;
+use mun_syntax::{ast, AstNode, SyntaxNodePtr};
 
 use crate::{
-    code_model::{Field, Function, Struct, TypeAlias},
-    ids::{AssocItemLoc, Lookup},
+    code_model::{Const, Field, Function, Static, Struct, TypeAlias},
+    ids::{AssocItemLoc, ItemDefinitionId, Lookup},
     in_file::InFile,
     item_tree::{ItemTreeId, ItemTreeNode},
     DefDatabase, ItemLoc,
@@ -92,3 +92,39 @@ impl HasSource for TypeAlias {
         self.id.lookup(db).source(db)
     }
 }
+
+impl HasSource for Const {
+    type Ast = ast::ConstDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
+impl HasSource for Static {
+    type Ast = ast::StaticDef;
+    fn source(&self, db: &dyn DefDatabase) -> InFile<Self::Ast> {
+        self.id.lookup(db).source(db)
+    }
+}
+
+/// Returns the location of the definition of an item, or `None` if the item is
+/// not defined in source, e.g. a primitive type.
+pub(crate) fn item_definition_source(
+    db: &dyn DefDatabase,
+    id: ItemDefinitionId,
+) -> Option<InFile<SyntaxNodePtr>> {
+    fn node_ptr<N: AstNode>(source: InFile<N>) -> InFile<SyntaxNodePtr> {
+        source.map(|node| SyntaxNodePtr::new(node.syntax()))
+    }
+
+    match id {
+        ItemDefinitionId::FunctionId(id) => Some(node_ptr(Function::from(id).source(db))),
+        ItemDefinitionId::StructId(id) => Some(node_ptr(Struct::from(id).source(db))),
+        ItemDefinitionId::TypeAliasId(id) => Some(node_ptr(TypeAlias::from(id).source(db))),
+        ItemDefinitionId::ConstId(id) => Some(node_ptr(Const::from(id).source(db))),
+        ItemDefinitionId::StaticId(id) => Some(node_ptr(Static::from(id).source(db))),
+        ItemDefinitionId::ModuleId(_)
+        | ItemDefinitionId::PrimitiveType(_)
+        | ItemDefinitionId::BuiltinFunction(_) => None,
+    }
+}
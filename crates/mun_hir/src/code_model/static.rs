@@ -0,0 +1,162 @@
+use std::{iter::once, sync::Arc};
+
+use mun_hir_input::FileId;
+use mun_syntax::{ast::TypeAscriptionOwner, AstNode, SyntaxNodePtr};
+
+use super::Module;
+use crate::{
+    const_eval::{ConstEvalError, ConstValue},
+    diagnostics::InvalidStaticType,
+    expr::{validator::ExprValidator, BodySourceMap},
+    has_module::HasModule,
+    ids::{Lookup, StaticId},
+    resolve::HasResolver,
+    type_ref::{LocalTypeRefId, TypeRefMap, TypeRefSourceMap},
+    visibility::RawVisibility,
+    Body, DefDatabase, DiagnosticSink, HasSource, HasVisibility, HirDatabase, InFile,
+    InferenceResult, Name, Ty, TyKind, Visibility,
+};
+
+/// A module-level variable. Its storage lives in the assembly and its value is
+/// preserved when the assembly is hot reloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Static {
+    pub(crate) id: StaticId,
+}
+
+impl From<StaticId> for Static {
+    fn from(id: StaticId) -> Self {
+        Static { id }
+    }
+}
+
+impl Static {
+    pub fn module(self, db: &dyn HirDatabase) -> Module {
+        self.id.module(db.upcast()).into()
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        self.id.lookup(db.upcast()).id.file_id
+    }
+
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        self.data(db.upcast()).name.clone()
+    }
+
+    /// Returns the fully qualified name of the static, e.g. `foo::COUNTER`.
+    pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        itertools::Itertools::intersperse(
+            self.module(db)
+                .path_to_root(db)
+                .into_iter()
+                .filter_map(|module| module.name(db))
+                .chain(once(self.name(db).to_string())),
+            String::from("::"),
+        )
+        .collect()
+    }
+
+    pub fn data(self, db: &dyn DefDatabase) -> Arc<StaticData> {
+        db.static_data(self.id)
+    }
+
+    pub fn body(self, db: &dyn HirDatabase) -> Arc<Body> {
+        db.body(self.id.into())
+    }
+
+    /// Returns the declared type of the static.
+    pub fn ty(self, db: &dyn HirDatabase) -> Ty {
+        let data = self.data(db.upcast());
+        Ty::from_hir(
+            db,
+            &self.id.resolver(db.upcast()),
+            data.type_ref_map(),
+            data.type_ref_id,
+        )
+        .0
+    }
+
+    pub fn infer(self, db: &dyn HirDatabase) -> Arc<InferenceResult> {
+        db.infer(self.id.into())
+    }
+
+    /// Evaluates the value the static holds when its assembly is first loaded.
+    pub fn initializer(self, db: &dyn HirDatabase) -> Result<ConstValue, ConstEvalError> {
+        db.static_initializer(self.id)
+    }
+
+    pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
+        db.body_with_source_map(self.id.into()).1
+    }
+
+    pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
+        let body = self.body(db);
+        body.add_diagnostics(db, self.into(), sink);
+        let infer = self.infer(db);
+        infer.add_diagnostics(db, self.into(), sink);
+        let validator = ExprValidator::new(self.into(), db);
+        validator.validate_body(sink);
+
+        let ty = self.ty(db);
+        if !matches!(
+            ty.interned(),
+            TyKind::Int(_) | TyKind::Float(_) | TyKind::Bool | TyKind::Unknown
+        ) {
+            let src = self.source(db.upcast());
+            if let Some(type_ref) = src.value.ascribed_type() {
+                sink.push(InvalidStaticType {
+                    type_ref: InFile::new(src.file_id, SyntaxNodePtr::new(type_ref.syntax())),
+                });
+            }
+            return;
+        }
+
+        if let Err(err) = self.initializer(db) {
+            err.add_to(db, self.into(), sink);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StaticData {
+    pub name: Name,
+    pub visibility: RawVisibility,
+    pub type_ref_id: LocalTypeRefId,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl StaticData {
+    pub(crate) fn static_data_query(db: &dyn DefDatabase, id: StaticId) -> Arc<StaticData> {
+        let loc = id.lookup(db);
+        let item_tree = db.item_tree(loc.id.file_id);
+        let static_def = &item_tree[loc.id.value];
+        let src = item_tree.source(db, loc.id.value);
+        let mut type_ref_builder = TypeRefMap::builder();
+        let type_ref_id = type_ref_builder.alloc_from_node_opt(src.ascribed_type().as_ref());
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+        Arc::new(StaticData {
+            name: static_def.name.clone(),
+            visibility: item_tree[static_def.visibility].clone(),
+            type_ref_id,
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
+
+impl HasVisibility for Static {
+    fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
+        self.data(db.upcast())
+            .visibility
+            .resolve(db.upcast(), &self.id.resolver(db.upcast()))
+    }
+}
@@ -10,6 +10,7 @@ use mun_syntax::{
 
 use super::Module;
 use crate::{
+    attrs::Attrs,
     has_module::HasModule,
     ids::{Lookup, StructId},
     name::AsName,
@@ -99,6 +100,12 @@ impl Struct {
         .collect()
     }
 
+    /// Returns the documentation of the struct, which is specified with doc
+    /// comments.
+    pub fn docs(self, db: &dyn HirDatabase) -> Option<String> {
+        self.data(db.upcast()).attrs().docs().map(ToOwned::to_owned)
+    }
+
     pub fn fields(self, db: &dyn HirDatabase) -> Vec<Field> {
         self.data(db.upcast())
             .fields
@@ -179,6 +186,7 @@ pub struct StructData {
     pub fields: Arena<FieldData>,
     pub kind: StructKind,
     pub memory_kind: StructMemoryKind,
+    attrs: Attrs,
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
 }
@@ -232,11 +240,17 @@ impl StructData {
             fields,
             kind,
             memory_kind,
+            attrs: item_tree.attrs(loc.id.value.into()).clone(),
             type_ref_map,
             type_ref_source_map,
         })
     }
 
+    /// Returns the attributes and documentation of the struct
+    pub fn attrs(&self) -> &Attrs {
+        &self.attrs
+    }
+
     pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
         &self.type_ref_source_map
     }
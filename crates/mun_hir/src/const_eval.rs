@@ -0,0 +1,520 @@
+//! Compile-time evaluation of constants.
+//!
+//! The initializer of a `const` or `static` item is evaluated by interpreting
+//! its body.
+//! Only a subset of expressions is supported: literals, unary and binary
+//! operators, references to other constants and blocks without statements.
+
+use std::fmt;
+
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{ConstEvalCycle, ConstOverflow, DiagnosticSink, DivisionByZero, NotConstExpr},
+    expr::{ArithOp, BinaryOp, CmpOp, LogicOp, Ordering},
+    ids::{ConstId, DefWithBodyId, StaticId},
+    resolve::{resolver_for_expr, ValueNs},
+    ty::ResolveBitness,
+    Body, Expr, ExprId, FloatBitness, FloatTy, HirDatabase, InferenceResult, IntBitness, IntTy,
+    Literal, TyKind, UnaryOp,
+};
+
+/// The value of a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConstValue {
+    Bool(bool),
+
+    /// An integer, stored as its two's complement representation truncated
+    /// to the size of its type. The bitness of `ty` is always resolved.
+    Int {
+        bits: u128,
+        ty: IntTy,
+    },
+
+    /// A floating point value, stored as the bits of an `f64`. If `ty` is
+    /// `f32`, the value is exactly representable as an `f32`.
+    Float {
+        bits: u64,
+        ty: FloatTy,
+    },
+}
+
+impl ConstValue {
+    /// Constructs an integer value, or returns `None` if the value does not
+    /// fit in the specified type.
+    fn from_i128(value: i128, ty: IntTy) -> Option<ConstValue> {
+        let (min, max) = signed_range(ty);
+        (min..=max).contains(&value).then(|| ConstValue::Int {
+            bits: truncate(value as u128, ty),
+            ty,
+        })
+    }
+
+    /// Constructs an unsigned integer value, or returns `None` if the value
+    /// does not fit in the specified type.
+    fn from_u128(value: u128, ty: IntTy) -> Option<ConstValue> {
+        (value <= ty.max()).then_some(ConstValue::Int { bits: value, ty })
+    }
+
+    /// Constructs a floating point value, rounding it to the precision of the
+    /// specified type.
+    fn from_f64(value: f64, ty: FloatTy) -> ConstValue {
+        let value = match ty.bitness {
+            FloatBitness::X32 => f64::from(value as f32),
+            FloatBitness::X64 => value,
+        };
+        ConstValue::Float {
+            bits: value.to_bits(),
+            ty,
+        }
+    }
+
+    /// Returns the value as a signed integer if this is a signed integer.
+    pub fn as_i128(self) -> Option<i128> {
+        match self {
+            ConstValue::Int { bits, ty } if ty.signedness.is_signed() => {
+                let shift = 128 - bit_width(ty);
+                Some(((bits << shift) as i128) >> shift)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an unsigned integer if this is an unsigned
+    /// integer.
+    pub fn as_u128(self) -> Option<u128> {
+        match self {
+            ConstValue::Int { bits, ty } if !ty.signedness.is_signed() => Some(bits),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a floating point number if this is a floating
+    /// point value.
+    pub fn as_f64(self) -> Option<f64> {
+        match self {
+            ConstValue::Float { bits, .. } => Some(f64::from_bits(bits)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a boolean if this is a boolean.
+    pub fn as_bool(self) -> Option<bool> {
+        match self {
+            ConstValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ConstValue::Bool(value) => write!(f, "{value}"),
+            ConstValue::Int { bits, ty } => match self.as_i128() {
+                Some(value) => write!(f, "{value}{}", ty.as_str()),
+                None => write!(f, "{bits}{}", ty.as_str()),
+            },
+            ConstValue::Float { bits, ty } => match ty.bitness {
+                FloatBitness::X32 => write!(f, "{:?}f32", f64::from_bits(bits) as f32),
+                FloatBitness::X64 => write!(f, "{:?}f64", f64::from_bits(bits)),
+            },
+        }
+    }
+}
+
+/// An error that occurred while evaluating a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConstEvalError {
+    /// The expression cannot be evaluated at compile time.
+    NotConst(ExprId),
+
+    /// The result of an operation does not fit in its type.
+    Overflow(ExprId),
+
+    /// Division or remainder by zero.
+    DivisionByZero(ExprId),
+
+    /// The value of the constant depends on itself.
+    Cycle,
+
+    /// The initializer contains errors that are reported elsewhere, or it
+    /// refers to a constant that failed to evaluate.
+    Invalid,
+}
+
+impl ConstEvalError {
+    /// Adds a diagnostic for this error to the `sink`.
+    pub(crate) fn add_to(
+        self,
+        db: &dyn HirDatabase,
+        owner: DefWithBody,
+        sink: &mut DiagnosticSink<'_>,
+    ) {
+        let source_map = owner.body_source_map(db);
+        let expr_ptr = |expr: ExprId| {
+            source_map
+                .expr_syntax(expr)
+                .expect("could not retrieve expr from source map")
+                .map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()))
+        };
+
+        match self {
+            ConstEvalError::NotConst(expr) => sink.push(NotConstExpr {
+                expr: expr_ptr(expr),
+            }),
+            ConstEvalError::Overflow(expr) => sink.push(ConstOverflow {
+                expr: expr_ptr(expr),
+            }),
+            ConstEvalError::DivisionByZero(expr) => sink.push(DivisionByZero {
+                expr: expr_ptr(expr),
+            }),
+            ConstEvalError::Cycle => {
+                // Only constants can refer to other constants
+                if let DefWithBody::Const(konst) = owner {
+                    sink.push(ConstEvalCycle {
+                        const_def: konst.source_ptr(db),
+                        name: konst.name(db),
+                    });
+                }
+            }
+            ConstEvalError::Invalid => {}
+        }
+    }
+}
+
+/// Evaluates the value of a constant.
+pub(crate) fn const_eval_query(
+    db: &dyn HirDatabase,
+    def: ConstId,
+) -> Result<ConstValue, ConstEvalError> {
+    eval_body(db, def.into())
+}
+
+/// Evaluates the initial value of a static.
+pub(crate) fn static_initializer_query(
+    db: &dyn HirDatabase,
+    def: StaticId,
+) -> Result<ConstValue, ConstEvalError> {
+    eval_body(db, def.into())
+}
+
+/// Evaluates the body of a constant or static.
+fn eval_body(db: &dyn HirDatabase, def: DefWithBodyId) -> Result<ConstValue, ConstEvalError> {
+    let body = db.body(def);
+    let infer = db.infer(def);
+    if !infer.diagnostics.is_empty() {
+        return Err(ConstEvalError::Invalid);
+    }
+
+    ConstEvalCtx {
+        db,
+        body: &body,
+        infer: &infer,
+    }
+    .eval(body.body_expr())
+}
+
+/// Called by salsa if the evaluation of a constant depends on itself.
+pub(crate) fn const_eval_recover(
+    _db: &dyn HirDatabase,
+    _cycle: &[String],
+    _def: &ConstId,
+) -> Result<ConstValue, ConstEvalError> {
+    Err(ConstEvalError::Cycle)
+}
+
+struct ConstEvalCtx<'a> {
+    db: &'a dyn HirDatabase,
+    body: &'a Body,
+    infer: &'a InferenceResult,
+}
+
+impl ConstEvalCtx<'_> {
+    fn eval(&self, expr: ExprId) -> Result<ConstValue, ConstEvalError> {
+        match &self.body[expr] {
+            Expr::Missing => Err(ConstEvalError::Invalid),
+            Expr::Literal(literal) => self.eval_literal(expr, literal, false),
+            Expr::Path(path) => {
+                let resolver = resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
+                match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+                    Some((ValueNs::ConstId(id), _)) => {
+                        self.db.const_eval(id).map_err(|err| match err {
+                            ConstEvalError::Cycle => ConstEvalError::Cycle,
+                            _ => ConstEvalError::Invalid,
+                        })
+                    }
+                    _ => Err(ConstEvalError::NotConst(expr)),
+                }
+            }
+            Expr::Block {
+                statements,
+                tail: Some(tail),
+            } if statements.is_empty() => self.eval(*tail),
+            Expr::UnaryOp { expr: operand, op } => self.eval_unary_op(expr, *operand, *op),
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(op),
+            } => self.eval_binary_op(expr, *lhs, *rhs, *op),
+            _ => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+
+    /// Evaluates a literal. If `negated` is true the literal is the operand of
+    /// a negation which allows it to be one larger than the maximum of a
+    /// signed type (e.g. `-128_i8`).
+    fn eval_literal(
+        &self,
+        expr: ExprId,
+        literal: &Literal,
+        negated: bool,
+    ) -> Result<ConstValue, ConstEvalError> {
+        match (literal, self.infer[expr].interned()) {
+            (Literal::Bool(value), _) => Ok(ConstValue::Bool(*value)),
+            (Literal::Int(lit), TyKind::Int(ty)) => {
+                let ty = ty.resolve(&self.db.target_data_layout());
+                let value = if negated && ty.signedness.is_signed() {
+                    // Out of range literals are reported by the expression validator
+                    if lit.value > 1 << 127 {
+                        return Err(ConstEvalError::Invalid);
+                    }
+                    ConstValue::from_i128((lit.value as i128).wrapping_neg(), ty)
+                } else if ty.signedness.is_signed() {
+                    i128::try_from(lit.value)
+                        .ok()
+                        .and_then(|value| ConstValue::from_i128(value, ty))
+                } else {
+                    ConstValue::from_u128(lit.value, ty)
+                };
+                value.ok_or(ConstEvalError::Invalid)
+            }
+            (Literal::Float(lit), TyKind::Float(ty)) => Ok(ConstValue::from_f64(lit.value, *ty)),
+            _ => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+
+    fn eval_unary_op(
+        &self,
+        expr: ExprId,
+        operand: ExprId,
+        op: UnaryOp,
+    ) -> Result<ConstValue, ConstEvalError> {
+        if let (UnaryOp::Neg, Expr::Literal(literal)) = (op, &self.body[operand]) {
+            return self.eval_literal(operand, literal, true);
+        }
+
+        let value = self.eval(operand)?;
+        match (op, value) {
+            (UnaryOp::Not, ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+            (UnaryOp::Not, ConstValue::Int { bits, ty }) => Ok(ConstValue::Int {
+                bits: truncate(!bits, ty),
+                ty,
+            }),
+            (UnaryOp::Neg, ConstValue::Int { ty, .. }) => value
+                .as_i128()
+                .and_then(i128::checked_neg)
+                .and_then(|value| ConstValue::from_i128(value, ty))
+                .ok_or(ConstEvalError::Overflow(expr)),
+            (UnaryOp::Neg, ConstValue::Float { bits, ty }) => {
+                Ok(ConstValue::from_f64(-f64::from_bits(bits), ty))
+            }
+            _ => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+
+    fn eval_binary_op(
+        &self,
+        expr: ExprId,
+        lhs: ExprId,
+        rhs: ExprId,
+        op: BinaryOp,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let lhs = self.eval(lhs)?;
+        let rhs = self.eval(rhs)?;
+        match op {
+            BinaryOp::ArithOp(op) => match (lhs, rhs) {
+                (ConstValue::Int { ty, .. }, ConstValue::Int { .. }) => {
+                    eval_int_arith_op(expr, lhs, rhs, op, ty)
+                }
+                (ConstValue::Float { bits: lhs, ty }, ConstValue::Float { bits: rhs, .. }) => {
+                    eval_float_arith_op(expr, f64::from_bits(lhs), f64::from_bits(rhs), op, ty)
+                }
+                (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => match op {
+                    ArithOp::BitAnd => Ok(ConstValue::Bool(lhs & rhs)),
+                    ArithOp::BitOr => Ok(ConstValue::Bool(lhs | rhs)),
+                    ArithOp::BitXor => Ok(ConstValue::Bool(lhs ^ rhs)),
+                    _ => Err(ConstEvalError::NotConst(expr)),
+                },
+                _ => Err(ConstEvalError::NotConst(expr)),
+            },
+            BinaryOp::LogicOp(op) => match (lhs, rhs) {
+                (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => Ok(ConstValue::Bool(match op {
+                    LogicOp::And => lhs && rhs,
+                    LogicOp::Or => lhs || rhs,
+                })),
+                _ => Err(ConstEvalError::NotConst(expr)),
+            },
+            BinaryOp::CmpOp(op) => {
+                let ordering = match (lhs, rhs) {
+                    (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => lhs.partial_cmp(&rhs),
+                    (ConstValue::Int { .. }, ConstValue::Int { .. }) => {
+                        match (lhs.as_i128(), rhs.as_i128()) {
+                            (Some(lhs), Some(rhs)) => lhs.partial_cmp(&rhs),
+                            _ => lhs.as_u128().partial_cmp(&rhs.as_u128()),
+                        }
+                    }
+                    (ConstValue::Float { bits: lhs, .. }, ConstValue::Float { bits: rhs, .. }) => {
+                        f64::from_bits(lhs).partial_cmp(&f64::from_bits(rhs))
+                    }
+                    _ => return Err(ConstEvalError::NotConst(expr)),
+                };
+                Ok(ConstValue::Bool(eval_cmp_op(op, ordering)))
+            }
+            BinaryOp::Assignment { .. } => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+}
+
+/// Evaluates an arithmetic operation on two integers of type `ty`.
+fn eval_int_arith_op(
+    expr: ExprId,
+    lhs: ConstValue,
+    rhs: ConstValue,
+    op: ArithOp,
+    ty: IntTy,
+) -> Result<ConstValue, ConstEvalError> {
+    let overflow = ConstEvalError::Overflow(expr);
+    let (ConstValue::Int { bits: lhs_bits, .. }, ConstValue::Int { bits: rhs_bits, .. }) =
+        (lhs, rhs)
+    else {
+        return Err(ConstEvalError::NotConst(expr));
+    };
+
+    match op {
+        ArithOp::BitAnd => {
+            return Ok(ConstValue::Int {
+                bits: lhs_bits & rhs_bits,
+                ty,
+            })
+        }
+        ArithOp::BitOr => {
+            return Ok(ConstValue::Int {
+                bits: lhs_bits | rhs_bits,
+                ty,
+            })
+        }
+        ArithOp::BitXor => {
+            return Ok(ConstValue::Int {
+                bits: lhs_bits ^ rhs_bits,
+                ty,
+            })
+        }
+        ArithOp::LeftShift | ArithOp::RightShift => {
+            let shift = u32::try_from(rhs_bits)
+                .ok()
+                .filter(|shift| *shift < bit_width(ty) && rhs.as_i128().map_or(true, |it| it >= 0))
+                .ok_or(overflow)?;
+            let bits = match (op, lhs.as_i128()) {
+                (ArithOp::LeftShift, _) => truncate(lhs_bits << shift, ty),
+                (_, Some(lhs)) => truncate((lhs >> shift) as u128, ty),
+                (_, None) => lhs_bits >> shift,
+            };
+            return Ok(ConstValue::Int { bits, ty });
+        }
+        _ => {}
+    }
+
+    if rhs_bits == 0 && matches!(op, ArithOp::Divide | ArithOp::Remainder) {
+        return Err(ConstEvalError::DivisionByZero(expr));
+    }
+
+    let value = if let (Some(lhs), Some(rhs)) = (lhs.as_i128(), rhs.as_i128()) {
+        match op {
+            ArithOp::Add => lhs.checked_add(rhs),
+            ArithOp::Subtract => lhs.checked_sub(rhs),
+            ArithOp::Multiply => lhs.checked_mul(rhs),
+            ArithOp::Divide => lhs.checked_div(rhs),
+            ArithOp::Remainder => lhs.checked_rem(rhs),
+            _ => unreachable!("bitwise operators have already been evaluated"),
+        }
+        .and_then(|value| ConstValue::from_i128(value, ty))
+    } else {
+        match op {
+            ArithOp::Add => lhs_bits.checked_add(rhs_bits),
+            ArithOp::Subtract => lhs_bits.checked_sub(rhs_bits),
+            ArithOp::Multiply => lhs_bits.checked_mul(rhs_bits),
+            ArithOp::Divide => lhs_bits.checked_div(rhs_bits),
+            ArithOp::Remainder => lhs_bits.checked_rem(rhs_bits),
+            _ => unreachable!("bitwise operators have already been evaluated"),
+        }
+        .and_then(|value| ConstValue::from_u128(value, ty))
+    };
+
+    value.ok_or(overflow)
+}
+
+/// Evaluates an arithmetic operation on two floating point values of type
+/// `ty`.
+fn eval_float_arith_op(
+    expr: ExprId,
+    lhs: f64,
+    rhs: f64,
+    op: ArithOp,
+    ty: FloatTy,
+) -> Result<ConstValue, ConstEvalError> {
+    let value = match op {
+        ArithOp::Add => lhs + rhs,
+        ArithOp::Subtract => lhs - rhs,
+        ArithOp::Multiply => lhs * rhs,
+        ArithOp::Divide => lhs / rhs,
+        ArithOp::Remainder => lhs % rhs,
+        _ => return Err(ConstEvalError::NotConst(expr)),
+    };
+    Ok(ConstValue::from_f64(value, ty))
+}
+
+/// Returns the result of a comparison given the ordering of its operands.
+fn eval_cmp_op(op: CmpOp, ordering: Option<std::cmp::Ordering>) -> bool {
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    match (op, ordering) {
+        (CmpOp::Eq { negated }, ordering) => (ordering == Some(Equal)) != negated,
+        (_, None) => false,
+        (CmpOp::Ord { ordering, strict }, Some(actual)) => match (ordering, actual) {
+            (_, Equal) => !strict,
+            (Ordering::Less, Less) | (Ordering::Greater, Greater) => true,
+            (Ordering::Less, Greater) | (Ordering::Greater, Less) => false,
+        },
+    }
+}
+
+/// Returns the number of bits of an integer type with a resolved bitness.
+fn bit_width(ty: IntTy) -> u32 {
+    match ty.bitness {
+        IntBitness::X8 => 8,
+        IntBitness::X16 => 16,
+        IntBitness::X32 => 32,
+        IntBitness::X64 => 64,
+        IntBitness::X128 => 128,
+        IntBitness::Xsize => unreachable!("bitness has been resolved"),
+    }
+}
+
+/// Truncates the bits of an integer to the size of its type.
+fn truncate(bits: u128, ty: IntTy) -> u128 {
+    bits & ty_mask(ty)
+}
+
+/// Returns a mask that covers all the bits of an integer type.
+fn ty_mask(ty: IntTy) -> u128 {
+    u128::MAX >> (128 - bit_width(ty))
+}
+
+/// Returns the range of values of a signed integer type.
+fn signed_range(ty: IntTy) -> (i128, i128) {
+    let max = (ty_mask(ty) >> 1) as i128;
+    (-max - 1, max)
+}
+
+#[cfg(test)]
+mod tests;
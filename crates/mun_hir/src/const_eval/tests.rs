@@ -0,0 +1,173 @@
+use mun_hir_input::WithFixture;
+
+use crate::{mock::MockDatabase, utils::tests::diagnostics, ModuleDef, Package};
+
+/// Evaluates all constants and static initializers in `content` and returns
+/// their values.
+fn eval(content: &str) -> String {
+    let (db, _file_id) = MockDatabase::with_single_file(content);
+
+    Package::all(&db)
+        .iter()
+        .flat_map(|pkg| pkg.modules(&db))
+        .flat_map(|module| module.declarations(&db))
+        .filter_map(|def| match def {
+            ModuleDef::Const(konst) => Some(match konst.eval(&db) {
+                Ok(value) => format!("{} = {value}", konst.name(&db)),
+                Err(err) => format!("{} = {err:?}", konst.name(&db)),
+            }),
+            ModuleDef::Static(static_def) => Some(match static_def.initializer(&db) {
+                Ok(value) => format!("static {} = {value}", static_def.name(&db)),
+                Err(err) => format!("static {} = {err:?}", static_def.name(&db)),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn eval_literals() {
+    insta::assert_snapshot!(eval(
+        r#"
+    const A: bool = true;
+    const B: i8 = -128;
+    const C: u8 = 255;
+    const D: f32 = 0.1;
+    const E: f64 = 0.1;
+    const F: f64 = 1e3;
+    const G: i128 = -170141183460469231731687303715884105728;
+    const H: u128 = 340282366920938463463374607431768211455;
+    "#),
+    @"
+    A = true
+    B = -128i8
+    C = 255u8
+    D = 0.1f32
+    E = 0.1f64
+    F = 1000.0f64
+    G = -170141183460469231731687303715884105728i128
+    H = 340282366920938463463374607431768211455u128
+    ");
+}
+
+#[test]
+fn eval_operators() {
+    insta::assert_snapshot!(eval(
+        r#"
+    const A: i32 = 1 + 2 * 3 - 4 / 2;
+    const B: i32 = -7 % 3;
+    const C: u8 = !0;
+    const D: i8 = -1 >> 1;
+    const E: u8 = 255 >> 4 << 1;
+    const F: i64 = 0b1100 & 0b1010 | 0b0001 ^ 0b0011;
+    const G: bool = 1 < 2 && 2.0 >= 3.0 || !(4 != 4);
+    const H: f32 = 1.0 / 3.0;
+    const I: i32 = { 42 };
+    "#),
+    @"
+    A = 5i32
+    B = -1i32
+    C = 255u8
+    D = -1i8
+    E = 30u8
+    F = 10i64
+    G = true
+    H = 0.33333334f32
+    I = 42i32
+    ");
+}
+
+#[test]
+fn eval_references() {
+    insta::assert_snapshot!(eval(
+        r#"
+    const SIZE: usize = 2 * HALF;
+    const HALF: usize = 32;
+    const MAX_SPEED: f32 = 5.0;
+    const DOUBLE_SPEED: f32 = MAX_SPEED * 2.0;
+    "#),
+    @"
+    SIZE = 64u64
+    HALF = 32u64
+    MAX_SPEED = 5.0f32
+    DOUBLE_SPEED = 10.0f32
+    ");
+}
+
+#[test]
+fn eval_errors() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    const A: u8 = 255 + 1;
+    const B: i32 = 1 / 0;
+    const C: i32 = 5 % (2 - 2);
+    const D: i8 = -(-128);
+    const E: u32 = 1 << 32;
+    const F: i32 = foo();
+    const G: i32 = { let a = 1; a };
+    const H: u8 = A + 1;
+
+    fn foo() -> i32 { 1 }
+    "#),
+    @"
+    14..21: this arithmetic operation will overflow
+    38..43: attempt to divide by zero
+    60..71: attempt to divide by zero
+    87..94: this arithmetic operation will overflow
+    111..118: this arithmetic operation will overflow
+    135..140: expression cannot be evaluated at compile time
+    157..173: expression cannot be evaluated at compile time
+    ");
+}
+
+#[test]
+fn eval_cycle() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    const A: i32 = B + 1;
+    const B: i32 = A;
+    const C: i32 = C;
+    "#),
+    @"
+    0..21: cycle detected when evaluating constant `A`
+    22..39: cycle detected when evaluating constant `B`
+    40..57: cycle detected when evaluating constant `C`
+    ");
+}
+
+#[test]
+fn eval_statics() {
+    insta::assert_snapshot!(eval(
+        r#"
+    const SPEED: f32 = 2.5;
+    static POSITION: f32 = SPEED * 2.0;
+    static COUNT: u64 = 0;
+    static ENABLED: bool = true;
+    "#),
+    @"
+    SPEED = 2.5f32
+    static POSITION = 5.0f32
+    static COUNT = 0u64
+    static ENABLED = true
+    ");
+}
+
+#[test]
+fn static_errors() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    struct Foo;
+    static A: i32 = 1;
+    static B: i32 = A;
+    const C: i32 = A;
+    static D: Foo = Foo;
+    static E: u8 = 256 - 1 + 1;
+    "#),
+    @"
+    47..48: expression cannot be evaluated at compile time
+    65..66: expression cannot be evaluated at compile time
+    78..81: static items must have a primitive type
+    104..107: literal out of range for `u8`
+    ");
+}
@@ -7,13 +7,20 @@ use mun_db::Upcast;
 use mun_hir_input::{FileId, PackageId, SourceDatabase};
 use mun_syntax::{ast, Parse, SourceFile};
 use mun_target::{abi, spec::Target};
+use rustc_hash::FxHashSet;
 
 use crate::{
-    code_model::{r#struct::LocalFieldId, FunctionData, ImplData, StructData, TypeAliasData},
+    cfg::CfgOptions,
+    code_model::{
+        r#struct::LocalFieldId, ConstData, FunctionData, ImplData, StaticData, StructData,
+        TypeAliasData,
+    },
+    const_eval::{ConstEvalError, ConstValue},
     expr::BodySourceMap,
     ids,
-    ids::{DefWithBodyId, FunctionId, ImplId, VariantId},
+    ids::{ConstId, DefWithBodyId, FunctionId, ImplId, StaticId, VariantId},
     item_tree::{self, ItemTree},
+    lint::LintLevels,
     method_resolution::InherentImpls,
     name_resolution::Namespace,
     package_defs::PackageDefs,
@@ -46,11 +53,20 @@ pub trait InternDatabase: SourceDatabase {
     #[salsa::interned]
     fn intern_type_alias(&self, loc: ids::TypeAliasLoc) -> ids::TypeAliasId;
     #[salsa::interned]
+    fn intern_const(&self, loc: ids::ConstLoc) -> ids::ConstId;
+    #[salsa::interned]
+    fn intern_static(&self, loc: ids::StaticLoc) -> ids::StaticId;
+    #[salsa::interned]
     fn intern_impl(self, loc: ids::ImplLoc) -> ids::ImplId;
 }
 
 #[salsa::query_group(DefDatabaseStorage)]
 pub trait DefDatabase: InternDatabase + AstDatabase + Upcast<dyn AstDatabase> {
+    /// Returns the options against which `#[cfg(...)]` attributes are
+    /// evaluated.
+    #[salsa::input]
+    fn cfg_options(&self) -> Arc<CfgOptions>;
+
     /// Returns the `ItemTree` for a specific file. An `ItemTree` represents all
     /// the top level declarations within a file.
     #[salsa::invoke(item_tree::ItemTree::item_tree_query)]
@@ -62,6 +78,12 @@ pub trait DefDatabase: InternDatabase + AstDatabase + Upcast<dyn AstDatabase> {
     #[salsa::invoke(TypeAliasData::type_alias_data_query)]
     fn type_alias_data(&self, id: ids::TypeAliasId) -> Arc<TypeAliasData>;
 
+    #[salsa::invoke(ConstData::const_data_query)]
+    fn const_data(&self, id: ConstId) -> Arc<ConstData>;
+
+    #[salsa::invoke(StaticData::static_data_query)]
+    fn static_data(&self, id: StaticId) -> Arc<StaticData>;
+
     #[salsa::invoke(crate::FunctionData::fn_data_query)]
     fn fn_data(&self, func: FunctionId) -> Arc<FunctionData>;
 
@@ -103,6 +125,15 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
     #[salsa::invoke(crate::ty::infer_query)]
     fn infer(&self, def: DefWithBodyId) -> Arc<InferenceResult>;
 
+    /// Evaluates the initializer of a constant at compile time.
+    #[salsa::invoke(crate::const_eval::const_eval_query)]
+    #[salsa::cycle(crate::const_eval::const_eval_recover)]
+    fn const_eval(&self, def: ConstId) -> Result<ConstValue, ConstEvalError>;
+
+    /// Evaluates the initial value of a static at compile time.
+    #[salsa::invoke(crate::const_eval::static_initializer_query)]
+    fn static_initializer(&self, def: StaticId) -> Result<ConstValue, ConstEvalError>;
+
     #[salsa::invoke(crate::ty::lower::lower_struct_query)]
     fn lower_struct(&self, def: Struct) -> Arc<LowerTyMap>;
 
@@ -123,6 +154,14 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
 
     #[salsa::invoke(InherentImpls::inherent_impls_in_package_query)]
     fn inherent_impls_in_package(&self, package: PackageId) -> Arc<InherentImpls>;
+
+    /// Returns the levels of the lints that are configured for the packages.
+    #[salsa::input]
+    fn lint_levels(&self) -> Arc<LintLevels>;
+
+    /// Returns all functions of a package that are used in the package.
+    #[salsa::invoke(crate::lint::used_functions_query)]
+    fn used_functions(&self, package: PackageId) -> Arc<FxHashSet<FunctionId>>;
 }
 
 fn parse_query(db: &dyn AstDatabase, file_id: FileId) -> Parse<SourceFile> {
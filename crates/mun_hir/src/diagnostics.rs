@@ -4,7 +4,11 @@ use mun_hir_input::FileId;
 use mun_syntax::{ast, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange};
 
 use crate::{
-    code_model::StructKind, ids::FunctionId, in_file::InFile, HirDatabase, IntTy, Name, Ty,
+    code_model::StructKind,
+    ids::FunctionId,
+    in_file::InFile,
+    lint::{Lint, LintLevel, LintSource},
+    BuiltinFunction, HirDatabase, IntTy, Name, Ty,
 };
 
 /// Diagnostic defines `mun_hir` API for errors and warnings.
@@ -23,9 +27,20 @@ pub trait Diagnostic: Any + Send + Sync + fmt::Debug + 'static {
     fn highlight_range(&self) -> TextRange {
         self.source().value.range()
     }
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
     fn as_any(&self) -> &(dyn Any + Send + 'static);
 }
 
+/// The severity of a [`Diagnostic`]. Only errors prevent a program from being
+/// compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
 pub trait AstDiagnostic {
     type AST;
     fn ast(&self, db: &dyn HirDatabase) -> Self::AST;
@@ -81,10 +96,22 @@ impl<'a> DiagnosticSink<'a> {
     }
 }
 
+/// A name that is similar to a name that could not be resolved, and is
+/// therefore suggested as a fix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarName {
+    pub name: Name,
+
+    /// The definition of the name, or `None` if it is not defined in source,
+    /// e.g. a primitive type.
+    pub definition: Option<InFile<SyntaxNodePtr>>,
+}
+
 #[derive(Debug)]
 pub struct UnresolvedValue {
     pub file: FileId,
     pub expr: SyntaxNodePtr,
+    pub similar_name: Option<SimilarName>,
 }
 
 impl Diagnostic for UnresolvedValue {
@@ -105,6 +132,7 @@ impl Diagnostic for UnresolvedValue {
 pub struct UnresolvedType {
     pub file: FileId,
     pub type_ref: AstPtr<ast::TypeRef>,
+    pub similar_name: Option<SimilarName>,
 }
 
 impl Diagnostic for UnresolvedType {
@@ -253,17 +281,23 @@ impl Diagnostic for MismatchedType {
 pub struct IncompatibleBranch {
     pub file: FileId,
     pub if_expr: SyntaxNodePtr,
+
+    /// The expression that determines the value of the `then` branch
+    pub then_expr: SyntaxNodePtr,
+
+    /// The expression that determines the value of the `else` branch
+    pub else_expr: SyntaxNodePtr,
     pub expected: Ty,
     pub found: Ty,
 }
 
 impl Diagnostic for IncompatibleBranch {
     fn message(&self) -> String {
-        "mismatched branches".to_string()
+        "`if` and `else` have incompatible types".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.if_expr.clone())
+        InFile::new(self.file, self.else_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -423,18 +457,18 @@ impl Diagnostic for BreakOutsideLoop {
 }
 
 #[derive(Debug)]
-pub struct BreakWithValueOutsideLoop {
+pub struct ContinueOutsideLoop {
     pub file: FileId,
-    pub break_expr: SyntaxNodePtr,
+    pub continue_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for BreakWithValueOutsideLoop {
+impl Diagnostic for ContinueOutsideLoop {
     fn message(&self) -> String {
-        "`break` with value can only appear in a `loop`".to_owned()
+        "`continue` outside of a loop".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.break_expr.clone())
+        InFile::new(self.file, self.continue_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -443,20 +477,19 @@ impl Diagnostic for BreakWithValueOutsideLoop {
 }
 
 #[derive(Debug)]
-pub struct AccessUnknownField {
+pub struct UndeclaredLabel {
     pub file: FileId,
-    pub expr: SyntaxNodePtr,
-    pub receiver_ty: Ty,
-    pub name: Name,
+    pub break_expr: SyntaxNodePtr,
+    pub label: Name,
 }
 
-impl Diagnostic for AccessUnknownField {
+impl Diagnostic for UndeclaredLabel {
     fn message(&self) -> String {
-        "attempted to access a non-existent field in a struct.".to_string()
+        format!("use of undeclared label `{}`", self.label)
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.expr.clone())
+        InFile::new(self.file, self.break_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -464,27 +497,25 @@ impl Diagnostic for AccessUnknownField {
     }
 }
 
+/// An error that is emitted for a loop label that shadows the label of an
+/// enclosing loop
 #[derive(Debug)]
-pub struct FieldCountMismatch {
+pub struct DuplicateLabel {
     pub file: FileId,
-    pub expr: SyntaxNodePtr,
-    pub expected: usize,
-    pub found: usize,
+    pub label_ptr: SyntaxNodePtr,
+    pub label: Name,
 }
 
-impl Diagnostic for FieldCountMismatch {
+impl Diagnostic for DuplicateLabel {
     fn message(&self) -> String {
         format!(
-            "this tuple struct literal has {} field{} but {} field{} supplied",
-            self.expected,
-            if self.expected == 1 { "" } else { "s" },
-            self.found,
-            if self.found == 1 { " was" } else { "s were" },
+            "label `{}` is already declared by an enclosing loop",
+            self.label
         )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.expr.clone())
+        InFile::new(self.file, self.label_ptr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -493,25 +524,18 @@ impl Diagnostic for FieldCountMismatch {
 }
 
 #[derive(Debug)]
-pub struct MissingFields {
+pub struct ExpectedStringLiteral {
     pub file: FileId,
-    pub fields: SyntaxNodePtr,
-    pub struct_ty: Ty,
-    pub field_names: Vec<Name>,
+    pub expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for MissingFields {
+impl Diagnostic for ExpectedStringLiteral {
     fn message(&self) -> String {
-        use std::fmt::Write;
-        let mut message = "missing record fields:\n".to_string();
-        for field in &self.field_names {
-            writeln!(message, "- {field}").unwrap();
-        }
-        message
+        "expected a string literal".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.fields.clone())
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -520,19 +544,15 @@ impl Diagnostic for MissingFields {
 }
 
 #[derive(Debug)]
-pub struct MismatchedStructLit {
+pub struct BuiltinFunctionNotCalled {
     pub file: FileId,
     pub expr: SyntaxNodePtr,
-    pub expected: StructKind,
-    pub found: StructKind,
+    pub function: BuiltinFunction,
 }
 
-impl Diagnostic for MismatchedStructLit {
+impl Diagnostic for BuiltinFunctionNotCalled {
     fn message(&self) -> String {
-        format!(
-            "mismatched struct literal kind. expected `{}`, found `{}`",
-            self.expected, self.found
-        )
+        format!("builtin function `{}` can only be called", self.function)
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
@@ -545,38 +565,49 @@ impl Diagnostic for MismatchedStructLit {
 }
 
 #[derive(Debug)]
-pub struct NoFields {
+pub struct InvalidBuiltinArgument {
     pub file: FileId,
-    pub receiver_expr: SyntaxNodePtr,
-    pub found: Ty,
+    pub expr: SyntaxNodePtr,
+    pub function: BuiltinFunction,
+    pub ty: Ty,
 }
 
-impl Diagnostic for NoFields {
+impl Diagnostic for InvalidBuiltinArgument {
     fn message(&self) -> String {
-        "attempted to access a field on a primitive type.".to_string()
+        let expected = match self.function {
+            BuiltinFunction::Abs => "signed integers and floating point numbers",
+            BuiltinFunction::Min | BuiltinFunction::Max => "integers and floating point numbers",
+            _ => "floating point numbers",
+        };
+        format!(
+            "builtin function `{}` can only be applied to {expected}",
+            self.function
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.receiver_expr.clone())
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
         self
     }
 }
+
 #[derive(Debug)]
-pub struct NoSuchField {
+pub struct NotIterable {
     pub file: FileId,
-    pub field: SyntaxNodePtr,
+    pub expr: SyntaxNodePtr,
+    pub ty: Ty,
 }
 
-impl Diagnostic for NoSuchField {
+impl Diagnostic for NotIterable {
     fn message(&self) -> String {
-        "no such field".to_string()
+        "only arrays and ranges can be iterated over in a `for` loop".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.field.clone())
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -584,19 +615,21 @@ impl Diagnostic for NoSuchField {
     }
 }
 
+/// An error that is emitted for an array of which the elements are tuples.
+/// Tuples have no runtime type information, so arrays cannot store them.
 #[derive(Debug)]
-pub struct PossiblyUninitializedVariable {
+pub struct TupleInArray {
     pub file: FileId,
-    pub pat: SyntaxNodePtr,
+    pub expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for PossiblyUninitializedVariable {
+impl Diagnostic for TupleInArray {
     fn message(&self) -> String {
-        "use of possibly-uninitialized variable".to_string()
+        "arrays cannot contain tuples".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.pat.clone())
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -605,17 +638,18 @@ impl Diagnostic for PossiblyUninitializedVariable {
 }
 
 #[derive(Debug)]
-pub struct ExternCannotHaveBody {
-    pub func: InFile<SyntaxNodePtr>,
+pub struct UnsupportedRange {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for ExternCannotHaveBody {
+impl Diagnostic for UnsupportedRange {
     fn message(&self) -> String {
-        "extern functions cannot have bodies".to_string()
+        "range expressions can only be used as the iterable of a `for` loop".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.func.clone()
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -624,17 +658,18 @@ impl Diagnostic for ExternCannotHaveBody {
 }
 
 #[derive(Debug)]
-pub struct ExternNonPrimitiveParam {
-    pub param: InFile<SyntaxNodePtr>,
+pub struct BreakWithValueOutsideLoop {
+    pub file: FileId,
+    pub break_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for ExternNonPrimitiveParam {
+impl Diagnostic for BreakWithValueOutsideLoop {
     fn message(&self) -> String {
-        "extern functions can only have primitives as parameter- and return types".to_string()
+        "`break` with value can only appear in a `loop`".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.param.clone()
+        InFile::new(self.file, self.break_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -642,19 +677,22 @@ impl Diagnostic for ExternNonPrimitiveParam {
     }
 }
 
-/// An error that is emitted if a literal is too large to even parse
 #[derive(Debug)]
-pub struct IntLiteralTooLarge {
-    pub literal: InFile<AstPtr<ast::Literal>>,
+pub struct AccessUnknownField {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub receiver_ty: Ty,
+    pub name: Name,
+    pub similar_name: Option<SimilarName>,
 }
 
-impl Diagnostic for IntLiteralTooLarge {
+impl Diagnostic for AccessUnknownField {
     fn message(&self) -> String {
-        "int literal is too large".to_owned()
+        "attempted to access a non-existent field in a struct.".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -662,20 +700,27 @@ impl Diagnostic for IntLiteralTooLarge {
     }
 }
 
-/// An error that is emitted if a literal is too large for its suffix
 #[derive(Debug)]
-pub struct LiteralOutOfRange {
-    pub literal: InFile<AstPtr<ast::Literal>>,
-    pub int_ty: IntTy,
+pub struct FieldCountMismatch {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub expected: usize,
+    pub found: usize,
 }
 
-impl Diagnostic for LiteralOutOfRange {
+impl Diagnostic for FieldCountMismatch {
     fn message(&self) -> String {
-        format!("literal out of range for `{}`", self.int_ty.as_str())
+        format!(
+            "this tuple struct literal has {} field{} but {} field{} supplied",
+            self.expected,
+            if self.expected == 1 { "" } else { "s" },
+            self.found,
+            if self.found == 1 { " was" } else { "s were" },
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -683,21 +728,26 @@ impl Diagnostic for LiteralOutOfRange {
     }
 }
 
-/// An error that is emitted for a literal with an invalid suffix (e.g.
-/// `123_foo`)
 #[derive(Debug)]
-pub struct InvalidLiteralSuffix {
-    pub literal: InFile<AstPtr<ast::Literal>>,
-    pub suffix: SmolStr,
+pub struct MissingFields {
+    pub file: FileId,
+    pub fields: SyntaxNodePtr,
+    pub struct_ty: Ty,
+    pub field_names: Vec<Name>,
 }
 
-impl Diagnostic for InvalidLiteralSuffix {
+impl Diagnostic for MissingFields {
     fn message(&self) -> String {
-        format!("invalid suffix `{}`", self.suffix)
+        use std::fmt::Write;
+        let mut message = "missing record fields:\n".to_string();
+        for field in &self.field_names {
+            writeln!(message, "- {field}").unwrap();
+        }
+        message
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.fields.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -705,26 +755,24 @@ impl Diagnostic for InvalidLiteralSuffix {
     }
 }
 
-/// An error that is emitted for a literal with a floating point suffix with a
-/// non 10 base (e.g. `0x123_f32`)
 #[derive(Debug)]
-pub struct InvalidFloatingPointLiteral {
-    pub literal: InFile<AstPtr<ast::Literal>>,
-    pub base: u32,
+pub struct MismatchedStructLit {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub expected: StructKind,
+    pub found: StructKind,
 }
 
-impl Diagnostic for InvalidFloatingPointLiteral {
+impl Diagnostic for MismatchedStructLit {
     fn message(&self) -> String {
-        match self.base {
-            2 => "binary float literal is not supported".to_owned(),
-            8 => "octal float literal is not supported".to_owned(),
-            16 => "hexadecimal float literal is not supported".to_owned(),
-            _ => "unsupported base for floating pointer literal".to_owned(),
-        }
+        format!(
+            "mismatched struct literal kind. expected `{}`, found `{}`",
+            self.expected, self.found
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -732,19 +780,24 @@ impl Diagnostic for InvalidFloatingPointLiteral {
     }
 }
 
-/// An error that is emitted for a malformed literal (e.g. `0b22222`)
 #[derive(Debug)]
-pub struct InvalidLiteral {
-    pub literal: InFile<AstPtr<ast::Literal>>,
+pub struct MismatchedStructPat {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub expected: StructKind,
+    pub found: StructKind,
 }
 
-impl Diagnostic for InvalidLiteral {
+impl Diagnostic for MismatchedStructPat {
     fn message(&self) -> String {
-        "invalid literal value".to_owned()
+        format!(
+            "mismatched struct pattern kind. expected `{}`, found `{}`",
+            self.expected, self.found
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.pat.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -753,17 +806,26 @@ impl Diagnostic for InvalidLiteral {
 }
 
 #[derive(Debug)]
-pub struct FreeTypeAliasWithoutTypeRef {
-    pub type_alias_def: InFile<SyntaxNodePtr>,
+pub struct PatFieldCountMismatch {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub expected: usize,
+    pub found: usize,
 }
 
-impl Diagnostic for FreeTypeAliasWithoutTypeRef {
+impl Diagnostic for PatFieldCountMismatch {
     fn message(&self) -> String {
-        "free type alias without type ref".to_string()
+        format!(
+            "this pattern has {} field{}, but the corresponding tuple struct has {} field{}",
+            self.found,
+            if self.found == 1 { "" } else { "s" },
+            self.expected,
+            if self.expected == 1 { "" } else { "s" },
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.type_alias_def.clone()
+        InFile::new(self.file, self.pat.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -771,58 +833,70 @@ impl Diagnostic for FreeTypeAliasWithoutTypeRef {
     }
 }
 
+/// An error that is emitted if a tuple pattern has a different number of
+/// elements than the tuple that it destructures.
 #[derive(Debug)]
-pub struct UnresolvedImport {
-    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+pub struct TuplePatLengthMismatch {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub expected: usize,
+    pub found: usize,
 }
 
-impl Diagnostic for UnresolvedImport {
+impl Diagnostic for TuplePatLengthMismatch {
     fn message(&self) -> String {
-        "unresolved import".to_string()
+        format!(
+            "mismatched types. expected a tuple with {} element{}, found one with {} element{}",
+            self.expected,
+            if self.expected == 1 { "" } else { "s" },
+            self.found,
+            if self.found == 1 { "" } else { "s" },
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.use_tree.clone().map(Into::into)
+        InFile::new(self.file, self.pat.clone())
     }
 
-    fn as_any(&self) -> &(dyn Any + Send) {
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
         self
     }
 }
 
 #[derive(Debug)]
-pub struct ImportDuplicateDefinition {
-    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+pub struct NoFields {
+    pub file: FileId,
+    pub receiver_expr: SyntaxNodePtr,
+    pub found: Ty,
 }
 
-impl Diagnostic for ImportDuplicateDefinition {
+impl Diagnostic for NoFields {
     fn message(&self) -> String {
-        "a second item with the same name imported. Try to use an alias.".to_string()
+        "attempted to access a field on a primitive type.".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.use_tree.clone().map(Into::into)
+        InFile::new(self.file, self.receiver_expr.clone())
     }
 
-    fn as_any(&self) -> &(dyn Any + Send) {
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
         self
     }
 }
-
 #[derive(Debug)]
-pub struct PrivateTypeAlias {
-    pub type_alias_def: InFile<SyntaxNodePtr>,
-    pub kind: String,
-    pub name: String,
+pub struct NoSuchField {
+    pub file: FileId,
+    pub field: SyntaxNodePtr,
+    pub similar_name: Option<SimilarName>,
 }
 
-impl Diagnostic for PrivateTypeAlias {
+impl Diagnostic for NoSuchField {
     fn message(&self) -> String {
-        format!("{} `{}` is private", self.kind, self.name)
+        "no such field".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.type_alias_def.clone()
+        InFile::new(self.file, self.field.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -831,17 +905,463 @@ impl Diagnostic for PrivateTypeAlias {
 }
 
 #[derive(Debug)]
-pub struct ImplForForeignType {
-    pub impl_: InFile<AstPtr<ast::Impl>>,
+pub struct PossiblyUninitializedVariable {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+}
+
+impl Diagnostic for PossiblyUninitializedVariable {
+    fn message(&self) -> String {
+        "use of possibly-uninitialized variable".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// Describes how a binding that is not declared as `mut` is mutated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImmutableAssignmentKind {
+    /// A variable that is already initialized is assigned again, e.g. `a = 5`
+    Variable,
+
+    /// A parameter is assigned, e.g. `a = 5`
+    Argument,
+
+    /// A field or an element of the binding is assigned, e.g. `a.b = 5`
+    Place,
+}
+
+/// An error that is emitted when a binding that is not declared as `mut` is
+/// mutated.
+#[derive(Debug)]
+pub struct AssignToImmutable {
+    pub file: FileId,
+    pub place: SyntaxNodePtr,
+    pub place_text: String,
+    pub binding: SyntaxNodePtr,
+    pub name: String,
+    pub kind: ImmutableAssignmentKind,
+}
+
+impl Diagnostic for AssignToImmutable {
+    fn message(&self) -> String {
+        match self.kind {
+            ImmutableAssignmentKind::Variable => {
+                format!("cannot assign twice to immutable variable `{}`", self.name)
+            }
+            ImmutableAssignmentKind::Argument => {
+                format!("cannot assign to immutable argument `{}`", self.name)
+            }
+            ImmutableAssignmentKind::Place => format!(
+                "cannot assign to `{}`, as `{}` is not declared as mutable",
+                self.place_text, self.name
+            ),
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.place.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternCannotHaveBody {
+    pub func: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ExternCannotHaveBody {
+    fn message(&self) -> String {
+        "extern functions cannot have bodies".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.func.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternNonPrimitiveParam {
+    pub param: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ExternNonPrimitiveParam {
+    fn message(&self) -> String {
+        "extern functions can only have primitives as parameter- and return types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.param.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a function marked with `#[test]` that cannot
+/// be invoked as a test, e.g. because it takes arguments.
+#[derive(Debug)]
+pub struct InvalidTestFunction {
+    pub func: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for InvalidTestFunction {
+    fn message(&self) -> String {
+        "test functions must be declared at module level, cannot be extern, and must have the signature `fn()`".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.func.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a literal is too large to even parse
+#[derive(Debug)]
+pub struct IntLiteralTooLarge {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+}
+
+impl Diagnostic for IntLiteralTooLarge {
+    fn message(&self) -> String {
+        "int literal is too large".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a literal is too large for its suffix
+#[derive(Debug)]
+pub struct LiteralOutOfRange {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub int_ty: IntTy,
+}
+
+impl Diagnostic for LiteralOutOfRange {
+    fn message(&self) -> String {
+        format!("literal out of range for `{}`", self.int_ty.as_str())
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a literal with an invalid suffix (e.g.
+/// `123_foo`)
+#[derive(Debug)]
+pub struct InvalidLiteralSuffix {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub suffix: SmolStr,
+}
+
+impl Diagnostic for InvalidLiteralSuffix {
+    fn message(&self) -> String {
+        format!("invalid suffix `{}`", self.suffix)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a literal with a floating point suffix with a
+/// non 10 base (e.g. `0x123_f32`)
+#[derive(Debug)]
+pub struct InvalidFloatingPointLiteral {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub base: u32,
+}
+
+impl Diagnostic for InvalidFloatingPointLiteral {
+    fn message(&self) -> String {
+        match self.base {
+            2 => "binary float literal is not supported".to_owned(),
+            8 => "octal float literal is not supported".to_owned(),
+            16 => "hexadecimal float literal is not supported".to_owned(),
+            _ => "unsupported base for floating pointer literal".to_owned(),
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct FreeTypeAliasWithoutTypeRef {
+    pub type_alias_def: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for FreeTypeAliasWithoutTypeRef {
+    fn message(&self) -> String {
+        "free type alias without type ref".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.type_alias_def.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct UnresolvedImport {
+    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+}
+
+impl Diagnostic for UnresolvedImport {
+    fn message(&self) -> String {
+        "unresolved import".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.use_tree.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ImportDuplicateDefinition {
+    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+}
+
+impl Diagnostic for ImportDuplicateDefinition {
+    fn message(&self) -> String {
+        "a second item with the same name imported. Try to use an alias.".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.use_tree.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ImportAmbiguousGlob {
+    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+    pub name: String,
+}
+
+impl Diagnostic for ImportAmbiguousGlob {
+    fn message(&self) -> String {
+        format!(
+            "`{}` is imported by multiple glob imports. Try to import it explicitly.",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.use_tree.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct GlobImportNotModule {
+    pub use_tree: InFile<AstPtr<ast::UseTree>>,
+}
+
+impl Diagnostic for GlobImportNotModule {
+    fn message(&self) -> String {
+        "glob imports can only import items from a module".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.use_tree.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct PrivateTypeAlias {
+    pub type_alias_def: InFile<SyntaxNodePtr>,
+    pub kind: String,
+    pub name: String,
+}
+
+impl Diagnostic for PrivateTypeAlias {
+    fn message(&self) -> String {
+        format!("{} `{}` is private", self.kind, self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.type_alias_def.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ImplForForeignType {
+    pub impl_: InFile<AstPtr<ast::Impl>>,
+}
+
+impl Diagnostic for ImplForForeignType {
+    fn message(&self) -> String {
+        String::from("cannot define inherent `impl` for foreign type")
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.impl_.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidSelfTyImpl {
+    pub impl_: InFile<AstPtr<ast::Impl>>,
+}
+
+impl Diagnostic for InvalidSelfTyImpl {
+    fn message(&self) -> String {
+        String::from("inherent `impl` blocks can only be added for structs")
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.impl_.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a method is called that is not visible from the
+/// current scope
+#[derive(Debug)]
+pub struct MethodNotInScope {
+    pub method_call: InFile<AstPtr<ast::MethodCallExpr>>,
+    pub receiver_ty: Ty,
+}
+
+impl Diagnostic for MethodNotInScope {
+    fn message(&self) -> String {
+        "method not in scope for type".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.method_call.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if an unknown method is called
+#[derive(Debug)]
+pub struct MethodNotFound {
+    pub method_call: InFile<AstPtr<ast::MethodCallExpr>>,
+    pub receiver_ty: Ty,
+    pub method_name: Name,
+    pub field_with_same_name: Option<Ty>,
+    pub associated_function_with_same_name: Option<FunctionId>,
+}
+
+impl Diagnostic for MethodNotFound {
+    fn message(&self) -> String {
+        format!("method `{}` does not exist", &self.method_name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.method_call.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if the initializer of a constant contains an
+/// expression that cannot be evaluated at compile time.
+#[derive(Debug)]
+pub struct NotConstExpr {
+    pub expr: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for NotConstExpr {
+    fn message(&self) -> String {
+        "expression cannot be evaluated at compile time".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.expr.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
 }
 
-impl Diagnostic for ImplForForeignType {
+/// An error that is emitted if an operation in the initializer of a constant
+/// overflows.
+#[derive(Debug)]
+pub struct ConstOverflow {
+    pub expr: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ConstOverflow {
     fn message(&self) -> String {
-        String::from("cannot define inherent `impl` for foreign type")
+        "this arithmetic operation will overflow".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.impl_.clone().map(Into::into)
+        self.expr.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -849,18 +1369,19 @@ impl Diagnostic for ImplForForeignType {
     }
 }
 
+/// An error that is emitted if the initializer of a constant divides by zero.
 #[derive(Debug)]
-pub struct InvalidSelfTyImpl {
-    pub impl_: InFile<AstPtr<ast::Impl>>,
+pub struct DivisionByZero {
+    pub expr: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for InvalidSelfTyImpl {
+impl Diagnostic for DivisionByZero {
     fn message(&self) -> String {
-        String::from("inherent `impl` blocks can only be added for structs")
+        "attempt to divide by zero".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.impl_.clone().map(Into::into)
+        self.expr.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -868,21 +1389,20 @@ impl Diagnostic for InvalidSelfTyImpl {
     }
 }
 
-/// An error that is emitted if a method is called that is not visible from the
-/// current scope
+/// An error that is emitted if the value of a constant depends on itself.
 #[derive(Debug)]
-pub struct MethodNotInScope {
-    pub method_call: InFile<AstPtr<ast::MethodCallExpr>>,
-    pub receiver_ty: Ty,
+pub struct ConstEvalCycle {
+    pub const_def: InFile<SyntaxNodePtr>,
+    pub name: Name,
 }
 
-impl Diagnostic for MethodNotInScope {
+impl Diagnostic for ConstEvalCycle {
     fn message(&self) -> String {
-        "method not in scope for type".to_string()
+        format!("cycle detected when evaluating constant `{}`", self.name)
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.method_call.clone().map(Into::into)
+        self.const_def.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -890,23 +1410,385 @@ impl Diagnostic for MethodNotInScope {
     }
 }
 
-/// An error that is emitted if an unknown method is called
+/// An error that is emitted if the type of a static is not a primitive type.
+/// Only primitive values can be stored in a static and migrated across hot
+/// reloads.
 #[derive(Debug)]
-pub struct MethodNotFound {
-    pub method_call: InFile<AstPtr<ast::MethodCallExpr>>,
-    pub receiver_ty: Ty,
-    pub method_name: Name,
-    pub field_with_same_name: Option<Ty>,
-    pub associated_function_with_same_name: Option<FunctionId>,
+pub struct InvalidStaticType {
+    pub type_ref: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for MethodNotFound {
+impl Diagnostic for InvalidStaticType {
     fn message(&self) -> String {
-        format!("method `{}` does not exist", &self.method_name)
+        "static items must have a primitive type".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.method_call.clone().map(Into::into)
+        self.type_ref.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a static is accessed from another module than
+/// the one it is defined in. Every module is compiled into its own assembly and
+/// a static is only available in the assembly that defines it.
+#[derive(Debug)]
+pub struct StaticInOtherModule {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub name: Name,
+}
+
+impl Diagnostic for StaticInOtherModule {
+    fn message(&self) -> String {
+        format!(
+            "static `{}` can only be accessed from the module in which it is defined",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an attribute that is not known to the compiler
+#[derive(Debug)]
+pub struct UnknownAttribute {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: String,
+}
+
+impl Diagnostic for UnknownAttribute {
+    fn message(&self) -> String {
+        format!("unknown attribute `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an attribute with an invalid input (e.g.
+/// `#[inline(sometimes)]`)
+#[derive(Debug)]
+pub struct MalformedAttribute {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: String,
+    pub expected: &'static str,
+}
+
+impl Diagnostic for MalformedAttribute {
+    fn message(&self) -> String {
+        format!(
+            "malformed `{}` attribute, expected {}",
+            self.name, self.expected
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an attribute that can only be applied to
+/// functions but is applied to another item
+#[derive(Debug)]
+pub struct AttributeNotOnFunction {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: String,
+}
+
+impl Diagnostic for AttributeNotOnFunction {
+    fn message(&self) -> String {
+        format!(
+            "the `{}` attribute can only be applied to functions",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for an attribute that can only be applied to `gc`
+/// structs, e.g. `#[align(64)]` on a value struct
+#[derive(Debug)]
+pub struct AttributeNotOnGcStruct {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: String,
+}
+
+impl Diagnostic for AttributeNotOnGcStruct {
+    fn message(&self) -> String {
+        format!(
+            "the `{}` attribute can only be applied to gc structs",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted when a function marked with `#[deprecated]` is
+/// called
+#[derive(Debug)]
+pub struct DeprecatedCall {
+    pub call: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub note: Option<SmolStr>,
+}
+
+impl Diagnostic for DeprecatedCall {
+    fn message(&self) -> String {
+        match &self.note {
+            Some(note) => format!("use of deprecated function `{}`: {}", self.name, note),
+            None => format!("use of deprecated function `{}`", self.name),
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.call.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a lint attribute that refers to a lint that
+/// doesn't exist, e.g. `#[allow(unused_things)]`
+#[derive(Debug)]
+pub struct UnknownLint {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: SmolStr,
+}
+
+impl Diagnostic for UnknownLint {
+    fn message(&self) -> String {
+        format!("unknown lint: `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a variable that is never used
+#[derive(Debug)]
+pub struct UnusedVariable {
+    pub pat: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for UnusedVariable {
+    fn message(&self) -> String {
+        format!("unused variable: `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.pat.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a function that cannot be called by the host
+/// and that is never used in its package
+#[derive(Debug)]
+pub struct UnusedFunction {
+    pub name_ptr: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for UnusedFunction {
+    fn message(&self) -> String {
+        format!("function `{}` is never used", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.name_ptr.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a statement or expression that follows an
+/// expression that never returns, e.g. `return`
+#[derive(Debug)]
+pub struct UnreachableCode {
+    pub code: InFile<SyntaxNodePtr>,
+    pub is_statement: bool,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for UnreachableCode {
+    fn message(&self) -> String {
+        if self.is_statement {
+            String::from("unreachable statement")
+        } else {
+            String::from("unreachable expression")
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.code.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a binding that is declared as `mut` but that is
+/// never mutated
+#[derive(Debug)]
+pub struct UnusedMut {
+    pub pat: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for UnusedMut {
+    fn message(&self) -> String {
+        format!("variable `{}` does not need to be mutable", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.pat.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a loop label that is never referred to by a
+/// `break` or `continue` expression
+#[derive(Debug)]
+pub struct UnusedLabel {
+    pub label: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for UnusedLabel {
+    fn message(&self) -> String {
+        format!("unused label `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.label.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A lint that is emitted for a binding that shadows a binding of a different
+/// type, which is still used after the shadowing binding is declared
+#[derive(Debug)]
+pub struct ShadowedVariable {
+    pub pat: InFile<SyntaxNodePtr>,
+    pub shadowed: InFile<SyntaxNodePtr>,
+    pub later_use: InFile<SyntaxNodePtr>,
+    pub name: String,
+    pub ty: Ty,
+    pub shadowed_ty: Ty,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl Diagnostic for ShadowedVariable {
+    fn message(&self) -> String {
+        format!(
+            "variable `{}` shadows a variable of a different type that is still used",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.pat.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        self.level.severity().unwrap_or(Severity::Warning)
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -3,11 +3,11 @@ use std::{borrow::Cow, ops::Index, str::FromStr, sync::Arc};
 use either::Either;
 use la_arena::{Arena, ArenaMap, Idx};
 use mun_hir_input::FileId;
-pub use mun_syntax::ast::PrefixOp as UnaryOp;
+pub use mun_syntax::ast::{PrefixOp as UnaryOp, RangeOp};
 use mun_syntax::{
     ast,
     ast::{ArgListOwner, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner},
-    AstNode, AstPtr,
+    AstNode, AstPtr, SyntaxNodePtr,
 };
 use rustc_hash::FxHashMap;
 
@@ -74,6 +74,18 @@ impl Body {
                 collector = ExprCollector::new(def, src.file_id, db);
                 collector.collect_fn_body(&src.value);
             }
+            DefWithBodyId::ConstId(c) => {
+                let c = c.lookup(db);
+                let src = c.source(db);
+                collector = ExprCollector::new(def, src.file_id, db);
+                collector.collect_const_body(&src.value);
+            }
+            DefWithBodyId::StaticId(s) => {
+                let s = s.lookup(db);
+                let src = s.source(db);
+                collector = ExprCollector::new(def, src.file_id, db);
+                collector.collect_static_body(&src.value);
+            }
         }
 
         let (body, source_map) = collector.finish();
@@ -178,6 +190,20 @@ impl BodySourceMap {
         self.expr_map_back.get(expr).cloned()
     }
 
+    /// Returns the syntax of the label of the loop expression `expr`, e.g.
+    /// `'outer:` in `'outer: loop {}`, or `None` if the loop has no label.
+    pub(crate) fn label_syntax(
+        &self,
+        db: &dyn DefDatabase,
+        expr: ExprId,
+    ) -> Option<InFile<SyntaxNodePtr>> {
+        let src = self.expr_syntax(expr)?;
+        let root = db.parse(src.file_id).syntax_node();
+        let node = src.value.left()?.to_node(&root);
+        let label = node.syntax().children().find_map(ast::Label::cast)?;
+        Some(InFile::new(src.file_id, SyntaxNodePtr::new(label.syntax())))
+    }
+
     pub fn type_ref_syntax(&self, type_ref: LocalTypeRefId) -> Option<AstPtr<ast::TypeRef>> {
         self.type_refs.type_ref_syntax(type_ref)
     }
@@ -237,7 +263,8 @@ pub enum LiteralError {
     IntTooLarge,
 
     /// A lexer error occurred. This might happen if the literal is malformed
-    /// (e.g. 0b01012)
+    /// (e.g. 0b01012 or 0x). These errors are reported by the syntax
+    /// validation.
     LexerError,
 
     /// Encountered an unknown suffix
@@ -277,6 +304,25 @@ pub enum LiteralFloatKind {
 
 impl Eq for Literal {}
 
+impl Literal {
+    /// Parses the value of a literal from the syntax tree. Any errors that
+    /// were encountered while parsing the literal are returned as well.
+    pub fn from_ast(lit: &ast::Literal) -> (Literal, Vec<LiteralError>) {
+        match lit.kind() {
+            ast::LiteralKind::Bool(value) => (Literal::Bool(value), Vec::new()),
+            ast::LiteralKind::IntNumber(lit) => {
+                let (text, suffix) = lit.split_into_parts();
+                integer_lit(text, suffix)
+            }
+            ast::LiteralKind::FloatNumber(lit) => {
+                let (text, suffix) = lit.split_into_parts();
+                float_lit(text, suffix)
+            }
+            ast::LiteralKind::String(lit) => (Literal::String(lit.value()), Vec::new()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Expr {
     /// Used if the syntax tree does not have a required expression piece
@@ -318,13 +364,30 @@ pub enum Expr {
     },
     Break {
         expr: Option<ExprId>,
+        label: Option<Name>,
+    },
+    Continue {
+        label: Option<Name>,
     },
     Loop {
         body: ExprId,
+        label: Option<Name>,
     },
     While {
         condition: ExprId,
         body: ExprId,
+        label: Option<Name>,
+    },
+    For {
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+        label: Option<Name>,
+    },
+    Range {
+        start: ExprId,
+        end: ExprId,
+        op: RangeOp,
     },
     RecordLit {
         type_id: LocalTypeRefId,
@@ -336,6 +399,7 @@ pub enum Expr {
         name: Name,
     },
     Array(Vec<ExprId>),
+    Tuple(Vec<ExprId>),
     Literal(Literal),
 }
 
@@ -382,7 +446,7 @@ pub enum ArithOp {
 impl Expr {
     pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
         match self {
-            Expr::Missing | Expr::Path(_) | Expr::Literal(_) => {}
+            Expr::Missing | Expr::Path(_) | Expr::Literal(_) | Expr::Continue { .. } => {}
             Expr::Block { statements, tail } => {
                 for stmt in statements {
                     match stmt {
@@ -428,18 +492,28 @@ impl Expr {
                     f(*else_expr);
                 }
             }
-            Expr::Return { expr } | Expr::Break { expr } => {
+            Expr::Return { expr } | Expr::Break { expr, .. } => {
                 if let Some(expr) = expr {
                     f(*expr);
                 }
             }
-            Expr::Loop { body } => {
+            Expr::Loop { body, .. } => {
                 f(*body);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition, body, ..
+            } => {
                 f(*condition);
                 f(*body);
             }
+            Expr::For { iterable, body, .. } => {
+                f(*iterable);
+                f(*body);
+            }
+            Expr::Range { start, end, .. } => {
+                f(*start);
+                f(*end);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields {
                     f(field.expr);
@@ -452,7 +526,7 @@ impl Expr {
                 f(*base);
                 f(*index);
             }
-            Expr::Array(exprs) => {
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
                 for expr in exprs {
                     f(*expr);
                 }
@@ -461,17 +535,52 @@ impl Expr {
     }
 }
 
+/// A field of a record pattern, e.g. `b: c` in `Foo { a, b: c }`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordFieldPat {
+    pub name: Name,
+    pub pat: PatId,
+}
+
 /// Similar to `ast::PatKind`
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Pat {
-    Missing,             // Indicates an error
-    Wild,                // `_`
-    Path(Path),          // E.g. `foo::bar`
-    Bind { name: Name }, // E.g. `a`
+    Missing,    // Indicates an error
+    Wild,       // `_`
+    Path(Path), // E.g. `foo::bar`
+    Bind {
+        name: Name,
+        mutable: bool,
+    }, // E.g. `a` or `mut a`
+    // E.g. `Foo { a, b: c, .. }`
+    Record {
+        path: Option<Path>,
+        args: Vec<RecordFieldPat>,
+        ellipsis: bool,
+    },
+    // E.g. `Foo(a, _)`
+    TupleStruct {
+        path: Option<Path>,
+        args: Vec<PatId>,
+        ellipsis: bool,
+    },
+    // E.g. `(a, _, ..)`
+    Tuple {
+        args: Vec<PatId>,
+        ellipsis: bool,
+    },
 }
 
 impl Pat {
-    pub fn walk_child_pats(&self, mut _f: impl FnMut(PatId)) {}
+    pub fn walk_child_pats(&self, mut f: impl FnMut(PatId)) {
+        match self {
+            Pat::Missing | Pat::Wild | Pat::Path(_) | Pat::Bind { .. } => {}
+            Pat::Record { args, .. } => args.iter().for_each(|field| f(field.pat)),
+            Pat::TupleStruct { args, .. } | Pat::Tuple { args, .. } => {
+                args.iter().copied().for_each(f);
+            }
+        }
+    }
 }
 
 // Queries
@@ -546,7 +655,10 @@ impl<'a> ExprCollector<'a> {
         if let Some(param_list) = node.param_list() {
             if let Some(self_param) = param_list.self_param() {
                 let self_pat = self.alloc_pat(
-                    Pat::Bind { name: name![self] },
+                    Pat::Bind {
+                        name: name![self],
+                        mutable: self_param.is_mutable(),
+                    },
                     Either::Right(AstPtr::new(&self_param)),
                 );
 
@@ -579,6 +691,26 @@ impl<'a> ExprCollector<'a> {
         self.ret_type = Some(ret_type);
     }
 
+    fn collect_const_body(&mut self, node: &ast::ConstDef) {
+        let body = self.collect_expr_opt(node.body());
+        self.body_expr = Some(body);
+
+        let ret_type = self
+            .type_ref_builder
+            .alloc_from_node_opt(node.ascribed_type().as_ref());
+        self.ret_type = Some(ret_type);
+    }
+
+    fn collect_static_body(&mut self, node: &ast::StaticDef) {
+        let body = self.collect_expr_opt(node.body());
+        self.body_expr = Some(body);
+
+        let ret_type = self
+            .type_ref_builder
+            .alloc_from_node_opt(node.ascribed_type().as_ref());
+        self.ret_type = Some(ret_type);
+    }
+
     fn collect_block_opt(&mut self, block: Option<ast::BlockExpr>) -> ExprId {
         if let Some(block) = block {
             self.collect_block(block)
@@ -634,43 +766,22 @@ impl<'a> ExprCollector<'a> {
         match expr.kind() {
             ast::ExprKind::LoopExpr(expr) => self.collect_loop(expr),
             ast::ExprKind::WhileExpr(expr) => self.collect_while(expr),
+            ast::ExprKind::ForExpr(expr) => self.collect_for(expr),
             ast::ExprKind::ReturnExpr(r) => self.collect_return(r),
             ast::ExprKind::BreakExpr(r) => self.collect_break(r),
+            ast::ExprKind::ContinueExpr(r) => self.collect_continue(r),
             ast::ExprKind::BlockExpr(b) => self.collect_block(b),
-            ast::ExprKind::Literal(e) => match e.kind() {
-                ast::LiteralKind::Bool(value) => {
-                    let lit = Literal::Bool(value);
-                    self.alloc_expr(Expr::Literal(lit), syntax_ptr)
-                }
-                ast::LiteralKind::IntNumber(lit) => {
-                    let (text, suffix) = lit.split_into_parts();
-                    let (lit, errors) = integer_lit(text, suffix);
-                    let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
-
-                    for err in errors {
-                        self.diagnostics
-                            .push(ExprDiagnostic::LiteralError { expr: expr_id, err });
-                    }
+            ast::ExprKind::Literal(e) => {
+                let (lit, errors) = Literal::from_ast(&e);
+                let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
 
-                    expr_id
+                for err in errors {
+                    self.diagnostics
+                        .push(ExprDiagnostic::LiteralError { expr: expr_id, err });
                 }
-                ast::LiteralKind::FloatNumber(lit) => {
-                    let (text, suffix) = lit.split_into_parts();
-                    let (lit, errors) = float_lit(text, suffix);
-                    let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
-
-                    for err in errors {
-                        self.diagnostics
-                            .push(ExprDiagnostic::LiteralError { expr: expr_id, err });
-                    }
 
-                    expr_id
-                }
-                ast::LiteralKind::String(_lit) => {
-                    let lit = Literal::String(String::default());
-                    self.alloc_expr(Expr::Literal(lit), syntax_ptr)
-                }
-            },
+                expr_id
+            }
             ast::ExprKind::PrefixExpr(e) => {
                 let expr = self.collect_expr_opt(e.expr());
                 if let Some(op) = e.op_kind() {
@@ -910,11 +1021,24 @@ impl<'a> ExprCollector<'a> {
                 let exprs = e.exprs().map(|expr| self.collect_expr(expr)).collect();
                 self.alloc_expr(Expr::Array(exprs), syntax_ptr)
             }
+            ast::ExprKind::TupleExpr(e) => {
+                let exprs = e.exprs().map(|expr| self.collect_expr(expr)).collect();
+                self.alloc_expr(Expr::Tuple(exprs), syntax_ptr)
+            }
             ast::ExprKind::IndexExpr(e) => {
                 let base = self.collect_expr_opt(e.base());
                 let index = self.collect_expr_opt(e.index());
                 self.alloc_expr(Expr::Index { base, index }, syntax_ptr)
             }
+            ast::ExprKind::RangeExpr(e) => {
+                let start = self.collect_expr_opt(e.start());
+                let end = self.collect_expr_opt(e.end());
+                if let Some(op) = e.op_kind() {
+                    self.alloc_expr(Expr::Range { start, end, op }, syntax_ptr)
+                } else {
+                    self.alloc_expr(Expr::Missing, syntax_ptr)
+                }
+            }
         }
     }
 
@@ -937,9 +1061,57 @@ impl<'a> ExprCollector<'a> {
         let pattern = match pat.kind() {
             ast::PatKind::BindPat(bp) => {
                 let name = bp.name().map_or_else(Name::missing, |nr| nr.as_name());
-                Pat::Bind { name }
+                Pat::Bind {
+                    name,
+                    mutable: bp.is_mutable(),
+                }
             }
             ast::PatKind::PlaceholderPat(_) => Pat::Wild,
+            ast::PatKind::RecordPat(p) => {
+                let path = p.path().and_then(Path::from_ast);
+                let mut args = Vec::new();
+                let mut ellipsis = false;
+                if let Some(list) = p.record_field_pat_list() {
+                    for field in list.fields() {
+                        let Some(pat) = field.pat() else {
+                            continue;
+                        };
+                        // The shorthand `Foo { a }` binds the field `a` to a variable `a`
+                        let name = match (field.name_ref(), pat.kind()) {
+                            (Some(name_ref), _) => name_ref.as_name(),
+                            (None, ast::PatKind::BindPat(bp)) => {
+                                bp.name().map_or_else(Name::missing, |nr| nr.as_name())
+                            }
+                            (None, _) => Name::missing(),
+                        };
+                        let pat = self.collect_pat(pat);
+                        args.push(RecordFieldPat { name, pat });
+                    }
+                    ellipsis = list.has_rest();
+                }
+                Pat::Record {
+                    path,
+                    args,
+                    ellipsis,
+                }
+            }
+            ast::PatKind::TupleStructPat(p) => Pat::TupleStruct {
+                path: p.path().and_then(Path::from_ast),
+                args: p.args().map(|pat| self.collect_pat(pat)).collect(),
+                ellipsis: p.has_rest(),
+            },
+            ast::PatKind::TuplePat(p) => Pat::Tuple {
+                args: p.args().map(|pat| self.collect_pat(pat)).collect(),
+                ellipsis: p.has_rest(),
+            },
+            ast::PatKind::ParenPat(p) => {
+                let inner = self.collect_pat_opt(p.pat());
+                // make the paren pattern point to the inner pattern as well
+                self.source_map
+                    .pat_map
+                    .insert(Either::Left(AstPtr::new(&pat)), inner);
+                return inner;
+            }
         };
         let ptr = AstPtr::new(&pat);
         self.alloc_pat(pattern, Either::Left(ptr))
@@ -953,21 +1125,54 @@ impl<'a> ExprCollector<'a> {
 
     fn collect_break(&mut self, expr: ast::BreakExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = expr.label_name().map(|token| Name::new(token.text()));
         let expr = expr.expr().map(|e| self.collect_expr(e));
-        self.alloc_expr(Expr::Break { expr }, syntax_node_ptr)
+        self.alloc_expr(Expr::Break { expr, label }, syntax_node_ptr)
+    }
+
+    fn collect_continue(&mut self, expr: ast::ContinueExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = expr.label_name().map(|token| Name::new(token.text()));
+        self.alloc_expr(Expr::Continue { label }, syntax_node_ptr)
     }
 
     fn collect_loop(&mut self, expr: ast::LoopExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = collect_label(&expr);
         let body = self.collect_block_opt(expr.loop_body());
-        self.alloc_expr(Expr::Loop { body }, syntax_node_ptr)
+        self.alloc_expr(Expr::Loop { body, label }, syntax_node_ptr)
     }
 
     fn collect_while(&mut self, expr: ast::WhileExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = collect_label(&expr);
         let condition = self.collect_condition_opt(expr.condition());
         let body = self.collect_block_opt(expr.loop_body());
-        self.alloc_expr(Expr::While { condition, body }, syntax_node_ptr)
+        self.alloc_expr(
+            Expr::While {
+                condition,
+                body,
+                label,
+            },
+            syntax_node_ptr,
+        )
+    }
+
+    fn collect_for(&mut self, expr: ast::ForExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = collect_label(&expr);
+        let pat = self.collect_pat_opt(expr.pat());
+        let iterable = self.collect_expr_opt(expr.iterable());
+        let body = self.collect_block_opt(expr.loop_body());
+        self.alloc_expr(
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            },
+            syntax_node_ptr,
+        )
     }
 
     fn finish(mut self) -> (Body, BodySourceMap) {
@@ -990,6 +1195,13 @@ impl<'a> ExprCollector<'a> {
     }
 }
 
+/// Returns the name of the label of a loop, if it has one.
+fn collect_label(expr: &impl LoopBodyOwner) -> Option<Name> {
+    expr.label()
+        .and_then(|label| label.label_name())
+        .map(|token| Name::new(token.text()))
+}
+
 /// Removes any underscores from a string if present
 fn strip_underscores(s: &str) -> Cow<'_, str> {
     if s.contains('_') {
@@ -1065,6 +1277,9 @@ fn integer_lit(str: &str, suffix: Option<&str>) -> (Literal, Vec<LiteralError>)
     let str = &str[if base == 10 { 0 } else { 2 }..];
     let (value, err) = if let Ok(i) = u128::from_str_radix(str, base) {
         (i, None)
+    } else if str.is_empty() {
+        // A base prefix without any digits, e.g. `0x`
+        (0, Some(LiteralError::LexerError))
     } else {
         // Small bases are lexed as if they were base 10, e.g. the string might be
         // `0b10201`. This will cause the conversion above to fail.
@@ -1172,6 +1387,16 @@ mod test {
                 vec![LiteralError::LexerError]
             )
         );
+        assert_eq!(
+            integer_lit("0x_", Some("u8")),
+            (
+                Literal::Int(LiteralInt {
+                    kind: LiteralIntKind::Suffixed(PrimitiveInt::U8),
+                    value: 0
+                }),
+                vec![LiteralError::LexerError]
+            )
+        );
 
         assert_eq!(
             integer_lit("1234", Some("foo")),
@@ -1416,8 +1641,7 @@ mod diagnostics {
     use crate::{
         code_model::DefWithBody,
         diagnostics::{
-            DiagnosticSink, IntLiteralTooLarge, InvalidFloatingPointLiteral, InvalidLiteral,
-            InvalidLiteralSuffix,
+            DiagnosticSink, IntLiteralTooLarge, InvalidFloatingPointLiteral, InvalidLiteralSuffix,
         },
         HirDatabase,
     };
@@ -1445,7 +1669,8 @@ mod diagnostics {
                         });
                     match err {
                         LiteralError::IntTooLarge => sink.push(IntLiteralTooLarge { literal }),
-                        LiteralError::LexerError => sink.push(InvalidLiteral { literal }),
+                        // Malformed literals are reported by the syntax validation
+                        LiteralError::LexerError => {}
                         LiteralError::InvalidIntSuffix(suffix) => sink.push(InvalidLiteralSuffix {
                             literal,
                             suffix: SmolStr::new(suffix),
@@ -60,6 +60,11 @@ impl ExprScopes {
         scopes
     }
 
+    /// Returns the IDs of all scopes
+    pub(crate) fn scopes(&self) -> impl Iterator<Item = LocalScopeId> + '_ {
+        self.scopes.iter().map(|(id, _)| id)
+    }
+
     pub(crate) fn entries(&self, scope: LocalScopeId) -> &[ScopeEntry] {
         &self.scopes[scope].entries
     }
@@ -158,6 +163,17 @@ fn compute_expr_scopes(expr: ExprId, body: &Body, scopes: &mut ExprScopes, scope
         Expr::Block { statements, tail } => {
             compute_block_scopes(statements, *tail, body, scopes, scope);
         }
+        Expr::For {
+            pat,
+            iterable,
+            body: loop_body,
+            ..
+        } => {
+            compute_expr_scopes(*iterable, body, scopes, scope);
+            let scope = scopes.new_scope(scope);
+            scopes.add_bindings(body, scope, *pat);
+            compute_expr_scopes(*loop_body, body, scopes, scope);
+        }
         e => e.walk_child_exprs(|e| compute_expr_scopes(e, body, scopes, scope)),
     };
 }
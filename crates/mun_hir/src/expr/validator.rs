@@ -1,28 +1,30 @@
 use std::sync::Arc;
 
-use mun_syntax::{AstNode, SyntaxNodePtr};
+use mun_syntax::{ast::NameOwner, AstNode, SyntaxNodePtr};
 
 use crate::{
     code_model::src::HasSource,
+    code_model::DefWithBody,
     diagnostics::{
         CyclicType, DiagnosticSink, ExportedPrivate, ExternCannotHaveBody, ExternNonPrimitiveParam,
-        FreeTypeAliasWithoutTypeRef, PrivateTypeAlias,
+        FreeTypeAliasWithoutTypeRef, InvalidTestFunction, PrivateTypeAlias,
     },
     expr::BodySourceMap,
     in_file::InFile,
     resolve::HasResolver,
-    Body, Expr, Function, HasVisibility, HirDatabase, InferenceResult, Ty, TyKind, TypeAlias,
-    Visibility,
+    Body, Expr, HasVisibility, HirDatabase, InferenceResult, Ty, TyKind, TypeAlias, Visibility,
 };
 
+mod deprecated_call;
 mod literal_out_of_range;
+pub(crate) mod mutability;
 mod uninitialized_access;
 
 #[cfg(test)]
 mod tests;
 
 pub struct ExprValidator<'a> {
-    func: Function,
+    owner: DefWithBody,
     infer: Arc<InferenceResult>,
     body: Arc<Body>,
     body_source_map: Arc<BodySourceMap>,
@@ -30,12 +32,12 @@ pub struct ExprValidator<'a> {
 }
 
 impl<'a> ExprValidator<'a> {
-    pub fn new(func: Function, db: &'a dyn HirDatabase) -> Self {
-        let (body, body_source_map) = db.body_with_source_map(func.id.into());
+    pub fn new(owner: DefWithBody, db: &'a dyn HirDatabase) -> Self {
+        let (body, body_source_map) = db.body_with_source_map(owner.into());
         ExprValidator {
-            func,
+            owner,
             db,
-            infer: db.infer(func.id.into()),
+            infer: db.infer(owner.into()),
             body,
             body_source_map,
         }
@@ -44,13 +46,20 @@ impl<'a> ExprValidator<'a> {
     pub fn validate_body(&self, sink: &mut DiagnosticSink<'_>) {
         self.validate_literal_ranges(sink);
         self.validate_uninitialized_access(sink);
+        self.validate_mutability(sink);
         self.validate_extern(sink);
+        self.validate_test(sink);
         self.validate_privacy(sink);
+        self.validate_deprecated_calls(sink);
     }
 
     pub fn validate_privacy(&self, sink: &mut DiagnosticSink<'_>) {
-        let resolver = self.func.id.resolver(self.db.upcast());
-        let fn_data = self.func.data(self.db.upcast());
+        let DefWithBody::Function(func) = self.owner else {
+            return;
+        };
+
+        let resolver = func.id.resolver(self.db.upcast());
+        let fn_data = func.data(self.db.upcast());
         let ret_type_ref = fn_data.ret_type();
         let param_types = fn_data
             .params()
@@ -61,7 +70,7 @@ impl<'a> ExprValidator<'a> {
                 (ty, type_ref)
             });
 
-        let fn_visibility = self.func.visibility(self.db);
+        let fn_visibility = func.visibility(self.db);
         let type_is_allowed = |ty: &Ty| match fn_visibility {
             Visibility::Module(module_id) => {
                 ty.visibility(self.db).is_visible_from(self.db, module_id)
@@ -69,7 +78,7 @@ impl<'a> ExprValidator<'a> {
             Visibility::Public => ty.visibility(self.db).is_externally_visible(),
         };
 
-        let file_id = self.func.source(self.db.upcast()).file_id;
+        let file_id = func.source(self.db.upcast()).file_id;
         param_types
             .filter(|(ty, _)| !type_is_allowed(ty))
             .for_each(|(_, type_ref)| {
@@ -83,24 +92,57 @@ impl<'a> ExprValidator<'a> {
             });
     }
 
+    /// Validates that a function marked with `#[test]` can be invoked as a
+    /// test: it must be declared at module level, must have a body, and must not
+    /// take arguments or return a value.
+    pub fn validate_test(&self, sink: &mut DiagnosticSink<'_>) {
+        let DefWithBody::Function(func) = self.owner else {
+            return;
+        };
+
+        let fn_data = func.data(self.db.upcast());
+        if !fn_data.attrs().is_test() {
+            return;
+        }
+
+        if !func.is_test(self.db)
+            || func.is_extern(self.db)
+            || !fn_data.params().is_empty()
+            || !func.ret_type(self.db).is_empty()
+        {
+            let src = func.source(self.db.upcast());
+            sink.push(InvalidTestFunction {
+                func: src.map(|f| {
+                    f.name().map_or_else(
+                        || SyntaxNodePtr::new(f.syntax()),
+                        |name| SyntaxNodePtr::new(name.syntax()),
+                    )
+                }),
+            });
+        }
+    }
+
     pub fn validate_extern(&self, sink: &mut DiagnosticSink<'_>) {
-        if !self.func.is_extern(self.db) {
+        let DefWithBody::Function(func) = self.owner else {
+            return;
+        };
+
+        if !func.is_extern(self.db) {
             return;
         }
 
         // Validate that there is no body
-        match self.body[self.func.body(self.db).body_expr] {
+        match self.body[func.body(self.db).body_expr] {
             Expr::Missing => {}
             _ => sink.push(ExternCannotHaveBody {
-                func: self
-                    .func
+                func: func
                     .source(self.db.upcast())
                     .map(|f| SyntaxNodePtr::new(f.syntax())),
             }),
         }
 
-        if let Some(sig) = self.func.ty(self.db).callable_sig(self.db) {
-            let fn_data = self.func.data(self.db.upcast());
+        if let Some(sig) = func.ty(self.db).callable_sig(self.db) {
+            let fn_data = func.data(self.db.upcast());
             for (arg_ty, ty_ref) in sig.params().iter().zip(fn_data.params()) {
                 if arg_ty.as_struct().is_some() {
                     let arg_ptr = fn_data
@@ -109,7 +151,7 @@ impl<'a> ExprValidator<'a> {
                         .map(|ptr| ptr.syntax_node_ptr())
                         .unwrap();
                     sink.push(ExternNonPrimitiveParam {
-                        param: InFile::new(self.func.source(self.db.upcast()).file_id, arg_ptr),
+                        param: InFile::new(func.source(self.db.upcast()).file_id, arg_ptr),
                     });
                 }
             }
@@ -122,7 +164,7 @@ impl<'a> ExprValidator<'a> {
                     .map(|ptr| ptr.syntax_node_ptr())
                     .unwrap();
                 sink.push(ExternNonPrimitiveParam {
-                    param: InFile::new(self.func.source(self.db.upcast()).file_id, arg_ptr),
+                    param: InFile::new(func.source(self.db.upcast()).file_id, arg_ptr),
                 });
             }
         }
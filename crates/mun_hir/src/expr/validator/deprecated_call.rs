@@ -0,0 +1,40 @@
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DeprecatedCall, DiagnosticSink},
+    CallableDef, Expr, Function,
+};
+
+impl ExprValidator<'_> {
+    /// Iterates over all expressions to find calls to functions that are
+    /// marked with the `#[deprecated]` attribute.
+    pub fn validate_deprecated_calls(&self, sink: &mut DiagnosticSink<'_>) {
+        for (expr_id, expr) in self.body.exprs() {
+            let function: Function = match expr {
+                Expr::Call { callee, .. } => match self.infer[*callee].as_callable_def() {
+                    Some(CallableDef::Function(function)) => function,
+                    _ => continue,
+                },
+                Expr::MethodCall { .. } => match self.infer.method_resolution(expr_id) {
+                    Some(function) => function.into(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let Some(deprecation) = function.data(self.db.upcast()).attrs().deprecated() else {
+                continue;
+            };
+
+            let Some(call) = self.body_source_map.expr_syntax(expr_id) else {
+                continue;
+            };
+
+            sink.push(DeprecatedCall {
+                call: call
+                    .map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())),
+                name: function.name(self.db).to_string(),
+                note: deprecation.note,
+            });
+        }
+    }
+}
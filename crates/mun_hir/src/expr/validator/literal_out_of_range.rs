@@ -2,43 +2,66 @@ use super::ExprValidator;
 use crate::{
     diagnostics::{DiagnosticSink, LiteralOutOfRange},
     ty::{ResolveBitness, TyKind},
-    Expr, HirDisplay, Literal,
+    Expr, ExprId, Literal, UnaryOp,
 };
 
 impl ExprValidator<'_> {
     /// Iterates over all expressions to determine if one of the literals has a
     /// value that is out of range of its type.
     pub fn validate_literal_ranges(&self, sink: &mut DiagnosticSink<'_>) {
-        self.body[self.body.body_expr].walk_child_exprs(move |expr_id| {
-            let expr = &self.body[expr_id];
-            if let Expr::Literal(Literal::Int(lit)) = &expr {
-                let ty = &self.infer[expr_id];
-                match ty.interned() {
-                    TyKind::Int(int_ty) => {
-                        if lit.value > int_ty.resolve(&self.db.target_data_layout()).max() {
-                            let literal = self
-                                .body_source_map
-                                .expr_syntax(expr_id)
-                                .expect("could not retrieve expr from source map")
-                                .map(|expr_src| {
-                                    expr_src
-                                        .left()
-                                        .expect("could not retrieve expr from ExprSource")
-                                        .cast()
-                                        .expect("could not cast expression to literal")
-                                });
-                            sink.push(LiteralOutOfRange {
-                                literal,
-                                int_ty: *int_ty,
-                            });
-                        }
-                    }
-                    _ => panic!(
-                        "expected int literal to have int ty while instead it is `{}`",
-                        ty.display(self.db)
-                    ),
+        self.validate_literal_ranges_in(self.body.body_expr, false, sink);
+    }
+
+    /// Recursively validates the ranges of the literals in the specified
+    /// expression. If `negated` is true, the expression is the operand of a
+    /// negation in which case a signed literal may be one larger than the
+    /// maximum of its type (e.g. `-128_i8`).
+    fn validate_literal_ranges_in(
+        &self,
+        expr_id: ExprId,
+        negated: bool,
+        sink: &mut DiagnosticSink<'_>,
+    ) {
+        match &self.body[expr_id] {
+            Expr::Literal(Literal::Int(lit)) => {
+                // If the type of the literal is not an integer, a type mismatch has already been
+                // reported.
+                let TyKind::Int(int_ty) = self.infer[expr_id].interned() else {
+                    return;
+                };
+
+                let resolved_ty = int_ty.resolve(&self.db.target_data_layout());
+                let max = if negated && resolved_ty.signedness.is_signed() {
+                    resolved_ty.max() + 1
+                } else {
+                    resolved_ty.max()
+                };
+
+                if lit.value > max {
+                    let literal = self
+                        .body_source_map
+                        .expr_syntax(expr_id)
+                        .expect("could not retrieve expr from source map")
+                        .map(|expr_src| {
+                            expr_src
+                                .left()
+                                .expect("could not retrieve expr from ExprSource")
+                                .cast()
+                                .expect("could not cast expression to literal")
+                        });
+                    sink.push(LiteralOutOfRange {
+                        literal,
+                        int_ty: *int_ty,
+                    });
                 }
             }
-        });
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Neg,
+            } => self.validate_literal_ranges_in(*expr, true, sink),
+            expr => expr.walk_child_exprs(|child| {
+                self.validate_literal_ranges_in(child, false, sink);
+            }),
+        }
     }
 }
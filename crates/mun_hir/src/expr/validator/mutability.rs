@@ -0,0 +1,267 @@
+use rustc_hash::FxHashSet;
+
+use super::ExprValidator;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{AssignToImmutable, DiagnosticSink, ImmutableAssignmentKind},
+    resolve::{resolver_for_expr, ValueNs},
+    BinaryOp, Body, Expr, ExprId, HirDatabase, InferenceResult, Pat, PatId, Statement,
+};
+
+/// A mutation of a binding that requires the binding to be declared as `mut`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Mutation {
+    /// The expression that is assigned to, e.g. `a` or `a.b`
+    pub place: ExprId,
+    /// The binding that is mutated
+    pub binding: PatId,
+    pub kind: ImmutableAssignmentKind,
+}
+
+/// Returns all mutations of bindings in the body of `owner`, in the order in
+/// which they appear.
+pub(crate) fn binding_mutations(db: &dyn HirDatabase, owner: DefWithBody) -> Vec<Mutation> {
+    let body = db.body(owner.into());
+    let infer = db.infer(owner.into());
+
+    let mut collector = MutationCollector {
+        db,
+        body: &body,
+        infer: &infer,
+        parameters: body
+            .self_param()
+            .into_iter()
+            .chain(body.params())
+            .map(|(pat, _)| *pat)
+            .collect(),
+        record: true,
+        mutations: Vec::new(),
+    };
+
+    // Parameters are initialized when the function is called
+    let mut assigned = FxHashSet::default();
+    for pat in collector.parameters.clone() {
+        collector.bind_pat(&mut assigned, pat, true);
+    }
+    collector.collect_expr(&mut assigned, body.body_expr());
+
+    collector.mutations.sort_by_key(|mutation| mutation.place);
+    collector.mutations.dedup();
+    collector.mutations
+}
+
+struct MutationCollector<'a> {
+    db: &'a dyn HirDatabase,
+    body: &'a Body,
+    infer: &'a InferenceResult,
+    parameters: FxHashSet<PatId>,
+    /// Whether mutations are currently recorded. Loop bodies are visited
+    /// twice, but only recorded once.
+    record: bool,
+    mutations: Vec<Mutation>,
+}
+
+impl MutationCollector<'_> {
+    /// Collects the mutations in `expr`. `assigned` contains the bindings that
+    /// might have been assigned a value before `expr` is evaluated.
+    fn collect_expr(&mut self, assigned: &mut FxHashSet<PatId>, expr: ExprId) {
+        let body = self.body;
+        match &body[expr] {
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::Assignment { op }),
+            } => {
+                self.collect_expr(assigned, *rhs);
+                self.collect_place(assigned, *lhs, op.is_some());
+            }
+            Expr::Block { statements, tail } => {
+                for statement in statements.iter() {
+                    match statement {
+                        Statement::Let {
+                            pat, initializer, ..
+                        } => {
+                            if let Some(initializer) = initializer {
+                                self.collect_expr(assigned, *initializer);
+                            }
+                            self.bind_pat(assigned, *pat, initializer.is_some());
+                        }
+                        Statement::Expr(expr) => {
+                            self.collect_expr(assigned, *expr);
+                            if self.infer[*expr].is_never() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                if let Some(tail) = tail {
+                    self.collect_expr(assigned, *tail);
+                }
+            }
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.collect_expr(assigned, *condition);
+                let mut branches = vec![*then_branch];
+                branches.extend(*else_branch);
+
+                // Without an `else` branch, the `then` branch might be skipped
+                let mut result = if else_branch.is_some() {
+                    FxHashSet::default()
+                } else {
+                    assigned.clone()
+                };
+                for branch in branches {
+                    let mut branch_assigned = assigned.clone();
+                    self.collect_expr(&mut branch_assigned, branch);
+                    if !self.infer[branch].is_never() {
+                        result.extend(branch_assigned);
+                    }
+                }
+                *assigned = result;
+            }
+            Expr::Loop { body, .. } => self.collect_loop_body(assigned, *body),
+            Expr::While {
+                condition, body, ..
+            } => {
+                self.collect_expr(assigned, *condition);
+                self.collect_loop_body(assigned, *body);
+            }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                ..
+            } => {
+                self.collect_expr(assigned, *iterable);
+                self.bind_pat(assigned, *pat, true);
+                self.collect_loop_body(assigned, *body);
+            }
+            expr => expr.walk_child_exprs(|expr| self.collect_expr(assigned, expr)),
+        }
+    }
+
+    /// Collects the mutations in the body of a loop. The body is visited a
+    /// second time, because the first iteration might have assigned bindings
+    /// that are assigned again in the next iteration.
+    fn collect_loop_body(&mut self, assigned: &mut FxHashSet<PatId>, body: ExprId) {
+        let record = std::mem::replace(&mut self.record, false);
+        let mut first_iteration = assigned.clone();
+        self.collect_expr(&mut first_iteration, body);
+        self.record = record;
+
+        assigned.extend(first_iteration);
+        self.collect_expr(assigned, body);
+    }
+
+    /// Collects the mutation of the place expression `place` that is assigned
+    /// to. If `is_compound` is true, the place is read before it is assigned,
+    /// e.g. `a += 1`.
+    fn collect_place(&mut self, assigned: &mut FxHashSet<PatId>, place: ExprId, is_compound: bool) {
+        // Assigning to a field or an element mutates the binding that contains it
+        let mut root = place;
+        loop {
+            match &self.body[root] {
+                Expr::Field { expr, .. } => root = *expr,
+                Expr::Index { base, index } => {
+                    self.collect_expr(assigned, *index);
+                    root = *base;
+                }
+                _ => break,
+            }
+        }
+
+        let Some(binding) = self.resolve_local_binding(root) else {
+            self.collect_expr(assigned, root);
+            return;
+        };
+
+        let kind = if root != place {
+            Some(ImmutableAssignmentKind::Place)
+        } else if is_compound || assigned.contains(&binding) {
+            if self.parameters.contains(&binding) {
+                Some(ImmutableAssignmentKind::Argument)
+            } else {
+                Some(ImmutableAssignmentKind::Variable)
+            }
+        } else {
+            // The first assignment initializes the binding
+            None
+        };
+
+        assigned.insert(binding);
+        if let Some(kind) = kind {
+            if self.record {
+                self.mutations.push(Mutation {
+                    place,
+                    binding,
+                    kind,
+                });
+            }
+        }
+    }
+
+    /// Returns the binding that the path expression `expr` refers to, if any.
+    fn resolve_local_binding(&self, expr: ExprId) -> Option<PatId> {
+        let Expr::Path(path) = &self.body[expr] else {
+            return None;
+        };
+
+        let resolver = resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
+        match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+            Some((ValueNs::LocalBinding(pat), _)) => Some(pat),
+            _ => None,
+        }
+    }
+
+    /// Introduces the bindings of the pattern `pat`. Every binding is a new
+    /// variable, which is assigned if `initialized` is true.
+    fn bind_pat(&self, assigned: &mut FxHashSet<PatId>, pat: PatId, initialized: bool) {
+        if initialized {
+            assigned.insert(pat);
+        } else {
+            assigned.remove(&pat);
+        }
+        self.body[pat].walk_child_pats(|pat| self.bind_pat(assigned, pat, initialized));
+    }
+}
+
+impl ExprValidator<'_> {
+    /// Validates that bindings that are not declared as `mut` are not assigned
+    /// after they have been initialized.
+    pub(super) fn validate_mutability(&self, sink: &mut DiagnosticSink<'_>) {
+        let file = self.owner.file_id(self.db);
+        let parse = self.db.parse(file);
+        for mutation in binding_mutations(self.db, self.owner) {
+            let Pat::Bind { name, mutable } = &self.body[mutation.binding] else {
+                continue;
+            };
+            if *mutable {
+                continue;
+            }
+
+            let (Some(place), Some(binding)) = (
+                self.body_source_map.expr_syntax(mutation.place),
+                self.body_source_map.pat_syntax(mutation.binding),
+            ) else {
+                continue;
+            };
+            let place = place
+                .value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+
+            sink.push(AssignToImmutable {
+                file,
+                place_text: place.to_node(&parse.syntax_node()).text().to_string(),
+                place,
+                binding: binding
+                    .value
+                    .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()),
+                name: name.to_string(),
+                kind: mutation.kind,
+            });
+        }
+    }
+}
@@ -54,13 +54,13 @@ fn test_uninitialized_access_if() {
 fn test_uninitialized_access_while() {
     insta::assert_snapshot!(diagnostics(
         r#"
-    fn foo(b:i64) {
-        let a:i64;
+    fn foo(mut b:i64) {
+        let mut a:i64;
         while b < 4 { b += 1; a = b; a += 1; }
         let c = a + 4;  // `a` is possibly-unitialized
     }
     "#,
-    ), @"86..87: use of possibly-uninitialized variable");
+    ), @"94..95: use of possibly-uninitialized variable");
 }
 
 #[test]
@@ -181,3 +181,175 @@ fn test_type_alias_with_private_type_alias() {
     "#,
     ), @"17..36: type alias `Foo` is private");
 }
+
+#[test]
+fn test_deprecated_call() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    #[deprecated]
+    fn foo() {}
+
+    #[deprecated = "use `new` instead"]
+    fn bar() {}
+
+    struct Baz;
+    impl Baz {
+        #[deprecated]
+        fn baz(self) {}
+    }
+
+    fn main() {
+        foo();
+        bar();
+        Baz.baz();
+        let a = foo;
+    }
+    "#,
+    ), @"
+    156..161: use of deprecated function `foo`
+    167..172: use of deprecated function `bar`: use `new` instead
+    178..187: use of deprecated function `baz`
+    ");
+}
+
+#[test]
+fn test_assign_to_immutable() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    struct(gc) Foo { a: i32 }
+
+    impl Foo {
+        fn set(self, a: i32) {
+            self.a = a;
+        }
+        fn set_mut(mut self, a: i32) {
+            self.a = a;
+        }
+    }
+
+    fn main(a: i32, mut b: i32) {
+        a = 1;
+        b = 1;
+        let c = 1;
+        c += 1;
+        let d;
+        d = 1;
+        let foo = Foo { a: 1 };
+        foo.a = 2;
+        let arr = [1, 2, 3];
+        arr[0] = 4;
+    }
+    "#,
+    ), @r###"
+    189..190: cannot assign to immutable argument `a`
+    226..227: cannot assign twice to immutable variable `c`
+    288..293: cannot assign to `foo.a`, as `foo` is not declared as mutable
+    328..334: cannot assign to `arr[0]`, as `arr` is not declared as mutable
+    73..79: cannot assign to `self.a`, as `self` is not declared as mutable
+    "###);
+}
+
+#[test]
+fn test_assign_to_immutable_control_flow() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn branches(c: bool) {
+        let a;
+        if c { a = 1; } else { a = 2; }
+        let b;
+        if c { b = 1; }
+        b = 2;
+        let d;
+        if c { d = 1; return; }
+        d = 2;
+    }
+
+    fn loops(c: bool) {
+        let a;
+        loop { a = 1; }
+    }
+
+    fn fresh_bindings() {
+        while true {
+            let a;
+            a = 1;
+        }
+        for i in 0..3 {
+            let b = i;
+        }
+    }
+    "#,
+    ), @r###"
+    105..106: cannot assign twice to immutable variable `b`
+    207..208: cannot assign twice to immutable variable `a`
+    "###);
+}
+
+#[test]
+fn test_invalid_test_function() {
+    insta::assert_snapshot!(diagnostics(r#"
+    #[test]
+    fn valid() {}
+
+    #[test]
+    fn with_param(a: i32) {}
+
+    #[test]
+    fn with_return() -> i32 { 0 }
+
+    #[test]
+    extern fn external();
+
+    struct Foo;
+    impl Foo {
+        #[test]
+        fn method() {}
+    }
+
+    #[test = "value"]
+    fn malformed() {}
+    "#), @r#"
+    184..201: malformed `test` attribute, expected `#[test]`
+    34..44: test functions must be declared at module level, cannot be extern, and must have the signature `fn()`
+    68..79: test functions must be declared at module level, cannot be extern, and must have the signature `fn()`
+    114..122: test functions must be declared at module level, cannot be extern, and must have the signature `fn()`
+    169..175: test functions must be declared at module level, cannot be extern, and must have the signature `fn()`
+    "#);
+}
+
+#[test]
+fn test_uninitialized_access_annotated() {
+    check_diagnostics(
+        r#"
+    fn foo() {
+        let a:i64;
+        let b = a + 3;
+              //^ error: use of possibly-uninitialized variable
+        let c: i64 = true;
+                   //^^^^ error: mismatched type
+    }
+    "#,
+    );
+}
+
+#[test]
+fn test_uninitialized_access_annotated_multiple_files() {
+    check_diagnostics(
+        r#"
+    //- /mod.mun
+    pub fn foo() {
+        let mut a:i64;
+        a += 1;
+      //^ error: use of possibly-uninitialized variable
+    }
+
+    //- /bar.mun
+    fn bar() -> i64 {
+        let b:i64;
+        if true { b = 3; }
+        b
+      //^ error: use of possibly-uninitialized variable
+    }
+    "#,
+    );
+}
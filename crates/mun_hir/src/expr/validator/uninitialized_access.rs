@@ -22,11 +22,11 @@ impl ExprValidator<'_> {
         // Add all parameter patterns to the set of initialized patterns (they must have
         // been initialized)
         if let Some((pat, _)) = self.body.self_param {
-            initialized_patterns.insert(pat);
+            self.initialize_pat(&mut initialized_patterns, pat);
         }
 
         for (pat, _) in self.body.params.iter() {
-            initialized_patterns.insert(*pat);
+            self.initialize_pat(&mut initialized_patterns, *pat);
         }
 
         self.validate_expr_access(
@@ -135,7 +135,7 @@ impl ExprValidator<'_> {
                                     *initializer,
                                     ExprKind::Normal,
                                 );
-                                initialized_patterns.insert(*pat);
+                                self.initialize_pat(initialized_patterns, *pat);
                             }
                         }
                         Statement::Expr(expr) => {
@@ -155,15 +155,17 @@ impl ExprValidator<'_> {
                     self.validate_expr_access(sink, initialized_patterns, *tail, ExprKind::Normal);
                 }
             }
-            Expr::Return { expr } | Expr::Break { expr } => {
+            Expr::Return { expr } | Expr::Break { expr, .. } => {
                 if let Some(expr) = expr {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Loop { body } => {
+            Expr::Loop { body, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *body, ExprKind::Normal);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition, body, ..
+            } => {
                 self.validate_expr_access(sink, initialized_patterns, *condition, ExprKind::Normal);
                 self.validate_expr_access(
                     sink,
@@ -172,6 +174,26 @@ impl ExprValidator<'_> {
                     ExprKind::Normal,
                 );
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                ..
+            } => {
+                self.validate_expr_access(sink, initialized_patterns, *iterable, ExprKind::Normal);
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                self.initialize_pat(&mut body_initialized_patterns, *pat);
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
+            Expr::Range { start, end, .. } => {
+                self.validate_expr_access(sink, initialized_patterns, *start, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *end, ExprKind::Normal);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields.iter() {
                     self.validate_expr_access(
@@ -189,15 +211,22 @@ impl ExprValidator<'_> {
                 self.validate_expr_access(sink, initialized_patterns, *base, ExprKind::Normal);
                 self.validate_expr_access(sink, initialized_patterns, *index, ExprKind::Normal);
             }
-            Expr::Array(exprs) => {
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
                 for expr in exprs {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Literal(_) | Expr::Missing => {}
+            Expr::Literal(_) | Expr::Continue { .. } | Expr::Missing => {}
         }
     }
 
+    /// Marks the specified pattern and all the bindings it destructures into
+    /// as initialized.
+    fn initialize_pat(&self, initialized_patterns: &mut HashSet<PatId>, pat: PatId) {
+        initialized_patterns.insert(pat);
+        self.body[pat].walk_child_pats(|pat| self.initialize_pat(initialized_patterns, pat));
+    }
+
     fn validate_path_access(
         &self,
         sink: &mut DiagnosticSink<'_>,
@@ -215,10 +244,10 @@ impl ExprValidator<'_> {
         if expr_side == ExprKind::Normal || expr_side == ExprKind::Both {
             // Check if the binding has already been initialized
             if initialized_patterns.get(&pat).is_none() {
-                let (_, body_source_map) = self.db.body_with_source_map(self.func.id.into());
                 sink.push(PossiblyUninitializedVariable {
-                    file: self.func.file_id(self.db),
-                    pat: body_source_map
+                    file: self.owner.file_id(self.db),
+                    pat: self
+                        .body_source_map
                         .expr_syntax(expr)
                         .unwrap()
                         .value
@@ -2,8 +2,8 @@ use mun_hir_input::ModuleId;
 
 use crate::{
     ids::{
-        AssocItemId, AssocItemLoc, FunctionId, ImplId, ItemContainerId, Lookup, StructId,
-        TypeAliasId, VariantId,
+        AssocItemId, AssocItemLoc, ConstId, FunctionId, ImplId, ItemContainerId, Lookup, StaticId,
+        StructId, TypeAliasId, VariantId,
     },
     item_tree::ItemTreeNode,
     DefDatabase,
@@ -53,6 +53,18 @@ impl HasModule for TypeAliasId {
     }
 }
 
+impl HasModule for ConstId {
+    fn module(&self, db: &dyn DefDatabase) -> ModuleId {
+        self.lookup(db).module
+    }
+}
+
+impl HasModule for StaticId {
+    fn module(&self, db: &dyn DefDatabase) -> ModuleId {
+        self.lookup(db).module
+    }
+}
+
 impl HasModule for AssocItemId {
     fn module(&self, db: &dyn DefDatabase) -> ModuleId {
         match self {
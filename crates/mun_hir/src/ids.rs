@@ -3,9 +3,9 @@ use std::hash::{Hash, Hasher};
 use mun_hir_input::ModuleId;
 
 use crate::{
-    item_tree::{Function, Impl, ItemTreeId, ItemTreeNode, Struct, TypeAlias},
+    item_tree::{Const, Function, Impl, ItemTreeId, ItemTreeNode, Static, Struct, TypeAlias},
     primitive_type::PrimitiveType,
-    DefDatabase,
+    BuiltinFunction, DefDatabase,
 };
 
 #[derive(Clone, Debug)]
@@ -134,6 +134,18 @@ impl_intern!(
     lookup_intern_type_alias
 );
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstId(salsa::InternId);
+
+pub(crate) type ConstLoc = ItemLoc<Const>;
+impl_intern!(ConstId, ConstLoc, intern_const, lookup_intern_const);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticId(salsa::InternId);
+
+pub(crate) type StaticLoc = ItemLoc<Static>;
+impl_intern!(StaticId, StaticLoc, intern_static, lookup_intern_static);
+
 pub trait Intern {
     type ID;
     fn intern(self, db: &dyn DefDatabase) -> Self::ID;
@@ -150,7 +162,10 @@ pub enum ItemDefinitionId {
     FunctionId(FunctionId),
     StructId(StructId),
     TypeAliasId(TypeAliasId),
+    ConstId(ConstId),
+    StaticId(StaticId),
     PrimitiveType(PrimitiveType),
+    BuiltinFunction(BuiltinFunction),
 }
 
 impl From<ModuleId> for ItemDefinitionId {
@@ -177,12 +192,30 @@ impl From<TypeAliasId> for ItemDefinitionId {
     }
 }
 
+impl From<ConstId> for ItemDefinitionId {
+    fn from(id: ConstId) -> Self {
+        ItemDefinitionId::ConstId(id)
+    }
+}
+
+impl From<StaticId> for ItemDefinitionId {
+    fn from(id: StaticId) -> Self {
+        ItemDefinitionId::StaticId(id)
+    }
+}
+
 impl From<PrimitiveType> for ItemDefinitionId {
     fn from(id: PrimitiveType) -> Self {
         ItemDefinitionId::PrimitiveType(id)
     }
 }
 
+impl From<BuiltinFunction> for ItemDefinitionId {
+    fn from(id: BuiltinFunction) -> Self {
+        ItemDefinitionId::BuiltinFunction(id)
+    }
+}
+
 /// Items that are associated with an `impl`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum AssocItemId {
@@ -190,9 +223,12 @@ pub enum AssocItemId {
 }
 
 /// Definitions which have a body
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBodyId {
     FunctionId(FunctionId),
+    ConstId(ConstId),
+    StaticId(StaticId),
 }
 
 impl From<FunctionId> for DefWithBodyId {
@@ -201,6 +237,18 @@ impl From<FunctionId> for DefWithBodyId {
     }
 }
 
+impl From<ConstId> for DefWithBodyId {
+    fn from(id: ConstId) -> Self {
+        DefWithBodyId::ConstId(id)
+    }
+}
+
+impl From<StaticId> for DefWithBodyId {
+    fn from(id: StaticId) -> Self {
+        DefWithBodyId::StaticId(id)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VariantId {
     StructId(StructId),
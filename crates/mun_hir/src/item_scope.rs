@@ -5,6 +5,7 @@ use once_cell::sync::Lazy;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
+    builtin_function::BuiltinFunction,
     ids::{ImplId, ItemDefinitionId},
     primitive_type::PrimitiveType,
     visibility::Visibility,
@@ -59,6 +60,11 @@ pub(crate) struct AddResolutionFromImportResult {
 
     /// Whether or not adding the resolution will overwrite an existing entry
     pub duplicate: bool,
+
+    /// Whether or not the resolution was imported through a glob import while
+    /// another glob import already brought a different item with the same
+    /// name into scope.
+    pub ambiguous: bool,
 }
 
 pub(crate) static BUILTIN_SCOPE: Lazy<FxHashMap<Name, PerNs<(ItemDefinitionId, Visibility)>>> =
@@ -71,6 +77,12 @@ pub(crate) static BUILTIN_SCOPE: Lazy<FxHashMap<Name, PerNs<(ItemDefinitionId, V
                     PerNs::types(((*ty).into(), Visibility::Public)),
                 )
             })
+            .chain(BuiltinFunction::ALL.iter().map(|(name, function)| {
+                (
+                    name.clone(),
+                    PerNs::values(((*function).into(), Visibility::Public)),
+                )
+            }))
             .collect()
     });
 
@@ -139,6 +151,7 @@ impl ItemScope {
     ) -> AddResolutionFromImportResult {
         let mut changed = false;
         let mut duplicate = false;
+        let mut ambiguous = false;
 
         macro_rules! check_changed {
             (
@@ -182,11 +195,18 @@ impl ItemScope {
                         }
                         $changed = true;
                     }
-                    (Entry::Occupied(_), Some(_)) => {
+                    (Entry::Occupied(entry), Some((item, _))) => {
                         let is_previous_from_glob = $glob_imports.$field.contains(&$lookup);
                         let is_explicit_import = matches!($def_import_type, ImportType::Named);
                         if is_explicit_import && !is_previous_from_glob {
                             duplicate = true;
+                        } else if !is_explicit_import
+                            && is_previous_from_glob
+                            && entry.get().0 != item
+                        {
+                            // Two glob imports bring a different item into scope under the same
+                            // name. The first one is kept.
+                            ambiguous = true;
                         }
                     }
                     _ => {}
@@ -207,7 +227,11 @@ impl ItemScope {
             def_import_type
         );
 
-        AddResolutionFromImportResult { changed, duplicate }
+        AddResolutionFromImportResult {
+            changed,
+            duplicate,
+            ambiguous,
+        }
     }
 
     /// Gets a name from the current module scope
@@ -226,7 +250,10 @@ impl PerNs<(ItemDefinitionId, Visibility)> {
         has_constructor: bool,
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         match def {
-            ItemDefinitionId::FunctionId(_) => PerNs::values((def, vis)),
+            ItemDefinitionId::FunctionId(_)
+            | ItemDefinitionId::ConstId(_)
+            | ItemDefinitionId::StaticId(_)
+            | ItemDefinitionId::BuiltinFunction(_) => PerNs::values((def, vis)),
             ItemDefinitionId::StructId(_) => {
                 if has_constructor {
                     PerNs::both((def, vis), (def, vis))
@@ -5,6 +5,7 @@ mod tests;
 
 use std::{
     any::type_name,
+    collections::HashMap,
     fmt,
     fmt::Formatter,
     hash::{Hash, Hasher},
@@ -18,6 +19,7 @@ use mun_hir_input::FileId;
 use mun_syntax::ast;
 
 use crate::{
+    attrs::Attrs,
     path::ImportAlias,
     source_id::{AstIdNode, FileAstId},
     type_ref::{LocalTypeRefId, TypeRefMap},
@@ -91,6 +93,11 @@ impl ItemTree {
         let ptr = map.get(id);
         ptr.to_node(&root.syntax_node())
     }
+
+    /// Returns the attributes attached to the specified item.
+    pub fn attrs(&self, item: ModItem) -> &Attrs {
+        self.data.attrs.get(&item).unwrap_or(&Attrs::EMPTY)
+    }
 }
 
 #[derive(Default, Debug, Eq, PartialEq)]
@@ -117,9 +124,12 @@ struct ItemTreeData {
     structs: Arena<Struct>,
     fields: Arena<Field>,
     type_aliases: Arena<TypeAlias>,
+    consts: Arena<Const>,
+    statics: Arena<Static>,
     impls: Arena<Impl>,
 
     visibilities: ItemVisibilities,
+    attrs: HashMap<ModItem, Attrs>,
 }
 
 /// Trait implemented by all item nodes in the item tree.
@@ -228,6 +238,8 @@ mod_items! {
     Function in functions -> ast::FunctionDef,
     Struct in structs -> ast::StructDef,
     TypeAlias in type_aliases -> ast::TypeAliasDef,
+    Const in consts -> ast::ConstDef,
+    Static in statics -> ast::StaticDef,
     Import in imports -> ast::Use,
     Impl in impls -> ast::Impl,
 }
@@ -374,6 +386,24 @@ pub struct TypeAlias {
     pub ast_id: FileAstId<ast::TypeAliasDef>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Const {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub types: TypeRefMap,
+    pub type_ref: LocalTypeRefId,
+    pub ast_id: FileAstId<ast::ConstDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Static {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub types: TypeRefMap,
+    pub type_ref: LocalTypeRefId,
+    pub ast_id: FileAstId<ast::StaticDef>,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum AssociatedItem {
     Function(LocalItemTreeId<Function>),
@@ -471,12 +501,19 @@ impl<T> PartialEq for IdRange<T> {
 impl<T> Eq for IdRange<T> {}
 
 mod diagnostics {
-    use mun_syntax::{AstNode, SyntaxNodePtr};
+    use mun_syntax::{
+        ast::{self, AttrsOwner},
+        AstNode, AstPtr, SyntaxNodePtr,
+    };
 
     use super::{ItemTree, ModItem};
     use crate::{
-        diagnostics::DuplicateDefinition, DefDatabase, DiagnosticSink, HirDatabase, InFile, Name,
-        Path,
+        attrs::AttrError,
+        diagnostics::{
+            AttributeNotOnFunction, AttributeNotOnGcStruct, DuplicateDefinition,
+            MalformedAttribute, UnknownAttribute, UnknownLint,
+        },
+        DefDatabase, DiagnosticSink, HirDatabase, InFile, Name, Path,
     };
 
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -486,6 +523,11 @@ mod diagnostics {
             first: ModItem,
             second: ModItem,
         },
+        InvalidAttribute {
+            item: ModItem,
+            index: usize,
+            error: AttrError,
+        },
     }
 
     impl ItemTreeDiagnostic {
@@ -513,6 +555,14 @@ mod diagnostics {
                         item_tree.file_id,
                         SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
                     ),
+                    ModItem::Const(item) => InFile::new(
+                        item_tree.file_id,
+                        SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
+                    ),
+                    ModItem::Static(item) => InFile::new(
+                        item_tree.file_id,
+                        SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
+                    ),
                     ModItem::Import(it) => {
                         let import = &item_tree[it];
                         let import_src = item_tree.source(db, it);
@@ -543,7 +593,53 @@ mod diagnostics {
                     first_definition: ast_ptr_from_mod(db.upcast(), item_tree, *first),
                     definition: ast_ptr_from_mod(db.upcast(), item_tree, *second),
                 }),
+                ItemTreeDiagnostic::InvalidAttribute { item, index, error } => {
+                    let attr = attr_ptr(db.upcast(), item_tree, *item, *index);
+                    let name = item_tree.attrs(*item)[*index].name.to_string();
+                    match error {
+                        AttrError::Unknown => sink.push(UnknownAttribute { attr, name }),
+                        AttrError::Malformed { expected } => sink.push(MalformedAttribute {
+                            attr,
+                            name,
+                            expected,
+                        }),
+                        AttrError::NotOnFunction => {
+                            sink.push(AttributeNotOnFunction { attr, name });
+                        }
+                        AttrError::NotOnGcStruct => {
+                            sink.push(AttributeNotOnGcStruct { attr, name });
+                        }
+                        AttrError::UnknownLint { name } => sink.push(UnknownLint {
+                            attr,
+                            name: name.clone(),
+                        }),
+                    }
+                }
             };
         }
     }
+
+    /// Returns a pointer to the attribute at `index` of the specified item.
+    fn attr_ptr(
+        db: &dyn DefDatabase,
+        item_tree: &ItemTree,
+        item: ModItem,
+        index: usize,
+    ) -> InFile<AstPtr<ast::Attr>> {
+        fn nth_attr(owner: &impl AttrsOwner, index: usize) -> AstPtr<ast::Attr> {
+            let attr = owner.attrs().nth(index).expect("cannot find attribute");
+            AstPtr::new(&attr)
+        }
+
+        let ptr = match item {
+            ModItem::Function(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::Struct(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::TypeAlias(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::Const(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::Static(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::Import(it) => nth_attr(&item_tree.source(db, it), index),
+            ModItem::Impl(it) => nth_attr(&item_tree.source(db, it), index),
+        };
+        InFile::new(item_tree.file_id, ptr)
+    }
 }
@@ -10,11 +10,13 @@ use mun_syntax::ast::{
 use smallvec::SmallVec;
 
 use super::{
-    diagnostics, AssociatedItem, Field, Fields, Function, FunctionFlags, IdRange, Impl, ItemTree,
-    ItemTreeData, ItemTreeNode, ItemVisibilities, LocalItemTreeId, ModItem, Param, ParamAstId,
-    RawVisibilityId, Struct, TypeAlias,
+    diagnostics, AssociatedItem, Const, Field, Fields, Function, FunctionFlags, IdRange, Impl,
+    ItemTree, ItemTreeData, ItemTreeNode, ItemVisibilities, LocalItemTreeId, ModItem, Param,
+    ParamAstId, RawVisibilityId, Static, Struct, TypeAlias,
 };
 use crate::{
+    attrs::{AttrTarget, Attrs},
+    cfg::CfgOptions,
     item_tree::Import,
     name::AsName,
     source_id::AstIdMap,
@@ -46,6 +48,7 @@ impl<N: ItemTreeNode> From<Idx<N>> for LocalItemTreeId<N> {
 pub(super) struct Context {
     file: FileId,
     source_ast_id_map: Arc<AstIdMap>,
+    cfg_options: Arc<CfgOptions>,
     data: ItemTreeData,
     diagnostics: Vec<diagnostics::ItemTreeDiagnostic>,
 }
@@ -56,6 +59,7 @@ impl Context {
         Self {
             file,
             source_ast_id_map: db.ast_id_map(file),
+            cfg_options: db.cfg_options(),
             data: ItemTreeData::default(),
             diagnostics: Vec::new(),
         }
@@ -77,6 +81,8 @@ impl Context {
                 ModItem::Function(item) => Some(&self.data.functions[item.index].name),
                 ModItem::Struct(item) => Some(&self.data.structs[item.index].name),
                 ModItem::TypeAlias(item) => Some(&self.data.type_aliases[item.index].name),
+                ModItem::Const(item) => Some(&self.data.consts[item.index].name),
+                ModItem::Static(item) => Some(&self.data.statics[item.index].name),
                 ModItem::Import(item) => {
                     let import = &self.data.imports[item.index];
                     if import.is_glob {
@@ -114,10 +120,16 @@ impl Context {
 
     /// Lowers a single module item
     fn lower_mod_item(&mut self, item: &ast::ModuleItem) -> Option<ModItems> {
+        if !self.is_cfg_enabled(item) {
+            return None;
+        }
+
         match item.kind() {
             ast::ModuleItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
             ast::ModuleItemKind::StructDef(ast) => self.lower_struct(&ast).map(Into::into),
             ast::ModuleItemKind::TypeAliasDef(ast) => self.lower_type_alias(&ast).map(Into::into),
+            ast::ModuleItemKind::ConstDef(ast) => self.lower_const(&ast).map(Into::into),
+            ast::ModuleItemKind::StaticDef(ast) => self.lower_static(&ast).map(Into::into),
             ast::ModuleItemKind::Use(ast) => Some(ModItems(
                 self.lower_use(&ast).into_iter().map(Into::into).collect(),
             )),
@@ -125,6 +137,34 @@ impl Context {
         }
     }
 
+    /// Returns `true` if the specified item is not excluded from compilation by
+    /// its `#[cfg(...)]` attributes.
+    fn is_cfg_enabled(&self, owner: &(impl ast::AttrsOwner + ast::DocCommentsOwner)) -> bool {
+        Attrs::from_ast(owner).is_cfg_enabled(&self.cfg_options)
+    }
+
+    /// Lowers the attributes of the specified item and validates them.
+    fn lower_attrs(
+        &mut self,
+        item: impl Into<ModItem>,
+        owner: &(impl ast::AttrsOwner + ast::DocCommentsOwner),
+        target: AttrTarget,
+    ) {
+        let item = item.into();
+        let attrs = Attrs::from_ast(owner);
+        if attrs.is_empty() {
+            return;
+        }
+
+        for (index, attr) in attrs.iter().enumerate() {
+            if let Err(error) = attr.validate(target) {
+                self.diagnostics
+                    .push(diagnostics::ItemTreeDiagnostic::InvalidAttribute { item, index, error });
+            }
+        }
+        self.data.attrs.insert(item, attrs);
+    }
+
     /// Lowers a `use` statement
     fn lower_use(&mut self, use_item: &ast::Use) -> Vec<LocalItemTreeId<Import>> {
         let visibility = lower_visibility(use_item);
@@ -148,6 +188,10 @@ impl Context {
             );
         });
 
+        for import in imports.iter() {
+            self.lower_attrs(*import, use_item, AttrTarget::Other);
+        }
+
         imports
     }
 
@@ -216,7 +260,9 @@ impl Context {
             flags,
         };
 
-        Some(self.data.functions.alloc(res).into())
+        let id = self.data.functions.alloc(res).into();
+        self.lower_attrs(id, func, AttrTarget::Function);
+        Some(id)
     }
 
     /// Lowers a struct
@@ -235,7 +281,13 @@ impl Context {
             fields,
             ast_id,
         };
-        Some(self.data.structs.alloc(res).into())
+        let id = self.data.structs.alloc(res).into();
+        let memory_kind = strukt
+            .memory_type_specifier()
+            .map(|s| s.kind())
+            .unwrap_or_default();
+        self.lower_attrs(id, strukt, AttrTarget::Struct(memory_kind));
+        Some(id)
     }
 
     /// Lowers the fields of a struct or enum
@@ -306,7 +358,49 @@ impl Context {
             type_ref,
             ast_id,
         };
-        Some(self.data.type_aliases.alloc(res).into())
+        let id = self.data.type_aliases.alloc(res).into();
+        self.lower_attrs(id, type_alias, AttrTarget::Other);
+        Some(id)
+    }
+
+    /// Lowers a constant (e.g. `const FOO: i32 = 5;`)
+    fn lower_const(&mut self, konst: &ast::ConstDef) -> Option<LocalItemTreeId<Const>> {
+        let name = konst.name()?.as_name();
+        let visibility = lower_visibility(konst);
+        let mut types = TypeRefMap::builder();
+        let type_ref = types.alloc_from_node_opt(konst.ascribed_type().as_ref());
+        let ast_id = self.source_ast_id_map.ast_id(konst);
+        let (types, _types_source_map) = types.finish();
+        let res = Const {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id,
+        };
+        let id = self.data.consts.alloc(res).into();
+        self.lower_attrs(id, konst, AttrTarget::Other);
+        Some(id)
+    }
+
+    /// Lowers a static (e.g. `static COUNTER: u64 = 0;`)
+    fn lower_static(&mut self, static_def: &ast::StaticDef) -> Option<LocalItemTreeId<Static>> {
+        let name = static_def.name()?.as_name();
+        let visibility = lower_visibility(static_def);
+        let mut types = TypeRefMap::builder();
+        let type_ref = types.alloc_from_node_opt(static_def.ascribed_type().as_ref());
+        let ast_id = self.source_ast_id_map.ast_id(static_def);
+        let (types, _types_source_map) = types.finish();
+        let res = Static {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id,
+        };
+        let id = self.data.statics.alloc(res).into();
+        self.lower_attrs(id, static_def, AttrTarget::Other);
+        Some(id)
     }
 
     fn lower_impl(&mut self, impl_def: &ast::Impl) -> Option<LocalItemTreeId<Impl>> {
@@ -330,10 +424,16 @@ impl Context {
             ast_id,
         };
 
-        Some(self.data.impls.alloc(res).into())
+        let id = self.data.impls.alloc(res).into();
+        self.lower_attrs(id, impl_def, AttrTarget::Other);
+        Some(id)
     }
 
     fn lower_associated_item(&mut self, item: &ast::AssociatedItem) -> Option<AssociatedItem> {
+        if !self.is_cfg_enabled(item) {
+            return None;
+        }
+
         let item: AssociatedItem = match item.kind() {
             ast::AssociatedItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
         }?;
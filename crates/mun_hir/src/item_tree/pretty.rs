@@ -1,9 +1,10 @@
 use std::{fmt, fmt::Write};
 
 use crate::{
+    attrs::{Attr, AttrInput},
     item_tree::{
-        Fields, Function, Impl, Import, ItemTree, LocalItemTreeId, ModItem, Param, RawVisibilityId,
-        Struct, TypeAlias,
+        Const, Fields, Function, Impl, Import, ItemTree, LocalItemTreeId, ModItem, Param,
+        RawVisibilityId, Static, Struct, TypeAlias,
     },
     path::ImportAlias,
     pretty::{print_path, print_type_ref},
@@ -64,10 +65,13 @@ impl Printer<'_> {
 
     /// Print a module item to the buffer.
     fn print_mod_item(&mut self, item: ModItem) -> fmt::Result {
+        self.print_attrs(item)?;
         match item {
             ModItem::Function(it) => self.print_function(it),
             ModItem::Struct(it) => self.print_struct(it),
             ModItem::TypeAlias(it) => self.print_type_alias(it),
+            ModItem::Const(it) => self.print_const(it),
+            ModItem::Static(it) => self.print_static(it),
             ModItem::Import(it) => self.print_use(it),
             ModItem::Impl(it) => self.print_impl(it),
         }
@@ -115,6 +119,36 @@ impl Printer<'_> {
         writeln!(self, ";")
     }
 
+    /// Prints a constant to the buffer.
+    fn print_const(&mut self, it: LocalItemTreeId<Const>) -> fmt::Result {
+        let Const {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id: _,
+        } = &self.tree[it];
+        self.print_visibility(*visibility)?;
+        write!(self, "const {name}: ")?;
+        self.print_type_ref(*type_ref, types)?;
+        writeln!(self, " = _;")
+    }
+
+    /// Prints a static to the buffer.
+    fn print_static(&mut self, it: LocalItemTreeId<Static>) -> fmt::Result {
+        let Static {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id: _,
+        } = &self.tree[it];
+        self.print_visibility(*visibility)?;
+        write!(self, "static {name}: ")?;
+        self.print_type_ref(*type_ref, types)?;
+        writeln!(self, " = _;")
+    }
+
     /// Prints a struct to the buffer.
     fn print_struct(&mut self, it: LocalItemTreeId<Struct>) -> fmt::Result {
         let Struct {
@@ -205,6 +239,29 @@ impl Printer<'_> {
         writeln!(self, ";")
     }
 
+    /// Prints the attributes of an item to the buffer.
+    fn print_attrs(&mut self, item: ModItem) -> fmt::Result {
+        let attrs = self.tree.attrs(item);
+        if let Some(docs) = attrs.docs() {
+            for line in docs.lines() {
+                if line.is_empty() {
+                    writeln!(self, "///")?;
+                } else {
+                    writeln!(self, "/// {line}")?;
+                }
+            }
+        }
+        for Attr { name, input } in attrs.iter() {
+            match input {
+                None => writeln!(self, "#[{name}]")?,
+                Some(AttrInput::String(value)) => writeln!(self, "#[{name} = {value:?}]")?,
+                Some(AttrInput::Literal(value)) => writeln!(self, "#[{name} = {value}]")?,
+                Some(AttrInput::TokenTree(value)) => writeln!(self, "#[{name}({value})]")?,
+            }
+        }
+        Ok(())
+    }
+
     /// Prints a [`RawVisibilityId`] to the buffer.
     fn print_visibility(&mut self, vis: RawVisibilityId) -> fmt::Result {
         match &self.tree[vis] {
@@ -238,7 +295,7 @@ impl Printer<'_> {
             }
             Ok(())
         })?;
-        write!(self, "}}")
+        writeln!(self, "}}")
     }
 }
 
@@ -1,12 +1,19 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use mun_db::Upcast;
 use mun_hir_input::WithFixture;
 
-use crate::{mock::MockDatabase, DefDatabase, DiagnosticSink};
+use crate::{mock::MockDatabase, CfgOptions, DefDatabase, DiagnosticSink};
 
 fn print_item_tree(text: &str) -> Result<String, fmt::Error> {
-    let (db, file_id) = MockDatabase::with_single_file(text);
+    print_item_tree_with_features(text, &[])
+}
+
+fn print_item_tree_with_features(text: &str, features: &[&str]) -> Result<String, fmt::Error> {
+    let (mut db, file_id) = MockDatabase::with_single_file(text);
+    db.set_cfg_options(Arc::new(CfgOptions::with_features(
+        features.iter().copied(),
+    )));
     let item_tree = db.item_tree(file_id);
     let mut result_str = super::pretty::print_item_tree(db.upcast(), &item_tree)?;
     let mut sink = DiagnosticSink::new(|diag| {
@@ -94,3 +101,141 @@ fn test_duplicate_import() {
     )
     .unwrap());
 }
+
+#[test]
+fn test_attributes() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[inline]
+    #[export_name = "exported_foo"]
+    pub fn foo() {}
+
+    #[deprecated = "use `foo` instead"]
+    fn bar() {}
+
+    impl Bar {
+        #[inline(always)]
+        fn baz() {}
+    }
+
+    #[inline(sometimes)]
+    fn qux() {}
+
+    #[export_name]
+    fn quux() {}
+
+    #[inline]
+    struct Bar;
+
+    #[unknown]
+    #[foo::bar = 5]
+    use foo::Baz;
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_link_namespace_attribute() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[link_namespace = "host::math"]
+    extern fn sin(x: f32) -> f32;
+
+    #[link_namespace = "host::"]
+    extern fn cos(x: f32) -> f32;
+
+    #[link_namespace]
+    extern fn tan(x: f32) -> f32;
+
+    #[link_namespace = "host"]
+    struct Foo;
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_align_attribute() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[align(64)]
+    struct Foo(f32, f32, f32, f32);
+
+    #[align(48)]
+    struct Bar;
+
+    #[align = 8]
+    struct Baz;
+
+    #[align(16)]
+    struct(value) Qux;
+
+    #[align(16)]
+    fn quux() {}
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_cfg_attributes() {
+    insta::assert_snapshot!(print_item_tree_with_features(
+        r#"
+    #[cfg(feature = "debug_draw")]
+    fn draw_debug() {}
+
+    #[cfg(not(feature = "debug_draw"))]
+    fn draw_release() {}
+
+    #[cfg(all(feature = "debug_draw", feature = "profiling"))]
+    struct Profiler;
+
+    #[cfg(any(feature = "debug_draw", feature = "profiling"))]
+    use foo::Bar;
+
+    impl Foo {
+        #[cfg(feature = "profiling")]
+        fn profile() {}
+
+        fn update() {}
+    }
+
+    #[cfg(feature = "profiling")]
+    fn foo() -> i32 { 0 }
+
+    #[cfg(not(feature = "profiling"))]
+    fn foo() -> i32 { 1 }
+
+    #[cfg(target_os = "linux")]
+    fn malformed() {}
+    "#,
+        &["debug_draw"]
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_doc_comments() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    /// Adds two numbers.
+    ///
+    /// The result wraps on overflow.
+    #[inline]
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+    /** A point in 2D space */
+    struct Point(f32, f32);
+
+    // Not a doc comment
+    fn undocumented() {}
+
+    impl Point {
+        /// Creates a new point.
+        fn new(x: f32, y: f32) -> Self { Point(x, y) }
+    }
+    "#
+    )
+    .unwrap());
+}
@@ -11,10 +11,14 @@ pub use mun_hir_input::ModuleId;
 pub use salsa;
 
 pub use self::code_model::{
-    Field, Function, FunctionData, HasSource, Module, ModuleDef, Package, Struct, StructMemoryKind,
-    TypeAlias,
+    Const, ConstData, Field, Function, FunctionData, HasSource, LifecycleHook, Module, ModuleDef,
+    Package, Static, StaticData, Struct, StructMemoryKind, TypeAlias,
 };
 pub use crate::{
+    attrs::{Attr, AttrInput, Attrs, Deprecation, InlineAttr},
+    builtin_function::BuiltinFunction,
+    cfg::{CfgExpr, CfgOptions},
+    const_eval::{ConstEvalError, ConstValue},
     db::{
         AstDatabase, AstDatabaseStorage, DefDatabase, DefDatabaseStorage, HirDatabase,
         HirDatabaseStorage, InternDatabase, InternDatabaseStorage,
@@ -22,11 +26,14 @@ pub use crate::{
     diagnostics::{Diagnostic, DiagnosticSink},
     display::HirDisplay,
     expr::{
-        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, Ordering, Pat,
-        PatId, RecordLitField, Statement, UnaryOp,
+        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LiteralError,
+        LiteralFloat, LiteralFloatKind, LiteralInt, LiteralIntKind, LogicOp, Ordering, Pat, PatId,
+        RangeOp, RecordFieldPat, RecordLitField, Statement, UnaryOp,
     },
     ids::{AssocItemId, ItemLoc},
     in_file::InFile,
+    lint::{Lint, LintLevel, LintLevels, LintSource},
+    memory_usage::{memory_usage, LruCapacities, QueryMemoryUsage},
     name::Name,
     name_resolution::{Namespace, PerNs},
     path::{Path, PathKind},
@@ -42,7 +49,11 @@ use crate::{name::AsName, source_id::AstIdMap};
 
 #[macro_use]
 mod macros;
+mod attrs;
+mod builtin_function;
+mod cfg;
 mod code_model;
+mod const_eval;
 mod db;
 pub mod diagnostics;
 mod display;
@@ -50,6 +61,8 @@ mod expr;
 mod ids;
 mod in_file;
 mod item_tree;
+mod lint;
+mod memory_usage;
 mod name;
 mod name_resolution;
 mod path;
@@ -63,8 +76,8 @@ mod utils;
 mod has_module;
 mod item_scope;
 pub mod method_resolution;
-#[cfg(test)]
-mod mock;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
 mod package_defs;
 mod pretty;
 pub mod semantics;
@@ -0,0 +1,270 @@
+//! Lints are checks for code that is valid but likely a mistake, e.g. a
+//! variable that is never used. Every lint has a level that determines whether
+//! it is reported as a warning, as an error, or not at all.
+//!
+//! The levels of a package are configured with [`LintLevels`]. An item can
+//! override the level of a lint with the `#[allow(...)]`, `#[warn(...)]` or
+//! `#[deny(...)]` attributes.
+
+mod dead_code;
+mod shadowed_variables;
+mod unreachable_code;
+mod unused_labels;
+mod unused_mut;
+mod unused_variables;
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    attrs::Attrs, diagnostics::Severity, name, DiagnosticSink, Function, HirDatabase, Name,
+};
+
+pub(crate) use self::dead_code::used_functions_query;
+
+/// A check for code that is valid but likely a mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// A variable that is never used
+    UnusedVariables,
+
+    /// A function that is never used
+    DeadCode,
+
+    /// Code that is never executed
+    UnreachableCode,
+
+    /// A variable that is declared as `mut` but never mutated
+    UnusedMut,
+
+    /// A variable that shadows a variable of a different type which is still
+    /// used afterwards
+    ShadowedVariables,
+
+    /// A loop label that is never referred to by a `break` or `continue`
+    UnusedLabels,
+}
+
+impl Lint {
+    /// All lints
+    pub const ALL: &'static [Lint] = &[
+        Lint::UnusedVariables,
+        Lint::DeadCode,
+        Lint::UnreachableCode,
+        Lint::UnusedMut,
+        Lint::ShadowedVariables,
+        Lint::UnusedLabels,
+    ];
+
+    /// Returns the name of the lint as used in attributes and manifests, e.g.
+    /// `unused_variables`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused_variables",
+            Lint::DeadCode => "dead_code",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::UnusedMut => "unused_mut",
+            Lint::ShadowedVariables => "shadowed_variables",
+            Lint::UnusedLabels => "unused_labels",
+        }
+    }
+
+    /// Returns the lint with the specified name.
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::ALL.iter().copied().find(|lint| lint.name() == name)
+    }
+
+    /// Returns the level of the lint if it is not configured.
+    pub fn default_level(self) -> LintLevel {
+        LintLevel::Warn
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Determines how a [`Lint`] is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+
+    /// The lint is reported as a warning
+    Warn,
+
+    /// The lint is reported as an error, which prevents compilation
+    Deny,
+}
+
+impl LintLevel {
+    /// Returns the name of the level, which is also the name of the attribute
+    /// that sets it.
+    pub fn name(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        }
+    }
+
+    /// Returns the severity of the diagnostics of a lint with this level, or
+    /// `None` if the lint is not reported.
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+
+    /// Returns the level that is set by the attribute with the specified name.
+    pub(crate) fn from_attr_name(name: &Name) -> Option<LintLevel> {
+        if *name == name![allow] {
+            Some(LintLevel::Allow)
+        } else if *name == name![warn] {
+            Some(LintLevel::Warn)
+        } else if *name == name![deny] {
+            Some(LintLevel::Deny)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Where the level of a reported lint was specified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSource {
+    /// The lint has its default level
+    Default,
+
+    /// The level is configured for the package, e.g. in its manifest
+    Package,
+
+    /// The level is set with an attribute on the item
+    Attribute,
+}
+
+/// The levels of lints that are configured for a package. Lints that are not
+/// configured have their default level.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: FxHashMap<Lint, LintLevel>,
+}
+
+impl LintLevels {
+    /// Returns levels that allow all lints.
+    pub fn allow_all() -> Self {
+        LintLevels {
+            levels: Lint::ALL
+                .iter()
+                .map(|lint| (*lint, LintLevel::Allow))
+                .collect(),
+        }
+    }
+
+    /// Sets the level of the specified lint.
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    /// Returns the level of the specified lint.
+    pub fn get(&self, lint: Lint) -> LintLevel {
+        self.levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+/// The level of a lint for a specific item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ItemLint {
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub source: LintSource,
+}
+
+impl ItemLint {
+    /// Determines the level of `lint` for an item with the specified
+    /// attributes.
+    fn new(db: &dyn HirDatabase, lint: Lint, attrs: &Attrs) -> Self {
+        let (level, source) = if let Some(level) = attrs.lint_level(lint) {
+            (level, LintSource::Attribute)
+        } else {
+            let levels = db.lint_levels();
+            match levels.levels.get(&lint) {
+                Some(level) => (*level, LintSource::Package),
+                None => (lint.default_level(), LintSource::Default),
+            }
+        };
+
+        ItemLint {
+            lint,
+            level,
+            source,
+        }
+    }
+
+    /// Returns `true` if the lint is reported.
+    fn is_enabled(&self) -> bool {
+        self.level != LintLevel::Allow
+    }
+}
+
+/// Checks the specified function for all lints and reports them to the `sink`.
+pub(crate) fn check_function(
+    db: &dyn HirDatabase,
+    function: Function,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let data = function.data(db.upcast());
+    let attrs = data.attrs();
+
+    let lint = ItemLint::new(db, Lint::DeadCode, attrs);
+    if lint.is_enabled() {
+        dead_code::check_function(db, function, lint, sink);
+    }
+
+    // Extern functions don't have a body to check
+    if function.is_extern(db) {
+        return;
+    }
+
+    let lint = ItemLint::new(db, Lint::UnusedVariables, attrs);
+    if lint.is_enabled() {
+        unused_variables::check_body(db, function.into(), lint, sink);
+    }
+
+    let lint = ItemLint::new(db, Lint::UnreachableCode, attrs);
+    if lint.is_enabled() {
+        unreachable_code::check_body(db, function.into(), lint, sink);
+    }
+
+    let lint = ItemLint::new(db, Lint::UnusedMut, attrs);
+    if lint.is_enabled() {
+        unused_mut::check_body(db, function.into(), lint, sink);
+    }
+
+    let lint = ItemLint::new(db, Lint::ShadowedVariables, attrs);
+    if lint.is_enabled() {
+        shadowed_variables::check_body(db, function.into(), lint, sink);
+    }
+
+    let lint = ItemLint::new(db, Lint::UnusedLabels, attrs);
+    if lint.is_enabled() {
+        unused_labels::check_body(db, function.into(), lint, sink);
+    }
+}
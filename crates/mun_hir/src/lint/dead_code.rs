@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use mun_hir_input::PackageId;
+use mun_syntax::{ast::NameOwner, AstNode, SyntaxNodePtr};
+use rustc_hash::FxHashSet;
+
+use super::ItemLint;
+use crate::{
+    code_model::{AssocItem, DefWithBody},
+    diagnostics::{DiagnosticSink, UnusedFunction},
+    ids::FunctionId,
+    CallableDef, Expr, Function, HasSource, HasVisibility, HirDatabase, ModuleDef, Package,
+};
+
+/// Returns all functions of a package that are referenced from the body of
+/// another function, a constant, or a static in the package.
+pub(crate) fn used_functions_query(
+    db: &dyn HirDatabase,
+    package: PackageId,
+) -> Arc<FxHashSet<FunctionId>> {
+    let mut used_functions = FxHashSet::default();
+
+    let bodies = Package { id: package }
+        .modules(db)
+        .into_iter()
+        .flat_map(|module| {
+            let declarations = module
+                .declarations(db)
+                .into_iter()
+                .filter_map(|def| match def {
+                    ModuleDef::Function(f) => Some(DefWithBody::Function(f)),
+                    ModuleDef::Const(c) => Some(DefWithBody::Const(c)),
+                    ModuleDef::Static(s) => Some(DefWithBody::Static(s)),
+                    _ => None,
+                });
+            let assoc_functions = module
+                .impls(db)
+                .into_iter()
+                .flat_map(|item| item.items(db))
+                .map(|AssocItem::Function(f)| DefWithBody::Function(f));
+            declarations.chain(assoc_functions).collect::<Vec<_>>()
+        });
+
+    for owner in bodies {
+        let body = db.body(owner.into());
+        let infer = db.infer(owner.into());
+        for (expr_id, expr) in body.exprs() {
+            let function = match expr {
+                Expr::Path(_) => match infer[expr_id].as_callable_def() {
+                    Some(CallableDef::Function(function)) => function,
+                    _ => continue,
+                },
+                Expr::MethodCall { .. } => match infer.method_resolution(expr_id) {
+                    Some(function) => function.into(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            // A function that only calls itself is still unused
+            if owner != DefWithBody::Function(function) {
+                used_functions.insert(function.id);
+            }
+        }
+    }
+
+    Arc::new(used_functions)
+}
+
+/// Reports the function if it cannot be called by the host and it is not used
+/// anywhere in its package.
+pub(super) fn check_function(
+    db: &dyn HirDatabase,
+    function: Function,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    // Public functions and functions with an explicit export name or link namespace
+    // can be called by the host. Extern functions are provided by the host.
+    // Lifecycle hooks and tests are invoked by the runtime.
+    let data = function.data(db.upcast());
+    if function.visibility(db).is_externally_visible()
+        || function.is_extern(db)
+        || data.attrs().export_name().is_some()
+        || data.attrs().link_namespace().is_some()
+        || function.lifecycle_hook(db).is_some()
+        || function.is_test(db)
+    {
+        return;
+    }
+
+    let package = function.module(db).package();
+    if db.used_functions(package.id).contains(&function.id) {
+        return;
+    }
+
+    let source = function.source(db.upcast());
+    sink.push(UnusedFunction {
+        name_ptr: source.map(|fn_def| {
+            fn_def.name().map_or_else(
+                || SyntaxNodePtr::new(fn_def.syntax()),
+                |name| SyntaxNodePtr::new(name.syntax()),
+            )
+        }),
+        name: function.name(db).to_string(),
+        lint: lint.lint,
+        level: lint.level,
+        source: lint.source,
+    });
+}
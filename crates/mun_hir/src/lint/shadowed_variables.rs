@@ -0,0 +1,99 @@
+use rustc_hash::FxHashMap;
+
+use super::ItemLint;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{DiagnosticSink, ShadowedVariable},
+    expr::scope::ScopeEntry,
+    resolve::{resolver_for_expr, ValueNs},
+    Expr, ExprId, HirDatabase, PatId,
+};
+
+/// Reports all bindings in the body of `owner` that shadow a binding of a
+/// different type which is still used after the shadowing binding is
+/// declared, e.g. because the shadowing binding is declared in a nested block.
+pub(super) fn check_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBody,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let def = owner.into();
+    let (body, source_map) = db.body_with_source_map(def);
+    let scopes = db.expr_scopes(def);
+    let infer = db.infer(def);
+
+    // Find the uses of all bindings
+    let mut uses: FxHashMap<PatId, Vec<ExprId>> = FxHashMap::default();
+    for (expr_id, expr) in body.exprs() {
+        let Expr::Path(path) = expr else {
+            continue;
+        };
+
+        let resolver = resolver_for_expr(db.upcast(), body.owner(), expr_id);
+        if let Some((ValueNs::LocalBinding(pat), _)) =
+            resolver.resolve_path_as_value_fully(db.upcast(), path)
+        {
+            uses.entry(pat).or_default().push(expr_id);
+        }
+    }
+
+    for scope in scopes.scopes() {
+        for entry in scopes.entries(scope) {
+            // The binding that is visible where `entry` is declared
+            let Some(shadowed): Option<PatId> = scopes
+                .scope_chain(Some(scope))
+                .skip(1)
+                .flat_map(|scope| scopes.entries(scope).iter().rev())
+                .find(|it| it.name() == entry.name())
+                .map(ScopeEntry::pat)
+            else {
+                continue;
+            };
+
+            let (ty, shadowed_ty) = (&infer[entry.pat()], &infer[shadowed]);
+            if !ty.is_known() || !shadowed_ty.is_known() || ty == shadowed_ty {
+                continue;
+            }
+
+            let (Some(source), Some(shadowed_source)) = (
+                source_map.pat_syntax(entry.pat()),
+                source_map.pat_syntax(shadowed),
+            ) else {
+                continue;
+            };
+            let source =
+                source.map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()));
+            let shadowed_source = shadowed_source
+                .map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()));
+
+            // A use of the shadowed binding after the declaration of the shadowing
+            // binding means that the shadowed binding is still live.
+            let later_use = uses
+                .get(&shadowed)
+                .into_iter()
+                .flatten()
+                .filter_map(|expr| source_map.expr_syntax(*expr))
+                .map(|src| {
+                    src.map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()))
+                })
+                .filter(|src| src.value.range().start() >= source.value.range().end())
+                .min_by_key(|src| src.value.range().start());
+            let Some(later_use) = later_use else {
+                continue;
+            };
+
+            sink.push(ShadowedVariable {
+                pat: source,
+                shadowed: shadowed_source,
+                later_use,
+                name: entry.name().to_string(),
+                ty: ty.clone(),
+                shadowed_ty: shadowed_ty.clone(),
+                lint: lint.lint,
+                level: lint.level,
+                source: lint.source,
+            });
+        }
+    }
+}
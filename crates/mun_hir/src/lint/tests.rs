@@ -0,0 +1,379 @@
+use std::{fmt::Write, sync::Arc};
+
+use mun_hir_input::WithFixture;
+
+use crate::{
+    diagnostics::Severity, mock::MockDatabase, DiagnosticSink, HirDatabase, Lint, LintLevel,
+    LintLevels, Package,
+};
+
+/// Returns the diagnostics of the specified code with the lint levels
+/// configured by `configure`.
+fn lints_with(content: &str, configure: impl FnOnce(&mut LintLevels)) -> String {
+    let (mut db, _file_id) = MockDatabase::with_single_file(content);
+    let mut levels = LintLevels::default();
+    configure(&mut levels);
+    db.set_lint_levels(Arc::new(levels));
+
+    let mut diags = String::new();
+    let mut diag_sink = DiagnosticSink::new(|diag| {
+        let severity = match diag.severity() {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        writeln!(
+            diags,
+            "{:?}: {severity}: {}",
+            diag.highlight_range(),
+            diag.message()
+        )
+        .unwrap();
+    });
+
+    for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+        module.diagnostics(&db, &mut diag_sink);
+    }
+
+    drop(diag_sink);
+    diags
+}
+
+/// Returns the diagnostics of the specified code with the default lint levels.
+fn lints(content: &str) -> String {
+    lints_with(content, |_| {})
+}
+
+#[test]
+fn unused_variables() {
+    insta::assert_snapshot!(lints(
+        r#"
+    pub fn foo(a: i32, b: i32, _c: i32) -> i32 {
+        let d = 5;
+        let _e = 6;
+        let f;
+        f = 7;
+        a
+    }
+    "#,
+    ), @r###"
+    19..20: warning: unused variable: `b`
+    53..54: warning: unused variable: `d`
+    "###);
+}
+
+#[test]
+fn unused_variables_in_patterns() {
+    insta::assert_snapshot!(lints(
+        r#"
+    struct Foo { a: i32, b: i32 }
+    struct Bar(i32, i32);
+
+    pub fn foo() -> i32 {
+        let Foo { a, b: c } = Foo { a: 1, b: 2 };
+        let Bar(d, e) = Bar(a, 3);
+        d
+    }
+    "#,
+    ), @r###"
+    95..96: warning: unused variable: `c`
+    136..137: warning: unused variable: `e`
+    "###);
+}
+
+#[test]
+fn unused_variables_method_self() {
+    insta::assert_snapshot!(lints(
+        r#"
+    pub struct Foo;
+    impl Foo {
+        pub fn foo(self, a: i32) {}
+    }
+    "#,
+    ), @"48..49: warning: unused variable: `a`");
+}
+
+#[test]
+fn dead_code() {
+    insta::assert_snapshot!(lints(
+        r#"
+    fn unused() {}
+    fn used() {}
+    fn recursive() { recursive() }
+    fn referenced() {}
+    pub(package) fn unused_in_package() {}
+
+    pub fn exported() { used(); let _f = referenced; }
+    extern fn from_host();
+    #[export_name = "bar"]
+    fn exported_by_name() {}
+
+    struct Foo;
+    impl Foo {
+        fn new() -> Self { Foo }
+        fn method(self) {}
+        fn unused_method(self) {}
+    }
+
+    pub fn create() { Foo::new().method() }
+    "#,
+    ), @r###"
+    3..9: warning: function `unused` is never used
+    31..40: warning: function `recursive` is never used
+    94..111: warning: function `unused_in_package` is never used
+    323..336: warning: function `unused_method` is never used
+    "###);
+}
+
+#[test]
+fn dead_code_lifecycle_hooks() {
+    insta::assert_snapshot!(lints(
+        r#"
+    fn init() {}
+    fn shutdown() {}
+
+    struct Foo;
+    impl Foo {
+        fn init() {}
+    }
+    "#,
+    ), @r###"
+    61..65: warning: function `init` is never used
+    "###);
+}
+
+#[test]
+fn dead_code_tests() {
+    insta::assert_snapshot!(lints(
+        r#"
+    fn helper() -> i32 { 5 }
+
+    #[test]
+    fn check_helper() {
+        assert(helper() == 5);
+    }
+    "#,
+    ), @"");
+}
+
+#[test]
+fn unreachable_code() {
+    insta::assert_snapshot!(lints(
+        r#"
+    pub fn foo(a: bool) -> i32 {
+        if a {
+            return 1;
+            let _b = 2;
+        }
+        loop {
+            break;
+            foo(a);
+        }
+        return 3;
+        4
+    }
+
+    pub fn bar() -> i32 {
+        let a = return 5;
+        a
+    }
+
+    pub fn baz() {
+        panic("a");
+        baz();
+        baz();
+    }
+    "#,
+    ), @r###"
+    66..77: warning: unreachable statement
+    118..124: warning: unreachable statement
+    150..151: warning: unreachable expression
+    203..204: warning: unreachable expression
+    243..248: warning: unreachable statement
+    "###);
+}
+
+#[test]
+fn lint_attributes() {
+    insta::assert_snapshot!(lints(
+        r#"
+    #[allow(dead_code)]
+    fn unused() {}
+
+    #[deny(dead_code, unused_variables)]
+    fn denied(a: i32) {}
+
+    #[allow(unused_variables)]
+    #[warn(unused_variables)]
+    pub fn warned(a: i32) {}
+
+    #[allow(unreachable_code, unused_variables)]
+    pub fn allowed(a: i32) { return; 5; }
+    "#,
+    ), @r###"
+    76..82: error: function `denied` is never used
+    83..84: error: unused variable: `a`
+    162..163: warning: unused variable: `a`
+    "###);
+}
+
+#[test]
+fn configured_lint_levels() {
+    insta::assert_snapshot!(lints_with(
+        r#"
+    fn unused(a: i32) {}
+
+    #[warn(unused_variables)]
+    pub fn warned(a: i32) {}
+    "#,
+        |levels| {
+            levels.set(Lint::DeadCode, LintLevel::Deny);
+            levels.set(Lint::UnusedVariables, LintLevel::Allow);
+        }
+    ), @r###"
+    3..9: error: function `unused` is never used
+    62..63: warning: unused variable: `a`
+    "###);
+}
+
+#[test]
+fn invalid_lint_attributes() {
+    insta::assert_snapshot!(lints(
+        r#"
+    #[allow(unused_things)]
+    pub fn foo() {}
+
+    #[deny]
+    pub fn bar() {}
+
+    #[warn(dead_code, 5)]
+    pub fn baz() {}
+
+    #[allow(dead_code)]
+    struct Foo;
+    "#,
+    ), @r###"
+    0..23: warning: unknown lint: `unused_things`
+    41..48: error: malformed `deny` attribute, expected a comma separated list of lints, e.g. `#[allow(unused_variables)]`
+    66..87: error: malformed `warn` attribute, expected a comma separated list of lints, e.g. `#[allow(unused_variables)]`
+    "###);
+}
+
+#[test]
+fn unused_mut() {
+    insta::assert_snapshot!(lints(
+        r#"
+    struct(gc) Foo { a: i32 }
+
+    impl Foo {
+        pub fn set(mut self, a: i32) { self.a = a; }
+        pub fn get(mut self) -> i32 { self.a }
+    }
+
+    pub fn foo(mut a: i32, mut b: i32) -> i32 {
+        a += 1;
+        let mut c = 5;
+        let mut d;
+        d = 6;
+        let mut e = Foo { a: 1 };
+        e.a = b;
+        a + c + d
+    }
+
+    #[allow(unused_mut)]
+    pub fn bar() -> i32 {
+        let mut a = 5;
+        a
+    }
+    "#,
+    ), @r###"
+    156..161: warning: variable `b` does not need to be mutable
+    197..202: warning: variable `c` does not need to be mutable
+    216..221: warning: variable `d` does not need to be mutable
+    102..110: warning: variable `self` does not need to be mutable
+    "###);
+}
+
+#[test]
+fn shadowed_variables() {
+    insta::assert_snapshot!(lints(
+        r#"
+    pub fn same_block() -> f64 {
+        let a = 5;
+        let _b = a;
+        let a = 1.0;
+        a
+    }
+
+    pub fn nested_block(c: bool) -> i32 {
+        let a = 5;
+        if c {
+            let a = 1.0;
+            let _b = a * 2.0;
+        }
+        a
+    }
+
+    pub fn same_type(c: bool) -> i32 {
+        let a = 5;
+        if c {
+            let a = 6;
+            let _b = a;
+        }
+        a
+    }
+
+    pub fn for_loop(i: bool) -> bool {
+        for i in 0..3 {
+            let _b = i;
+        }
+        i
+    }
+
+    #[allow(shadowed_variables)]
+    pub fn allowed(c: bool) -> i32 {
+        let a = 5;
+        if c {
+            let a = 1.0;
+            let _b = a;
+        }
+        a
+    }
+    "#,
+    ), @r###"
+    162..163: warning: variable `a` shadows a variable of a different type that is still used
+    370..371: warning: variable `i` shadows a variable of a different type that is still used
+    "###);
+}
+
+#[test]
+fn unused_labels() {
+    insta::assert_snapshot!(lints(
+        r#"
+    pub fn used() {
+        'outer: loop {
+            'inner: while true {
+                continue 'outer;
+            }
+        }
+    }
+
+    pub fn shadowed() {
+        'a: loop {
+            'a: loop {
+                break 'a;
+            }
+            break;
+        }
+    }
+
+    #[allow(unused_labels)]
+    pub fn allowed() {
+        'a: for _i in 0..3 {}
+    }
+    "#),
+    @r#"
+    43..50: warning: unused label `'inner`
+    155..158: error: label `'a` is already declared by an enclosing loop
+    136..139: warning: unused label `'a`
+    "#);
+}
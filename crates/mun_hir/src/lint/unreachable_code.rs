@@ -0,0 +1,80 @@
+use mun_syntax::{ast, AstNode, SyntaxNodePtr};
+
+use super::ItemLint;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{DiagnosticSink, UnreachableCode},
+    Expr, ExprId, HirDatabase, InFile, Statement,
+};
+
+/// Reports the code in the body of `owner` that follows an expression that
+/// never returns, e.g. a `return` expression. Only the first unreachable
+/// statement or expression of every block is reported.
+pub(super) fn check_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBody,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let (body, source_map) = db.body_with_source_map(owner.into());
+    let infer = db.infer(owner.into());
+    let file_id = owner.file_id(db);
+
+    let diverges = |expr: ExprId| infer[expr].is_never();
+    let mut report = |syntax: Option<SyntaxNodePtr>, is_statement| {
+        if let Some(syntax) = syntax {
+            sink.push(UnreachableCode {
+                code: InFile::new(file_id, syntax),
+                is_statement,
+                lint: lint.lint,
+                level: lint.level,
+                source: lint.source,
+            });
+        }
+    };
+
+    for (_, expr) in body.exprs() {
+        let Expr::Block { statements, tail } = expr else {
+            continue;
+        };
+
+        let diverging_statement = statements.iter().position(|statement| match statement {
+            Statement::Let { initializer, .. } => initializer.is_some_and(diverges),
+            Statement::Expr(expr) => diverges(*expr),
+        });
+        let Some(diverging_statement) = diverging_statement else {
+            continue;
+        };
+
+        let expr_syntax = |expr| {
+            source_map.expr_syntax(expr).map(|src| {
+                src.value
+                    .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+            })
+        };
+
+        match statements.get(diverging_statement + 1) {
+            Some(Statement::Expr(expr)) => report(expr_syntax(*expr), true),
+            Some(Statement::Let { pat, .. }) => {
+                // Report the entire `let` statement
+                let let_stmt = source_map.pat_syntax(*pat).and_then(|src| {
+                    let root = db.parse(src.file_id).syntax_node();
+                    let pat = src
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                        .to_node(&root);
+                    pat.ancestors()
+                        .find_map(ast::LetStmt::cast)
+                        .map(|it| SyntaxNodePtr::new(it.syntax()))
+                });
+                report(let_stmt, true);
+            }
+            // The diverging statement is the last statement
+            None => {
+                if let Some(tail) = tail {
+                    report(expr_syntax(*tail), false);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use super::ItemLint;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{DiagnosticSink, UnusedLabel},
+    Body, Expr, ExprId, HirDatabase, Name,
+};
+
+/// A label of a loop that encloses the expression that is currently visited.
+struct ActiveLabel {
+    name: Name,
+    loop_expr: ExprId,
+    used: bool,
+}
+
+/// Reports all loop labels in the body of `owner` that are never referred to
+/// by a `break` or `continue` expression.
+pub(super) fn check_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBody,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let (body, source_map) = db.body_with_source_map(owner.into());
+
+    let mut unused = Vec::new();
+    visit_expr(&body, body.body_expr(), &mut Vec::new(), &mut unused);
+
+    for (loop_expr, name) in unused {
+        let Some(label) = source_map.label_syntax(db.upcast(), loop_expr) else {
+            continue;
+        };
+
+        sink.push(UnusedLabel {
+            label,
+            name: name.to_string(),
+            lint: lint.lint,
+            level: lint.level,
+            source: lint.source,
+        });
+    }
+}
+
+/// Visits `expr` and its children, marking the labels in `active` that are
+/// referred to and collecting the labels that are never referred to in
+/// `unused`.
+fn visit_expr(
+    body: &Body,
+    expr: ExprId,
+    active: &mut Vec<ActiveLabel>,
+    unused: &mut Vec<(ExprId, Name)>,
+) {
+    let label = match &body[expr] {
+        Expr::Loop { label, .. } | Expr::While { label, .. } | Expr::For { label, .. } => {
+            label.clone()
+        }
+        Expr::Break {
+            label: Some(label), ..
+        }
+        | Expr::Continue { label: Some(label) } => {
+            // Labels are resolved to the innermost loop with that name
+            if let Some(target) = active.iter_mut().rev().find(|it| &it.name == label) {
+                target.used = true;
+            }
+            None
+        }
+        _ => None,
+    };
+
+    let Some(name) = label else {
+        body[expr].walk_child_exprs(|child| visit_expr(body, child, active, unused));
+        return;
+    };
+
+    active.push(ActiveLabel {
+        name,
+        loop_expr: expr,
+        used: false,
+    });
+    body[expr].walk_child_exprs(|child| visit_expr(body, child, active, unused));
+    let label = active.pop().expect("the label must still be active");
+    if !label.used {
+        unused.push((label.loop_expr, label.name));
+    }
+}
@@ -0,0 +1,51 @@
+use rustc_hash::FxHashSet;
+
+use super::ItemLint;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{DiagnosticSink, UnusedMut},
+    expr::validator::mutability::binding_mutations,
+    HirDatabase, Pat,
+};
+
+/// Reports all bindings in the body of `owner` that are declared as `mut` but
+/// that are never mutated.
+pub(super) fn check_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBody,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let (body, source_map) = db.body_with_source_map(owner.into());
+
+    let mutated_bindings: FxHashSet<_> = binding_mutations(db, owner)
+        .into_iter()
+        .map(|mutation| mutation.binding)
+        .collect();
+
+    for (pat_id, pat) in body.pats() {
+        let Pat::Bind {
+            name,
+            mutable: true,
+        } = pat
+        else {
+            continue;
+        };
+
+        if mutated_bindings.contains(&pat_id) {
+            continue;
+        }
+
+        let Some(source) = source_map.pat_syntax(pat_id) else {
+            continue;
+        };
+
+        sink.push(UnusedMut {
+            pat: source.map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())),
+            name: name.to_string(),
+            lint: lint.lint,
+            level: lint.level,
+            source: lint.source,
+        });
+    }
+}
@@ -0,0 +1,61 @@
+use rustc_hash::FxHashSet;
+
+use super::ItemLint;
+use crate::{
+    code_model::DefWithBody,
+    diagnostics::{DiagnosticSink, UnusedVariable},
+    name,
+    resolve::{resolver_for_expr, ValueNs},
+    Expr, HirDatabase, Pat,
+};
+
+/// Reports all bindings in the body of `owner` that are never used. Bindings
+/// whose name starts with an underscore are ignored.
+pub(super) fn check_body(
+    db: &dyn HirDatabase,
+    owner: DefWithBody,
+    lint: ItemLint,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let (body, source_map) = db.body_with_source_map(owner.into());
+
+    // Find all bindings that are referenced by a path
+    let mut used_bindings = FxHashSet::default();
+    for (expr_id, expr) in body.exprs() {
+        let Expr::Path(path) = expr else {
+            continue;
+        };
+
+        let resolver = resolver_for_expr(db.upcast(), body.owner(), expr_id);
+        if let Some((ValueNs::LocalBinding(pat), _)) =
+            resolver.resolve_path_as_value_fully(db.upcast(), path)
+        {
+            used_bindings.insert(pat);
+        }
+    }
+
+    for (pat_id, pat) in body.pats() {
+        let Pat::Bind { name, .. } = pat else {
+            continue;
+        };
+
+        if used_bindings.contains(&pat_id)
+            || *name == name![self]
+            || name.to_string().starts_with('_')
+        {
+            continue;
+        }
+
+        let Some(source) = source_map.pat_syntax(pat_id) else {
+            continue;
+        };
+
+        sink.push(UnusedVariable {
+            pat: source.map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())),
+            name: name.to_string(),
+            lint: lint.lint,
+            level: lint.level,
+            source: lint.source,
+        });
+    }
+}
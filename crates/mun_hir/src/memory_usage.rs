@@ -0,0 +1,141 @@
+use std::{fmt, str::FromStr};
+
+use salsa::debug::{DebugQueryTable, TableEntry};
+
+use crate::{
+    db::{
+        AstIdMapQuery, BodyQuery, BodyWithSourceMapQuery, ExprScopesQuery, InferQuery,
+        ItemTreeQuery, PackageDefsQuery, ParseQuery,
+    },
+    AstDatabase, DefDatabase, HirDatabase,
+};
+
+/// The maximum number of results that are kept in memory for the queries whose
+/// results take up the most memory. When a query has more results, the least
+/// recently used ones are evicted and recomputed when they are needed again.
+/// A capacity of `0` keeps all results, which is the default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LruCapacities {
+    /// The number of syntax trees of parsed files
+    pub parse: usize,
+    /// The number of item trees of files
+    pub item_tree: usize,
+    /// The number of lowered bodies of functions, constants, and statics,
+    /// together with their source maps
+    pub body: usize,
+    /// The number of type inference results of bodies
+    pub infer: usize,
+}
+
+impl LruCapacities {
+    /// Sets the capacities of the queries of `db`.
+    pub fn apply<DB: HirDatabase>(&self, db: &mut DB) {
+        ParseQuery.in_db_mut(db).set_lru_capacity(self.parse);
+        ItemTreeQuery.in_db_mut(db).set_lru_capacity(self.item_tree);
+        BodyWithSourceMapQuery
+            .in_db_mut(db)
+            .set_lru_capacity(self.body);
+        InferQuery.in_db_mut(db).set_lru_capacity(self.infer);
+    }
+}
+
+impl FromStr for LruCapacities {
+    type Err = String;
+
+    /// Parses a comma separated list of capacities, e.g. `parse=128,infer=512`.
+    /// Queries that are not listed keep all of their results.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut capacities = LruCapacities::default();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (query, capacity) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected `<query>=<capacity>`, found '{entry}'"))?;
+            let capacity = capacity
+                .trim()
+                .parse()
+                .map_err(|_err| format!("invalid capacity for '{}': '{capacity}'", query.trim()))?;
+            match query.trim() {
+                "parse" => capacities.parse = capacity,
+                "item_tree" => capacities.item_tree = capacity,
+                "body" => capacities.body = capacity,
+                "infer" => capacities.infer = capacity,
+                query => {
+                    return Err(format!(
+                        "unknown query '{query}', expected one of: parse, item_tree, body, infer"
+                    ))
+                }
+            }
+        }
+        Ok(capacities)
+    }
+}
+
+/// Describes how many results of a query are stored in a database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryMemoryUsage {
+    /// The name of the query
+    pub name: &'static str,
+    /// The number of keys for which the query was executed
+    pub entries: usize,
+    /// The number of results that are kept in memory. Results that were
+    /// evicted because of the [`LruCapacities`] of the query are not counted.
+    pub retained: usize,
+}
+
+impl fmt::Display for QueryMemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<24}{:>10}{:>10}",
+            self.name, self.entries, self.retained
+        )
+    }
+}
+
+/// Returns how many results of the queries that take up the most memory are
+/// stored in `db`.
+pub fn memory_usage(db: &dyn HirDatabase) -> Vec<QueryMemoryUsage> {
+    let def_db: &dyn DefDatabase = db.upcast();
+    let ast_db: &dyn AstDatabase = def_db.upcast();
+    vec![
+        query_memory_usage("parse", ParseQuery.in_db(ast_db)),
+        query_memory_usage("ast_id_map", AstIdMapQuery.in_db(ast_db)),
+        query_memory_usage("item_tree", ItemTreeQuery.in_db(def_db)),
+        query_memory_usage("package_defs", PackageDefsQuery.in_db(def_db)),
+        query_memory_usage("body_with_source_map", BodyWithSourceMapQuery.in_db(def_db)),
+        query_memory_usage("body", BodyQuery.in_db(def_db)),
+        query_memory_usage("expr_scopes", ExprScopesQuery.in_db(def_db)),
+        query_memory_usage("infer", InferQuery.in_db(db)),
+    ]
+}
+
+fn query_memory_usage(name: &'static str, table: impl DebugQueryTable) -> QueryMemoryUsage {
+    let EntryCounts { entries, retained } = table.entries();
+    QueryMemoryUsage {
+        name,
+        entries,
+        retained,
+    }
+}
+
+/// Counts the entries of a query table, without keeping their values alive.
+#[derive(Default)]
+struct EntryCounts {
+    entries: usize,
+    retained: usize,
+}
+
+impl<K, V> FromIterator<TableEntry<K, V>> for EntryCounts {
+    fn from_iter<T: IntoIterator<Item = TableEntry<K, V>>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(EntryCounts::default(), |mut counts, entry| {
+                counts.entries += 1;
+                counts.retained += usize::from(entry.value.is_some());
+                counts
+            })
+    }
+}
@@ -1,13 +1,36 @@
-#![cfg(test)]
+//! A mock database that implements all the queries of this crate. It is
+//! available to other crates through the `test-support` feature, which makes it
+//! possible to unit test compiler extensions against the real query
+//! infrastructure.
+//!
+//! Databases are most easily constructed from a fixture through the
+//! [`WithFixture`] trait:
+//!
+//! ```
+//! use mun_hir::mock::{MockDatabase, WithFixture};
+//!
+//! let db = MockDatabase::with_files(
+//!     r#"
+//! //- /foo.mun
+//! pub fn foo() -> i32 { 5 }
+//!
+//! //- /mod.mun
+//! fn main() -> i32 { foo::foo() }
+//! "#,
+//! );
+//! ```
+
+use std::sync::Arc;
 
 use mun_db::Upcast;
 use mun_hir_input::SourceDatabase;
+pub use mun_hir_input::WithFixture;
 use mun_target::spec::Target;
 use parking_lot::Mutex;
 
 use crate::{
     db::{AstDatabase, HirDatabase},
-    DefDatabase,
+    DefDatabase, LintLevels,
 };
 
 /// A mock implementation of the IR database. It can be used to set up a simple
@@ -19,7 +42,7 @@ use crate::{
     crate::DefDatabaseStorage,
     crate::HirDatabaseStorage
 )]
-pub(crate) struct MockDatabase {
+pub struct MockDatabase {
     storage: salsa::Storage<Self>,
     events: Mutex<Option<Vec<salsa::Event>>>,
 }
@@ -58,17 +81,25 @@ impl Default for MockDatabase {
             events: Mutex::default(),
         };
         db.set_target(Target::host_target().unwrap());
+        db.set_cfg_options(Arc::default());
+        // Lints are allowed so tests only report the diagnostics they are
+        // interested in
+        db.set_lint_levels(Arc::new(LintLevels::allow_all()));
         db
     }
 }
 
 impl MockDatabase {
+    /// Runs `f` and returns all the salsa events that occurred while it ran.
     pub fn log(&self, f: impl FnOnce()) -> Vec<salsa::Event> {
         *self.events.lock() = Some(Vec::new());
         f();
         self.events.lock().take().unwrap()
     }
 
+    /// Runs `f` and returns a description of every query that was executed
+    /// while it ran. This is useful to verify that a change does or doesn't
+    /// trigger recomputation.
     pub fn log_executed(&self, f: impl FnOnce()) -> Vec<String> {
         let events = self.log(f);
         events
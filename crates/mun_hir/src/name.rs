@@ -117,8 +117,44 @@ pub mod known {
 
     known_names!(
         // Primitives
-        int, isize, i8, i16, i32, i64, i128, uint, usize, u8, u16, u32, u64, u128, float, f32, f64,
-        bool,
+        int,
+        isize,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        uint,
+        usize,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        float,
+        f32,
+        f64,
+        bool, // Builtin functions
+        panic,
+        assert,
+        sin,
+        cos,
+        sqrt,
+        abs,
+        min,
+        max, // Attributes
+        inline,
+        export_name,
+        link_namespace,
+        deprecated,
+        cfg,
+        allow,
+        warn,
+        deny,
+        test,
+        align, // Lifecycle hooks
+        init,
+        shutdown,
     );
 
     // self/Self cannot be used as an identifier
@@ -54,17 +54,34 @@ mod diagnostics {
     use mun_syntax::{ast, ast::Use, AstPtr};
 
     use crate::{
-        diagnostics::{ImportDuplicateDefinition, UnresolvedImport},
+        diagnostics::{
+            GlobImportNotModule, ImportAmbiguousGlob, ImportDuplicateDefinition, UnresolvedImport,
+        },
         source_id::AstId,
-        AstDatabase, DefDatabase, DiagnosticSink, InFile, Path,
+        AstDatabase, DefDatabase, DiagnosticSink, InFile, Name, Path,
     };
 
     /// A type of diagnostic that may be emitted during resolving all package
     /// definitions.
     #[derive(Debug, PartialEq, Eq)]
     enum DiagnosticKind {
-        UnresolvedImport { ast: AstId<ast::Use>, index: usize },
-        DuplicateImport { ast: AstId<ast::Use>, index: usize },
+        UnresolvedImport {
+            ast: AstId<ast::Use>,
+            index: usize,
+        },
+        DuplicateImport {
+            ast: AstId<ast::Use>,
+            index: usize,
+        },
+        AmbiguousGlobImport {
+            ast: AstId<ast::Use>,
+            index: usize,
+            name: Name,
+        },
+        GlobImportNotModule {
+            ast: AstId<ast::Use>,
+            index: usize,
+        },
     }
 
     /// A diagnostic that may be emitted during resolving all package
@@ -105,6 +122,34 @@ mod diagnostics {
             }
         }
 
+        /// Constructs a new `DefDiagnostic` which indicates that a glob import
+        /// brings an item into scope with the same name as an item from
+        /// another glob import.
+        pub(super) fn ambiguous_glob_import(
+            container: PackageModuleId,
+            ast: AstId<ast::Use>,
+            index: usize,
+            name: Name,
+        ) -> Self {
+            Self {
+                in_module: container,
+                kind: DiagnosticKind::AmbiguousGlobImport { ast, index, name },
+            }
+        }
+
+        /// Constructs a new `DefDiagnostic` which indicates that a glob import
+        /// refers to something other than a module.
+        pub(super) fn glob_import_not_module(
+            container: PackageModuleId,
+            ast: AstId<ast::Use>,
+            index: usize,
+        ) -> Self {
+            Self {
+                in_module: container,
+                kind: DiagnosticKind::GlobImportNotModule { ast, index },
+            }
+        }
+
         pub(super) fn add_to(
             &self,
             db: &dyn DefDatabase,
@@ -143,6 +188,19 @@ mod diagnostics {
                         sink.push(ImportDuplicateDefinition { use_tree });
                     }
                 }
+                DiagnosticKind::AmbiguousGlobImport { ast, index, name } => {
+                    if let Some(use_tree) = use_tree_ptr_from_ast(db.upcast(), ast, *index) {
+                        sink.push(ImportAmbiguousGlob {
+                            use_tree,
+                            name: name.to_string(),
+                        });
+                    }
+                }
+                DiagnosticKind::GlobImportNotModule { ast, index } => {
+                    if let Some(use_tree) = use_tree_ptr_from_ast(db.upcast(), ast, *index) {
+                        sink.push(GlobImportNotModule { use_tree });
+                    }
+                }
             }
         }
     }
@@ -5,12 +5,13 @@ use rustc_hash::FxHashMap;
 use super::PackageDefs;
 use crate::{
     ids::{
-        FunctionLoc, ImplLoc, Intern, ItemContainerId, ItemDefinitionId, StructLoc, TypeAliasLoc,
+        ConstLoc, FunctionLoc, ImplLoc, Intern, ItemContainerId, ItemDefinitionId, StaticLoc,
+        StructLoc, TypeAliasLoc,
     },
     item_scope::{ImportType, ItemScope, PerNsGlobImports},
     item_tree::{
-        self, Fields, Function, Impl, ItemTree, ItemTreeId, LocalItemTreeId, ModItem, Struct,
-        TypeAlias,
+        self, Const, Fields, Function, Impl, ItemTree, ItemTreeId, LocalItemTreeId, ModItem,
+        Static, Struct, TypeAlias,
     },
     name_resolution::ReachedFixedPoint,
     package_defs::diagnostics::DefDiagnostic,
@@ -294,7 +295,6 @@ impl DefCollector<'_> {
         );
 
         if import.is_glob {
-            #[allow(clippy::match_same_arms)]
             match resolution.take_types() {
                 Some((ItemDefinitionId::ModuleId(m), _)) => {
                     let scope = &self.package_defs[m.local_id];
@@ -334,8 +334,17 @@ impl DefCollector<'_> {
                     }
                 }
                 Some((_, _)) => {
-                    // Happens when wildcard importing something other than a
-                    // module. I guess it's ok to do nothing here?
+                    // Only modules contain items that can be imported
+                    let item_tree = self.db.item_tree(import.source.file_id);
+                    let import_data = &item_tree[import.source.value];
+                    push_diagnostic(
+                        &mut self.package_defs.diagnostics,
+                        DefDiagnostic::glob_import_not_module(
+                            import_module_id,
+                            InFile::new(import.source.file_id, import_data.ast_id),
+                            import_data.index,
+                        ),
+                    );
                 }
                 None => {
                     // Happens if a wildcard import refers to something other
@@ -423,16 +432,25 @@ impl DefCollector<'_> {
                     if add_result.changed {
                         changed = true;
                     }
-                    if add_result.duplicate {
+                    if add_result.duplicate || add_result.ambiguous {
                         let item_tree = self.db.item_tree(import_source.file_id);
                         let import_data = &item_tree[import_source.value];
-                        self.package_defs
-                            .diagnostics
-                            .push(DefDiagnostic::duplicate_import(
+                        let ast = InFile::new(import_source.file_id, import_data.ast_id);
+                        let diagnostic = if add_result.duplicate {
+                            DefDiagnostic::duplicate_import(
+                                import_module_id,
+                                ast,
+                                import_data.index,
+                            )
+                        } else {
+                            DefDiagnostic::ambiguous_glob_import(
                                 import_module_id,
-                                InFile::new(import_source.file_id, import_data.ast_id),
+                                ast,
                                 import_data.index,
-                            ));
+                                name.clone(),
+                            )
+                        };
+                        push_diagnostic(&mut self.package_defs.diagnostics, diagnostic);
                     }
                 }
                 None => {
@@ -520,6 +538,8 @@ impl<'a> ModCollectorContext<'a, '_> {
                 ModItem::Function(id) => self.collect_function(id),
                 ModItem::Struct(id) => self.collect_struct(id),
                 ModItem::TypeAlias(id) => self.collect_type_alias(id),
+                ModItem::Const(id) => self.collect_const(id),
+                ModItem::Static(id) => self.collect_static(id),
                 ModItem::Import(id) => {
                     self.collect_import(id);
                     continue;
@@ -624,6 +644,44 @@ impl<'a> ModCollectorContext<'a, '_> {
             has_constructor: false,
         }
     }
+
+    /// Collects the definition data from a `Static`
+    fn collect_static(&self, id: LocalItemTreeId<Static>) -> DefData<'a> {
+        let static_def = &self.item_tree[id];
+        DefData {
+            id: StaticLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &static_def.name,
+            visibility: &self.item_tree[static_def.visibility],
+            has_constructor: false,
+        }
+    }
+
+    /// Collects the definition data from a `Const`
+    fn collect_const(&self, id: LocalItemTreeId<Const>) -> DefData<'a> {
+        let konst = &self.item_tree[id];
+        DefData {
+            id: ConstLoc {
+                module: ModuleId {
+                    package: self.def_collector.package_id,
+                    local_id: self.module_id,
+                },
+                id: ItemTreeId::new(self.file_id, id),
+            }
+            .intern(self.def_collector.db)
+            .into(),
+            name: &konst.name,
+            visibility: &self.item_tree[konst.visibility],
+            has_constructor: false,
+        }
+    }
 }
 
 struct DefData<'a> {
@@ -632,3 +690,12 @@ struct DefData<'a> {
     visibility: &'a RawVisibility,
     has_constructor: bool,
 }
+
+/// Adds a diagnostic to the list, unless the exact same diagnostic was already
+/// reported. Imports can be resolved more than once while iterating towards a
+/// fixed point.
+fn push_diagnostic(diagnostics: &mut Vec<DefDiagnostic>, diagnostic: DefDiagnostic) {
+    if !diagnostics.contains(&diagnostic) {
+        diagnostics.push(diagnostic);
+    }
+}
@@ -2,8 +2,8 @@ use mun_hir_input::WithFixture;
 use rustc_hash::FxHashSet;
 
 use crate::{
-    db::DefDatabase, ids::ItemDefinitionId, mock::MockDatabase, package_defs::PackageDefs,
-    DiagnosticSink, Function, HirDatabase, Module, Package, Struct, TypeAlias,
+    db::DefDatabase, ids::ItemDefinitionId, mock::MockDatabase, package_defs::PackageDefs, Const,
+    DiagnosticSink, Function, HirDatabase, Module, Package, Static, Struct, TypeAlias,
 };
 
 #[test]
@@ -106,6 +106,90 @@ fn use_wildcard() {
     "###);
 }
 
+#[test]
+fn use_wildcard_shadowed() {
+    insta::assert_snapshot!(resolve(
+        r#"
+    //- /foo.mun
+    pub struct Foo;
+    pub struct Bar;
+    pub struct Baz;
+
+    //- /bar.mun
+    pub struct Foo;
+    pub struct Bar;
+
+    //- /mod.mun
+    use foo::*;
+    use bar::Bar;           // Explicit imports shadow glob imports
+    use bar::Foo as BarFoo; // Aliases avoid conflicts with glob imports
+
+    struct Baz;             // Local definitions shadow glob imports
+    "#),
+    @r###"
+    mod mod
+    +-- struct Baz
+    +-- use struct package::foo::Foo
+    +-- use struct package::bar::Bar
+    +-- use struct package::bar::Foo
+    +-- mod bar
+    |   +-- struct Foo
+    |   '-- struct Bar
+    '-- mod foo
+        +-- struct Foo
+        +-- struct Bar
+        '-- struct Baz
+    "###);
+}
+
+#[test]
+fn use_wildcard_ambiguous() {
+    insta::assert_snapshot!(resolve(
+        r#"
+    //- /foo.mun
+    pub struct Foo;
+    pub struct Bar;
+
+    //- /bar.mun
+    pub struct Foo;
+    pub use super::foo::Bar; // Same item, so not ambiguous
+
+    //- /mod.mun
+    use foo::*;
+    use bar::*;
+    "#),
+    @r###"
+    mod mod
+    +-- ERROR: 16..22: `Foo` is imported by multiple glob imports. Try to import it explicitly.
+    +-- use struct package::foo::Foo
+    +-- use struct package::foo::Bar
+    +-- mod bar
+    |   +-- struct Foo
+    |   '-- use struct package::foo::Bar
+    '-- mod foo
+        +-- struct Foo
+        '-- struct Bar
+    "###);
+}
+
+#[test]
+fn use_wildcard_not_module() {
+    insta::assert_snapshot!(resolve(
+        r#"
+    //- /foo.mun
+    pub struct Foo;
+
+    //- /mod.mun
+    use foo::Foo::*;
+    "#),
+    @r###"
+    mod mod
+    +-- ERROR: 4..15: glob imports can only import items from a module
+    '-- mod foo
+        '-- struct Foo
+    "###);
+}
+
 #[test]
 fn use_self() {
     insta::assert_snapshot!(resolve(
@@ -324,7 +408,35 @@ fn tree_for_module(
                     node.push(format!("use type {fully_qualified_name}"));
                 }
             }
-            ItemDefinitionId::PrimitiveType(_) => {}
+            ItemDefinitionId::StaticId(s) => {
+                let static_def: Static = (*s).into();
+                let name = static_def.name(db);
+                if is_local {
+                    node.push(format!("static {name}"));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, static_def.module(db)),
+                        name
+                    );
+                    node.push(format!("use static {fully_qualified_name}"));
+                }
+            }
+            ItemDefinitionId::ConstId(c) => {
+                let konst: Const = (*c).into();
+                let name = konst.name(db);
+                if is_local {
+                    node.push(format!("const {name}"));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, konst.module(db)),
+                        name
+                    );
+                    node.push(format!("use const {fully_qualified_name}"));
+                }
+            }
+            ItemDefinitionId::PrimitiveType(_) | ItemDefinitionId::BuiltinFunction(_) => {}
         }
     }
 
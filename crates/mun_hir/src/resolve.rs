@@ -6,15 +6,16 @@ use crate::{
     expr::{scope::LocalScopeId, PatId},
     has_module::HasModule,
     ids::{
-        DefWithBodyId, FunctionId, ImplId, ItemContainerId, ItemDefinitionId, Lookup, StructId,
-        TypeAliasId,
+        ConstId, DefWithBodyId, FunctionId, ImplId, ItemContainerId, ItemDefinitionId, Lookup,
+        StaticId, StructId, TypeAliasId,
     },
     item_scope::BUILTIN_SCOPE,
     name,
     package_defs::PackageDefs,
     primitive_type::PrimitiveType,
+    utils::find_similar_name,
     visibility::RawVisibility,
-    DefDatabase, ExprId, ExprScopes, Name, Path, PerNs, Visibility,
+    BuiltinFunction, DefDatabase, ExprId, ExprScopes, Name, Namespace, Path, PerNs, Visibility,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -57,6 +58,9 @@ pub enum ValueNs {
     LocalBinding(PatId),
     FunctionId(FunctionId),
     StructId(StructId),
+    ConstId(ConstId),
+    StaticId(StaticId),
+    BuiltinFunction(BuiltinFunction),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -68,6 +72,7 @@ pub enum TypeNs {
 }
 
 /// An item definition visible from a certain scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScopeDef {
     ImplSelfType(ImplId),
     PerNs(PerNs<(ItemDefinitionId, Visibility)>),
@@ -173,6 +178,9 @@ impl Resolver {
             let (res, vis) = match per_ns.take_values()? {
                 (ItemDefinitionId::FunctionId(id), vis) => (ValueNs::FunctionId(id), vis),
                 (ItemDefinitionId::StructId(id), vis) => (ValueNs::StructId(id), vis),
+                (ItemDefinitionId::ConstId(id), vis) => (ValueNs::ConstId(id), vis),
+                (ItemDefinitionId::StaticId(id), vis) => (ValueNs::StaticId(id), vis),
+                (ItemDefinitionId::BuiltinFunction(id), vis) => (ValueNs::BuiltinFunction(id), vis),
                 (
                     ItemDefinitionId::ModuleId(_)
                     | ItemDefinitionId::TypeAliasId(_)
@@ -236,7 +244,11 @@ impl Resolver {
                                     TypeNs::PrimitiveType(id)
                                 }
                                 (
-                                    ItemDefinitionId::ModuleId(_) | ItemDefinitionId::FunctionId(_),
+                                    ItemDefinitionId::ModuleId(_)
+                                    | ItemDefinitionId::FunctionId(_)
+                                    | ItemDefinitionId::ConstId(_)
+                                    | ItemDefinitionId::StaticId(_)
+                                    | ItemDefinitionId::BuiltinFunction(_),
                                     _,
                                 ) => return None,
                             };
@@ -277,7 +289,14 @@ impl Resolver {
                 (ItemDefinitionId::StructId(id), vis) => (TypeNs::StructId(id), vis),
                 (ItemDefinitionId::TypeAliasId(id), vis) => (TypeNs::TypeAliasId(id), vis),
                 (ItemDefinitionId::PrimitiveType(id), vis) => (TypeNs::PrimitiveType(id), vis),
-                (ItemDefinitionId::ModuleId(_) | ItemDefinitionId::FunctionId(_), _) => {
+                (
+                    ItemDefinitionId::ModuleId(_)
+                    | ItemDefinitionId::FunctionId(_)
+                    | ItemDefinitionId::ConstId(_)
+                    | ItemDefinitionId::StaticId(_)
+                    | ItemDefinitionId::BuiltinFunction(_),
+                    _,
+                ) => {
                     return None;
                 }
             };
@@ -355,6 +374,53 @@ impl Resolver {
             scope.visit_names(db, visitor);
         }
     }
+
+    /// Returns the name in scope in the namespace `ns` that is most similar to
+    /// `name`, if any is similar enough to likely be what was meant. This is
+    /// used to suggest a fix for a name that cannot be resolved.
+    pub(crate) fn find_similar_name(
+        &self,
+        db: &dyn DefDatabase,
+        name: &Name,
+        ns: Namespace,
+    ) -> Option<(Name, ScopeDef)> {
+        let mut candidates = Vec::new();
+        self.visit_all_names(db, &mut |name, def| {
+            if def.is_in_namespace(ns) {
+                candidates.push((name, def));
+            }
+        });
+        find_similar_name(name, candidates)
+    }
+}
+
+impl ScopeDef {
+    /// Returns the item that is referred to from the namespace `ns`, if the
+    /// definition is an item.
+    pub(crate) fn item(&self, ns: Namespace) -> Option<ItemDefinitionId> {
+        let ScopeDef::PerNs(per_ns) = self else {
+            return None;
+        };
+
+        let (item, _) = match ns {
+            Namespace::Types => per_ns.types?,
+            Namespace::Values => per_ns.values?,
+        };
+        Some(item)
+    }
+
+    /// Returns true if the definition can be referred to from the namespace
+    /// `ns`.
+    fn is_in_namespace(&self, ns: Namespace) -> bool {
+        match self {
+            ScopeDef::ImplSelfType(_) => ns == Namespace::Types,
+            ScopeDef::PerNs(per_ns) => match ns {
+                Namespace::Types => per_ns.types.is_some(),
+                Namespace::Values => per_ns.values.is_some(),
+            },
+            ScopeDef::Local(_) => ns == Namespace::Values,
+        }
+    }
 }
 
 impl Scope {
@@ -433,10 +499,24 @@ impl HasResolver for TypeAliasId {
     }
 }
 
+impl HasResolver for ConstId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.module(db).resolver(db)
+    }
+}
+
+impl HasResolver for StaticId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.module(db).resolver(db)
+    }
+}
+
 impl HasResolver for DefWithBodyId {
     fn resolver(self, db: &dyn DefDatabase) -> Resolver {
         match self {
             DefWithBodyId::FunctionId(f) => f.resolver(db),
+            DefWithBodyId::ConstId(c) => c.resolver(db),
+            DefWithBodyId::StaticId(s) => s.resolver(db),
         }
     }
 }
@@ -4,7 +4,10 @@ use rustc_hash::FxHashMap;
 
 use crate::{
     code_model::src::HasSource,
-    ids::{DefWithBodyId, FunctionId, ImplId, ItemDefinitionId, Lookup, StructId, TypeAliasId},
+    ids::{
+        ConstId, DefWithBodyId, FunctionId, ImplId, ItemDefinitionId, Lookup, StaticId, StructId,
+        TypeAliasId,
+    },
     item_scope::ItemScope,
     AssocItemId, DefDatabase, HirDatabase, InFile,
 };
@@ -40,6 +43,14 @@ impl SourceToDefContext<'_, '_> {
                     let def = self.fn_to_def(container.with_value(it))?;
                     SourceToDefContainer::DefWithBodyId(def.into())
                 },
+                ast::ConstDef(it) => {
+                    let def = self.const_to_def(container.with_value(it))?;
+                    SourceToDefContainer::DefWithBodyId(def.into())
+                },
+                ast::StaticDef(it) => {
+                    let def = self.static_to_def(container.with_value(it))?;
+                    SourceToDefContainer::DefWithBodyId(def.into())
+                },
                 ast::Impl(it) => {
                     let def = self.impl_to_def(container.with_value(it))?;
                     SourceToDefContainer::Impl(def)
@@ -64,6 +75,28 @@ impl SourceToDefContext<'_, '_> {
         def_map.functions.get(&src).copied()
     }
 
+    /// Find the `StaticId` associated with the specified syntax tree node.
+    fn static_to_def(&mut self, src: InFile<ast::StaticDef>) -> Option<StaticId> {
+        let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.statics.get(&src).copied()
+    }
+
+    /// Find the `ConstId` associated with the specified syntax tree node.
+    fn const_to_def(&mut self, src: InFile<ast::ConstDef>) -> Option<ConstId> {
+        let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.consts.get(&src).copied()
+    }
+
     /// Find the `ImplId` associated with the specified syntax tree node.
     fn impl_to_def(&mut self, src: InFile<ast::Impl>) -> Option<ImplId> {
         let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
@@ -158,6 +191,14 @@ impl SourceToDef for ItemScope {
                     let src = id.lookup(db).source(db);
                     map.type_aliases.insert(src, id);
                 }
+                ItemDefinitionId::ConstId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.consts.insert(src, id);
+                }
+                ItemDefinitionId::StaticId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.statics.insert(src, id);
+                }
                 _ => {}
             }
         }
@@ -198,4 +239,6 @@ pub(crate) struct SourceToDefMap {
     impls: FxHashMap<InFile<ast::Impl>, ImplId>,
     structs: FxHashMap<InFile<ast::StructDef>, StructId>,
     type_aliases: FxHashMap<InFile<ast::TypeAliasDef>, TypeAliasId>,
+    consts: FxHashMap<InFile<ast::ConstDef>, ConstId>,
+    statics: FxHashMap<InFile<ast::StaticDef>, StaticId>,
 }
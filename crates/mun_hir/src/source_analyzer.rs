@@ -7,9 +7,9 @@ use crate::{
     expr::{scope::LocalScopeId, BodySourceMap},
     ids::DefWithBodyId,
     resolver_for_scope,
-    semantics::PathResolution,
-    Body, ExprId, ExprScopes, HirDatabase, InFile, InferenceResult, Path, Resolver, Struct, Ty,
-    TypeAlias, TypeNs,
+    semantics::{Local, PathResolution},
+    Body, Const, ExprId, ExprScopes, Function, HirDatabase, InFile, InferenceResult, Path,
+    Resolver, Static, Struct, Ty, TypeAlias, TypeNs, ValueNs,
 };
 
 /// A `SourceAnalyzer` is a wrapper which exposes the HIR API in terms of the
@@ -96,6 +96,11 @@ impl SourceAnalyzer {
             return resolve_hir_path_qualifier(db, &self.resolver, &hir_path);
         }
 
+        // Case where the path is used as a value, e.g. in a path expression
+        if path.syntax().parent().and_then(ast::PathExpr::cast).is_some() {
+            return resolve_hir_path_value(db, &self.resolver, &hir_path);
+        }
+
         None
     }
 }
@@ -194,6 +199,29 @@ fn adjust(
 }
 
 /// Resolves a path where we know it is a qualifier of another path.
+/// Resolves a path that is used as a value.
+fn resolve_hir_path_value(
+    db: &dyn HirDatabase,
+    resolver: &Resolver,
+    path: &Path,
+) -> Option<PathResolution> {
+    let (value, _) = resolver.resolve_path_as_value_fully(db.upcast(), path)?;
+    let res = match value {
+        ValueNs::LocalBinding(pat_id) => PathResolution::Local(Local {
+            parent: resolver.body_owner()?,
+            pat_id,
+        }),
+        ValueNs::FunctionId(it) => PathResolution::Def(Function::from(it).into()),
+        ValueNs::StructId(it) => PathResolution::Def(Struct::from(it).into()),
+        ValueNs::ConstId(it) => PathResolution::Def(Const::from(it).into()),
+        ValueNs::StaticId(it) => PathResolution::Def(Static::from(it).into()),
+        ValueNs::BuiltinFunction(it) => PathResolution::Def(it.into()),
+        ValueNs::ImplSelf(_) => return None,
+    };
+
+    Some(res)
+}
+
 fn resolve_hir_path_qualifier(
     db: &dyn HirDatabase,
     resolver: &Resolver,
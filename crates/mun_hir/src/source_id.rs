@@ -71,6 +71,8 @@ register_ast_id_node! {
         StructDef,
         Impl,
         TypeAliasDef,
+        ConstDef,
+        StaticDef,
     Param, SelfParam
 }
 
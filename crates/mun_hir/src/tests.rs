@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
-use mun_hir_input::{PackageId, SourceDatabase, WithFixture};
+use mun_hir_input::{FileId, PackageId, SourceDatabase, WithFixture};
 
-use crate::{db::DefDatabase, mock::MockDatabase};
+use crate::{
+    db::{AstDatabase, DefDatabase},
+    memory_usage,
+    mock::MockDatabase,
+    LruCapacities,
+};
 
 /// This function tests that the `ModuleData` of a module does not change if the
 /// contents of a function is changed.
@@ -46,3 +51,53 @@ fn check_package_defs_does_not_change() {
         );
     }
 }
+
+/// Tests that the results of a query with an LRU capacity are evicted when
+/// the query has more results than its capacity.
+#[test]
+fn lru_capacity_evicts_results() {
+    let mut db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    pub fn main() {}
+    //- /foo.mun
+    pub fn foo() {}
+    //- /bar.mun
+    pub fn bar() {}
+    //- /baz.mun
+    pub fn baz() {}
+    "#,
+    );
+    LruCapacities {
+        parse: 1,
+        ..LruCapacities::default()
+    }
+    .apply(&mut db);
+
+    for file_id in 0..4 {
+        db.parse(FileId(file_id));
+    }
+
+    let parse = memory_usage(&db)
+        .into_iter()
+        .find(|usage| usage.name == "parse")
+        .unwrap();
+    assert_eq!(parse.entries, 4);
+    assert!(parse.retained < parse.entries, "{parse:?}");
+}
+
+#[test]
+fn parse_lru_capacities() {
+    assert_eq!(
+        "parse=128, infer=512".parse::<LruCapacities>(),
+        Ok(LruCapacities {
+            parse: 128,
+            infer: 512,
+            ..LruCapacities::default()
+        })
+    );
+    assert_eq!("".parse::<LruCapacities>(), Ok(LruCapacities::default()));
+    assert!("parse".parse::<LruCapacities>().is_err());
+    assert!("parse=many".parse::<LruCapacities>().is_err());
+    assert!("lower=1".parse::<LruCapacities>().is_err());
+}
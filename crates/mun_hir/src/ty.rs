@@ -260,7 +260,7 @@ impl Ty {
     pub fn equals_ctor(&self, other: &Ty) -> bool {
         match (self.interned(), other.interned()) {
             (TyKind::Struct(s1), TyKind::Struct(s2)) => s1 == s2,
-            (TyKind::Tuple(_, substs1), TyKind::Tuple(_, substs2)) => substs1 == substs2,
+            (TyKind::Tuple(len1, _), TyKind::Tuple(len2, _)) => len1 == len2,
             (TyKind::Array(_), TyKind::Array(_)) | (TyKind::Bool, TyKind::Bool) => true,
             (TyKind::Float(f1), TyKind::Float(f2)) => f1 == f2,
             (TyKind::Int(i1), TyKind::Int(i2)) => i1 == i2,
@@ -363,6 +363,15 @@ impl FnSig {
         }
         true
     }
+
+    /// Returns `true` if the function returns a value struct. When called
+    /// through the public API such a function writes its return value to a
+    /// caller-provided buffer, which is passed as an extra first argument.
+    pub fn returns_value_struct(&self, db: &dyn HirDatabase) -> bool {
+        self.ret()
+            .as_struct()
+            .is_some_and(|s| s.data(db.upcast()).memory_kind == StructMemoryKind::Value)
+    }
 }
 
 impl HirDisplay for Ty {
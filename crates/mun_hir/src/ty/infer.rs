@@ -5,18 +5,22 @@ use mun_hir_input::ModuleId;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    code_model::{Struct, StructKind},
+    code_model::DefWithBody,
+    code_model::{Field, Static, Struct, StructKind},
     diagnostics::DiagnosticSink,
-    expr::{Body, Expr, ExprId, Literal, Pat, PatId, RecordLitField, Statement, UnaryOp},
+    expr::{
+        Body, Expr, ExprId, Literal, Pat, PatId, RecordFieldPat, RecordLitField, Statement, UnaryOp,
+    },
     name_resolution::Namespace,
     resolve::{Resolver, TypeNs, ValueNs},
     ty::{
         infer::{diagnostics::InferenceDiagnostic, type_variable::TypeVariableTable},
         lower::LowerDiagnostic,
-        op, Ty, TypableDef,
+        op, Substitution, Ty, TypableDef,
     },
     type_ref::LocalTypeRefId,
-    BinaryOp, CallableDef, Function, HirDatabase, Name, Path,
+    utils::find_similar_name,
+    BinaryOp, BuiltinFunction, CallableDef, Function, HirDatabase, Name, Path,
 };
 
 mod place_expr;
@@ -62,6 +66,10 @@ pub struct InferenceResult {
     /// For each method call expression, records the function it resolves to.
     pub(crate) method_resolutions: FxHashMap<ExprId, FunctionId>,
 
+    /// For each call of a builtin function, records the function that is
+    /// called.
+    pub(crate) builtin_calls: FxHashMap<ExprId, BuiltinFunction>,
+
     /// Interned Unknown to return references to.
     standard_types: InternedStandardTypes,
 }
@@ -91,12 +99,18 @@ impl InferenceResult {
         self.method_resolutions.get(&expr).cloned()
     }
 
+    /// Find the builtin function that is called by the given expression.
+    /// Returns `None` if the expression is not a call to a builtin function.
+    pub fn builtin_call(&self, expr: ExprId) -> Option<BuiltinFunction> {
+        self.builtin_calls.get(&expr).copied()
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the
     /// `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
         &self,
         db: &dyn HirDatabase,
-        owner: Function,
+        owner: DefWithBody,
         sink: &mut DiagnosticSink<'_>,
     ) {
         self.diagnostics
@@ -115,6 +129,7 @@ pub fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceRes
 
     match def {
         DefWithBodyId::FunctionId(_) => ctx.infer_signature(),
+        DefWithBodyId::ConstId(_) | DefWithBodyId::StaticId(_) => ctx.infer_const_signature(),
     }
 
     ctx.infer_body();
@@ -168,18 +183,19 @@ struct InferenceResultBuilder<'a> {
 
     type_variables: TypeVariableTable,
 
-    /// Information on the current loop that we're processing (or None if we're
-    /// not in a loop) the entry contains the current type of the loop
-    /// statement (initially `never`) and the expected type of the loop
-    /// expression. Both these values are updated when a break statement is
-    /// encountered.
-    active_loop: Option<ActiveLoop>,
+    /// Information on the loops that we're processing, from the outermost to
+    /// the innermost loop, together with their labels. For a `loop` the entry
+    /// contains the current type of the loop statement (initially `never`)
+    /// and the expected type of the loop expression. Both these values are
+    /// updated when a break statement that targets the loop is encountered.
+    active_loops: Vec<(Option<Name>, ActiveLoop)>,
 
     /// The return type of the function being inferred.
     return_ty: Ty,
 
     /// Stores the resolution of method calls
     method_resolution: FxHashMap<ExprId, FunctionId>,
+    builtin_calls: FxHashMap<ExprId, BuiltinFunction>,
 }
 
 impl<'a> InferenceResultBuilder<'a> {
@@ -190,13 +206,14 @@ impl<'a> InferenceResultBuilder<'a> {
             type_of_expr: ArenaMap::default(),
             type_of_pat: ArenaMap::default(),
             diagnostics: Vec::default(),
-            active_loop: None,
+            active_loops: Vec::new(),
             type_variables: TypeVariableTable::default(),
             db,
             body,
             resolver,
             return_ty: TyKind::Unknown.intern(), // set in collect_fn_signature
             method_resolution: FxHashMap::default(),
+            builtin_calls: FxHashMap::default(),
         }
     }
 
@@ -204,6 +221,8 @@ impl<'a> InferenceResultBuilder<'a> {
     pub fn module(&self) -> ModuleId {
         match self.body.owner() {
             DefWithBodyId::FunctionId(func) => func.module(self.db.upcast()),
+            DefWithBodyId::ConstId(konst) => konst.module(self.db.upcast()),
+            DefWithBodyId::StaticId(static_def) => static_def.module(self.db.upcast()),
         }
     }
 
@@ -233,8 +252,8 @@ impl<'a> InferenceResultBuilder<'a> {
         // Convert the diagnostics from resolving the type reference
         for diag in diagnostics {
             let diag = match diag {
-                LowerDiagnostic::UnresolvedType { id } => {
-                    InferenceDiagnostic::UnresolvedType { id }
+                LowerDiagnostic::UnresolvedType { id, similar_name } => {
+                    InferenceDiagnostic::UnresolvedType { id, similar_name }
                 }
                 LowerDiagnostic::TypeIsPrivate { id } => InferenceDiagnostic::TypeIsPrivate { id },
             };
@@ -266,17 +285,280 @@ impl InferenceResultBuilder<'_> {
         self.return_ty = self.resolve_type(self.body.ret_type());
     }
 
+    /// Resolves the declared type of a constant or static, the type its
+    /// initializer must have.
+    fn infer_const_signature(&mut self) {
+        self.return_ty = self.resolve_type(self.body.ret_type());
+    }
+
     /// Record the type of the specified pattern and all sub-patterns.
+    ///
+    /// Mun has no references, so every binding binds by value: a binding of a
+    /// field of a struct holds a copy of the field. For fields that are `gc`
+    /// structs, this is a copy of the reference.
     fn infer_pat(&mut self, pat: PatId, ty: Ty) {
-        #[allow(clippy::single_match)]
-        match &self.body[pat] {
-            Pat::Bind { name: _name } => {
+        let body = self.body;
+        match &body[pat] {
+            Pat::Bind { .. } => {
+                self.set_pat_type(pat, ty);
+            }
+            Pat::Record {
+                path,
+                args,
+                ellipsis,
+            } => {
+                let ty = self.infer_record_pat(pat, path.as_ref(), args, *ellipsis, ty);
+                self.set_pat_type(pat, ty);
+            }
+            Pat::TupleStruct {
+                path,
+                args,
+                ellipsis,
+            } => {
+                let ty = self.infer_tuple_struct_pat(pat, path.as_ref(), args, *ellipsis, ty);
                 self.set_pat_type(pat, ty);
             }
-            _ => {}
+            Pat::Tuple { args, ellipsis } => {
+                let ty = self.infer_tuple_pat(pat, args, *ellipsis, ty);
+                self.set_pat_type(pat, ty);
+            }
+            Pat::Missing | Pat::Wild | Pat::Path(_) => {}
         }
     }
 
+    /// Infers the types of the fields of a record pattern, e.g.
+    /// `Foo { a, b: c }`, destructuring a value of type `expected`.
+    fn infer_record_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        args: &[RecordFieldPat],
+        ellipsis: bool,
+        expected: Ty,
+    ) -> Ty {
+        let Some((ty, def)) = self.resolve_struct_pat(pat, path, &expected, StructKind::Record)
+        else {
+            for field in args {
+                self.infer_pat(field.pat, error_type());
+            }
+            return error_type();
+        };
+
+        for field in args {
+            let field_ty =
+                if let Some((field_ty, is_visible)) = self.lookup_field(ty.clone(), &field.name) {
+                    if !is_visible {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::PrivatePatField { id: field.pat });
+                    }
+                    field_ty
+                } else {
+                    let similar_field = self.find_similar_field(&ty, &field.name);
+                    self.diagnostics.push(InferenceDiagnostic::NoSuchPatField {
+                        id: field.pat,
+                        similar_field,
+                    });
+                    error_type()
+                };
+            self.infer_pat(field.pat, field_ty);
+        }
+
+        if !ellipsis {
+            let pat_fields: FxHashSet<_> = args.iter().map(|f| &f.name).collect();
+            let missed_fields: Vec<Name> = def
+                .data(self.db.upcast())
+                .fields
+                .iter()
+                .map(|(_, d)| d.name.clone())
+                .filter(|name| !pat_fields.contains(name))
+                .collect();
+            if !missed_fields.is_empty() {
+                self.diagnostics
+                    .push(InferenceDiagnostic::MissingPatFields {
+                        id: pat,
+                        struct_ty: ty.clone(),
+                        names: missed_fields,
+                    });
+            }
+        }
+
+        ty
+    }
+
+    /// Infers the types of the fields of a tuple struct pattern, e.g.
+    /// `Foo(a, _)`, destructuring a value of type `expected`.
+    fn infer_tuple_struct_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        args: &[PatId],
+        ellipsis: bool,
+        expected: Ty,
+    ) -> Ty {
+        let Some((ty, def)) = self.resolve_struct_pat(pat, path, &expected, StructKind::Tuple)
+        else {
+            for arg in args {
+                self.infer_pat(*arg, error_type());
+            }
+            return error_type();
+        };
+
+        let num_fields = def.data(self.db.upcast()).fields.len();
+        if args.len() > num_fields || (!ellipsis && args.len() != num_fields) {
+            self.diagnostics
+                .push(InferenceDiagnostic::PatFieldCountMismatch {
+                    id: pat,
+                    found: args.len(),
+                    expected: num_fields,
+                });
+        }
+
+        for (idx, arg) in args.iter().enumerate() {
+            let field_ty = match self.lookup_field(ty.clone(), &Name::new_tuple_field(idx)) {
+                Some((field_ty, is_visible)) => {
+                    if !is_visible {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::PrivatePatField { id: *arg });
+                    }
+                    field_ty
+                }
+                None => error_type(),
+            };
+            self.infer_pat(*arg, field_ty);
+        }
+
+        ty
+    }
+
+    /// Infers the types of the elements of a tuple pattern, e.g. `(a, _, ..)`,
+    /// destructuring a value of type `expected`.
+    fn infer_tuple_pat(&mut self, pat: PatId, args: &[PatId], ellipsis: bool, expected: Ty) -> Ty {
+        let expected = self.replace_if_possible(&expected).into_owned();
+        let elems = match expected.interned() {
+            TyKind::Tuple(_, elems) => elems.clone(),
+            // The type of the destructured value is not yet known, without `..` the pattern
+            // determines the type.
+            TyKind::InferenceVar(InferTy::Type(_)) if !ellipsis => {
+                let elems: Substitution = args
+                    .iter()
+                    .map(|_| self.type_variables.new_type_var())
+                    .collect();
+                let ty = TyKind::Tuple(elems.len(), elems.clone()).intern();
+                self.unify(&ty, &expected);
+                elems
+            }
+            TyKind::Unknown | TyKind::InferenceVar(InferTy::Type(_)) => {
+                for arg in args {
+                    self.infer_pat(*arg, error_type());
+                }
+                return error_type();
+            }
+            _ => {
+                self.diagnostics
+                    .push(InferenceDiagnostic::MismatchedPatType {
+                        id: pat,
+                        expected,
+                        found: TyKind::Tuple(
+                            args.len(),
+                            args.iter().map(|_| error_type()).collect(),
+                        )
+                        .intern(),
+                    });
+                for arg in args {
+                    self.infer_pat(*arg, error_type());
+                }
+                return error_type();
+            }
+        };
+
+        if args.len() > elems.len() || (!ellipsis && args.len() != elems.len()) {
+            self.diagnostics
+                .push(InferenceDiagnostic::TuplePatLengthMismatch {
+                    id: pat,
+                    found: args.len(),
+                    expected: elems.len(),
+                });
+        }
+
+        for (idx, arg) in args.iter().enumerate() {
+            let elem_ty = elems.get(idx).cloned().unwrap_or_else(error_type);
+            self.infer_pat(*arg, elem_ty);
+        }
+
+        TyKind::Tuple(elems.len(), elems).intern()
+    }
+
+    /// Resolves the path of a struct pattern and checks that it matches the
+    /// type that is destructured. Returns `None` if the path does not refer
+    /// to a struct of the expected kind.
+    fn resolve_struct_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        expected: &Ty,
+        kind: StructKind,
+    ) -> Option<(Ty, Struct)> {
+        let resolved = path.and_then(|path| {
+            self.resolver
+                .resolve_path_as_type_fully(self.db.upcast(), path)
+        });
+        let Some((type_ns, vis)) = resolved else {
+            let similar_name = path.and_then(Path::as_ident).and_then(|name| {
+                self.resolver
+                    .find_similar_name(self.db.upcast(), name, Namespace::Types)
+            });
+            self.diagnostics.push(InferenceDiagnostic::UnresolvedValue {
+                id: pat.into(),
+                similar_name,
+            });
+            return None;
+        };
+
+        if !vis.is_visible_from(self.db, self.module()) {
+            self.diagnostics
+                .push(InferenceDiagnostic::PatPathIsPrivate { id: pat });
+        }
+
+        let type_for_def_fn = |def| self.db.type_for_def(def, Namespace::Types);
+        let ty = match type_ns {
+            TypeNs::SelfType(id) => self.db.type_for_impl_self(id),
+            TypeNs::StructId(id) => type_for_def_fn(TypableDef::Struct(id.into())),
+            TypeNs::TypeAliasId(id) => type_for_def_fn(TypableDef::TypeAlias(id.into())),
+            TypeNs::PrimitiveType(id) => type_for_def_fn(TypableDef::PrimitiveType(id)),
+        };
+
+        if !self.unify(&ty, expected) {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedPatType {
+                    id: pat,
+                    expected: expected.clone(),
+                    found: ty,
+                });
+            return None;
+        }
+
+        let Some(def) = ty.as_struct() else {
+            self.diagnostics.push(InferenceDiagnostic::UnresolvedValue {
+                id: pat.into(),
+                similar_name: None,
+            });
+            return None;
+        };
+
+        let struct_kind = def.data(self.db.upcast()).kind;
+        if struct_kind != kind {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedStructPat {
+                    id: pat,
+                    expected: struct_kind,
+                    found: kind,
+                });
+            return None;
+        }
+
+        Some((ty, def))
+    }
+
     /// Infer the types of all the expressions and sub-expressions in the body.
     fn infer_body(&mut self) {
         self.infer_expr_coerce(
@@ -314,10 +596,13 @@ impl InferenceResultBuilder<'_> {
         let ty = if !self.coerce(&ty, &expected.ty) {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
-                found: ty.clone(),
+                found: ty,
                 id: expr,
             });
-            ty
+
+            // Continue with the expected type, otherwise the mismatch is reported again
+            // for every block or branch that encloses `expr`.
+            expected.ty.clone()
         } else if expected.ty.is_unknown() {
             ty
         } else {
@@ -423,10 +708,30 @@ impl InferenceResultBuilder<'_> {
 
                 TyKind::Never.intern()
             }
-            Expr::Break { expr } => self.infer_break(tgt_expr, *expr),
-            Expr::Loop { body } => self.infer_loop_expr(tgt_expr, *body, expected),
-            Expr::While { condition, body } => {
-                self.infer_while_expr(tgt_expr, *condition, *body, expected)
+            Expr::Break { expr, label } => self.infer_break(tgt_expr, *expr, label.as_ref()),
+            Expr::Continue { label } => self.infer_continue(tgt_expr, label.as_ref()),
+            Expr::Loop { body, label } => {
+                self.infer_loop_expr(tgt_expr, *body, label.clone(), expected)
+            }
+            Expr::While {
+                condition,
+                body,
+                label,
+            } => self.infer_while_expr(tgt_expr, *condition, *body, label.clone(), expected),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            } => self.infer_for_expr(tgt_expr, *pat, *iterable, *body, label.clone()),
+            Expr::Range { start, end, .. } => {
+                // Ranges are only supported as the iterable of a `for` loop, where they are
+                // handled by `infer_for_expr`.
+                self.diagnostics
+                    .push(InferenceDiagnostic::UnsupportedRange { id: tgt_expr });
+                self.infer_expr(*start, &Expectation::none());
+                self.infer_expr(*end, &Expectation::none());
+                error_type()
             }
             Expr::RecordLit {
                 type_id,
@@ -447,6 +752,7 @@ impl InferenceResultBuilder<'_> {
                                 self.diagnostics.push(InferenceDiagnostic::NoSuchField {
                                     id: tgt_expr,
                                     field: idx,
+                                    similar_field: self.find_similar_field(&ty, &field.name),
                                 });
                                 None
                             }
@@ -475,11 +781,13 @@ impl InferenceResultBuilder<'_> {
                     }
                     field_ty
                 } else {
+                    let similar_field = self.find_similar_field(&receiver_ty, name);
                     self.diagnostics
                         .push(InferenceDiagnostic::AccessUnknownField {
                             id: tgt_expr,
                             receiver_ty,
                             name: name.clone(),
+                            similar_field,
                         });
                     error_type()
                 }
@@ -526,8 +834,37 @@ impl InferenceResultBuilder<'_> {
                     self.infer_expr_coerce(*expr, &Expectation::has_type(elem_ty.clone()));
                 }
 
+                // Tuples have no runtime type information, so they cannot be stored in the heap
+                // allocated memory of an array.
+                let elem_ty = self.resolve_ty_as_far_as_possible(elem_ty);
+                if matches!(elem_ty.interned(), TyKind::Tuple(..)) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::TupleInArray { id: tgt_expr });
+                }
+
                 TyKind::Array(elem_ty).intern()
             }
+            Expr::Tuple(exprs) => {
+                let expected_elems = match expected.ty.interned() {
+                    TyKind::Tuple(len, elems) if *len == exprs.len() => Some(elems.clone()),
+                    _ => None,
+                };
+
+                let elems: Substitution = exprs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, expr)| {
+                        let expected = expected_elems
+                            .as_ref()
+                            .map_or_else(Expectation::none, |elems| {
+                                Expectation::has_type(elems[idx].clone())
+                            });
+                        self.infer_expr_coerce(*expr, &expected)
+                    })
+                    .collect();
+
+                TyKind::Tuple(elems.len(), elems).intern()
+            }
             Expr::Index { base, index } => {
                 let elem_ty = if expected.ty.is_unknown() {
                     self.type_variables.new_type_var()
@@ -574,23 +911,50 @@ impl InferenceResultBuilder<'_> {
                 self.diagnostics
                     .push(InferenceDiagnostic::IncompatibleBranches {
                         id: tgt_expr,
+                        then_branch: self.value_expr(then_branch),
+                        else_branch: self.value_expr(else_branch),
                         then_ty: then_ty.clone(),
                         else_ty: else_ty.clone(),
                     });
                 then_ty
             }
+        } else if !self.coerce(&then_ty, &Ty::unit()) {
+            self.diagnostics
+                .push(InferenceDiagnostic::MissingElseBranch {
+                    id: tgt_expr,
+                    then_ty: then_ty.clone(),
+                });
+
+            // Continue with the type of the `then` branch to avoid reporting a mismatch
+            // for every use of the value of the `if` expression.
+            then_ty
         } else {
-            if !self.coerce(&then_ty, &Ty::unit()) {
-                self.diagnostics
-                    .push(InferenceDiagnostic::MissingElseBranch {
-                        id: tgt_expr,
-                        then_ty,
-                    });
-            }
             Ty::unit()
         }
     }
 
+    /// Returns the expression that determines the value of `expr`. For a block
+    /// that is the trailing expression of the block, e.g. `b` for `{ a; b }`.
+    fn value_expr(&self, expr: ExprId) -> ExprId {
+        match &self.body[expr] {
+            Expr::Block {
+                tail: Some(tail), ..
+            } => self.value_expr(*tail),
+            _ => expr,
+        }
+    }
+
+    /// Returns the field of the struct `ty` with a name that is most similar to
+    /// `field_name`, which is suggested when there is no field with that name.
+    fn find_similar_field(&self, ty: &Ty, field_name: &Name) -> Option<Field> {
+        let fields = ty.as_struct()?.fields(self.db);
+        find_similar_name(
+            field_name,
+            fields.into_iter().map(|field| (field.name(self.db), field)),
+        )
+        .map(|(_, field)| field)
+    }
+
     fn lookup_field(&mut self, receiver_ty: Ty, field_name: &Name) -> Option<(Ty, bool)> {
         match receiver_ty.interned() {
             TyKind::Tuple(_, subs) => {
@@ -719,6 +1083,20 @@ impl InferenceResultBuilder<'_> {
         args: &[ExprId],
         _expected: &Expectation,
     ) -> Ty {
+        // Builtin functions have no type, their calls are checked separately
+        if let Expr::Path(path) = &self.body[callee] {
+            let resolver = resolver_for_expr(self.db.upcast(), self.body.owner(), callee);
+            if let Some((ValueNs::BuiltinFunction(function), _)) =
+                resolver.resolve_path_as_value_fully(self.db.upcast(), path)
+            {
+                return if function.is_math() {
+                    self.infer_math_builtin_call(tgt_expr, function, args)
+                } else {
+                    self.infer_builtin_call(tgt_expr, function, args)
+                };
+            }
+        }
+
         let callee_ty = self.infer_expr_inner(
             callee,
             &Expectation::none(),
@@ -779,6 +1157,111 @@ impl InferenceResultBuilder<'_> {
         }
     }
 
+    /// Inferences the type of a call to a builtin function.
+    fn infer_builtin_call(
+        &mut self,
+        tgt_expr: ExprId,
+        function: BuiltinFunction,
+        args: &[ExprId],
+    ) -> Ty {
+        self.builtin_calls.insert(tgt_expr, function);
+
+        // Both `panic(message)` and `assert(condition, message?)` take an optional
+        // message as their last argument
+        let (params, min_args, ret_ty) = match function {
+            BuiltinFunction::Panic => (&[][..], 1, TyKind::Never.intern()),
+            BuiltinFunction::Assert => (&[TyKind::Bool][..], 1, Ty::unit()),
+            _ => unreachable!("math builtins are inferred separately"),
+        };
+        let max_args = params.len() + 1;
+        if args.len() < min_args || args.len() > max_args {
+            self.diagnostics
+                .push(InferenceDiagnostic::ParameterCountMismatch {
+                    id: tgt_expr,
+                    found: args.len(),
+                    expected: if args.len() < min_args {
+                        min_args
+                    } else {
+                        max_args
+                    },
+                });
+        }
+
+        for (idx, &arg) in args.iter().enumerate() {
+            if let Some(param_ty) = params.get(idx) {
+                self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone().intern()));
+            } else {
+                if !matches!(self.body[arg], Expr::Literal(Literal::String(_))) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::ExpectedStringLiteral { id: arg });
+                }
+                self.infer_expr(arg, &Expectation::none());
+            }
+        }
+
+        ret_ty
+    }
+
+    /// Inferences the type of a call to a builtin math function. All arguments
+    /// must be numbers of the same type, which is also the type of the result.
+    fn infer_math_builtin_call(
+        &mut self,
+        tgt_expr: ExprId,
+        function: BuiltinFunction,
+        args: &[ExprId],
+    ) -> Ty {
+        self.builtin_calls.insert(tgt_expr, function);
+
+        let param_count = match function {
+            BuiltinFunction::Min | BuiltinFunction::Max => 2,
+            _ => 1,
+        };
+        if args.len() != param_count {
+            self.diagnostics
+                .push(InferenceDiagnostic::ParameterCountMismatch {
+                    id: tgt_expr,
+                    found: args.len(),
+                    expected: param_count,
+                });
+        }
+
+        // The first argument determines the type of all other arguments
+        let Some((&first, rest)) = args.split_first() else {
+            return error_type();
+        };
+        let ty = self.infer_expr(first, &Expectation::none());
+        for &arg in rest {
+            self.infer_expr_coerce(arg, &Expectation::has_type(ty.clone()));
+        }
+
+        let ty = self.replace_if_possible(&ty).into_owned();
+        let is_valid = match ty.interned() {
+            TyKind::Float(_) | TyKind::InferenceVar(InferTy::Float(_)) => true,
+            TyKind::Int(int_ty) => match function {
+                BuiltinFunction::Abs => int_ty.signedness.is_signed(),
+                BuiltinFunction::Min | BuiltinFunction::Max => true,
+                _ => false,
+            },
+            TyKind::InferenceVar(InferTy::Int(_)) => matches!(
+                function,
+                BuiltinFunction::Abs | BuiltinFunction::Min | BuiltinFunction::Max
+            ),
+            TyKind::Unknown => return ty,
+            _ => false,
+        };
+        if is_valid {
+            ty
+        } else {
+            self.diagnostics
+                .push(InferenceDiagnostic::InvalidBuiltinArgument {
+                    id: first,
+                    function,
+                    ty,
+                });
+            error_type()
+        }
+    }
+
     /// Checks whether the specified struct type is a unit struct.
     fn check_unit_struct_lit(&mut self, tgt_expr: ExprId, expected: Struct) {
         let struct_data = expected.data(self.db.upcast());
@@ -946,6 +1429,30 @@ impl InferenceResultBuilder<'_> {
                         .type_for_def(TypableDef::Function(f.into()), Namespace::Values);
                     Some(ty)
                 }
+                ValueNs::ConstId(c) => {
+                    let ty = self
+                        .db
+                        .type_for_def(TypableDef::Const(c.into()), Namespace::Values);
+                    Some(ty)
+                }
+                ValueNs::StaticId(s) => {
+                    // A static lives in the assembly of the module that defines it. There is
+                    // no way (yet) to link to the global of another assembly.
+                    if s.module(self.db.upcast()) != self.module() {
+                        let name = Static::from(s).name(self.db);
+                        self.diagnostics
+                            .push(InferenceDiagnostic::StaticInOtherModule { id, name });
+                    }
+                    let ty = self
+                        .db
+                        .type_for_def(TypableDef::Static(s.into()), Namespace::Values);
+                    Some(ty)
+                }
+                ValueNs::BuiltinFunction(function) => {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::BuiltinFunctionNotCalled { id, function });
+                    None
+                }
                 ValueNs::StructId(s) => {
                     if check_params.is_unit_struct {
                         self.check_unit_struct_lit(id, s.into());
@@ -993,8 +1500,13 @@ impl InferenceResultBuilder<'_> {
 
             // If the path also cannot be resolved as type, it must be considered an invalid
             // value and there is nothing we can make of this path.
-            self.diagnostics
-                .push(InferenceDiagnostic::UnresolvedValue { id: id.into() });
+            let similar_name = path.as_ident().and_then(|name| {
+                resolver.find_similar_name(self.db.upcast(), name, Namespace::Values)
+            });
+            self.diagnostics.push(InferenceDiagnostic::UnresolvedValue {
+                id: id.into(),
+                similar_name,
+            });
             None
         }
     }
@@ -1029,6 +1541,7 @@ impl InferenceResultBuilder<'_> {
             diagnostics: self.diagnostics,
             standard_types: InternedStandardTypes::default(),
             method_resolutions: self.method_resolution,
+            builtin_calls: self.builtin_calls,
         }
     }
 
@@ -1087,22 +1600,40 @@ impl InferenceResultBuilder<'_> {
         }
     }
 
-    fn infer_break(&mut self, tgt_expr: ExprId, expr: Option<ExprId>) -> Ty {
-        let expected = match &self.active_loop {
-            Some(ActiveLoop::Loop(_, info)) => info.clone(),
-            Some(_) => {
-                if expr.is_some() {
-                    self.diagnostics
-                        .push(InferenceDiagnostic::BreakWithValueOutsideLoop { id: tgt_expr });
-                }
-                return TyKind::Never.intern();
-            }
-            None => {
+    /// Returns the index in `active_loops` of the loop with the given label, or
+    /// of the innermost loop if no label is specified.
+    fn find_active_loop(&self, label: Option<&Name>) -> Option<usize> {
+        match label {
+            Some(label) => self
+                .active_loops
+                .iter()
+                .rposition(|(loop_label, _)| loop_label.as_ref() == Some(label)),
+            None => self.active_loops.len().checked_sub(1),
+        }
+    }
+
+    fn infer_break(&mut self, tgt_expr: ExprId, expr: Option<ExprId>, label: Option<&Name>) -> Ty {
+        // Find the loop that is broken out of, which is the innermost loop if no label
+        // is specified
+        let Some(target) = self.find_active_loop(label) else {
+            self.diagnostics.push(match label {
+                Some(label) => InferenceDiagnostic::UndeclaredLabel {
+                    id: tgt_expr,
+                    label: label.clone(),
+                },
+                None => InferenceDiagnostic::BreakOutsideLoop { id: tgt_expr },
+            });
+            return TyKind::Never.intern();
+        };
+
+        let ActiveLoop::Loop(_, expected) = &self.active_loops[target].1 else {
+            if expr.is_some() {
                 self.diagnostics
-                    .push(InferenceDiagnostic::BreakOutsideLoop { id: tgt_expr });
-                return TyKind::Never.intern();
+                    .push(InferenceDiagnostic::BreakWithValueOutsideLoop { id: tgt_expr });
             }
+            return TyKind::Never.intern();
         };
+        let expected = expected.clone();
 
         // Infer the type of the break expression
         let ty = if let Some(expr) = expr {
@@ -1118,20 +1649,42 @@ impl InferenceResultBuilder<'_> {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
                 found: ty,
-                id: tgt_expr,
+                id: expr.unwrap_or(tgt_expr),
             });
             expected.ty
         };
 
         // Update the expected type for the rest of the loop
-        self.active_loop = Some(ActiveLoop::Loop(ty.clone(), Expectation::has_type(ty)));
+        self.active_loops[target].1 = ActiveLoop::Loop(ty.clone(), Expectation::has_type(ty));
+
+        TyKind::Never.intern()
+    }
+
+    fn infer_continue(&mut self, tgt_expr: ExprId, label: Option<&Name>) -> Ty {
+        if self.find_active_loop(label).is_none() {
+            self.diagnostics.push(match label {
+                Some(label) => InferenceDiagnostic::UndeclaredLabel {
+                    id: tgt_expr,
+                    label: label.clone(),
+                },
+                None => InferenceDiagnostic::ContinueOutsideLoop { id: tgt_expr },
+            });
+        }
 
         TyKind::Never.intern()
     }
 
-    fn infer_loop_expr(&mut self, _tgt_expr: ExprId, body: ExprId, expected: &Expectation) -> Ty {
+    fn infer_loop_expr(
+        &mut self,
+        tgt_expr: ExprId,
+        body: ExprId,
+        label: Option<Name>,
+        expected: &Expectation,
+    ) -> Ty {
         if let ActiveLoop::Loop(ty, _) = self.infer_loop_block(
+            tgt_expr,
             body,
+            label,
             ActiveLoop::Loop(TyKind::Never.intern(), expected.clone()),
         ) {
             ty
@@ -1140,25 +1693,87 @@ impl InferenceResultBuilder<'_> {
         }
     }
 
-    fn infer_loop_block(&mut self, body: ExprId, lp: ActiveLoop) -> ActiveLoop {
-        let top_level_loop = std::mem::replace(&mut self.active_loop, Some(lp));
+    fn infer_loop_block(
+        &mut self,
+        tgt_expr: ExprId,
+        body: ExprId,
+        label: Option<Name>,
+        lp: ActiveLoop,
+    ) -> ActiveLoop {
+        // A label shadowing the label of an enclosing loop would make it impossible to
+        // refer to the outer loop.
+        if let Some(label) = &label {
+            if self
+                .active_loops
+                .iter()
+                .any(|(loop_label, _)| loop_label.as_ref() == Some(label))
+            {
+                self.diagnostics.push(InferenceDiagnostic::DuplicateLabel {
+                    id: tgt_expr,
+                    label: label.clone(),
+                });
+            }
+        }
+
+        self.active_loops.push((label, lp));
 
         // Infer the body of the loop
         self.infer_expr_coerce(body, &Expectation::has_type(Ty::unit()));
 
-        // Take the result of the loop information and replace with top level loop
-        std::mem::replace(&mut self.active_loop, top_level_loop).unwrap()
+        // Take the result of the loop information
+        let (_, lp) = self
+            .active_loops
+            .pop()
+            .expect("the active loop must still be on the stack");
+        lp
     }
 
     fn infer_while_expr(
         &mut self,
-        _tgt_expr: ExprId,
+        tgt_expr: ExprId,
         condition: ExprId,
         body: ExprId,
+        label: Option<Name>,
         _expected: &Expectation,
     ) -> Ty {
         self.infer_expr(condition, &Expectation::has_type(TyKind::Bool.intern()));
-        self.infer_loop_block(body, ActiveLoop::While);
+        self.infer_loop_block(tgt_expr, body, label, ActiveLoop::While);
+        Ty::unit()
+    }
+
+    fn infer_for_expr(
+        &mut self,
+        tgt_expr: ExprId,
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+        label: Option<Name>,
+    ) -> Ty {
+        let elem_ty = if let Expr::Range { start, end, .. } = &self.body[iterable] {
+            // Both bounds of a range must be of the same integer type. The type of the range
+            // expression itself is recorded as the type of its elements.
+            let int_ty = self.type_variables.new_integer_var();
+            let start_ty = self.infer_expr(*start, &Expectation::has_type(int_ty));
+            let elem_ty = self.infer_expr(*end, &Expectation::has_type(start_ty));
+            self.set_expr_type(iterable, elem_ty.clone());
+            elem_ty
+        } else {
+            let iterable_ty = self.infer_expr(iterable, &Expectation::none());
+            if let TyKind::Array(elem_ty) = iterable_ty.interned() {
+                elem_ty.clone()
+            } else {
+                if iterable_ty.is_known() {
+                    self.diagnostics.push(InferenceDiagnostic::NotIterable {
+                        id: iterable,
+                        ty: iterable_ty,
+                    });
+                }
+                error_type()
+            }
+        };
+
+        self.infer_pat(pat, elem_ty);
+        self.infer_loop_block(tgt_expr, body, label, ActiveLoop::For);
         Ty::unit()
     }
 
@@ -1250,29 +1865,40 @@ impl From<PatId> for ExprOrPatId {
 }
 
 mod diagnostics {
+    use mun_syntax::{AstNode, SyntaxNodePtr};
+
     use crate::{
-        code_model::{src::HasSource, StructKind},
+        code_model::{src::item_definition_source, DefWithBody, HasSource, StructKind},
         diagnostics::{
-            AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-            CannotApplyUnaryOp, CyclicType, DiagnosticSink, ExpectedFunction, FieldCountMismatch,
-            IncompatibleBranch, InvalidLhs, LiteralOutOfRange, MethodNotFound, MethodNotInScope,
-            MismatchedStructLit, MismatchedType, MissingElseBranch, MissingFields, NoFields,
-            NoSuchField, ParameterCountMismatch, PrivateAccess, ReturnMissingExpression,
-            UnresolvedType, UnresolvedValue,
+            AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop,
+            BuiltinFunctionNotCalled, CannotApplyBinaryOp, CannotApplyUnaryOp, ContinueOutsideLoop,
+            CyclicType, DiagnosticSink, DuplicateLabel, ExpectedFunction, ExpectedStringLiteral,
+            FieldCountMismatch, IncompatibleBranch, InvalidBuiltinArgument, InvalidLhs,
+            LiteralOutOfRange, MethodNotFound, MethodNotInScope, MismatchedStructLit,
+            MismatchedStructPat, MismatchedType, MissingElseBranch, MissingFields, NoFields,
+            NoSuchField, NotIterable, ParameterCountMismatch, PatFieldCountMismatch, PrivateAccess,
+            ReturnMissingExpression, SimilarName, StaticInOtherModule, TupleInArray,
+            TuplePatLengthMismatch, UndeclaredLabel, UnresolvedType, UnresolvedValue,
+            UnsupportedRange,
         },
         ids::FunctionId,
+        in_file::InFile,
+        name_resolution::Namespace,
+        resolve::ScopeDef,
         ty::infer::ExprOrPatId,
         type_ref::LocalTypeRefId,
-        ExprId, Function, HirDatabase, IntTy, Name, Ty,
+        BuiltinFunction, ExprId, Field, HirDatabase, IntTy, Name, PatId, Ty,
     };
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub(crate) enum InferenceDiagnostic {
         UnresolvedValue {
             id: ExprOrPatId,
+            similar_name: Option<(Name, ScopeDef)>,
         },
         UnresolvedType {
             id: LocalTypeRefId,
+            similar_name: Option<(Name, ScopeDef)>,
         },
         CyclicType {
             id: LocalTypeRefId,
@@ -1293,6 +1919,8 @@ mod diagnostics {
         },
         IncompatibleBranches {
             id: ExprId,
+            then_branch: ExprId,
+            else_branch: ExprId,
             then_ty: Ty,
             else_ty: Ty,
         },
@@ -1319,13 +1947,44 @@ mod diagnostics {
         BreakOutsideLoop {
             id: ExprId,
         },
+        ContinueOutsideLoop {
+            id: ExprId,
+        },
+        UndeclaredLabel {
+            id: ExprId,
+            label: Name,
+        },
+        DuplicateLabel {
+            id: ExprId,
+            label: Name,
+        },
+        ExpectedStringLiteral {
+            id: ExprId,
+        },
+        BuiltinFunctionNotCalled {
+            id: ExprId,
+            function: BuiltinFunction,
+        },
+        InvalidBuiltinArgument {
+            id: ExprId,
+            function: BuiltinFunction,
+            ty: Ty,
+        },
         BreakWithValueOutsideLoop {
             id: ExprId,
         },
+        NotIterable {
+            id: ExprId,
+            ty: Ty,
+        },
+        UnsupportedRange {
+            id: ExprId,
+        },
         AccessUnknownField {
             id: ExprId,
             receiver_ty: Ty,
             name: Name,
+            similar_field: Option<Field>,
         },
         AccessPrivateField {
             id: ExprId,
@@ -1354,6 +2013,7 @@ mod diagnostics {
         NoSuchField {
             id: ExprId,
             field: usize,
+            similar_field: Option<Field>,
         },
         LiteralOutOfRange {
             id: ExprId,
@@ -1365,10 +2025,52 @@ mod diagnostics {
         PathIsPrivate {
             id: ExprId,
         },
+        StaticInOtherModule {
+            id: ExprId,
+            name: Name,
+        },
         MethodNotInScope {
             id: ExprId,
             receiver_ty: Ty,
         },
+        MismatchedPatType {
+            id: PatId,
+            expected: Ty,
+            found: Ty,
+        },
+        MismatchedStructPat {
+            id: PatId,
+            expected: StructKind,
+            found: StructKind,
+        },
+        MissingPatFields {
+            id: PatId,
+            struct_ty: Ty,
+            names: Vec<Name>,
+        },
+        NoSuchPatField {
+            id: PatId,
+            similar_field: Option<Field>,
+        },
+        PrivatePatField {
+            id: PatId,
+        },
+        PatFieldCountMismatch {
+            id: PatId,
+            found: usize,
+            expected: usize,
+        },
+        PatPathIsPrivate {
+            id: PatId,
+        },
+        TuplePatLengthMismatch {
+            id: PatId,
+            found: usize,
+            expected: usize,
+        },
+        TupleInArray {
+            id: ExprId,
+        },
         MethodNotFound {
             id: ExprId,
             method_name: Name,
@@ -1382,13 +2084,43 @@ mod diagnostics {
         pub(crate) fn add_to(
             &self,
             db: &dyn HirDatabase,
-            owner: Function,
+            owner: DefWithBody,
             sink: &mut DiagnosticSink<'_>,
         ) {
-            let file = owner.source(db.upcast()).file_id;
+            let file = owner.file_id(db);
             let body = owner.body_source_map(db);
+            let pat_syntax = |id: PatId| {
+                body.pat_syntax(id)
+                    .expect("could not resolve pattern to syntax node")
+                    .value
+                    .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+            };
+            let similar_name = |similar_name: &Option<(Name, ScopeDef)>, ns: Namespace| {
+                similar_name.as_ref().map(|(name, def)| SimilarName {
+                    name: name.clone(),
+                    definition: match def {
+                        ScopeDef::Local(pat) => Some(InFile::new(file, pat_syntax(*pat))),
+                        _ => def
+                            .item(ns)
+                            .and_then(|item| item_definition_source(db.upcast(), item)),
+                    },
+                })
+            };
+            let similar_field = |field: &Option<Field>| {
+                field.map(|field| SimilarName {
+                    name: field.name(db),
+                    definition: Some(
+                        field
+                            .source(db.upcast())
+                            .map(|it| SyntaxNodePtr::new(it.syntax())),
+                    ),
+                })
+            };
             match self {
-                InferenceDiagnostic::UnresolvedValue { id } => {
+                InferenceDiagnostic::UnresolvedValue {
+                    id,
+                    similar_name: similar,
+                } => {
                     let expr = match id {
                         ExprOrPatId::ExprId(id) => body.expr_syntax(*id).map(|ptr| {
                             ptr.value
@@ -1401,11 +2133,27 @@ mod diagnostics {
                     }
                     .unwrap();
 
-                    sink.push(UnresolvedValue { file, expr });
+                    // Paths in patterns refer to struct types
+                    let ns = match id {
+                        ExprOrPatId::ExprId(_) => Namespace::Values,
+                        ExprOrPatId::PatId(_) => Namespace::Types,
+                    };
+                    sink.push(UnresolvedValue {
+                        file,
+                        expr,
+                        similar_name: similar_name(similar, ns),
+                    });
                 }
-                InferenceDiagnostic::UnresolvedType { id } => {
+                InferenceDiagnostic::UnresolvedType {
+                    id,
+                    similar_name: similar,
+                } => {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
-                    sink.push(UnresolvedType { file, type_ref });
+                    sink.push(UnresolvedType {
+                        file,
+                        type_ref,
+                        similar_name: similar_name(similar, Namespace::Types),
+                    });
                 }
                 InferenceDiagnostic::CyclicType { id } => {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
@@ -1431,6 +2179,18 @@ mod diagnostics {
                         expr: expr_syntax,
                     });
                 }
+                InferenceDiagnostic::StaticInOtherModule { id, name } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(StaticInOtherModule {
+                        file,
+                        expr,
+                        name: name.clone(),
+                    });
+                }
                 InferenceDiagnostic::ParameterCountMismatch {
                     id,
                     expected,
@@ -1479,17 +2239,22 @@ mod diagnostics {
                 }
                 InferenceDiagnostic::IncompatibleBranches {
                     id,
+                    then_branch,
+                    else_branch,
                     then_ty,
                     else_ty,
                 } => {
-                    let expr = body
-                        .expr_syntax(*id)
-                        .unwrap()
-                        .value
-                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    let expr_syntax = |id: ExprId| {
+                        body.expr_syntax(id)
+                            .unwrap()
+                            .value
+                            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                    };
                     sink.push(IncompatibleBranch {
                         file,
-                        if_expr: expr,
+                        if_expr: expr_syntax(*id),
+                        then_expr: expr_syntax(*then_branch),
+                        else_expr: expr_syntax(*else_branch),
                         expected: then_ty.clone(),
                         found: else_ty.clone(),
                     });
@@ -1570,6 +2335,73 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
+                InferenceDiagnostic::ExpectedStringLiteral { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(ExpectedStringLiteral { file, expr });
+                }
+                InferenceDiagnostic::BuiltinFunctionNotCalled { id, function } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(BuiltinFunctionNotCalled {
+                        file,
+                        expr,
+                        function: *function,
+                    });
+                }
+                InferenceDiagnostic::InvalidBuiltinArgument { id, function, ty } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidBuiltinArgument {
+                        file,
+                        expr,
+                        function: *function,
+                        ty: ty.clone(),
+                    });
+                }
+                InferenceDiagnostic::ContinueOutsideLoop { id } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(ContinueOutsideLoop {
+                        file,
+                        continue_expr: id,
+                    });
+                }
+                InferenceDiagnostic::DuplicateLabel { id, label } => {
+                    let label_ptr = body
+                        .label_syntax(db.upcast(), *id)
+                        .expect("a loop with a label must have label syntax")
+                        .value;
+                    sink.push(DuplicateLabel {
+                        file,
+                        label_ptr,
+                        label: label.clone(),
+                    });
+                }
+                InferenceDiagnostic::UndeclaredLabel { id, label } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(UndeclaredLabel {
+                        file,
+                        break_expr: id,
+                        label: label.clone(),
+                    });
+                }
                 InferenceDiagnostic::BreakWithValueOutsideLoop { id } => {
                     let id = body
                         .expr_syntax(*id)
@@ -1581,10 +2413,39 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
+                InferenceDiagnostic::NotIterable { id, ty } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(NotIterable {
+                        file,
+                        expr,
+                        ty: ty.clone(),
+                    });
+                }
+                InferenceDiagnostic::TupleInArray { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(TupleInArray { file, expr });
+                }
+                InferenceDiagnostic::UnsupportedRange { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(UnsupportedRange { file, expr });
+                }
                 InferenceDiagnostic::AccessUnknownField {
                     id,
                     receiver_ty,
                     name,
+                    similar_field: field,
                 } => {
                     let expr = body
                         .expr_syntax(*id)
@@ -1596,6 +2457,7 @@ mod diagnostics {
                         expr,
                         receiver_ty: receiver_ty.clone(),
                         name: name.clone(),
+                        similar_name: similar_field(field),
                     });
                 }
                 InferenceDiagnostic::FieldCountMismatch {
@@ -1662,9 +2524,17 @@ mod diagnostics {
                         found: found.clone(),
                     });
                 }
-                InferenceDiagnostic::NoSuchField { id, field } => {
+                InferenceDiagnostic::NoSuchField {
+                    id,
+                    field,
+                    similar_field: similar,
+                } => {
                     let field = owner.body_source_map(db).field_syntax(*id, *field).into();
-                    sink.push(NoSuchField { file, field });
+                    sink.push(NoSuchField {
+                        file,
+                        field,
+                        similar_name: similar_field(similar),
+                    });
                 }
                 InferenceDiagnostic::LiteralOutOfRange { id, literal_ty } => {
                     let literal = body
@@ -1732,6 +2602,84 @@ mod diagnostics {
                         .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
                     sink.push(PrivateAccess { file, expr });
                 }
+                InferenceDiagnostic::MismatchedPatType {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let expr = pat_syntax(*id);
+                    sink.push(MismatchedType {
+                        file,
+                        expr,
+                        expected: expected.clone(),
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::MismatchedStructPat {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    sink.push(MismatchedStructPat {
+                        file,
+                        pat: pat_syntax(*id),
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
+                InferenceDiagnostic::MissingPatFields {
+                    id,
+                    struct_ty,
+                    names,
+                } => {
+                    sink.push(MissingFields {
+                        file,
+                        struct_ty: struct_ty.clone(),
+                        fields: pat_syntax(*id),
+                        field_names: names.clone(),
+                    });
+                }
+                InferenceDiagnostic::NoSuchPatField {
+                    id,
+                    similar_field: similar,
+                } => {
+                    sink.push(NoSuchField {
+                        file,
+                        field: pat_syntax(*id),
+                        similar_name: similar_field(similar),
+                    });
+                }
+                InferenceDiagnostic::PrivatePatField { id }
+                | InferenceDiagnostic::PatPathIsPrivate { id } => {
+                    sink.push(PrivateAccess {
+                        file,
+                        expr: pat_syntax(*id),
+                    });
+                }
+                InferenceDiagnostic::PatFieldCountMismatch {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    sink.push(PatFieldCountMismatch {
+                        file,
+                        pat: pat_syntax(*id),
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
+                InferenceDiagnostic::TuplePatLengthMismatch {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    sink.push(TuplePatLengthMismatch {
+                        file,
+                        pat: pat_syntax(*id),
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
             }
         }
     }
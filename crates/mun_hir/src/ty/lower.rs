@@ -15,7 +15,8 @@ use crate::{
     resolve::{HasResolver, Resolver, TypeNs},
     ty::{FnSig, Substitution, Ty, TyKind},
     type_ref::{LocalTypeRefId, TypeRef, TypeRefMap, TypeRefSourceMap},
-    Function, HasVisibility, HirDatabase, ModuleDef, Path, Struct, TypeAlias, Visibility,
+    Const, Function, HasVisibility, HirDatabase, ModuleDef, Path, Static, Struct, TypeAlias,
+    Visibility,
 };
 
 /// A struct which holds resolved type references to `Ty`s.
@@ -109,7 +110,16 @@ impl Ty {
         if let Some(ty) = res {
             ty
         } else {
-            diagnostics.push(LowerDiagnostic::UnresolvedType { id: type_ref });
+            let similar_name = match &type_ref_map[type_ref] {
+                TypeRef::Path(path) => path.as_ident().and_then(|name| {
+                    resolver.find_similar_name(db.upcast(), name, Namespace::Types)
+                }),
+                _ => None,
+            };
+            diagnostics.push(LowerDiagnostic::UnresolvedType {
+                id: type_ref,
+                similar_name,
+            });
             TyKind::Unknown.intern()
         }
     }
@@ -175,6 +185,8 @@ pub enum TypableDef {
     PrimitiveType(PrimitiveType),
     Struct(Struct),
     TypeAlias(TypeAlias),
+    Const(Const),
+    Static(Static),
 }
 
 impl From<Function> for TypableDef {
@@ -195,6 +207,18 @@ impl From<Struct> for TypableDef {
     }
 }
 
+impl From<Static> for TypableDef {
+    fn from(s: Static) -> Self {
+        TypableDef::Static(s)
+    }
+}
+
+impl From<Const> for TypableDef {
+    fn from(c: Const) -> Self {
+        TypableDef::Const(c)
+    }
+}
+
 impl From<ModuleDef> for Option<TypableDef> {
     fn from(d: ModuleDef) -> Self {
         match d {
@@ -202,7 +226,9 @@ impl From<ModuleDef> for Option<TypableDef> {
             ModuleDef::PrimitiveType(t) => Some(TypableDef::PrimitiveType(t)),
             ModuleDef::Struct(t) => Some(TypableDef::Struct(t)),
             ModuleDef::TypeAlias(t) => Some(TypableDef::TypeAlias(t)),
-            ModuleDef::Module(_) => None,
+            ModuleDef::Const(c) => Some(TypableDef::Const(c)),
+            ModuleDef::Static(s) => Some(TypableDef::Static(s)),
+            ModuleDef::Module(_) | ModuleDef::BuiltinFunction(_) => None,
         }
     }
 }
@@ -244,9 +270,14 @@ pub(crate) fn type_for_def(db: &dyn HirDatabase, def: TypableDef, ns: Namespace)
         (TypableDef::Struct(s), Namespace::Values) => type_for_struct_constructor(db, s),
         (TypableDef::Struct(s), Namespace::Types) => type_for_struct(db, s),
         (TypableDef::TypeAlias(t), Namespace::Types) => type_for_type_alias(db, t),
+        (TypableDef::Const(c), Namespace::Values) => c.ty(db),
+        (TypableDef::Static(s), Namespace::Values) => s.ty(db),
 
         // 'error' cases:
-        (TypableDef::Function(_), Namespace::Types)
+        (
+            TypableDef::Function(_) | TypableDef::Const(_) | TypableDef::Static(_),
+            Namespace::Types,
+        )
         | (TypableDef::PrimitiveType(_) | TypableDef::TypeAlias(_), Namespace::Values) => {
             TyKind::Unknown.intern()
         }
@@ -334,29 +365,43 @@ pub mod diagnostics {
     use mun_hir_input::FileId;
 
     use crate::{
-        diagnostics::{DiagnosticSink, PrivateAccess, UnresolvedType},
+        code_model::src::item_definition_source,
+        diagnostics::{DiagnosticSink, PrivateAccess, SimilarName, UnresolvedType},
+        name_resolution::Namespace,
+        resolve::ScopeDef,
         type_ref::{LocalTypeRefId, TypeRefSourceMap},
-        HirDatabase,
+        HirDatabase, Name,
     };
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub enum LowerDiagnostic {
-        UnresolvedType { id: LocalTypeRefId },
-        TypeIsPrivate { id: LocalTypeRefId },
+        UnresolvedType {
+            id: LocalTypeRefId,
+            similar_name: Option<(Name, ScopeDef)>,
+        },
+        TypeIsPrivate {
+            id: LocalTypeRefId,
+        },
     }
 
     impl LowerDiagnostic {
         pub(crate) fn add_to(
             &self,
-            _db: &dyn HirDatabase,
+            db: &dyn HirDatabase,
             file_id: FileId,
             source_map: &TypeRefSourceMap,
             sink: &mut DiagnosticSink<'_>,
         ) {
             match self {
-                LowerDiagnostic::UnresolvedType { id } => sink.push(UnresolvedType {
+                LowerDiagnostic::UnresolvedType { id, similar_name } => sink.push(UnresolvedType {
                     file: file_id,
                     type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                    similar_name: similar_name.as_ref().map(|(name, def)| SimilarName {
+                        name: name.clone(),
+                        definition: def
+                            .item(Namespace::Types)
+                            .and_then(|item| item_definition_source(db.upcast(), item)),
+                    }),
                 }),
                 LowerDiagnostic::TypeIsPrivate { id } => sink.push(PrivateAccess {
                     file: file_id,
@@ -14,22 +14,22 @@ fn issue_354() {
     fn value() -> i64 { 6 }
 
     pub fn main() {
-        let t = 2;
+        let mut t = 2;
         t = loop { break value(); };
     }"#),
     @r###"
     18..23 '{ 6 }': i64
     20..21 '6': i64
-    39..90 '{     ...; }; }': ()
-    49..50 't': i64
-    53..54 '2': i64
-    60..61 't': i64
-    60..87 't = lo...e(); }': ()
-    64..87 'loop {...e(); }': i64
-    69..87 '{ brea...e(); }': never
-    71..84 'break value()': never
-    77..82 'value': function value() -> i64
-    77..84 'value()': i64
+    39..94 '{     ...; }; }': ()
+    49..54 'mut t': i64
+    57..58 '2': i64
+    64..65 't': i64
+    64..91 't = lo...e(); }': ()
+    68..91 'loop {...e(); }': i64
+    73..91 '{ brea...e(); }': never
+    75..88 'break value()': never
+    81..86 'value': function value() -> i64
+    81..88 'value()': i64
     "###);
 }
 
@@ -38,23 +38,23 @@ fn array_element_assignment() {
     insta::assert_snapshot!(infer(
         r"
     fn main() {
-        let a = [1,2,3,4,5]
+        let mut a = [1,2,3,4,5]
         a[2] = 4u8
     }",
     ), @r###"
-    10..52 '{     ... 4u8 }': ()
-    20..21 'a': [u8]
-    24..35 '[1,2,3,4,5]': [u8]
-    25..26 '1': u8
-    27..28 '2': u8
-    29..30 '3': u8
-    31..32 '4': u8
-    33..34 '5': u8
-    40..41 'a': [u8]
-    40..44 'a[2]': u8
-    40..50 'a[2] = 4u8': ()
-    42..43 '2': i32
-    47..50 '4u8': u8
+    10..56 '{     ... 4u8 }': ()
+    20..25 'mut a': [u8]
+    28..39 '[1,2,3,4,5]': [u8]
+    29..30 '1': u8
+    31..32 '2': u8
+    33..34 '3': u8
+    35..36 '4': u8
+    37..38 '5': u8
+    44..45 'a': [u8]
+    44..48 'a[2]': u8
+    44..54 'a[2] = 4u8': ()
+    46..47 '2': i32
+    51..54 '4u8': u8
     "###);
 }
 
@@ -63,39 +63,39 @@ fn array_is_place_expr() {
     insta::assert_snapshot!(infer(
         r"
     fn main() {
-        let a = [1,2,3,4,5]
+        let mut a = [1,2,3,4,5]
         a = [5,6,7]
         a[0] = 0;
         [1,2,3][0] = 4
     }",
     ), @r###"
-    10..86 '{     ... = 4 }': ()
-    20..21 'a': [i32]
-    24..35 '[1,2,3,4,5]': [i32]
-    25..26 '1': i32
-    27..28 '2': i32
-    29..30 '3': i32
-    31..32 '4': i32
-    33..34 '5': i32
-    40..41 'a': [i32]
-    40..51 'a = [5,6,7]': ()
-    44..51 '[5,6,7]': [i32]
-    45..46 '5': i32
-    47..48 '6': i32
-    49..50 '7': i32
-    56..57 'a': [i32]
-    56..60 'a[0]': i32
-    56..64 'a[0] = 0': ()
-    58..59 '0': i32
-    63..64 '0': i32
-    70..77 '[1,2,3]': [i32]
-    70..80 '[1,2,3][0]': i32
-    70..84 '[1,2,3][0] = 4': ()
-    71..72 '1': i32
-    73..74 '2': i32
-    75..76 '3': i32
-    78..79 '0': i32
-    83..84 '4': i32
+    10..90 '{     ... = 4 }': ()
+    20..25 'mut a': [i32]
+    28..39 '[1,2,3,4,5]': [i32]
+    29..30 '1': i32
+    31..32 '2': i32
+    33..34 '3': i32
+    35..36 '4': i32
+    37..38 '5': i32
+    44..45 'a': [i32]
+    44..55 'a = [5,6,7]': ()
+    48..55 '[5,6,7]': [i32]
+    49..50 '5': i32
+    51..52 '6': i32
+    53..54 '7': i32
+    60..61 'a': [i32]
+    60..64 'a[0]': i32
+    60..68 'a[0] = 0': ()
+    62..63 '0': i32
+    67..68 '0': i32
+    74..81 '[1,2,3]': [i32]
+    74..84 '[1,2,3][0]': i32
+    74..88 '[1,2,3][0] = 4': ()
+    75..76 '1': i32
+    77..78 '2': i32
+    79..80 '3': i32
+    82..83 '0': i32
+    87..88 '4': i32
     "###);
 }
 
@@ -423,7 +423,6 @@ fn scoped_path() {
     @r###"
     71..81: undefined value
     35..45: mismatched type
-    29..67: mismatched type
     36..47 '{     Foo }': Foo
     42..45 'Foo': Foo
     65..103 '{     ...alue }': Foo
@@ -528,10 +527,12 @@ fn infer_suffix_literals() {
         128_i8; // literal out of range for `i8`
         12712371237123_u32; // literal out of range `u32`
         9999999999999999999999999; // literal out of range `i32`
+        1 + 0x1_00_u8; // literal out of range for `u8`
+        -128_i8;
+        -129_i8; // literal out of range for `i8`
     }
     "),
-    @r###"
-    358..365: invalid literal value
+    @"
     390..401: binary float literal is not supported
     426..437: octal float literal is not supported
     462..470: invalid suffix `foo`
@@ -541,6 +542,8 @@ fn infer_suffix_literals() {
     635..641: literal out of range for `i8`
     680..698: literal out of range for `u32`
     734..759: literal out of range for `i32`
+    799..808: literal out of range for `u8`
+    861..867: literal out of range for `i8`
     9..298 '{     ...f64; }': ()
     15..18 '123': i32
     24..29 '123u8': u8
@@ -564,7 +567,7 @@ fn infer_suffix_literals() {
     327..328 'a': u32
     327..336 'a + 12u32': u32
     331..336 '12u32': u32
-    352..792 '{     ...i32` }': ()
+    352..903 '{     ...`i8` }': ()
     358..365 '0b22222': i32
     390..401 '0b00010_f32': f32
     426..437 '0o71234_f32': f32
@@ -575,7 +578,14 @@ fn infer_suffix_literals() {
     635..641 '128_i8': i8
     680..698 '127123...23_u32': u32
     734..759 '999999...999999': i32
-    "###);
+    795..796 '1': u8
+    795..808 '1 + 0x1_00_u8': u8
+    799..808 '0x1_00_u8': u8
+    847..854 '-128_i8': i8
+    848..854 '128_i8': i8
+    860..867 '-129_i8': i8
+    861..867 '129_i8': i8
+    ");
 }
 
 #[test]
@@ -863,7 +873,7 @@ fn infer_branching() {
     "#),
     @r###"
     61..74: missing else branch
-    208..234: mismatched branches
+    231..232: `if` and `else` have incompatible types
     10..260 '{     ...ches }': ()
     20..21 'a': i32
     24..48 'if tru... { 4 }': i32
@@ -872,8 +882,8 @@ fn infer_branching() {
     34..35 '3': i32
     43..48 '{ 4 }': i32
     45..46 '4': i32
-    57..58 'b': ()
-    61..74 'if true { 3 }': ()
+    57..58 'b': i32
+    61..74 'if true { 3 }': i32
     64..68 'true': bool
     69..74 '{ 3 }': i32
     71..72 '3': i32
@@ -930,21 +940,21 @@ fn void_return() {
 fn place_expressions() {
     insta::assert_snapshot!(infer(
         r#"
-    fn foo(a:i32) {
+    fn foo(mut a:i32) {
         a += 3;
         3 = 5; // error: invalid left hand side of expression
     }
     "#),
     @r###"
-    32..33: invalid left hand side of expression
-    7..8 'a': i32
-    14..87 '{     ...sion }': ()
-    20..21 'a': i32
-    20..26 'a += 3': ()
-    25..26 '3': i32
-    32..33 '3': i32
-    32..37 '3 = 5': ()
-    36..37 '5': i32
+    36..37: invalid left hand side of expression
+    7..12 'mut a': i32
+    18..91 '{     ...sion }': ()
+    24..25 'a': i32
+    24..30 'a += 3': ()
+    29..30 '3': i32
+    36..37 '3': i32
+    36..41 '3 = 5': ()
+    40..41 '5': i32
     "###);
 }
 
@@ -952,7 +962,7 @@ fn place_expressions() {
 fn update_operators() {
     insta::assert_snapshot!(infer(
         r#"
-    fn foo(a:i32, b:f64) {
+    fn foo(mut a:i32, mut b:f64) {
         a += 3;
         a -= 3;
         a *= 3;
@@ -968,47 +978,47 @@ fn update_operators() {
     }
     "#),
     @r###"
-    162..165: mismatched type
-    195..196: mismatched type
-    7..8 'a': i32
-    14..15 'b': f64
-    21..218 '{     ...type }': ()
-    27..28 'a': i32
-    27..33 'a += 3': ()
-    32..33 '3': i32
-    39..40 'a': i32
-    39..45 'a -= 3': ()
-    44..45 '3': i32
-    51..52 'a': i32
-    51..57 'a *= 3': ()
-    56..57 '3': i32
-    63..64 'a': i32
-    63..69 'a /= 3': ()
-    68..69 '3': i32
-    75..76 'a': i32
-    75..81 'a %= 3': ()
-    80..81 '3': i32
-    87..88 'b': f64
-    87..95 'b += 3.0': ()
-    92..95 '3.0': f64
-    101..102 'b': f64
-    101..109 'b -= 3.0': ()
-    106..109 '3.0': f64
-    115..116 'b': f64
-    115..123 'b *= 3.0': ()
-    120..123 '3.0': f64
-    129..130 'b': f64
-    129..137 'b /= 3.0': ()
-    134..137 '3.0': f64
-    143..144 'b': f64
-    143..151 'b %= 3.0': ()
-    148..151 '3.0': f64
-    157..158 'a': i32
-    157..165 'a *= 3.0': ()
-    162..165 '3.0': f64
-    190..191 'b': f64
-    190..196 'b *= 3': ()
-    195..196 '3': i32
+    170..173: mismatched type
+    203..204: mismatched type
+    7..12 'mut a': i32
+    18..23 'mut b': f64
+    29..226 '{     ...type }': ()
+    35..36 'a': i32
+    35..41 'a += 3': ()
+    40..41 '3': i32
+    47..48 'a': i32
+    47..53 'a -= 3': ()
+    52..53 '3': i32
+    59..60 'a': i32
+    59..65 'a *= 3': ()
+    64..65 '3': i32
+    71..72 'a': i32
+    71..77 'a /= 3': ()
+    76..77 '3': i32
+    83..84 'a': i32
+    83..89 'a %= 3': ()
+    88..89 '3': i32
+    95..96 'b': f64
+    95..103 'b += 3.0': ()
+    100..103 '3.0': f64
+    109..110 'b': f64
+    109..117 'b -= 3.0': ()
+    114..117 '3.0': f64
+    123..124 'b': f64
+    123..131 'b *= 3.0': ()
+    128..131 '3.0': f64
+    137..138 'b': f64
+    137..145 'b /= 3.0': ()
+    142..145 '3.0': f64
+    151..152 'b': f64
+    151..159 'b %= 3.0': ()
+    156..159 '3.0': f64
+    165..166 'a': i32
+    165..173 'a *= 3.0': ()
+    170..173 '3.0': f64
+    198..199 'b': f64
+    198..204 'b *= 3': ()
+    203..204 '3': i32
     "###);
 }
 
@@ -1016,23 +1026,23 @@ fn update_operators() {
 fn infer_unary_ops() {
     insta::assert_snapshot!(infer(
         r#"
-    fn foo(a: i32, b: bool) {
+    fn foo(mut a: i32, mut b: bool) {
         a = -a;
         b = !b;
     }
         "#),
     @r###"
-    7..8 'a': i32
-    15..16 'b': bool
-    24..51 '{     ... !b; }': ()
-    30..31 'a': i32
-    30..36 'a = -a': ()
-    34..36 '-a': i32
-    35..36 'a': i32
-    42..43 'b': bool
-    42..48 'b = !b': ()
-    46..48 '!b': bool
-    47..48 'b': bool
+    7..12 'mut a': i32
+    19..24 'mut b': bool
+    32..59 '{     ... !b; }': ()
+    38..39 'a': i32
+    38..44 'a = -a': ()
+    42..44 '-a': i32
+    43..44 'a': i32
+    50..51 'b': bool
+    50..56 'b = !b': ()
+    54..56 '!b': bool
+    55..56 'b': bool
     "###);
 }
 
@@ -1040,25 +1050,25 @@ fn infer_unary_ops() {
 fn invalid_unary_ops() {
     insta::assert_snapshot!(infer(
         r#"
-    fn bar(a: f64, b: bool) {
+    fn bar(mut a: f64, mut b: bool) {
         a = !a; // mismatched type
         b = -b; // mismatched type
     }
         "#),
     @r###"
-    35..36: cannot apply unary operator
-    66..67: cannot apply unary operator
-    7..8 'a': f64
-    15..16 'b': bool
-    24..89 '{     ...type }': ()
-    30..31 'a': f64
-    30..36 'a = !a': ()
-    34..36 '!a': {unknown}
-    35..36 'a': f64
-    61..62 'b': bool
-    61..67 'b = -b': ()
-    65..67 '-b': {unknown}
-    66..67 'b': bool
+    43..44: cannot apply unary operator
+    74..75: cannot apply unary operator
+    7..12 'mut a': f64
+    19..24 'mut b': bool
+    32..97 '{     ...type }': ()
+    38..39 'a': f64
+    38..44 'a = !a': ()
+    42..44 '!a': {unknown}
+    43..44 'a': f64
+    69..70 'b': bool
+    69..75 'b = -b': ()
+    73..75 '-b': {unknown}
+    74..75 'b': bool
     "###);
 }
 
@@ -1077,6 +1087,162 @@ fn infer_loop() {
     "###);
 }
 
+#[test]
+fn infer_nested_block_values() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn test(a: bool, b: bool) -> i32 {
+        let c = {
+            let d = if a { { 1 } } else { if b { 2 } else { 3 } };
+            d
+        };
+        let e = loop {
+            if a { break { c } }
+        };
+        if b { { e } } else { return c; }
+    }
+    "#),
+    @r###"
+    8..9 'a': bool
+    17..18 'b': bool
+    33..223 '{     ...c; } }': i32
+    43..44 'c': i32
+    47..127 '{     ...     }': i32
+    61..62 'd': i32
+    65..110 'if a {... 3 } }': i32
+    68..69 'a': bool
+    70..79 '{ { 1 } }': i32
+    72..77 '{ 1 }': i32
+    74..75 '1': i32
+    85..110 '{ if b... 3 } }': i32
+    87..108 'if b {... { 3 }': i32
+    90..91 'b': bool
+    92..97 '{ 2 }': i32
+    94..95 '2': i32
+    103..108 '{ 3 }': i32
+    105..106 '3': i32
+    120..121 'd': i32
+    137..138 'e': i32
+    141..182 'loop {...     }': i32
+    146..182 '{     ...     }': ()
+    156..176 'if a {... c } }': ()
+    159..160 'a': bool
+    161..176 '{ break { c } }': never
+    163..174 'break { c }': never
+    169..174 '{ c }': i32
+    171..172 'c': i32
+    188..221 'if b {...n c; }': i32
+    191..192 'b': bool
+    193..202 '{ { e } }': i32
+    195..200 '{ e }': i32
+    197..198 'e': i32
+    208..221 '{ return c; }': never
+    210..218 'return c': never
+    217..218 'c': i32
+    "###);
+}
+
+#[test]
+fn infer_mismatched_branch_values() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn tail(a: bool) -> i32 {
+        if a { 1 } else { let b = 2; 2.0 }  // error: mismatched type
+    }
+    fn nested_blocks() -> i32 {
+        { { 1.0 } }                         // error: mismatched type
+    }
+    fn annotated(a: bool) {
+        let b: i32 = if a { 1 } else { { true } }  // error: mismatched type
+    }
+    fn incompatible(a: bool, b: bool) {
+        let c = if a { 1 } else { { 2.0 } }   // error: `if` and `else` have incompatible types
+        let d = if a { 1 } else if b { 2 } else { 3.0 }   // error: `if` and `else` have incompatible types
+    }
+    fn missing_else(a: bool) -> i32 {
+        if a { 1 }                          // error: missing else branch
+    }
+    fn break_value(a: bool) -> i32 {
+        loop {
+            if a { break 1; }
+            break true;                     // error: mismatched type
+        }
+    }
+    "#),
+    @r###"
+    59..62: mismatched type
+    130..133: mismatched type
+    251..255: mismatched type
+    357..360: `if` and `else` have incompatible types
+    463..466: `if` and `else` have incompatible types
+    561..571: missing else branch
+    713..717: mismatched type
+    8..9 'a': bool
+    24..93 '{     ...type }': i32
+    30..64 'if a {... 2.0 }': i32
+    33..34 'a': bool
+    35..40 '{ 1 }': i32
+    37..38 '1': i32
+    46..64 '{ let ... 2.0 }': i32
+    52..53 'b': i32
+    56..57 '2': i32
+    59..62 '2.0': f64
+    120..189 '{     ...type }': i32
+    126..137 '{ { 1.0 } }': i32
+    128..135 '{ 1.0 }': i32
+    130..133 '1.0': f64
+    203..204 'a': bool
+    212..288 '{     ...type }': ()
+    222..223 'b': i32
+    231..259 'if a {...ue } }': i32
+    234..235 'a': bool
+    236..241 '{ 1 }': i32
+    238..239 '1': i32
+    247..259 '{ { true } }': i32
+    249..257 '{ true }': i32
+    251..255 'true': bool
+    305..306 'a': bool
+    314..315 'b': bool
+    323..522 '{     ...ypes }': ()
+    333..334 'c': i32
+    337..364 'if a {....0 } }': i32
+    340..341 'a': bool
+    342..347 '{ 1 }': i32
+    344..345 '1': i32
+    353..364 '{ { 2.0 } }': f64
+    355..362 '{ 2.0 }': f64
+    357..360 '2.0': f64
+    425..426 'd': i32
+    429..468 'if a {... 3.0 }': i32
+    432..433 'a': bool
+    434..439 '{ 1 }': i32
+    436..437 '1': i32
+    445..468 'if b {... 3.0 }': i32
+    448..449 'b': bool
+    450..455 '{ 2 }': i32
+    452..453 '2': i32
+    461..468 '{ 3.0 }': f64
+    463..466 '3.0': f64
+    539..540 'a': bool
+    555..628 '{     ...anch }': i32
+    561..571 'if a { 1 }': i32
+    564..565 'a': bool
+    566..571 '{ 1 }': i32
+    568..569 '1': i32
+    644..645 'a': bool
+    660..772 '{     ...   } }': i32
+    666..770 'loop {...     }': i32
+    671..770 '{     ...     }': never
+    681..698 'if a {...k 1; }': ()
+    684..685 'a': bool
+    686..698 '{ break 1; }': never
+    688..695 'break 1': never
+    694..695 '1': i32
+    707..717 'break true': never
+    713..717 'true': bool
+    "###);
+}
+
 #[test]
 fn infer_break() {
     insta::assert_snapshot!(infer(
@@ -1092,9 +1258,9 @@ fn infer_break() {
     "#),
     @r###"
     20..25: `break` outside of a loop
-    71..80: mismatched type
-    133..142: mismatched type
-    267..276: mismatched type
+    77..80: mismatched type
+    139..142: mismatched type
+    273..276: mismatched type
     14..309 '{     ...type }': never
     20..25 'break': never
     55..83 'loop {...3.0; }': i32
@@ -1130,12 +1296,275 @@ fn infer_break() {
     "###);
 }
 
+#[test]
+fn infer_builtin_calls() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo(a: i32) -> i32 {
+        assert(a > 0);
+        assert(a < 10, "a is too large");
+        if a == 5 {
+            panic("five is not allowed")
+        } else {
+            a
+        }
+    }
+
+    fn bar(a: i32) {
+        assert(a); // error: mismatched type
+        assert(true, a); // error: expected a string literal
+        assert(); // error: wrong number of arguments
+        panic("a", "b"); // error: wrong number of arguments
+        let p = panic; // error: builtin function can only be called
+    }
+    "#),
+    @r#"
+    194..195: mismatched type
+    241..242: expected a string literal
+    285..293: this function takes 1 parameters but 0 parameters was supplied
+    335..350: this function takes 1 parameters but 2 parameters was supplied
+    400..405: builtin function `panic` can only be called
+    7..8 'a': i32
+    22..164 '{     ...   } }': i32
+    28..41 'assert(a > 0)': ()
+    35..36 'a': i32
+    35..40 'a > 0': bool
+    39..40 '0': i32
+    47..79 'assert...arge")': ()
+    54..55 'a': i32
+    54..60 'a < 10': bool
+    58..60 '10': i32
+    62..78 '"a is ...large"': {unknown}
+    85..162 'if a =...     }': i32
+    88..89 'a': i32
+    88..94 'a == 5': bool
+    93..94 '5': i32
+    95..139 '{     ...     }': never
+    105..133 'panic(...owed")': never
+    111..132 '"five ...lowed"': {unknown}
+    145..162 '{     ...     }': i32
+    155..156 'a': i32
+    173..174 'a': i32
+    181..454 '{     ...lled }': never
+    187..196 'assert(a)': ()
+    194..195 'a': i32
+    228..243 'assert(true, a)': ()
+    235..239 'true': bool
+    241..242 'a': i32
+    285..293 'assert()': ()
+    335..350 'panic("a", "b")': never
+    341..344 '"a"': {unknown}
+    346..349 '"b"': {unknown}
+    396..397 'p': {unknown}
+    400..405 'panic': {unknown}
+    "#
+    );
+}
+
+#[test]
+fn infer_math_builtin_calls() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo(a: f32, b: i64, c: u8) {
+        let x = sin(a) + cos(a) + sqrt(2.0);
+        let y = abs(b) + min(b, 3) + max(-1, b);
+        let z = min(c, 2);
+        let w = abs(-1.5);
+    }
+
+    fn bar(a: f32, b: u8) {
+        sin(1); // error: not a float
+        abs(b); // error: unsigned
+        min(a, b); // error: mismatched type
+        max(a); // error: wrong number of arguments
+        let s = sqrt; // error: builtin function can only be called
+    }
+    "#),
+    @"
+    199..200: builtin function `sin` can only be applied to floating point numbers
+    233..234: builtin function `abs` can only be applied to signed integers and floating point numbers
+    267..268: mismatched type
+    301..307: this function takes 2 parameters but 1 parameters was supplied
+    357..361: builtin function `sqrt` can only be called
+    7..8 'a': f32
+    15..16 'b': i64
+    23..24 'c': u8
+    30..165 '{     ....5); }': ()
+    40..41 'x': f32
+    44..50 'sin(a)': f32
+    44..59 'sin(a) + cos(a)': f32
+    44..71 'sin(a)...t(2.0)': f32
+    48..49 'a': f32
+    53..59 'cos(a)': f32
+    57..58 'a': f32
+    62..71 'sqrt(2.0)': f32
+    67..70 '2.0': f32
+    81..82 'y': i64
+    85..91 'abs(b)': i64
+    85..103 'abs(b)...(b, 3)': i64
+    85..116 'abs(b)...-1, b)': i64
+    89..90 'b': i64
+    94..103 'min(b, 3)': i64
+    98..99 'b': i64
+    101..102 '3': i64
+    106..116 'max(-1, b)': i64
+    110..112 '-1': i64
+    111..112 '1': i64
+    114..115 'b': i64
+    126..127 'z': u8
+    130..139 'min(c, 2)': u8
+    134..135 'c': u8
+    137..138 '2': u8
+    149..150 'w': f64
+    153..162 'abs(-1.5)': f64
+    157..161 '-1.5': f64
+    158..161 '1.5': f64
+    174..175 'a': f32
+    182..183 'b': u8
+    189..410 '{     ...lled }': ()
+    195..201 'sin(1)': {unknown}
+    199..200 '1': i32
+    229..235 'abs(b)': {unknown}
+    233..234 'b': u8
+    260..269 'min(a, b)': f32
+    264..265 'a': f32
+    267..268 'b': u8
+    301..307 'max(a)': f32
+    305..306 'a': f32
+    353..354 's': {unknown}
+    357..361 'sqrt': {unknown}
+    ");
+}
+
+#[test]
+fn infer_labeled_break() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo() -> i32 {
+        'outer: loop {
+            while true {
+                break 'outer 3;
+            }
+        }
+    }
+
+    fn bar() {
+        'outer: for i in 0..3 {
+            loop { break 'outer; }
+        }
+        loop { break 'inner; } // error: undeclared label
+        'a: while true { break 'a 3; } // error: break with value can only appear in a loop
+    }
+    "#),
+    @"
+    192..204: use of undeclared label `'inner`
+    256..266: `break` with value can only appear in a `loop`
+    16..103 '{     ...   } }': i32
+    22..101 ''outer...     }': i32
+    35..101 '{     ...     }': ()
+    45..95 'while ...     }': ()
+    51..55 'true': bool
+    56..95 '{     ...     }': never
+    70..84 'break 'outer 3': never
+    83..84 '3': i32
+    114..324 '{     ...loop }': never
+    120..180 ''outer...     }': ()
+    132..133 'i': i32
+    137..138 '0': i32
+    137..141 '0..3': i32
+    140..141 '3': i32
+    142..180 '{     ...     }': never
+    152..174 'loop {...ter; }': never
+    157..174 '{ brea...ter; }': never
+    159..171 'break 'outer': never
+    185..207 'loop {...ner; }': never
+    190..207 '{ brea...ner; }': never
+    192..204 'break 'inner': never
+    239..269 ''a: wh...a 3; }': ()
+    249..253 'true': bool
+    254..269 '{ break 'a 3; }': never
+    256..266 'break 'a 3': never
+    ");
+}
+
+#[test]
+fn infer_continue() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo() -> i32 {
+        let mut n = 0;
+        'outer: loop {
+            n += 1;
+            for i in 0..n {
+                if i == 2 { continue 'outer; }
+                continue;
+            }
+            while n < 3 { continue; }
+            break n;
+        }
+    }
+
+    fn bar() {
+        continue; // error: continue outside of a loop
+        loop { continue 'inner; } // error: undeclared label
+        'a: loop {
+            'a: while true { break 'a; } // error: duplicate label
+        }
+    }
+    "#),
+    @r#"
+    246..254: `continue` outside of a loop
+    304..319: use of undeclared label `'inner`
+    373..376: label `'a` is already declared by an enclosing loop
+    16..229 '{     ...   } }': i32
+    26..31 'mut n': i32
+    34..35 '0': i32
+    41..227 ''outer...     }': i32
+    54..227 '{     ...     }': never
+    64..65 'n': i32
+    64..70 'n += 1': ()
+    69..70 '1': i32
+    80..170 'for i ...     }': ()
+    84..85 'i': i32
+    89..90 '0': i32
+    89..93 '0..n': i32
+    92..93 'n': i32
+    94..170 '{     ...     }': never
+    108..138 'if i =...ter; }': ()
+    111..112 'i': i32
+    111..117 'i == 2': bool
+    116..117 '2': i32
+    118..138 '{ cont...ter; }': never
+    120..135 'continue 'outer': never
+    151..159 'continue': never
+    179..204 'while ...nue; }': ()
+    185..186 'n': i32
+    185..190 'n < 3': bool
+    189..190 '3': i32
+    191..204 '{ continue; }': never
+    193..201 'continue': never
+    213..220 'break n': never
+    219..220 'n': i32
+    240..435 '{     ...   } }': never
+    246..254 'continue': never
+    297..322 'loop {...ner; }': never
+    302..322 '{ cont...ner; }': never
+    304..319 'continue 'inner': never
+    354..433 ''a: lo...     }': never
+    363..433 '{     ...     }': ()
+    373..401 ''a: wh... 'a; }': ()
+    383..387 'true': bool
+    388..401 '{ break 'a; }': never
+    390..398 'break 'a': never
+    "#);
+}
+
 #[test]
 fn infer_while() {
     insta::assert_snapshot!(infer(
         r#"
     fn foo() {
-        let n = 0;
+        let mut n = 0;
         while n < 3 { n += 1; };
         while n < 3 { n += 1; break; };
         while n < 3 { break 3; };   // error: break with value can only appear in a loop
@@ -1143,45 +1572,106 @@ fn infer_while() {
     }
     "#),
     @r###"
-    109..116: `break` with value can only appear in a `loop`
-    9..217 '{     ...; }; }': ()
-    19..20 'n': i32
-    23..24 '0': i32
-    30..53 'while ...= 1; }': ()
-    36..37 'n': i32
-    36..41 'n < 3': bool
-    40..41 '3': i32
-    42..53 '{ n += 1; }': ()
-    44..45 'n': i32
-    44..50 'n += 1': ()
-    49..50 '1': i32
-    59..89 'while ...eak; }': ()
-    65..66 'n': i32
-    65..70 'n < 3': bool
-    69..70 '3': i32
-    71..89 '{ n +=...eak; }': never
-    73..74 'n': i32
-    73..79 'n += 1': ()
-    78..79 '1': i32
-    81..86 'break': never
-    95..119 'while ...k 3; }': ()
-    101..102 'n': i32
-    101..106 'n < 3': bool
-    105..106 '3': i32
-    107..119 '{ break 3; }': never
-    109..116 'break 3': never
-    180..214 'while ...; }; }': ()
-    186..187 'n': i32
-    186..191 'n < 3': bool
-    190..191 '3': i32
-    192..214 '{ loop...; }; }': ()
-    194..211 'loop {...k 3; }': i32
-    199..211 '{ break 3; }': never
-    201..208 'break 3': never
-    207..208 '3': i32
+    113..120: `break` with value can only appear in a `loop`
+    9..221 '{     ...; }; }': ()
+    19..24 'mut n': i32
+    27..28 '0': i32
+    34..57 'while ...= 1; }': ()
+    40..41 'n': i32
+    40..45 'n < 3': bool
+    44..45 '3': i32
+    46..57 '{ n += 1; }': ()
+    48..49 'n': i32
+    48..54 'n += 1': ()
+    53..54 '1': i32
+    63..93 'while ...eak; }': ()
+    69..70 'n': i32
+    69..74 'n < 3': bool
+    73..74 '3': i32
+    75..93 '{ n +=...eak; }': never
+    77..78 'n': i32
+    77..83 'n += 1': ()
+    82..83 '1': i32
+    85..90 'break': never
+    99..123 'while ...k 3; }': ()
+    105..106 'n': i32
+    105..110 'n < 3': bool
+    109..110 '3': i32
+    111..123 '{ break 3; }': never
+    113..120 'break 3': never
+    184..218 'while ...; }; }': ()
+    190..191 'n': i32
+    190..195 'n < 3': bool
+    194..195 '3': i32
+    196..218 '{ loop...; }; }': ()
+    198..215 'loop {...k 3; }': i32
+    203..215 '{ break 3; }': never
+    205..212 'break 3': never
+    211..212 '3': i32
     "###);
 }
 
+#[test]
+fn infer_for() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo(a: [f32]) {
+        let mut n = 0;
+        for i in 0..10u8 { n += 1; };
+        for i in 0..=n { break; };
+        for x in a { let y: f32 = x; };
+        for _ in 0..3 { break 3; };    // error: break with value can only appear in a loop
+        for x in 5 {};                  // error: not iterable
+        let r = 0..10;                  // error: ranges are only supported in for loops
+    }
+    "#),
+    @"
+    159..166: `break` with value can only appear in a `loop`
+    240..241: only arrays and ranges can be iterated over in a `for` loop
+    298..303: range expressions can only be used as the iterable of a `for` loop
+    7..8 'a': [f32]
+    17..372 '{     ...oops }': ()
+    27..32 'mut n': i32
+    35..36 '0': i32
+    42..70 'for i ...= 1; }': ()
+    46..47 'i': u8
+    51..52 '0': u8
+    51..58 '0..10u8': u8
+    54..58 '10u8': u8
+    59..70 '{ n += 1; }': ()
+    61..62 'n': i32
+    61..67 'n += 1': ()
+    66..67 '1': i32
+    76..101 'for i ...eak; }': ()
+    80..81 'i': i32
+    85..86 '0': i32
+    85..90 '0..=n': i32
+    89..90 'n': i32
+    91..101 '{ break; }': never
+    93..98 'break': never
+    107..137 'for x ...= x; }': ()
+    111..112 'x': f32
+    116..117 'a': [f32]
+    118..137 '{ let ...= x; }': ()
+    124..125 'y': f32
+    133..134 'x': f32
+    143..169 'for _ ...k 3; }': ()
+    152..153 '0': i32
+    152..156 '0..3': i32
+    155..156 '3': i32
+    157..169 '{ break 3; }': never
+    159..166 'break 3': never
+    231..244 'for x in 5 {}': ()
+    235..236 'x': {unknown}
+    240..241 '5': i32
+    242..244 '{}': ()
+    294..295 'r': {unknown}
+    298..299 '0': i32
+    298..303 '0..10': {unknown}
+    301..303 '10': i32
+    ");
+}
+
 #[test]
 fn invalid_binary_ops() {
     insta::assert_snapshot!(infer(
@@ -1300,6 +1790,177 @@ fn struct_lit() {
     "###);
 }
 
+#[test]
+fn struct_destructuring() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+    struct(gc) Line {
+        start: Vec2,
+        end: Vec2,
+    }
+    struct Pair(i32, bool);
+
+    fn length(Vec2 { x, y }: Vec2) -> f32 {
+        x + y
+    }
+
+    fn main(line: Line, pair: Pair) {
+        let Line { start: Vec2 { x, .. }, end } = line;
+        let Pair(a, _) = pair;
+        let Pair(b, ..) = pair;
+
+        let Vec2 { x } = line.start; // error: missing record fields: y
+        let Vec2 { x, z } = line.start; // error: no such field
+        let Pair { a, b } = pair; // error: mismatched struct pattern kind. expected `tuple`, found `record`
+        let Vec2(a, b) = pair; // error: mismatched type
+        let Pair(a) = pair; // error: this pattern has 1 field, but the corresponding tuple struct has 2 fields
+        let Foo { a } = pair; // error: undefined value
+    }
+    "#),
+    @"
+    320..330: missing record fields:
+    - y
+
+    398..399: no such field
+    388..401: missing record fields:
+    - y
+
+    448..461: mismatched struct pattern kind. expected `tuple`, found `record`
+    553..563: mismatched type
+    606..613: this pattern has 1 field, but the corresponding tuple struct has 2 fields
+    714..723: undefined value
+    127..140 'Vec2 { x, y }': Vec2
+    134..135 'x': f32
+    137..138 'y': f32
+    155..168 '{     x + y }': f32
+    161..162 'x': f32
+    161..166 'x + y': f32
+    165..166 'y': f32
+    178..182 'line': Line
+    190..194 'pair': Pair
+    202..759 '{     ...alue }': ()
+    212..247 'Line {... end }': Line
+    226..240 'Vec2 { x, .. }': Vec2
+    233..234 'x': f32
+    242..245 'end': Vec2
+    250..254 'line': Line
+    264..274 'Pair(a, _)': Pair
+    269..270 'a': i32
+    277..281 'pair': Pair
+    291..302 'Pair(b, ..)': Pair
+    296..297 'b': i32
+    305..309 'pair': Pair
+    320..330 'Vec2 { x }': Vec2
+    327..328 'x': f32
+    333..337 'line': Line
+    333..343 'line.start': Vec2
+    388..401 'Vec2 { x, z }': Vec2
+    395..396 'x': f32
+    398..399 'z': {unknown}
+    404..408 'line': Line
+    404..414 'line.start': Vec2
+    448..461 'Pair { a, b }': {unknown}
+    455..456 'a': {unknown}
+    458..459 'b': {unknown}
+    464..468 'pair': Pair
+    553..563 'Vec2(a, b)': {unknown}
+    558..559 'a': {unknown}
+    561..562 'b': {unknown}
+    566..570 'pair': Pair
+    606..613 'Pair(a)': Pair
+    611..612 'a': i32
+    616..620 'pair': Pair
+    714..723 'Foo { a }': {unknown}
+    720..721 'a': {unknown}
+    726..730 'pair': Pair
+    ");
+}
+
+#[test]
+fn tuple_destructuring() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    fn main(v: Vec2) {
+        let unit = ();
+        let point = (v.x, v.y);
+        let (x, y) = point;
+        let (a, (b, c), ..) = (1, (true, v), 2.0);
+        let (d,) = (a + 1,);
+        let (e) = 3;
+        let Vec2 { x, y } = v;
+
+        let (f, g, h) = point; // error: mismatched types. expected a tuple with 2 elements, found one with 3 elements
+        let (i, j) = v; // error: mismatched type
+        let k = [(1, 2)]; // error: arrays cannot contain tuples
+    }
+    "#),
+    @r#"
+    256..265: mismatched types. expected a tuple with 2 elements, found one with 3 elements
+    371..377: mismatched type
+    421..429: arrays cannot contain tuples
+    49..50 'v': Vec2
+    58..471 '{     ...ples }': ()
+    68..72 'unit': ()
+    75..77 '()': ()
+    87..92 'point': (f32, f32)
+    95..105 '(v.x, v.y)': (f32, f32)
+    96..97 'v': Vec2
+    96..99 'v.x': f32
+    101..102 'v': Vec2
+    101..104 'v.y': f32
+    115..121 '(x, y)': (f32, f32)
+    116..117 'x': f32
+    119..120 'y': f32
+    124..129 'point': (f32, f32)
+    139..154 '(a, (b, c), ..)': (i32, (bool, Vec2), f64)
+    140..141 'a': i32
+    143..149 '(b, c)': (bool, Vec2)
+    144..145 'b': bool
+    147..148 'c': Vec2
+    157..176 '(1, (t..., 2.0)': (i32, (bool, Vec2), f64)
+    158..159 '1': i32
+    161..170 '(true, v)': (bool, Vec2)
+    162..166 'true': bool
+    168..169 'v': Vec2
+    172..175 '2.0': f64
+    186..190 '(d,)': (i32,)
+    187..188 'd': i32
+    193..201 '(a + 1,)': (i32,)
+    194..195 'a': i32
+    194..199 'a + 1': i32
+    198..199 '1': i32
+    212..213 'e': i32
+    217..218 '3': i32
+    228..241 'Vec2 { x, y }': Vec2
+    235..236 'x': f32
+    238..239 'y': f32
+    244..245 'v': Vec2
+    256..265 '(f, g, h)': (f32, f32)
+    257..258 'f': f32
+    260..261 'g': f32
+    263..264 'h': {unknown}
+    268..273 'point': (f32, f32)
+    371..377 '(i, j)': {unknown}
+    372..373 'i': {unknown}
+    375..376 'j': {unknown}
+    380..381 'v': Vec2
+    417..418 'k': [({integer}, {integer})]
+    421..429 '[(1, 2)]': [({integer}, {integer})]
+    422..428 '(1, 2)': (i32, i32)
+    423..424 '1': i32
+    426..427 '2': i32
+    "#);
+}
+
 #[test]
 fn struct_field_visibility() {
     insta::assert_snapshot!(infer(
@@ -1500,6 +2161,133 @@ fn recursive_alias() {
     "###);
 }
 
+#[test]
+fn infer_const() {
+    insta::assert_snapshot!(infer(
+        r#"
+    const MAX_SPEED: f32 = 5.0;
+    const LIMIT: i32 = 2 * OFFSET + 1;
+    const OFFSET: i32 = -4;
+    const ENABLED: bool = LIMIT < 0 && !false;
+
+    fn main() -> f32 {
+        if ENABLED { MAX_SPEED * 2.0 } else { 0.0 }
+    }
+    "#),
+    @"
+    23..26 '5.0': f32
+    47..48 '2': i32
+    47..57 '2 * OFFSET': i32
+    47..61 '2 * OFFSET + 1': i32
+    51..57 'OFFSET': i32
+    60..61 '1': i32
+    83..85 '-4': i32
+    84..85 '4': i32
+    109..114 'LIMIT': i32
+    109..118 'LIMIT < 0': bool
+    109..128 'LIMIT ...!false': bool
+    117..118 '0': i32
+    122..128 '!false': bool
+    123..128 'false': bool
+    148..199 '{     ....0 } }': f32
+    154..197 'if ENA... 0.0 }': f32
+    157..164 'ENABLED': bool
+    165..184 '{ MAX_... 2.0 }': f32
+    167..176 'MAX_SPEED': f32
+    167..182 'MAX_SPEED * 2.0': f32
+    179..182 '2.0': f32
+    190..197 '{ 0.0 }': f32
+    192..195 '0.0': f32
+    ");
+}
+
+#[test]
+fn infer_const_mismatched_type() {
+    insta::assert_snapshot!(infer(
+        r#"
+    const FOO: i32 = true;
+    const BAR: f64 = FOO;
+    const BAZ: Unknown = 1;
+    "#),
+    @"
+    17..21: mismatched type
+    40..43: mismatched type
+    56..63: undefined type
+    17..21 'true': bool
+    40..43 'FOO': i32
+    66..67 '1': i32
+    ");
+}
+
+#[test]
+fn infer_static() {
+    insta::assert_snapshot!(infer(
+        r#"
+    const STEP: u32 = 2;
+    static COUNTER: u32 = 0;
+
+    fn tick() -> u32 {
+        COUNTER += STEP;
+        COUNTER
+    }
+
+    fn reset() {
+        COUNTER = 0;
+        STEP = 1;
+    }
+    "#),
+    @"
+    136..140: invalid left hand side of expression
+    18..19 '2': u32
+    43..44 '0': u32
+    64..100 '{     ...NTER }': u32
+    70..77 'COUNTER': u32
+    70..85 'COUNTER += STEP': ()
+    81..85 'STEP': u32
+    91..98 'COUNTER': u32
+    113..147 '{     ...= 1; }': ()
+    119..126 'COUNTER': u32
+    119..130 'COUNTER = 0': ()
+    129..130 '0': u32
+    136..140 'STEP': u32
+    136..144 'STEP = 1': ()
+    143..144 '1': u32
+    ");
+}
+
+#[test]
+fn infer_static_in_other_module() {
+    insta::assert_snapshot!(infer(
+        r#"
+    //- /foo.mun
+    pub static COUNTER: u32 = 0;
+
+    pub fn tick() {
+        COUNTER += 1;
+    }
+
+    //- /mod.mun
+    fn main() -> u32 {
+        foo::COUNTER = 5;
+        foo::COUNTER
+    }
+    "#),
+    @"
+    23..35: static `COUNTER` can only be accessed from the module in which it is defined
+    45..57: static `COUNTER` can only be accessed from the module in which it is defined
+    17..59 '{     ...NTER }': u32
+    23..35 'foo::COUNTER': u32
+    23..39 'foo::C...ER = 5': ()
+    38..39 '5': u32
+    45..57 'foo::COUNTER': u32
+    26..27 '0': u32
+    44..65 '{     ...= 1; }': ()
+    50..57 'COUNTER': u32
+    50..62 'COUNTER += 1': ()
+    61..62 '1': u32
+    ");
+}
+
 fn infer(content: &str) -> String {
     let db = MockDatabase::with_files(content);
 
@@ -1568,10 +2356,23 @@ fn infer(content: &str) -> String {
         .flat_map(|pkg| pkg.modules(&db))
         .flat_map(|module| module.declarations(&db))
     {
-        if let ModuleDef::Function(fun) = item {
-            let source_map = fun.body_source_map(&db);
-            let infer_result = fun.infer(&db);
-            infer_def(infer_result, source_map);
+        match item {
+            ModuleDef::Function(fun) => {
+                let source_map = fun.body_source_map(&db);
+                let infer_result = fun.infer(&db);
+                infer_def(infer_result, source_map);
+            }
+            ModuleDef::Const(konst) => {
+                let source_map = konst.body_source_map(&db);
+                let infer_result = konst.infer(&db);
+                infer_def(infer_result, source_map);
+            }
+            ModuleDef::Static(static_def) => {
+                let source_map = static_def.body_source_map(&db);
+                let infer_result = static_def.infer(&db);
+                infer_def(infer_result, source_map);
+            }
+            _ => {}
         }
     }
 
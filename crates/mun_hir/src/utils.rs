@@ -1,4 +1,6 @@
-use std::sync::Arc;
+use std::{cmp, sync::Arc};
+
+use crate::Name;
 
 /// Helper for mutating `Arc<[T]>` (i.e. `Arc::make_mut` for Arc slices).
 /// The underlying values are cloned if there are other strong references.
@@ -9,11 +11,55 @@ pub(crate) fn make_mut_slice<T: Clone>(a: &mut Arc<[T]>) -> &mut [T] {
     Arc::get_mut(a).unwrap()
 }
 
+/// Returns the Levenshtein distance between two strings, which is the minimum
+/// number of single-character edits (insertions, deletions or substitutions)
+/// required to change one into the other.
+pub(crate) fn lev_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(a_char != *b_char);
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = cmp::min(substitution, cmp::min(distances[j], distances[j + 1]) + 1);
+        }
+    }
+    distances[b.len()]
+}
+
+/// Returns the candidate with the name that is most similar to `name`, or
+/// `None` if no name is similar enough to likely be what was meant. Like
+/// `rustc`, names are considered similar if their edit distance is at most a
+/// third of the length of `name`. Of multiple equally similar candidates the
+/// first is returned.
+pub(crate) fn find_similar_name<T>(
+    name: &Name,
+    candidates: impl IntoIterator<Item = (Name, T)>,
+) -> Option<(Name, T)> {
+    let name = name.as_str()?;
+    let max_distance = cmp::max(name.chars().count(), 3) / 3;
+    candidates
+        .into_iter()
+        .filter_map(|(candidate, value)| {
+            let distance = lev_distance(name, candidate.as_str()?);
+            (distance > 0 && distance <= max_distance).then_some((distance, candidate, value))
+        })
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, candidate, value)| (candidate, value))
+}
+
 #[cfg(test)]
 pub mod tests {
-    use mun_hir_input::WithFixture;
+    use mun_hir_input::{check_annotations, Fixture, SourceDatabase, WithFixture};
+    use mun_syntax::{Location, TextRange};
 
-    use crate::{diagnostics::DiagnosticSink, mock::MockDatabase, AstDatabase, Package};
+    use crate::{
+        diagnostics::{DiagnosticSink, Severity},
+        mock::MockDatabase,
+        AstDatabase, Package,
+    };
 
     pub fn diagnostics(content: &str) -> String {
         let (db, _file_id) = MockDatabase::with_single_file(content);
@@ -40,4 +86,49 @@ pub mod tests {
         drop(diag_sink);
         diags.join("\n")
     }
+    /// Asserts that the diagnostics emitted for the files of the `fixture`
+    /// match its inline annotations, e.g.:
+    ///
+    /// ```not_rust
+    /// fn main() {
+    ///     let a: i32 = true;
+    ///                //^^^^ error: mismatched type
+    /// }
+    /// ```
+    pub fn check_diagnostics(fixture: &str) {
+        let db = MockDatabase::with_files(fixture);
+
+        let mut diags = Vec::new();
+        for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+            let Some(file_id) = module.file_id(&db) else {
+                continue;
+            };
+            let path = db.file_relative_path(file_id);
+            for err in db.parse(file_id).errors() {
+                // An offset is marked by a single caret
+                let range = match err.location() {
+                    Location::Offset(offset) => TextRange::at(offset, 1.into()),
+                    Location::Range(range) => range,
+                };
+                diags.push((path.clone(), range, format!("error: {err}")));
+            }
+
+            module.diagnostics(
+                &db,
+                &mut DiagnosticSink::new(|diag| {
+                    let severity = match diag.severity() {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    };
+                    diags.push((
+                        path.clone(),
+                        diag.highlight_range(),
+                        format!("{severity}: {}", diag.message()),
+                    ));
+                }),
+            );
+        }
+
+        check_annotations(&Fixture::parse(fixture), diags);
+    }
 }
@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use mun_paths::RelativePathBuf;
+use mun_syntax::{TextRange, TextSize};
 
 mod with_fixture;
 
@@ -7,6 +8,8 @@ pub use with_fixture::WithFixture;
 
 const DEFAULT_FILE_NAME: &str = "mod.mun";
 const META_LINE: &str = "//-";
+const ANNOTATION_START: &str = "//";
+const ANNOTATION_MARKER: char = '^';
 
 /// A `Fixture` describes an single file in a project workspace. `Fixture`s can
 /// be parsed from a single string with the `parse` function. Using that
@@ -95,6 +98,122 @@ impl Fixture {
             text: String::new(),
         }
     }
+
+    /// Returns the inline annotations in the text of this fixture. See
+    /// [`extract_annotations`].
+    pub fn annotations(&self) -> Vec<(TextRange, String)> {
+        extract_annotations(&self.text)
+    }
+}
+
+/// Extracts annotations from comments that mark a range of the previous line,
+/// like:
+///
+/// ```not_rust
+/// let a: i32 = true;
+///            //^^^^ error: mismatched type
+/// ```
+///
+/// The carets of an annotation are aligned with the columns of the text they
+/// mark. Multiple annotations can follow the same line. Returns the marked
+/// ranges, which index into `text`, together with the trimmed text that
+/// follows the carets.
+pub fn extract_annotations(text: &str) -> Vec<(TextRange, String)> {
+    let mut result = Vec::new();
+    let mut line_start = TextSize::from(0);
+    let mut annotated_line_start = None;
+    for line in text.split_inclusive('\n') {
+        if let Some((column, len, annotation)) = parse_annotation(line) {
+            let annotated_line_start = annotated_line_start
+                .unwrap_or_else(|| panic!("annotation does not follow a line of text: {line:?}"));
+            let start = annotated_line_start + TextSize::from(column as u32);
+            result.push((
+                TextRange::at(start, TextSize::from(len as u32)),
+                annotation.to_owned(),
+            ));
+        } else {
+            annotated_line_start = Some(line_start);
+        }
+        line_start += TextSize::of(line);
+    }
+    result
+}
+
+/// Asserts that the `actual` annotations, e.g. the diagnostics emitted for the
+/// files of a fixture, equal the inline annotations of the `fixtures`. See
+/// [`extract_annotations`].
+///
+/// # Panics
+///
+/// Panics with a listing of both the expected and actual annotations if they
+/// differ.
+pub fn check_annotations(
+    fixtures: &[Fixture],
+    actual: impl IntoIterator<Item = (RelativePathBuf, TextRange, String)>,
+) {
+    let describe = |path: &RelativePathBuf, range: TextRange, annotation: &str| {
+        let text = fixtures
+            .iter()
+            .find(|fixture| &fixture.relative_path == path)
+            .and_then(|fixture| fixture.text.get(std::ops::Range::<usize>::from(range)));
+        match text {
+            Some(text) => format!("{path} {range:?} {text:?}: {annotation}"),
+            None => format!("{path} {range:?}: {annotation}"),
+        }
+    };
+
+    let render = |annotations: Vec<(RelativePathBuf, TextRange, String)>| {
+        annotations
+            .into_iter()
+            .sorted_by(|(lhs_path, lhs_range, lhs), (rhs_path, rhs_range, rhs)| {
+                (lhs_path, lhs_range.start(), lhs_range.end(), lhs).cmp(&(
+                    rhs_path,
+                    rhs_range.start(),
+                    rhs_range.end(),
+                    rhs,
+                ))
+            })
+            .map(|(path, range, annotation)| describe(&path, range, &annotation))
+            .join("\n")
+    };
+
+    let expected = render(
+        fixtures
+            .iter()
+            .flat_map(|fixture| {
+                fixture
+                    .annotations()
+                    .into_iter()
+                    .map(|(range, annotation)| (fixture.relative_path.clone(), range, annotation))
+            })
+            .collect(),
+    );
+    let actual = render(actual.into_iter().collect());
+
+    assert!(
+        expected == actual,
+        "annotations do not match\n\nexpected:\n{expected}\n\nactual:\n{actual}\n"
+    );
+}
+
+/// Parses a line like `    //^^^ error: message` into the column of the first
+/// caret, the number of carets and the annotation text.
+fn parse_annotation(line: &str) -> Option<(usize, usize, &str)> {
+    let comment_start = line.find(ANNOTATION_START)?;
+    if !line[..comment_start].trim().is_empty() {
+        return None;
+    }
+
+    let comment = &line[comment_start + ANNOTATION_START.len()..];
+    let markers = comment.trim_start_matches(' ');
+    if !markers.starts_with(ANNOTATION_MARKER) {
+        return None;
+    }
+
+    let column = line.len() - markers.len();
+    let annotation = markers.trim_start_matches(ANNOTATION_MARKER);
+    let len = markers.len() - annotation.len();
+    Some((column, len, annotation.trim()))
 }
 
 /// Turns a string that is likely to come from a raw string literal into
@@ -211,6 +330,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn annotations() {
+        let text = super::trim_raw_string_literal(
+            r#"
+            fn main() {
+                let a: i32 = true;
+                           //^^^^ error: mismatched type
+                foo(a)
+              //^^^ error: cannot find value `foo` in this scope
+                //  ^ warning: unused value
+            }
+            "#,
+        );
+        let annotations = extract_annotations(&text)
+            .into_iter()
+            .map(|(range, annotation)| (&text[range], annotation))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            annotations,
+            vec![
+                ("true", "error: mismatched type".to_owned()),
+                (
+                    "foo",
+                    "error: cannot find value `foo` in this scope".to_owned()
+                ),
+                ("a", "warning: unused value".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_are_not_annotations() {
+        assert_eq!(
+            extract_annotations("fn main() {}\n// a comment\nlet a = 1; //^ not an annotation\n"),
+            vec![]
+        );
+    }
+
     #[test]
     #[should_panic]
     fn incorrectly_indented_fixture() {
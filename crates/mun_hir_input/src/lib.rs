@@ -8,7 +8,7 @@ mod package_set;
 mod source_root;
 
 pub use db::{SourceDatabase, SourceDatabaseStorage};
-pub use fixture::{Fixture, WithFixture};
+pub use fixture::{check_annotations, extract_annotations, Fixture, WithFixture};
 pub use line_index::{LineCol, LineIndex};
 pub use module_tree::{ModuleData, ModuleTree, PackageModuleId};
 pub use package_set::{PackageData, PackageId, PackageSet};
@@ -20,6 +20,9 @@ pub struct PackageId(pub u32);
 pub struct PackageData {
     /// The source root which groups together all the source files of a package.
     pub source_root: SourceRootId,
+
+    /// The version of the package, if it has one.
+    pub version: Option<semver::Version>,
 }
 
 /// Contains information about all the packages in the project.
@@ -32,12 +35,23 @@ impl PackageSet {
     /// Adds a new package to the package set with the source files located add
     /// the specified root. Returns the `PackageId` associated with the package.
     pub fn add_package(&mut self, source_root: SourceRootId) -> PackageId {
-        let data = PackageData { source_root };
+        let data = PackageData {
+            source_root,
+            version: None,
+        };
         let package_id = PackageId(self.arena.len() as u32);
         self.arena.insert(package_id, data);
         package_id
     }
 
+    /// Sets the version of the specified package.
+    pub fn set_version(&mut self, package: PackageId, version: semver::Version) {
+        self.arena
+            .get_mut(&package)
+            .expect("package is not part of the package set")
+            .version = Some(version);
+    }
+
     /// Iterates over all packages
     pub fn iter(&self) -> impl Iterator<Item = PackageId> + '_ {
         self.arena.keys().copied()
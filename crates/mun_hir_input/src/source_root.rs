@@ -28,6 +28,9 @@ impl SourceRoot {
     pub fn remove_file(&mut self, file_id: FileId) -> bool {
         self.files.remove(&file_id).is_some()
     }
+    pub fn contains(&self, file_id: FileId) -> bool {
+        self.files.contains_key(&file_id)
+    }
     pub fn relative_path(&self, file_id: FileId) -> &RelativePath {
         &self.files[&file_id]
     }
@@ -1,13 +1,16 @@
-use std::sync::Arc;
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
 
-use mun_hir::AstDatabase;
+use mun_hir::{AstDatabase, LruCapacities};
 use mun_hir_input::{FileId, LineIndex, PackageId, SourceDatabase};
 use mun_syntax::SourceFile;
 use salsa::{ParallelDatabase, Snapshot};
 
 use crate::{
     cancelation::Canceled, change::AnalysisChange, completion, db::AnalysisDatabase, diagnostics,
-    diagnostics::Diagnostic, file_structure, FilePosition,
+    diagnostics::Diagnostic, file_structure, hover, FilePosition,
 };
 
 /// Result of an operation that can be canceled.
@@ -35,6 +38,12 @@ impl Analysis {
         }
     }
 
+    /// Sets the maximum number of results that are kept in memory for the
+    /// queries whose results take up the most memory.
+    pub fn set_lru_capacities(&mut self, capacities: LruCapacities) {
+        capacities.apply(&mut self.db);
+    }
+
     /// Requests any outstanding snapshot to cancel computations.
     pub fn request_cancelation(&mut self) {
         self.db.request_cancelation();
@@ -72,6 +81,29 @@ impl AnalysisSnapshot {
         })
     }
 
+    /// Returns a fingerprint of the paths and contents of all source files of
+    /// the given package. Fingerprints are stable between sessions of the
+    /// same language server.
+    pub fn package_fingerprint(&self, package_id: PackageId) -> Cancelable<u64> {
+        self.with_db(|db| {
+            let packages = db.packages();
+            let source_root = db.source_root(packages[package_id].source_root);
+            let mut files: Vec<_> = source_root
+                .files()
+                .map(|file_id| (source_root.relative_path(file_id), db.file_text(file_id)))
+                .collect();
+            files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let mut hasher = DefaultHasher::new();
+            env!("CARGO_PKG_VERSION").hash(&mut hasher);
+            for (path, text) in files {
+                path.as_str().hash(&mut hasher);
+                text.hash(&mut hasher);
+            }
+            hasher.finish()
+        })
+    }
+
     /// Returns the line index for the specified file
     pub fn file_line_index(&self, file_id: FileId) -> Cancelable<Arc<LineIndex>> {
         self.with_db(|db| db.line_index(file_id))
@@ -93,6 +125,12 @@ impl AnalysisSnapshot {
         self.with_db(|db| completion::completions(db, position).map(Into::into))
     }
 
+    /// Computes the information to display when hovering over the given
+    /// position
+    pub fn hover(&self, position: FilePosition) -> Cancelable<Option<hover::HoverResult>> {
+        self.with_db(|db| hover::hover(db, position))
+    }
+
     /// Performs an operation on that may be Canceled.
     fn with_db<F: FnOnce(&AnalysisDatabase) -> T + std::panic::UnwindSafe, T>(
         &self,
@@ -13,7 +13,7 @@ mod dot;
 mod expr;
 mod name_ref;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 
 use context::{
     CompletionAnalysis, CompletionContext, DotAccess, NameRefContext, NameRefKind,
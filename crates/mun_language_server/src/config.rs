@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+
+use mun_hir::LruCapacities;
 use mun_paths::AbsPathBuf;
 use mun_project::ProjectManifest;
 
@@ -11,8 +14,31 @@ pub struct Config {
 
     /// A collection of projects discovered within the workspace
     pub discovered_projects: Option<Vec<ProjectManifest>>,
+
+    /// Whether the client wants to receive `mun/serverStatus` notifications
+    pub server_status_notification: bool,
+
+    /// An optional file to which reports of panics are appended
+    pub panic_log_file: Option<PathBuf>,
+
+    /// An optional directory in which diagnostics are persisted between
+    /// sessions
+    pub diagnostics_cache_dir: Option<PathBuf>,
+
+    /// The maximum number of results that are kept in memory for the queries
+    /// whose results take up the most memory, which keeps the memory usage of
+    /// long sessions bounded
+    pub lru_capacities: LruCapacities,
 }
 
+/// The LRU capacities that are used unless the client overrides them.
+const DEFAULT_LRU_CAPACITIES: LruCapacities = LruCapacities {
+    parse: 128,
+    item_tree: 128,
+    body: 512,
+    infer: 512,
+};
+
 impl Config {
     /// Constructs a new instance of a `Config`
     pub fn new(root_path: AbsPathBuf) -> Self {
@@ -20,6 +46,10 @@ impl Config {
             watcher: FilesWatcher::Notify,
             root_dir: root_path,
             discovered_projects: None,
+            server_status_notification: false,
+            panic_log_file: None,
+            diagnostics_cache_dir: None,
+            lru_capacities: DEFAULT_LRU_CAPACITIES,
         }
     }
 }
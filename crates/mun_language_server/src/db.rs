@@ -1,9 +1,9 @@
 #![allow(clippy::enum_variant_names)] // This is a HACK because we use salsa
 
-use std::panic;
+use std::{panic, sync::Arc};
 
 use mun_db::Upcast;
-use mun_hir::HirDatabase;
+use mun_hir::{DefDatabase, HirDatabase};
 use mun_target::spec::Target;
 use salsa::{Database, Durability, Snapshot};
 
@@ -38,6 +38,8 @@ impl Default for AnalysisDatabase {
             storage: salsa::Storage::default(),
         };
         db.set_target(Target::host_target().expect("could not determine host target spec"));
+        db.set_cfg_options(Arc::default());
+        db.set_lint_levels(Arc::default());
         db
     }
 }
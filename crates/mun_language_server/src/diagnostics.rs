@@ -1,9 +1,10 @@
 use std::cell::RefCell;
 
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{AstDatabase, InFile, Module};
+use mun_hir::{diagnostics::Severity, AstDatabase, InFile, Module};
 use mun_hir_input::{FileId, ModuleId, PackageId, SourceDatabase};
 use mun_syntax::{Location, TextRange};
+use ra_ap_text_edit::Indel;
 
 use crate::db::AnalysisDatabase;
 
@@ -13,13 +14,22 @@ pub struct SourceAnnotation {
     pub range: InFile<TextRange>,
 }
 
+/// A change to the file of a diagnostic that resolves the diagnostic.
+#[derive(Debug)]
+pub struct Fix {
+    pub label: String,
+    pub edit: Indel,
+}
+
 #[derive(Debug)]
 pub struct Diagnostic {
     pub message: String,
     pub range: TextRange,
+    /// The error code or the name of the lint that emitted the diagnostic
+    pub code: Option<String>,
     pub additional_annotations: Vec<SourceAnnotation>,
-    // pub fix: Option<SourceChange>,
-    // pub severity: Severity,
+    pub severity: Severity,
+    pub fixes: Vec<Fix>,
 }
 
 /// Converts a location to a a range for use in diagnostics
@@ -39,18 +49,23 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: FileId) -> Vec<Diagnos
     result.extend(parse.errors().iter().map(|err| Diagnostic {
         message: format!("parse error: {err}"),
         range: location_to_range(err.location()),
+        code: None,
         additional_annotations: vec![],
+        severity: Severity::Error,
+        fixes: vec![],
     }));
 
     // Add all HIR diagnostics
     let result = RefCell::new(result);
     let mut sink = mun_hir::diagnostics::DiagnosticSink::new(|d| {
+        let severity = d.severity();
         result.borrow_mut().push(d.with_diagnostic(db, |d| {
             Diagnostic {
                 message: format!("{}\n{}", d.title(), d.footer().join("\n"))
                     .trim()
                     .to_owned(),
                 range: d.range(),
+                code: d.code(),
                 additional_annotations: d
                     .secondary_annotations()
                     .into_iter()
@@ -59,6 +74,16 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: FileId) -> Vec<Diagnos
                         range: annotation.range,
                     })
                     .collect(),
+                severity,
+                fixes: d
+                    .fixes()
+                    .into_iter()
+                    .filter(|fix| fix.range.file_id == file_id)
+                    .map(|fix| Fix {
+                        label: fix.label,
+                        edit: Indel::replace(fix.range.value, fix.replacement),
+                    })
+                    .collect(),
             }
         }));
     });
@@ -0,0 +1,175 @@
+//! Persists the diagnostics of packages between sessions of the language
+//! server. Computing diagnostics requires inferring the types of every body in
+//! a package, which is expensive for large workspaces. On startup the cached
+//! diagnostics of a package are reused as long as the fingerprint of its
+//! source files did not change.
+//!
+//! Only the published diagnostics are cached, not the results of the queries
+//! they were derived from. Requests like hover and completion still infer the
+//! types of the bodies they need on a cold start.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use lsp_types::{Diagnostic, Url};
+use serde_derive::{Deserialize, Serialize};
+
+/// The name of the file in the cache directory that holds the diagnostics.
+const CACHE_FILE_NAME: &str = "diagnostics.json";
+
+/// The diagnostics of all files of a package.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedPackage {
+    /// The fingerprint of the source files from which the diagnostics were
+    /// computed
+    pub fingerprint: u64,
+
+    /// The diagnostics of every source file in the package
+    pub files: Vec<(Url, Vec<Diagnostic>)>,
+}
+
+/// The contents of the cache file.
+#[derive(Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// The version of the language server that wrote the file
+    version: String,
+
+    /// The cached diagnostics of every package, keyed by the path of its
+    /// manifest
+    packages: HashMap<String, CachedPackage>,
+}
+
+/// An on-disk cache of the diagnostics of packages.
+#[derive(Debug)]
+pub(crate) struct DiagnosticsCache {
+    path: PathBuf,
+    packages: HashMap<String, CachedPackage>,
+    modified: bool,
+}
+
+impl DiagnosticsCache {
+    /// Loads the cache from the specified directory. If the cache does not
+    /// exist, is corrupt, or was written by a different version of the
+    /// language server an empty cache is returned.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE_NAME);
+        let packages = match fs::read(&path) {
+            Ok(contents) => match serde_json::from_slice::<CacheFile>(&contents) {
+                Ok(file) if file.version == env!("CARGO_PKG_VERSION") => file.packages,
+                Ok(_) => HashMap::new(),
+                Err(err) => {
+                    log::warn!("ignoring corrupt cache {}: {err}", path.display());
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        Self {
+            path,
+            packages,
+            modified: false,
+        }
+    }
+
+    /// Returns the cached diagnostics of the package with the given manifest
+    /// if they were computed from sources with the same `fingerprint`.
+    pub fn get(&self, manifest_path: &Path, fingerprint: u64) -> Option<&CachedPackage> {
+        self.packages
+            .get(manifest_path.to_string_lossy().as_ref())
+            .filter(|package| package.fingerprint == fingerprint)
+    }
+
+    /// Stores the diagnostics of the package with the given manifest.
+    pub fn insert(&mut self, manifest_path: &Path, package: CachedPackage) {
+        let key = manifest_path.to_string_lossy().into_owned();
+        if self.packages.get(&key) != Some(&package) {
+            self.packages.insert(key, package);
+            self.modified = true;
+        }
+    }
+
+    /// Writes the cache to disk if it was modified since it was loaded.
+    pub fn save(&mut self) -> io::Result<()> {
+        if !self.modified {
+            return Ok(());
+        }
+
+        let file = CacheFile {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            packages: self.packages.clone(),
+        };
+        let contents = serde_json::to_vec(&file).map_err(io::Error::other)?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Write to a temporary file first to never leave a partially written cache
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        self.modified = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use lsp_types::{Diagnostic, Url};
+
+    use super::{CachedPackage, DiagnosticsCache, CACHE_FILE_NAME};
+
+    fn package(fingerprint: u64) -> CachedPackage {
+        CachedPackage {
+            fingerprint,
+            files: vec![(
+                Url::parse("file:///foo/src/mod.mun").unwrap(),
+                vec![Diagnostic {
+                    message: "mismatched type".to_owned(),
+                    ..Diagnostic::default()
+                }],
+            )],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let dir = tempdir::TempDir::new("cache").unwrap();
+        let manifest = Path::new("/foo/mun.toml");
+
+        let mut cache = DiagnosticsCache::load(dir.path());
+        assert!(cache.get(manifest, 1).is_none());
+        cache.insert(manifest, package(1));
+        cache.save().unwrap();
+
+        let cache = DiagnosticsCache::load(dir.path());
+        assert_eq!(cache.get(manifest, 1), Some(&package(1)));
+        assert!(cache.get(manifest, 2).is_none());
+        assert!(cache.get(Path::new("/bar/mun.toml"), 1).is_none());
+    }
+
+    #[test]
+    fn corrupt_cache_is_ignored() {
+        let dir = tempdir::TempDir::new("cache").unwrap();
+        std::fs::write(dir.path().join(CACHE_FILE_NAME), "not json").unwrap();
+
+        let cache = DiagnosticsCache::load(dir.path());
+        assert!(cache.packages.is_empty());
+    }
+
+    #[test]
+    fn unmodified_cache_is_not_written() {
+        let dir = tempdir::TempDir::new("cache").unwrap();
+
+        let mut cache = DiagnosticsCache::load(dir.path());
+        cache.save().unwrap();
+        assert!(!dir.path().join(CACHE_FILE_NAME).exists());
+    }
+}
@@ -86,6 +86,66 @@ pub(crate) fn handle_completion(
     Ok(Some(items.into()))
 }
 
+/// Computes the information to display when hovering over a specific
+/// location.
+pub(crate) fn handle_hover(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::HoverParams,
+) -> anyhow::Result<Option<lsp_types::Hover>> {
+    let position = from_lsp::file_position(&snapshot, params.text_document_position_params)?;
+    let Some(result) = snapshot.analysis.hover(position)? else {
+        return Ok(None);
+    };
+
+    let line_index = snapshot.analysis.file_line_index(position.file_id)?;
+    Ok(Some(lsp_types::Hover {
+        contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: result.markup,
+        }),
+        range: Some(to_lsp::range(result.range, &line_index)),
+    }))
+}
+
+/// Computes the quick fixes for the diagnostics that overlap with the
+/// requested range.
+pub(crate) fn handle_code_action(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::CodeActionParams,
+) -> anyhow::Result<Option<lsp_types::CodeActionResponse>> {
+    let file_id = from_lsp::file_id(&snapshot, &params.text_document.uri)?;
+    let line_index = snapshot.analysis.file_line_index(file_id)?;
+    let range = from_lsp::text_range(&line_index, params.range);
+
+    let mut actions = Vec::new();
+    for diagnostic in snapshot.analysis.diagnostics(file_id)? {
+        if diagnostic.range.intersect(range).is_none() {
+            continue;
+        }
+
+        for fix in diagnostic.fixes {
+            let edit = to_lsp::text_edit(fix.edit, &line_index);
+            actions.push(lsp_types::CodeActionOrCommand::CodeAction(
+                lsp_types::CodeAction {
+                    title: fix.label,
+                    kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                    edit: Some(lsp_types::WorkspaceEdit {
+                        changes: Some(
+                            std::iter::once((params.text_document.uri.clone(), vec![edit]))
+                                .collect(),
+                        ),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+
+    Ok(Some(actions))
+}
+
 /// Constructs a hierarchy of `DocumentSymbols` for a list of symbols that
 /// specify which index is the parent of a symbol. The parent index must always
 /// be smaller than the current index.
@@ -0,0 +1,384 @@
+use mun_hir::{
+    semantics::{PathResolution, Semantics},
+    CallableDef, Const, ConstValue, FloatBitness, FloatTy, HirDatabase, HirDisplay, IntBitness,
+    IntTy, Literal, ModuleDef, ResolveBitness, Signedness, TyKind,
+};
+use mun_syntax::{ast, AstNode, SyntaxKind, SyntaxToken, TextRange};
+
+use crate::{db::AnalysisDatabase, FilePosition};
+
+/// The result of a hover request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoverResult {
+    /// The markdown formatted contents to display
+    pub markup: String,
+
+    /// The range of the text that was hovered
+    pub range: TextRange,
+}
+
+/// Computes the information to display when hovering over the given position.
+pub(crate) fn hover(db: &AnalysisDatabase, position: FilePosition) -> Option<HoverResult> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+    let token = file
+        .syntax()
+        .token_at_offset(position.offset)
+        .find(|token| {
+            matches!(
+                token.kind(),
+                SyntaxKind::INT_NUMBER | SyntaxKind::FLOAT_NUMBER | SyntaxKind::IDENT
+            )
+        })?;
+
+    if token.kind() == SyntaxKind::IDENT {
+        return hover_path(&sema, &token);
+    }
+
+    let literal = token.parent().and_then(ast::Literal::cast)?;
+    let ty = sema.type_of_expr(&ast::Expr::cast(literal.syntax().clone())?)?;
+    let is_negated = literal
+        .syntax()
+        .parent()
+        .and_then(ast::PrefixExpr::cast)
+        .and_then(|prefix| prefix.op_kind())
+        == Some(ast::PrefixOp::Neg);
+    let markup = match (Literal::from_ast(&literal).0, ty.interned()) {
+        (Literal::Int(lit), TyKind::Int(int_ty)) => hover_int(db, lit.value, *int_ty, is_negated),
+        (Literal::Float(lit), TyKind::Float(float_ty)) => hover_float(lit.value, *float_ty),
+        _ => return None,
+    };
+
+    Some(HoverResult {
+        markup,
+        range: token.text_range(),
+    })
+}
+
+/// Renders the signature and documentation of the function or struct, or the
+/// value of the constant that the path expression containing `token` refers
+/// to.
+fn hover_path(sema: &Semantics<'_>, token: &SyntaxToken) -> Option<HoverResult> {
+    let path = token.parent()?.ancestors().find_map(ast::Path::cast)?;
+    let path_expr = path.syntax().parent().and_then(ast::PathExpr::cast)?;
+
+    let db = sema.db;
+    if let Some(PathResolution::Def(ModuleDef::Const(konst))) = sema.resolve_path(&path) {
+        return Some(HoverResult {
+            markup: hover_const(db, konst),
+            range: token.text_range(),
+        });
+    }
+
+    let ty = sema.type_of_expr(&ast::Expr::cast(path_expr.syntax().clone())?)?;
+    let (signature, docs) = match ty.interned() {
+        TyKind::FnDef(CallableDef::Function(function), _) => {
+            (function.display(db).to_string(), function.docs(db))
+        }
+        TyKind::FnDef(CallableDef::Struct(strukt), _) | TyKind::Struct(strukt) => {
+            (format!("struct {}", strukt.name(db)), strukt.docs(db))
+        }
+        _ => return None,
+    };
+
+    let mut markup = format!("```mun\n{signature}\n```");
+    if let Some(docs) = docs {
+        markup.push_str("\n___\n");
+        markup.push_str(&docs);
+    }
+
+    Some(HoverResult {
+        markup,
+        range: token.text_range(),
+    })
+}
+
+/// Renders the value of an integer literal of the given type in multiple
+/// bases. `is_negated` indicates that the literal is the operand of a minus
+/// sign.
+fn hover_int(db: &dyn HirDatabase, value: u128, ty: IntTy, is_negated: bool) -> String {
+    let resolved_ty = IntTy {
+        signedness: ty.signedness,
+        bitness: ty.bitness.resolve(&db.target_data_layout()),
+    };
+
+    let mut markup = format!(
+        "```mun\n{ty}\n```\n___\n{}",
+        int_info(value, value, resolved_ty.bitness)
+    );
+
+    // A literal is only ever positive, a minus sign is a separate prefix
+    // operator. The minimum value of a signed integer is one larger in
+    // magnitude than its maximum, so a negated literal may be one larger.
+    let max = resolved_ty.max();
+    let fits = match resolved_ty.signedness {
+        Signedness::Signed if is_negated => value <= max + 1,
+        Signedness::Signed | Signedness::Unsigned => value <= max,
+    };
+    if !fits {
+        markup.push_str(&format!("\n\n*the value does not fit in `{ty}`*"));
+    }
+
+    markup
+}
+
+/// Renders the exact value that is stored for a floating point literal of the
+/// given type.
+fn hover_float(value: f64, ty: FloatTy) -> String {
+    format!("```mun\n{ty}\n```\n___\n{}", float_info(value, ty))
+}
+
+/// Renders the signature of a constant together with its value.
+fn hover_const(db: &dyn HirDatabase, konst: Const) -> String {
+    let mut markup = format!(
+        "```mun\nconst {}: {}\n```",
+        konst.name(db),
+        konst.ty(db).display(db)
+    );
+    let info = match konst.eval(db) {
+        Ok(value @ ConstValue::Int { bits, ty }) => {
+            let decimal = value
+                .as_i128()
+                .map_or_else(|| bits.to_string(), |value| value.to_string());
+            int_info(decimal, bits, ty.bitness)
+        }
+        Ok(value @ ConstValue::Float { ty, .. }) => {
+            float_info(value.as_f64().expect("expected a float"), ty)
+        }
+        Ok(ConstValue::Bool(value)) => format!("value: `{value}`"),
+        Err(_) => return markup,
+    };
+    markup.push_str("\n___\n");
+    markup.push_str(&info);
+    markup
+}
+
+/// Renders an integer in multiple bases. `bits` is the two's complement
+/// representation of the integer and `bitness` must be resolved.
+fn int_info(decimal: impl std::fmt::Display, bits: u128, bitness: IntBitness) -> String {
+    let width = match bitness {
+        IntBitness::X8 => 8,
+        IntBitness::X16 => 16,
+        IntBitness::X32 => 32,
+        IntBitness::X64 => 64,
+        IntBitness::X128 => 128,
+        IntBitness::Xsize => unreachable!("bitness has been resolved"),
+    };
+    format!(
+        "decimal: `{decimal}`  \n\
+         hex: `{bits:#x}`  \n\
+         octal: `{bits:#o}`  \n\
+         binary: `{bits:#b}`  \n\
+         bit width: {width}"
+    )
+}
+
+/// Renders the exact value that is stored for a floating point value of the
+/// given type.
+fn float_info(value: f64, ty: FloatTy) -> String {
+    let (shortest, exact, bits) = match ty.bitness {
+        FloatBitness::X32 => {
+            let stored = value as f32;
+            (
+                format!("{stored:?}"),
+                exact_decimal(f64::from(stored)),
+                format!("{:#010x}", stored.to_bits()),
+            )
+        }
+        FloatBitness::X64 => (
+            format!("{value:?}"),
+            exact_decimal(value),
+            format!("{:#018x}", value.to_bits()),
+        ),
+    };
+
+    format!(
+        "value: `{shortest}`  \n\
+         exact stored value: `{exact}`  \n\
+         bits: `{bits}`"
+    )
+}
+
+/// Returns the exact decimal representation of a floating point value. Every
+/// finite binary floating point value has a finite decimal representation;
+/// 1074 fractional digits suffice for the smallest subnormal `f64`.
+fn exact_decimal(value: f64) -> String {
+    if !value.is_finite() {
+        return format!("{value}");
+    }
+
+    let text = format!("{value:.1074}");
+    let text = text.trim_end_matches('0');
+    if let Some(text) = text.strip_suffix('.') {
+        format!("{text}.0")
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::completion::test_utils::position;
+
+    fn hover(fixture: &str) -> String {
+        let (db, position) = position(fixture);
+        super::hover(&db, position).map_or_else(
+            || String::from("no hover information"),
+            |result| result.markup,
+        )
+    }
+
+    #[test]
+    fn test_hover_int() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> u8 {
+            0x2$0a
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_int_does_not_fit() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> i8 {
+            1$029
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_int_boundary() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> i8 {
+            12$08
+        }
+        "#,
+        ));
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> i8 {
+            -12$08
+        }
+        "#,
+        ));
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> i8 {
+            12$07
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_const() {
+        insta::assert_snapshot!(hover(
+            r#"
+        const MIN: i16 = -2 * 4;
+
+        fn main() -> i16 {
+            MI$0N
+        }
+        "#,
+        ));
+        insta::assert_snapshot!(hover(
+            r#"
+        const HALF: f32 = 1.0 / 2.0;
+
+        fn main() -> f32 {
+            HA$0LF
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_usize() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> usize {
+            1_000$0
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_float() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() -> f32 {
+            0.$01
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_f64() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn main() {
+            let a = 2.5e$03;
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_function_docs() {
+        insta::assert_snapshot!(hover(
+            r#"
+        /// Adds two numbers.
+        ///
+        /// The result wraps on overflow.
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+        fn main() -> i32 {
+            ad$0d(1, 2)
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_struct_docs() {
+        insta::assert_snapshot!(hover(
+            r#"
+        /** A point in 2D space */
+        struct Point(f32, f32);
+
+        fn main() {
+            let p = Po$0int(1.0, 2.0);
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_undocumented_function() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn foo() {}
+
+        fn main() {
+            fo$0o()
+        }
+        "#,
+        ));
+    }
+
+    #[test]
+    fn test_hover_no_literal() {
+        insta::assert_snapshot!(hover(
+            r#"
+        fn ma$0in() {}
+        "#,
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, path::PathBuf};
 
 pub use config::{Config, FilesWatcher};
 pub use main_loop::main_loop;
@@ -20,11 +20,15 @@ mod completion;
 mod config;
 mod db;
 mod diagnostics;
+mod diagnostics_cache;
 mod file_structure;
 mod from_lsp;
 mod handlers;
+mod hover;
+pub mod lsp_ext;
 mod lsp_utils;
 mod main_loop;
+mod panic_handler;
 mod state;
 mod symbol_kind;
 mod to_lsp;
@@ -60,6 +64,7 @@ pub fn to_json<T: Serialize>(value: T) -> anyhow::Result<serde_json::Value> {
 /// Main entry point for the language server
 pub fn run_server() -> anyhow::Result<()> {
     log::info!("language server started");
+    mun_crash_report::install("mun-language-server", env!("CARGO_PKG_VERSION"));
 
     // Setup IO connections
     let (connection, io_threads) = lsp_server::Connection::stdio();
@@ -119,6 +124,30 @@ pub fn run_server() -> anyhow::Result<()> {
             config.watcher = FilesWatcher::Client;
         }
 
+        // Determine whether the client wants to be notified about the health of the server
+        config.server_status_notification = initialize_params
+            .capabilities
+            .experimental
+            .as_ref()
+            .and_then(|experimental| experimental.get("serverStatusNotification"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        // Reports of panics are optionally written to a log file
+        config.panic_log_file = std::env::var_os("MUN_LSP_PANIC_LOG").map(PathBuf::from);
+
+        // Diagnostics are optionally persisted between sessions
+        config.diagnostics_cache_dir =
+            std::env::var_os("MUN_LSP_DIAGNOSTICS_CACHE_DIR").map(PathBuf::from);
+
+        // The memory usage of the analysis can optionally be tuned
+        if let Ok(capacities) = std::env::var("MUN_LSP_LRU_CAPACITIES") {
+            match capacities.parse() {
+                Ok(capacities) => config.lru_capacities = capacities,
+                Err(err) => log::error!("invalid MUN_LSP_LRU_CAPACITIES: {}", err),
+            }
+        }
+
         // Convert the workspace_roots, if these are empy use the root_uri or the cwd
         let workspace_roots = initialize_params
             .workspace_folders
@@ -0,0 +1,41 @@
+//! Mun specific extensions to the language server protocol.
+
+use lsp_types::notification::Notification;
+use serde_derive::{Deserialize, Serialize};
+
+/// A notification sent from the server to the client to inform it about the
+/// health of the server. Clients opt-in to receiving this notification by
+/// setting the `serverStatusNotification` field of the experimental client
+/// capabilities to `true`.
+pub enum ServerStatusNotification {}
+
+impl Notification for ServerStatusNotification {
+    type Params = ServerStatusParams;
+    const METHOD: &'static str = "mun/serverStatus";
+}
+
+/// The parameters of a [`ServerStatusNotification`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatusParams {
+    /// The health of the server
+    pub health: Health,
+
+    /// An optional message explaining the health status
+    pub message: Option<String>,
+}
+
+/// Describes the health of the language server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Health {
+    /// The server is operating normally
+    Ok,
+
+    /// The server recovered from an internal error, results might be
+    /// incomplete
+    Warning,
+
+    /// The server encountered an error it could not recover from
+    Error,
+}
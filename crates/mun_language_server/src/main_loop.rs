@@ -1,10 +1,12 @@
 use lsp_server::Connection;
 
-use crate::{Config, LanguageServerState};
+use crate::{panic_handler, Config, LanguageServerState};
 
 /// Runs the main loop of the language server. This will receive requests and
-/// handle them.
+/// handle them. A panic that occurs while handling a request is reported back
+/// to the client as an error instead of bringing down the server.
 pub fn main_loop(connection: Connection, config: Config) -> anyhow::Result<()> {
     log::info!("initial config: {:#?}", config);
+    panic_handler::install_hook();
     LanguageServerState::new(connection.sender, config).run(connection.receiver)
 }
@@ -0,0 +1,108 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::RefCell,
+    fmt,
+    panic::{AssertUnwindSafe, PanicHookInfo},
+    sync::Once,
+};
+
+use crate::cancelation::Canceled;
+
+thread_local! {
+    /// The backtrace of the last panic that occurred on this thread. The panic
+    /// hook stores it here so it can be picked up after the stack has been
+    /// unwound.
+    static LAST_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+/// Information about a panic that occurred while handling a request.
+#[derive(Clone, Debug)]
+pub(crate) struct PanicReport {
+    /// The message passed to `panic!`
+    pub message: String,
+
+    /// The backtrace captured at the moment of the panic, if any
+    pub backtrace: Option<String>,
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "request handler panicked: {}", self.message)?;
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\n\nbacktrace:\n{backtrace}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PanicReport {}
+
+/// Installs a panic hook that captures the backtrace of a panic so that it can
+/// be included in a [`PanicReport`]. The previously installed hook is still
+/// invoked. Calling this function multiple times has no effect.
+pub(crate) fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+            LAST_BACKTRACE.with(|bt| *bt.borrow_mut() = Some(Backtrace::force_capture()));
+            previous_hook(info);
+        }));
+    });
+}
+
+/// Invokes `f`, catching any panic that occurs. A panic is turned into an
+/// error containing a [`PanicReport`]. Cancelation of the analysis, which is
+/// also implemented through unwinding, is turned into a [`Canceled`] error.
+pub(crate) fn catch_unwind<T>(f: impl FnOnce() -> T) -> anyhow::Result<T> {
+    LAST_BACKTRACE.with(|bt| bt.borrow_mut().take());
+    std::panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        match payload.downcast::<Canceled>() {
+            Ok(canceled) => anyhow::Error::new(*canceled),
+            Err(payload) => anyhow::Error::new(PanicReport {
+                message: panic_message(&*payload),
+                backtrace: LAST_BACKTRACE
+                    .with(|bt| bt.borrow_mut().take())
+                    .map(|bt| bt.to_string()),
+            }),
+        }
+    })
+}
+
+/// Extracts a human readable message from the payload of a panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<unknown panic payload>".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{catch_unwind, install_hook, PanicReport};
+    use crate::cancelation::{is_canceled, Canceled};
+
+    #[test]
+    fn panic_is_reported() {
+        install_hook();
+        let err = catch_unwind(|| panic!("something went wrong")).unwrap_err();
+        let report = err.downcast_ref::<PanicReport>().unwrap();
+        assert_eq!(report.message, "something went wrong");
+        assert!(report.backtrace.is_some());
+    }
+
+    #[test]
+    fn cancelation_is_not_a_panic() {
+        let err = catch_unwind(|| Canceled::throw()).unwrap_err();
+        assert!(is_canceled(&*err));
+    }
+
+    #[test]
+    fn no_panic() {
+        assert_eq!(catch_unwind(|| 5).unwrap(), 5);
+    }
+}
@@ -1,21 +1,25 @@
-use std::{sync::Arc, time::Instant};
+use std::{io::Write, sync::Arc, time::Instant};
 
 use crossbeam_channel::{select, unbounded, Receiver, Sender};
 use lsp_server::{ReqQueue, Response};
 use lsp_types::{
     notification::{Notification, PublishDiagnostics},
-    PublishDiagnosticsParams,
+    PublishDiagnosticsParams, Url,
 };
+use mun_hir::diagnostics::Severity;
 use mun_hir_input::{FileId, PackageId, PackageSet};
 use mun_paths::AbsPathBuf;
 use mun_vfs::VirtualFileSystem;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rustc_hash::FxHashSet;
 
 use crate::{
     analysis::{Analysis, AnalysisSnapshot},
     change::AnalysisChange,
     config::Config,
+    diagnostics_cache::{CachedPackage, DiagnosticsCache},
+    lsp_ext,
+    panic_handler::PanicReport,
     state::utils::Progress,
     to_json, to_lsp,
 };
@@ -31,6 +35,7 @@ mod workspace;
 pub(crate) enum Task {
     Response(Response),
     Notify(lsp_server::Notification),
+    Panicked(&'static str, PanicReport),
 }
 
 #[derive(Debug)]
@@ -80,6 +85,12 @@ pub(crate) struct LanguageServerState {
     /// All the packages known to the server
     pub packages: Arc<Vec<mun_project::Package>>,
 
+    /// Diagnostics persisted between sessions, if enabled
+    pub diagnostics_cache: Option<Arc<Mutex<DiagnosticsCache>>>,
+
+    /// The health of the server that was last reported to the client
+    pub health: lsp_ext::Health,
+
     /// True if the client requested that we shut down
     pub shutdown_requested: bool,
 }
@@ -94,6 +105,9 @@ pub(crate) struct LanguageServerSnapshot {
 
     /// All the packages known to the server
     pub packages: Arc<Vec<mun_project::Package>>,
+
+    /// Diagnostics persisted between sessions, if enabled
+    pub diagnostics_cache: Option<Arc<Mutex<DiagnosticsCache>>>,
 }
 
 impl LanguageServerState {
@@ -113,11 +127,18 @@ impl LanguageServerState {
         // Construct the state that will hold all the analysis and apply the initial
         // state
         let mut analysis = Analysis::default();
+        analysis.set_lru_capacities(config.lru_capacities);
         let mut change = AnalysisChange::new();
         change.set_packages(PackageSet::default());
         change.set_roots(Vec::default());
         analysis.apply_change(change);
 
+        // Load the diagnostics of a previous session
+        let diagnostics_cache = config
+            .diagnostics_cache_dir
+            .as_deref()
+            .map(|dir| Arc::new(Mutex::new(DiagnosticsCache::load(dir))));
+
         LanguageServerState {
             sender,
             request_queue: ReqQueue::default(),
@@ -131,6 +152,8 @@ impl LanguageServerState {
             task_receiver,
             analysis,
             packages: Arc::new(Vec::new()),
+            diagnostics_cache,
+            health: lsp_ext::Health::Ok,
             shutdown_requested: false,
         }
     }
@@ -150,6 +173,7 @@ impl LanguageServerState {
     pub fn run(mut self, receiver: Receiver<lsp_server::Message>) -> anyhow::Result<()> {
         // Start by updating the current workspace
         self.fetch_workspaces();
+        self.send_server_status(lsp_ext::Health::Ok, None);
 
         while let Some(event) = self.next_event(&receiver) {
             if let Event::Lsp(lsp_server::Message::Notification(notification)) = &event {
@@ -202,6 +226,7 @@ impl LanguageServerState {
                 self.send(notification.into());
             }
             Task::Response(response) => self.respond(response),
+            Task::Panicked(method, report) => self.on_panic(method, report),
         }
         Ok(())
     }
@@ -244,75 +269,140 @@ impl LanguageServerState {
     }
 }
 
+impl LanguageServerState {
+    /// Called when handling a request resulted in a panic. The panic is
+    /// logged, optionally written to the panic log file, and the client is
+    /// informed that the server is in a degraded state.
+    pub(crate) fn on_panic(&mut self, method: &str, report: PanicReport) {
+        log::error!("panic while handling `{method}`: {report}");
+
+        if let Some(path) = &self.config.panic_log_file {
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "panic while handling `{method}`: {report}\n"));
+            if let Err(err) = result {
+                log::error!("could not write panic to {}: {err}", path.display());
+            }
+        }
+
+        self.send_server_status(
+            lsp_ext::Health::Warning,
+            Some(format!(
+                "the language server recovered from a panic while handling `{method}`: {}",
+                report.message
+            )),
+        );
+    }
+}
+
 /// Sends all diagnostics of all files
 fn handle_diagnostics(state: LanguageServerSnapshot, sender: Sender<Task>) -> anyhow::Result<()> {
     // Iterate over all files
-    for (idx, _package) in state.packages.iter().enumerate() {
+    for (idx, package) in state.packages.iter().enumerate() {
         let package_id = PackageId(idx as u32);
 
+        // Reuse the diagnostics of a previous session if the sources did not change
+        let fingerprint = state.analysis.package_fingerprint(package_id)?;
+        let cached = state.diagnostics_cache.as_ref().and_then(|cache| {
+            cache
+                .lock()
+                .get(package.manifest_path(), fingerprint)
+                .cloned()
+        });
+        if let Some(cached) = cached {
+            for (uri, diagnostics) in cached.files {
+                publish_diagnostics(&sender, uri, diagnostics);
+            }
+            continue;
+        }
+
         // Get all the files
         let files = state.analysis.package_source_files(package_id)?;
 
         // Publish all diagnostics
+        let mut package_diagnostics = Vec::with_capacity(files.len());
         for file in files {
-            let line_index = state.analysis.file_line_index(file)?;
             let uri = to_lsp::url(&state, file)?;
-            let diagnostics = state.analysis.diagnostics(file)?;
-
-            let diagnostics = {
-                let mut lsp_diagnostics = Vec::with_capacity(diagnostics.len());
-                for d in diagnostics {
-                    lsp_diagnostics.push(lsp_types::Diagnostic {
-                        range: to_lsp::range(d.range, &line_index),
-                        severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                        code: None,
-                        code_description: None,
-                        source: Some("mun".to_string()),
-                        message: d.message,
-                        related_information: {
-                            let mut annotations =
-                                Vec::with_capacity(d.additional_annotations.len());
-                            for annotation in d.additional_annotations {
-                                annotations.push(lsp_types::DiagnosticRelatedInformation {
-                                    location: lsp_types::Location {
-                                        uri: to_lsp::url(&state, annotation.range.file_id)?,
-                                        range: to_lsp::range(
-                                            annotation.range.value,
-                                            &*state
-                                                .analysis
-                                                .file_line_index(annotation.range.file_id)?,
-                                        ),
-                                    },
-                                    message: annotation.message,
-                                });
-                            }
-                            if annotations.is_empty() {
-                                None
-                            } else {
-                                Some(annotations)
-                            }
+            let diagnostics = file_diagnostics(&state, file)?;
+            publish_diagnostics(&sender, uri.clone(), diagnostics.clone());
+            package_diagnostics.push((uri, diagnostics));
+        }
+
+        if let Some(cache) = &state.diagnostics_cache {
+            cache.lock().insert(
+                package.manifest_path(),
+                CachedPackage {
+                    fingerprint,
+                    files: package_diagnostics,
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Computes the LSP diagnostics of a single file
+fn file_diagnostics(
+    state: &LanguageServerSnapshot,
+    file: FileId,
+) -> anyhow::Result<Vec<lsp_types::Diagnostic>> {
+    let line_index = state.analysis.file_line_index(file)?;
+    let diagnostics = state.analysis.diagnostics(file)?;
+
+    let mut lsp_diagnostics = Vec::with_capacity(diagnostics.len());
+    for d in diagnostics {
+        lsp_diagnostics.push(lsp_types::Diagnostic {
+            range: to_lsp::range(d.range, &line_index),
+            severity: Some(match d.severity {
+                Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+            }),
+            code: d.code.map(lsp_types::NumberOrString::String),
+            code_description: None,
+            source: Some("mun".to_string()),
+            message: d.message,
+            related_information: {
+                let mut annotations = Vec::with_capacity(d.additional_annotations.len());
+                for annotation in d.additional_annotations {
+                    annotations.push(lsp_types::DiagnosticRelatedInformation {
+                        location: lsp_types::Location {
+                            uri: to_lsp::url(state, annotation.range.file_id)?,
+                            range: to_lsp::range(
+                                annotation.range.value,
+                                &*state.analysis.file_line_index(annotation.range.file_id)?,
+                            ),
                         },
-                        tags: None,
-                        data: None,
+                        message: annotation.message,
                     });
                 }
-                lsp_diagnostics
-            };
-
-            sender
-                .send(Task::Notify(lsp_server::Notification {
-                    method: PublishDiagnostics::METHOD.to_owned(),
-                    params: to_json(PublishDiagnosticsParams {
-                        uri,
-                        diagnostics,
-                        version: None,
-                    })
-                    .unwrap(),
-                }))
-                .unwrap();
-        }
+                if annotations.is_empty() {
+                    None
+                } else {
+                    Some(annotations)
+                }
+            },
+            tags: None,
+            data: None,
+        });
     }
-    Ok(())
+    Ok(lsp_diagnostics)
+}
+
+/// Sends the diagnostics of a single file to the client
+fn publish_diagnostics(sender: &Sender<Task>, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) {
+    sender
+        .send(Task::Notify(lsp_server::Notification {
+            method: PublishDiagnostics::METHOD.to_owned(),
+            params: to_json(PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            })
+            .unwrap(),
+        }))
+        .unwrap();
 }
 
 impl LanguageServerState {
@@ -322,6 +412,18 @@ impl LanguageServerState {
             vfs: self.vfs.clone(),
             analysis: self.analysis.snapshot(),
             packages: self.packages.clone(),
+            diagnostics_cache: self.diagnostics_cache.clone(),
+        }
+    }
+
+    /// Waits for outstanding diagnostics to be computed and persists them, if
+    /// persistence is enabled.
+    pub fn save_diagnostics_cache(&mut self) {
+        if let Some(cache) = &self.diagnostics_cache {
+            self.thread_pool.join();
+            if let Err(err) = cache.lock().save() {
+                log::error!("could not persist diagnostics: {err}");
+            }
         }
     }
 
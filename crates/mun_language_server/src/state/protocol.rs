@@ -4,9 +4,12 @@ use dispatcher::{NotificationDispatcher, RequestDispatcher};
 use lsp_types::notification::{
     DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
 };
+use mun_paths::AbsPath;
 
 use super::LanguageServerState;
-use crate::{from_lsp, handlers, lsp_utils::apply_document_changes, state::RequestHandler};
+use crate::{
+    from_lsp, handlers, lsp_ext, lsp_utils::apply_document_changes, state::RequestHandler,
+};
 
 pub mod dispatcher;
 
@@ -18,6 +21,7 @@ impl LanguageServerState {
     ) -> anyhow::Result<()> {
         let path = from_lsp::abs_path(&params.text_document.uri)?;
         self.open_docs.insert(path.clone());
+        set_open_file_context(&path, params.text_document.text.as_bytes());
         self.vfs
             .write()
             .set_file_contents(&path, Some(params.text_document.text.into_bytes()));
@@ -43,6 +47,7 @@ impl LanguageServerState {
             .and_then(|contents| String::from_utf8(contents.to_vec()).ok())
             .expect("if the file_id exists it must be valid utf8");
         apply_document_changes(&mut text, content_changes);
+        set_open_file_context(&path, text.as_bytes());
         vfs.set_file_contents(&path, Some(text.into_bytes()));
         Ok(())
     }
@@ -77,6 +82,10 @@ impl LanguageServerState {
         request_received: Instant,
     ) -> anyhow::Result<()> {
         self.register_request(&request, request_received);
+        mun_crash_report::set_context(
+            "last request",
+            format!("{} (id {})", request.method, request.id),
+        );
 
         // If a shutdown was requested earlier, immediately respond with an error
         if self.shutdown_requested {
@@ -92,10 +101,13 @@ impl LanguageServerState {
         RequestDispatcher::new(self, request)
             .on_sync::<lsp_types::request::Shutdown>(|state, _request| {
                 state.shutdown_requested = true;
+                state.save_diagnostics_cache();
                 Ok(())
             })?
             .on::<lsp_types::request::DocumentSymbolRequest>(handlers::handle_document_symbol)?
             .on::<lsp_types::request::Completion>(handlers::handle_completion)?
+            .on::<lsp_types::request::HoverRequest>(handlers::handle_hover)?
+            .on::<lsp_types::request::CodeActionRequest>(handlers::handle_code_action)?
             .finish();
 
         Ok(())
@@ -106,6 +118,7 @@ impl LanguageServerState {
         &mut self,
         notification: lsp_server::Notification,
     ) -> anyhow::Result<()> {
+        mun_crash_report::set_context("last notification", notification.method.clone());
         NotificationDispatcher::new(self, notification)
             .on::<DidOpenTextDocument>(LanguageServerState::on_did_open_text_document)?
             .on::<DidChangeTextDocument>(LanguageServerState::on_did_change_text_document)?
@@ -160,12 +173,17 @@ impl LanguageServerState {
     }
 
     /// Sends a response to the client. This method logs the time it took us to
-    /// reply to a request from the client.
+    /// reply to a request from the client. After recovering from a panic, the
+    /// first successful response restores the reported health of the server.
     pub(super) fn respond(&mut self, response: lsp_server::Response) {
         if let Some((_method, start)) = self.request_queue.incoming.complete(&response.id) {
             let duration = start.elapsed();
             log::info!("handled req#{} in {:?}", response.id, duration);
+            let succeeded = response.error.is_none();
             self.send(response.into());
+            if succeeded && self.health == lsp_ext::Health::Warning {
+                self.send_server_status(lsp_ext::Health::Ok, None);
+            }
         }
     }
 
@@ -176,3 +194,16 @@ impl LanguageServerState {
             .expect("error sending lsp message to the outgoing channel");
     }
 }
+
+/// Records the last opened or changed document for crash reports. Only a hash
+/// of its contents is recorded.
+fn set_open_file_context(path: &AbsPath, contents: &[u8]) {
+    mun_crash_report::set_context(
+        "open file",
+        format!(
+            "{} (hash {})",
+            path.display(),
+            mun_crash_report::content_hash(contents)
+        ),
+    );
+}
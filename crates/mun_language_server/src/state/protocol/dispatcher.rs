@@ -5,6 +5,7 @@ use super::LanguageServerState;
 use crate::{
     cancelation::is_canceled,
     from_json,
+    panic_handler::{self, PanicReport},
     state::{LanguageServerSnapshot, Task},
 };
 
@@ -39,13 +40,19 @@ impl<'a> RequestDispatcher<'a> {
             None => return Ok(self),
         };
 
-        let result = compute_response_fn(self.state, params);
+        let result = panic_handler::catch_unwind(|| compute_response_fn(self.state, params))
+            .and_then(|result| result);
+        if let Some(report) = panic_report(&result) {
+            self.state.on_panic(R::METHOD, report.clone());
+        }
         let response = result_to_response::<R>(id, result);
         self.state.respond(response);
         Ok(self)
     }
 
     /// Try to dispatch the event as the given Request type on the thread pool.
+    /// If the handler panics, the client receives an error response and the
+    /// panic is reported to the main loop.
     pub fn on<R>(
         &mut self,
         compute_response_fn: fn(LanguageServerSnapshot, R::Params) -> anyhow::Result<R::Result>,
@@ -65,7 +72,13 @@ impl<'a> RequestDispatcher<'a> {
             let sender = self.state.task_sender.clone();
 
             move || {
-                let result = compute_response_fn(snapshot, params);
+                let result = panic_handler::catch_unwind(|| compute_response_fn(snapshot, params))
+                    .and_then(|result| result);
+                if let Some(report) = panic_report(&result) {
+                    sender
+                        .send(Task::Panicked(R::METHOD, report.clone()))
+                        .unwrap();
+                }
                 sender
                     .send(Task::Response(result_to_response::<R>(id, result)))
                     .unwrap();
@@ -171,6 +184,15 @@ impl<'a> NotificationDispatcher<'a> {
     }
 }
 
+/// Returns the [`PanicReport`] if the specified result is the result of a
+/// panic.
+fn panic_report<T>(result: &anyhow::Result<T>) -> Option<&PanicReport> {
+    result
+        .as_ref()
+        .err()
+        .and_then(|err| err.downcast_ref::<PanicReport>())
+}
+
 /// Converts the specified results of an LSP request into an LSP response
 /// handling any errors that may have occurred.
 fn result_to_response<R>(
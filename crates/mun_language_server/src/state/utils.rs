@@ -1,4 +1,5 @@
 use super::LanguageServerState;
+use crate::lsp_ext;
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Progress {
@@ -25,6 +26,17 @@ impl LanguageServerState {
         );
     }
 
+    /// Informs the client about the health of the server, if the client
+    /// opted-in to receiving these notifications.
+    pub(crate) fn send_server_status(&mut self, health: lsp_ext::Health, message: Option<String>) {
+        self.health = health;
+        if self.config.server_status_notification {
+            self.send_notification::<lsp_ext::ServerStatusNotification>(
+                lsp_ext::ServerStatusParams { health, message },
+            );
+        }
+    }
+
     /// Reports progress to the user via the `WorkDoneProgress` protocol.
     pub(crate) fn report_progress(
         &mut self,
@@ -6,6 +6,7 @@ use std::{
 use lsp_types::Url;
 use mun_hir_input::{FileId, LineIndex};
 use mun_syntax::{TextRange, TextSize};
+use ra_ap_text_edit::Indel;
 
 use crate::{
     completion::{CompletionItem, CompletionItemKind},
@@ -62,6 +63,13 @@ pub(crate) fn range(range: TextRange, line_index: &LineIndex) -> lsp_types::Rang
     }
 }
 
+pub(crate) fn text_edit(indel: Indel, line_index: &LineIndex) -> lsp_types::TextEdit {
+    lsp_types::TextEdit {
+        range: range(indel.delete, line_index),
+        new_text: indel.insert,
+    }
+}
+
 pub(crate) fn position(range: TextSize, line_index: &LineIndex) -> lsp_types::Position {
     let line_col = line_index.line_col(range);
     lsp_types::Position {
@@ -73,11 +81,14 @@ pub(crate) fn position(range: TextSize, line_index: &LineIndex) -> lsp_types::Po
 /// Converts a symbol kind from this crate to one for the LSP protocol.
 pub(crate) fn symbol_kind(symbol_kind: SymbolKind) -> lsp_types::SymbolKind {
     match symbol_kind {
+        SymbolKind::Const => lsp_types::SymbolKind::CONSTANT,
         SymbolKind::Function => lsp_types::SymbolKind::FUNCTION,
         SymbolKind::Struct => lsp_types::SymbolKind::STRUCT,
         SymbolKind::TypeAlias | SymbolKind::SelfType => lsp_types::SymbolKind::TYPE_PARAMETER,
         SymbolKind::Field => lsp_types::SymbolKind::FIELD,
-        SymbolKind::Local | SymbolKind::SelfParam => lsp_types::SymbolKind::VARIABLE,
+        SymbolKind::Local | SymbolKind::SelfParam | SymbolKind::Static => {
+            lsp_types::SymbolKind::VARIABLE
+        }
         SymbolKind::Module => lsp_types::SymbolKind::MODULE,
         SymbolKind::Method => lsp_types::SymbolKind::METHOD,
         SymbolKind::Impl => lsp_types::SymbolKind::OBJECT,
@@ -113,9 +124,10 @@ pub(crate) fn completion_item_kind(
         CompletionItemKind::Snippet => lsp_types::CompletionItemKind::SNIPPET,
         CompletionItemKind::UnresolvedReference => lsp_types::CompletionItemKind::REFERENCE,
         CompletionItemKind::SymbolKind(symbol) => match symbol {
+            SymbolKind::Const => lsp_types::CompletionItemKind::CONSTANT,
             SymbolKind::Field => lsp_types::CompletionItemKind::FIELD,
             SymbolKind::Function => lsp_types::CompletionItemKind::FUNCTION,
-            SymbolKind::Local => lsp_types::CompletionItemKind::VARIABLE,
+            SymbolKind::Local | SymbolKind::Static => lsp_types::CompletionItemKind::VARIABLE,
             SymbolKind::Module => lsp_types::CompletionItemKind::MODULE,
             SymbolKind::SelfParam => lsp_types::CompletionItemKind::VALUE,
             SymbolKind::SelfType => lsp_types::CompletionItemKind::TYPE_PARAMETER,
@@ -0,0 +1,56 @@
+use lsp_types::{
+    CodeActionContext, CodeActionOrCommand, PartialResultParams, Position, Range,
+    WorkDoneProgressParams,
+};
+
+use crate::Project;
+
+#[test]
+fn test_mutability_quick_fixes() {
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name = "foo"
+    version = "0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 {
+        let a = 5;
+        a = 6;
+        let mut b = 7;
+        a + b
+    }
+    "#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let actions = server
+        .send_request::<lsp_types::request::CodeActionRequest>(lsp_types::CodeActionParams {
+            text_document: server.doc_id("src/mod.mun"),
+            range: Range::new(Position::new(0, 0), Position::new(5, 0)),
+            context: CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .unwrap_or_default();
+
+    insta::assert_debug_snapshot!(actions
+        .iter()
+        .map(|action| {
+            let CodeActionOrCommand::CodeAction(action) = action else {
+                panic!("expected a code action");
+            };
+            let edits = action
+                .edit
+                .as_ref()
+                .and_then(|edit| edit.changes.as_ref())
+                .into_iter()
+                .flat_map(|changes| changes.values().flatten())
+                .map(|edit| (edit.range, edit.new_text.as_str()))
+                .collect::<Vec<_>>();
+            (action.title.as_str(), edits)
+        })
+        .collect::<Vec<_>>());
+}
@@ -0,0 +1,61 @@
+use itertools::Itertools;
+use lsp_types::{
+    CompletionParams, CompletionResponse, PartialResultParams, WorkDoneProgressParams,
+};
+
+use crate::Project;
+
+#[test]
+fn test_completion() {
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name = "foo"
+    version = "0.0.0"
+
+    //- /src/mod.mun
+    struct Foo {
+        bar: i32,
+        baz: f64,
+    }
+
+    fn main(foo: Foo) -> i32 {
+        foo.$0
+    }
+    "#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let completions = server.send_request::<lsp_types::request::Completion>(CompletionParams {
+        text_document_position: server.cursor_position(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        context: None,
+    });
+
+    insta::assert_snapshot!(format_completion_response(completions));
+}
+
+fn format_completion_response(response: Option<CompletionResponse>) -> String {
+    let items = match response {
+        None => return "received empty response".to_string(),
+        Some(CompletionResponse::Array(items)) => items,
+        Some(CompletionResponse::List(list)) => list.items,
+    };
+
+    format!(
+        "{}",
+        items
+            .iter()
+            .map(|item| format!(
+                "{}{}",
+                item.label,
+                item.detail
+                    .as_ref()
+                    .map_or_else(String::new, |s| format!(" ({s})"))
+            ))
+            .format("\n")
+    )
+}
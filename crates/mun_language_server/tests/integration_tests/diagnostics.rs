@@ -0,0 +1,75 @@
+use lsp_types::notification::PublishDiagnostics;
+
+use crate::Project;
+
+#[test]
+fn test_diagnostics_after_change() {
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name = "foo"
+    version = "0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 {
+        5
+    }
+    "#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let uri = server.doc_id("src/mod.mun").uri;
+
+    // The initial version of the file does not contain any errors
+    let params = server.wait_for_notification::<PublishDiagnostics>(|params| params.uri == uri);
+    assert!(params.diagnostics.is_empty());
+
+    // Introduce a type error in the open document
+    server.open_document("src/mod.mun");
+    server.change_document("src/mod.mun", 1, "pub fn main() -> i32 {\n    true\n}\n");
+
+    let params = server.wait_for_notification::<PublishDiagnostics>(|params| {
+        params.uri == uri && !params.diagnostics.is_empty()
+    });
+    insta::assert_debug_snapshot!(params
+        .diagnostics
+        .iter()
+        .map(|d| (d.range, d.message.as_str()))
+        .collect::<Vec<_>>());
+}
+
+#[test]
+fn test_diagnostics_are_persisted() {
+    let cache_dir = tempdir::TempDir::new("cache").unwrap();
+    let cache_path = cache_dir.path().to_path_buf();
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name = "foo"
+    version = "0.0.0"
+
+    //- /src/mod.mun
+    fn main() -> i32 {
+        true
+    }
+    "#,
+    )
+    .with_config(move |config| config.diagnostics_cache_dir = Some(cache_path))
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let uri = server.doc_id("src/mod.mun").uri;
+    server.wait_for_notification::<PublishDiagnostics>(|params| {
+        params.uri == uri && !params.diagnostics.is_empty()
+    });
+
+    // The cache is written when the server shuts down
+    drop(server);
+
+    let cache = std::fs::read_to_string(cache_dir.path().join("diagnostics.json")).unwrap();
+    assert!(cache.contains(uri.as_str()));
+    assert!(cache.contains("expected `i32`, found `bool`"));
+}
@@ -1,3 +1,9 @@
+use lsp_types::{
+    request::HoverRequest, HoverParams, Position, TextDocumentPositionParams,
+    WorkDoneProgressParams,
+};
+use mun_language_server::lsp_ext::{Health, ServerStatusNotification};
+
 use crate::Project;
 
 #[test]
@@ -18,3 +24,63 @@ fn add(a: i32, b: i32) -> i32 {
     .server()
     .wait_until_workspace_is_loaded();
 }
+
+#[test]
+fn test_server_status() {
+    let server = Project::with_fixture(
+        r#"
+//- /mun.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/mod.mun
+fn main() {}
+"#,
+    )
+    .with_config(|config| config.server_status_notification = true)
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let status = server.wait_for_notification::<ServerStatusNotification>(|_| true);
+    assert_eq!(status.health, Health::Ok);
+}
+
+#[test]
+fn test_panicking_request() {
+    let server = Project::with_fixture(
+        r#"
+//- /mun.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/mod.mun
+fn main() {}
+"#,
+    )
+    .with_config(|config| config.server_status_notification = true)
+    .server()
+    .wait_until_workspace_is_loaded();
+    server.open_document("src/mod.mun");
+
+    let hover_params = |line| HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: server.doc_id("src/mod.mun"),
+            position: Position::new(line, 0),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+
+    // Hovering beyond the end of the file panics, which is reported as an error
+    let error = server.send_request_for_error::<HoverRequest>(hover_params(100));
+    assert!(error.message.contains("panicked"), "{}", error.message);
+    let status = server.wait_for_notification::<ServerStatusNotification>(|status| {
+        status.health == Health::Warning
+    });
+    assert!(status.message.unwrap().contains("textDocument/hover"));
+
+    // The server keeps working and reports that it recovered
+    server.send_request::<HoverRequest>(hover_params(0));
+    server.wait_for_notification::<ServerStatusNotification>(|status| status.health == Health::Ok);
+}
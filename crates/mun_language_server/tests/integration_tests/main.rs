@@ -1,3 +1,6 @@
+mod code_action;
+mod completion;
+mod diagnostics;
 mod document_symbols;
 mod initialization;
 mod support;
@@ -6,35 +6,59 @@ use std::{
 };
 
 use crossbeam_channel::{after, select};
-use lsp_server::{Connection, Message, Notification, Request};
+use lsp_server::{Connection, Message, Notification, Request, Response, ResponseError};
 use lsp_types::{
-    notification::Exit, request::Shutdown, ProgressParams, ProgressParamsValue, Url,
+    notification::{DidChangeTextDocument, DidOpenTextDocument, Exit},
+    request::Shutdown,
+    Position, ProgressParams, ProgressParamsValue, TextDocumentContentChangeEvent,
+    TextDocumentItem, TextDocumentPositionParams, Url, VersionedTextDocumentIdentifier,
     WorkDoneProgress,
 };
 use mun_hir_input::Fixture;
 use mun_language_server::{main_loop, Config, FilesWatcher};
 use mun_paths::AbsPathBuf;
 use mun_project::ProjectManifest;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 
+/// The marker that can be used in a fixture to indicate the position of the
+/// cursor.
+pub const CURSOR_MARKER: &str = "$0";
+
+/// A function that modifies the configuration of the language server.
+type ConfigureFn<'a> = Box<dyn FnOnce(&mut Config) + 'a>;
+
 /// A `Project` represents a project that a language server can work with. Call
 /// the [`server`] method to instantiate a language server that will serve
 /// information about the project.
+///
+/// A single file in the fixture may contain a `$0` marker to indicate the
+/// position of the cursor. The marker is removed from the file before it is
+/// written to disk, its position can be retrieved with
+/// [`Server::cursor_position`].
 pub struct Project<'a> {
     fixture: &'a str,
     tmp_dir: Option<tempdir::TempDir>,
+    configure: Option<ConfigureFn<'a>>,
 }
 
-impl Project<'_> {
+impl<'a> Project<'a> {
     /// Constructs a project from a fixture.
-    pub fn with_fixture(fixture: &str) -> Project<'_> {
+    pub fn with_fixture(fixture: &'a str) -> Project<'a> {
         Project {
             fixture,
             tmp_dir: None,
+            configure: None,
         }
     }
 
+    /// Allows modifying the configuration with which the language server is
+    /// started.
+    pub fn with_config(mut self, configure: impl FnOnce(&mut Config) + 'a) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+
     /// Instantiates a language server for this project.
     pub fn server(self) -> Server {
         // Get or create a temporary directory
@@ -43,10 +67,18 @@ impl Project<'_> {
             .unwrap_or_else(|| tempdir::TempDir::new("testdir").unwrap());
 
         // Write all fixtures to a folder
+        let mut cursor = None;
         for entry in Fixture::parse(self.fixture) {
             let path = entry.relative_path.to_path(tmp_dir.path());
+            let text = if let Some((position, text)) = extract_cursor_position(&entry.text) {
+                assert!(cursor.is_none(), "cannot have multiple cursor markers");
+                cursor = Some((entry.relative_path.as_str().to_owned(), position));
+                text
+            } else {
+                entry.text
+            };
             fs::create_dir_all(path.parent().unwrap()).unwrap();
-            fs::write(path.as_path(), entry.text.as_bytes()).unwrap();
+            fs::write(path.as_path(), text.as_bytes()).unwrap();
         }
 
         let tmp_dir_path: AbsPathBuf = tmp_dir
@@ -59,31 +91,55 @@ impl Project<'_> {
         let discovered_projects = ProjectManifest::discover_all(roots.into_iter());
 
         // Construct a default configuration for the server
-        let config = Config {
+        let mut config = Config {
             discovered_projects: Some(discovered_projects),
             watcher: FilesWatcher::Client,
             ..Config::new(tmp_dir_path)
         };
+        if let Some(configure) = self.configure {
+            configure(&mut config);
+        }
 
-        // TODO: Provide the ability to modify the configuration externally
-
-        Server::new(tmp_dir, config)
+        Server::new(tmp_dir, config, cursor)
     }
 }
 
+/// Returns the position of the first occurrence of the `$0` marker and a copy
+/// of `text` without the marker.
+fn extract_cursor_position(text: &str) -> Option<(Position, String)> {
+    let offset = text.find(CURSOR_MARKER)?;
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let character = before[line_start..].encode_utf16().count();
+    let text = format!("{before}{}", &text[offset + CURSOR_MARKER.len()..]);
+    Some((
+        Position::new(line.try_into().unwrap(), character.try_into().unwrap()),
+        text,
+    ))
+}
+
 /// An object that runs the language server main loop and enables sending and
 /// receiving messages to and from it.
 pub struct Server {
     next_request_id: Cell<i32>,
     messages: RefCell<Vec<Message>>,
+    /// The index of the first message in `messages` that has not yet been
+    /// inspected by [`Server::wait_for_notification`]
+    next_notification: Cell<usize>,
     worker: Option<std::thread::JoinHandle<()>>,
     client: Connection,
     tmp_dir: tempdir::TempDir,
+    cursor: Option<(String, Position)>,
 }
 
 impl Server {
     /// Constructs and initializes a new `Server`
-    pub fn new(tmp_dir: tempdir::TempDir, config: Config) -> Self {
+    pub fn new(
+        tmp_dir: tempdir::TempDir,
+        config: Config,
+        cursor: Option<(String, Position)>,
+    ) -> Self {
         let (connection, client) = Connection::memory();
 
         let worker = std::thread::spawn(move || {
@@ -93,9 +149,11 @@ impl Server {
         Self {
             next_request_id: Cell::new(1),
             messages: RefCell::new(Vec::new()),
+            next_notification: Cell::new(0),
             worker: Some(worker),
             client,
             tmp_dir,
+            cursor,
         }
     }
 
@@ -107,6 +165,79 @@ impl Server {
         }
     }
 
+    /// Returns the position of the `$0` marker in the fixture.
+    pub fn cursor_position(&self) -> TextDocumentPositionParams {
+        let (path, position) = self
+            .cursor
+            .as_ref()
+            .expect("the fixture does not contain a cursor marker");
+        TextDocumentPositionParams {
+            text_document: self.doc_id(path),
+            position: *position,
+        }
+    }
+
+    /// Notifies the server that the document at the given path was opened by
+    /// the client. The contents of the document are read from disk.
+    pub fn open_document(&self, rel_path: &str) {
+        let text = fs::read_to_string(self.tmp_dir.path().join(rel_path)).unwrap();
+        self.notification::<DidOpenTextDocument>(lsp_types::DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: self.doc_id(rel_path).uri,
+                language_id: "mun".to_owned(),
+                version: 0,
+                text,
+            },
+        });
+    }
+
+    /// Notifies the server that the entire contents of the open document at
+    /// the given path were replaced by `text`.
+    pub fn change_document(&self, rel_path: &str, version: i32, text: &str) {
+        self.notification::<DidChangeTextDocument>(lsp_types::DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: self.doc_id(rel_path).uri,
+                version,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: text.to_owned(),
+            }],
+        });
+    }
+
+    /// Waits until a notification of type `N` is received for which `cond`
+    /// returns `true` and returns its parameters. Notifications that were
+    /// received before but have not yet been inspected by this method are
+    /// considered as well.
+    pub fn wait_for_notification<N: lsp_types::notification::Notification>(
+        &self,
+        cond: impl Fn(&N::Params) -> bool,
+    ) -> N::Params
+    where
+        N::Params: DeserializeOwned,
+    {
+        let matches = |msg: &Message| match msg {
+            Message::Notification(n) if n.method == N::METHOD => {
+                let params = serde_json::from_value::<N::Params>(n.params.clone()).unwrap();
+                cond(&params).then_some(params)
+            }
+            _ => None,
+        };
+
+        loop {
+            let next = self.next_notification.get();
+            if next >= self.messages.borrow().len() {
+                self.recv().expect("no notification");
+            }
+            self.next_notification.set(next + 1);
+            if let Some(params) = matches(&self.messages.borrow()[next]) {
+                return params;
+            }
+        }
+    }
+
     /// Waits until all projects in the workspace have been loaded
     pub fn wait_until_workspace_is_loaded(self) -> Server {
         self.wait_for_message_cond(1, &|msg: &Message| match msg {
@@ -159,18 +290,42 @@ impl Server {
 
     /// Sends a request to main loop, returning the response
     fn send_request_for_value<R: lsp_types::request::Request>(&self, params: R::Params) -> Value
+    where
+        R::Params: Serialize,
+    {
+        let response = self.send_and_receive(self.new_request::<R>(params));
+        if let Some(err) = response.error {
+            panic!("received error response as a response to a request: {err:#?}");
+        }
+        response.result.unwrap()
+    }
+
+    /// Sends a request to the language server that is expected to fail,
+    /// returning the error of the response
+    pub fn send_request_for_error<R: lsp_types::request::Request>(
+        &self,
+        params: R::Params,
+    ) -> ResponseError
+    where
+        R::Params: Serialize,
+    {
+        self.send_and_receive(self.new_request::<R>(params))
+            .error
+            .expect("expected an error response")
+    }
+
+    /// Constructs a request with a new id
+    fn new_request<R: lsp_types::request::Request>(&self, params: R::Params) -> Request
     where
         R::Params: Serialize,
     {
         let id = self.next_request_id.get();
         self.next_request_id.set(id.wrapping_add(1));
-
-        let r = Request::new(id.into(), R::METHOD.to_string(), params);
-        self.send_and_receive(r)
+        Request::new(id.into(), R::METHOD.to_string(), params)
     }
 
     /// Sends an LSP notification to the main loop.
-    fn notification<N: lsp_types::notification::Notification>(&self, params: N::Params)
+    pub fn notification<N: lsp_types::notification::Notification>(&self, params: N::Params)
     where
         N::Params: Serialize,
     {
@@ -184,7 +339,7 @@ impl Server {
     }
 
     /// Sends a request to the main loop and receives its response
-    fn send_and_receive(&self, r: Request) -> Value {
+    fn send_and_receive(&self, r: Request) -> Response {
         let id = r.id.clone();
         self.client.sender.send(r.into()).unwrap();
         while let Some(msg) = self.recv() {
@@ -195,10 +350,7 @@ impl Server {
                 Message::Notification(_) => (),
                 Message::Response(res) => {
                     assert_eq!(res.id, id);
-                    if let Some(err) = res.error {
-                        panic!("received error response as a response to a request: {err:#?}");
-                    }
-                    return res.result.unwrap();
+                    return res;
                 }
             }
         }
@@ -40,8 +40,20 @@ impl MunLibrary {
     pub unsafe fn new(library_path: &Path) -> Result<Self, InitError> {
         // Although loading a library is technically unsafe, we assume here that this is
         // not the case for munlibs.
-        let library = TempLibrary::new(library_path)?;
+        Self::from_temp_library(TempLibrary::new(library_path)?)
+    }
+
+    /// Loads a munlib library from the contents of a munlib in memory.
+    ///
+    /// # Safety
+    ///
+    /// See [`MunLibrary::new`] for more information.
+    pub unsafe fn from_bytes(bytes: &[u8]) -> Result<Self, InitError> {
+        Self::from_temp_library(TempLibrary::from_bytes(bytes)?)
+    }
 
+    /// Verifies that the loaded `library` is a munlib.
+    unsafe fn from_temp_library(library: TempLibrary) -> Result<Self, InitError> {
         // Verify that the `*.munlib` contains all required functions. Note that this is
         // an unsafe operation because the loaded symbols don't actually contain
         // type information. Casting is therefore unsafe.
@@ -1,4 +1,4 @@
-use std::{fs, io, path::Path};
+use std::{fs, io, io::Write, path::Path};
 
 use libloading::Library;
 
@@ -9,6 +9,8 @@ pub enum InitError {
     CreateTempFile(io::Error),
     #[error("Failed to copy shared library: {0}.")]
     CopyLibrary(io::Error),
+    #[error("Failed to write shared library: {0}.")]
+    WriteLibrary(io::Error),
     #[error("Failed to load temp shared library: {0}")]
     LoadTempLibrary(#[from] libloading::Error),
 }
@@ -60,6 +62,28 @@ impl TempLibrary {
         })
     }
 
+    /// Loads a dynamic library from the contents of a library file in memory,
+    /// e.g. a library that was read from an asset pack or downloaded over the
+    /// network. Operating systems can only load libraries from files, so
+    /// `bytes` are written to a temporary file first.
+    ///
+    /// # Safety
+    ///
+    /// See [`TempLibrary::new`] for more information.
+    pub unsafe fn from_bytes(bytes: &[u8]) -> Result<Self, InitError> {
+        let mut tmp_file = tempfile::NamedTempFile::new().map_err(InitError::CreateTempFile)?;
+        tmp_file
+            .write_all(bytes)
+            .and_then(|()| tmp_file.flush())
+            .map_err(InitError::WriteLibrary)?;
+        let tmp_path = tmp_file.into_temp_path();
+        let library = Library::new(&tmp_path)?;
+        Ok(TempLibrary {
+            _tmp_path: tmp_path,
+            library,
+        })
+    }
+
     /// Returns the loaded library
     pub fn library(&self) -> &Library {
         &self.library
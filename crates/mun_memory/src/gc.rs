@@ -1,9 +1,16 @@
 mod array;
+mod heap;
 mod mark_sweep;
 mod ptr;
 mod root_ptr;
 
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{
+    backtrace::Backtrace,
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 pub use mark_sweep::MarkSweep;
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
@@ -15,6 +22,87 @@ use crate::r#type::Type;
 #[derive(Debug, Clone, Default)]
 pub struct Stats {
     pub allocated_memory: usize,
+
+    /// Stats about the size classes from which small objects are allocated
+    pub size_classes: Vec<SizeClassStats>,
+
+    /// Stats about the space in which large objects are allocated
+    pub large_objects: LargeObjectStats,
+}
+
+/// Contains stats about a size class of the GC heap. A size class allocates
+/// blocks of a fixed size from chunks of memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeClassStats {
+    /// The size of a block in bytes
+    pub block_size: usize,
+
+    /// The number of blocks that are in use
+    pub allocated_blocks: usize,
+
+    /// The amount of memory that was reserved for blocks
+    pub reserved_memory: usize,
+}
+
+/// Contains stats about the large object space of the GC heap. Objects that are
+/// too large for any size class are allocated in the large object space.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LargeObjectStats {
+    /// The number of large objects
+    pub allocated_objects: usize,
+
+    /// The amount of memory used by large objects
+    pub allocated_memory: usize,
+}
+
+/// Contains stats about a single garbage collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionStats {
+    /// The time at which the collection started
+    pub started_at: Instant,
+
+    /// The duration for which the collection paused the program
+    pub pause: Duration,
+
+    /// The time between the end of the previous collection, or the creation
+    /// of the collector, and the start of this collection
+    pub interval: Duration,
+
+    /// The amount of memory that was allocated during the `interval`
+    pub allocated_memory: usize,
+
+    /// The amount of memory used by the objects that survived the collection
+    pub surviving_memory: usize,
+
+    /// The amount of memory that was reclaimed by the collection
+    pub reclaimed_memory: usize,
+}
+
+impl CollectionStats {
+    /// Returns the number of bytes per second that were allocated between the
+    /// previous collection and this collection.
+    pub fn allocation_rate(&self) -> f64 {
+        if self.interval.is_zero() {
+            0.0
+        } else {
+            self.allocated_memory as f64 / self.interval.as_secs_f64()
+        }
+    }
+}
+
+/// Describes an object that is still rooted, e.g. by a `GcRootPtr` that was
+/// never dropped.
+#[derive(Debug, Clone)]
+pub struct RootedObject {
+    /// The type of the object
+    pub ty: Type,
+
+    /// The number of roots that keep the object alive
+    pub roots: u32,
+
+    /// The backtrace of the allocation of the object. Only captured if the
+    /// `gc-tracing` feature is enabled.
+    pub backtrace: Option<Arc<Backtrace>>,
 }
 
 /// A trait used to trace an object type.
@@ -75,6 +163,17 @@ pub trait GcRuntime: Send + Sync {
     /// `root` was called before the object can be collected.
     fn unroot(&self, obj: GcPtr);
 
+    /// Pins the specified `obj`, which prevents the memory of the object from
+    /// being relocated when the heap is compacted. Pinning an object does
+    /// not keep it alive. An object can be pinned multiple times, but you
+    /// must make sure to call `unpin` an equal number of times before the
+    /// object can be relocated.
+    fn pin(&self, obj: GcPtr);
+
+    /// Unpins the specified `obj`, potentially allowing its memory to be
+    /// relocated when the heap is compacted.
+    fn unpin(&self, obj: GcPtr);
+
     /// Returns stats about the current state of the runtime.
     fn stats(&self) -> Stats;
 }
@@ -100,6 +199,9 @@ pub enum Event {
     /// A deallocation took place
     Deallocation(GcPtr),
 
+    /// The memory of an object was relocated by compaction
+    Relocation(GcPtr),
+
     /// A GC cycle ended
     End,
 }
@@ -0,0 +1,727 @@
+use std::{
+    alloc::Layout,
+    cell::RefCell,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
+
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+
+use crate::gc::{LargeObjectStats, SizeClassStats};
+
+/// The block sizes of the size classes. Allocations that are larger than the
+/// largest size class are stored in the large object space.
+const SIZE_CLASSES: [usize; 8] = [16, 32, 64, 128, 256, 512, 1024, 2048];
+
+/// The size of a chunk of memory from which the blocks of a size class are
+/// allocated.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The alignment of a chunk. As every block size is a power of two that
+/// divides this alignment, every block is aligned to its own size.
+const CHUNK_ALIGN: usize = SIZE_CLASSES[SIZE_CLASSES.len() - 1];
+
+/// The amount of memory that a thread-local allocation buffer takes from a size
+/// class at once.
+const TLAB_SIZE: usize = 4 * 1024;
+
+/// The byte pattern with which freed memory is overwritten if the `gc-debug`
+/// feature is enabled, to make reads of freed memory recognizable.
+#[cfg(feature = "gc-debug")]
+pub(crate) const POISON: u8 = 0xdd;
+
+thread_local! {
+    /// The thread-local allocation buffers of the current thread, keyed by the
+    /// address of the state they were taken from.
+    static TLABS: RefCell<FxHashMap<usize, Tlab>> = RefCell::new(FxHashMap::default());
+}
+
+/// A heap that segregates allocations by size.
+///
+/// Small allocations are served by size classes. Each size class bump
+/// allocates fixed-size blocks from large chunks of memory and reuses freed
+/// blocks through a free list. Large allocations bypass the size classes and
+/// are directly allocated in the large object space, so they don't fragment
+/// the chunks.
+///
+/// Every thread allocates small objects from its own thread-local allocation
+/// buffer (TLAB), which caches blocks of each size class. Only when a TLAB runs
+/// empty or overflows, the heap is locked to exchange a batch of blocks, so
+/// threads that allocate in parallel don't serialize on a global lock.
+///
+/// [`Heap::compact`] moves blocks to the lowest free blocks of their size
+/// class and returns chunks that no longer contain any blocks to the system.
+pub(crate) struct Heap {
+    shared: Arc<SharedHeap>,
+}
+
+/// The state of a `Heap` that is shared with the TLABs of all threads.
+struct SharedHeap {
+    inner: Mutex<HeapInner>,
+
+    /// The number of blocks of each size class that are in use, excluding the
+    /// blocks that are cached by TLABs
+    allocated_blocks: [AtomicUsize; SIZE_CLASSES.len()],
+
+    /// Incremented by every compaction. TLABs that were filled before the last
+    /// compaction discard their blocks, because compaction rebuilds the free
+    /// lists from scratch and may have released the chunks of their blocks.
+    generation: AtomicUsize,
+}
+
+struct HeapInner {
+    size_classes: Vec<SizeClass>,
+    large_objects: LargeObjectStats,
+}
+
+/// A `Heap` is thread-safe, because all access to its memory is synchronized.
+unsafe impl Send for HeapInner {}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Heap {
+            shared: Arc::new(SharedHeap {
+                inner: Mutex::new(HeapInner {
+                    size_classes: SIZE_CLASSES.iter().copied().map(SizeClass::new).collect(),
+                    large_objects: LargeObjectStats::default(),
+                }),
+                allocated_blocks: Default::default(),
+                generation: AtomicUsize::new(0),
+            }),
+        }
+    }
+}
+
+/// A block that was allocated from a size class, as passed to
+/// [`Heap::compact`].
+pub(crate) struct LiveBlock {
+    /// The address of the block, which is updated if the block is moved
+    pub ptr: NonNull<u8>,
+
+    /// The layout with which the block was allocated
+    pub layout: Layout,
+
+    /// Whether the block must remain at its address
+    pub pinned: bool,
+}
+
+/// Returns the index of the size class that stores allocations with the
+/// specified `layout`, or `None` if the allocation belongs in the large
+/// object space. Over-aligned allocations are stored in a size class whose
+/// blocks are at least as large as their alignment.
+fn size_class_index(layout: Layout) -> Option<usize> {
+    SIZE_CLASSES
+        .iter()
+        .position(|&block_size| block_size >= layout.size() && block_size >= layout.align())
+}
+
+impl Heap {
+    /// Allocates memory with the specified `layout`. The contents of the memory
+    /// are undefined.
+    pub fn alloc(&self, layout: Layout) -> NonNull<u8> {
+        self.alloc_impl(layout, false)
+    }
+
+    /// Allocates zero-initialized memory with the specified `layout`.
+    pub fn alloc_zeroed(&self, layout: Layout) -> NonNull<u8> {
+        self.alloc_impl(layout, true)
+    }
+
+    fn alloc_impl(&self, layout: Layout, zeroed: bool) -> NonNull<u8> {
+        if let Some(index) = size_class_index(layout) {
+            let block = self
+                .with_tlab(|tlab| tlab.alloc(&self.shared, index))
+                .unwrap_or_else(|| self.shared.inner.lock().size_classes[index].alloc());
+            self.shared.allocated_blocks[index].fetch_add(1, Ordering::Relaxed);
+
+            if zeroed {
+                unsafe { std::ptr::write_bytes(block.as_ptr(), 0, SIZE_CLASSES[index]) };
+            }
+            block
+        } else {
+            let ptr = unsafe {
+                if zeroed {
+                    std::alloc::alloc_zeroed(layout)
+                } else {
+                    std::alloc::alloc(layout)
+                }
+            };
+            let mut inner = self.shared.inner.lock();
+            inner.large_objects.allocated_objects += 1;
+            inner.large_objects.allocated_memory += layout.size();
+            NonNull::new(ptr).expect("failed to allocate memory for large object")
+        }
+    }
+
+    /// Deallocates the memory at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this heap with the same `layout`.
+    pub unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        #[cfg(feature = "gc-debug")]
+        std::ptr::write_bytes(ptr.as_ptr(), POISON, layout.size());
+
+        if let Some(index) = size_class_index(layout) {
+            self.shared.allocated_blocks[index].fetch_sub(1, Ordering::Relaxed);
+            if self
+                .with_tlab(|tlab| tlab.dealloc(&self.shared, index, ptr))
+                .is_none()
+            {
+                self.shared.inner.lock().size_classes[index].dealloc(ptr);
+            }
+        } else {
+            std::alloc::dealloc(ptr.as_ptr(), layout);
+            let mut inner = self.shared.inner.lock();
+            inner.large_objects.allocated_objects -= 1;
+            inner.large_objects.allocated_memory -= layout.size();
+        }
+    }
+
+    /// Moves the blocks of every size class to the lowest free blocks of the
+    /// size class, and returns chunks that no longer contain any blocks to the
+    /// system. Pinned blocks are never moved. The contents of a moved block
+    /// are copied, and its `ptr` is updated to the new address. Blocks that
+    /// are allocated in the large object space are ignored.
+    ///
+    /// Returns the number of moved blocks.
+    ///
+    /// # Safety
+    ///
+    /// `blocks` must contain every block that is allocated from the size
+    /// classes of this heap, and no thread may allocate or deallocate memory
+    /// from the heap until this function returns. Pointers into moved blocks
+    /// are invalidated.
+    pub unsafe fn compact(&self, blocks: &mut [LiveBlock]) -> usize {
+        let mut inner = self.shared.inner.lock();
+
+        // The blocks that are cached by TLABs are considered free, so the TLABs must
+        // no longer use them
+        self.shared.generation.fetch_add(1, Ordering::AcqRel);
+
+        let mut size_class_blocks = SIZE_CLASSES.map(|_| Vec::new());
+        for block in blocks.iter_mut() {
+            if let Some(index) = size_class_index(block.layout) {
+                size_class_blocks[index].push(block);
+            }
+        }
+
+        let mut moved = 0;
+        for ((size_class, blocks), allocated_blocks) in inner
+            .size_classes
+            .iter_mut()
+            .zip(size_class_blocks)
+            .zip(self.shared.allocated_blocks.iter())
+        {
+            debug_assert_eq!(
+                blocks.len(),
+                allocated_blocks.load(Ordering::Relaxed),
+                "every allocated block must be passed to `Heap::compact`"
+            );
+            moved += size_class.compact(blocks);
+        }
+        moved
+    }
+
+    /// Returns statistics about the size classes and the large object space.
+    pub fn stats(&self) -> (Vec<SizeClassStats>, LargeObjectStats) {
+        let inner = self.shared.inner.lock();
+        let size_classes = inner
+            .size_classes
+            .iter()
+            .zip(self.shared.allocated_blocks.iter())
+            .map(|(size_class, allocated_blocks)| SizeClassStats {
+                block_size: size_class.block_size,
+                allocated_blocks: allocated_blocks.load(Ordering::Relaxed),
+                reserved_memory: size_class.chunks.len() * CHUNK_SIZE,
+            })
+            .collect();
+        (size_classes, inner.large_objects.clone())
+    }
+
+    /// Calls `f` with the TLAB of the current thread for this heap. Returns
+    /// `None` if the TLAB is not accessible because the current thread is
+    /// being destroyed.
+    fn with_tlab<R>(&self, f: impl FnOnce(&mut Tlab) -> R) -> Option<R> {
+        let key = Arc::as_ptr(&self.shared) as usize;
+        TLABS
+            .try_with(|tlabs| {
+                let mut tlabs = tlabs.borrow_mut();
+                if !tlabs.contains_key(&key) {
+                    // TLABs of heaps that no longer exist are never used again
+                    tlabs.retain(|_, tlab| tlab.shared.strong_count() > 0);
+                }
+                let generation = self.shared.generation.load(Ordering::Acquire);
+                let tlab = tlabs
+                    .entry(key)
+                    .or_insert_with(|| Tlab::new(Arc::downgrade(&self.shared), generation));
+                if tlab.generation != generation {
+                    // The heap was compacted since the TLAB was filled
+                    tlab.blocks.iter_mut().for_each(Vec::clear);
+                    tlab.generation = generation;
+                }
+                f(tlab)
+            })
+            .ok()
+    }
+}
+
+/// A thread-local allocation buffer, which caches free blocks of every size
+/// class of a heap for a single thread.
+struct Tlab {
+    /// The heap from which the blocks were taken. As long as the `Tlab` exists,
+    /// the address of the heap's state cannot be reused by another heap.
+    shared: Weak<SharedHeap>,
+
+    /// The cached blocks of every size class
+    blocks: [Vec<NonNull<u8>>; SIZE_CLASSES.len()],
+
+    /// The generation of the heap in which the blocks were cached
+    generation: usize,
+}
+
+impl Tlab {
+    fn new(shared: Weak<SharedHeap>, generation: usize) -> Self {
+        Tlab {
+            shared,
+            blocks: Default::default(),
+            generation,
+        }
+    }
+
+    /// Returns the number of blocks of the size class with the specified
+    /// `index` that are exchanged with the heap at once.
+    fn batch_size(index: usize) -> usize {
+        (TLAB_SIZE / SIZE_CLASSES[index]).max(1)
+    }
+
+    /// Allocates a block, refilling the buffer from `shared` if it is empty.
+    fn alloc(&mut self, shared: &SharedHeap, index: usize) -> NonNull<u8> {
+        let blocks = &mut self.blocks[index];
+        if let Some(block) = blocks.pop() {
+            return block;
+        }
+
+        {
+            let mut inner = shared.inner.lock();
+            let size_class = &mut inner.size_classes[index];
+            blocks.extend((0..Self::batch_size(index)).map(|_| size_class.alloc()));
+        }
+
+        // Hand out blocks in the order in which they were taken from the heap
+        blocks.reverse();
+        blocks.pop().expect("a batch contains at least one block")
+    }
+
+    /// Returns a block to the buffer. If the buffer overflows, a batch of
+    /// blocks is returned to `shared`.
+    fn dealloc(&mut self, shared: &SharedHeap, index: usize, block: NonNull<u8>) {
+        let batch_size = Self::batch_size(index);
+        let blocks = &mut self.blocks[index];
+        blocks.push(block);
+
+        if blocks.len() > 2 * batch_size {
+            let mut inner = shared.inner.lock();
+            let size_class = &mut inner.size_classes[index];
+            for block in blocks.drain(..batch_size) {
+                size_class.dealloc(block);
+            }
+        }
+    }
+}
+
+impl Drop for Tlab {
+    fn drop(&mut self) {
+        // Return all cached blocks when the thread exits, unless the heap no longer
+        // exists or was compacted since the blocks were cached
+        if let Some(shared) = self.shared.upgrade() {
+            let mut inner = shared.inner.lock();
+            if shared.generation.load(Ordering::Acquire) != self.generation {
+                return;
+            }
+            for (size_class, blocks) in inner.size_classes.iter_mut().zip(self.blocks.iter_mut()) {
+                for block in blocks.drain(..) {
+                    size_class.dealloc(block);
+                }
+            }
+        }
+    }
+}
+
+/// Allocates blocks of a fixed size.
+struct SizeClass {
+    block_size: usize,
+
+    /// All chunks that were allocated for this size class
+    chunks: Vec<NonNull<u8>>,
+
+    /// The next free block in the last chunk, and the end of that chunk
+    bump: Option<(NonNull<u8>, NonNull<u8>)>,
+
+    /// A linked list of freed blocks. Every free block stores a pointer to
+    /// the next free block.
+    free_list: Option<NonNull<u8>>,
+}
+
+impl SizeClass {
+    fn new(block_size: usize) -> Self {
+        SizeClass {
+            block_size,
+            chunks: Vec::new(),
+            bump: None,
+            free_list: None,
+        }
+    }
+
+    /// Allocates a block, preferably by reusing a freed block.
+    fn alloc(&mut self) -> NonNull<u8> {
+        if let Some(block) = self.free_list {
+            self.free_list = unsafe { *block.cast::<Option<NonNull<u8>>>().as_ptr() };
+            return block;
+        }
+
+        match self.bump {
+            Some((next, end)) if next < end => {
+                let block = next;
+                let next = unsafe { NonNull::new_unchecked(next.as_ptr().add(self.block_size)) };
+                self.bump = Some((next, end));
+                block
+            }
+            _ => {
+                let chunk = NonNull::new(unsafe { std::alloc::alloc_zeroed(chunk_layout()) })
+                    .expect("failed to allocate memory for size class");
+                self.chunks.push(chunk);
+
+                let next = unsafe { NonNull::new_unchecked(chunk.as_ptr().add(self.block_size)) };
+                let end = unsafe { NonNull::new_unchecked(chunk.as_ptr().add(CHUNK_SIZE)) };
+                self.bump = Some((next, end));
+                chunk
+            }
+        }
+    }
+
+    /// Returns a block to the free list.
+    fn dealloc(&mut self, block: NonNull<u8>) {
+        unsafe { *block.cast::<Option<NonNull<u8>>>().as_ptr() = self.free_list };
+        self.free_list = Some(block);
+    }
+
+    /// Moves the unpinned `blocks` to the lowest free blocks, releases the
+    /// chunks that become empty and rebuilds the free list from the remaining
+    /// free blocks. Returns the number of moved blocks.
+    ///
+    /// # Safety
+    ///
+    /// `blocks` must contain every block that is allocated from this size
+    /// class.
+    unsafe fn compact(&mut self, mut blocks: Vec<&mut LiveBlock>) -> usize {
+        let blocks_per_chunk = CHUNK_SIZE / self.block_size;
+        self.chunks.sort_unstable();
+
+        // Every block of every chunk is identified by its index in the sorted chunks
+        let chunks = &self.chunks;
+        let block_size = self.block_size;
+        let index_of = |ptr: NonNull<u8>| {
+            let chunk = chunks.partition_point(|chunk| *chunk <= ptr) - 1;
+            let offset = ptr.as_ptr() as usize - chunks[chunk].as_ptr() as usize;
+            chunk * blocks_per_chunk + offset / block_size
+        };
+        let block_at = |index: usize| {
+            let chunk = chunks[index / blocks_per_chunk];
+            NonNull::new_unchecked(chunk.as_ptr().add((index % blocks_per_chunk) * block_size))
+        };
+
+        let mut in_use = vec![false; chunks.len() * blocks_per_chunk];
+        for block in blocks.iter() {
+            in_use[index_of(block.ptr)] = true;
+        }
+        let free = (0..in_use.len())
+            .filter(|&index| !in_use[index])
+            .collect::<Vec<_>>();
+
+        // Move the blocks with the highest addresses into the lowest free blocks, until
+        // no free block remains below a movable block
+        blocks.sort_unstable_by_key(|block| block.ptr);
+        let mut free = free.into_iter().peekable();
+        let mut moved = 0;
+        for block in blocks.into_iter().rev().filter(|block| !block.pinned) {
+            let from = index_of(block.ptr);
+            let Some(to) = free.next_if(|&to| to < from) else {
+                break;
+            };
+
+            let new_ptr = block_at(to);
+            std::ptr::copy_nonoverlapping(
+                block.ptr.as_ptr(),
+                new_ptr.as_ptr(),
+                block.layout.size(),
+            );
+            #[cfg(feature = "gc-debug")]
+            std::ptr::write_bytes(block.ptr.as_ptr(), POISON, self.block_size);
+
+            in_use[to] = true;
+            in_use[from] = false;
+            block.ptr = new_ptr;
+            moved += 1;
+        }
+
+        // Release the empty chunks, and make all free blocks of the other chunks
+        // available, lowest address first
+        let mut free_blocks = Vec::new();
+        let mut retained_chunks = Vec::with_capacity(chunks.len());
+        for (chunk_index, chunk_in_use) in in_use.chunks(blocks_per_chunk).enumerate() {
+            let chunk = chunks[chunk_index];
+            if chunk_in_use.iter().any(|&in_use| in_use) {
+                retained_chunks.push(chunk);
+                free_blocks.extend(
+                    chunk_in_use
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, &in_use)| !in_use)
+                        .map(|(index, _)| block_at(chunk_index * blocks_per_chunk + index)),
+                );
+            } else {
+                std::alloc::dealloc(chunk.as_ptr(), chunk_layout());
+            }
+        }
+
+        self.chunks = retained_chunks;
+        self.bump = None;
+        self.free_list = None;
+        for block in free_blocks.into_iter().rev() {
+            self.dealloc(block);
+        }
+
+        moved
+    }
+}
+
+impl Drop for SizeClass {
+    fn drop(&mut self) {
+        for chunk in self.chunks.drain(..) {
+            unsafe { std::alloc::dealloc(chunk.as_ptr(), chunk_layout()) };
+        }
+    }
+}
+
+/// Returns the layout of a chunk
+fn chunk_layout() -> Layout {
+    Layout::from_size_align(CHUNK_SIZE, CHUNK_ALIGN).expect("invalid chunk layout")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{alloc::Layout, sync::Arc};
+
+    use super::{Heap, LiveBlock, CHUNK_SIZE};
+
+    #[test]
+    fn size_classes() {
+        let heap = Heap::default();
+
+        let small = Layout::from_size_align(24, 8).unwrap();
+        let a = heap.alloc_zeroed(small);
+        let b = heap.alloc_zeroed(small);
+        assert_eq!(unsafe { a.as_ptr().add(32) }, b.as_ptr());
+
+        let (size_classes, large_objects) = heap.stats();
+        let size_class = size_classes
+            .iter()
+            .find(|size_class| size_class.block_size == 32)
+            .unwrap();
+        assert_eq!(size_class.allocated_blocks, 2);
+        assert_eq!(size_class.reserved_memory, CHUNK_SIZE);
+        assert_eq!(large_objects.allocated_objects, 0);
+
+        // Freed blocks are reused and zero-initialized
+        unsafe {
+            b.as_ptr().write(42);
+            heap.dealloc(b, small);
+        }
+        let c = heap.alloc_zeroed(small);
+        assert_eq!(c, b);
+        assert_eq!(unsafe { c.as_ptr().read() }, 0);
+    }
+
+    #[test]
+    fn over_aligned_allocations() {
+        let heap = Heap::default();
+
+        // Small over-aligned allocations are served by a size class with large enough
+        // blocks
+        let aligned = Layout::from_size_align(16, 64).unwrap();
+        let ptrs: Vec<_> = (0..3).map(|_| heap.alloc_zeroed(aligned)).collect();
+        assert!(ptrs.iter().all(|ptr| ptr.as_ptr() as usize % 64 == 0));
+
+        // Alignments that exceed all block sizes are served by the large object space
+        let page_aligned = Layout::from_size_align(16, 4096).unwrap();
+        let page = heap.alloc_zeroed(page_aligned);
+        assert_eq!(page.as_ptr() as usize % 4096, 0);
+
+        let (size_classes, large_objects) = heap.stats();
+        let size_class = size_classes
+            .iter()
+            .find(|size_class| size_class.block_size == 64)
+            .unwrap();
+        assert_eq!(size_class.allocated_blocks, 3);
+        assert_eq!(large_objects.allocated_objects, 1);
+
+        unsafe {
+            for ptr in ptrs {
+                heap.dealloc(ptr, aligned);
+            }
+            heap.dealloc(page, page_aligned);
+        }
+    }
+
+    #[cfg(feature = "gc-debug")]
+    #[test]
+    fn poison() {
+        let heap = Heap::default();
+
+        let small = Layout::from_size_align(32, 8).unwrap();
+        let ptr = heap.alloc_zeroed(small);
+        unsafe { heap.dealloc(ptr, small) };
+
+        // The first bytes of a free block store the free list
+        let poisoned = unsafe { std::slice::from_raw_parts(ptr.as_ptr().add(8), 24) };
+        assert!(poisoned.iter().all(|&byte| byte == super::POISON));
+    }
+
+    #[test]
+    fn tlabs_are_returned_on_thread_exit() {
+        let heap = Arc::new(Heap::default());
+        let block = Layout::from_size_align(2048, 8).unwrap();
+
+        // Every thread takes a batch of blocks into its TLAB. If the blocks were not
+        // returned when a thread exits, every thread would consume new memory.
+        for _ in 0..100 {
+            let heap = heap.clone();
+            std::thread::spawn(move || {
+                let ptr = heap.alloc(block);
+                unsafe { heap.dealloc(ptr, block) };
+            })
+            .join()
+            .unwrap();
+        }
+
+        let (size_classes, _) = heap.stats();
+        let size_class = size_classes
+            .iter()
+            .find(|size_class| size_class.block_size == 2048)
+            .unwrap();
+        assert_eq!(size_class.allocated_blocks, 0);
+        assert_eq!(size_class.reserved_memory, CHUNK_SIZE);
+    }
+
+    #[test]
+    fn parallel_allocations() {
+        let heap = Arc::new(Heap::default());
+        let small = Layout::from_size_align(64, 8).unwrap();
+
+        let threads = (0..4)
+            .map(|_| {
+                let heap = heap.clone();
+                std::thread::spawn(move || {
+                    (0..1000)
+                        .map(|_| heap.alloc_zeroed(small).as_ptr() as usize)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut blocks = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+
+        // No block was handed out twice
+        blocks.sort_unstable();
+        blocks.dedup();
+        assert_eq!(blocks.len(), 4000);
+
+        let (size_classes, _) = heap.stats();
+        let size_class = size_classes
+            .iter()
+            .find(|size_class| size_class.block_size == 64)
+            .unwrap();
+        assert_eq!(size_class.allocated_blocks, 4000);
+    }
+
+    #[test]
+    fn compact() {
+        let heap = Heap::default();
+        let block = Layout::from_size_align(2048, 8).unwrap();
+        let reserved_memory = |heap: &Heap| {
+            let (size_classes, _) = heap.stats();
+            size_classes
+                .iter()
+                .find(|size_class| size_class.block_size == 2048)
+                .unwrap()
+                .reserved_memory
+        };
+
+        // Spread a few live blocks over four chunks
+        let blocks_per_chunk = CHUNK_SIZE / 2048;
+        let ptrs = (0..4 * blocks_per_chunk)
+            .map(|_| heap.alloc_zeroed(block))
+            .collect::<Vec<_>>();
+        let mut live = Vec::new();
+        for (index, ptr) in ptrs.into_iter().enumerate() {
+            if index % blocks_per_chunk == blocks_per_chunk - 1 {
+                unsafe { ptr.as_ptr().write(index as u8) };
+                live.push(LiveBlock {
+                    ptr,
+                    layout: block,
+                    pinned: false,
+                });
+            } else {
+                unsafe { heap.dealloc(ptr, block) };
+            }
+        }
+        live[1].pinned = true;
+        let pinned = live[1].ptr;
+        assert_eq!(reserved_memory(&heap), 4 * CHUNK_SIZE);
+
+        // The pinned block keeps its chunk alive, the other blocks are moved into the
+        // lowest free blocks
+        assert_eq!(unsafe { heap.compact(&mut live) }, 3);
+        assert_eq!(reserved_memory(&heap), 2 * CHUNK_SIZE);
+        assert_eq!(live[1].ptr, pinned);
+        for (index, block) in live.iter().enumerate() {
+            let expected = ((index + 1) * blocks_per_chunk - 1) as u8;
+            assert_eq!(unsafe { block.ptr.as_ptr().read() }, expected);
+        }
+
+        // Blocks that were cached before the compaction are not handed out again
+        let allocated = (0..2 * blocks_per_chunk)
+            .map(|_| heap.alloc(block))
+            .collect::<Vec<_>>();
+        assert!(allocated
+            .iter()
+            .all(|ptr| live.iter().all(|block| block.ptr != *ptr)));
+    }
+
+    #[test]
+    fn large_objects() {
+        let heap = Heap::default();
+
+        let large = Layout::from_size_align(4096, 8).unwrap();
+        let ptr = heap.alloc_zeroed(large);
+
+        let (size_classes, large_objects) = heap.stats();
+        assert!(size_classes
+            .iter()
+            .all(|size_class| size_class.allocated_blocks == 0));
+        assert_eq!(large_objects.allocated_objects, 1);
+        assert_eq!(large_objects.allocated_memory, 4096);
+
+        unsafe { heap.dealloc(ptr, large) };
+        let (_, large_objects) = heap.stats();
+        assert_eq!(large_objects.allocated_objects, 0);
+        assert_eq!(large_objects.allocated_memory, 0);
+    }
+}
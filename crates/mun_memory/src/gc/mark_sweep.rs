@@ -1,10 +1,17 @@
+#[cfg(feature = "gc-debug")]
+mod debug;
+
 use std::{
     alloc::{Layout, LayoutError},
     borrow::Cow,
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     pin::Pin,
     ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
 };
+#[cfg(feature = "gc-tracing")]
+use std::{backtrace::Backtrace, sync::Arc};
 
 use mapping::{Mapping, StructMapping};
 use parking_lot::RwLock;
@@ -12,8 +19,10 @@ use parking_lot::RwLock;
 use crate::{
     cast,
     gc::{
-        array::ArrayHeader, Array as GcArray, Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats,
-        TypeTrace,
+        array::ArrayHeader,
+        heap::{Heap, LiveBlock},
+        Array as GcArray, CollectionStats, Event, GcPtr, GcRuntime, Observer, RawGcPtr,
+        RootedObject, Stats, TypeTrace,
     },
     mapping::{self, resolve_struct_to_struct_edit, Action, FieldMapping, MemoryMapper},
     r#type::Type,
@@ -177,14 +186,45 @@ impl TypeTrace for Type {
     }
 }
 
+/// The number of collections of which the stats are kept.
+const COLLECTION_HISTORY_LEN: usize = 128;
+
+/// Keeps track of the stats of the most recent collections.
+struct CollectionHistory {
+    /// The stats of the most recent collections, oldest first
+    collections: VecDeque<CollectionStats>,
+    /// The amount of memory that was allocated since the previous collection
+    allocated_memory: usize,
+    /// The time at which the previous collection ended
+    previous_collection_end: Instant,
+}
+
+impl Default for CollectionHistory {
+    fn default() -> Self {
+        Self {
+            collections: VecDeque::with_capacity(COLLECTION_HISTORY_LEN),
+            allocated_memory: 0,
+            previous_collection_end: Instant::now(),
+        }
+    }
+}
+
 /// Implements a simple mark-sweep type garbage collector.
 pub struct MarkSweep<O>
 where
     O: Observer<Event = Event>,
 {
     objects: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo>>>>,
+    heap: Heap,
     observer: O,
     stats: RwLock<Stats>,
+    history: RwLock<CollectionHistory>,
+    /// The maximum number of bytes that objects may occupy, or `usize::MAX` if
+    /// there is no limit
+    memory_limit: AtomicUsize,
+    /// The headers of collected objects, which are used to detect stale handles
+    #[cfg(feature = "gc-debug")]
+    graveyard: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo>>>>,
 }
 
 impl<O> Default for MarkSweep<O>
@@ -194,8 +234,13 @@ where
     fn default() -> Self {
         MarkSweep {
             objects: RwLock::new(HashMap::new()),
+            heap: Heap::default(),
             observer: O::default(),
             stats: RwLock::new(Stats::default()),
+            history: RwLock::new(CollectionHistory::default()),
+            memory_limit: AtomicUsize::new(usize::MAX),
+            #[cfg(feature = "gc-debug")]
+            graveyard: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -208,8 +253,13 @@ where
     pub fn with_observer(observer: O) -> Self {
         Self {
             objects: RwLock::new(HashMap::new()),
+            heap: Heap::default(),
             observer,
             stats: RwLock::new(Stats::default()),
+            history: RwLock::new(CollectionHistory::default()),
+            memory_limit: AtomicUsize::new(usize::MAX),
+            #[cfg(feature = "gc-debug")]
+            graveyard: RwLock::new(HashMap::new()),
         }
     }
 
@@ -219,6 +269,7 @@ where
             let mut stats = self.stats.write();
             stats.allocated_memory += size;
         }
+        self.history.write().allocated_memory += size;
 
         self.observer.event(Event::Allocation(handle));
     }
@@ -227,16 +278,106 @@ where
     pub fn observer(&self) -> &O {
         &self.observer
     }
+
+    /// Sets the maximum number of bytes that the allocated objects may occupy,
+    /// or removes the limit if `None` is specified. The collector does not
+    /// refuse allocations that exceed the limit; its users are expected to
+    /// check [`MarkSweep::exceeds_memory_limit`] after allocating.
+    pub fn set_memory_limit(&self, limit: Option<usize>) {
+        self.memory_limit
+            .store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of bytes that the allocated objects may
+    /// occupy, if a limit was set.
+    pub fn memory_limit(&self) -> Option<usize> {
+        let limit = self.memory_limit.load(Ordering::Relaxed);
+        (limit != usize::MAX).then_some(limit)
+    }
+
+    /// Returns true if the allocated objects occupy more memory than the limit
+    /// set with [`MarkSweep::set_memory_limit`].
+    pub fn exceeds_memory_limit(&self) -> bool {
+        self.stats.read().allocated_memory > self.memory_limit.load(Ordering::Relaxed)
+    }
+
+    /// Returns the stats of the most recent collections, oldest first. The
+    /// stats of at most 128 collections are kept.
+    pub fn collection_history(&self) -> Vec<CollectionStats> {
+        self.history.read().collections.iter().cloned().collect()
+    }
+
+    /// Returns all objects that are currently rooted. If the `gc-tracing`
+    /// feature is enabled, the backtrace of each object's allocation is
+    /// included.
+    pub fn rooted_objects(&self) -> Vec<RootedObject> {
+        self.objects
+            .read()
+            .values()
+            .filter(|obj| obj.roots > 0)
+            .map(|obj| RootedObject {
+                ty: obj.ty.clone(),
+                roots: obj.roots,
+                #[cfg(feature = "gc-tracing")]
+                backtrace: Some(obj.backtrace.clone()),
+                #[cfg(not(feature = "gc-tracing"))]
+                backtrace: None,
+            })
+            .collect()
+    }
+
+    /// Panics if `handle` does not refer to a live object. Only performs
+    /// validation if the `gc-debug` feature is enabled.
+    #[allow(unused_variables, clippy::unused_self)]
+    fn validate_handle(&self, objects: &HashMap<GcPtr, Pin<Box<ObjectInfo>>>, handle: GcPtr) {
+        #[cfg(feature = "gc-debug")]
+        debug::validate_handle(objects, &self.graveyard.read(), handle);
+    }
+
+    /// Panics because the object `handle` references `reference`, which is not
+    /// a live object.
+    #[allow(unused_variables, clippy::unused_self)]
+    fn invalid_reference(
+        &self,
+        objects: &HashMap<GcPtr, Pin<Box<ObjectInfo>>>,
+        handle: GcPtr,
+        reference: GcPtr,
+    ) -> ! {
+        #[cfg(feature = "gc-debug")]
+        debug::invalid_reference(objects, &self.graveyard.read(), handle, reference);
+        #[cfg(not(feature = "gc-debug"))]
+        panic!("found invalid reference");
+    }
+
+    /// Panics if any corruption of the heap is detected. Only performs
+    /// validation if the `gc-debug` feature is enabled.
+    #[allow(unused_variables, clippy::unused_self)]
+    fn validate_heap(&self, objects: &HashMap<GcPtr, Pin<Box<ObjectInfo>>>) {
+        #[cfg(feature = "gc-debug")]
+        debug::validate_heap(objects, &self.graveyard.read());
+    }
+
+    /// Disposes of the header of a collected object. If the `gc-debug` feature
+    /// is enabled, the header is retained to detect the use of stale handles.
+    #[allow(unused_variables, clippy::unused_self)]
+    fn bury(&self, handle: GcPtr, object: Pin<Box<ObjectInfo>>) {
+        #[cfg(feature = "gc-debug")]
+        debug::bury(&mut self.graveyard.write(), handle, object);
+    }
 }
 
-fn alloc_obj(ty: Type) -> Pin<Box<ObjectInfo>> {
-    let ptr = NonNull::new(unsafe { std::alloc::alloc_zeroed(ty.value_layout()) })
-        .expect("failed to allocate memory for new object");
+fn alloc_obj(heap: &Heap, ty: Type) -> Pin<Box<ObjectInfo>> {
+    let ptr = heap.alloc_zeroed(ty.value_layout());
     Box::pin(ObjectInfo {
         data: ObjectInfoData { ptr },
         ty,
         roots: 0,
+        pins: 0,
+        #[cfg(feature = "gc-debug")]
+        canary: debug::ALIVE_CANARY,
         color: Color::White,
+        #[cfg(feature = "gc-tracing")]
+        backtrace: Arc::new(Backtrace::force_capture()),
     })
 }
 
@@ -426,35 +567,45 @@ fn repeat_layout(layout: Layout, n: usize) -> Result<Layout, MemoryLayoutError>
 
 /// Allocates memory for an array type with `length` elements. `array_ty` must
 /// be an array type.
-fn alloc_array(ty: Type, length: usize) -> Pin<Box<ObjectInfo>> {
+fn alloc_array(heap: &Heap, ty: Type, length: usize) -> Pin<Box<ObjectInfo>> {
     Box::pin(ObjectInfo {
         data: ObjectInfoData {
-            array: array_header(&ty, length),
+            array: array_header(heap, &ty, length),
         },
         ty,
         roots: 0,
+        pins: 0,
+        #[cfg(feature = "gc-debug")]
+        canary: debug::ALIVE_CANARY,
         color: Color::White,
+        #[cfg(feature = "gc-tracing")]
+        backtrace: Arc::new(Backtrace::force_capture()),
     })
 }
 
+/// Returns the memory layout of an array with `capacity` elements of type
+/// `element_ty`, including its header.
+fn array_layout(element_ty: &Type, capacity: usize) -> Layout {
+    let header_layout = Layout::new::<ArrayHeader>();
+    let element_ty_layout = element_ty.reference_layout();
+    let elements_layout = repeat_layout(element_ty_layout, capacity)
+        .expect("unable to create a memory layout for array elemets");
+    let (layout, _) = header_layout
+        .extend(elements_layout)
+        .expect("unable to create memory layout for array");
+    layout
+}
+
 /// Constructs an array header for an array type with `length` elements.
-fn array_header(ty: &Type, length: usize) -> NonNull<ArrayHeader> {
+fn array_header(heap: &Heap, ty: &Type, length: usize) -> NonNull<ArrayHeader> {
     let array_ty = ty
         .as_array()
         .expect("array type doesnt have an element type");
 
     // Allocate memory for the array data
-    let header_layout = Layout::new::<ArrayHeader>();
-    let element_ty_layout = array_ty.element_type().reference_layout();
-    let elements_layout = repeat_layout(element_ty_layout, length)
-        .expect("unable to create a memory layout for array elemets");
-    let (layout, _) = header_layout
-        .extend(elements_layout)
-        .expect("unable to create memory layout for array");
+    let layout = array_layout(&array_ty.element_type(), length);
 
-    let mut array_header: NonNull<ArrayHeader> =
-        NonNull::new(unsafe { std::alloc::alloc_zeroed(layout).cast() })
-            .expect("error allocating memory for array");
+    let mut array_header: NonNull<ArrayHeader> = heap.alloc_zeroed(layout).cast();
     let array = unsafe { array_header.as_mut() };
     array.length = length;
     array.capacity = length;
@@ -471,32 +622,34 @@ where
     fn alloc(&self, ty: &Type) -> GcPtr {
         assert!(ty.is_concrete());
 
-        let object = alloc_obj(ty.clone());
+        // The object must be registered before the heap can be compacted, because
+        // compaction only retains the memory of registered objects
+        let mut objects = self.objects.write();
+        let object = alloc_obj(&self.heap, ty.clone());
         let size = object.layout().size();
 
         // We want to return a pointer to the `ObjectInfo`, to be used as handle.
         let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
 
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
-        }
+        objects.insert(handle, object);
+        drop(objects);
 
         self.log_alloc(handle, size);
         handle
     }
 
     fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array {
-        let object = alloc_array(ty.clone(), n);
+        // The object must be registered before the heap can be compacted, because
+        // compaction only retains the memory of registered objects
+        let mut objects = self.objects.write();
+        let object = alloc_array(&self.heap, ty.clone(), n);
         let size = object.layout().size();
 
         // We want to return a pointer to the `ObjectInfo`, to be used as handle.
         let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
 
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
-        }
+        objects.insert(handle, object);
+        drop(objects);
 
         self.log_alloc(handle, size);
         ArrayHandle {
@@ -505,7 +658,8 @@ where
     }
 
     fn ptr_type(&self, handle: GcPtr) -> Type {
-        let _lock = self.objects.read();
+        let objects = self.objects.read();
+        self.validate_handle(&objects, handle);
 
         // Convert the handle to our internal representation
         let object_info: *const ObjectInfo = handle.into();
@@ -515,7 +669,8 @@ where
     }
 
     fn array(&self, handle: GcPtr) -> Option<Self::Array> {
-        let _lock = self.objects.read();
+        let objects = self.objects.read();
+        self.validate_handle(&objects, handle);
         let obj: NonNull<ObjectInfo> =
             NonNull::new(handle.into()).expect("cannot have a null handle here");
         unsafe {
@@ -528,7 +683,8 @@ where
     }
 
     fn root(&self, handle: GcPtr) {
-        let _lock = self.objects.write();
+        let objects = self.objects.write();
+        self.validate_handle(&objects, handle);
 
         // Convert the handle to our internal representation
         let object_info: *mut ObjectInfo = handle.into();
@@ -537,7 +693,8 @@ where
     }
 
     fn unroot(&self, handle: GcPtr) {
-        let _lock = self.objects.write();
+        let objects = self.objects.write();
+        self.validate_handle(&objects, handle);
 
         // Convert the handle to our internal representation
         let object_info: *mut ObjectInfo = handle.into();
@@ -545,8 +702,33 @@ where
         unsafe { (*object_info).roots -= 1 };
     }
 
+    fn pin(&self, handle: GcPtr) {
+        let objects = self.objects.write();
+        self.validate_handle(&objects, handle);
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe { (*object_info).pins += 1 };
+    }
+
+    fn unpin(&self, handle: GcPtr) {
+        let objects = self.objects.write();
+        self.validate_handle(&objects, handle);
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe { (*object_info).pins -= 1 };
+    }
+
     fn stats(&self) -> Stats {
-        self.stats.read().clone()
+        let (size_classes, large_objects) = self.heap.stats();
+        Stats {
+            size_classes,
+            large_objects,
+            ..self.stats.read().clone()
+        }
     }
 }
 
@@ -557,6 +739,7 @@ where
     /// Collects all memory that is no longer referenced by rooted objects.
     /// Returns `true` if memory was reclaimed, `false` otherwise.
     pub fn collect(&self) -> bool {
+        let started_at = Instant::now();
         self.observer.event(Event::Start);
 
         let mut objects = self.objects.write();
@@ -579,9 +762,10 @@ where
 
             // Trace all other objects
             for reference in unsafe { (*next).ty.trace(handle) } {
-                let ref_ptr = objects
-                    .get_mut(&reference)
-                    .expect("found invalid reference");
+                if !objects.contains_key(&reference) {
+                    self.invalid_reference(&objects, handle, reference);
+                }
+                let ref_ptr = objects.get_mut(&reference).unwrap();
                 if ref_ptr.color == Color::White {
                     let ptr = ref_ptr.as_ref().get_ref() as *const _ as *mut ObjectInfo;
                     unsafe { (*ptr).color = Color::Gray };
@@ -596,29 +780,156 @@ where
         }
 
         // Sweep all non-reachable objects
-        let size_before = objects.len();
-        objects.retain(|h, obj| {
-            if obj.color == Color::Black {
-                unsafe {
-                    obj.as_mut().get_unchecked_mut().color = Color::White;
-                }
-                true
-            } else {
-                let value_memory_layout = obj.layout();
-                unsafe { std::alloc::dealloc(obj.data.ptr.as_mut(), value_memory_layout) };
-                self.observer.event(Event::Deallocation(*h));
-                {
-                    let mut stats = self.stats.write();
-                    stats.allocated_memory -= value_memory_layout.size();
+        let garbage = objects
+            .iter_mut()
+            .filter_map(|(handle, obj)| {
+                if obj.color == Color::Black {
+                    unsafe {
+                        obj.as_mut().get_unchecked_mut().color = Color::White;
+                    }
+                    None
+                } else {
+                    Some(*handle)
                 }
-                false
+            })
+            .collect::<Vec<_>>();
+        let mut reclaimed_memory = 0;
+        for handle in garbage.iter() {
+            let obj = objects
+                .remove(handle)
+                .expect("garbage must be a live object");
+            let value_memory_layout = obj.layout();
+            unsafe { self.heap.dealloc(obj.data.ptr, value_memory_layout) };
+            self.observer.event(Event::Deallocation(*handle));
+            {
+                let mut stats = self.stats.write();
+                stats.allocated_memory -= value_memory_layout.size();
             }
-        });
-        let size_after = objects.len();
+            reclaimed_memory += value_memory_layout.size();
+            self.bury(*handle, obj);
+        }
+
+        self.validate_heap(&objects);
 
         self.observer.event(Event::End);
 
-        size_before != size_after
+        let surviving_memory = self.stats.read().allocated_memory;
+        let mut history = self.history.write();
+        let ended_at = Instant::now();
+        let collection = CollectionStats {
+            started_at,
+            pause: ended_at - started_at,
+            interval: started_at.saturating_duration_since(history.previous_collection_end),
+            allocated_memory: history.allocated_memory,
+            surviving_memory,
+            reclaimed_memory,
+        };
+        if history.collections.len() == COLLECTION_HISTORY_LEN {
+            history.collections.pop_front();
+        }
+        history.collections.push_back(collection);
+        history.allocated_memory = 0;
+        history.previous_collection_end = ended_at;
+
+        !garbage.is_empty()
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects,
+    /// followed by a compaction of the heap. Returns `true` if memory was
+    /// reclaimed, `false` otherwise.
+    ///
+    /// See [`MarkSweep::compact`] for more information.
+    pub fn collect_and_compact(&self) -> bool {
+        let reclaimed = self.collect();
+        self.compact();
+        reclaimed
+    }
+
+    /// Compacts the heap to combat fragmentation. Objects that are not pinned
+    /// are moved to the lowest free memory of their size class, and chunks
+    /// of memory that no longer contain any objects are returned to the
+    /// system. Unused capacity of arrays is released. The indirection of
+    /// every relocated object is updated, so [`GcPtr`]s to the object remain
+    /// valid. Returns the number of relocated objects.
+    ///
+    /// Raw pointers to the memory of an object are invalidated when it is
+    /// relocated. Use [`GcRuntime::pin`] to prevent relocation of an object.
+    pub fn compact(&self) -> usize {
+        let mut objects = self.objects.write();
+        let mut relocated = HashSet::new();
+
+        // Release the unused capacity of arrays by moving their elements to a smaller
+        // allocation
+        for (handle, obj) in objects.iter_mut() {
+            if obj.pins > 0 {
+                continue;
+            }
+            let Some(element_ty) = obj.ty.as_array().map(|array| array.element_type()) else {
+                continue;
+            };
+
+            // Safety: relocating the data does not move the `ObjectInfo` itself
+            let obj = unsafe { obj.as_mut().get_unchecked_mut() };
+            let old_layout = obj.layout();
+            let new_layout = array_layout(&element_ty, unsafe { obj.data.array.as_ref().length });
+            if new_layout.size() == old_layout.size() {
+                continue;
+            }
+
+            unsafe {
+                let old_ptr = obj.data.ptr;
+                let new_ptr = self.heap.alloc(new_layout);
+                std::ptr::copy_nonoverlapping(
+                    old_ptr.as_ptr(),
+                    new_ptr.as_ptr(),
+                    new_layout.size(),
+                );
+                self.heap.dealloc(old_ptr, old_layout);
+
+                obj.data.ptr = new_ptr;
+                let header = obj.data.array.as_mut();
+                header.capacity = header.length;
+            }
+
+            {
+                let mut stats = self.stats.write();
+                stats.allocated_memory -= old_layout.size() - new_layout.size();
+            }
+            relocated.insert(*handle);
+        }
+
+        // Defragment the heap
+        let (handles, mut blocks): (Vec<_>, Vec<_>) = objects
+            .iter()
+            .map(|(handle, obj)| {
+                let block = LiveBlock {
+                    ptr: unsafe { obj.data.ptr },
+                    layout: obj.layout(),
+                    pinned: obj.pins > 0,
+                };
+                (*handle, block)
+            })
+            .unzip();
+
+        // Safety: all objects are passed to the heap, and the lock on `objects`
+        // prevents other threads from allocating or deallocating objects
+        unsafe { self.heap.compact(&mut blocks) };
+
+        for (handle, block) in handles.into_iter().zip(blocks) {
+            let obj = objects.get_mut(&handle).expect("object must be alive");
+            if unsafe { obj.data.ptr } != block.ptr {
+                // Safety: relocating the data does not move the `ObjectInfo` itself
+                unsafe { obj.as_mut().get_unchecked_mut().data.ptr = block.ptr };
+                relocated.insert(handle);
+            }
+        }
+
+        self.validate_heap(&objects);
+
+        for handle in relocated.iter() {
+            self.observer.event(Event::Relocation(*handle));
+        }
+        relocated.len()
     }
 }
 
@@ -635,6 +946,7 @@ where
 
         #[allow(clippy::mutable_key_type)]
         fn map_array(
+            heap: &Heap,
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
             conversions: &HashMap<Type, StructMapping>,
             mut src_object: NonNull<ObjectInfo>,
@@ -644,12 +956,17 @@ where
             let src_array = ArrayHandle { obj: src_object };
 
             // Initialize the array
-            let new_header = array_header(new_ty, src_array.length());
+            let new_header = array_header(heap, new_ty, src_array.length());
 
             let mut dest_obj = ObjectInfo {
                 data: ObjectInfoData { array: new_header },
                 roots: unsafe { src_object.as_ref().roots },
+                pins: unsafe { src_object.as_ref().pins },
+                #[cfg(feature = "gc-debug")]
+                canary: unsafe { src_object.as_ref().canary },
                 color: unsafe { src_object.as_ref().color },
+                #[cfg(feature = "gc-tracing")]
+                backtrace: unsafe { src_object.as_ref().backtrace.clone() },
                 ty: new_ty.clone(),
             };
 
@@ -663,6 +980,7 @@ where
                 .zip(dest_array.elements())
                 .for_each(|(src, dest)| {
                     map_type(
+                        heap,
                         new_allocations,
                         conversions,
                         src,
@@ -674,13 +992,14 @@ where
 
             unsafe {
                 let src_obj = src_object.as_mut();
-                std::alloc::dealloc(src_obj.data.ptr.as_mut(), src_obj.layout());
+                heap.dealloc(src_obj.data.ptr, src_obj.layout());
                 *src_obj = dest_obj;
             };
         }
 
         #[allow(clippy::mutable_key_type)]
         fn map_type(
+            heap: &Heap,
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
             conversions: &HashMap<Type, StructMapping>,
             src: NonNull<u8>,
@@ -691,7 +1010,7 @@ where
             match action {
                 mapping::Action::ArrayAlloc => {
                     // Initialize the array with no values
-                    let object = alloc_array(new_ty.clone(), 0);
+                    let object = alloc_array(heap, new_ty.clone(), 0);
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
                     let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
@@ -707,7 +1026,7 @@ where
                     old_offset,
                 } => {
                     // Initialize the array with a single value
-                    let mut object = alloc_array(new_ty.clone(), 1);
+                    let mut object = alloc_array(heap, new_ty.clone(), 1);
 
                     let array_handle = ArrayHandle {
                         obj: unsafe {
@@ -717,6 +1036,7 @@ where
 
                     // Map single element to array
                     map_type(
+                        heap,
                         new_allocations,
                         conversions,
                         unsafe { get_field_ptr(src, *old_offset) },
@@ -744,6 +1064,7 @@ where
                     let src_obj = unsafe { *src_ptr.cast::<NonNull<ObjectInfo>>().as_ref() };
 
                     map_array(
+                        heap,
                         new_allocations,
                         conversions,
                         src_obj,
@@ -796,6 +1117,7 @@ where
                     if array_handle.header().length > 0 {
                         // Map single element from array
                         map_type(
+                            heap,
                             new_allocations,
                             conversions,
                             array_handle.data(),
@@ -808,7 +1130,7 @@ where
                     }
                 }
                 mapping::Action::StructAlloc => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(heap, new_ty.clone());
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
                     let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
@@ -835,6 +1157,7 @@ where
 
                     // Map heap-allocated struct to in-memory struct
                     map_struct(
+                        heap,
                         new_allocations,
                         conversions,
                         &conversion.field_mapping,
@@ -844,7 +1167,7 @@ where
                     );
                 }
                 mapping::Action::StructMapFromValue { old_ty, old_offset } => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(heap, new_ty.clone());
 
                     let conversion = conversions.get(old_ty).unwrap_or_else(|| {
                         panic!(
@@ -854,6 +1177,7 @@ where
 
                     // Map in-memory struct to heap-allocated struct
                     map_struct(
+                        heap,
                         new_allocations,
                         conversions,
                         &conversion.field_mapping,
@@ -879,6 +1203,7 @@ where
                     });
 
                     map_struct(
+                        heap,
                         new_allocations,
                         conversions,
                         &conversion.field_mapping,
@@ -894,6 +1219,7 @@ where
 
         #[allow(clippy::mutable_key_type)]
         fn map_struct(
+            heap: &Heap,
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
             conversions: &HashMap<Type, StructMapping>,
             mapping: &[FieldMapping],
@@ -908,6 +1234,7 @@ where
             {
                 let field_dest = unsafe { get_field_ptr(dest, *new_offset) };
                 map_type(
+                    heap,
                     new_allocations,
                     conversions,
                     src,
@@ -918,8 +1245,13 @@ where
             }
         }
 
+        let heap = &self.heap;
         let mut objects = self.objects.write();
 
+        // Mapping changes the size of objects, so the allocated memory has to be
+        // recomputed afterwards
+        let memory_before_mapping = objects_memory(&objects);
+
         // Determine which types are still allocated with deleted types
         let deleted = objects
             .iter()
@@ -941,7 +1273,12 @@ where
                             ptr: unsafe { object_info.data.ptr },
                         },
                         roots: object_info.roots,
+                        pins: object_info.pins,
+                        #[cfg(feature = "gc-debug")]
+                        canary: object_info.canary,
                         color: object_info.color,
+                        #[cfg(feature = "gc-tracing")]
+                        backtrace: object_info.backtrace.clone(),
                         ty: new_ty.clone(),
                     });
                 }
@@ -958,13 +1295,10 @@ where
                 if let Some(conversion) = mapping.struct_mappings.get(&object_info.ty) {
                     let old_layout = object_info.ty.value_layout();
                     let src = unsafe { object_info.data.ptr };
-                    let dest = unsafe {
-                        NonNull::new_unchecked(std::alloc::alloc_zeroed(
-                            conversion.new_ty.value_layout(),
-                        ))
-                    };
+                    let dest = heap.alloc_zeroed(conversion.new_ty.value_layout());
 
                     map_struct(
+                        heap,
                         &mut new_allocations,
                         &mapping.struct_mappings,
                         &conversion.field_mapping,
@@ -972,12 +1306,17 @@ where
                         dest,
                     );
 
-                    unsafe { std::alloc::dealloc(src.as_ptr(), old_layout) };
+                    unsafe { heap.dealloc(src, old_layout) };
 
                     object_info.set(ObjectInfo {
                         data: ObjectInfoData { ptr: dest },
                         roots: object_info.roots,
+                        pins: object_info.pins,
+                        #[cfg(feature = "gc-debug")]
+                        canary: object_info.canary,
                         color: object_info.color,
+                        #[cfg(feature = "gc-tracing")]
+                        backtrace: object_info.backtrace.clone(),
                         ty: conversion.new_ty.clone(),
                     });
                 }
@@ -1014,6 +1353,7 @@ where
                             resolve_struct_to_struct_edit(&old_element_ty, &new_element_ty, 0);
 
                         map_array(
+                            heap,
                             &mut new_allocations,
                             &mapping.struct_mappings,
                             unsafe {
@@ -1031,6 +1371,12 @@ where
                 }
             });
 
+        {
+            let mut stats = self.stats.write();
+            stats.allocated_memory =
+                stats.allocated_memory - memory_before_mapping + objects_memory(&objects);
+        }
+
         // Retroactively store newly allocated objects
         // This cannot be done while mapping because we hold a mutable reference to
         // objects
@@ -1048,6 +1394,11 @@ where
     }
 }
 
+/// Returns the number of bytes that the values of `objects` occupy.
+fn objects_memory(objects: &HashMap<GcPtr, Pin<Box<ObjectInfo>>>) -> usize {
+    objects.values().map(|object| object.layout().size()).sum()
+}
+
 /// Coloring used in the Mark Sweep phase.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Color {
@@ -1068,8 +1419,14 @@ enum Color {
 struct ObjectInfo {
     pub data: ObjectInfoData,
     pub roots: u32,
+    pub pins: u32,
+    #[cfg(feature = "gc-debug")]
+    pub canary: u64,
     pub color: Color,
     pub ty: Type,
+    /// The backtrace of the allocation of the object
+    #[cfg(feature = "gc-tracing")]
+    pub backtrace: Arc<Backtrace>,
 }
 
 #[repr(C)]
@@ -1121,13 +1478,8 @@ impl ObjectInfo {
                 self.ty.value_layout()
             }
             TypeKind::Array(array) => {
-                let elem_count = unsafe { self.data.array.as_ref().capacity };
-                let elem_layout = repeat_layout(array.element_type().value_layout(), elem_count)
-                    .expect("unable to determine layout of array elements");
-                let (layout, _) = Layout::new::<ArrayHeader>()
-                    .extend(elem_layout)
-                    .expect("unable to determine layout of array");
-                layout
+                let capacity = unsafe { self.data.array.as_ref().capacity };
+                array_layout(&array.element_type(), capacity)
             }
         }
     }
@@ -0,0 +1,114 @@
+//! Validation of the garbage collector's heap, which is enabled by the
+//! `gc-debug` feature. Instead of silently corrupting memory, misuse of the
+//! garbage collector results in a panic that describes the problem.
+
+use std::{collections::HashMap, pin::Pin, ptr::NonNull};
+
+use super::{ArrayHandle, Color, ObjectInfo};
+use crate::gc::{Array, GcPtr, TypeTrace};
+
+/// The canary that is stored in the header of a live object.
+pub(super) const ALIVE_CANARY: u64 = 0x4d55_4e5f_4f42_4a54;
+
+/// The canary that is stored in the header of a collected object.
+pub(super) const DEAD_CANARY: u64 = 0xdead_dead_dead_dead;
+
+type Objects = HashMap<GcPtr, Pin<Box<ObjectInfo>>>;
+
+/// Stores the header of a collected object in the `graveyard`, so any use of a
+/// stale handle to the object can be detected.
+pub(super) fn bury(graveyard: &mut Objects, handle: GcPtr, mut object: Pin<Box<ObjectInfo>>) {
+    // Safety: the header is not moved
+    let object_info = unsafe { object.as_mut().get_unchecked_mut() };
+    object_info.canary = DEAD_CANARY;
+
+    // Make sure that Mun code that dereferences a stale handle crashes, instead of accessing
+    // memory that is reused by another object.
+    object_info.data.ptr = NonNull::dangling();
+
+    graveyard.insert(handle, object);
+}
+
+/// Panics if `handle` does not refer to a live object.
+pub(super) fn validate_handle(objects: &Objects, graveyard: &Objects, handle: GcPtr) {
+    if let Some(object) = objects.get(&handle) {
+        validate_canary(handle, object);
+    } else if graveyard.contains_key(&handle) {
+        panic!(
+            "use after free: {handle:?} refers to an object that was deallocated by a previous \
+            garbage collection. Root objects that must outlive a garbage collection."
+        );
+    } else {
+        panic!("invalid handle: {handle:?} does not refer to an object of this garbage collector");
+    }
+}
+
+/// Panics because `object` references `reference`, which is not a live object.
+pub(super) fn invalid_reference(
+    objects: &Objects,
+    graveyard: &Objects,
+    handle: GcPtr,
+    reference: GcPtr,
+) -> ! {
+    let ty = objects
+        .get(&handle)
+        .map(|object| object.ty.name().to_owned())
+        .unwrap_or_default();
+
+    if graveyard.contains_key(&reference) {
+        panic!(
+            "use after free: object {handle:?} of type `{ty}` references a collected object \
+            {reference:?}, which was deallocated by a previous garbage collection. Root objects \
+            that must outlive a garbage collection before storing them in another object."
+        );
+    } else {
+        panic!(
+            "heap corruption: object {handle:?} of type `{ty}` references {reference:?}, which \
+            does not refer to an object of this garbage collector"
+        );
+    }
+}
+
+/// Validates all live `objects` after a garbage collection, panicking if any
+/// corruption is detected.
+pub(super) fn validate_heap(objects: &Objects, graveyard: &Objects) {
+    for (handle, object) in objects {
+        validate_canary(*handle, object);
+
+        assert_eq!(
+            object.color,
+            Color::White,
+            "heap corruption: object {handle:?} of type `{}` was not reset after the garbage \
+            collection",
+            object.ty.name()
+        );
+
+        if object.ty.is_array() {
+            let array = ArrayHandle {
+                obj: NonNull::from(&**object),
+            };
+            assert!(
+                array.length() <= array.capacity(),
+                "heap corruption: array {handle:?} of type `{}` has a length of {}, which exceeds \
+                its capacity of {}",
+                object.ty.name(),
+                array.length(),
+                array.capacity()
+            );
+        }
+
+        for reference in object.ty.trace(*handle) {
+            if !objects.contains_key(&reference) {
+                invalid_reference(objects, graveyard, *handle, reference);
+            }
+        }
+    }
+}
+
+/// Panics if the canary in the header of `object` was overwritten.
+fn validate_canary(handle: GcPtr, object: &ObjectInfo) {
+    assert_eq!(
+        object.canary, ALIVE_CANARY,
+        "heap corruption: the header of object {handle:?} was overwritten"
+    );
+}
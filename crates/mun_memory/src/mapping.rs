@@ -1,9 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    ptr::NonNull,
+};
 
 use itertools::Itertools;
 use mun_abi::Guid;
 
 use crate::{
+    cast,
     diff::{compute_struct_diff, FieldDiff, StructDiff},
     gc::GcPtr,
     r#type::Type,
@@ -315,6 +319,35 @@ pub unsafe fn field_mapping(old_ty: &Type, new_ty: &Type, diff: &[FieldDiff]) ->
     }
 }
 
+/// Maps a single primitive value of type `old_ty` at `src` to a value of type
+/// `new_ty` at `dest`, following the same rules as struct fields: identical
+/// types are copied and widening conversions are cast. Returns `false` if the
+/// value could not be mapped, in which case `dest` is left untouched.
+///
+/// # Safety
+///
+/// `src` must point to a valid value of type `old_ty` and `dest` must point to
+/// writable memory that can hold a value of type `new_ty`.
+pub unsafe fn map_primitive(
+    old_ty: &Type,
+    new_ty: &Type,
+    src: NonNull<u8>,
+    dest: NonNull<u8>,
+) -> bool {
+    if !old_ty.is_primitive() || !new_ty.is_primitive() {
+        return false;
+    }
+
+    match resolve_edit(old_ty, new_ty, 0) {
+        Action::Copy { size, .. } => {
+            std::ptr::copy_nonoverlapping(src.as_ptr(), dest.as_ptr(), size);
+            true
+        }
+        Action::Cast { old_ty, .. } => cast::try_cast_from_to(old_ty, new_ty.clone(), src, dest),
+        _ => false,
+    }
+}
+
 pub fn resolve_edit(old_ty: &Type, new_ty: &Type, old_offset: usize) -> Action {
     match &old_ty.kind() {
         TypeKind::Primitive(old_guid) => {
@@ -1172,9 +1172,10 @@ impl StructTypeBuilder {
             abi::Guid::from_str(&guid_string)
         };
 
+        // Like a C struct, the size of the struct is a multiple of its alignment
         Type::new_struct(
             self.name,
-            self.layout,
+            self.layout.pad_to_align(),
             guid,
             self.fields
                 .into_iter()
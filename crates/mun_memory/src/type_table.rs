@@ -28,6 +28,11 @@ impl TypeTable {
         }
     }
 
+    /// Returns all concrete types in the type table, in no particular order.
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.concrete.values()
+    }
+
     /// Inserts `type_info` into the type table for a type that has static type
     /// info.
     ///
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use mun_memory::{
-    gc::{Event, GcRootPtr, GcRuntime, MarkSweep},
+    gc::{Array, Event, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep},
     HasStaticType,
 };
 
@@ -68,3 +68,185 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+/// Returns the amount of memory that is reserved for the size class of `i64`s.
+fn reserved_i64_memory(runtime: &MarkSweep<EventAggregator<Event>>) -> usize {
+    runtime
+        .stats()
+        .size_classes
+        .iter()
+        .find(|size_class| size_class.block_size >= std::mem::size_of::<i64>())
+        .unwrap()
+        .reserved_memory
+}
+
+#[test]
+fn compact() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+
+    // Fragment the heap by keeping only a few objects that are spread over many
+    // chunks
+    let mut survivors = (0..16384)
+        .filter_map(|index| {
+            let handle = runtime.alloc(i64::type_info());
+            (index % 512 == 0).then(|| {
+                let mut root = GcRootPtr::new(&runtime, handle);
+                unsafe { *root.deref_mut::<i64>() = index };
+                root
+            })
+        })
+        .collect::<Vec<_>>();
+    runtime.collect();
+    let fragmented_memory = reserved_i64_memory(&runtime);
+
+    // Pin the first survivor, which resides in the first chunk
+    let pinned = survivors.remove(0);
+    runtime.pin(pinned.handle());
+    let pinned_data = unsafe { pinned.deref::<i64>() };
+    let moved = survivors.pop().unwrap();
+    let moved_data = unsafe { moved.deref::<i64>() };
+
+    let mut array = runtime.alloc_array(&i64::type_info().array_type(), 4);
+    let array_handle = array.as_raw();
+    let array_root = GcRootPtr::new(&runtime, array_handle);
+    unsafe { array.set_length(2) };
+    let allocated_memory = runtime.stats().allocated_memory;
+
+    runtime.observer().take_all();
+    assert!(runtime.compact() > 2);
+
+    // Objects are moved to the lowest free memory and empty chunks are released
+    assert!(reserved_i64_memory(&runtime) < fragmented_memory);
+
+    // Pinned objects are not moved, relocated objects retain their values
+    assert_eq!(unsafe { pinned.deref::<i64>() }, pinned_data);
+    assert_eq!(unsafe { *pinned.deref::<i64>() }, 0);
+    assert_ne!(unsafe { moved.deref::<i64>() }, moved_data);
+    assert_eq!(unsafe { *moved.deref::<i64>() }, 16384 - 512);
+    for (index, survivor) in survivors.iter().enumerate() {
+        assert_eq!(
+            unsafe { *survivor.deref::<i64>() },
+            (index as i64 + 1) * 512
+        );
+    }
+
+    // Unused capacity of arrays is released
+    let array = runtime.array(array_root.handle()).unwrap();
+    assert_eq!(array.length(), 2);
+    assert_eq!(array.capacity(), 2);
+    assert!(runtime.stats().allocated_memory < allocated_memory);
+
+    let events = runtime.observer().take_all();
+    assert!(events.contains(&Event::Relocation(moved.handle())));
+    assert!(events.contains(&Event::Relocation(array_handle)));
+    assert!(!events.contains(&Event::Relocation(pinned.handle())));
+
+    // Compacting a compacted heap doesn't move anything
+    assert_eq!(runtime.compact(), 0);
+
+    runtime.unpin(pinned.handle());
+    assert_eq!(unsafe { *pinned.deref::<i64>() }, 0);
+}
+
+#[test]
+fn collection_history() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    assert!(runtime.collection_history().is_empty());
+
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    let _garbage = runtime.alloc(i64::type_info());
+    runtime.collect();
+
+    let history = runtime.collection_history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].allocated_memory, 2 * std::mem::size_of::<i64>());
+    assert_eq!(history[0].surviving_memory, std::mem::size_of::<i64>());
+    assert_eq!(history[0].reclaimed_memory, std::mem::size_of::<i64>());
+
+    drop(rooted);
+    runtime.collect();
+
+    let history = runtime.collection_history();
+    assert_eq!(history.len(), 2);
+    assert!(history[1].started_at >= history[0].started_at + history[0].pause);
+    assert_eq!(history[1].allocated_memory, 0);
+    assert_eq!(history[1].allocation_rate(), 0.0);
+    assert_eq!(history[1].surviving_memory, 0);
+    assert_eq!(history[1].reclaimed_memory, std::mem::size_of::<i64>());
+
+    // Only the most recent collections are kept
+    for _ in 0..200 {
+        runtime.collect();
+    }
+    assert_eq!(runtime.collection_history().len(), 128);
+}
+
+#[test]
+fn memory_limit() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    assert_eq!(runtime.memory_limit(), None);
+
+    runtime.set_memory_limit(Some(std::mem::size_of::<i64>()));
+    assert_eq!(runtime.memory_limit(), Some(std::mem::size_of::<i64>()));
+
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    assert!(!runtime.exceeds_memory_limit());
+    let _garbage = runtime.alloc(i64::type_info());
+    assert!(runtime.exceeds_memory_limit());
+
+    // Collecting the garbage brings the memory back within the limit
+    runtime.collect();
+    assert!(!runtime.exceeds_memory_limit());
+
+    runtime.set_memory_limit(None);
+    assert_eq!(runtime.memory_limit(), None);
+    drop(rooted);
+}
+
+#[test]
+fn size_class_stats() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+
+    let _small = runtime.alloc(i64::type_info());
+    let _large = runtime.alloc_array(&i64::type_info().array_type(), 1024);
+
+    let stats = runtime.stats();
+    let allocated_blocks: usize = stats
+        .size_classes
+        .iter()
+        .map(|size_class| size_class.allocated_blocks)
+        .sum();
+    assert_eq!(allocated_blocks, 1);
+    assert_eq!(stats.large_objects.allocated_objects, 1);
+    assert!(stats.large_objects.allocated_memory >= 1024 * std::mem::size_of::<i64>());
+
+    runtime.collect();
+
+    let stats = runtime.stats();
+    assert!(stats
+        .size_classes
+        .iter()
+        .all(|size_class| size_class.allocated_blocks == 0));
+    assert_eq!(stats.large_objects.allocated_objects, 0);
+    assert_eq!(stats.allocated_memory, 0);
+}
+
+#[test]
+fn rooted_objects() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+
+    let _handle = runtime.alloc(i64::type_info());
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+
+    let rooted_objects = runtime.rooted_objects();
+    assert_eq!(rooted_objects.len(), 1);
+    assert_eq!(&rooted_objects[0].ty, i64::type_info());
+    assert_eq!(rooted_objects[0].roots, 1);
+    assert_eq!(
+        rooted_objects[0].backtrace.is_some(),
+        cfg!(feature = "gc-tracing")
+    );
+
+    rooted.unroot();
+    assert!(runtime.rooted_objects().is_empty());
+}
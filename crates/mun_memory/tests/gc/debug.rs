@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use mun_memory::{
+    gc::{Event, GcPtr, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep},
+    type_table::TypeTable,
+    HasStaticType,
+};
+
+use super::util::EventAggregator;
+use crate::fake_struct;
+
+struct FooObject {
+    bar: GcPtr,
+}
+
+#[test]
+#[should_panic(expected = "use after free")]
+fn root_collected_object() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let handle = runtime.alloc(i64::type_info());
+
+    runtime.collect();
+
+    runtime.root(handle);
+}
+
+#[test]
+#[should_panic(expected = "references a collected object")]
+fn reference_collected_object() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info));
+    let bar = runtime.alloc(&bar_type_info);
+
+    // Let foo reference itself, so bar is not referenced
+    unsafe {
+        (*foo_ptr.deref_mut::<FooObject>()).bar = foo_ptr.handle();
+    }
+
+    // Collect garbage, bar is not referenced so it is collected
+    runtime.collect();
+
+    // Assign the stale bar to foo.bar
+    unsafe {
+        (*foo_ptr.deref_mut::<FooObject>()).bar = bar;
+    }
+
+    runtime.collect();
+}
@@ -1,4 +1,6 @@
 mod alloc;
+#[cfg(feature = "gc-debug")]
+mod debug;
 mod structs;
 #[macro_use]
 mod util;
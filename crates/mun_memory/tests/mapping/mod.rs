@@ -0,0 +1,61 @@
+use std::ptr::NonNull;
+
+use mun_memory::{mapping::map_primitive, HasStaticType, StructTypeBuilder};
+
+fn map<A: HasStaticType, B: HasStaticType>(old: &A, new: &mut B) -> bool {
+    unsafe {
+        map_primitive(
+            A::type_info(),
+            B::type_info(),
+            NonNull::from(old).cast(),
+            NonNull::from(new).cast(),
+        )
+    }
+}
+
+#[test]
+fn map_identical_primitive() {
+    let mut new = 0u64;
+    assert!(map(&1234u64, &mut new));
+    assert_eq!(new, 1234);
+}
+
+#[test]
+fn map_widening_primitive() {
+    let mut new = 0i64;
+    assert!(map(&-5i32, &mut new));
+    assert_eq!(new, -5);
+
+    let mut new = 0f64;
+    assert!(map(&1.5f32, &mut new));
+    assert_eq!(new, 1.5);
+}
+
+#[test]
+fn map_incompatible_primitive() {
+    let mut new = 7u8;
+    assert!(!map(&1000u32, &mut new));
+    assert_eq!(new, 7);
+
+    let mut new = 3.0f32;
+    assert!(!map(&true, &mut new));
+    assert_eq!(new, 3.0);
+}
+
+#[test]
+fn map_struct_is_not_primitive() {
+    let struct_ty = StructTypeBuilder::new("Foo")
+        .add_field("a", i32::type_info().clone())
+        .finish();
+    let old = 5i32;
+    let mut new = 0i32;
+    assert!(!unsafe {
+        map_primitive(
+            &struct_ty,
+            i32::type_info(),
+            NonNull::from(&old).cast(),
+            NonNull::from(&mut new).cast(),
+        )
+    });
+    assert_eq!(new, 0);
+}
@@ -1,4 +1,5 @@
 mod diff;
 mod gc;
+mod mapping;
 #[macro_use]
 mod util;
@@ -0,0 +1,5 @@
+//! Runs the code of a [`mun_playground::Playground`] in a separate process.
+
+fn main() -> std::io::Result<()> {
+    mun_playground::run_worker()
+}
@@ -0,0 +1,236 @@
+//! Compiles and runs a single Mun source file in a sandbox, e.g. to power a
+//! web playground or an in-editor scratchpad.
+//!
+//! Mun code can only interact with its host through extern functions. The
+//! playground does not provide any, so the code that it runs has no access to
+//! the filesystem or any other resource of the host. The code is compiled in
+//! memory and run in a separate worker process, the `mun-playground-worker`
+//! executable, which is killed when it exceeds the time limit. The memory and
+//! time that an invocation may use are limited through [`Limits`].
+//!
+//! ```no_run
+//! use mun_playground::{Limits, Playground};
+//!
+//! let playground = Playground::new(Limits::default());
+//! let output = playground
+//!     .run("pub fn main() -> i32 { 6 * 7 }")
+//!     .expect("the code should run");
+//! assert_eq!(output.value.as_deref(), Some("42"));
+//! ```
+
+mod worker;
+
+use std::{
+    io::{Cursor, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use mun_compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+use mun_runtime::RuntimeError;
+
+pub use crate::worker::run_worker;
+use crate::worker::Report;
+
+/// The name of the function that is invoked by [`Playground::run`]
+pub const ENTRY_POINT: &str = "main";
+
+/// The resources that a single invocation of Mun code may use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum duration of the invocation, including the start of the
+    /// worker process but excluding compilation
+    pub time: Duration,
+    /// The maximum number of bytes that objects allocated by the Mun code
+    /// may occupy
+    pub memory: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(5),
+            memory: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// The result of successfully running Mun code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Output {
+    /// The warnings emitted by the compiler. Empty if there were none.
+    pub diagnostics: String,
+    /// The value returned by the entry point, or `None` if it doesn't return
+    /// a value.
+    pub value: Option<String>,
+}
+
+/// An error that prevented Mun code from running to completion.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The code contains errors. Contains the emitted diagnostics.
+    #[error("{0}")]
+    Compile(String),
+    /// The code doesn't contain a public `main` function.
+    #[error("could not find a public `{ENTRY_POINT}` function")]
+    MissingEntryPoint,
+    /// The `main` function takes arguments or returns a type that cannot be
+    /// displayed.
+    #[error("the `{ENTRY_POINT}` function must not take arguments and must return nothing or a primitive type, found: {0}")]
+    UnsupportedEntryPoint(String),
+    /// The compiled code could not be loaded, e.g. because it uses an extern
+    /// function.
+    #[error("could not load the compiled code: {0}")]
+    Load(String),
+    /// The Mun code panicked or exceeded the memory limit.
+    #[error(transparent)]
+    Runtime(RuntimeError),
+    /// The Mun code did not finish within the time limit, so its worker
+    /// process was killed.
+    #[error("the code did not finish within {0:?}")]
+    TimeLimitExceeded(Duration),
+    /// An error that is not caused by the Mun code, e.g. an IO error.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+/// The name of the executable that runs the code of a [`Playground`]
+pub const WORKER_NAME: &str = "mun-playground-worker";
+
+/// The interval at which a [`Playground`] checks whether its worker process
+/// finished
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Compiles and runs Mun code within [`Limits`].
+#[derive(Clone, Debug)]
+pub struct Playground {
+    limits: Limits,
+    worker: PathBuf,
+}
+
+impl Default for Playground {
+    fn default() -> Self {
+        Self::new(Limits::default())
+    }
+}
+
+impl Playground {
+    /// Constructs a playground that runs code within the specified `limits`.
+    /// The code is run by the `mun-playground-worker` executable in the
+    /// directory of the current executable.
+    pub fn new(limits: Limits) -> Self {
+        let worker = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+            .join(WORKER_NAME)
+            .with_extension(std::env::consts::EXE_EXTENSION);
+        Self { limits, worker }
+    }
+
+    /// Sets the executable that runs the code. The executable receives the
+    /// memory limit as its only argument and has to call [`run_worker`].
+    pub fn with_worker(mut self, worker: impl Into<PathBuf>) -> Self {
+        self.worker = worker.into();
+        self
+    }
+
+    /// Returns the limits within which code is run.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Compiles `source` and invokes its `main` function.
+    pub fn run(&self, source: &str) -> Result<Output, Error> {
+        let config = Config {
+            optimization_lvl: mun_compiler::OptimizationLevel::None,
+            overflow_checks: true,
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: source.to_owned(),
+        };
+        let (driver, _file_id) =
+            Driver::with_file(config, input).map_err(|e| Error::Internal(e.to_string()))?;
+
+        let mut diagnostics = Vec::new();
+        let has_errors = driver
+            .emit_diagnostics(&mut Cursor::new(&mut diagnostics), DisplayColor::Disable)
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let diagnostics = String::from_utf8_lossy(&diagnostics).into_owned();
+        if has_errors {
+            return Err(Error::Compile(diagnostics));
+        }
+
+        // The source consists of a single module, which is compiled into a single
+        // assembly
+        let (_, library) = driver
+            .emit_assemblies_to_memory()
+            .map_err(|e| Error::Internal(e.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Internal(String::from("no assembly was built")))?;
+
+        let value = self.run_worker(&library)?;
+        Ok(Output { diagnostics, value })
+    }
+
+    /// Runs `library` in a worker process, which is killed if it doesn't
+    /// finish within the time limit.
+    fn run_worker(&self, library: &[u8]) -> Result<Option<String>, Error> {
+        let mut child = Command::new(&self.worker)
+            .arg(self.limits.memory.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "could not start worker '{}': {e}",
+                    self.worker.display()
+                ))
+            })?;
+        let start = Instant::now();
+
+        // The worker reads the library before it starts running, so this doesn't
+        // block for long. A worker that fails to read the library reports so itself.
+        // Dropping the handle closes the standard input of the worker.
+        let mut stdin = child.stdin.take().expect("stdin of the worker is piped");
+        let _ = stdin.write_all(library);
+        drop(stdin);
+
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|e| Error::Internal(e.to_string()))?
+            {
+                break status;
+            }
+            if start.elapsed() >= self.limits.time {
+                // The worker might have finished in the meantime, in which case killing it
+                // fails, which is fine.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::TimeLimitExceeded(self.limits.time));
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let mut report = String::new();
+        child
+            .stdout
+            .take()
+            .expect("stdout of the worker is piped")
+            .read_to_string(&mut report)
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let report: Report = serde_json::from_str(&report).map_err(|_e| {
+            Error::Internal(format!(
+                "the code was aborted, the worker exited with {status}"
+            ))
+        })?;
+        report.into()
+    }
+}
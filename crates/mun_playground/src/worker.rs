@@ -0,0 +1,168 @@
+//! The worker process that runs compiled Mun code on behalf of a
+//! [`Playground`](crate::Playground).
+//!
+//! The worker receives the memory limit as its only argument and the contents
+//! of the munlib through its standard input. It writes a single [`Report`] as
+//! JSON to its standard output and exits.
+
+use std::io::{Read, Write};
+
+use mun_runtime::{PanicLocation, Runtime, RuntimeBuilder, RuntimeError};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{Error, ENTRY_POINT};
+
+/// The path that identifies the library that is loaded from memory
+const LIBRARY_PATH: &str = "mod.munlib";
+
+/// The outcome of running Mun code in a worker process, as communicated to
+/// the playground.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) enum Report {
+    Value(Option<String>),
+    MissingEntryPoint,
+    UnsupportedEntryPoint(String),
+    Load(String),
+    Panic {
+        message: String,
+        file: String,
+        line: u32,
+        column: u32,
+    },
+    MemoryLimitExceeded {
+        limit: usize,
+    },
+    Internal(String),
+}
+
+impl From<Result<Option<String>, Error>> for Report {
+    fn from(result: Result<Option<String>, Error>) -> Self {
+        match result {
+            Ok(value) => Report::Value(value),
+            Err(Error::MissingEntryPoint) => Report::MissingEntryPoint,
+            Err(Error::UnsupportedEntryPoint(message)) => Report::UnsupportedEntryPoint(message),
+            Err(Error::Load(message)) => Report::Load(message),
+            Err(Error::Runtime(RuntimeError::Panic { message, location })) => Report::Panic {
+                message,
+                file: location.file,
+                line: location.line,
+                column: location.column,
+            },
+            Err(Error::Runtime(RuntimeError::MemoryLimitExceeded { limit })) => {
+                Report::MemoryLimitExceeded { limit }
+            }
+            Err(error) => Report::Internal(error.to_string()),
+        }
+    }
+}
+
+impl From<Report> for Result<Option<String>, Error> {
+    fn from(report: Report) -> Self {
+        match report {
+            Report::Value(value) => Ok(value),
+            Report::MissingEntryPoint => Err(Error::MissingEntryPoint),
+            Report::UnsupportedEntryPoint(message) => Err(Error::UnsupportedEntryPoint(message)),
+            Report::Load(message) => Err(Error::Load(message)),
+            Report::Panic {
+                message,
+                file,
+                line,
+                column,
+            } => Err(Error::Runtime(RuntimeError::Panic {
+                message,
+                location: PanicLocation { file, line, column },
+            })),
+            Report::MemoryLimitExceeded { limit } => {
+                Err(Error::Runtime(RuntimeError::MemoryLimitExceeded { limit }))
+            }
+            Report::Internal(message) => Err(Error::Internal(message)),
+        }
+    }
+}
+
+/// Runs the worker, which is the entry point of the `mun-playground-worker`
+/// executable. Hosts that ship their own executable instead can call this
+/// function from their `main` function when they are started as a worker,
+/// see [`Playground::with_worker`](crate::Playground::with_worker).
+pub fn run_worker() -> std::io::Result<()> {
+    let report = Report::from(run());
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, &report)?;
+    stdout.flush()
+}
+
+/// Loads the munlib from the standard input and invokes its entry point.
+fn run() -> Result<Option<String>, Error> {
+    let memory_limit = std::env::args()
+        .nth(1)
+        .and_then(|limit| limit.parse().ok())
+        .ok_or_else(|| Error::Internal(String::from("missing memory limit argument")))?;
+
+    let mut library = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut library)
+        .map_err(|e| Error::Internal(e.to_string()))?;
+
+    // Safety: the library was built by the Mun compiler of the playground
+    let runtime = unsafe {
+        RuntimeBuilder::from_bytes(LIBRARY_PATH, library)
+            .memory_limit(memory_limit)
+            .disable_file_watching()
+            .finish()
+    }
+    .map_err(|e| Error::Load(e.to_string()))?;
+
+    invoke_entry_point(&runtime)
+}
+
+/// Invokes the entry point of `runtime` and returns its value formatted as a
+/// string.
+fn invoke_entry_point(runtime: &Runtime) -> Result<Option<String>, Error> {
+    let definition = runtime
+        .get_function_definition(ENTRY_POINT)
+        .ok_or(Error::MissingEntryPoint)?;
+    let signature = &definition.prototype.signature;
+    if !signature.arg_types.is_empty() {
+        return Err(Error::UnsupportedEntryPoint(format!(
+            "{} arguments",
+            signature.arg_types.len()
+        )));
+    }
+
+    let return_type = &signature.return_type;
+    if return_type.equals::<()>() {
+        return runtime
+            .invoke::<(), ()>(ENTRY_POINT, ())
+            .map(|()| None)
+            .map_err(|e| invoke_error(e.runtime_error(), e.to_string()));
+    }
+
+    macro_rules! invoke_returning {
+        ($($ty:ty),+) => {
+            $(
+                if return_type.equals::<$ty>() {
+                    return runtime
+                        .invoke::<$ty, ()>(ENTRY_POINT, ())
+                        .map(|value| Some(value.to_string()))
+                        .map_err(|e| invoke_error(e.runtime_error(), e.to_string()));
+                }
+            )+
+        };
+    }
+    invoke_returning!(
+        bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+    );
+
+    Err(Error::UnsupportedEntryPoint(format!(
+        "returns `{}`",
+        return_type.name()
+    )))
+}
+
+/// Converts a failed invocation into an [`Error`].
+fn invoke_error(runtime_error: Option<&RuntimeError>, message: String) -> Error {
+    match runtime_error {
+        Some(error) => Error::Runtime(error.clone()),
+        None => Error::Internal(message),
+    }
+}
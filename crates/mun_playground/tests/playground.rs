@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use mun_playground::{Error, Limits, Playground};
+use mun_runtime::RuntimeError;
+
+/// Constructs a playground that runs code with the worker of this crate
+fn playground(limits: Limits) -> Playground {
+    Playground::new(limits).with_worker(env!("CARGO_BIN_EXE_mun-playground-worker"))
+}
+
+#[test]
+fn returns_value() {
+    let output = playground(Limits::default())
+        .run(
+            r#"
+    pub fn main() -> i32 {
+        let mut sum = 0;
+        let mut i = 1;
+        while i <= 10 {
+            sum += i;
+            i += 1;
+        }
+        sum
+    }
+    "#,
+        )
+        .expect("the code should run");
+
+    assert_eq!(output.value.as_deref(), Some("55"));
+    assert!(output.diagnostics.is_empty());
+}
+
+#[test]
+fn returns_nothing() {
+    let output = playground(Limits::default())
+        .run("pub fn main() {}")
+        .expect("the code should run");
+
+    assert_eq!(output.value, None);
+}
+
+#[test]
+fn compile_error() {
+    let error = playground(Limits::default())
+        .run("pub fn main() -> i32 { true }")
+        .expect_err("the code should not compile");
+
+    assert!(
+        matches!(error, Error::Compile(diagnostics) if diagnostics.contains("expected `i32`, found `bool`"))
+    );
+}
+
+#[test]
+fn missing_entry_point() {
+    let error = playground(Limits::default())
+        .run("pub fn foo() {}")
+        .expect_err("the code should not run");
+
+    assert!(matches!(error, Error::MissingEntryPoint));
+}
+
+#[test]
+fn unsupported_entry_point() {
+    let error = playground(Limits::default())
+        .run("pub fn main(a: i32) -> i32 { a }")
+        .expect_err("the code should not run");
+
+    assert!(matches!(error, Error::UnsupportedEntryPoint(_)));
+}
+
+#[test]
+fn extern_functions_are_not_available() {
+    let error = playground(Limits::default())
+        .run(
+            r#"
+    extern fn read_file() -> i32;
+
+    pub fn main() -> i32 { read_file() }
+    "#,
+        )
+        .expect_err("the code should not link");
+
+    assert!(matches!(error, Error::Load(_)));
+}
+
+#[test]
+fn panic() {
+    let error = playground(Limits::default())
+        .run(r#"pub fn main() { panic("oops") }"#)
+        .expect_err("the code should panic");
+
+    assert!(matches!(error, Error::Runtime(RuntimeError::Panic { .. })));
+}
+
+#[test]
+fn memory_limit() {
+    let playground = playground(Limits {
+        memory: 4096,
+        ..Limits::default()
+    });
+    let error = playground
+        .run(
+            r#"
+    pub fn main() {
+        loop {
+            let _values = [1, 2, 3, 4];
+        }
+    }
+    "#,
+        )
+        .expect_err("the code should exceed the memory limit");
+
+    assert!(matches!(
+        error,
+        Error::Runtime(RuntimeError::MemoryLimitExceeded { limit: 4096 })
+    ));
+}
+
+#[test]
+fn time_limit() {
+    let playground = playground(Limits {
+        time: Duration::from_millis(100),
+        ..Limits::default()
+    });
+    let error = playground
+        .run("pub fn main() { loop {} }")
+        .expect_err("the code should exceed the time limit");
+
+    assert!(matches!(error, Error::TimeLimitExceeded(time) if time == Duration::from_millis(100)));
+}
@@ -1,4 +1,4 @@
-pub use manifest::{Manifest, ManifestMetadata, PackageId};
+pub use manifest::{LintLevel, Manifest, ManifestMetadata, PackageId, Profile, Workspace};
 pub use package::Package;
 pub use project_manifest::ProjectManifest;
 
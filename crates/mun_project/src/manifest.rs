@@ -1,4 +1,11 @@
-use std::{fmt, path::Path, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::Path,
+    str::FromStr,
+};
+
+use serde_derive::{Deserialize, Serialize};
 
 mod toml;
 
@@ -8,8 +15,47 @@ mod toml;
 pub struct Manifest {
     package_id: PackageId,
     metadata: ManifestMetadata,
+    features: BTreeMap<String, Vec<String>>,
+    lints: BTreeMap<String, LintLevel>,
+    profile: Profile,
+    workspace: Option<Workspace>,
+}
+
+/// The level of a lint as configured in the `[lints]` section of a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+
+    /// The lint is reported as a warning
+    Warn,
+
+    /// The lint is reported as an error
+    Deny,
+}
+
+/// The settings with which a package is compiled, as configured in the
+/// `[profile]` section of a manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// Whether debug information is generated
+    pub debug: bool,
 }
 
+/// The packages that are built together with a package, as configured in the
+/// `[workspace]` section of a manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Workspace {
+    /// The paths of the directories that contain the manifests of the member
+    /// packages, relative to the directory of the manifest of the workspace
+    pub members: Vec<String>,
+}
+
+/// The name of the feature that is enabled unless default features are
+/// disabled
+const DEFAULT_FEATURE: &str = "default";
+
 /// General metadata for a package.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ManifestMetadata {
@@ -52,6 +98,61 @@ impl Manifest {
     pub fn metadata(&self) -> &ManifestMetadata {
         &self.metadata
     }
+
+    /// Returns the features of the package, mapped to the other features that
+    /// they enable.
+    pub fn features(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.features
+    }
+
+    /// Returns the levels of the lints that are configured for the package,
+    /// keyed by the name of the lint.
+    pub fn lints(&self) -> &BTreeMap<String, LintLevel> {
+        &self.lints
+    }
+
+    /// Returns the settings with which the package is compiled
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns the workspace of which this package is the root, if the
+    /// manifest has a `[workspace]` section
+    pub fn workspace(&self) -> Option<&Workspace> {
+        self.workspace.as_ref()
+    }
+
+    /// Returns all features that are enabled when the specified `features` are
+    /// requested, including the features that they enable. If
+    /// `default_features` is `true`, the `default` feature is requested as
+    /// well, if the package has one.
+    pub fn resolve_features<S: AsRef<str>>(
+        &self,
+        features: &[S],
+        default_features: bool,
+    ) -> Result<BTreeSet<String>, anyhow::Error> {
+        let mut requested = features
+            .iter()
+            .map(|feature| feature.as_ref().to_owned())
+            .collect::<Vec<_>>();
+        if default_features && self.features.contains_key(DEFAULT_FEATURE) {
+            requested.push(DEFAULT_FEATURE.to_owned());
+        }
+
+        let mut enabled = BTreeSet::new();
+        while let Some(feature) = requested.pop() {
+            let enabled_features = self.features.get(&feature).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "package `{}` does not have the feature `{feature}`",
+                    self.name()
+                )
+            })?;
+            if enabled.insert(feature) {
+                requested.extend(enabled_features.iter().cloned());
+            }
+        }
+        Ok(enabled)
+    }
 }
 
 impl PackageId {
@@ -87,7 +188,7 @@ impl std::str::FromStr for Manifest {
 mod tests {
     use std::str::FromStr;
 
-    use crate::Manifest;
+    use crate::{LintLevel, Manifest, Profile, Workspace};
 
     #[test]
     fn parse() {
@@ -108,5 +209,146 @@ mod tests {
         );
         assert_eq!(manifest.metadata().authors, vec!["Mun Team"]);
         assert_eq!(format!("{}", manifest.package_id()), "test v0.2.0");
+        assert!(manifest.features().is_empty());
+        assert!(manifest.lints().is_empty());
+        assert_eq!(manifest.profile(), &Profile::default());
+        assert_eq!(manifest.workspace(), None);
+    }
+
+    #[test]
+    fn profile() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [profile]
+        debug = true
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.profile(), &Profile { debug: true });
+    }
+
+    #[test]
+    fn workspace() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [workspace]
+        members = ["game", "tools/editor"]
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.workspace(),
+            Some(&Workspace {
+                members: vec![String::from("game"), String::from("tools/editor")]
+            })
+        );
+    }
+
+    #[test]
+    fn empty_workspace_member() {
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [workspace]
+        members = [" "]
+        "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "workspace member path cannot be an empty string"
+        );
+    }
+
+    #[test]
+    fn lints() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [lints]
+        dead_code = "allow"
+        unused_variables = "deny"
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.lints().iter().collect::<Vec<_>>(),
+            vec![
+                (&String::from("dead_code"), &LintLevel::Allow),
+                (&String::from("unused_variables"), &LintLevel::Deny)
+            ]
+        );
+    }
+
+    #[test]
+    fn features() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [features]
+        default = ["debug_draw"]
+        debug_draw = ["logging"]
+        logging = []
+        profiling = []
+        "#,
+        )
+        .unwrap();
+
+        let resolve = |features: &[&str], default_features| {
+            manifest
+                .resolve_features(features, default_features)
+                .map(|features| features.into_iter().collect::<Vec<_>>())
+        };
+        assert_eq!(
+            resolve(&[], true).unwrap(),
+            vec!["debug_draw", "default", "logging"]
+        );
+        assert_eq!(resolve(&[], false).unwrap(), Vec::<String>::new());
+        assert_eq!(resolve(&["profiling"], false).unwrap(), vec!["profiling"]);
+        assert_eq!(
+            resolve(&["unknown"], true).unwrap_err().to_string(),
+            "package `test` does not have the feature `unknown`"
+        );
+    }
+
+    #[test]
+    fn unknown_enabled_feature() {
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [features]
+        default = ["debug_draw"]
+        "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "feature `default` enables `debug_draw`, which is not a feature of the package"
+        );
     }
 }
@@ -1,12 +1,18 @@
+use std::collections::BTreeMap;
+
 use serde_derive::{Deserialize, Serialize};
 
-use super::{Manifest, ManifestMetadata, PackageId};
+use super::{LintLevel, Manifest, ManifestMetadata, PackageId, Profile, Workspace};
 
 /// A manifest as specified in a mun.toml file.
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TomlManifest {
     package: TomlProject,
+    features: Option<BTreeMap<String, Vec<String>>>,
+    lints: Option<BTreeMap<String, LintLevel>>,
+    profile: Option<TomlProfile>,
+    workspace: Option<TomlWorkspace>,
 }
 
 /// Represents the `package` section of a mun.toml file.
@@ -17,6 +23,18 @@ pub struct TomlProject {
     authors: Option<Vec<String>>,
 }
 
+/// Represents the `profile` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TomlProfile {
+    debug: Option<bool>,
+}
+
+/// Represents the `workspace` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TomlWorkspace {
+    members: Vec<String>,
+}
+
 impl TomlManifest {
     /// Convert this toml manifest into a "real" manifest.
     pub fn into_real_manifest(self) -> Result<Manifest, anyhow::Error> {
@@ -25,6 +43,33 @@ impl TomlManifest {
             anyhow::bail!("package name cannot be an empty string");
         }
 
+        let features = self.features.unwrap_or_default();
+        for (feature, enabled_features) in features.iter() {
+            if feature.trim().is_empty() {
+                anyhow::bail!("feature name cannot be an empty string");
+            }
+            for enabled_feature in enabled_features {
+                if !features.contains_key(enabled_feature) {
+                    anyhow::bail!(
+                        "feature `{feature}` enables `{enabled_feature}`, which is not a feature of the package"
+                    );
+                }
+            }
+        }
+
+        let workspace = self.workspace.map(|workspace| Workspace {
+            members: workspace
+                .members
+                .iter()
+                .map(|member| member.trim().to_owned())
+                .collect(),
+        });
+        if let Some(workspace) = &workspace {
+            if workspace.members.iter().any(String::is_empty) {
+                anyhow::bail!("workspace member path cannot be an empty string");
+            }
+        }
+
         Ok(Manifest {
             package_id: PackageId {
                 name: name.to_owned(),
@@ -33,6 +78,15 @@ impl TomlManifest {
             metadata: ManifestMetadata {
                 authors: self.package.authors.unwrap_or_default(),
             },
+            features,
+            lints: self.lints.unwrap_or_default(),
+            profile: Profile {
+                debug: self
+                    .profile
+                    .and_then(|profile| profile.debug)
+                    .unwrap_or_default(),
+            },
+            workspace,
         })
     }
 }
@@ -5,7 +5,7 @@ use std::{
 
 use semver::Version;
 
-use crate::{Manifest, PackageId};
+use crate::{Manifest, PackageId, MANIFEST_FILENAME};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Package {
@@ -65,6 +65,43 @@ impl Package {
     pub fn source_directory(&self) -> PathBuf {
         self.root().join("src")
     }
+
+    /// Loads the member packages of the workspace of which this package is
+    /// the root. Returns an empty list if the package does not define a
+    /// workspace.
+    pub fn workspace_members(&self) -> anyhow::Result<Vec<Package>> {
+        let Some(workspace) = self.manifest.workspace() else {
+            return Ok(Vec::new());
+        };
+
+        let mut members: Vec<Package> = Vec::with_capacity(workspace.members.len());
+        for member in &workspace.members {
+            let manifest_path = self.root().join(member).join(MANIFEST_FILENAME);
+            let package = Package::from_file(&manifest_path).map_err(|e| {
+                anyhow::anyhow!(
+                    "could not load workspace member '{}': {:#}",
+                    manifest_path.display(),
+                    e
+                )
+            })?;
+            if package.manifest.workspace().is_some() {
+                anyhow::bail!(
+                    "workspace member `{}` cannot itself define a workspace",
+                    package.name()
+                );
+            }
+            if package.name() == self.name()
+                || members.iter().any(|other| other.name() == package.name())
+            {
+                anyhow::bail!(
+                    "the workspace contains multiple packages named `{}`",
+                    package.name()
+                );
+            }
+            members.push(package);
+        }
+        Ok(members)
+    }
 }
 
 impl fmt::Display for Package {
@@ -4,7 +4,7 @@ use anyhow::bail;
 use mun_paths::{AbsPath, AbsPathBuf};
 use rustc_hash::FxHashSet;
 
-use crate::MANIFEST_FILENAME;
+use crate::{Manifest, MANIFEST_FILENAME};
 
 /// A wrapper around a path to a mun project
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -29,6 +29,30 @@ impl ProjectManifest {
         }
     }
 
+    /// Returns the manifests of the members of the workspace that is defined
+    /// by this manifest. Members that cannot be found are skipped; they are
+    /// reported when the packages are loaded.
+    fn workspace_members(&self) -> Vec<ProjectManifest> {
+        let Ok(manifest) = Manifest::from_file(&self.path) else {
+            return Vec::new();
+        };
+        let Some(root) = self.path.parent() else {
+            return Vec::new();
+        };
+        manifest
+            .workspace()
+            .map(|workspace| {
+                workspace
+                    .members
+                    .iter()
+                    .map(|member| root.join(member).join(MANIFEST_FILENAME))
+                    .filter(|path| path.is_file())
+                    .map(|path| ProjectManifest { path })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Find all project manifests in the given directory
     pub fn discover(path: impl AsRef<AbsPath>) -> io::Result<Vec<ProjectManifest>> {
         Ok(read_dir(path.as_ref())?
@@ -48,11 +72,16 @@ impl ProjectManifest {
             .collect())
     }
 
-    /// Find all project manifests in a collection of paths
+    /// Find all project manifests in a collection of paths, including the
+    /// manifests of the members of the workspaces that they define
     pub fn discover_all(paths: impl Iterator<Item = impl AsRef<AbsPath>>) -> Vec<ProjectManifest> {
         let mut project_manifests = paths
             .filter_map(|path| ProjectManifest::discover(path).ok())
             .flatten()
+            .flat_map(|project_manifest| {
+                let members = project_manifest.workspace_members();
+                std::iter::once(project_manifest).chain(members)
+            })
             .collect::<FxHashSet<_>>()
             .into_iter()
             .collect::<Vec<_>>();
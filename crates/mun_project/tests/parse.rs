@@ -1,6 +1,7 @@
-use std::{path::Path, str::FromStr};
+use std::{convert::TryFrom, path::Path, str::FromStr};
 
-use mun_project::{Manifest, Package};
+use mun_paths::AbsPathBuf;
+use mun_project::{Manifest, Package, ProjectManifest};
 use semver::Version;
 
 #[test]
@@ -26,3 +27,51 @@ fn package_from_file() {
     let source_dir = package.source_directory();
     assert_eq!(source_dir, manifest_path.parent().unwrap().join("src"));
 }
+
+#[test]
+fn workspace_members() {
+    let manifest_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/resources/workspace/mun.toml");
+    let package = Package::from_file(&manifest_path).expect("could not load package");
+    let members = package
+        .workspace_members()
+        .expect("could not load workspace members");
+    assert_eq!(
+        members.iter().map(Package::name).collect::<Vec<_>>(),
+        vec!["game", "editor"]
+    );
+    assert_eq!(
+        members[1].root(),
+        manifest_path.parent().unwrap().join("tools/editor")
+    );
+}
+
+#[test]
+fn no_workspace_members() {
+    let manifest_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/resources/mun.toml");
+    let package = Package::from_file(manifest_path).expect("could not load package");
+    assert!(package.workspace_members().unwrap().is_empty());
+}
+
+#[test]
+fn discover_workspace_members() {
+    let workspace_dir = AbsPathBuf::try_from(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/resources/workspace"),
+    )
+    .unwrap();
+    let discovered = ProjectManifest::discover_all(std::iter::once(&workspace_dir));
+    assert_eq!(
+        discovered,
+        vec![
+            ProjectManifest {
+                path: workspace_dir.join("game/mun.toml")
+            },
+            ProjectManifest {
+                path: workspace_dir.join("mun.toml")
+            },
+            ProjectManifest {
+                path: workspace_dir.join("tools/editor/mun.toml")
+            },
+        ]
+    );
+}
@@ -18,10 +18,17 @@ use crate::{
 /// Represents a Mun struct pointer.
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct RawStruct(GcPtr);
+pub struct RawStruct(pub(crate) GcPtr);
 
 impl RawStruct {
     /// Returns a pointer to the struct memory.
+    ///
+    /// # Safety
+    ///
+    /// Dereferencing might cause undefined behavior. The pointer is
+    /// invalidated when the struct is relocated by
+    /// [`Runtime::gc_collect_and_compact`], unless the struct is pinned with
+    /// [`GcRuntime::pin`].
     pub unsafe fn get_ptr(&self) -> *const u8 {
         self.0.deref()
     }
@@ -194,6 +201,152 @@ impl<'s> StructRef<'s> {
     }
 }
 
+/// An error that occurs when constructing a struct with
+/// [`Runtime::construct_struct`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConstructStructError {
+    /// There is no type with the requested name.
+    #[error("Type `{type_name}` does not exist.")]
+    TypeNotFound {
+        /// The name of the requested type
+        type_name: String,
+    },
+    /// The requested type exists, but it is not a struct.
+    #[error("Type `{type_name}` is not a struct.")]
+    NotAStruct {
+        /// The name of the requested type
+        type_name: String,
+    },
+    /// The struct does not contain a field with the requested name.
+    #[error("Struct `{struct_name}` does not contain field `{field_name}`.")]
+    FieldNotFound {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the requested field
+        field_name: String,
+    },
+    /// The type of a value does not match the type of the field it was
+    /// provided for.
+    #[error(
+        "Mismatched types for `{struct_name}::{field_name}`. Expected: `{}`. Found: `{}`.",
+        .expected.name(),
+        .found.name()
+    )]
+    FieldTypeMismatch {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the field
+        field_name: String,
+        /// The type of the field
+        expected: Type,
+        /// The type of the value that was provided
+        found: Type,
+    },
+    /// No value was provided for a field of the struct.
+    #[error("Missing value for field `{struct_name}::{field_name}`.")]
+    MissingField {
+        /// The name of the struct
+        struct_name: String,
+        /// The name of the field
+        field_name: String,
+    },
+}
+
+/// Writes the value of a field to the memory of a newly allocated struct.
+type FieldInitializer<'s> = Box<dyn FnOnce(&StructRef<'s>) + 's>;
+
+/// Constructs an instance of a Mun struct from Rust values. Created with
+/// [`Runtime::construct_struct`].
+///
+/// The struct is only allocated by [`StructBuilder::finish`], after the values
+/// of all of its fields have been provided, so the garbage collector never
+/// observes a partially initialized struct.
+pub struct StructBuilder<'s> {
+    runtime: &'s Runtime,
+    type_info: Type,
+    fields: Vec<Option<FieldInitializer<'s>>>,
+}
+
+impl<'s> StructBuilder<'s> {
+    /// Creates a builder for a struct of the specified type.
+    pub(crate) fn new(runtime: &'s Runtime, type_info: Type) -> Self {
+        // Safety: `construct_struct` only creates builders for struct types.
+        let field_count = type_info.as_struct().unwrap().fields().len();
+        Self {
+            runtime,
+            type_info,
+            fields: std::iter::repeat_with(|| None).take(field_count).collect(),
+        }
+    }
+
+    /// Sets the value of the field corresponding to the specified
+    /// `field_name`.
+    pub fn set<T: ArgumentReflection + Marshal<'s> + 's>(
+        mut self,
+        field_name: &str,
+        value: T,
+    ) -> Result<Self, ConstructStructError> {
+        // Safety: `construct_struct` only creates builders for struct types.
+        let struct_info = self.type_info.as_struct().unwrap();
+
+        let (index, field_info) = struct_info
+            .fields()
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.name() == field_name)
+            .ok_or_else(|| ConstructStructError::FieldNotFound {
+                struct_name: self.type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
+            })?;
+
+        let field_type = field_info.ty();
+        let value_type = value.type_info(self.runtime);
+        if field_type != value_type {
+            return Err(ConstructStructError::FieldTypeMismatch {
+                struct_name: self.type_info.name().to_owned(),
+                field_name: field_name.to_owned(),
+                expected: field_type,
+                found: value_type,
+            });
+        }
+
+        let offset = field_info.offset();
+        self.fields[index] = Some(Box::new(move |instance: &StructRef<'s>| {
+            // SAFETY: The offset in the ABI is always valid.
+            let field_ptr = unsafe { instance.get_field_ptr_unchecked::<T::MunType>(offset) };
+            Marshal::marshal_to_ptr(value, field_ptr, &field_type);
+        }));
+        Ok(self)
+    }
+
+    /// Allocates the struct and initializes its fields. Fails if the value of
+    /// a field was not set.
+    pub fn finish(self) -> Result<StructRef<'s>, ConstructStructError> {
+        // Safety: `construct_struct` only creates builders for struct types.
+        let struct_info = self.type_info.as_struct().unwrap();
+        if let Some(field) = struct_info
+            .fields()
+            .iter()
+            .zip(&self.fields)
+            .find_map(|(field, initializer)| initializer.is_none().then_some(field))
+        {
+            return Err(ConstructStructError::MissingField {
+                struct_name: self.type_info.name().to_owned(),
+                field_name: field.name().to_owned(),
+            });
+        }
+
+        let instance = StructRef::new(
+            RawStruct(self.runtime.gc.alloc(&self.type_info)),
+            self.runtime,
+        );
+        for initializer in self.fields.into_iter().flatten() {
+            initializer(&instance);
+        }
+        Ok(instance)
+    }
+}
+
 impl ArgumentReflection for StructRef<'_> {
     fn type_info(&self, _runtime: &Runtime) -> Type {
         self.type_info()
@@ -7,7 +7,7 @@ use mun_memory::{
 
 use crate::{
     garbage_collector::GcRootPtr, ArgumentReflection, GarbageCollector, Marshal,
-    ReturnTypeReflection, Runtime,
+    ReturnTypeReflection, Runtime, Value,
 };
 
 /// Represents a Mun array pointer.
@@ -20,7 +20,10 @@ impl RawArray {
     ///
     /// # Safety
     ///
-    /// Dereferencing might cause undefined behavior
+    /// Dereferencing might cause undefined behavior. The pointer is
+    /// invalidated when the array is relocated by
+    /// [`Runtime::gc_collect_and_compact`], unless the array is pinned with
+    /// [`GcRuntime::pin`].
     pub unsafe fn get_ptr(&self) -> *const u8 {
         self.0.deref()
     }
@@ -36,7 +39,7 @@ pub struct ArrayRef<'a, T> {
     _phantom: PhantomData<T>,
 }
 
-impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
+impl<'array, T> ArrayRef<'array, T> {
     /// Creates a `ArrayRef` that wraps a raw Mun struct.
     pub(crate) fn new<'runtime>(raw: RawArray, runtime: &'runtime Runtime) -> Self
     where
@@ -88,8 +91,31 @@ impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
             .expect("the internal handle does not refer to an array")
             .capacity()
     }
+}
+
+/// Pins an array for as long as its elements are accessed through a pointer,
+/// so compacting the heap doesn't invalidate the pointer.
+struct PinnedArray<'r> {
+    gc: &'r GarbageCollector,
+    handle: GcPtr,
+}
 
-    /// Returns an iterator to iterate over the elements of the array.
+impl<'r> PinnedArray<'r> {
+    fn new(gc: &'r GarbageCollector, handle: GcPtr) -> Self {
+        gc.pin(handle);
+        Self { gc, handle }
+    }
+}
+
+impl Drop for PinnedArray<'_> {
+    fn drop(&mut self) {
+        self.gc.unpin(self.handle);
+    }
+}
+
+impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
+    /// Returns an iterator to iterate over the elements of the array. The array
+    /// is pinned while the iterator exists.
     pub fn iter(&self) -> impl Iterator<Item = T> + 'array
     where
         T: 'array,
@@ -102,9 +128,34 @@ impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
             .expect("type of the array value must be an array");
         let element_ty = handle.element_type();
         let runtime = self.runtime;
-        handle
-            .elements()
-            .map(move |element_ptr| T::marshal_from_ptr(element_ptr.cast(), runtime, &element_ty))
+        let pinned = PinnedArray::new(&runtime.gc, self.raw.0);
+        handle.elements().map(move |element_ptr| {
+            let _pinned = &pinned;
+            T::marshal_from_ptr(element_ptr.cast(), runtime, &element_ty)
+        })
+    }
+}
+
+impl<'array> ArrayRef<'array, Value<'array>> {
+    /// Returns an iterator to iterate over the elements of the array as
+    /// dynamically typed values. The array is pinned while the iterator
+    /// exists.
+    pub fn values(&self) -> impl Iterator<Item = Value<'array>> + 'array {
+        let handle = self
+            .runtime
+            .gc
+            .as_ref()
+            .array(self.raw.0)
+            .expect("type of the array value must be an array");
+        let element_ty = handle.element_type();
+        let runtime = self.runtime;
+        let pinned = PinnedArray::new(&runtime.gc, self.raw.0);
+        handle.elements().map(move |element_ptr| {
+            let _pinned = &pinned;
+            // Safety: The element is of the element type of the array
+            unsafe { Value::from_ptr(element_ptr, runtime, &element_ty) }
+                .expect("the element type of an array is always supported")
+        })
     }
 }
 
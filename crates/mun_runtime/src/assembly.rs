@@ -1,9 +1,11 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     ffi::c_void,
     io,
     path::{Path, PathBuf},
+    ptr::NonNull,
     sync::Arc,
+    time::SystemTime,
 };
 
 use itertools::Itertools;
@@ -11,12 +13,14 @@ use log::error;
 use mun_abi as abi;
 use mun_libloader::{MunLibrary, TempLibrary};
 use mun_memory::{
-    mapping::{Mapping, MemoryMapper},
+    mapping::{self, Mapping, MemoryMapper},
     type_table::TypeTable,
     Type,
 };
 
-use crate::{garbage_collector::GarbageCollector, DispatchTable};
+use crate::{
+    garbage_collector::GarbageCollector, metadata::AssemblyMetadata, DispatchTable, RuntimeError,
+};
 
 /// An error that occurs upon loading of a Mun library.
 #[derive(Debug, thiserror::Error)]
@@ -46,6 +50,27 @@ pub enum LinkError {
     /// Failed to link assembly's types
     #[error("Failed to link types: {0:?}")]
     MissingTypes(Vec<String>),
+    /// The runtime did not load an assembly from the path
+    #[error("No assembly was loaded from '{}'", .0.display())]
+    UnknownAssembly(PathBuf),
+    /// The version of a dependency does not satisfy the version requirement of
+    /// the assembly that depends on it
+    #[error(
+        "Assembly '{}' requires version {requirement} of '{}', but found {}",
+        dependent.display(),
+        dependency.display(),
+        version.as_deref().unwrap_or("no version")
+    )]
+    MismatchedDependencyVersion {
+        /// Path of the dependent assembly
+        dependent: PathBuf,
+        /// Path of the dependency
+        dependency: PathBuf,
+        /// The version requirement of the dependent assembly
+        requirement: String,
+        /// The version of the dependency
+        version: Option<String>,
+    },
 }
 
 /// An error that occurs upon linking of a Mun function prototype.
@@ -83,6 +108,19 @@ pub enum LinkFunctionsError {
         /// Function names for which dependencies were missing
         functions: Vec<String>,
     },
+    /// The host provided multiple functions with the same name
+    #[error("Multiple host functions are named `{fn_name}`")]
+    DuplicateHostFunction {
+        /// Function name
+        fn_name: String,
+    },
+    /// An assembly defines a function with the same name as a function that is
+    /// provided by the host
+    #[error("Function `{fn_name}` is defined by an assembly but also provided by the host")]
+    HostFunctionCollision {
+        /// Function name
+        fn_name: String,
+    },
 }
 
 /// An assembly is a hot reloadable compilation unit, consisting of one or more
@@ -92,6 +130,8 @@ pub struct Assembly {
     library: TempLibrary,
     info: abi::AssemblyInfo<'static>,
     allocator: Arc<GarbageCollector>,
+    loaded_at: SystemTime,
+    content_hash: [u8; 16],
 }
 
 impl Assembly {
@@ -113,8 +153,44 @@ impl Assembly {
     ///
     /// See [`libloading::Library::new`] for more information.
     pub unsafe fn load(library_path: &Path, gc: Arc<GarbageCollector>) -> Result<Self, LoadError> {
-        let mut library = MunLibrary::new(library_path)?;
+        let loaded_at = SystemTime::now();
+        let content_hash = extendhash::md5::compute_hash(&std::fs::read(library_path)?);
+        let library = MunLibrary::new(library_path)?;
+        Self::from_library(library, library_path, gc, loaded_at, content_hash)
+    }
 
+    /// Loads an assembly and its information from the contents of a munlib in
+    /// memory, e.g. a munlib that was read from an asset pack or downloaded
+    /// over the network. The operating system can only load libraries from
+    /// files, so the contents are written to a temporary file first.
+    ///
+    /// The `library_path` identifies the assembly in the runtime and is used to
+    /// resolve the paths of its dependencies, but the file doesn't need to
+    /// exist.
+    ///
+    /// # Safety
+    ///
+    /// See [`Assembly::load`] for more information.
+    pub unsafe fn load_from_bytes(
+        bytes: &[u8],
+        library_path: &Path,
+        gc: Arc<GarbageCollector>,
+    ) -> Result<Self, LoadError> {
+        let loaded_at = SystemTime::now();
+        let content_hash = extendhash::md5::compute_hash(bytes);
+        let library = MunLibrary::from_bytes(bytes)?;
+        Self::from_library(library, library_path, gc, loaded_at, content_hash)
+    }
+
+    /// Constructs an assembly from a loaded munlib, after checking that its ABI
+    /// version is supported by the runtime.
+    unsafe fn from_library(
+        mut library: MunLibrary,
+        library_path: &Path,
+        gc: Arc<GarbageCollector>,
+        loaded_at: SystemTime,
+        content_hash: [u8; 16],
+    ) -> Result<Self, LoadError> {
         let version = library.get_abi_version();
         if abi::ABI_VERSION != version {
             return Err(LoadError::MismatchedAbiVersions {
@@ -131,6 +207,8 @@ impl Assembly {
             library_path: library_path.to_path_buf(),
             library: library.into_inner(),
             allocator: gc,
+            loaded_at,
+            content_hash,
         };
 
         Ok(assembly)
@@ -289,7 +367,7 @@ impl Assembly {
 
         // Insert all assemblies' functions into the dispatch table
         for assembly in assemblies.iter() {
-            dispatch_table.insert_module(&assembly.info().symbols, &type_table);
+            dispatch_table.insert_module(&assembly.info().symbols, &type_table)?;
         }
 
         let functions_to_link = assemblies
@@ -307,6 +385,50 @@ impl Assembly {
         Ok((dispatch_table, type_table))
     }
 
+    /// Copies the values of the globals of `old_assembly` into the globals of
+    /// `new_assembly` with the same name. Globals whose type changed are
+    /// converted when possible; otherwise they keep their initial value.
+    fn migrate_globals(old_assembly: &Assembly, new_assembly: &Assembly, type_table: &TypeTable) {
+        let old_globals: HashMap<&str, &abi::GlobalDefinition<'_>> = old_assembly
+            .info()
+            .symbols
+            .globals()
+            .iter()
+            .map(|global| (global.name(), global))
+            .collect();
+
+        for new_global in new_assembly.info().symbols.globals() {
+            let Some(old_global) = old_globals.get(new_global.name()) else {
+                continue;
+            };
+
+            let (Some(old_ty), Some(new_ty)) = (
+                type_table.find_type_info_by_id(&old_global.type_id),
+                type_table.find_type_info_by_id(&new_global.type_id),
+            ) else {
+                continue;
+            };
+
+            let (Some(src), Some(dest)) = (
+                NonNull::new(old_global.value_ptr.cast::<u8>()),
+                NonNull::new(new_global.value_ptr.cast::<u8>()),
+            ) else {
+                continue;
+            };
+
+            // SAFETY: Both pointers refer to the storage of a global with the
+            // corresponding type, and both libraries are loaded.
+            if !unsafe { mapping::map_primitive(&old_ty, &new_ty, src, dest) } {
+                error!(
+                    "Could not migrate the value of global `{}`, its type changed from `{}` to `{}`.",
+                    new_global.name(),
+                    old_ty,
+                    new_ty
+                );
+            }
+        }
+    }
+
     /// Tries to link the `unlinked_assemblies`, resulting in a new
     /// [`DispatchTable`] on success. This leaves the original
     /// `dispatch_table` intact, in case of linking errors.
@@ -316,13 +438,27 @@ impl Assembly {
         dispatch_table: &DispatchTable,
         type_table: &TypeTable,
     ) -> Result<(DispatchTable, TypeTable), LinkError> {
-        let mut dependencies: HashMap<String, Vec<String>> = unlinked_assemblies
+        // Only the dependencies that are linked along with an assembly have to be linked
+        // before it
+        let library_paths: HashSet<&Path> = unlinked_assemblies
+            .values()
+            .map(|assembly| assembly.library_path.as_path())
+            .collect();
+        let mut dependencies: HashMap<PathBuf, Vec<PathBuf>> = unlinked_assemblies
             .values()
             .map(|assembly| {
-                let info = &assembly.info;
-                let dependencies: Vec<String> = info.dependencies().map(From::from).collect();
+                let dependencies: Vec<PathBuf> = assembly
+                    .info
+                    .dependencies()
+                    .map(|dependency| {
+                        crate::resolve_dependency_path(&assembly.library_path, dependency)
+                    })
+                    .filter(|path| {
+                        path != &assembly.library_path && library_paths.contains(path.as_path())
+                    })
+                    .collect();
 
-                (info.symbols.path().to_owned(), dependencies)
+                (assembly.library_path.clone(), dependencies)
             })
             .filter(|(_, dependencies)| !dependencies.is_empty())
             .collect();
@@ -339,17 +475,28 @@ impl Assembly {
         // Clone the dispatch table, such that we can roll back if linking fails
         let mut dispatch_table = dispatch_table.clone();
 
-        while let Some(mut entry) = assemblies_to_link.pop_front() {
-            let (ref old_assembly, ref mut new_assembly) = entry;
+        // Functions are linked after the functions of all assemblies have been inserted
+        // into the dispatch table, because assemblies can depend on each other.
+        let mut assemblies_to_link_functions = Vec::with_capacity(assemblies_to_link.len());
 
-            let new_path = new_assembly.info().symbols.path().to_owned();
+        // The number of assemblies that were postponed since the last assembly was
+        // linked
+        let mut postponed = 0;
+        while let Some(entry) = assemblies_to_link.pop_front() {
+            let (old_assembly, new_assembly) = entry;
 
-            // Are there any dependencies that still need to be loaded?
-            if dependencies.contains_key(&new_path) {
-                assemblies_to_link.push_back(entry);
+            let new_path = new_assembly.library_path.clone();
+
+            // Are there any dependencies that still need to be loaded? If every remaining
+            // assembly is waiting for another one, they depend on each other and are linked
+            // in any order.
+            if dependencies.contains_key(&new_path) && postponed <= assemblies_to_link.len() {
+                assemblies_to_link.push_back((old_assembly, new_assembly));
+                postponed += 1;
 
                 continue;
             }
+            postponed = 0;
 
             let old_types: Option<(&Assembly, Vec<Type>)> = old_assembly.map(|old_assembly| {
                 // Remove the old assemblies' types from the type table
@@ -395,34 +542,23 @@ impl Assembly {
                 // LUT of allocated objects with deleted types?
             }
 
+            // Carry over the values of globals from the old assembly
+            if let Some(old_assembly) = old_assembly {
+                Assembly::migrate_globals(old_assembly, new_assembly, &type_table);
+            }
+
             // Remove the old assembly's functions from the dispatch table
             if let Some(old_assembly) = old_assembly {
                 dispatch_table.remove_module(&old_assembly.info.symbols);
             }
 
             // Insert the new assembly's functions into the dispatch table
-            dispatch_table.insert_module(&new_assembly.info.symbols, &type_table);
+            dispatch_table.insert_module(&new_assembly.info.symbols, &type_table)?;
 
-            let functions_to_link = new_assembly
-                .info_mut()
-                .dispatch_table
-                .iter_mut()
-                // Only take signatures into account that do *not* yet have a function pointer
-                // assigned by the compiler. When an assembly is compiled it
-                // "pre-fills" its internal dispatch table with pointers to
-                // self-referencing functions.
-                .filter(|(ptr, _)| ptr.is_null());
-
-            // Update the dispatch tables of the assemblies themselves based on our global
-            // dispatch table. This will effectively link the function
-            // definitions of the assemblies together. It also modifies the
-            // internal state of the assemblies.
-            //
-            // Note that linking may fail because for instance functions remaining unlinked
-            // (missing) or the signature of a function doesnt match.
-            Assembly::link_all_functions(&dispatch_table, &type_table, functions_to_link)?;
+            assemblies_to_link_functions.push(new_assembly);
 
             // Remove this assembly from the dependencies
+            dependencies.remove(&new_path);
             dependencies
                 .values_mut()
                 .for_each(|dependencies| dependencies.retain(|path| path != &new_path));
@@ -431,6 +567,24 @@ impl Assembly {
             dependencies.retain(|_, dependencies| !dependencies.is_empty());
         }
 
+        let functions_to_link = assemblies_to_link_functions
+            .into_iter()
+            .flat_map(|assembly| assembly.info_mut().dispatch_table.iter_mut())
+            // Only take signatures into account that do *not* yet have a function pointer
+            // assigned by the compiler. When an assembly is compiled it
+            // "pre-fills" its internal dispatch table with pointers to
+            // self-referencing functions.
+            .filter(|(ptr, _)| ptr.is_null());
+
+        // Update the dispatch tables of the assemblies themselves based on our global
+        // dispatch table. This will effectively link the function
+        // definitions of the assemblies together. It also modifies the
+        // internal state of the assemblies.
+        //
+        // Note that linking may fail because for instance functions remaining unlinked
+        // (missing) or the signature of a function doesnt match.
+        Assembly::link_all_functions(&dispatch_table, &type_table, functions_to_link)?;
+
         let mut newly_linked = HashMap::new();
         std::mem::swap(unlinked_assemblies, &mut newly_linked);
 
@@ -450,6 +604,43 @@ impl Assembly {
         Ok((dispatch_table, type_table))
     }
 
+    /// Invokes the assembly's `init` function, if it has one. This must only be
+    /// called after the assembly has been linked.
+    pub(crate) fn init(&self) {
+        self.invoke_lifecycle_fn("init", self.info.init_fn());
+    }
+
+    /// Invokes the assembly's `shutdown` function, if it has one.
+    pub(crate) fn shutdown(&self) {
+        self.invoke_lifecycle_fn("shutdown", self.info.shutdown_fn());
+    }
+
+    /// Invokes a lifecycle function. If the Mun code panics, the error is
+    /// logged instead of being propagated to the host.
+    fn invoke_lifecycle_fn(&self, name: &str, fn_ptr: Option<*const c_void>) {
+        let Some(fn_ptr) = fn_ptr else {
+            return;
+        };
+
+        // SAFETY: The compiler only records lifecycle functions that take no arguments
+        // and return nothing.
+        if let Err(error) = unsafe { invoke_catching_panics(fn_ptr) } {
+            error!(
+                "The `{name}` function of assembly '{}' failed: {error}",
+                self.library_path.display()
+            );
+        }
+    }
+
+    /// Returns the functions of the assembly that are marked with the `#[test]`
+    /// attribute.
+    pub fn tests(&self) -> impl Iterator<Item = TestFunction<'_>> {
+        self.info
+            .tests()
+            .iter()
+            .map(|definition| TestFunction { definition })
+    }
+
     /// Returns the assembly's information.
     pub fn info(&self) -> &abi::AssemblyInfo<'_> {
         &self.info
@@ -461,14 +652,71 @@ impl Assembly {
         unsafe { std::mem::transmute(&mut self.info) }
     }
 
+    /// Returns the metadata of the assembly: the functions and types that it
+    /// exports and the assemblies that it depends on.
+    pub fn metadata(&self) -> AssemblyMetadata {
+        AssemblyMetadata::new(self.library_path.clone(), &self.info)
+    }
+
     /// Returns the path corresponding to the assembly's library.
     pub fn library_path(&self) -> &Path {
         self.library_path.as_path()
     }
 
+    /// Returns the time at which the assembly's library was loaded.
+    pub fn loaded_at(&self) -> SystemTime {
+        self.loaded_at
+    }
+
+    /// Returns the MD5 hash of the contents of the assembly's library, which
+    /// identifies the version of the code that was loaded.
+    pub fn content_hash(&self) -> [u8; 16] {
+        self.content_hash
+    }
+
     /// Converts the `Assembly` into a `TempLibrary`, consuming the input in the
     /// process.
     pub fn into_library(self) -> TempLibrary {
         self.library
     }
 }
+
+/// A function of an assembly that is marked with the `#[test]` attribute.
+#[derive(Clone, Copy)]
+pub struct TestFunction<'a> {
+    definition: &'a abi::TestDefinition,
+}
+
+impl TestFunction<'_> {
+    /// Returns the fully qualified name of the test.
+    pub fn name(&self) -> &str {
+        self.definition.name()
+    }
+
+    /// Runs the test. If the Mun code panics, e.g. because an `assert` failed,
+    /// the error is returned instead of being propagated to the host.
+    pub fn run(&self) -> Result<(), RuntimeError> {
+        // SAFETY: The compiler only records test functions that take no arguments and
+        // return nothing.
+        unsafe { invoke_catching_panics(self.definition.fn_ptr) }
+    }
+}
+
+/// Invokes the Mun function at `fn_ptr` and returns the error if the Mun code
+/// panics.
+///
+/// # Safety
+///
+/// `fn_ptr` must point to a Mun function that takes no arguments and returns
+/// nothing.
+unsafe fn invoke_catching_panics(fn_ptr: *const c_void) -> Result<(), RuntimeError> {
+    let function: extern "C-unwind" fn() = std::mem::transmute(fn_ptr);
+    // `extern "C-unwind"` function pointers don't implement `FnOnce`
+    #[allow(clippy::redundant_closure)]
+    std::panic::catch_unwind(|| function()).map_err(|payload| {
+        match payload.downcast::<RuntimeError>() {
+            Ok(error) => *error,
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    })
+}
@@ -0,0 +1,112 @@
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Weak},
+};
+
+use crate::{
+    check_return_type, garbage_collector::GarbageCollector, FunctionDefinition, InvokeArgs,
+    InvokeErrorKind, Marshal, ReturnTypeReflection, Runtime, RuntimeError,
+};
+
+/// An error that occurs when binding or invoking a [`BoundFunction`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum BoundFunctionError {
+    /// The function does not exist, is not public, or its signature doesn't
+    /// match the bound argument and return types.
+    #[error(transparent)]
+    Signature(InvokeErrorKind),
+    /// An error occurred while executing the Mun function.
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
+
+/// A handle to a Mun function of which the signature has been validated
+/// against the argument types `ArgTypes` and the return type `ReturnType`,
+/// constructed with [`Runtime::bind`].
+///
+/// Invoking a bound function directly calls the function, without looking up
+/// its name or validating its signature like [`Runtime::invoke`] does. Only the
+/// types of arguments that depend on their value, e.g. structs and arrays, are
+/// validated for every call. When the assemblies of the runtime have been
+/// reloaded, the function is resolved and validated again on the next
+/// invocation.
+pub struct BoundFunction<ArgTypes, ReturnType> {
+    function_name: String,
+    function_info: Arc<FunctionDefinition>,
+    check_arguments: bool,
+
+    /// The garbage collector of the runtime that the function was resolved
+    /// with, which identifies the runtime
+    gc: Weak<GarbageCollector>,
+
+    /// The generation of the runtime when the function was resolved
+    generation: u64,
+
+    _signature: PhantomData<fn(ArgTypes) -> ReturnType>,
+}
+
+impl<ArgTypes: InvokeArgs, ReturnType: ReturnTypeReflection> BoundFunction<ArgTypes, ReturnType> {
+    /// Resolves the function called `function_name` in `runtime`.
+    pub(crate) fn new(runtime: &Runtime, function_name: &str) -> Result<Self, BoundFunctionError> {
+        let function_info = Self::resolve(runtime, function_name)?;
+        Ok(Self {
+            function_name: function_name.to_owned(),
+            function_info,
+            check_arguments: ArgTypes::has_dynamic_types(),
+            gc: Arc::downgrade(&runtime.gc),
+            generation: runtime.generation(),
+            _signature: PhantomData,
+        })
+    }
+
+    /// Returns the name of the function.
+    pub fn name(&self) -> &str {
+        &self.function_name
+    }
+
+    /// Invokes the function with the specified `arguments`. If the assemblies
+    /// of `runtime` have been reloaded since the function was resolved, or if
+    /// the function was bound with another runtime, the function is resolved
+    /// again first.
+    pub fn invoke<'runtime, 'ret>(
+        &mut self,
+        runtime: &'runtime Runtime,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, BoundFunctionError>
+    where
+        ReturnType: Marshal<'ret> + 'ret,
+        'runtime: 'ret,
+    {
+        if self.generation != runtime.generation() || self.gc.as_ptr() != Arc::as_ptr(&runtime.gc) {
+            self.function_info = Self::resolve(runtime, &self.function_name)?;
+            self.gc = Arc::downgrade(&runtime.gc);
+            self.generation = runtime.generation();
+        }
+
+        if self.check_arguments {
+            arguments
+                .can_invoke(runtime, &self.function_info.prototype.signature)
+                .map_err(BoundFunctionError::Signature)?;
+        }
+
+        // Safety: The signature of the function was validated when it was resolved
+        // and the function pointer stays valid until the assemblies are reloaded.
+        unsafe { runtime.call(&self.function_info, arguments) }.map_err(BoundFunctionError::Runtime)
+    }
+
+    /// Finds the function called `function_name` and validates its signature.
+    fn resolve(
+        runtime: &Runtime,
+        function_name: &str,
+    ) -> Result<Arc<FunctionDefinition>, BoundFunctionError> {
+        let function_info = runtime
+            .find_public_function(function_name)
+            .map_err(BoundFunctionError::Signature)?;
+
+        let signature = &function_info.prototype.signature;
+        ArgTypes::can_bind(signature).map_err(BoundFunctionError::Signature)?;
+        check_return_type::<ReturnType>(signature).map_err(BoundFunctionError::Signature)?;
+
+        Ok(function_info)
+    }
+}
@@ -2,15 +2,17 @@ use std::sync::Arc;
 
 use mun_abi as abi;
 use mun_memory::type_table::TypeTable;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::function_info::FunctionDefinition;
+use crate::{function_info::FunctionDefinition, LinkFunctionsError};
 
 /// A runtime dispatch table that maps full paths to function and struct
 /// information.
 #[derive(Clone, Default)]
 pub struct DispatchTable {
     functions: FxHashMap<String, Arc<FunctionDefinition>>,
+    /// The full paths of the functions that are provided by the host
+    host_functions: FxHashSet<String>,
 }
 
 impl DispatchTable {
@@ -20,9 +22,19 @@ impl DispatchTable {
         self.functions.get(fn_path).cloned()
     }
 
-    /// Retrieves the name of all available functions.
-    pub fn get_fn_names(&self) -> impl Iterator<Item = &str> {
-        self.functions.keys().map(String::as_str)
+    /// Returns the full paths and definitions of all functions.
+    pub fn functions(&self) -> impl Iterator<Item = (&str, &Arc<FunctionDefinition>)> {
+        self.functions
+            .iter()
+            .map(|(name, fn_def)| (name.as_str(), fn_def))
+    }
+
+    /// Retrieves the name of all functions that can be invoked by the host.
+    pub fn get_public_fn_names(&self) -> impl Iterator<Item = &str> {
+        self.functions
+            .iter()
+            .filter(|(_, fn_def)| fn_def.is_public())
+            .map(|(name, _)| name.as_str())
     }
 
     /// Inserts the `fn_info` for `fn_path` into the dispatch table.
@@ -37,6 +49,19 @@ impl DispatchTable {
         self.functions.insert(fn_path.to_string(), fn_info)
     }
 
+    /// Inserts a function that is provided by the host into the dispatch
+    /// table. Fails if the host already provided a function with the same
+    /// full path.
+    pub fn insert_host_fn(&mut self, fn_def: FunctionDefinition) -> Result<(), LinkFunctionsError> {
+        let fn_path = fn_def.prototype.name.clone();
+        if !self.host_functions.insert(fn_path.clone()) {
+            return Err(LinkFunctionsError::DuplicateHostFunction { fn_name: fn_path });
+        }
+
+        self.insert_fn(fn_path, Arc::new(fn_def));
+        Ok(())
+    }
+
     // /// Removes and returns the `fn_info` corresponding to `fn_path`, if it
     // exists. pub fn remove_fn<S: AsRef<str>>(&mut self, fn_path: S) ->
     // Option<Arc<FunctionDefinition>> {     self.functions.remove(fn_path.
@@ -55,13 +80,29 @@ impl DispatchTable {
     }
 
     /// Add the function definitions from the given assembly from this dispatch
-    /// table.
-    pub fn insert_module(&mut self, assembly: &abi::ModuleInfo<'_>, type_table: &TypeTable) {
+    /// table. Fails if the assembly defines a function with the same full
+    /// path as a function that is provided by the host.
+    pub fn insert_module(
+        &mut self,
+        assembly: &abi::ModuleInfo<'_>,
+        type_table: &TypeTable,
+    ) -> Result<(), LinkFunctionsError> {
+        if let Some(fn_def) = assembly
+            .functions()
+            .iter()
+            .find(|fn_def| self.host_functions.contains(fn_def.prototype.name()))
+        {
+            return Err(LinkFunctionsError::HostFunctionCollision {
+                fn_name: fn_def.prototype.name().to_owned(),
+            });
+        }
+
         for fn_def in assembly.functions() {
             let fn_def = FunctionDefinition::try_from_abi(fn_def, type_table)
                 .expect("All types from a loaded assembly must exist in the type table.");
 
             self.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
         }
+        Ok(())
     }
 }
@@ -11,6 +11,16 @@ pub struct FunctionDefinition {
     pub prototype: FunctionPrototype,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Pointer to the trampoline of the function, or null if the function
+    /// can't be invoked with
+    /// [`Runtime::invoke_dynamic`](crate::Runtime::invoke_dynamic),
+    /// e.g. because it is defined by the host or was compiled without dynamic
+    /// invocation
+    pub trampoline_ptr: *const c_void,
+    /// Documentation of the function
+    pub docs: Option<String>,
+    /// Whether the function can be invoked by the host
+    pub privacy: abi::Privacy,
 }
 
 impl FunctionDefinition {
@@ -21,8 +31,20 @@ impl FunctionDefinition {
             arg_types: vec![],
             return_type: <()>::type_info().clone(),
             fn_ptr: ptr::null(),
+            docs: None,
         }
     }
+
+    /// Returns `true` if the function can be invoked by the host. Functions
+    /// that are not `pub` are only used to link assemblies.
+    pub fn is_public(&self) -> bool {
+        self.privacy == abi::Privacy::Public
+    }
+
+    /// Returns the documentation of the function, if any.
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
 }
 
 unsafe impl Send for FunctionDefinition {}
@@ -39,6 +61,9 @@ impl FunctionDefinition {
         Ok(Self {
             prototype,
             fn_ptr: fn_def.fn_ptr,
+            trampoline_ptr: fn_def.trampoline_ptr,
+            docs: fn_def.docs().map(ToOwned::to_owned),
+            privacy: fn_def.privacy,
         })
     }
 }
@@ -116,24 +141,31 @@ macro_rules! into_function_info_impl {
         extern "C" fn($($T:ident),*) -> $R:ident;
     )+) => {
         $(
-            impl<$R: mun_memory::HasStaticType, $($T: mun_memory::HasStaticType,)*> IntoFunctionDefinition
-            for extern "C" fn($($T),*) -> $R
-            {
-                fn into<S: Into<String>>(self, name: S) -> FunctionDefinition {
-                    FunctionDefinition {
-                        fn_ptr: self as *const std::ffi::c_void,
-                        prototype: FunctionPrototype {
-                            name: name.into(),
-                            signature: FunctionSignature {
-                                arg_types: vec![$(<$T as mun_memory::HasStaticType>::type_info().clone(),)*],
-                                return_type: <R as mun_memory::HasStaticType>::type_info().clone(),
-                            }
+            into_function_info_impl!(@impl "C", $R $(, $T)*);
+            into_function_info_impl!(@impl "C-unwind", $R $(, $T)*);
+        )+
+    };
+    (@impl $abi:literal, $R:ident $(, $T:ident)*) => {
+        impl<$R: mun_memory::HasStaticType, $($T: mun_memory::HasStaticType,)*> IntoFunctionDefinition
+        for extern $abi fn($($T),*) -> $R
+        {
+            fn into<S: Into<String>>(self, name: S) -> FunctionDefinition {
+                FunctionDefinition {
+                    fn_ptr: self as *const std::ffi::c_void,
+                    trampoline_ptr: std::ptr::null(),
+                    docs: None,
+                    privacy: mun_abi::Privacy::Public,
+                    prototype: FunctionPrototype {
+                        name: name.into(),
+                        signature: FunctionSignature {
+                            arg_types: vec![$(<$T as mun_memory::HasStaticType>::type_info().clone(),)*],
+                            return_type: <$R as mun_memory::HasStaticType>::type_info().clone(),
                         }
                     }
                 }
             }
-        )+
-    }
+        }
+    };
 }
 
 into_function_info_impl! {
@@ -157,6 +189,7 @@ pub struct FunctionDefinitionBuilder {
     arg_types: Vec<Type>,
     return_type: Type,
     fn_ptr: *const c_void,
+    docs: Option<String>,
 }
 
 impl FunctionDefinitionBuilder {
@@ -186,6 +219,12 @@ impl FunctionDefinitionBuilder {
         self
     }
 
+    /// Sets the documentation
+    pub fn set_docs(mut self, docs: impl Into<String>) -> Self {
+        self.docs = Some(docs.into());
+        self
+    }
+
     /// Construct the [`FunctionDefinition`]
     pub fn finish(self) -> Arc<FunctionDefinition> {
         Arc::new(FunctionDefinition {
@@ -197,6 +236,9 @@ impl FunctionDefinitionBuilder {
                 },
             },
             fn_ptr: self.fn_ptr,
+            trampoline_ptr: ptr::null(),
+            docs: self.docs,
+            privacy: abi::Privacy::Public,
         })
     }
 }
@@ -0,0 +1,123 @@
+use std::fmt::{self, Display, Formatter};
+
+use mun_memory::Type;
+
+use crate::RuntimeError;
+
+/// Describes why invoking a Mun function failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvokeErrorKind {
+    /// There is no function with the requested name.
+    FunctionNotFound {
+        /// The name of the requested function
+        name: String,
+        /// The name of a public function with a similar name, if any
+        similar_name: Option<String>,
+    },
+    /// The requested function exists, but it is not public.
+    FunctionNotPublic {
+        /// The name of the requested function
+        name: String,
+    },
+    /// The number of arguments does not match the signature of the function.
+    ArgumentCountMismatch {
+        /// The number of arguments of the function
+        expected: usize,
+        /// The number of arguments that were passed
+        found: usize,
+    },
+    /// The type of an argument does not match the signature of the function.
+    ArgumentTypeMismatch {
+        /// The index of the argument
+        index: usize,
+        /// The type of the argument in the signature of the function
+        expected: Type,
+        /// The type of the argument that was passed
+        found: Type,
+    },
+    /// The return type of the function cannot be stored in the requested
+    /// return type.
+    ReturnTypeMismatch {
+        /// A description of the requested return type
+        expected: &'static str,
+        /// The return type of the function
+        found: Type,
+    },
+    /// The function cannot be invoked with
+    /// [`Runtime::invoke_dynamic`](crate::Runtime::invoke_dynamic), because it
+    /// is defined by the host, was compiled without dynamic invocation, or
+    /// returns a type that cannot be represented as a [`Value`](crate::Value),
+    /// e.g. a pointer.
+    UnsupportedDynamicSignature,
+    /// The function was invoked, but an error occurred while executing it,
+    /// e.g. the Mun code panicked.
+    Runtime(RuntimeError),
+}
+
+impl Display for InvokeErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvokeErrorKind::FunctionNotFound { name, similar_name } => {
+                write!(
+                    f,
+                    "failed to obtain function '{name}', no such function exists."
+                )?;
+                if let Some(similar_name) = similar_name {
+                    write!(
+                        f,
+                        " There is a function with a similar name: {similar_name}"
+                    )?;
+                }
+                Ok(())
+            }
+            InvokeErrorKind::FunctionNotPublic { name } => {
+                write!(
+                    f,
+                    "failed to obtain function '{name}', the function is not public."
+                )
+            }
+            InvokeErrorKind::ArgumentCountMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Invalid argument count. Expected {expected} arguments, got {found}"
+                )
+            }
+            InvokeErrorKind::ArgumentTypeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid argument type at index {index}. Expected: {}. Found: {}.",
+                expected.name(),
+                found.name()
+            ),
+            InvokeErrorKind::ReturnTypeMismatch { expected, found } => write!(
+                f,
+                "unexpected return type, got '{}', expected '{expected}'",
+                found.name()
+            ),
+            InvokeErrorKind::UnsupportedDynamicSignature => write!(
+                f,
+                "the function cannot be invoked dynamically, only Mun functions that are \
+                 compiled with dynamic invocation and don't return a pointer are supported"
+            ),
+            InvokeErrorKind::Runtime(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for InvokeErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InvokeErrorKind::Runtime(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<RuntimeError> for InvokeErrorKind {
+    fn from(error: RuntimeError) -> Self {
+        InvokeErrorKind::Runtime(error)
+    }
+}
@@ -9,20 +9,27 @@ mod assembly;
 mod garbage_collector;
 mod adt;
 mod array;
+mod bound_function;
 mod dispatch_table;
 mod function_info;
+mod invoke_error;
 mod marshal;
+mod metadata;
+mod panic;
 mod reflection;
+mod reload;
 mod utils;
+mod value;
 
 use std::{
     cmp,
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     ffi,
-    ffi::c_void,
+    ffi::{c_void, OsStr},
     fmt::{Debug, Display, Formatter},
     mem::ManuallyDrop,
-    path::{Path, PathBuf},
+    panic::AssertUnwindSafe,
+    path::{Component, Path, PathBuf},
     ptr::NonNull,
     sync::{
         mpsc::{channel, Receiver},
@@ -33,26 +40,41 @@ use std::{
 use assembly::LoadError;
 use dispatch_table::DispatchTable;
 use garbage_collector::GarbageCollector;
-use log::{debug, error, info};
+use itertools::Itertools;
+use log::{debug, error, info, warn};
+use marshal::ReturnSlot;
 use mun_abi as abi;
 use mun_memory::{
     gc::{self, Array, GcRuntime},
     type_table::TypeTable,
 };
+use reload::ReloadHandler;
+use rustc_hash::FxHashMap;
 // Re-export some useful types so crates dont have to depend on mun_memory as well.
-pub use mun_memory::{Field, FieldData, HasStaticType, PointerType, StructType, Type};
+pub use mun_abi::StructMemoryKind;
+pub use mun_memory::{
+    Field, FieldData, HasStaticType, PointerType, StructType, StructTypeBuilder, Type,
+};
 use mun_project::LOCKFILE_NAME;
 use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
 pub use crate::{
-    adt::{RootedStruct, StructRef},
+    adt::{ConstructStructError, RootedStruct, StructBuilder, StructRef},
     array::{ArrayRef, RawArray, RootedArray},
-    assembly::{Assembly, LinkError, LinkFunctionsError},
+    assembly::{Assembly, LinkError, LinkFunctionsError, TestFunction},
+    bound_function::{BoundFunction, BoundFunctionError},
     function_info::{
         FunctionDefinition, FunctionPrototype, FunctionSignature, IntoFunctionDefinition,
     },
+    invoke_error::InvokeErrorKind,
     marshal::Marshal,
+    metadata::{
+        AssemblyMetadata, FieldMetadata, FunctionMetadata, Origin, RuntimeMetadata, TypeMetadata,
+    },
+    panic::{PanicLocation, RuntimeError},
     reflection::{ArgumentReflection, ReturnTypeReflection},
+    reload::ReloadEvent,
+    value::Value,
 };
 
 /// Options for the construction of a [`Runtime`].
@@ -63,6 +85,17 @@ pub struct RuntimeOptions {
     pub type_table: TypeTable,
     /// Custom user injected functions
     pub user_functions: Vec<FunctionDefinition>,
+    /// The maximum number of bytes that objects allocated by Mun code may
+    /// occupy
+    pub memory_limit: Option<usize>,
+    /// The contents of libraries that are loaded from memory instead of from
+    /// disk, keyed by the path that identifies them. The entry point library
+    /// and its dependencies are looked up in memory before the file system.
+    pub library_bytes: HashMap<PathBuf, Vec<u8>>,
+    /// Whether the runtime watches the libraries on disk for changes. If
+    /// disabled, assemblies are only reloaded with
+    /// [`Runtime::reload_assembly`] and [`Runtime::reload_all`].
+    pub watch_files: bool,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -85,7 +118,7 @@ unsafe fn get_type_info(type_handle: *const ffi::c_void) -> Type {
     Type::from_raw(type_handle)
 }
 
-extern "C" fn new(
+extern "C-unwind" fn new(
     type_handle: *const ffi::c_void,
     alloc_handle: *mut ffi::c_void,
 ) -> *const *mut ffi::c_void {
@@ -102,11 +135,12 @@ extern "C" fn new(
     // Safety: the Mun Compiler guarantees that `new` is never called with
     // `ptr::null()`.
     let handle = allocator.as_ref().alloc(&type_info);
+    check_memory_limit(&allocator);
 
     handle.into()
 }
 
-extern "C" fn new_array(
+extern "C-unwind" fn new_array(
     type_handle: *const ffi::c_void,
     length: usize,
     alloc_handle: *mut ffi::c_void,
@@ -122,10 +156,23 @@ extern "C" fn new_array(
     let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
 
     let handle = allocator.as_ref().alloc_array(&type_info, length);
+    check_memory_limit(&allocator);
 
     handle.as_raw().into()
 }
 
+/// Unwinds the stack up to the `Runtime::invoke` call that executed the Mun
+/// code if the objects allocated by the Mun code exceed the memory limit of
+/// the runtime.
+fn check_memory_limit(allocator: &GarbageCollector) {
+    if allocator.exceeds_memory_limit() {
+        let limit = allocator
+            .memory_limit()
+            .expect("the memory limit can only be exceeded if there is one");
+        std::panic::resume_unwind(Box::new(RuntimeError::MemoryLimitExceeded { limit }))
+    }
+}
+
 /// A builder for the [`Runtime`].
 pub struct RuntimeBuilder {
     options: RuntimeOptions,
@@ -140,10 +187,38 @@ impl RuntimeBuilder {
                 library_path: library_path.into(),
                 type_table: TypeTable::default(),
                 user_functions: Vec::default(),
+                memory_limit: None,
+                library_bytes: HashMap::default(),
+                watch_files: true,
             },
         }
     }
 
+    /// Constructs a new `RuntimeBuilder` for the shared library with the
+    /// specified contents, e.g. a munlib that was read from an asset pack or
+    /// downloaded over the network. The `library_path` identifies the library
+    /// and is used to resolve its dependencies, but the file doesn't need to
+    /// exist. Dependencies that are not on disk can be added with
+    /// [`RuntimeBuilder::insert_library_bytes`].
+    pub fn from_bytes<P: Into<PathBuf>, B: Into<Vec<u8>>>(library_path: P, bytes: B) -> Self {
+        let library_path = library_path.into();
+        Self::new(library_path.clone()).insert_library_bytes(library_path, bytes)
+    }
+
+    /// Adds the contents of a library that is loaded from memory instead of
+    /// from the file at `library_path`, e.g. a dependency of the entry point
+    /// library.
+    pub fn insert_library_bytes<P: Into<PathBuf>, B: Into<Vec<u8>>>(
+        mut self,
+        library_path: P,
+        bytes: B,
+    ) -> Self {
+        self.options
+            .library_bytes
+            .insert(normalize_path(&library_path.into()), bytes.into());
+        self
+    }
+
     /// Adds a custom user function to the dispatch table.
     pub fn insert_fn<S: Into<String>, F: IntoFunctionDefinition>(
         mut self,
@@ -154,6 +229,36 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Adds a custom user function to the dispatch table in the specified
+    /// `namespace`, e.g. `"host::math"`. Mun code links to the function by
+    /// declaring it as an `extern` function with the
+    /// `#[link_namespace = "host::math"]` attribute.
+    pub fn insert_fn_in<N: AsRef<str>, S: AsRef<str>, F: IntoFunctionDefinition>(
+        self,
+        namespace: N,
+        name: S,
+        func: F,
+    ) -> Self {
+        self.insert_fn(format!("{}::{}", namespace.as_ref(), name.as_ref()), func)
+    }
+
+    /// Limits the number of bytes that objects allocated by Mun code may
+    /// occupy. An allocation that exceeds the limit makes the invocation that
+    /// performed it fail with [`RuntimeError::MemoryLimitExceeded`].
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.options.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Disables the file watcher of the runtime. [`Runtime::update`] then
+    /// never reloads assemblies; hosts that know when a build finished, e.g.
+    /// through their own asset pipeline, reload them deterministically with
+    /// [`Runtime::reload_assembly`] or [`Runtime::reload_all`] instead.
+    pub fn disable_file_watching(mut self) -> Self {
+        self.options.watch_files = false;
+        self
+    }
+
     /// Constructs a [`Runtime`] with the builder's options.
     ///
     /// # Safety
@@ -203,12 +308,29 @@ pub struct Runtime {
     assemblies_to_relink: BTreeMap<PathBuf, PathBuf>,
     dispatch_table: DispatchTable,
     type_table: TypeTable,
-    watcher: RecommendedWatcher,
+    /// Watches the directories of the libraries for changes, unless file
+    /// watching is disabled
+    watcher: Option<RecommendedWatcher>,
     watcher_rx: Receiver<notify::Result<Event>>,
     renamed_files: HashMap<usize, PathBuf>,
+    /// The contents of the libraries that are loaded from memory
+    library_bytes: HashMap<PathBuf, Vec<u8>>,
     gc: Arc<GarbageCollector>,
+    /// The number of times the assemblies were successfully relinked
+    generation: u64,
+    /// The callbacks that are invoked after assemblies were relinked
+    reload_handlers: Vec<Box<ReloadHandler>>,
+    /// The reload events that were not yet taken by the host
+    reload_events: Vec<ReloadEvent>,
+    /// The finalizers that are run when the runtime shuts down
+    shutdown_handlers: Vec<Box<ShutdownHandler>>,
+    /// Whether the runtime was shut down, see [`Runtime::shutdown`]
+    is_shut_down: bool,
 }
 
+/// A finalizer that is run when the runtime shuts down.
+type ShutdownHandler = dyn FnOnce(&Runtime) + Send;
+
 impl Runtime {
     /// Constructs a new [`RuntimeBuilder`] to construct a new [`Runtime`]
     /// instance.
@@ -242,13 +364,16 @@ impl Runtime {
 
         // Add internal functions
         options.user_functions.push(IntoFunctionDefinition::into(
-            new as extern "C" fn(*const ffi::c_void, *mut ffi::c_void) -> *const *mut ffi::c_void,
+            new as extern "C-unwind" fn(
+                *const ffi::c_void,
+                *mut ffi::c_void,
+            ) -> *const *mut ffi::c_void,
             "new",
         ));
 
         options.user_functions.push(IntoFunctionDefinition::into(
             new_array
-                as extern "C" fn(
+                as extern "C-unwind" fn(
                     *const ffi::c_void,
                     usize,
                     *mut ffi::c_void,
@@ -256,13 +381,27 @@ impl Runtime {
             "new_array",
         ));
 
-        options.user_functions.into_iter().for_each(|fn_def| {
-            dispatch_table.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
-        });
+        options.user_functions.push(IntoFunctionDefinition::into(
+            panic::panic as extern "C-unwind" fn(*const u8, *const u8, u32, u32),
+            "panic",
+        ));
+
+        for fn_def in options.user_functions {
+            dispatch_table
+                .insert_host_fn(fn_def)
+                .map_err(LinkError::from)?;
+        }
 
-        let watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
-            tx.send(res).expect("Failed to send filesystem event.");
-        })?;
+        let gc = Arc::new(self::garbage_collector::GarbageCollector::default());
+        gc.set_memory_limit(options.memory_limit);
+
+        let watcher = if options.watch_files {
+            Some(notify::recommended_watcher(move |res| {
+                tx.send(res).expect("Failed to send filesystem event.");
+            })?)
+        } else {
+            None
+        };
         let mut runtime = Runtime {
             assemblies: HashMap::new(),
             assemblies_to_relink: BTreeMap::new(),
@@ -271,7 +410,13 @@ impl Runtime {
             watcher,
             watcher_rx: rx,
             renamed_files: HashMap::new(),
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            library_bytes: options.library_bytes,
+            gc,
+            generation: 0,
+            reload_handlers: Vec::new(),
+            reload_events: Vec::new(),
+            shutdown_handlers: Vec::new(),
+            is_shut_down: false,
         };
 
         runtime.add_assembly(&options.library_path)?;
@@ -295,9 +440,7 @@ impl Runtime {
     ///
     /// See [`Assembly::load`] for more information.
     unsafe fn add_assembly(&mut self, library_path: &Path) -> Result<(), LinkError> {
-        let library_path = library_path
-            .canonicalize()
-            .map_err(|e| LinkError::LoadAssembly(LoadError::Other(e)))?;
+        let library_path = self.resolve_library_path(library_path)?;
 
         if self.assemblies.contains_key(&library_path) {
             return Err(LoadError::AlreadyExists.into());
@@ -315,7 +458,7 @@ impl Runtime {
                 continue;
             }
 
-            let assembly = Assembly::load(&library_path, self.gc.clone())?;
+            let assembly = self.load_assembly(&library_path)?;
 
             let parent = library_path.parent().expect("Invalid library path");
             let extension = library_path.extension();
@@ -325,37 +468,230 @@ impl Runtime {
             loaded.insert(library_path.clone(), assembly);
 
             for dependency in dependencies {
-                let mut library_path = parent.join(dependency);
-                if let Some(extension) = extension {
-                    library_path = library_path.with_extension(extension);
-                }
-
+                let library_path = dependency_path(parent, &dependency, extension);
                 if !loaded.contains_key(&library_path) {
                     to_load.push_back(library_path);
                 }
             }
         }
 
+        check_dependency_versions(loaded.iter(), |library_path| {
+            loaded
+                .get(library_path)
+                .or_else(|| self.assemblies.get(library_path))
+        })?;
+
         (self.dispatch_table, self.type_table) =
             Assembly::link_all(loaded.values_mut(), &self.dispatch_table, &self.type_table)?;
 
+        let loaded_paths: Vec<PathBuf> = loaded.keys().cloned().collect();
         for (library_path, assembly) in loaded {
-            self.watcher
-                .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)
-                .expect("Path must exist as we just loaded the library");
+            // Libraries that are loaded from memory are not backed by a file
+            if let Some(watcher) = self.watcher.as_mut() {
+                if !self.library_bytes.contains_key(&library_path) {
+                    watcher
+                        .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)
+                        .expect("Path must exist as we just loaded the library");
+                }
+            }
 
             self.assemblies.insert(library_path, assembly);
         }
 
+        // Initialize the new assemblies after their dependencies
+        for (_, assembly) in dependency_order(&self.assemblies)
+            .into_iter()
+            .filter(|(library_path, _)| loaded_paths.contains(library_path))
+        {
+            assembly.init();
+        }
+
         Ok(())
     }
 
+    /// Returns the path that identifies the library at `library_path` in the
+    /// runtime: the normalized path of a library that is loaded from memory,
+    /// or the canonical path of a library on disk.
+    fn resolve_library_path(&self, library_path: &Path) -> Result<PathBuf, LinkError> {
+        let normalized_path = normalize_path(library_path);
+        if self.library_bytes.contains_key(&normalized_path) {
+            return Ok(normalized_path);
+        }
+
+        library_path
+            .canonicalize()
+            .map_err(|e| LinkError::LoadAssembly(LoadError::Other(e)))
+    }
+
+    /// Loads the assembly at the resolved `library_path`, either from memory or
+    /// from disk.
+    ///
+    /// # Safety
+    ///
+    /// See [`Assembly::load`] for more information.
+    unsafe fn load_assembly(&self, library_path: &Path) -> Result<Assembly, LoadError> {
+        match self.library_bytes.get(library_path) {
+            Some(bytes) => Assembly::load_from_bytes(bytes, library_path, self.gc.clone()),
+            None => Assembly::load(library_path, self.gc.clone()),
+        }
+    }
+
+    /// Registers a `callback` that is invoked every time the runtime
+    /// successfully relinked assemblies, after their `init` functions ran. The
+    /// [`ReloadEvent`] describes which functions and types changed.
+    pub fn on_reload<F: FnMut(&ReloadEvent) + Send + 'static>(&mut self, callback: F) {
+        self.reload_handlers.push(Box::new(callback));
+    }
+
+    /// Registers a `finalizer` that is run when the runtime shuts down, before
+    /// the `shutdown` functions of the assemblies are invoked. Finalizers run
+    /// in the reverse order of their registration and can still invoke Mun
+    /// functions.
+    pub fn on_shutdown<F: FnOnce(&Runtime) + Send + 'static>(&mut self, finalizer: F) {
+        self.shutdown_handlers.push(Box::new(finalizer));
+    }
+
+    /// Shuts down the runtime in a well-defined order, instead of relying on
+    /// the order in which its parts are dropped:
+    ///
+    /// 1. the finalizers registered with [`Runtime::on_shutdown`] are run,
+    /// 2. the `shutdown` functions of the assemblies are invoked, before those
+    ///    of the assemblies they depend on,
+    /// 3. the file watcher is stopped,
+    /// 4. all objects that are no longer rooted are collected.
+    ///
+    /// Returns the objects that are still rooted afterwards, which are leaked
+    /// by the host, e.g. by a forgotten `RootedStruct`. When a runtime is
+    /// dropped without being shut down, the same steps are performed and the
+    /// leaked objects are logged as a warning.
+    pub fn shutdown(mut self) -> Vec<gc::RootedObject> {
+        self.shutdown_impl()
+    }
+
+    /// Performs the steps of [`Runtime::shutdown`], unless the runtime was
+    /// already shut down.
+    fn shutdown_impl(&mut self) -> Vec<gc::RootedObject> {
+        if self.is_shut_down {
+            return Vec::new();
+        }
+        self.is_shut_down = true;
+
+        for finalizer in std::mem::take(&mut self.shutdown_handlers)
+            .into_iter()
+            .rev()
+        {
+            finalizer(self);
+        }
+
+        // Shut down assemblies before the assemblies they depend on
+        for (_, assembly) in dependency_order(&self.assemblies).into_iter().rev() {
+            assembly.shutdown();
+        }
+
+        self.watcher = None;
+
+        self.gc.collect();
+        self.gc.rooted_objects()
+    }
+
+    /// Returns the events of all reloads since the last call, oldest first.
+    /// This is an alternative to [`Runtime::on_reload`] for hosts that poll for
+    /// changes, e.g. once per frame.
+    pub fn take_reload_events(&mut self) -> Vec<ReloadEvent> {
+        std::mem::take(&mut self.reload_events)
+    }
+
+    /// Returns the number of times the runtime successfully reloaded its
+    /// assemblies. The generation starts at zero and is incremented every time
+    /// [`Runtime::update`], [`Runtime::reload_assembly`], or
+    /// [`Runtime::reload_all`] relinks assemblies.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the assemblies that are currently loaded by the runtime.
+    pub fn assemblies(&self) -> impl Iterator<Item = &Assembly> {
+        self.assemblies.values()
+    }
+
+    /// Returns the metadata of all assemblies that are currently loaded by the
+    /// runtime, which describes their functions, types, and dependencies.
+    pub fn metadata(&self) -> RuntimeMetadata {
+        RuntimeMetadata {
+            abi_version: abi::ABI_VERSION,
+            assemblies: self
+                .assemblies
+                .values()
+                .map(Assembly::metadata)
+                .sorted_by(|a, b| a.library_path.cmp(&b.library_path))
+                .collect(),
+        }
+    }
+
+    /// Returns the definitions of all functions in the dispatch table, in no
+    /// particular order, together with where they are defined. This includes
+    /// functions that are not `pub` and can therefore not be invoked by the
+    /// host, see [`FunctionDefinition::is_public`].
+    pub fn functions(&self) -> impl Iterator<Item = (Arc<FunctionDefinition>, Origin<'_>)> {
+        let origins: FxHashMap<*const c_void, &Path> = self
+            .assemblies
+            .values()
+            .flat_map(|assembly| {
+                assembly
+                    .info()
+                    .symbols
+                    .functions()
+                    .iter()
+                    .map(move |function| (function.fn_ptr, assembly.library_path()))
+            })
+            .collect();
+
+        self.dispatch_table.functions().map(move |(_, fn_def)| {
+            let origin = origins
+                .get(&fn_def.fn_ptr)
+                .map_or(Origin::Host, |library_path| Origin::Assembly(library_path));
+            (fn_def.clone(), origin)
+        })
+    }
+
+    /// Returns all types in the type table, in no particular order, together
+    /// with where they are defined.
+    pub fn types(&self) -> impl Iterator<Item = (Type, Origin<'_>)> {
+        let origins: FxHashMap<abi::Guid, &Path> = self
+            .assemblies
+            .values()
+            .flat_map(|assembly| {
+                assembly
+                    .info()
+                    .symbols
+                    .types()
+                    .iter()
+                    .map(move |ty| (*ty.as_concrete(), assembly.library_path()))
+            })
+            .collect();
+
+        self.type_table.types().map(move |ty| {
+            let origin = ty
+                .as_concrete()
+                .and_then(|guid| origins.get(guid))
+                .map_or(Origin::Host, |library_path| Origin::Assembly(library_path));
+            (ty.clone(), origin)
+        })
+    }
+
+    /// Retrieves the assembly corresponding to the library at `library_path`,
+    /// if it is loaded.
+    pub fn get_assembly(&self, library_path: &Path) -> Option<&Assembly> {
+        let library_path = self.resolve_library_path(library_path).ok()?;
+        self.assemblies.get(&library_path)
+    }
+
     /// Retrieves the function definition corresponding to `function_name`, if
-    /// available.
+    /// available. Functions that are not `pub` are not available to the host.
     pub fn get_function_definition(&self, function_name: &str) -> Option<Arc<FunctionDefinition>> {
-        // TODO: Verify that when someone tries to invoke a non-public function, it
-        // should fail.
-        self.dispatch_table.get_fn(function_name)
+        self.dispatch_table
+            .get_fn(function_name)
+            .filter(|fn_def| fn_def.is_public())
     }
 
     /// For a given `fn_name`, find the most similar name in `fn_names`
@@ -395,7 +731,8 @@ impl Runtime {
     }
 
     /// Updates the state of the runtime. This includes checking for file
-    /// changes, and reloading compiled assemblies.
+    /// changes, and reloading compiled assemblies. If file watching is
+    /// disabled, this never reloads assemblies.
     /// # Safety
     ///
     /// A munlib is simply a shared object. When a library is loaded,
@@ -415,60 +752,6 @@ impl Runtime {
             path.file_name().expect("Invalid file path.") == LOCKFILE_NAME
         }
 
-        unsafe fn relink_assemblies(
-            runtime: &mut Runtime,
-        ) -> Result<(DispatchTable, TypeTable), LinkError> {
-            let mut loaded = HashMap::new();
-            let to_load = &mut runtime.assemblies_to_relink;
-
-            info!("Relinking assemblies:");
-            for (old_path, new_path) in to_load.iter() {
-                info!(
-                    "{} -> {}",
-                    old_path.to_string_lossy(),
-                    new_path.to_string_lossy()
-                );
-            }
-
-            // Load all assemblies and their dependencies
-            while let Some((old_path, new_path)) = to_load.pop_first() {
-                // A dependency can be added by multiple dependants, so check that we didn't
-                // load it yet
-                if loaded.contains_key(&old_path) {
-                    continue;
-                }
-
-                let assembly = Assembly::load(&new_path, runtime.gc.clone())?;
-
-                let parent = new_path.parent().expect("Invalid library path");
-                let extension = new_path.extension();
-
-                let dependencies: Vec<String> =
-                    assembly.info().dependencies().map(From::from).collect();
-                loaded.insert(old_path.clone(), assembly);
-
-                for dependency in dependencies {
-                    let mut library_path = parent.join(dependency);
-                    if let Some(extension) = extension {
-                        library_path = library_path.with_extension(extension);
-                    }
-
-                    if !loaded.contains_key(&library_path)
-                        && !runtime.assemblies.contains_key(&library_path)
-                    {
-                        to_load.insert(old_path.clone(), library_path);
-                    }
-                }
-            }
-
-            Assembly::relink_all(
-                &mut loaded,
-                &mut runtime.assemblies,
-                &runtime.dispatch_table,
-                &runtime.type_table,
-            )
-        }
-
         let mut requires_relink = false;
         while let Ok(Ok(event)) = self.watcher_rx.try_recv() {
             for path in event.paths {
@@ -511,16 +794,8 @@ impl Runtime {
             if self.assemblies_to_relink.is_empty() {
                 debug!("The compiler didn't write a munlib.");
             } else {
-                match relink_assemblies(self) {
-                    Ok((dispatch_table, type_table)) => {
-                        info!("Succesfully reloaded assemblies.");
-
-                        self.dispatch_table = dispatch_table;
-                        self.type_table = type_table;
-                        self.assemblies_to_relink.clear();
-
-                        return true;
-                    }
+                match self.relink_assemblies() {
+                    Ok(()) => return true,
                     Err(e) => error!("Failed to relink assemblies: {e}"),
                 }
             }
@@ -529,6 +804,184 @@ impl Runtime {
         false
     }
 
+    /// Reloads the assembly that was loaded from `library_path`, regardless of
+    /// whether the file watcher detected a change. This allows hosts that
+    /// replace munlibs through their own means, e.g. an asset system, to
+    /// reload them on demand.
+    ///
+    /// # Safety
+    ///
+    /// A munlib is simply a shared object. When a library is loaded,
+    /// initialisation routines contained within it are executed. For the
+    /// purposes of safety, the execution of these routines is conceptually
+    /// the same calling an unknown foreign function and may impose
+    /// arbitrary requirements on the caller for the call to be sound.
+    ///
+    /// Additionally, the callers of this function must also ensure that
+    /// execution of the termination routines contained within the library
+    /// is safe as well. These routines may be executed when the library is
+    /// unloaded.
+    ///
+    /// See [`Assembly::load`] for more information.
+    pub unsafe fn reload_assembly(&mut self, library_path: &Path) -> Result<(), LinkError> {
+        let library_path = self.resolve_library_path(library_path)?;
+        if !self.assemblies.contains_key(&library_path) {
+            return Err(LinkError::UnknownAssembly(library_path));
+        }
+
+        self.assemblies_to_relink
+            .insert(library_path.clone(), library_path);
+        self.relink_assemblies()
+    }
+
+    /// Reloads all assemblies, regardless of whether the file watcher detected
+    /// a change.
+    ///
+    /// # Safety
+    ///
+    /// See [`Runtime::reload_assembly`] for more information.
+    pub unsafe fn reload_all(&mut self) -> Result<(), LinkError> {
+        let library_paths: Vec<PathBuf> = self.assemblies.keys().cloned().collect();
+        for library_path in library_paths {
+            self.assemblies_to_relink
+                .insert(library_path.clone(), library_path);
+        }
+        self.relink_assemblies()
+    }
+
+    /// Loads the assemblies that need to be relinked, and their dependencies,
+    /// and relinks them with the assemblies that are already loaded. On
+    /// success, the generation of the runtime is incremented.
+    ///
+    /// # Safety
+    ///
+    /// See [`Runtime::reload_assembly`] for more information.
+    unsafe fn relink_assemblies(&mut self) -> Result<(), LinkError> {
+        let mut loaded = HashMap::new();
+
+        info!("Relinking assemblies:");
+        for (old_path, new_path) in self.assemblies_to_relink.iter() {
+            info!(
+                "{} -> {}",
+                old_path.to_string_lossy(),
+                new_path.to_string_lossy()
+            );
+        }
+
+        // Load all assemblies and their dependencies
+        while let Some((old_path, new_path)) = self.assemblies_to_relink.pop_first() {
+            // A dependency can be added by multiple dependants, so check that we didn't
+            // load it yet
+            if loaded.contains_key(&old_path) {
+                continue;
+            }
+
+            let assembly = self.load_assembly(&new_path)?;
+
+            let parent = new_path.parent().expect("Invalid library path");
+            let extension = new_path.extension();
+
+            let dependencies: Vec<String> =
+                assembly.info().dependencies().map(From::from).collect();
+            loaded.insert(old_path.clone(), assembly);
+
+            for dependency in dependencies {
+                let library_path = dependency_path(parent, &dependency, extension);
+                if !loaded.contains_key(&library_path)
+                    && !self.assemblies.contains_key(&library_path)
+                {
+                    self.assemblies_to_relink
+                        .insert(old_path.clone(), library_path);
+                }
+            }
+        }
+
+        // The assemblies that are not replaced must also be compatible with the new
+        // versions of their dependencies
+        check_dependency_versions(
+            loaded.iter().chain(
+                self.assemblies
+                    .iter()
+                    .filter(|(library_path, _)| !loaded.contains_key(*library_path)),
+            ),
+            |library_path| {
+                loaded
+                    .get(library_path)
+                    .or_else(|| self.assemblies.get(library_path))
+            },
+        )?;
+
+        // Shut down the assemblies that are replaced before their dependencies. If
+        // relinking fails, they are initialized again.
+        let replaced_paths: Vec<PathBuf> = dependency_order(&self.assemblies)
+            .into_iter()
+            .filter(|(library_path, _)| loaded.contains_key(*library_path))
+            .map(|(library_path, _)| library_path.clone())
+            .collect();
+        for library_path in replaced_paths.iter().rev() {
+            self.assemblies[library_path].shutdown();
+        }
+
+        let relinked_paths: Vec<PathBuf> = loaded
+            .values()
+            .map(|assembly| assembly.library_path().to_path_buf())
+            .collect();
+        let old_dispatch_table = self.dispatch_table.clone();
+        let old_types = reload::assembly_types(
+            loaded.keys().filter_map(|path| self.assemblies.get(path)),
+            &self.type_table,
+        );
+        let (dispatch_table, type_table) = match Assembly::relink_all(
+            &mut loaded,
+            &mut self.assemblies,
+            &self.dispatch_table,
+            &self.type_table,
+        ) {
+            Ok(tables) => tables,
+            Err(e) => {
+                for library_path in replaced_paths.iter() {
+                    self.assemblies[library_path].init();
+                }
+                return Err(e);
+            }
+        };
+
+        info!("Succesfully reloaded assemblies.");
+
+        for (_, assembly) in dependency_order(&self.assemblies)
+            .into_iter()
+            .filter(|(library_path, _)| relinked_paths.contains(library_path))
+        {
+            assembly.init();
+        }
+
+        self.dispatch_table = dispatch_table;
+        self.type_table = type_table;
+        self.assemblies_to_relink.clear();
+        self.generation += 1;
+
+        let new_types = reload::assembly_types(
+            relinked_paths
+                .iter()
+                .filter_map(|path| self.assemblies.get(path)),
+            &self.type_table,
+        );
+        let event = ReloadEvent::new(
+            self.generation,
+            relinked_paths,
+            &old_dispatch_table,
+            &self.dispatch_table,
+            old_types,
+            new_types,
+        );
+        for handler in &mut self.reload_handlers {
+            handler(&event);
+        }
+        self.reload_events.push(event);
+
+        Ok(())
+    }
+
     /// Returns a shared reference to the runtime's garbage collector.
     ///
     /// We cannot return an `Arc` here, because the lifetime of data contained
@@ -544,11 +997,55 @@ impl Runtime {
         self.gc.collect()
     }
 
+    /// Collects all memory that is no longer referenced by rooted objects and
+    /// compacts the heap afterwards. Returns `true` if memory was reclaimed,
+    /// `false` otherwise.
+    ///
+    /// Compaction relocates the memory of all objects that are not pinned,
+    /// which combats fragmentation in long running sessions. References to
+    /// objects, such as [`StructRef`] and [`ArrayRef`], remain valid, but raw
+    /// pointers into the memory of objects are invalidated. Iterators over the
+    /// elements of an [`ArrayRef`] pin the array. Use [`GcRuntime::pin`] on
+    /// [`Runtime::gc`] to keep a raw pointer valid.
+    pub fn gc_collect_and_compact(&self) -> bool {
+        self.gc.collect_and_compact()
+    }
+
     /// Returns statistics about the garbage collector.
     pub fn gc_stats(&self) -> gc::Stats {
         self.gc.stats()
     }
 
+    /// Returns statistics about the most recent garbage collections, oldest
+    /// first. This includes the duration for which each collection paused
+    /// the program and the rate at which memory was allocated in between
+    /// collections.
+    pub fn gc_pause_history(&self) -> Vec<gc::CollectionStats> {
+        self.gc.collection_history()
+    }
+
+    /// Starts the construction of an instance of the struct called
+    /// `type_name`. The values of its fields are set with
+    /// [`StructBuilder::set`], after which [`StructBuilder::finish`] allocates
+    /// the struct. This enables hosts to create structs without calling a
+    /// constructor function written in Mun.
+    pub fn construct_struct(
+        &self,
+        type_name: &str,
+    ) -> Result<StructBuilder<'_>, ConstructStructError> {
+        let type_info = self.get_type_info_by_name(type_name).ok_or_else(|| {
+            ConstructStructError::TypeNotFound {
+                type_name: type_name.to_owned(),
+            }
+        })?;
+        if !type_info.is_struct() {
+            return Err(ConstructStructError::NotAStruct {
+                type_name: type_name.to_owned(),
+            });
+        }
+        Ok(StructBuilder::new(self, type_info))
+    }
+
     /// Constructs an array with a predefined element type.
     pub fn construct_typed_array<
         't,
@@ -651,28 +1148,78 @@ impl Runtime {
     }
 }
 
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        // Objects that are still rooted when the runtime is dropped are kept alive by
+        // the host, e.g. by a forgotten `RootedStruct`.
+        let rooted_objects = self.shutdown_impl();
+        if rooted_objects.is_empty() {
+            return;
+        }
+
+        let mut report = format!(
+            "{} object(s) are still rooted while the runtime is dropped:",
+            rooted_objects.len()
+        );
+        for object in rooted_objects {
+            report.push_str(&format!(
+                "\n  - `{}` with {} root(s)",
+                object.ty, object.roots
+            ));
+            if let Some(backtrace) = object.backtrace {
+                report.push_str(&format!(", allocated at:\n{backtrace}"));
+            }
+        }
+        warn!("{report}");
+    }
+}
+
 /// An error that might occur when calling a mun function from Rust.
 pub struct InvokeErr<'name, T> {
-    msg: String,
+    kind: InvokeErrorKind,
     function_name: &'name str,
-    arguments: T,
+    /// The arguments of the invocation, unless they were consumed by executing
+    /// the function
+    arguments: Option<T>,
 }
 
 impl<T> Debug for InvokeErr<'_, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.msg)
+        write!(f, "{}", &self.kind)
     }
 }
 
 impl<T> Display for InvokeErr<'_, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.msg)
+        write!(f, "{}", &self.kind)
+    }
+}
+
+impl<T> InvokeErr<'_, T> {
+    /// Returns why invoking the function failed.
+    pub fn kind(&self) -> &InvokeErrorKind {
+        &self.kind
+    }
+
+    /// Returns the name of the function that was invoked.
+    pub fn function_name(&self) -> &str {
+        self.function_name
+    }
+
+    /// Returns the error that occurred while executing the Mun function, if the
+    /// function could be invoked at all.
+    pub fn runtime_error(&self) -> Option<&RuntimeError> {
+        match &self.kind {
+            InvokeErrorKind::Runtime(error) => Some(error),
+            _ => None,
+        }
     }
 }
 
 impl<T: InvokeArgs> InvokeErr<'_, T> {
     /// Retries a function invocation once, resulting in a potentially
-    /// successful invocation.
+    /// successful invocation. If the error occurred while executing the
+    /// function, its arguments were consumed and the error is returned as is.
     // FIXME: `unwrap_or_else` does not compile for `StructRef`, due to
     // https://doc.rust-lang.org/nomicon/lifetime-mismatch.html#improperly-reduced-borrows
     pub fn retry<'r, 'o, Output>(self, runtime: &'r mut Runtime) -> Result<Output, Self>
@@ -687,6 +1234,11 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
 
     /// Retries the function invocation until it succeeds, resulting in an
     /// output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the error occurred while executing the function, because its
+    /// arguments were consumed and the invocation can no longer be retried.
     // FIXME: `unwrap_or_else` does not compile for `StructRef`, due to
     // https://doc.rust-lang.org/nomicon/lifetime-mismatch.html#improperly-reduced-borrows
     pub fn wait<'r, 'o, Output>(mut self, runtime: &'r mut Runtime) -> Output
@@ -697,6 +1249,9 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
         loop {
             self = match unsafe { self.retry_impl(runtime) } {
                 Ok(output) => return output,
+                Err(e) if e.arguments.is_none() => {
+                    panic!("cannot retry `{}`: {}", e.function_name, e.kind)
+                }
                 Err(e) => e,
             };
         }
@@ -711,19 +1266,25 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
     /// When calling this function, you have to guarantee that `runtime` can be
     /// dereferenced and is valid for `'o`. The `Output` value can only
     /// contain a shared borrow of `runtime`.
-    unsafe fn retry_impl<'o, Output>(self, runtime: *mut Runtime) -> Result<Output, Self>
+    unsafe fn retry_impl<'o, Output>(mut self, runtime: *mut Runtime) -> Result<Output, Self>
     where
         Output: 'o + ReturnTypeReflection + Marshal<'o>,
     {
         // Safety: Guaranteed by the caller to be valid to dereference.
         let runtime = &mut *runtime;
 
-        eprintln!("{}", self.msg);
+        // The arguments were consumed by executing the function, so there is
+        // nothing to retry it with.
+        let Some(arguments) = self.arguments.take() else {
+            return Err(self);
+        };
+
+        eprintln!("{}", self.kind);
         while !runtime.update() {
             // Wait until there has been an update that might fix the error
         }
 
-        runtime.invoke(self.function_name, self.arguments)
+        runtime.invoke(self.function_name, arguments)
     }
 }
 
@@ -732,7 +1293,20 @@ impl<T: InvokeArgs> InvokeErr<'_, T> {
 pub trait InvokeArgs {
     /// Determines whether the specified function can be called with these
     /// arguments
-    fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String>;
+    fn can_invoke(
+        &self,
+        runtime: &Runtime,
+        signature: &FunctionSignature,
+    ) -> Result<(), InvokeErrorKind>;
+
+    /// Determines whether the specified function can be called with arguments
+    /// of these types. The types of arguments that depend on their values, as
+    /// indicated by [`InvokeArgs::has_dynamic_types`], are not checked.
+    fn can_bind(signature: &FunctionSignature) -> Result<(), InvokeErrorKind>;
+
+    /// Returns `true` if the type of any of the arguments depends on its
+    /// value, e.g. for structs and arrays.
+    fn has_dynamic_types() -> bool;
 
     /// Calls the specified function with these function arguments
     ///
@@ -741,6 +1315,17 @@ pub trait InvokeArgs {
     /// The `fn_ptr` is cast and invoked which might result in undefined
     /// behavior.
     unsafe fn invoke<ReturnType>(self, fn_ptr: *const c_void) -> ReturnType;
+
+    /// Calls the specified function with these function arguments, passing
+    /// `return_slot` as an extra first argument to which the function writes
+    /// its return value. This is used for functions that return a value struct.
+    ///
+    /// # Safety
+    ///
+    /// The `fn_ptr` is cast and invoked which might result in undefined
+    /// behavior. `return_slot` must point to memory that is large enough to
+    /// hold the return value.
+    unsafe fn invoke_with_return_slot(self, fn_ptr: *const c_void, return_slot: NonNull<u8>);
 }
 
 // Implement `InvokeTraits` for tuples up to and including 20 elements
@@ -749,34 +1334,69 @@ seq_macro::seq!(I in 0..N {
     #[allow(clippy::extra_unused_lifetimes)]
     impl<'arg, #(T~I: ArgumentReflection + Marshal<'arg>,)*> InvokeArgs for (#(T~I,)*) {
         #[allow(unused_variables)]
-        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String> {
+        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), InvokeErrorKind> {
+            let arg_types = &signature.arg_types;
+
+            // Ensure the number of arguments match
+            #[allow(clippy::len_zero)]
+            if N != arg_types.len() {
+                return Err(InvokeErrorKind::ArgumentCountMismatch { expected: arg_types.len(), found: N })
+            }
+
+            #(
+            let found = self.I.type_info(runtime);
+            if arg_types[I] != found {
+                return Err(InvokeErrorKind::ArgumentTypeMismatch {
+                    index: I,
+                    expected: arg_types[I].clone(),
+                    found,
+                });
+            }
+            )*
+
+            Ok(())
+        }
+
+        fn can_bind(signature: &FunctionSignature) -> Result<(), InvokeErrorKind> {
             let arg_types = &signature.arg_types;
 
             // Ensure the number of arguments match
             #[allow(clippy::len_zero)]
             if N != arg_types.len() {
-                return Err(format!("Invalid argument count. Expected {} arguments, got {}", arg_types.len(), N))
+                return Err(InvokeErrorKind::ArgumentCountMismatch { expected: arg_types.len(), found: N })
             }
 
             #(
-            if arg_types[I] != self.I.type_info(runtime) {
-                return Err(format!(
-                    "Invalid argument type at index {}. Expected: {}. Found: {}.",
-                    I,
-                    self.I.type_info(runtime).name(),
-                    arg_types[I].name(),
-                ));
+            if let Some(found) = T~I::static_type_info() {
+                if arg_types[I] != found {
+                    return Err(InvokeErrorKind::ArgumentTypeMismatch {
+                        index: I,
+                        expected: arg_types[I].clone(),
+                        found,
+                    });
+                }
             }
             )*
 
             Ok(())
         }
 
+        #[allow(clippy::nonminimal_bool)]
+        fn has_dynamic_types() -> bool {
+            false #(|| T~I::static_type_info().is_none())*
+        }
+
         unsafe fn invoke<ReturnType>(self, fn_ptr: *const c_void) -> ReturnType {
             #[allow(clippy::type_complexity)]
-            let function: fn(#(T~I::MunType,)*) -> ReturnType = core::mem::transmute(fn_ptr);
+            let function: extern "C-unwind" fn(#(T~I::MunType,)*) -> ReturnType = core::mem::transmute(fn_ptr);
             function(#(self.I.marshal_into(),)*)
         }
+
+        unsafe fn invoke_with_return_slot(self, fn_ptr: *const c_void, return_slot: NonNull<u8>) {
+            #[allow(clippy::type_complexity)]
+            let function: extern "C-unwind" fn(*mut u8, #(T~I::MunType,)*) = core::mem::transmute(fn_ptr);
+            function(return_slot.as_ptr(), #(self.I.marshal_into(),)*);
+        }
     }
 });
 )*});
@@ -799,24 +1419,13 @@ impl Runtime {
         'runtime: 'ret,
     {
         // Get the function information from the runtime
-        let function_info = match self.get_function_definition(function_name).ok_or_else(|| {
-            format!("failed to obtain function '{function_name}', no such function exists.")
-        }) {
+        let function_info = match self.find_public_function(function_name) {
             Ok(function_info) => function_info,
-            Err(msg) => {
-                let available_names = self.dispatch_table.get_fn_names();
-                let suggested_name =
-                    Self::find_best_match_for_fn_name(function_name, available_names, None);
-
-                let suggested_message = suggested_name.map_or_else(
-                    || msg.clone(),
-                    |name| format!("{msg} There is a function with a similar name: {name}"),
-                );
-
+            Err(kind) => {
                 return Err(InvokeErr {
-                    msg: suggested_message,
+                    kind,
                     function_name,
-                    arguments,
+                    arguments: Some(arguments),
                 });
             }
         };
@@ -824,29 +1433,342 @@ impl Runtime {
         // Validate the arguments
         match arguments.can_invoke(self, &function_info.prototype.signature) {
             Ok(_) => {}
-            Err(msg) => {
+            Err(kind) => {
                 return Err(InvokeErr {
-                    msg,
+                    kind,
                     function_name,
-                    arguments,
+                    arguments: Some(arguments),
                 })
             }
         };
 
         // Validate the return type
-        if !ReturnType::accepts_type(&function_info.prototype.signature.return_type) {
+        if let Err(kind) = check_return_type::<ReturnType>(&function_info.prototype.signature) {
             return Err(InvokeErr {
-                msg: format!(
-                    "unexpected return type, got '{}', expected '{}",
-                    &function_info.prototype.signature.return_type.name(),
-                    ReturnType::type_hint()
-                ),
+                kind,
                 function_name,
+                arguments: Some(arguments),
+            });
+        }
+
+        // Safety: The arguments and return type were validated against the
+        // signature of the function.
+        match unsafe { self.call(&function_info, arguments) } {
+            Ok(result) => Ok(result),
+            Err(error) => Err(InvokeErr {
+                kind: InvokeErrorKind::Runtime(error),
+                function_name,
+                arguments: None,
+            }),
+        }
+    }
+
+    /// Invokes the Mun function called `function_name` with the specified
+    /// dynamically typed `arguments`. Unlike [`Runtime::invoke`], the types of
+    /// the arguments and the return value don't have to be known at compile
+    /// time, which enables calling functions that are discovered at runtime,
+    /// e.g. from a console or an editor.
+    ///
+    /// The function is called through a trampoline that the compiler only
+    /// generates for public functions when dynamic invocation is enabled, e.g.
+    /// with `mun build --dynamic-invocation`. Functions that are defined by the
+    /// host cannot be invoked dynamically.
+    pub fn invoke_dynamic<'r>(
+        &'r self,
+        function_name: &str,
+        arguments: &[Value<'r>],
+    ) -> Result<Value<'r>, InvokeErrorKind> {
+        let function_info = self.find_public_function(function_name)?;
+        let signature = &function_info.prototype.signature;
+
+        // Validate the arguments
+        if arguments.len() != signature.arg_types.len() {
+            return Err(InvokeErrorKind::ArgumentCountMismatch {
+                expected: signature.arg_types.len(),
+                found: arguments.len(),
+            });
+        }
+        for (index, (argument, expected)) in arguments.iter().zip(&signature.arg_types).enumerate()
+        {
+            let found = argument.type_info();
+            if *expected != found {
+                return Err(InvokeErrorKind::ArgumentTypeMismatch {
+                    index,
+                    expected: expected.clone(),
+                    found,
+                });
+            }
+        }
+
+        if function_info.trampoline_ptr.is_null() || !value::can_return(&signature.return_type) {
+            return Err(InvokeErrorKind::UnsupportedDynamicSignature);
+        }
+
+        // Safety: The arguments and return type were validated against the
+        // signature of the function.
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            value::call_dynamic(
+                function_info.trampoline_ptr,
                 arguments,
+                &signature.return_type,
+                self,
+            )
+        }));
+        match result {
+            Ok(result) => Ok(result),
+            Err(payload) => match payload.downcast::<RuntimeError>() {
+                Ok(error) => Err(InvokeErrorKind::Runtime(*error)),
+                Err(payload) => std::panic::resume_unwind(payload),
+            },
+        }
+    }
+
+    /// Resolves the Mun function called `function_name` and validates that it
+    /// can be called with arguments of type `ArgTypes` and returns a
+    /// `ReturnType`. Invoking the resulting [`BoundFunction`] skips the lookup
+    /// and validation that [`Runtime::invoke`] performs for every call, until
+    /// the assemblies are reloaded.
+    pub fn bind<ArgTypes: InvokeArgs, ReturnType: ReturnTypeReflection>(
+        &self,
+        function_name: &str,
+    ) -> Result<BoundFunction<ArgTypes, ReturnType>, BoundFunctionError> {
+        BoundFunction::new(self, function_name)
+    }
+
+    /// Returns the definition of the public function called `function_name`.
+    /// If there is no such function, the error message suggests a function
+    /// with a similar name.
+    fn find_public_function(
+        &self,
+        function_name: &str,
+    ) -> Result<Arc<FunctionDefinition>, InvokeErrorKind> {
+        if let Some(function_info) = self.get_function_definition(function_name) {
+            return Ok(function_info);
+        }
+
+        if self.dispatch_table.get_fn(function_name).is_some() {
+            return Err(InvokeErrorKind::FunctionNotPublic {
+                name: function_name.to_owned(),
             });
         }
 
-        let result: ReturnType::MunType = unsafe { arguments.invoke(function_info.fn_ptr) };
-        Ok(Marshal::marshal_from(result, self))
+        let available_names = self.dispatch_table.get_public_fn_names();
+        let similar_name = Self::find_best_match_for_fn_name(function_name, available_names, None);
+        Err(InvokeErrorKind::FunctionNotFound {
+            name: function_name.to_owned(),
+            similar_name: similar_name.map(ToOwned::to_owned),
+        })
+    }
+
+    /// Calls the function of `function_info` with the specified `arguments`.
+    /// Mun code that panics unwinds the stack up to here, in which case the
+    /// error that occurred is returned.
+    ///
+    /// # Safety
+    ///
+    /// The types of the arguments and the return type must match the signature
+    /// of the function.
+    unsafe fn call<'runtime, 'ret, ReturnType: Marshal<'ret> + 'ret, ArgTypes: InvokeArgs>(
+        &'runtime self,
+        function_info: &FunctionDefinition,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, RuntimeError>
+    where
+        'runtime: 'ret,
+    {
+        let return_type = &function_info.prototype.signature.return_type;
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            if return_type
+                .as_struct()
+                .is_some_and(|struct_type| struct_type.is_value_struct())
+            {
+                // A value struct is written to a buffer provided by the caller
+                let return_slot = ReturnSlot::new(return_type);
+                arguments.invoke_with_return_slot(function_info.fn_ptr, return_slot.as_ptr());
+                ReturnType::marshal_from_ptr(return_slot.as_ptr().cast(), self, return_type)
+            } else {
+                let result = arguments.invoke::<ReturnType::MunType>(function_info.fn_ptr);
+                Marshal::marshal_from(result, self)
+            }
+        }));
+        match result {
+            Ok(result) => Ok(result),
+            Err(payload) => match payload.downcast::<RuntimeError>() {
+                Ok(error) => Err(*error),
+                Err(payload) => std::panic::resume_unwind(payload),
+            },
+        }
+    }
+}
+
+/// Validates that a function with the specified signature returns a value that
+/// can be stored in a `ReturnType`.
+fn check_return_type<ReturnType: ReturnTypeReflection>(
+    signature: &FunctionSignature,
+) -> Result<(), InvokeErrorKind> {
+    if ReturnType::accepts_type(&signature.return_type) {
+        Ok(())
+    } else {
+        Err(InvokeErrorKind::ReturnTypeMismatch {
+            expected: ReturnType::type_hint(),
+            found: signature.return_type.clone(),
+        })
+    }
+}
+
+/// Returns the path from which the runtime loads the dependency `dependency`
+/// of the assembly at `library_path`. Tools that inspect assemblies use this to
+/// resolve dependencies exactly like the runtime does.
+pub fn resolve_dependency_path(library_path: &Path, dependency: &str) -> PathBuf {
+    let parent = library_path.parent().expect("Invalid library path");
+    dependency_path(parent, dependency, library_path.extension())
+}
+
+/// Returns true if a dependency with the specified `version` satisfies the
+/// version `requirement` that a dependent assembly was compiled against.
+pub fn is_compatible_version(requirement: &str, version: Option<&str>) -> bool {
+    semver::VersionReq::parse(requirement)
+        .ok()
+        .zip(version.and_then(|version| semver::Version::parse(version).ok()))
+        .is_some_and(|(requirement, version)| requirement.matches(&version))
+}
+
+/// Returns the path of the assembly `dependency` of an assembly stored in the
+/// `parent` directory. Dependencies are relative to the directory of the
+/// dependent assembly and may refer to parent directories, e.g. `../foo`.
+fn dependency_path(parent: &Path, dependency: &str, extension: Option<&OsStr>) -> PathBuf {
+    let mut library_path = parent.join(dependency);
+    if let Some(extension) = extension {
+        library_path.set_extension(extension);
+    }
+
+    // Resolve `..` components, so an assembly is always identified by the same path
+    library_path
+        .canonicalize()
+        .unwrap_or_else(|_| normalize_path(&library_path))
+}
+
+/// Lexically resolves the `.` and `..` components of `path`, without accessing
+/// the file system. Used for the paths of libraries that are loaded from
+/// memory, which don't exist on disk.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Checks that the versions of the dependencies of the `assemblies` satisfy the
+/// version requirements they were compiled against. Dependencies are looked up
+/// with `find_assembly`; dependencies that cannot be found are ignored.
+fn check_dependency_versions<'a>(
+    assemblies: impl Iterator<Item = (&'a PathBuf, &'a Assembly)>,
+    find_assembly: impl Fn(&Path) -> Option<&'a Assembly>,
+) -> Result<(), LinkError> {
+    for (library_path, assembly) in assemblies {
+        for (dependency, requirement) in assembly.info().dependency_requirements() {
+            let Some(requirement) = requirement else {
+                continue;
+            };
+
+            let dependency_path = resolve_dependency_path(library_path, dependency);
+            let Some(dependency) = find_assembly(&dependency_path) else {
+                continue;
+            };
+
+            let version = dependency.info().version();
+            if !is_compatible_version(requirement, version) {
+                return Err(LinkError::MismatchedDependencyVersion {
+                    dependent: library_path.clone(),
+                    dependency: dependency_path,
+                    requirement: requirement.to_owned(),
+                    version: version.map(ToOwned::to_owned),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the `assemblies` ordered such that every assembly comes after the
+/// assemblies it depends on.
+fn dependency_order(assemblies: &HashMap<PathBuf, Assembly>) -> Vec<(&PathBuf, &Assembly)> {
+    fn visit<'a>(
+        library_path: &'a PathBuf,
+        assembly: &'a Assembly,
+        assemblies: &'a HashMap<PathBuf, Assembly>,
+        visited: &mut HashSet<&'a PathBuf>,
+        order: &mut Vec<(&'a PathBuf, &'a Assembly)>,
+    ) {
+        if !visited.insert(library_path) {
+            return;
+        }
+
+        let parent = library_path.parent().expect("Invalid library path");
+        for dependency in assembly.info().dependencies() {
+            let dependency = dependency_path(parent, dependency, library_path.extension());
+            if let Some((dependency_path, dependency)) = assemblies.get_key_value(&dependency) {
+                visit(dependency_path, dependency, assemblies, visited, order);
+            }
+        }
+
+        order.push((library_path, assembly));
+    }
+
+    let mut visited = HashSet::new();
+    let mut order = Vec::with_capacity(assemblies.len());
+    for (library_path, assembly) in assemblies.iter().sorted_by_key(|(path, _)| *path) {
+        visit(library_path, assembly, assemblies, &mut visited, &mut order);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsStr, path::Path};
+
+    use super::{dependency_path, is_compatible_version, normalize_path};
+
+    #[test]
+    fn normalize_path_resolves_parent_directories() {
+        assert_eq!(
+            normalize_path(Path::new("/target/foo/./../bar/baz")),
+            Path::new("/target/bar/baz")
+        );
+    }
+
+    #[test]
+    fn dependency_path_is_relative_to_dependent() {
+        // The paths don't exist, so they are resolved lexically
+        let parent = Path::new("/does/not/exist/target/foo");
+        let extension = Some(OsStr::new("munlib"));
+        assert_eq!(
+            dependency_path(parent, "bar", extension),
+            Path::new("/does/not/exist/target/foo/bar.munlib")
+        );
+        assert_eq!(
+            dependency_path(parent, "../mod", extension),
+            Path::new("/does/not/exist/target/mod.munlib")
+        );
+        assert_eq!(
+            dependency_path(parent, "../baz/qux", None),
+            Path::new("/does/not/exist/target/baz/qux")
+        );
+    }
+
+    #[test]
+    fn compatible_versions() {
+        assert!(is_compatible_version("^0.1.0", Some("0.1.3")));
+        assert!(!is_compatible_version("^0.1.0", Some("1.0.0")));
+        assert!(!is_compatible_version("^0.1.0", None));
+        assert!(!is_compatible_version("not a requirement", Some("0.1.0")));
     }
 }
@@ -1,4 +1,4 @@
-use std::ptr::NonNull;
+use std::{alloc::Layout, ptr::NonNull};
 
 use mun_memory::Type;
 
@@ -35,3 +35,42 @@ pub trait Marshal<'t>: Sized {
     /// Marshals `value` to memory location `ptr` (i.e. Rust -> Mun).
     fn marshal_to_ptr(value: Self, ptr: NonNull<Self::MunType>, type_info: &Type);
 }
+
+/// A buffer, with the layout of a value struct, to which a Mun function writes
+/// its return value. Public Mun functions that return a value struct receive a
+/// pointer to such a buffer as their first argument.
+pub(crate) struct ReturnSlot {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl ReturnSlot {
+    /// Allocates an uninitialized buffer for a value of the specified type.
+    pub fn new(type_info: &Type) -> Self {
+        let layout = type_info.value_layout();
+        let ptr = if layout.size() == 0 {
+            // A zero-sized allocation is not allowed, but no memory is ever read from or
+            // written to a zero-sized value.
+            NonNull::new(layout.align() as *mut u8).expect("alignment is never zero")
+        } else {
+            // SAFETY: The layout has a non-zero size
+            NonNull::new(unsafe { std::alloc::alloc(layout) })
+                .unwrap_or_else(|| std::alloc::handle_alloc_error(layout))
+        };
+        Self { ptr, layout }
+    }
+
+    /// Returns a pointer to the buffer.
+    pub fn as_ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+}
+
+impl Drop for ReturnSlot {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: The memory was allocated with the same layout in `ReturnSlot::new`
+            unsafe { std::alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use mun_abi as abi;
+use mun_memory::type_table::TypeTable;
+use rustc_hash::FxHashMap;
+use serde_derive::Serialize;
+
+/// The metadata of all assemblies that are loaded by a [`Runtime`], returned
+/// by [`Runtime::metadata`].
+///
+/// [`Runtime`]: crate::Runtime
+/// [`Runtime::metadata`]: crate::Runtime::metadata
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RuntimeMetadata {
+    /// The version of the ABI of the assemblies
+    pub abi_version: u32,
+    /// The metadata of the loaded assemblies, ordered by their library path
+    pub assemblies: Vec<AssemblyMetadata>,
+}
+
+/// Where a function or type that is known to a [`Runtime`] is defined,
+/// returned by [`Runtime::functions`] and [`Runtime::types`].
+///
+/// [`Runtime`]: crate::Runtime
+/// [`Runtime::functions`]: crate::Runtime::functions
+/// [`Runtime::types`]: crate::Runtime::types
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin<'r> {
+    /// Defined by the assembly with the specified library path
+    Assembly(&'r Path),
+    /// Provided by the host, e.g. a function that was inserted with
+    /// [`RuntimeBuilder::insert_fn`] or a primitive type
+    ///
+    /// [`RuntimeBuilder::insert_fn`]: crate::RuntimeBuilder::insert_fn
+    Host,
+}
+
+/// The API of an assembly: the functions and types that it exports and the
+/// assemblies that it depends on. In contrast to the [`abi::AssemblyInfo`] of
+/// an assembly, the metadata owns its data and can be serialized, which allows
+/// external tools to consume the API of an assembly.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AssemblyMetadata {
+    /// The path of the assembly's library
+    pub library_path: PathBuf,
+    /// The path of the module from which the assembly was compiled
+    pub module_path: String,
+    /// The paths of the assemblies that the assembly depends on, relative to
+    /// its library path
+    pub dependencies: Vec<String>,
+    /// The functions that the assembly exports
+    pub functions: Vec<FunctionMetadata>,
+    /// The types that the assembly defines
+    pub types: Vec<TypeMetadata>,
+}
+
+/// The signature of a function that is exported by an assembly.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FunctionMetadata {
+    /// The name of the function
+    pub name: String,
+    /// Whether the function can be called by the host
+    pub privacy: abi::Privacy,
+    /// The names of the types of the function's arguments
+    pub arg_types: Vec<String>,
+    /// The name of the function's return type, if it returns a value
+    pub return_type: Option<String>,
+}
+
+/// A type that is defined by an assembly.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TypeMetadata {
+    /// The name of the type
+    pub name: String,
+    /// The globally unique identifier of the type
+    pub guid: String,
+    /// The size of the type in bytes
+    pub size_in_bytes: usize,
+    /// The alignment of the type in bytes
+    pub alignment: usize,
+    /// The memory management of the type
+    pub memory_kind: abi::StructMemoryKind,
+    /// The fields of the type
+    pub fields: Vec<FieldMetadata>,
+}
+
+/// A field of a type that is defined by an assembly.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldMetadata {
+    /// The name of the field
+    pub name: String,
+    /// The name of the type of the field
+    pub type_name: String,
+    /// The offset of the field in bytes from the start of the type
+    pub offset: u16,
+}
+
+impl AssemblyMetadata {
+    /// Collects the metadata of an assembly from its ABI.
+    pub(crate) fn new(library_path: PathBuf, info: &abi::AssemblyInfo<'_>) -> Self {
+        let type_names = TypeNames::new(info);
+        let symbols = &info.symbols;
+
+        let functions = symbols
+            .functions()
+            .iter()
+            .map(|function| {
+                let signature = &function.prototype.signature;
+                FunctionMetadata {
+                    name: function.prototype.name().to_owned(),
+                    privacy: function.privacy,
+                    arg_types: signature
+                        .arg_types()
+                        .iter()
+                        .map(|ty| type_names.name(ty))
+                        .collect(),
+                    return_type: signature.return_type().map(|ty| type_names.name(&ty)),
+                }
+            })
+            .collect();
+
+        let types = symbols
+            .types()
+            .iter()
+            .filter_map(|ty| {
+                let s = ty.as_struct()?;
+                Some(TypeMetadata {
+                    name: ty.name().to_owned(),
+                    guid: s.guid.to_string(),
+                    size_in_bytes: ty.size_in_bytes(),
+                    alignment: ty.alignment(),
+                    memory_kind: s.memory_kind,
+                    fields: s
+                        .field_names()
+                        .zip(s.field_types())
+                        .zip(s.field_offsets())
+                        .map(|((name, ty), &offset)| FieldMetadata {
+                            name: name.to_owned(),
+                            type_name: type_names.name(ty),
+                            offset,
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        Self {
+            library_path,
+            module_path: symbols.path().to_owned(),
+            dependencies: info.dependencies().map(ToOwned::to_owned).collect(),
+            functions,
+            types,
+        }
+    }
+}
+
+/// Resolves the names of the types that are referred to by an assembly.
+struct TypeNames {
+    names: FxHashMap<abi::Guid, String>,
+    primitives: TypeTable,
+}
+
+impl TypeNames {
+    /// Collects the names of the types that the assembly defines and the types
+    /// that it looks up at runtime.
+    fn new(info: &abi::AssemblyInfo<'_>) -> Self {
+        let mut names = FxHashMap::default();
+        for (type_id, _, name) in info.type_lut.iter() {
+            if let abi::TypeId::Concrete(guid) = type_id {
+                names.insert(*guid, name.to_owned());
+            }
+        }
+        for ty in info.symbols.types() {
+            names.insert(*ty.as_concrete(), ty.name().to_owned());
+        }
+        Self {
+            names,
+            primitives: TypeTable::default(),
+        }
+    }
+
+    /// Returns the name of the type with the specified id, or the id itself if
+    /// the type is unknown.
+    fn name(&self, type_id: &abi::TypeId<'_>) -> String {
+        match type_id {
+            abi::TypeId::Concrete(guid) => self
+                .names
+                .get(guid)
+                .cloned()
+                .or_else(|| {
+                    self.primitives
+                        .find_type_info_by_id(type_id)
+                        .map(|ty| ty.name().to_owned())
+                })
+                .unwrap_or_else(|| guid.to_string()),
+            abi::TypeId::Pointer(pointer) => format!(
+                "*{} {}",
+                if pointer.mutable { "mut" } else { "const" },
+                self.name(pointer.pointee)
+            ),
+            abi::TypeId::Array(array) => format!("[{}]", self.name(array.element)),
+        }
+    }
+}
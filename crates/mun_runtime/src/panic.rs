@@ -0,0 +1,67 @@
+use std::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+    os::raw::c_char,
+};
+
+/// An error that occurs while executing Mun code.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RuntimeError {
+    /// The Mun code panicked, e.g. by calling `panic` or by a failed `assert`.
+    #[error("Mun code panicked at {location}: {message}")]
+    Panic {
+        /// The message of the panic
+        message: String,
+        /// The location in the source code that panicked
+        location: PanicLocation,
+    },
+    /// The Mun code allocated more memory than the limit set with
+    /// [`RuntimeBuilder::memory_limit`](crate::RuntimeBuilder::memory_limit).
+    #[error("Mun code exceeded the memory limit of {limit} bytes")]
+    MemoryLimitExceeded {
+        /// The maximum number of bytes that objects may occupy
+        limit: usize,
+    },
+}
+
+/// The location in the Mun source code where a panic occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanicLocation {
+    /// The path of the source file, relative to the source directory of the
+    /// package
+    pub file: String,
+    /// The one-based line number
+    pub line: u32,
+    /// The one-based column number
+    pub column: u32,
+}
+
+impl Display for PanicLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Called by Mun code when it panics. Instead of returning, this function
+/// unwinds the stack up to the `Runtime::invoke` call that executed the Mun
+/// code, which turns the panic into a [`RuntimeError`].
+pub(crate) extern "C-unwind" fn panic(message: *const u8, file: *const u8, line: u32, column: u32) {
+    // Safety: the Mun Compiler guarantees that `message` and `file` are
+    // null-terminated strings.
+    let to_string = |ptr: *const u8| {
+        unsafe { CStr::from_ptr(ptr.cast::<c_char>()) }
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let error = RuntimeError::Panic {
+        message: to_string(message),
+        location: PanicLocation {
+            file: to_string(file),
+            line,
+            column,
+        },
+    };
+
+    std::panic::resume_unwind(Box::new(error))
+}
@@ -16,6 +16,13 @@ pub trait ReturnTypeReflection: Sized {
 pub trait ArgumentReflection: Sized {
     /// Retrieves the argument's type information.
     fn type_info(&self, runtime: &Runtime) -> Type;
+
+    /// Retrieves the type information that all arguments of this type share,
+    /// or `None` if the type information depends on the value, e.g. for
+    /// structs and arrays.
+    fn static_type_info() -> Option<Type> {
+        None
+    }
 }
 
 macro_rules! impl_primitive_type {
@@ -25,6 +32,10 @@ macro_rules! impl_primitive_type {
                 fn type_info(&self, _runtime: &Runtime) -> Type {
                     <Self as HasStaticType>::type_info().clone()
                 }
+
+                fn static_type_info() -> Option<Type> {
+                    Some(<Self as HasStaticType>::type_info().clone())
+                }
             }
 
             impl ReturnTypeReflection for $ty {
@@ -104,6 +115,10 @@ where
     fn type_info(&self, _runtime: &Runtime) -> Type {
         <Self as HasStaticType>::type_info().clone()
     }
+
+    fn static_type_info() -> Option<Type> {
+        Some(<Self as HasStaticType>::type_info().clone())
+    }
 }
 
 impl<T> ReturnTypeReflection for *const T
@@ -126,6 +141,10 @@ where
     fn type_info(&self, _runtime: &Runtime) -> Type {
         <Self as HasStaticType>::type_info().clone()
     }
+
+    fn static_type_info() -> Option<Type> {
+        Some(<Self as HasStaticType>::type_info().clone())
+    }
 }
 
 impl<T> ReturnTypeReflection for *mut T
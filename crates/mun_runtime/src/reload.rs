@@ -0,0 +1,112 @@
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use mun_memory::{type_table::TypeTable, Type};
+
+use crate::{dispatch_table::DispatchTable, Assembly};
+
+/// A callback that is invoked after the runtime reloaded assemblies.
+pub(crate) type ReloadHandler = dyn FnMut(&ReloadEvent) + Send;
+
+/// Describes the changes of a successful reload of assemblies.
+///
+/// Hosts that cache [`crate::FunctionDefinition`]s or [`Type`]s must retrieve
+/// the changed items again, as the code of the previous versions is
+/// unloaded. Objects of changed types are mapped to their new layout by the
+/// runtime.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReloadEvent {
+    /// The generation of the runtime after the reload
+    pub generation: u64,
+    /// The paths of the assemblies that were relinked
+    pub relinked_assemblies: Vec<PathBuf>,
+    /// The names of the functions that were added
+    pub added_functions: Vec<String>,
+    /// The names of the functions whose definitions were replaced
+    pub changed_functions: Vec<String>,
+    /// The names of the functions that were removed
+    pub removed_functions: Vec<String>,
+    /// The names of the types that were added
+    pub added_types: Vec<String>,
+    /// The names of the types whose layout or fields changed
+    pub changed_types: Vec<String>,
+    /// The names of the types that were removed
+    pub removed_types: Vec<String>,
+}
+
+impl ReloadEvent {
+    /// Constructs an event by comparing the dispatch tables and the types of
+    /// the relinked assemblies from before and after the reload.
+    pub(crate) fn new(
+        generation: u64,
+        mut relinked_assemblies: Vec<PathBuf>,
+        old_dispatch_table: &DispatchTable,
+        new_dispatch_table: &DispatchTable,
+        old_types: BTreeMap<String, Type>,
+        new_types: BTreeMap<String, Type>,
+    ) -> Self {
+        relinked_assemblies.sort();
+
+        let old_functions: BTreeMap<_, _> = old_dispatch_table.functions().collect();
+        let new_functions: BTreeMap<_, _> = new_dispatch_table.functions().collect();
+
+        let (added_functions, changed_functions, removed_functions) =
+            diff(&old_functions, &new_functions, |old, new| {
+                !Arc::ptr_eq(old, new)
+            });
+        let (added_types, changed_types, removed_types) =
+            diff(&old_types, &new_types, |old, new| old != new);
+
+        Self {
+            generation,
+            relinked_assemblies,
+            added_functions,
+            changed_functions,
+            removed_functions,
+            added_types,
+            changed_types,
+            removed_types,
+        }
+    }
+}
+
+/// Returns the names and types of the structs that are defined by the
+/// `assemblies`, as they are stored in the `type_table`.
+pub(crate) fn assembly_types<'a>(
+    assemblies: impl Iterator<Item = &'a Assembly>,
+    type_table: &TypeTable,
+) -> BTreeMap<String, Type> {
+    assemblies
+        .flat_map(|assembly| assembly.info().symbols.types())
+        .filter_map(|type_def| {
+            let name = type_def.name();
+            type_table
+                .find_type_info_by_name(name)
+                .map(|ty| (name.to_owned(), ty))
+        })
+        .collect()
+}
+
+/// Returns the sorted names of the entries that were added, changed, and
+/// removed between `old` and `new`.
+fn diff<K: ToString + Ord, V>(
+    old: &BTreeMap<K, V>,
+    new: &BTreeMap<K, V>,
+    is_changed: impl Fn(&V, &V) -> bool,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let added = new
+        .keys()
+        .filter(|name| !old.contains_key(name))
+        .map(ToString::to_string)
+        .collect();
+    let changed = new
+        .iter()
+        .filter(|(name, new)| old.get(name).is_some_and(|old| is_changed(old, new)))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|name| !new.contains_key(name))
+        .map(ToString::to_string)
+        .collect();
+    (added, changed, removed)
+}
@@ -0,0 +1,250 @@
+use std::{
+    ffi::c_void,
+    mem::{align_of, size_of, MaybeUninit},
+    ptr::NonNull,
+};
+
+use mun_memory::{
+    gc::{GcPtr, RawGcPtr},
+    HasStaticType, Type,
+};
+
+use crate::{
+    array::RawArray,
+    marshal::{Marshal, ReturnSlot},
+    ArrayRef, Runtime, StructRef,
+};
+
+/// A dynamically typed Mun value. Enables invoking functions whose signature is
+/// only known at runtime, see [`Runtime::invoke_dynamic`].
+#[derive(Clone)]
+pub enum Value<'r> {
+    /// The empty tuple `()`, returned by functions that don't return a value
+    Empty,
+    /// A `bool`
+    Bool(bool),
+    /// An `i8`
+    I8(i8),
+    /// An `i16`
+    I16(i16),
+    /// An `i32`
+    I32(i32),
+    /// An `i64`
+    I64(i64),
+    /// An `i128`
+    I128(i128),
+    /// An `isize`
+    ISize(isize),
+    /// A `u8`
+    U8(u8),
+    /// A `u16`
+    U16(u16),
+    /// A `u32`
+    U32(u32),
+    /// A `u64`
+    U64(u64),
+    /// A `u128`
+    U128(u128),
+    /// A `usize`
+    USize(usize),
+    /// An `f32`
+    F32(f32),
+    /// An `f64`
+    F64(f64),
+    /// A reference to a struct
+    Struct(StructRef<'r>),
+    /// A reference to an array, whose elements are dynamically typed values
+    Array(ArrayRef<'r, Value<'r>>),
+}
+
+impl<'r> Value<'r> {
+    /// Returns the type information of the value.
+    pub fn type_info(&self) -> Type {
+        match self {
+            Value::Empty => <()>::type_info().clone(),
+            Value::Bool(_) => bool::type_info().clone(),
+            Value::I8(_) => i8::type_info().clone(),
+            Value::I16(_) => i16::type_info().clone(),
+            Value::I32(_) => i32::type_info().clone(),
+            Value::I64(_) => i64::type_info().clone(),
+            Value::I128(_) => i128::type_info().clone(),
+            Value::ISize(_) => isize::type_info().clone(),
+            Value::U8(_) => u8::type_info().clone(),
+            Value::U16(_) => u16::type_info().clone(),
+            Value::U32(_) => u32::type_info().clone(),
+            Value::U64(_) => u64::type_info().clone(),
+            Value::U128(_) => u128::type_info().clone(),
+            Value::USize(_) => usize::type_info().clone(),
+            Value::F32(_) => f32::type_info().clone(),
+            Value::F64(_) => f64::type_info().clone(),
+            Value::Struct(value) => value.type_info(),
+            Value::Array(value) => value.type_info(),
+        }
+    }
+
+    /// Reads the value of type `type_info` that is stored at `ptr`. Returns
+    /// `None` if the type is not supported, e.g. for pointers.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid value of type `type_info`.
+    pub(crate) unsafe fn from_ptr(
+        ptr: NonNull<u8>,
+        runtime: &'r Runtime,
+        type_info: &Type,
+    ) -> Option<Self> {
+        macro_rules! read_primitive {
+            ($($ty:ty => $variant:ident),+) => {
+                $(
+                    if type_info == <$ty>::type_info() {
+                        return Some(Value::$variant(ptr.cast::<$ty>().as_ptr().read_unaligned()));
+                    }
+                )+
+            };
+        }
+
+        if type_info == <()>::type_info() {
+            return Some(Value::Empty);
+        }
+        read_primitive!(
+            bool => Bool,
+            i8 => I8,
+            i16 => I16,
+            i32 => I32,
+            i64 => I64,
+            i128 => I128,
+            isize => ISize,
+            u8 => U8,
+            u16 => U16,
+            u32 => U32,
+            u64 => U64,
+            u128 => U128,
+            usize => USize,
+            f32 => F32,
+            f64 => F64
+        );
+
+        if type_info.is_struct() {
+            Some(Value::Struct(StructRef::marshal_from_ptr(
+                ptr.cast(),
+                runtime,
+                type_info,
+            )))
+        } else if type_info.is_array() {
+            let handle = *ptr.cast::<GcPtr>().as_ptr();
+            Some(Value::Array(ArrayRef::new(RawArray(handle), runtime)))
+        } else {
+            None
+        }
+    }
+
+    /// Stores the value in an [`ArgumentSlot`], the way it is passed to a
+    /// function. Structs and arrays are passed as a handle.
+    fn into_argument_slot(self) -> ArgumentSlot {
+        match self {
+            Value::Empty => ArgumentSlot::new(()),
+            Value::Bool(value) => ArgumentSlot::new(value),
+            Value::I8(value) => ArgumentSlot::new(value),
+            Value::I16(value) => ArgumentSlot::new(value),
+            Value::I32(value) => ArgumentSlot::new(value),
+            Value::I64(value) => ArgumentSlot::new(value),
+            Value::I128(value) => ArgumentSlot::new(value),
+            Value::ISize(value) => ArgumentSlot::new(value),
+            Value::U8(value) => ArgumentSlot::new(value),
+            Value::U16(value) => ArgumentSlot::new(value),
+            Value::U32(value) => ArgumentSlot::new(value),
+            Value::U64(value) => ArgumentSlot::new(value),
+            Value::U128(value) => ArgumentSlot::new(value),
+            Value::USize(value) => ArgumentSlot::new(value),
+            Value::F32(value) => ArgumentSlot::new(value),
+            Value::F64(value) => ArgumentSlot::new(value),
+            Value::Struct(value) => ArgumentSlot::new(RawGcPtr::from(value.into_raw().0)),
+            Value::Array(value) => ArgumentSlot::new(RawGcPtr::from(value.into_raw().0)),
+        }
+    }
+}
+
+/// A buffer that holds a single argument or return value of a function that is
+/// invoked through its trampoline. It is large enough and sufficiently aligned
+/// for any primitive or handle.
+#[repr(C, align(16))]
+struct ArgumentSlot(MaybeUninit<[u8; 16]>);
+
+impl ArgumentSlot {
+    /// Constructs a slot that holds `value`.
+    fn new<T: Copy>(value: T) -> Self {
+        assert!(size_of::<T>() <= size_of::<Self>() && align_of::<T>() <= align_of::<Self>());
+        let mut slot = Self(MaybeUninit::uninit());
+        // Safety: the slot is large enough and sufficiently aligned to hold a `T`
+        unsafe { slot.as_mut_ptr().cast::<T>().as_ptr().write(value) };
+        slot
+    }
+
+    /// Constructs an uninitialized slot, to which a return value is written.
+    fn uninit() -> Self {
+        Self(MaybeUninit::uninit())
+    }
+
+    /// Returns a pointer to the contents of the slot.
+    fn as_mut_ptr(&mut self) -> NonNull<u8> {
+        NonNull::from(&mut self.0).cast()
+    }
+}
+
+/// The signature of the trampoline of a function, see
+/// [`mun_abi::FunctionDefinition`].
+type Trampoline = extern "C-unwind" fn(*const *mut c_void, *mut c_void);
+
+/// Returns whether a function that returns a value of type `type_info` can be
+/// invoked dynamically.
+pub(crate) fn can_return(type_info: &Type) -> bool {
+    type_info == <()>::type_info()
+        || type_info.is_primitive()
+        || type_info.is_struct()
+        || type_info.is_array()
+}
+
+/// Calls the function whose trampoline is `trampoline`, and which returns a
+/// value of type `return_type`, with the specified arguments.
+///
+/// # Safety
+///
+/// `trampoline` must be the trampoline of a function, the arguments must match
+/// the signature of that function, and the return type must be supported, see
+/// [`can_return`].
+pub(crate) unsafe fn call_dynamic<'r>(
+    trampoline: *const c_void,
+    arguments: &[Value<'r>],
+    return_type: &Type,
+    runtime: &'r Runtime,
+) -> Value<'r> {
+    let trampoline: Trampoline = std::mem::transmute(trampoline);
+
+    let mut argument_slots: Vec<ArgumentSlot> = arguments
+        .iter()
+        .cloned()
+        .map(Value::into_argument_slot)
+        .collect();
+    let argument_ptrs: Vec<*mut c_void> = argument_slots
+        .iter_mut()
+        .map(|slot| slot.as_mut_ptr().cast().as_ptr())
+        .collect();
+
+    let value = if return_type
+        .as_struct()
+        .is_some_and(|struct_type| struct_type.is_value_struct())
+    {
+        // A value struct is written to a buffer with the layout of the struct
+        let return_slot = ReturnSlot::new(return_type);
+        trampoline(argument_ptrs.as_ptr(), return_slot.as_ptr().cast().as_ptr());
+        Value::from_ptr(return_slot.as_ptr(), runtime, return_type)
+    } else {
+        let mut return_slot = ArgumentSlot::uninit();
+        trampoline(
+            argument_ptrs.as_ptr(),
+            return_slot.as_mut_ptr().cast().as_ptr(),
+        );
+        Value::from_ptr(return_slot.as_mut_ptr(), runtime, return_type)
+    };
+    value.expect("the return type is validated")
+}
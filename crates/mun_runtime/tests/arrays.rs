@@ -1,5 +1,5 @@
 use itertools::Itertools;
-use mun_runtime::{ArrayRef, StructRef};
+use mun_runtime::{ArrayRef, PanicLocation, RuntimeError, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -51,8 +51,8 @@ fn arrays_as_argument() {
     let driver = CompileAndRunTestDriver::new(
         r"
     pub fn generate() -> [i32] { [5,4,3,2,1] }
-    pub fn add_one(array: [i32], len: usize) -> [i32] {
-        let i = 0;
+    pub fn add_one(mut array: [i32], len: usize) -> [i32] {
+        let mut i = 0;
         loop {
             array[i] += 1;
             i += 1;
@@ -103,6 +103,38 @@ fn root_array() {
     assert_eq!(result.iter().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
 }
 
+#[test]
+fn iterating_pins_array() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> [i32] { [5,4,3,2,1] }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    // Garbage in front of the array makes compaction relocate it
+    for _ in 0..3 {
+        let _: ArrayRef<'_, i32> = runtime.invoke("main", ()).unwrap();
+    }
+    let array: ArrayRef<'_, i32> = runtime.invoke("main", ()).unwrap();
+    let array = array.root();
+    let array = array.as_ref(runtime);
+    let data = unsafe { array.clone().into_raw().get_ptr() };
+
+    let mut iter = array.iter();
+    assert_eq!(iter.next(), Some(5));
+    runtime.gc_collect_and_compact();
+    assert_eq!(unsafe { array.clone().into_raw().get_ptr() }, data);
+    assert_eq!(iter.collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+    // Once the iterator is dropped, the array can be relocated
+    runtime.gc_collect_and_compact();
+    assert_ne!(unsafe { array.clone().into_raw().get_ptr() }, data);
+    assert_eq!(array.iter().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+}
+
 #[test]
 fn construct_array_simple() {
     let driver =
@@ -114,3 +146,105 @@ fn construct_array_simple() {
     assert_eq!(array.len(), test_data.len());
     assert_eq!(array.iter().collect_vec(), test_data);
 }
+
+#[test]
+fn for_loops() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn sum_array(array: [i32]) -> i32 {
+        let mut sum = 0;
+        for value in array {
+            sum += value;
+        }
+        sum
+    }
+    pub fn sum_range(n: i32) -> i32 {
+        let mut sum = 0;
+        for i in 0..n {
+            sum += i;
+        }
+        sum
+    }
+    pub fn count_inclusive_range(n: u8) -> u32 {
+        let mut count = 0;
+        for _ in 0..=n {
+            count += 1;
+        }
+        count
+    }
+    pub fn first_even(array: [i32]) -> i32 {
+        let mut result = -1;
+        for value in array {
+            if value % 2 == 0 {
+                result = value;
+                break;
+            }
+        }
+        result
+    }
+    pub fn generate() -> [i32] { [5,3,8,2,1] }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let array: ArrayRef<'_, i32> = driver.runtime.invoke("generate", ()).unwrap();
+    let result: i32 = driver.runtime.invoke("sum_array", (array,)).unwrap();
+    assert_eq!(result, 19);
+
+    let array: ArrayRef<'_, i32> = driver.runtime.invoke("generate", ()).unwrap();
+    let result: i32 = driver.runtime.invoke("first_even", (array,)).unwrap();
+    assert_eq!(result, 8);
+
+    let result: i32 = driver.runtime.invoke("sum_range", (10i32,)).unwrap();
+    assert_eq!(result, 45);
+
+    let result: u32 = driver
+        .runtime
+        .invoke("count_inclusive_range", (255u8,))
+        .unwrap();
+    assert_eq!(result, 256);
+}
+
+#[test]
+fn index_out_of_bounds() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn get(index: i32) -> i32 {
+        let a = [1,2,3]
+        a[index]
+    }
+    pub fn set(index: u64) {
+        let mut a = [1,2,3]
+        a[index] = 4
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("get", (2i32,)).unwrap();
+    assert_eq!(result, 3);
+
+    for index in [3i32, -1i32] {
+        let result: Result<i32, _> = driver.runtime.invoke("get", (index,));
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.runtime_error(),
+            Some(&RuntimeError::Panic {
+                message: String::from("index out of bounds"),
+                location: PanicLocation {
+                    file: String::from("mod.mun"),
+                    line: 4,
+                    column: 9,
+                },
+            })
+        );
+    }
+
+    let result: Result<(), _> = driver.runtime.invoke("set", (3u64,));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Mun code panicked at mod.mun:8:9: index out of bounds"
+    );
+}
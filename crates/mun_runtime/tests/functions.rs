@@ -1,6 +1,11 @@
 #[macro_use]
 mod util;
 
+use mun_compiler::{Config, OptimizationLevel};
+use mun_runtime::{
+    BoundFunctionError, InvokeErrorKind, LinkFunctionsError, PanicLocation, RuntimeBuilder,
+    RuntimeError, StructRef, Value,
+};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -24,6 +29,329 @@ fn unknown_function() {
     );
 }
 
+/// Compiles `text` with trampolines so that its public functions can be
+/// invoked with `Runtime::invoke_dynamic`.
+fn dynamic_invocation_driver(
+    text: &str,
+    config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+) -> CompileAndRunTestDriver {
+    CompileAndRunTestDriver::with_compiler_config(
+        text,
+        Config {
+            dynamic_invocation: true,
+            ..Config::default()
+        },
+        config_fn,
+    )
+    .expect("Failed to build test driver")
+}
+
+#[test]
+fn invoke_dynamic() {
+    extern "C" fn host_add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let driver = dynamic_invocation_driver(
+        r#"
+    pub fn mix(a: i8, b: f32, c: u64, d: f64, e: bool) -> f64 {
+        if e && a == -1 && b == 0.5 && c == 2 { d * 7.0 } else { 0.0 }
+    }
+    pub fn numbers() -> [i32] { [1, 2, 3] }
+    pub fn first(array: [i32]) -> i32 { array[0] }
+    pub fn half(a: f32) -> f32 { a / 2.0 }
+    pub fn nothing() {}
+    pub fn wide(a: i128, b: u128) -> u128 { if a == -1 { b + 1 } else { 0 } }
+    pub fn fail() -> i32 { panic("fail") }
+    extern fn host_add(a: i32, b: i32) -> i32;
+    pub fn call_host() -> i32 { host_add(1, 2) }
+    "#,
+        |builder| builder.insert_fn("host_add", host_add as extern "C" fn(i32, i32) -> i32),
+    );
+    let runtime = &driver.runtime;
+
+    let result = runtime
+        .invoke_dynamic(
+            "mix",
+            &[
+                Value::I8(-1),
+                Value::F32(0.5),
+                Value::U64(2),
+                Value::F64(0.25),
+                Value::Bool(true),
+            ],
+        )
+        .unwrap();
+    assert!(matches!(result, Value::F64(value) if value == 1.75));
+
+    assert!(matches!(
+        runtime.invoke_dynamic("half", &[Value::F32(3.0)]).unwrap(),
+        Value::F32(value) if value == 1.5
+    ));
+    assert!(matches!(
+        runtime.invoke_dynamic("nothing", &[]).unwrap(),
+        Value::Empty
+    ));
+
+    // Arrays can be passed back to Mun and their elements are dynamically typed
+    let Value::Array(numbers) = runtime.invoke_dynamic("numbers", &[]).unwrap() else {
+        panic!("expected an array");
+    };
+    assert_eq!(
+        numbers
+            .values()
+            .map(|value| match value {
+                Value::I32(value) => value,
+                _ => panic!("expected an i32"),
+            })
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert!(matches!(
+        runtime
+            .invoke_dynamic("first", &[Value::Array(numbers)])
+            .unwrap(),
+        Value::I32(1)
+    ));
+
+    assert!(matches!(
+        runtime.invoke_dynamic("half", &[Value::F64(3.0)]),
+        Err(InvokeErrorKind::ArgumentTypeMismatch { index: 0, .. })
+    ));
+    // 128-bit integers are passed and returned with their real type
+    assert!(matches!(
+        runtime
+            .invoke_dynamic("wide", &[Value::I128(-1), Value::U128(u128::MAX - 1)])
+            .unwrap(),
+        Value::U128(u128::MAX)
+    ));
+
+    // Functions that are defined by the host don't have a trampoline
+    assert_eq!(
+        runtime
+            .invoke_dynamic("host_add", &[Value::I32(1), Value::I32(2)])
+            .err(),
+        Some(InvokeErrorKind::UnsupportedDynamicSignature)
+    );
+    assert!(matches!(
+        runtime.invoke_dynamic("fail", &[]),
+        Err(InvokeErrorKind::Runtime(_))
+    ));
+}
+
+#[test]
+fn invoke_dynamic_many_arguments() {
+    let driver = dynamic_invocation_driver(
+        r#"
+    pub fn many(a: i8, b: f32, c: i16, d: f64, e: i32, f: f32, g: i64, h: f64, i: u8, j: f64) -> f64 {
+        if a == 1 && b == 2.0 && c == 3 && e == 5 && f == 6.0 && g == 7 && i == 9 {
+            d + h + j
+        } else {
+            0.0
+        }
+    }
+    pub fn sum(a: i64, b: i64, c: i64, d: i64, e: i64, f: i64, g: i64, h: i64) -> i64 {
+        a + b + c + d + e + f + g + h * 100
+    }
+    "#,
+        |builder| builder,
+    );
+    let runtime = &driver.runtime;
+
+    // More integer arguments than there are argument registers, so the last ones
+    // are passed on the stack
+    assert!(matches!(
+        runtime
+            .invoke_dynamic(
+                "sum",
+                &[
+                    Value::I64(1),
+                    Value::I64(2),
+                    Value::I64(3),
+                    Value::I64(4),
+                    Value::I64(5),
+                    Value::I64(6),
+                    Value::I64(7),
+                    Value::I64(8),
+                ],
+            )
+            .unwrap(),
+        Value::I64(828)
+    ));
+    assert!(matches!(
+        runtime
+            .invoke_dynamic(
+                "many",
+                &[
+                    Value::I8(1),
+                    Value::F32(2.0),
+                    Value::I16(3),
+                    Value::F64(4.0),
+                    Value::I32(5),
+                    Value::F32(6.0),
+                    Value::I64(7),
+                    Value::F64(8.0),
+                    Value::U8(9),
+                    Value::F64(10.0),
+                ],
+            )
+            .unwrap(),
+        Value::F64(value) if value == 22.0
+    ));
+}
+
+#[test]
+fn invoke_dynamic_value_struct_return() {
+    let driver = dynamic_invocation_driver(
+        r#"
+    pub struct(value) Vec2 { x: f32, y: f32 }
+    pub struct(value) Big { a: i64, b: i64, c: i64, d: f64 }
+    pub fn scale(v: Vec2, factor: f32) -> Vec2 { Vec2 { x: v.x * factor, y: v.y * factor } }
+    pub fn big(a: i64, d: f64) -> Big { Big { a, b: a * 2, c: a * 3, d } }
+    "#,
+        |builder| builder,
+    );
+    let runtime = &driver.runtime;
+
+    let vec2 = runtime
+        .construct_struct("Vec2")
+        .unwrap()
+        .set("x", 1.0f32)
+        .unwrap()
+        .set("y", 2.0f32)
+        .unwrap()
+        .finish()
+        .unwrap();
+    let Value::Struct(scaled) = runtime
+        .invoke_dynamic("scale", &[Value::Struct(vec2), Value::F32(2.0)])
+        .unwrap()
+    else {
+        panic!("expected a struct");
+    };
+    assert_eq!(scaled.get::<f32>("x").unwrap(), 2.0);
+    assert_eq!(scaled.get::<f32>("y").unwrap(), 4.0);
+
+    // A struct that doesn't fit in registers is returned through a return slot
+    let Value::Struct(big) = runtime
+        .invoke_dynamic("big", &[Value::I64(7), Value::F64(0.5)])
+        .unwrap()
+    else {
+        panic!("expected a struct");
+    };
+    assert_eq!(big.get::<i64>("a").unwrap(), 7);
+    assert_eq!(big.get::<i64>("b").unwrap(), 14);
+    assert_eq!(big.get::<i64>("c").unwrap(), 21);
+    assert_eq!(big.get::<f64>("d").unwrap(), 0.5);
+}
+
+#[test]
+fn invoke_dynamic_requires_dynamic_invocation() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // Without `Config::dynamic_invocation` no trampolines are generated
+    assert_eq!(
+        driver
+            .runtime
+            .invoke_dynamic("add", &[Value::I32(1), Value::I32(2)])
+            .err(),
+        Some(InvokeErrorKind::UnsupportedDynamicSignature)
+    );
+}
+
+#[test]
+fn invoke_error_kinds() {
+    // A private function is only exported when another module uses it
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn fail() -> i32 { panic("fail") }
+    fn private() {}
+
+    //- /src/foo.mun
+    pub fn foo() { super::private() }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    let err = runtime.invoke::<i32, ()>("ad", ()).unwrap_err();
+    assert_eq!(err.function_name(), "ad");
+    assert_eq!(
+        err.kind(),
+        &InvokeErrorKind::FunctionNotFound {
+            name: String::from("ad"),
+            similar_name: Some(String::from("add")),
+        }
+    );
+
+    let err = runtime.invoke::<(), ()>("private", ()).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeErrorKind::FunctionNotPublic {
+            name: String::from("private"),
+        }
+    );
+
+    let err = runtime.invoke::<i32, _>("add", (1i32,)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeErrorKind::ArgumentCountMismatch {
+            expected: 2,
+            found: 1
+        }
+    );
+
+    let err = runtime.invoke::<i32, _>("add", (1i32, 2.0f32)).unwrap_err();
+    assert!(
+        matches!(
+            err.kind(),
+            InvokeErrorKind::ArgumentTypeMismatch { index: 1, expected, found }
+                if expected.equals::<i32>() && found.equals::<f32>()
+        ),
+        "{err}"
+    );
+    assert_eq!(
+        err.to_string(),
+        "Invalid argument type at index 1. Expected: core::i32. Found: core::f32."
+    );
+
+    let err = runtime.invoke::<f32, _>("add", (1i32, 2i32)).unwrap_err();
+    assert!(
+        matches!(
+            err.kind(),
+            InvokeErrorKind::ReturnTypeMismatch { expected: "core::f32", found }
+                if found.equals::<i32>()
+        ),
+        "{err}"
+    );
+
+    let err = runtime.invoke::<i32, ()>("fail", ()).unwrap_err();
+    assert!(
+        matches!(
+            err.kind(),
+            InvokeErrorKind::Runtime(RuntimeError::Panic { message, .. }) if message == "fail"
+        ),
+        "{err}"
+    );
+    assert_eq!(
+        err.runtime_error().map(ToString::to_string),
+        Some(err.kind().to_string())
+    );
+}
+
 #[test]
 fn exact_case_sensitive_match_exists_function() {
     const EXPECTED_FN_NAME: &str = "Foo";
@@ -121,3 +449,404 @@ fn multiple_match_exists_function() {
         )
     );
 }
+
+#[test]
+fn panic_is_returned_as_error() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn checked_div(a: i32, b: i32) -> i32 {
+        assert(b != 0, "division by zero");
+        a / b
+    }
+    pub fn fail() -> i32 {
+        panic("not implemented")
+    }
+    pub fn assert_default(a: bool) { assert(a) }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 3, driver, "checked_div", 6i32, 2i32);
+
+    let result: Result<i32, _> = driver.runtime.invoke("checked_div", (6i32, 0i32));
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.runtime_error(),
+        Some(&RuntimeError::Panic {
+            message: String::from("division by zero"),
+            location: PanicLocation {
+                file: String::from("mod.mun"),
+                line: 3,
+                column: 9,
+            },
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        "Mun code panicked at mod.mun:3:9: division by zero"
+    );
+
+    let result: Result<i32, _> = driver.runtime.invoke("fail", ());
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Mun code panicked at mod.mun:7:9: not implemented"
+    );
+
+    // The arguments were consumed by the panic, so retrying returns the error
+    // without waiting for a reload
+    let result: Result<i32, _> = err.retry(&mut driver.runtime);
+    assert!(result.unwrap_err().runtime_error().is_some());
+
+    let result: Result<(), _> = driver.runtime.invoke("assert_default", (false,));
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Mun code panicked at mod.mun:9:38: assertion failed"
+    );
+
+    // The runtime is still usable after a panic
+    assert_invoke_eq!(i32, 5, driver, "checked_div", 10i32, 2i32);
+}
+
+#[test]
+fn overflow_checks() {
+    let source = r"
+    pub fn add(a: u8, b: u8) -> u8 { a + b }
+    ";
+
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        source,
+        Config {
+            overflow_checks: true,
+            ..Config::default()
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(u8, 255, driver, "add", 200u8, 55u8);
+
+    let result: Result<u8, _> = driver.runtime.invoke("add", (200u8, 56u8));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Mun code panicked at mod.mun:2:38: attempt to add with overflow"
+    );
+
+    // Without overflow checks, the result wraps around
+    let driver = CompileAndRunTestDriver::new(source, |builder| builder)
+        .expect("Failed to build test driver");
+    assert_invoke_eq!(u8, 0, driver, "add", 200u8, 56u8);
+}
+
+#[test]
+fn builtin_math() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn length(x: f32, y: f32) -> f32 { sqrt(x * x + y * y) }
+    pub fn rotate_x(x: f64, y: f64, angle: f64) -> f64 { x * cos(angle) - y * sin(angle) }
+    pub fn abs_int(a: i32) -> i32 { abs(a) }
+    pub fn abs_float(a: f64) -> f64 { abs(a) }
+    pub fn clamp(a: i64, lo: i64, hi: i64) -> i64 { min(max(a, lo), hi) }
+    pub fn largest(a: f32, b: f32) -> f32 { max(a, b) }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(f32, 5.0, driver, "length", 3.0f32, 4.0f32);
+    assert_invoke_eq!(
+        f64,
+        -2.0,
+        driver,
+        "rotate_x",
+        1.0f64,
+        2.0f64,
+        std::f64::consts::FRAC_PI_2
+    );
+    assert_invoke_eq!(i32, 7, driver, "abs_int", -7i32);
+    assert_invoke_eq!(f64, 1.5, driver, "abs_float", -1.5f64);
+    assert_invoke_eq!(i64, 10, driver, "clamp", 42i64, -10i64, 10i64);
+    assert_invoke_eq!(i64, -10, driver, "clamp", -42i64, -10i64, 10i64);
+    assert_invoke_eq!(f32, 2.5, driver, "largest", -1.0f32, 2.5f32);
+}
+
+#[test]
+fn export_name() {
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    use package::foo::bar;
+
+    #[export_name = "calculate"]
+    pub fn main() -> i32 { bar() }
+
+    //- /src/foo.mun
+    #[inline]
+    #[export_name = "exported_bar"]
+    pub fn bar() -> i32 { 5 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "calculate");
+    assert_invoke_eq!(i32, 5, driver, "exported_bar");
+
+    let result: Result<i32, _> = driver.runtime.invoke("main", ());
+    assert!(result.is_err());
+}
+
+#[test]
+fn namespaced_host_functions() {
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    extern "C" fn sub(a: i32, b: i32) -> i32 {
+        a - b
+    }
+
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    #[link_namespace = "host::math"]
+    extern fn add(a: i32, b: i32) -> i32;
+
+    pub fn main() -> i32 { add(3, 4) * foo::diff(5, 3) }
+
+    //- /src/foo.mun
+    extern fn sub(a: i32, b: i32) -> i32;
+
+    pub(package) fn diff(a: i32, b: i32) -> i32 { sub(a, b) }
+    "#,
+        |builder| {
+            builder
+                .insert_fn_in("host::math", "add", add as extern "C" fn(i32, i32) -> i32)
+                .insert_fn_in("foo", "sub", sub as extern "C" fn(i32, i32) -> i32)
+        },
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 14, driver, "main");
+}
+
+#[test]
+fn host_function_collisions() {
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let error = CompileAndRunTestDriver::new(
+        r"
+    extern fn add(a: i32, b: i32) -> i32;
+    pub fn main() -> i32 { add(3, 4) }
+    ",
+        |builder| {
+            builder
+                .insert_fn("math::add", add as extern "C" fn(i32, i32) -> i32)
+                .insert_fn_in("math", "add", add as extern "C" fn(i32, i32) -> i32)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        LinkFunctionsError::DuplicateHostFunction {
+            fn_name: String::from("math::add")
+        }
+        .to_string()
+    );
+
+    let error = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder.insert_fn("add", add as extern "C" fn(i32, i32) -> i32),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        LinkFunctionsError::HostFunctionCollision {
+            fn_name: String::from("add")
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn bind_function() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    pub struct Bar { b: i32 }
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn new_foo(a: i32) -> Foo { Foo { a } }
+    pub fn new_bar(b: i32) -> Bar { Bar { b } }
+    pub fn get_a(foo: Foo) -> i32 { foo.a }
+    pub fn fail() { panic("failed") }
+    fn hidden() -> i32 { 5 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mut add = driver
+        .runtime
+        .bind::<(i32, i32), i32>("add")
+        .expect("Failed to bind function");
+    assert_eq!(add.name(), "add");
+    assert_eq!(add.invoke(&driver.runtime, (1, 2)), Ok(3));
+
+    // Arguments of which the type depends on their value are checked for every call
+    let foo: StructRef<'_> = driver.runtime.invoke("new_foo", (5i32,)).unwrap();
+    let bar: StructRef<'_> = driver.runtime.invoke("new_bar", (6i32,)).unwrap();
+    let mut get_a = driver
+        .runtime
+        .bind::<(StructRef<'_>,), i32>("get_a")
+        .expect("Failed to bind function");
+    assert_eq!(get_a.invoke(&driver.runtime, (foo,)), Ok(5));
+    assert!(matches!(
+        get_a.invoke(&driver.runtime, (bar,)),
+        Err(BoundFunctionError::Signature(
+            InvokeErrorKind::ArgumentTypeMismatch { index: 0, .. }
+        ))
+    ));
+
+    let mut fail = driver
+        .runtime
+        .bind::<(), ()>("fail")
+        .expect("Failed to bind function");
+    assert!(matches!(
+        fail.invoke(&driver.runtime, ()),
+        Err(BoundFunctionError::Runtime(RuntimeError::Panic { .. }))
+    ));
+
+    // The signature is validated when binding
+    assert!(driver.runtime.bind::<(i32, i32), i32>("unknown").is_err());
+    assert!(driver.runtime.bind::<(), i32>("hidden").is_err());
+    assert!(driver.runtime.bind::<(i32,), i32>("add").is_err());
+    assert!(driver.runtime.bind::<(f32, i32), i32>("add").is_err());
+    assert!(driver.runtime.bind::<(i32, i32), f32>("add").is_err());
+}
+
+#[test]
+fn non_public_functions_are_hidden() {
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub(package) fn helper() -> i32 { 5 }
+
+    pub fn main() -> i32 { foo::bar() }
+
+    //- /src/foo.mun
+    pub(super) fn bar() -> i32 { package::helper() + 1 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 6, driver, "main");
+
+    for name in ["helper", "foo::bar"] {
+        assert!(driver.runtime.get_function_definition(name).is_none());
+
+        let err = driver.runtime.invoke::<i32, ()>(name, ()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("failed to obtain function '{name}', the function is not public.")
+        );
+    }
+}
+
+#[test]
+fn tail_calls_do_not_grow_the_stack() {
+    // Compile without optimizations, so LLVM does not optimize the tail call itself
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn sum(n: i64, acc: i64) -> i64 {
+        if n == 0 {
+            acc
+        } else {
+            sum(n - 1, acc + n)
+        }
+    }
+    ",
+        Config {
+            optimization_lvl: OptimizationLevel::None,
+            ..Config::default()
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // Without tail call optimization, this would overflow the stack
+    let result: i64 = driver.runtime.invoke("sum", (10_000_000i64, 0i64)).unwrap();
+    assert_eq!(result, 50_000_005_000_000);
+}
+
+#[test]
+fn function_docs() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    /// Returns the answer.
+    ///
+    /// Computed by Deep Thought.
+    pub fn answer() -> i32 { 42 }
+    pub fn undocumented() -> i32 { 0 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let answer = driver
+        .runtime
+        .get_function_definition("answer")
+        .expect("missing function");
+    assert_eq!(
+        answer.docs(),
+        Some("Returns the answer.\n\nComputed by Deep Thought.")
+    );
+
+    let undocumented = driver
+        .runtime
+        .get_function_definition("undocumented")
+        .expect("missing function");
+    assert_eq!(undocumented.docs(), None);
+}
+
+#[test]
+fn const_items() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    const MAX_HEALTH: i32 = 10 * 10;
+    const QUARTER: f64 = 1.0 / 4.0;
+    const IS_HEALTHY: bool = MAX_HEALTH > 50;
+    pub fn health(damage: i32) -> i32 { MAX_HEALTH - damage }
+    pub fn quarter(a: f64) -> f64 { a * QUARTER }
+    pub fn is_healthy() -> bool { IS_HEALTHY }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    assert_eq!(runtime.invoke::<i32, _>("health", (30,)).unwrap(), 70);
+    assert_eq!(runtime.invoke::<f64, _>("quarter", (2.0f64,)).unwrap(), 0.5);
+    assert!(runtime.invoke::<bool, ()>("is_healthy", ()).unwrap());
+}
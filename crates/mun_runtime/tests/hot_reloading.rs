@@ -1,7 +1,9 @@
 #[macro_use]
 mod util;
 
-use mun_runtime::StructRef;
+use std::cell::RefCell;
+
+use mun_runtime::{BoundFunctionError, InvokeErrorKind, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -24,6 +26,299 @@ fn reloadable_function_single_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn bound_function_is_resolved_after_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn combine(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mut combine = driver
+        .runtime
+        .bind::<(i32, i32), i32>("combine")
+        .expect("Failed to bind function");
+    assert_eq!(combine.invoke(&driver.runtime, (2, 3)), Ok(5));
+    assert_eq!(combine.invoke(&driver.runtime, (4, 3)), Ok(7));
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn combine(a: i32, b: i32) -> i32 { a * b }
+    ",
+    );
+    assert_eq!(combine.invoke(&driver.runtime, (2, 3)), Ok(6));
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn combine(a: f32, b: f32) -> f32 { a * b }
+    ",
+    );
+    assert!(matches!(
+        combine.invoke(&driver.runtime, (2, 3)),
+        Err(BoundFunctionError::Signature(
+            InvokeErrorKind::ArgumentTypeMismatch { index: 0, .. }
+        ))
+    ));
+}
+
+#[test]
+fn generation_and_assembly_version() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_eq!(driver.runtime.generation(), 0);
+
+    let assembly = driver.runtime.assemblies().next().unwrap();
+    let (loaded_at, content_hash) = (assembly.loaded_at(), assembly.content_hash());
+    assert!(driver
+        .runtime
+        .get_assembly(assembly.library_path())
+        .is_some());
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+    assert_eq!(driver.runtime.generation(), 1);
+
+    let assembly = driver.runtime.assemblies().next().unwrap();
+    assert!(assembly.loaded_at() >= loaded_at);
+    assert_ne!(assembly.content_hash(), content_hash);
+}
+
+#[test]
+fn reload_events() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Foo { a: i32 }
+    pub struct Bar { b: i32 }
+    pub fn main() -> i32 { 5 }
+    pub fn removed() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let handler_events = events.clone();
+    driver
+        .runtime
+        .on_reload(move |event| handler_events.lock().unwrap().push(event.clone()));
+    assert!(driver.runtime.take_reload_events().is_empty());
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub struct Foo { a: i32 }
+    pub struct Bar { b: f64 }
+    pub fn main() -> i32 { 10 }
+    pub fn added() -> i32 { 5 }
+    ",
+    );
+
+    let reload_events = driver.runtime.take_reload_events();
+    assert_eq!(reload_events.len(), 1);
+    assert_eq!(*events.lock().unwrap(), reload_events);
+
+    let event = &reload_events[0];
+    assert_eq!(event.generation, 1);
+    assert_eq!(
+        event.relinked_assemblies,
+        vec![driver.lib_path().canonicalize().unwrap()]
+    );
+    assert_eq!(event.added_functions, vec!["added"]);
+    assert_eq!(event.changed_functions, vec!["main"]);
+    assert_eq!(event.removed_functions, vec!["removed"]);
+    assert!(event.added_types.is_empty());
+    assert_eq!(event.changed_types, vec!["Bar"]);
+    assert!(event.removed_types.is_empty());
+    assert!(driver.runtime.take_reload_events().is_empty());
+}
+
+#[test]
+fn manual_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    driver.recompile_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+    let lib_path = driver.lib_path().to_path_buf();
+    unsafe { driver.runtime.reload_assembly(&lib_path) }.expect("Failed to reload assembly");
+    assert_eq!(driver.runtime.generation(), 1);
+    assert_invoke_eq!(i32, 10, driver, "main");
+
+    driver.recompile_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 15 }
+    ",
+    );
+    unsafe { driver.runtime.reload_all() }.expect("Failed to reload assemblies");
+    assert_eq!(driver.runtime.generation(), 2);
+    assert_invoke_eq!(i32, 15, driver, "main");
+
+    let unknown_path = lib_path.with_file_name("unknown.munlib");
+    std::fs::write(&unknown_path, []).unwrap();
+    assert!(matches!(
+        unsafe { driver.runtime.reload_assembly(&unknown_path) },
+        Err(mun_runtime::LinkError::UnknownAssembly(_))
+    ));
+}
+
+#[test]
+fn manual_reload_without_file_watching() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        mun_runtime::RuntimeBuilder::disable_file_watching,
+    )
+    .expect("Failed to build test driver");
+
+    driver.recompile_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+
+    // Without a file watcher, the runtime doesn't notice the new assembly
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!unsafe { driver.runtime.update() });
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    let lib_path = driver.lib_path().to_path_buf();
+    unsafe { driver.runtime.reload_assembly(&lib_path) }.expect("Failed to reload assembly");
+    assert_eq!(driver.runtime.generation(), 1);
+    assert_invoke_eq!(i32, 10, driver, "main");
+}
+
+#[test]
+fn init_and_shutdown() {
+    thread_local! {
+        static EVENTS: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    extern "C" fn record(event: i32) {
+        EVENTS.with(|events| events.borrow_mut().push(event));
+    }
+
+    let take_events = || EVENTS.with(RefCell::take);
+
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    extern fn record(event: i32);
+    fn init() { record(1) }
+    fn shutdown() { record(2) }
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder.insert_fn("record", record as extern "C" fn(i32)),
+    )
+    .expect("Failed to build test driver");
+    assert_eq!(take_events(), [1]);
+
+    // The old assembly is shut down before the new assembly is initialized
+    driver.recompile_file(
+        "mod.mun",
+        r#"
+    extern fn record(event: i32);
+    fn init() {
+        record(3);
+        panic("init failed")
+    }
+    fn shutdown() { record(4) }
+    pub fn main() -> i32 { 10 }
+    "#,
+    );
+    unsafe { driver.runtime.reload_all() }.expect("Failed to reload assemblies");
+    assert_eq!(take_events(), [2, 3]);
+
+    // A panic in `init` does not prevent the assembly from being used
+    assert_invoke_eq!(i32, 10, driver, "main");
+
+    drop(driver);
+    assert_eq!(take_events(), [4]);
+}
+
+#[test]
+fn shutdown() {
+    thread_local! {
+        static EVENTS: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    extern "C" fn record(event: i32) {
+        EVENTS.with(|events| events.borrow_mut().push(event));
+    }
+
+    let mut driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    extern fn record(event: i32);
+    fn shutdown() { record(3) }
+    pub fn main() -> i32 { package::foo::bar() }
+    pub struct Foo { a: i32 }
+    pub fn new_foo() -> Foo { Foo { a: 5 } }
+
+    //- /src/foo.mun
+    extern fn record(event: i32);
+    fn shutdown() { record(4) }
+    pub fn bar() -> i32 { 5 }
+    "#,
+        |builder| {
+            // Extern functions are resolved by their full name
+            builder
+                .insert_fn("record", record as extern "C" fn(i32))
+                .insert_fn("foo::record", record as extern "C" fn(i32))
+        },
+    )
+    .expect("Failed to build test driver");
+
+    // Finalizers run in reverse order of their registration and can still invoke
+    // Mun functions
+    driver.runtime.on_shutdown(|runtime| {
+        record(runtime.invoke::<i32, ()>("main", ()).unwrap() - 3);
+    });
+    driver.runtime.on_shutdown(|_| record(1));
+
+    let foo: StructRef<'_> = driver.runtime.invoke("new_foo", ()).unwrap();
+    let leaked = foo.root();
+    let _unrooted: StructRef<'_> = driver.runtime.invoke("new_foo", ()).unwrap();
+
+    // Assemblies are shut down before the assemblies they depend on, and only
+    // the rooted object is reported
+    let rooted_objects = driver.runtime.shutdown();
+    assert_eq!(EVENTS.with(RefCell::take), [1, 2, 3, 4]);
+    assert_eq!(rooted_objects.len(), 1);
+    assert_eq!(rooted_objects[0].ty.name(), "Foo");
+    assert_eq!(rooted_objects[0].roots, 1);
+    drop(leaked);
+}
+
 #[test]
 fn reloadable_function_multi_file() {
     let mut driver = CompileAndRunTestDriver::from_fixture(
@@ -54,6 +349,102 @@ fn reloadable_function_multi_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn reloadable_function_nested_module() {
+    // The assembly of `foo::bar` is stored in a subdirectory and depends on the
+    // assembly of the root module through a relative path.
+    let mut driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { foo::bar::baz() }
+    pub fn five() -> i32 { 5 }
+
+    //- /src/foo.mun
+    pub fn unused() {}
+
+    //- /src/foo/bar.mun
+    pub fn baz() -> i32 { package::five() }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    driver.update_file(
+        "foo/bar.mun",
+        r#"
+    pub fn baz() -> i32 { package::five() * 2 }
+    "#,
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+}
+
+#[test]
+fn static_keeps_value_across_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    static COUNTER: u32 = 0;
+
+    pub fn tick() -> u32 {
+        COUNTER += 1;
+        COUNTER
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(u32, 1, driver, "tick");
+    assert_invoke_eq!(u32, 2, driver, "tick");
+
+    // The value of the static is copied into the new assembly, its new
+    // initializer is ignored.
+    driver.update_file(
+        "mod.mun",
+        r"
+    static COUNTER: u32 = 100;
+
+    pub fn tick() -> u32 {
+        COUNTER += 10;
+        COUNTER
+    }
+    ",
+    );
+    assert_invoke_eq!(u32, 12, driver, "tick");
+
+    // A static whose type changed is converted
+    driver.update_file(
+        "mod.mun",
+        r"
+    static COUNTER: u64 = 0;
+
+    pub fn tick() -> u64 {
+        COUNTER += 1;
+        COUNTER
+    }
+    ",
+    );
+    assert_invoke_eq!(u64, 13, driver, "tick");
+
+    // A static that cannot be converted keeps its initial value
+    driver.update_file(
+        "mod.mun",
+        r"
+    static COUNTER: bool = false;
+
+    pub fn tick() -> bool {
+        COUNTER = !COUNTER;
+        COUNTER
+    }
+    ",
+    );
+    assert_invoke_eq!(bool, true, driver, "tick");
+}
+
 #[test]
 fn reloadable_struct_decl_single_file() {
     let mut driver = CompileAndRunTestDriver::new(
@@ -1,4 +1,7 @@
-use mun_runtime::{ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_codegen_macros::TestAbiType;
+use mun_runtime::{
+    ArgumentReflection, ConstructStructError, Marshal, ReturnTypeReflection, StructRef,
+};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
@@ -138,9 +141,9 @@ fn fibonacci_loop() {
     let driver = CompileAndRunTestDriver::new(
         r#"
     pub fn fibonacci(n:i64)->i64 {
-        let a = 0;
-        let b = 1;
-        let i = 1;
+        let mut a = 0;
+        let mut b = 1;
+        let mut i = 1;
         loop {
             if i > n {
                 return a
@@ -167,9 +170,9 @@ fn fibonacci_loop_break() {
     let driver = CompileAndRunTestDriver::new(
         r#"
     pub fn fibonacci(n:i64)->i64 {
-        let a = 0;
-        let b = 1;
-        let i = 1;
+        let mut a = 0;
+        let mut b = 1;
+        let mut i = 1;
         loop {
             if i > n {
                 break a;
@@ -196,9 +199,9 @@ fn fibonacci_while() {
     let driver = CompileAndRunTestDriver::new(
         r#"
     pub fn fibonacci(n:i64)->i64 {
-        let a = 0;
-        let b = 1;
-        let i = 1;
+        let mut a = 0;
+        let mut b = 1;
+        let mut i = 1;
         while i <= n {
             let sum = a + b;
             a = b;
@@ -218,6 +221,96 @@ fn fibonacci_while() {
     assert_invoke_eq!(i64, 46368, driver, "fibonacci", 24i64);
 }
 
+#[test]
+fn labeled_break() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn find(n:i32)->i32 {
+        let mut i = 0;
+        'outer: loop {
+            for j in 0..n {
+                if i * j == n {
+                    break 'outer i + j;
+                }
+            }
+            i += 1;
+        }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 7, driver, "find", 10i32);
+    assert_invoke_eq!(i32, 8, driver, "find", 7i32);
+}
+
+#[test]
+fn continue_loops() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn sum_odd(n: i32) -> i32 {
+        let mut sum = 0;
+        for i in 0..n {
+            if i % 2 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn skip_three(n: i32) -> i32 {
+        let mut i = 0;
+        let mut sum = 0;
+        while i < n {
+            i += 1;
+            if i == 3 {
+                continue;
+            }
+            sum += i;
+        }
+        sum
+    }
+
+    pub fn skip_multiples_of_three(n: i32) -> i32 {
+        let mut i = 0;
+        let mut sum = 0;
+        loop {
+            i += 1;
+            if i > n {
+                break sum;
+            }
+            if i % 3 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+    }
+
+    pub fn triangle(n: i32) -> i32 {
+        let mut count = 0;
+        'outer: for i in 0..=n {
+            for j in 0..=n {
+                if j > i {
+                    continue 'outer;
+                }
+                count += 1;
+            }
+        }
+        count
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 25, driver, "sum_odd", 10i32);
+    assert_invoke_eq!(i32, 12, driver, "skip_three", 5i32);
+    assert_invoke_eq!(i32, 12, driver, "skip_multiples_of_three", 6i32);
+    assert_invoke_eq!(i32, 10, driver, "triangle", 3i32);
+}
+
 #[test]
 fn true_is_true() {
     let driver = CompileAndRunTestDriver::new(
@@ -304,9 +397,9 @@ fn fields() {
         r#"
         struct(gc) Foo { a:i32, b:i32 };
         pub fn main(foo:i32)->bool {
-            let a = Foo { a: foo, b: foo };
+            let mut a = Foo { a: foo, b: foo };
             a.a += a.b;
-            let result = a;
+            let mut result = a;
             result.a += a.b;
             result.a == a.a
         }
@@ -336,6 +429,49 @@ fn field_crash() {
     assert_invoke_eq!(i32, 15, driver, "main", 10i32);
 }
 
+#[test]
+fn destructuring() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Vec2 { x: f32, y: f32 }
+    pub struct(gc) Pair(i32, i32);
+
+    fn length_squared(Vec2 { x, y: y_coord }: Vec2) -> f32 {
+        x * x + y_coord * y_coord
+    }
+
+    fn first(Pair(a, _): Pair) -> i32 {
+        a
+    }
+
+    pub fn product(a: i32, b: i32) -> i32 {
+        let Pair(c, d) = Pair(a, b);
+        first(Pair(c * d, 0))
+    }
+
+    pub fn length_squared_with_x(x: f32, v: Vec2) -> f32 {
+        let Vec2 { y, .. } = v;
+        length_squared(Vec2 { x, y })
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 6, driver, "product", 2i32, 3i32);
+    let v = driver
+        .runtime
+        .construct_struct("Vec2")
+        .unwrap()
+        .set("x", 1.0f32)
+        .unwrap()
+        .set("y", 4.0f32)
+        .unwrap()
+        .finish()
+        .unwrap();
+    assert_invoke_eq!(f32, 25.0, driver, "length_squared_with_x", 3.0f32, v);
+}
+
 #[test]
 fn marshal_struct() {
     struct TestData<T>(T, T);
@@ -553,6 +689,157 @@ fn marshal_struct() {
     assert!(bar_err.is_err());
 }
 
+#[test]
+fn return_value_struct_round_trip() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Byte(u8);
+    pub struct(value) Pair(u8, u16);
+    pub struct(value) Word(u32);
+    pub struct(value) Mixed(u8, f64);
+    pub struct(value) Triple(i32, i32, i32);
+    pub struct(value) Floats(f32, f32, f32, f32);
+    pub struct(value) Large(i64, i64, i64, i64, i64, i64, i64, i64);
+    pub struct(value) Nested(Triple, Mixed, bool);
+    pub struct Gc(i64);
+    pub struct(value) WithGc(Gc, i8);
+
+    pub fn byte(a: u8) -> Byte { Byte(a) }
+    pub fn pair(a: u8, b: u16) -> Pair { Pair(a, b) }
+    pub fn word(a: u32) -> Word { Word(a) }
+    pub fn mixed(a: u8, b: f64) -> Mixed { Mixed(a, b) }
+    pub fn triple(a: i32) -> Triple { Triple(a, a + 1, a + 2) }
+    pub fn floats(a: f32) -> Floats { Floats(a, a * 2.0, a * 3.0, a * 4.0) }
+    pub fn large(a: i64) -> Large { Large(a, a + 1, a + 2, a + 3, a + 4, a + 5, a + 6, a + 7) }
+    pub fn nested(a: i32, b: u8, c: f64) -> Nested { Nested(triple(a), Mixed(b, c), true) }
+    pub fn with_gc(a: i64, b: i8) -> WithGc { WithGc(Gc(a), b) }
+
+    pub fn echo_large(large: Large) -> Large { large }
+    pub fn echo_nested(nested: Nested) -> Nested { nested }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let byte: StructRef<'_> = driver.runtime.invoke("byte", (255u8,)).unwrap();
+    assert_eq!(byte.get::<u8>("0"), Ok(255));
+
+    let pair: StructRef<'_> = driver.runtime.invoke("pair", (1u8, 0xABCDu16)).unwrap();
+    assert_eq!(pair.get::<u8>("0"), Ok(1));
+    assert_eq!(pair.get::<u16>("1"), Ok(0xABCD));
+
+    let word: StructRef<'_> = driver.runtime.invoke("word", (0xDEAD_BEEFu32,)).unwrap();
+    assert_eq!(word.get::<u32>("0"), Ok(0xDEAD_BEEF));
+
+    let mixed: StructRef<'_> = driver.runtime.invoke("mixed", (7u8, 1.5f64)).unwrap();
+    assert_eq!(mixed.get::<u8>("0"), Ok(7));
+    assert_eq!(mixed.get::<f64>("1"), Ok(1.5));
+
+    let triple: StructRef<'_> = driver.runtime.invoke("triple", (-1i32,)).unwrap();
+    assert_eq!(triple.get::<i32>("0"), Ok(-1));
+    assert_eq!(triple.get::<i32>("1"), Ok(0));
+    assert_eq!(triple.get::<i32>("2"), Ok(1));
+
+    let floats: StructRef<'_> = driver.runtime.invoke("floats", (0.5f32,)).unwrap();
+    for (idx, expected) in [0.5f32, 1.0, 1.5, 2.0].into_iter().enumerate() {
+        assert_eq!(floats.get::<f32>(&idx.to_string()), Ok(expected));
+    }
+
+    let large: StructRef<'_> = driver.runtime.invoke("large", (100i64,)).unwrap();
+    for idx in 0..8i64 {
+        assert_eq!(large.get::<i64>(&idx.to_string()), Ok(100 + idx));
+    }
+
+    let large: StructRef<'_> = driver.runtime.invoke("echo_large", (large,)).unwrap();
+    for idx in 0..8i64 {
+        assert_eq!(large.get::<i64>(&idx.to_string()), Ok(100 + idx));
+    }
+
+    let nested: StructRef<'_> = driver
+        .runtime
+        .invoke("nested", (10i32, 20u8, 30.5f64))
+        .unwrap();
+    let nested: StructRef<'_> = driver.runtime.invoke("echo_nested", (nested,)).unwrap();
+    let triple = nested.get::<StructRef<'_>>("0").unwrap();
+    assert_eq!(triple.get::<i32>("0"), Ok(10));
+    assert_eq!(triple.get::<i32>("2"), Ok(12));
+    let mixed = nested.get::<StructRef<'_>>("1").unwrap();
+    assert_eq!(mixed.get::<u8>("0"), Ok(20));
+    assert_eq!(mixed.get::<f64>("1"), Ok(30.5));
+    assert_eq!(nested.get::<bool>("2"), Ok(true));
+
+    let with_gc: StructRef<'_> = driver.runtime.invoke("with_gc", (42i64, -3i8)).unwrap();
+    let gc = with_gc.get::<StructRef<'_>>("0").unwrap();
+    assert_eq!(gc.get::<i64>("0"), Ok(42));
+    assert_eq!(with_gc.get::<i8>("1"), Ok(-3));
+}
+
+#[test]
+fn return_value_struct_as_rust_struct() {
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, TestAbiType)]
+    struct Mixed(u8, f64);
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, TestAbiType)]
+    struct Padded {
+        a: f64,
+        b: u8,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, TestAbiType)]
+    struct Nested {
+        mixed: Mixed,
+        padded: Padded,
+        flag: bool,
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Mixed(u8, f64);
+    pub struct(value) Padded { a: f64, b: u8 };
+    pub struct(value) Nested { mixed: Mixed, padded: Padded, flag: bool };
+    pub struct Holder { nested: Nested };
+
+    pub fn mixed(a: u8, b: f64) -> Mixed { Mixed(a, b) }
+    pub fn padded(a: f64, b: u8) -> Padded { Padded { a, b } }
+    pub fn nested(a: u8, b: f64) -> Nested {
+        Nested { mixed: mixed(a, b), padded: padded(b, a), flag: true }
+    }
+    pub fn holder() -> Holder { Holder { nested: nested(1, 2.0) } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mixed: Mixed = driver.runtime.invoke("mixed", (7u8, 1.5f64)).unwrap();
+    assert_eq!(mixed, Mixed(7, 1.5));
+
+    let padded: Padded = driver.runtime.invoke("padded", (2.5f64, 3u8)).unwrap();
+    assert_eq!(padded, Padded { a: 2.5, b: 3 });
+
+    let nested: Nested = driver.runtime.invoke("nested", (4u8, 8.5f64)).unwrap();
+    assert_eq!(
+        nested,
+        Nested {
+            mixed: Mixed(4, 8.5),
+            padded: Padded { a: 8.5, b: 4 },
+            flag: true,
+        }
+    );
+
+    let holder: StructRef<'_> = driver.runtime.invoke("holder", ()).unwrap();
+    let nested = holder.get::<Nested>("nested").unwrap();
+    assert_eq!(nested.mixed, Mixed(1, 2.0));
+    assert_eq!(nested.padded, Padded { a: 2.0, b: 1 });
+    assert!(holder.get::<Padded>("nested").is_err());
+
+    // A struct with a different name or different fields is a different type
+    let mixed_err: Result<Padded, _> = driver.runtime.invoke("mixed", (7u8, 1.5f64));
+    assert!(mixed_err.is_err());
+}
+
 #[test]
 fn extern_fn() {
     extern "C" fn add_int(a: i32, b: i32) -> i32 {
@@ -735,3 +1022,107 @@ fn signed_and_unsigned_rem() {
     assert_invoke_eq!(i32, -2, driver, "signed");
     assert_invoke_eq!(i32, 2, driver, "unsigned");
 }
+
+#[test]
+fn construct_struct() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Vector2 { x: f32, y: f32 }
+    pub struct(value) Color { r: u8, g: u8, b: u8 }
+    pub struct Sprite { position: Vector2, tint: Color, layer: i32 }
+
+    pub fn area(sprite: Sprite) -> f32 { sprite.position.x * sprite.position.y }
+    pub fn tint_sum(sprite: Sprite) -> u8 { sprite.tint.r + sprite.tint.g + sprite.tint.b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    let position = runtime
+        .construct_struct("Vector2")
+        .unwrap()
+        .set("x", 3.0f32)
+        .unwrap()
+        .set("y", 4.0f32)
+        .unwrap()
+        .finish()
+        .unwrap();
+    let tint = runtime
+        .construct_struct("Color")
+        .unwrap()
+        .set("r", 1u8)
+        .unwrap()
+        .set("g", 2u8)
+        .unwrap()
+        .set("b", 3u8)
+        .unwrap()
+        .finish()
+        .unwrap();
+    let sprite = runtime
+        .construct_struct("Sprite")
+        .unwrap()
+        .set("position", position)
+        .unwrap()
+        .set("tint", tint)
+        .unwrap()
+        .set("layer", 7i32)
+        .unwrap()
+        .finish()
+        .unwrap();
+
+    assert_eq!(sprite.get::<i32>("layer"), Ok(7));
+    let area: f32 = runtime.invoke("area", (sprite.clone(),)).unwrap();
+    assert_eq!(area, 12.0);
+    let tint_sum: u8 = runtime.invoke("tint_sum", (sprite,)).unwrap();
+    assert_eq!(tint_sum, 6);
+
+    assert_eq!(
+        runtime.construct_struct("Vector3").err(),
+        Some(ConstructStructError::TypeNotFound {
+            type_name: String::from("Vector3")
+        })
+    );
+    assert!(matches!(
+        runtime.construct_struct("core::i32").err(),
+        Some(ConstructStructError::NotAStruct { .. })
+    ));
+    assert_eq!(
+        runtime
+            .construct_struct("Vector2")
+            .unwrap()
+            .set("z", 1.0f32)
+            .err(),
+        Some(ConstructStructError::FieldNotFound {
+            struct_name: String::from("Vector2"),
+            field_name: String::from("z"),
+        })
+    );
+    let error = runtime
+        .construct_struct("Vector2")
+        .unwrap()
+        .set("x", 1i32)
+        .err()
+        .unwrap();
+    assert!(matches!(
+        &error,
+        ConstructStructError::FieldTypeMismatch { field_name, .. } if field_name == "x"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Mismatched types for `Vector2::x`. Expected: `core::f32`. Found: `core::i32`."
+    );
+    assert_eq!(
+        runtime
+            .construct_struct("Vector2")
+            .unwrap()
+            .set("x", 1.0f32)
+            .unwrap()
+            .finish()
+            .err(),
+        Some(ConstructStructError::MissingField {
+            struct_name: String::from("Vector2"),
+            field_name: String::from("y"),
+        })
+    );
+}
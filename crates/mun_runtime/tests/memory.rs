@@ -41,6 +41,82 @@ fn gc_trace() {
 
     assert!(runtime.gc_collect());
     assert_eq!(runtime.gc_stats().allocated_memory, 0);
+
+    let history = runtime.gc_pause_history();
+    assert_eq!(history.len(), 2);
+    assert!(history[0].allocated_memory > 0);
+    assert_eq!(history[1].allocated_memory, 0);
+    assert_eq!(history[1].reclaimed_memory, history[0].surviving_memory);
+}
+
+#[test]
+fn gc_compact() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        quz: f64,
+        bar: Bar,
+    }
+
+    pub struct Bar {
+        baz: i64
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo {
+            quz: 1.0,
+            bar: Bar {
+                baz: 3
+            }
+        }
+    }
+
+    pub fn garbage() -> [i64] { [1, 2, 3] }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let value: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let value = value.root();
+    let _: ArrayRef<'_, i64> = runtime.invoke("garbage", ()).unwrap();
+
+    assert!(runtime.gc_collect_and_compact());
+
+    // The references remain valid after the objects were relocated
+    let value = value.as_ref(runtime);
+    assert_eq!(value.get::<f64>("quz").unwrap(), 1.0);
+    let bar = value.get::<StructRef<'_>>("bar").unwrap();
+    assert_eq!(bar.get::<i64>("baz").unwrap(), 3);
+}
+
+#[test]
+fn gc_over_aligned_struct() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    #[align(64)]
+    pub struct Vector4(f32, f32, f32, f32);
+
+    pub fn new_vector() -> Vector4 {
+        Vector4(1.0, 2.0, 3.0, 4.0)
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let vectors: Vec<StructRef<'_>> = (0..3)
+        .map(|_| runtime.invoke("new_vector", ()).unwrap())
+        .collect();
+
+    for vector in vectors {
+        assert_eq!(vector.type_info().value_layout().align(), 64);
+        assert_eq!(vector.type_info().value_layout().size(), 64);
+        let ptr = unsafe { vector.into_raw().get_ptr() };
+        assert_eq!(ptr as usize % 64, 0);
+    }
 }
 
 #[test]
@@ -2510,3 +2586,43 @@ fn test_type_table() {
     // These types should be equal
     assert_eq!(foo_bar_field_type, bar_type);
 }
+
+#[test]
+fn memory_limit() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        a: i64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1 }
+    }
+
+    pub fn allocate_arrays(n: usize) -> [i64] {
+        for _i in 0..n {
+            let _array = [1, 2, 3, 4];
+        }
+        [0, 0, 0, 0]
+    }
+    "#,
+        |builder| builder.memory_limit(4096),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let _: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+
+    let result: Result<ArrayRef<'_, i64>, _> = runtime.invoke("allocate_arrays", (1000usize,));
+    let Err(error) = result else {
+        panic!("the allocations should exceed the memory limit");
+    };
+    assert_eq!(
+        error.runtime_error(),
+        Some(&mun_runtime::RuntimeError::MemoryLimitExceeded { limit: 4096 })
+    );
+
+    // Collecting the garbage makes room for new allocations
+    assert!(runtime.gc_collect());
+    let _: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+}
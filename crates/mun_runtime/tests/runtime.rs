@@ -1,5 +1,10 @@
-use mun_runtime::LinkFunctionsError;
-use mun_test::CompileAndRunTestDriver;
+use std::path::Path;
+
+use mun_runtime::{
+    HasStaticType, InitError, LinkError, LinkFunctionsError, Origin, Runtime, RuntimeBuilder,
+    StructMemoryKind,
+};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 
 #[macro_use]
 mod util;
@@ -60,10 +65,10 @@ fn arrays() {
     }
 
     /// Performs bubble sort on an array of versions
-    fn bubble_sort(array: [Version], len: usize) {
-        let i = 0;
+    fn bubble_sort(mut array: [Version], len: usize) {
+        let mut i = 0;
         while i<len {
-            let j = 1;
+            let mut j = 1;
             while j<len-i {
                 if version_greater(array[j-1], array[j]) {
                     let tmp = array[j];
@@ -111,6 +116,135 @@ fn multiple_modules() {
     assert_invoke_eq!(i32, 5, driver, "main");
 }
 
+#[test]
+fn metadata() {
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { foo::new(5).a }
+
+    //- /src/foo.mun
+    pub struct Foo { pub a: i32, b: bool }
+    pub fn new(a: i32) -> Foo { Foo { a: a, b: true } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let metadata = driver.runtime.metadata();
+    assert_eq!(metadata.assemblies.len(), 2);
+
+    let main = metadata
+        .assemblies
+        .iter()
+        .find(|assembly| assembly.library_path.ends_with("mod.munlib"))
+        .unwrap();
+    assert_eq!(main.dependencies.len(), 1);
+    assert!(main.types.is_empty());
+
+    let foo = metadata
+        .assemblies
+        .iter()
+        .find(|assembly| assembly.library_path.ends_with("foo.munlib"))
+        .unwrap();
+    assert!(foo.dependencies.is_empty());
+
+    let i32_name = i32::type_info().name().to_owned();
+    let new = foo
+        .functions
+        .iter()
+        .find(|function| function.name == "foo::new")
+        .unwrap();
+    assert_eq!(new.arg_types, vec![i32_name.clone()]);
+    assert_eq!(new.return_type.as_deref(), Some("foo::Foo"));
+
+    assert_eq!(foo.types.len(), 1);
+    let foo_type = &foo.types[0];
+    assert_eq!(foo_type.name, "foo::Foo");
+    assert_eq!(foo_type.memory_kind, StructMemoryKind::Gc);
+    let fields: Vec<_> = foo_type
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.type_name.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![("a", i32_name.as_str()), ("b", bool::type_info().name())]
+    );
+
+    // The metadata of a runtime is the metadata of its assemblies
+    assert_eq!(
+        driver
+            .runtime
+            .get_assembly(&foo.library_path)
+            .unwrap()
+            .metadata(),
+        *foo
+    );
+}
+
+#[test]
+fn functions_and_types() {
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    extern fn add(a: i32, b: i32) -> i32;
+    pub fn main() -> i32 { add(foo::new(5).a, 1) }
+
+    //- /src/foo.mun
+    pub struct Foo { pub a: i32 }
+    pub fn new(a: i32) -> Foo { Foo { a: a } }
+    "#,
+        |builder| builder.insert_fn("add", add as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    let origin_of_function = |name: &str| {
+        runtime
+            .functions()
+            .find(|(fn_def, _)| fn_def.prototype.name == name)
+            .map(|(_, origin)| origin)
+            .unwrap()
+    };
+    assert!(matches!(
+        origin_of_function("main"),
+        Origin::Assembly(path) if path.ends_with("mod.munlib")
+    ));
+    assert!(matches!(
+        origin_of_function("foo::new"),
+        Origin::Assembly(path) if path.ends_with("foo.munlib")
+    ));
+    assert_eq!(origin_of_function("add"), Origin::Host);
+
+    let origin_of_type = |name: &str| {
+        runtime
+            .types()
+            .find(|(ty, _)| ty.name() == name)
+            .map(|(_, origin)| origin)
+            .unwrap()
+    };
+    assert!(matches!(
+        origin_of_type("foo::Foo"),
+        Origin::Assembly(path) if path.ends_with("foo.munlib")
+    ));
+    assert_eq!(origin_of_type(i32::type_info().name()), Origin::Host);
+}
+
 #[test]
 fn cyclic_modules() {
     let driver = CompileAndRunTestDriver::from_fixture(
@@ -231,3 +365,120 @@ fn cyclic_struct() {
         .ty();
     assert_eq!(foo_foo_ty, foo_ty);
 }
+
+#[test]
+fn run_tests() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+        fn double(a: i32) -> i32 { a * 2 }
+
+        #[test]
+        fn double_works() {
+            assert(double(2) == 4);
+        }
+
+        #[test]
+        fn double_fails() {
+            assert(double(2) == 5);
+        }
+        "#,
+        |builder| builder,
+    )
+    .unwrap();
+
+    let assembly = driver.runtime.assemblies().next().unwrap();
+    let results: Vec<_> = assembly
+        .tests()
+        .map(|test| (test.name().to_owned(), test.run().is_ok()))
+        .collect();
+    assert_eq!(
+        results,
+        vec![
+            ("double_works".to_owned(), true),
+            ("double_fails".to_owned(), false)
+        ]
+    );
+}
+
+/// Compiles a package with the specified `version` that consists of a `mod`
+/// module that depends on a `foo` module.
+fn compile_versioned_package(version: &str) -> CompileTestDriver {
+    CompileTestDriver::from_fixture(&format!(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="{version}"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 {{ foo::bar() }}
+
+    //- /src/foo.mun
+    pub fn bar() -> i32 {{ 5 }}
+    "#
+    ))
+}
+
+/// Replaces the `foo` assembly of the `dependent` package with the one of the
+/// `dependency` package.
+fn replace_dependency(dependent: &CompileTestDriver, dependency: &CompileTestDriver) {
+    std::fs::copy(
+        dependency.lib_path().with_file_name("foo.munlib"),
+        dependent.lib_path().with_file_name("foo.munlib"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn compatible_dependency_version() {
+    let dependent = compile_versioned_package("1.0.0");
+    let dependency = compile_versioned_package("1.2.0");
+    replace_dependency(&dependent, &dependency);
+
+    // Safety: since we compiled the code ourselves, loading the library should be
+    // safe
+    let runtime = unsafe { Runtime::builder(dependent.lib_path()).finish() }.unwrap();
+    assert_eq!(runtime.invoke::<i32, ()>("main", ()).unwrap(), 5);
+}
+
+#[test]
+fn incompatible_dependency_version() {
+    let dependent = compile_versioned_package("1.0.0");
+    let dependency = compile_versioned_package("2.0.0");
+    replace_dependency(&dependent, &dependency);
+
+    // Safety: since we compiled the code ourselves, loading the library should be
+    // safe
+    let result = unsafe { Runtime::builder(dependent.lib_path()).finish() };
+    match result {
+        Err(InitError::LinkAssembly(LinkError::MismatchedDependencyVersion {
+            requirement,
+            version,
+            ..
+        })) => {
+            assert_eq!(requirement, "^1.0.0");
+            assert_eq!(version.as_deref(), Some("2.0.0"));
+        }
+        Err(e) => panic!("unexpected error: {e}"),
+        Ok(_) => panic!("expected the dependency version to be incompatible"),
+    }
+}
+
+#[test]
+fn from_bytes() {
+    let driver = compile_versioned_package("1.0.0");
+    let main_bytes = std::fs::read(driver.lib_path()).unwrap();
+    let foo_bytes = std::fs::read(driver.lib_path().with_file_name("foo.munlib")).unwrap();
+
+    // The libraries are loaded from memory, so their paths don't need to exist
+    let library_path = Path::new("assets").join("mod.munlib");
+    let runtime = unsafe {
+        RuntimeBuilder::from_bytes(&library_path, main_bytes)
+            .insert_library_bytes("assets/foo.munlib", foo_bytes)
+            .finish()
+    }
+    .unwrap();
+    assert_eq!(runtime.invoke::<i32, ()>("main", ()).unwrap(), 5);
+    assert!(runtime.get_assembly(&library_path).is_some());
+    assert_eq!(runtime.assemblies().count(), 2);
+}
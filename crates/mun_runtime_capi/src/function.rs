@@ -7,7 +7,10 @@ use std::{
     sync::Arc,
 };
 
-use mun_capi_utils::{error::ErrorHandle, mun_error_try, try_deref_mut};
+use mun_capi_utils::{
+    error::{catch_panic, ErrorHandle},
+    mun_error_try, try_deref_mut,
+};
 use mun_memory::ffi::{Type, Types};
 
 /// Describes a `Function` accessible from a Mun [`super::runtime::Runtime`].
@@ -59,12 +62,14 @@ impl From<Arc<mun_runtime::FunctionDefinition>> for Function {
 /// deallocated by a previous call to [`mun_function_release`].
 #[no_mangle]
 pub unsafe extern "C" fn mun_function_add_reference(function: Function) -> ErrorHandle {
-    if function.0.is_null() {
-        return ErrorHandle::new("invalid argument 'function': null pointer");
-    }
+    catch_panic(|| {
+        if function.0.is_null() {
+            return ErrorHandle::new("invalid argument 'function': null pointer");
+        }
 
-    Arc::increment_strong_count(function.0);
-    ErrorHandle::default()
+        Arc::increment_strong_count(function.0);
+        ErrorHandle::default()
+    })
 }
 
 /// Notifies the runtime that one of the references to the function is no longer
@@ -77,12 +82,14 @@ pub unsafe extern "C" fn mun_function_add_reference(function: Function) -> Error
 /// a previous call to [`mun_function_release`].
 #[no_mangle]
 pub unsafe extern "C" fn mun_function_release(function: Function) -> ErrorHandle {
-    if function.0.is_null() {
-        return ErrorHandle::new("invalid argument 'function': null pointer");
-    }
+    catch_panic(|| {
+        if function.0.is_null() {
+            return ErrorHandle::new("invalid argument 'function': null pointer");
+        }
 
-    Arc::decrement_strong_count(function.0);
-    ErrorHandle::default()
+        Arc::decrement_strong_count(function.0);
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the function's function pointer.
@@ -96,12 +103,14 @@ pub unsafe extern "C" fn mun_function_fn_ptr(
     function: Function,
     ptr: *mut *const c_void,
 ) -> ErrorHandle {
-    let function = mun_error_try!(function
-        .inner()
-        .map_err(|e| format!("invalid argument 'function': {e}")));
-    let ptr = try_deref_mut!(ptr);
-    *ptr = function.fn_ptr;
-    ErrorHandle::default()
+    catch_panic(|| {
+        let function = mun_error_try!(function
+            .inner()
+            .map_err(|e| format!("invalid argument 'function': {e}")));
+        let ptr = try_deref_mut!(ptr);
+        *ptr = function.fn_ptr;
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the function's name.
@@ -118,14 +127,16 @@ pub unsafe extern "C" fn mun_function_name(
     function: Function,
     name: *mut *const c_char,
 ) -> ErrorHandle {
-    let function = mun_error_try!(function
-        .inner()
-        .map_err(|e| format!("invalid argument 'function': {e}")));
-    let name = try_deref_mut!(name);
-    *name = CString::new(function.prototype.name.clone())
-        .unwrap()
-        .into_raw() as *const _;
-    ErrorHandle::default()
+    catch_panic(|| {
+        let function = mun_error_try!(function
+            .inner()
+            .map_err(|e| format!("invalid argument 'function': {e}")));
+        let name = try_deref_mut!(name);
+        *name = CString::new(function.prototype.name.clone())
+            .unwrap()
+            .into_raw() as *const _;
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the function's argument types.
@@ -143,19 +154,21 @@ pub unsafe extern "C" fn mun_function_argument_types(
     function: Function,
     arg_types: *mut Types,
 ) -> ErrorHandle {
-    let function = mun_error_try!(function
-        .inner()
-        .map_err(|e| format!("invalid argument 'function': {e}")));
-    let arg_types = try_deref_mut!(arg_types);
-    *arg_types = function
-        .prototype
-        .signature
-        .arg_types
-        .iter()
-        .map(|ty| ty.clone().into())
-        .collect::<Vec<_>>()
-        .into();
-    ErrorHandle::default()
+    catch_panic(|| {
+        let function = mun_error_try!(function
+            .inner()
+            .map_err(|e| format!("invalid argument 'function': {e}")));
+        let arg_types = try_deref_mut!(arg_types);
+        *arg_types = function
+            .prototype
+            .signature
+            .arg_types
+            .iter()
+            .map(|ty| ty.clone().into())
+            .collect::<Vec<_>>()
+            .into();
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the function's return type.
@@ -172,12 +185,14 @@ pub unsafe extern "C" fn mun_function_return_type(
     function: Function,
     ty: *mut Type,
 ) -> ErrorHandle {
-    let function = mun_error_try!(function
-        .inner()
-        .map_err(|e| format!("invalid argument 'function': {e}")));
-    let ty = try_deref_mut!(ty);
-    *ty = function.prototype.signature.return_type.clone().into();
-    ErrorHandle::default()
+    catch_panic(|| {
+        let function = mun_error_try!(function
+            .inner()
+            .map_err(|e| format!("invalid argument 'function': {e}")));
+        let ty = try_deref_mut!(ty);
+        *ty = function.prototype.signature.return_type.clone().into();
+        ErrorHandle::default()
+    })
 }
 
 #[cfg(test)]
@@ -1,8 +1,14 @@
 //! Exposes Mun garbage collection.
+//!
+//! The C API never compacts the heap, so a pointer to the memory of an object
+//! remains valid until the object is collected.
 
 use std::mem::ManuallyDrop;
 
-use mun_capi_utils::{error::ErrorHandle, mun_error_try, try_deref_mut};
+use mun_capi_utils::{
+    error::{catch_panic, ErrorHandle},
+    mun_error_try, try_deref_mut,
+};
 pub use mun_memory::gc::GcPtr;
 use mun_memory::{ffi::Type, gc::GcRuntime};
 
@@ -21,16 +27,18 @@ use crate::runtime::Runtime;
 /// data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_alloc(runtime: Runtime, ty: Type, obj: *mut GcPtr) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let ty = mun_error_try!(ty
-        .to_owned()
-        .map_err(|e| format!("invalid argument 'obj': {e}"))
-        .map(ManuallyDrop::new));
-    let obj = try_deref_mut!(obj);
-    *obj = runtime.gc().alloc(&ty);
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let ty = mun_error_try!(ty
+            .to_owned()
+            .map_err(|e| format!("invalid argument 'obj': {e}"))
+            .map(ManuallyDrop::new));
+        let obj = try_deref_mut!(obj);
+        *obj = runtime.gc().alloc(&ty);
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the `ty` for the specified `obj` from the runtime. If successful,
@@ -50,12 +58,14 @@ pub unsafe extern "C" fn mun_gc_ptr_type(
     obj: GcPtr,
     ty: *mut Type,
 ) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let ty = try_deref_mut!(ty);
-    *ty = runtime.gc().ptr_type(obj).into();
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let ty = try_deref_mut!(ty);
+        *ty = runtime.gc().ptr_type(obj).into();
+        ErrorHandle::default()
+    })
 }
 
 /// Roots the specified `obj`, which keeps it and objects it references alive.
@@ -75,11 +85,13 @@ pub unsafe extern "C" fn mun_gc_ptr_type(
 /// data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_root(runtime: Runtime, obj: GcPtr) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    runtime.gc().root(obj);
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        runtime.gc().root(obj);
+        ErrorHandle::default()
+    })
 }
 
 /// Unroots the specified `obj`, potentially allowing it and objects it
@@ -98,11 +110,13 @@ pub unsafe extern "C" fn mun_gc_root(runtime: Runtime, obj: GcPtr) -> ErrorHandl
 /// data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_unroot(runtime: Runtime, obj: GcPtr) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    runtime.gc().unroot(obj);
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        runtime.gc().unroot(obj);
+        ErrorHandle::default()
+    })
 }
 
 /// Collects all memory that is no longer referenced by rooted objects. If
@@ -120,12 +134,14 @@ pub unsafe extern "C" fn mun_gc_unroot(runtime: Runtime, obj: GcPtr) -> ErrorHan
 /// data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_collect(runtime: Runtime, reclaimed: *mut bool) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let reclaimed = try_deref_mut!(reclaimed);
-    *reclaimed = runtime.gc_collect();
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let reclaimed = try_deref_mut!(reclaimed);
+        *reclaimed = runtime.gc_collect();
+        ErrorHandle::default()
+    })
 }
 
 #[cfg(test)]
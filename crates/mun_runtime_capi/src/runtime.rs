@@ -1,10 +1,14 @@
 //! Exposes the Mun runtime using the C ABI.
 
-use std::{ffi::c_void, mem::ManuallyDrop, ops::Deref, os::raw::c_char, slice};
+use std::{
+    collections::HashMap, ffi::c_void, mem::ManuallyDrop, ops::Deref, os::raw::c_char, path::Path,
+    slice, time::UNIX_EPOCH,
+};
 
 use mun_abi as abi;
 use mun_capi_utils::{
-    error::ErrorHandle, mun_error_try, try_convert_c_string, try_deref, try_deref_mut,
+    error::{catch_panic, ErrorHandle},
+    mun_error_try, try_convert_c_string, try_deref, try_deref_mut,
 };
 use mun_memory::{ffi::Type, type_table::TypeTable, Type as RustType};
 use mun_runtime::{FunctionDefinition, FunctionPrototype, FunctionSignature};
@@ -91,6 +95,18 @@ pub struct RuntimeOptions {
     pub num_functions: u32,
 }
 
+/// Identifies the version of an assembly that is loaded by a runtime.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AssemblyVersion {
+    /// The time at which the assembly was loaded, in milliseconds since the
+    /// UNIX epoch
+    pub loaded_at_ms: u64,
+
+    /// The MD5 hash of the contents of the assembly's library
+    pub content_hash: [u8; 16],
+}
+
 impl Default for RuntimeOptions {
     fn default() -> Self {
         RuntimeOptions {
@@ -120,94 +136,104 @@ pub unsafe extern "C" fn mun_runtime_create(
     options: RuntimeOptions,
     handle: *mut Runtime,
 ) -> ErrorHandle {
-    let library_path = mun_error_try!(try_convert_c_string(library_path)
-        .map_err(|e| format!("invalid argument 'library_path': {e}")));
-    let handle = try_deref_mut!(handle);
-
-    let user_functions = if options.functions.is_null() {
-        if options.num_functions > 0 {
-            return ErrorHandle::new("invalid argument: 'functions' is null pointer.");
-        }
-        None
-    } else {
-        Some(std::slice::from_raw_parts(
-            options.functions,
-            options.num_functions as usize,
-        ))
-    };
-
-    let type_table = TypeTable::default();
-    let user_functions = mun_error_try!(user_functions
-        .into_iter()
-        .flatten()
-        .map(|def| {
-            let name = try_convert_c_string(def.name)
-                .map_err(|e| format!("invalid function name: {e}"))?;
-            let return_type = ManuallyDrop::new(
-                def.return_type
-                    .to_owned()
-                    .map_err(|e| format!("invalid function '{name}': 'return_type': {e}"))?,
-            )
-            .deref()
-            .clone();
-
-            if def.num_args > 0 && def.arg_types.is_null() {
-                return Err(format!(
-                    "invalid function '{name}': 'arg_types' is null pointer."
-                ));
+    catch_panic(|| {
+        let library_path = mun_error_try!(try_convert_c_string(library_path)
+            .map_err(|e| format!("invalid argument 'library_path': {e}")));
+        let handle = try_deref_mut!(handle);
+
+        let user_functions = if options.functions.is_null() {
+            if options.num_functions > 0 {
+                return ErrorHandle::new("invalid argument: 'functions' is null pointer.");
             }
+            None
+        } else {
+            Some(std::slice::from_raw_parts(
+                options.functions,
+                options.num_functions as usize,
+            ))
+        };
 
-            let arg_types: Vec<_> = if def.num_args > 0 {
-                std::slice::from_raw_parts(def.arg_types, def.num_args as usize)
-                    .iter()
-                    .enumerate()
-                    .map(|(i, arg)| -> Result<RustType, String> {
-                        let ty = (*arg).to_owned().map_err(|e| {
-                            format!("invalid function '{}': argument #{}: {}", name, i + 1, e)
-                        })?;
-                        Ok(ManuallyDrop::new(ty).deref().clone())
-                    })
-                    .collect::<Result<_, _>>()?
-            } else {
-                Vec::new()
-            };
-
-            Ok(FunctionDefinition {
-                prototype: FunctionPrototype {
-                    name: name.to_owned(),
-                    signature: FunctionSignature {
-                        arg_types,
-                        return_type,
+        let type_table = TypeTable::default();
+        let user_functions = mun_error_try!(user_functions
+            .into_iter()
+            .flatten()
+            .map(|def| {
+                let name = try_convert_c_string(def.name)
+                    .map_err(|e| format!("invalid function name: {e}"))?;
+                let return_type = ManuallyDrop::new(
+                    def.return_type
+                        .to_owned()
+                        .map_err(|e| format!("invalid function '{name}': 'return_type': {e}"))?,
+                )
+                .deref()
+                .clone();
+
+                if def.num_args > 0 && def.arg_types.is_null() {
+                    return Err(format!(
+                        "invalid function '{name}': 'arg_types' is null pointer."
+                    ));
+                }
+
+                let arg_types: Vec<_> = if def.num_args > 0 {
+                    std::slice::from_raw_parts(def.arg_types, def.num_args as usize)
+                        .iter()
+                        .enumerate()
+                        .map(|(i, arg)| -> Result<RustType, String> {
+                            let ty = (*arg).to_owned().map_err(|e| {
+                                format!("invalid function '{}': argument #{}: {}", name, i + 1, e)
+                            })?;
+                            Ok(ManuallyDrop::new(ty).deref().clone())
+                        })
+                        .collect::<Result<_, _>>()?
+                } else {
+                    Vec::new()
+                };
+
+                Ok(FunctionDefinition {
+                    prototype: FunctionPrototype {
+                        name: name.to_owned(),
+                        signature: FunctionSignature {
+                            arg_types,
+                            return_type,
+                        },
                     },
-                },
-                fn_ptr: def.fn_ptr,
+                    fn_ptr: def.fn_ptr,
+                    trampoline_ptr: std::ptr::null(),
+                    docs: None,
+                    privacy: abi::Privacy::Public,
+                })
             })
-        })
-        .collect::<Result<_, _>>());
-
-    let runtime_options = mun_runtime::RuntimeOptions {
-        library_path: library_path.into(),
-        user_functions,
-        type_table,
-    };
+            .collect::<Result<_, _>>());
+
+        let runtime_options = mun_runtime::RuntimeOptions {
+            library_path: library_path.into(),
+            user_functions,
+            type_table,
+            memory_limit: None,
+            library_bytes: HashMap::new(),
+            watch_files: true,
+        };
 
-    let runtime = match mun_runtime::Runtime::new(runtime_options) {
-        Ok(runtime) => runtime,
-        Err(e) => return ErrorHandle::new(format!("{e:?}")),
-    };
+        let runtime = match mun_runtime::Runtime::new(runtime_options) {
+            Ok(runtime) => runtime,
+            Err(e) => return ErrorHandle::new(format!("{e:?}")),
+        };
 
-    handle.0 = Box::into_raw(Box::new(runtime)).cast();
-    ErrorHandle::default()
+        handle.0 = Box::into_raw(Box::new(runtime)).cast();
+        ErrorHandle::default()
+    })
 }
 
 /// Destructs the runtime corresponding to `handle`.
 #[no_mangle]
 pub extern "C" fn mun_runtime_destroy(runtime: Runtime) -> ErrorHandle {
-    if runtime.0.is_null() {
-        return ErrorHandle::new("invalid argument 'runtime': null pointer");
-    }
-    let _runtime = unsafe { Box::from_raw(runtime.0.cast::<Runtime>()) };
-    ErrorHandle::default()
+    catch_panic(|| {
+        if runtime.0.is_null() {
+            return ErrorHandle::new("invalid argument 'runtime': null pointer");
+        }
+        let _runtime = unsafe { Box::from_raw(runtime.0.cast::<Runtime>()) };
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the [`FunctionDefinition`] for `fn_name` from the `runtime`. If
@@ -230,28 +256,30 @@ pub unsafe extern "C" fn mun_runtime_find_function_definition(
     has_fn_info: *mut bool,
     fn_info: *mut Function,
 ) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    if fn_name.is_null() {
-        return ErrorHandle::new("invalid argument 'fn_name': null pointer");
-    }
-    let name = mun_error_try!(std::str::from_utf8(slice::from_raw_parts(
-        fn_name.cast::<u8>(),
-        fn_name_len
-    ))
-    .map_err(|_error| String::from("invalid argument 'fn_name': invalid UTF-8 encoded")));
-    let has_fn_info = try_deref_mut!(has_fn_info);
-    let fn_info = try_deref_mut!(fn_info);
-    match runtime.get_function_definition(name) {
-        Some(info) => {
-            *has_fn_info = true;
-            *fn_info = info.into();
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        if fn_name.is_null() {
+            return ErrorHandle::new("invalid argument 'fn_name': null pointer");
+        }
+        let name = mun_error_try!(std::str::from_utf8(slice::from_raw_parts(
+            fn_name.cast::<u8>(),
+            fn_name_len
+        ))
+        .map_err(|_error| String::from("invalid argument 'fn_name': invalid UTF-8 encoded")));
+        let has_fn_info = try_deref_mut!(has_fn_info);
+        let fn_info = try_deref_mut!(fn_info);
+        match runtime.get_function_definition(name) {
+            Some(info) => {
+                *has_fn_info = true;
+                *fn_info = info.into();
+            }
+            None => *has_fn_info = false,
         }
-        None => *has_fn_info = false,
-    }
 
-    ErrorHandle::default()
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the type information corresponding to the specified `type_name`
@@ -273,23 +301,24 @@ pub unsafe extern "C" fn mun_runtime_get_type_info_by_name(
     has_type_info: *mut bool,
     type_info: *mut Type,
 ) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let type_name =
-        mun_error_try!(try_convert_c_string(type_name)
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let type_name = mun_error_try!(try_convert_c_string(type_name)
             .map_err(|e| format!("invalid argument 'type_name': {e}")));
-    let has_type_info = try_deref_mut!(has_type_info);
-    let type_info = try_deref_mut!(type_info);
-    match runtime.get_type_info_by_name(type_name) {
-        Some(info) => {
-            *has_type_info = true;
-            *type_info = info.into();
+        let has_type_info = try_deref_mut!(has_type_info);
+        let type_info = try_deref_mut!(type_info);
+        match runtime.get_type_info_by_name(type_name) {
+            Some(info) => {
+                *has_type_info = true;
+                *type_info = info.into();
+            }
+            None => *has_type_info = false,
         }
-        None => *has_type_info = false,
-    }
 
-    ErrorHandle::default()
+        ErrorHandle::default()
+    })
 }
 
 /// Retrieves the type information corresponding to the specified `type_id` from
@@ -311,22 +340,24 @@ pub unsafe extern "C" fn mun_runtime_get_type_info_by_id(
     has_type_info: *mut bool,
     type_info: *mut Type,
 ) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let type_id = try_deref!(type_id);
-    let has_type_info = try_deref_mut!(has_type_info);
-    let type_info = try_deref_mut!(type_info);
-
-    match runtime.get_type_info_by_id(type_id) {
-        Some(info) => {
-            *has_type_info = true;
-            *type_info = info.into();
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let type_id = try_deref!(type_id);
+        let has_type_info = try_deref_mut!(has_type_info);
+        let type_info = try_deref_mut!(type_info);
+
+        match runtime.get_type_info_by_id(type_id) {
+            Some(info) => {
+                *has_type_info = true;
+                *type_info = info.into();
+            }
+            None => *has_type_info = false,
         }
-        None => *has_type_info = false,
-    }
 
-    ErrorHandle::default()
+        ErrorHandle::default()
+    })
 }
 
 /// Updates the runtime corresponding to `handle`. If successful, `updated` is
@@ -342,12 +373,89 @@ pub unsafe extern "C" fn mun_runtime_get_type_info_by_id(
 /// data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_runtime_update(runtime: Runtime, updated: *mut bool) -> ErrorHandle {
-    let runtime = mun_error_try!(runtime
-        .inner_mut()
-        .map_err(|e| format!("invalid argument 'runtime': {e}")));
-    let updated = try_deref_mut!(updated);
-    *updated = runtime.update();
-    ErrorHandle::default()
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner_mut()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let updated = try_deref_mut!(updated);
+        *updated = runtime.update();
+        ErrorHandle::default()
+    })
+}
+
+/// Retrieves the number of times the runtime successfully reloaded its
+/// assemblies. If successful, `generation` is set, otherwise a non-zero error
+/// handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments
+/// is a null pointer, an error will be returned. Passing pointers to invalid
+/// data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_generation(
+    runtime: Runtime,
+    generation: *mut u64,
+) -> ErrorHandle {
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let generation = try_deref_mut!(generation);
+        *generation = runtime.generation();
+        ErrorHandle::default()
+    })
+}
+
+/// Retrieves the version of the assembly corresponding to the library at
+/// `library_path`. If successful, `has_assembly` and `version` are set,
+/// otherwise a non-zero error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments
+/// is a null pointer, an error will be returned. Passing pointers to invalid
+/// data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_get_assembly_version(
+    runtime: Runtime,
+    library_path: *const c_char,
+    has_assembly: *mut bool,
+    version: *mut AssemblyVersion,
+) -> ErrorHandle {
+    catch_panic(|| {
+        let runtime = mun_error_try!(runtime
+            .inner()
+            .map_err(|e| format!("invalid argument 'runtime': {e}")));
+        let library_path = mun_error_try!(try_convert_c_string(library_path)
+            .map_err(|e| format!("invalid argument 'library_path': {e}")));
+        let has_assembly = try_deref_mut!(has_assembly);
+        let version = try_deref_mut!(version);
+
+        match runtime.get_assembly(Path::new(library_path)) {
+            Some(assembly) => {
+                *has_assembly = true;
+                *version = AssemblyVersion {
+                    loaded_at_ms: assembly
+                        .loaded_at()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |duration| {
+                            u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+                        }),
+                    content_hash: assembly.content_hash(),
+                };
+            }
+            None => *has_assembly = false,
+        }
+
+        ErrorHandle::default()
+    })
 }
 
 #[cfg(test)]
@@ -367,7 +475,9 @@ mod tests {
         runtime_find_function_definition(ptr::null(), 0, ptr::null_mut(), ptr::null_mut()),
         runtime_get_type_info_by_name(ptr::null(), ptr::null_mut(), ptr::null_mut()),
         runtime_get_type_info_by_id(ptr::null(), ptr::null_mut(), ptr::null_mut()),
-        runtime_update(ptr::null_mut())
+        runtime_update(ptr::null_mut()),
+        runtime_generation(ptr::null_mut()),
+        runtime_get_assembly_version(ptr::null(), ptr::null_mut(), ptr::null_mut())
     );
 
     #[test]
@@ -931,4 +1041,76 @@ mod tests {
 
         assert_getter1!(mun_runtime_update(driver.runtime, _updated));
     }
+
+    #[test]
+    fn test_runtime_generation() {
+        let driver = TestDriver::new(
+            r#"
+            pub fn main() {}
+    "#,
+        );
+
+        assert_getter1!(mun_runtime_generation(driver.runtime, generation));
+        assert_eq!(generation, 0);
+    }
+
+    #[test]
+    fn test_runtime_get_assembly_version_invalid_library_path() {
+        let driver = TestDriver::new(
+            r#"
+            pub fn main() {}
+    "#,
+        );
+
+        assert_error_snapshot!(
+            unsafe {
+                mun_runtime_get_assembly_version(
+                    driver.runtime,
+                    ptr::null(),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                )
+            },
+            @r#""invalid argument \'library_path\': null pointer""#
+        );
+    }
+
+    #[test]
+    fn test_runtime_get_assembly_version_none() {
+        let driver = TestDriver::new(
+            r#"
+            pub fn main() {}
+    "#,
+        );
+
+        let library_path = CString::new("some/path").expect("Invalid library path");
+        assert_getter2!(mun_runtime_get_assembly_version(
+            driver.runtime,
+            library_path.as_ptr(),
+            has_assembly,
+            _version,
+        ));
+        assert!(!has_assembly);
+    }
+
+    #[test]
+    fn test_runtime_get_assembly_version_some() {
+        let driver = TestDriver::new(
+            r#"
+            pub fn main() {}
+    "#,
+        );
+
+        let library_path = CString::new(driver.lib_path.to_str().expect("Invalid lib path"))
+            .expect("Invalid library path");
+        assert_getter2!(mun_runtime_get_assembly_version(
+            driver.runtime,
+            library_path.as_ptr(),
+            has_assembly,
+            version,
+        ));
+        assert!(has_assembly);
+        assert!(version.loaded_at_ms > 0);
+        assert_ne!(version.content_hash, [0; 16]);
+    }
 }
@@ -1,4 +1,9 @@
-use std::{ffi::CString, io::stderr, path::Path, ptr};
+use std::{
+    ffi::CString,
+    io::stderr,
+    path::{Path, PathBuf},
+    ptr,
+};
 
 use mun_compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
 
@@ -8,6 +13,7 @@ use crate::runtime::{mun_runtime_create, mun_runtime_destroy, Runtime, RuntimeOp
 /// quick testing of Mun constructs in the runtime with hot-reloading support.
 pub(crate) struct TestDriver {
     _temp_dir: tempfile::TempDir,
+    pub(crate) lib_path: PathBuf,
     pub(crate) runtime: Runtime,
 }
 
@@ -35,6 +41,7 @@ impl TestDriver {
         let runtime = make_runtime(&out_path);
         TestDriver {
             _temp_dir: temp_dir,
+            lib_path: out_path,
             runtime,
         }
     }
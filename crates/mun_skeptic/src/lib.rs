@@ -247,12 +247,16 @@ fn emit_tests(out_path: impl AsRef<Path>, tests: Vec<Test>) {
 
 /// Creates the Mun code that this test will be operating on.
 fn create_test_input(lines: &[String]) -> String {
-    // Build the text of the code by stripping any leading #
+    // Build the text of the code by stripping the leading `# ` of hidden lines.
+    // Other lines that start with a `#`, like attributes, are kept as is.
     itertools::Itertools::intersperse(
-        lines
-            .iter()
-            .flat_map(|text| text.lines())
-            .map(|line| line.strip_prefix('#').unwrap_or(line)),
+        lines.iter().flat_map(|text| text.lines()).map(|line| {
+            if line == "#" {
+                ""
+            } else {
+                line.strip_prefix("# ").unwrap_or(line)
+            }
+        }),
         "\n",
     )
     .collect()
@@ -0,0 +1,92 @@
+//! Benchmarks for lexing and parsing large Mun files.
+//!
+//! The language server reparses a file on every change, so the performance of
+//! the lexer and parser directly affects the responsiveness of the editor.
+
+use std::fmt::Write;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mun_syntax::{tokenize, SourceFile};
+
+/// Generates a Mun file that contains `items` repetitions of a set of
+/// declarations that exercise most of the grammar.
+fn generate_source(items: usize) -> String {
+    let mut text = String::new();
+    for i in 0..items {
+        write!(
+            text,
+            r#"
+/// A two-dimensional vector with some documentation that spans
+/// multiple lines.
+pub struct(value) Vector{i} {{
+    x: f32, // The horizontal component
+    y: f32,
+}}
+
+/* A block comment /* with a nested comment */ and some more text */
+impl Vector{i} {{
+    pub fn new(x: f32, y: f32) -> Self {{
+        Vector{i} {{ x, y }}
+    }}
+
+    pub fn length_squared(self) -> f32 {{
+        self.x * self.x + self.y * self.y
+    }}
+}}
+
+const LIMIT_{i}: i64 = 0x7fff_ffff;
+
+pub fn fibonacci_{i}(n: i64) -> i64 {{
+    let mut a = 0;
+    let mut b = 1;
+    let mut i = 0;
+    while i < n && i < LIMIT_{i} {{
+        let next = a + b;
+        a = b;
+        b = next;
+        i += 1;
+    }}
+    for _ in 0..10 {{
+        if a > 1_000_000 {{ break; }} else {{ a = a * 2; }}
+    }}
+    a
+}}
+
+pub fn greeting_{i}() -> f64 {{
+    let message = "Hello, \"world\"! Ünïcödé is supported as well.";
+    let ratio = 1.5e3 / 2.0;
+    ratio
+}}
+"#
+        )
+        .unwrap();
+    }
+    text
+}
+
+fn lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lexer");
+    for items in [10, 100, 1000] {
+        let text = generate_source(items);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("tokenize", items), &text, |b, text| {
+            b.iter(|| tokenize(text));
+        });
+    }
+    group.finish();
+}
+
+fn parser(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parser");
+    for items in [10, 100, 1000] {
+        let text = generate_source(items);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("parse", items), &text, |b, text| {
+            b.iter(|| SourceFile::parse(text));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lexer, parser);
+criterion_main!(benches);
@@ -30,6 +30,15 @@ pub trait AstNode: Clone {
     where
         Self: Sized;
     fn syntax(&self) -> &SyntaxNode;
+
+    /// Returns a copy of this node that is part of a new, mutable tree, which
+    /// can be edited with the functions in [`crate::ted`].
+    fn clone_for_update(&self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::cast(self.syntax().clone_for_update()).unwrap()
+    }
 }
 
 /// Like an `AstNode`, but wraps tokens rather than interior nodes.
@@ -265,3 +265,46 @@ impl ast::IndexExpr {
         children(self).nth(1)
     }
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RangeOp {
+    /// The `..` operator for a half-open range
+    Exclusive,
+    /// The `..=` operator for a closed range
+    Inclusive,
+}
+
+impl ast::RangeExpr {
+    pub fn op_details(&self) -> Option<(SyntaxToken, RangeOp)> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(rowan::NodeOrToken::into_token)
+            .find_map(|c| {
+                let range_op = match c.kind() {
+                    T![..] => RangeOp::Exclusive,
+                    T![..=] => RangeOp::Inclusive,
+                    _ => return None,
+                };
+                Some((c, range_op))
+            })
+    }
+
+    pub fn op_kind(&self) -> Option<RangeOp> {
+        self.op_details().map(|t| t.1)
+    }
+
+    pub fn start(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+
+    pub fn end(&self) -> Option<ast::Expr> {
+        children(self).nth(1)
+    }
+}
+
+impl ast::ForExpr {
+    /// Returns the expression that is iterated over.
+    pub fn iterable(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+}
@@ -6,7 +6,7 @@ use text_size::TextRange;
 
 use crate::{
     ast::{self, child_opt, AstNode, NameOwner},
-    SyntaxKind, SyntaxNode, TokenText, T,
+    SyntaxKind, SyntaxNode, SyntaxToken, TokenText, T,
 };
 
 impl ast::Name {
@@ -236,6 +236,96 @@ impl ast::UseTree {
     }
 }
 
+impl ast::BindPat {
+    /// Returns true if the binding is declared with `mut`.
+    pub fn is_mutable(&self) -> bool {
+        self.mut_token().is_some()
+    }
+
+    /// Returns the `mut` token of the binding, if any.
+    pub fn mut_token(&self) -> Option<SyntaxToken> {
+        mut_token(self.syntax())
+    }
+}
+
+impl ast::SelfParam {
+    /// Returns true if `self` is declared with `mut`.
+    pub fn is_mutable(&self) -> bool {
+        self.mut_token().is_some()
+    }
+
+    /// Returns the `mut` token of the parameter, if any.
+    pub fn mut_token(&self) -> Option<SyntaxToken> {
+        mut_token(self.syntax())
+    }
+}
+
+/// Returns the `mut` token that is a direct child of `node`, if any.
+fn mut_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(rowan::NodeOrToken::into_token)
+        .find(|it| it.kind() == T![mut])
+}
+
+impl ast::RecordFieldPatList {
+    /// Returns true if the list ends with `..`, which ignores all remaining
+    /// fields.
+    pub fn has_rest(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![..])
+    }
+}
+
+impl ast::TupleStructPat {
+    /// Returns true if the fields contain `..`, which ignores all remaining
+    /// fields.
+    pub fn has_rest(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![..])
+    }
+}
+
+impl ast::TuplePat {
+    /// Returns true if the fields contain `..`, which ignores all remaining
+    /// fields.
+    pub fn has_rest(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![..])
+    }
+}
+
+impl ast::Label {
+    /// Returns the token that contains the name of the label, e.g. `'outer`.
+    pub fn label_name(&self) -> Option<SyntaxToken> {
+        label_name_token(self.syntax())
+    }
+}
+
+impl ast::BreakExpr {
+    /// Returns the token that contains the name of the label that is broken
+    /// out of, e.g. `'outer` in `break 'outer`.
+    pub fn label_name(&self) -> Option<SyntaxToken> {
+        label_name_token(self.syntax())
+    }
+}
+
+impl ast::ContinueExpr {
+    /// Returns the token that contains the name of the label of the loop that
+    /// is continued, e.g. `'outer` in `continue 'outer`.
+    pub fn label_name(&self) -> Option<SyntaxToken> {
+        label_name_token(self.syntax())
+    }
+}
+
+fn label_name_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .find(|it| it.kind() == SyntaxKind::LABEL_NAME)
+}
+
 impl ast::TypeAliasDef {
     /// Returns the signature range.
     ///
@@ -262,3 +352,14 @@ impl ast::TypeAliasDef {
         TextRange::new(start, end)
     }
 }
+
+impl ast::TokenTree {
+    /// Returns the text between the delimiters of the token tree, e.g.
+    /// `always` in `#[inline(always)]`.
+    pub fn inner_text(&self) -> String {
+        let text = self.syntax().text().to_string();
+        let text = text.strip_prefix('(').unwrap_or(&text);
+        let text = text.strip_suffix(')').unwrap_or(text);
+        text.trim().to_owned()
+    }
+}
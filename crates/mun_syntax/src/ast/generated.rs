@@ -143,6 +143,8 @@ impl AssociatedItem {
     }
 }
 
+impl ast::DocCommentsOwner for AssociatedItem {}
+impl ast::AttrsOwner for AssociatedItem {}
 impl AssociatedItem {}
 
 // AssociatedItemList
@@ -173,6 +175,42 @@ impl AssociatedItemList {
     }
 }
 
+// Attr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Attr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ATTR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Attr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Attr {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+
+    pub fn literal(&self) -> Option<Literal> {
+        super::child_opt(self)
+    }
+
+    pub fn token_tree(&self) -> Option<TokenTree> {
+        super::child_opt(self)
+    }
+}
+
 // BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -347,6 +385,63 @@ impl Condition {
     }
 }
 
+// ConstDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ConstDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONST_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ConstDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for ConstDef {}
+impl ast::VisibilityOwner for ConstDef {}
+impl ast::DocCommentsOwner for ConstDef {}
+impl ast::AttrsOwner for ConstDef {}
+impl ast::TypeAscriptionOwner for ConstDef {}
+impl ConstDef {
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// ContinueExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContinueExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ContinueExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONTINUE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ContinueExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ContinueExpr {}
+
 // Expr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -363,17 +458,21 @@ impl AstNode for Expr {
                 | PATH_EXPR
                 | BIN_EXPR
                 | PAREN_EXPR
+                | TUPLE_EXPR
                 | CALL_EXPR
                 | METHOD_CALL_EXPR
                 | FIELD_EXPR
                 | IF_EXPR
                 | LOOP_EXPR
                 | WHILE_EXPR
+                | FOR_EXPR
                 | RETURN_EXPR
                 | BREAK_EXPR
+                | CONTINUE_EXPR
                 | BLOCK_EXPR
                 | ARRAY_EXPR
                 | INDEX_EXPR
+                | RANGE_EXPR
                 | RECORD_LIT
         )
     }
@@ -395,17 +494,21 @@ pub enum ExprKind {
     PathExpr(PathExpr),
     BinExpr(BinExpr),
     ParenExpr(ParenExpr),
+    TupleExpr(TupleExpr),
     CallExpr(CallExpr),
     MethodCallExpr(MethodCallExpr),
     FieldExpr(FieldExpr),
     IfExpr(IfExpr),
     LoopExpr(LoopExpr),
     WhileExpr(WhileExpr),
+    ForExpr(ForExpr),
     ReturnExpr(ReturnExpr),
     BreakExpr(BreakExpr),
+    ContinueExpr(ContinueExpr),
     BlockExpr(BlockExpr),
     ArrayExpr(ArrayExpr),
     IndexExpr(IndexExpr),
+    RangeExpr(RangeExpr),
     RecordLit(RecordLit),
 }
 impl From<Literal> for Expr {
@@ -433,6 +536,11 @@ impl From<ParenExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<TupleExpr> for Expr {
+    fn from(n: TupleExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<CallExpr> for Expr {
     fn from(n: CallExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -463,6 +571,11 @@ impl From<WhileExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ForExpr> for Expr {
+    fn from(n: ForExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<ReturnExpr> for Expr {
     fn from(n: ReturnExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -473,6 +586,11 @@ impl From<BreakExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ContinueExpr> for Expr {
+    fn from(n: ContinueExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<BlockExpr> for Expr {
     fn from(n: BlockExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -488,6 +606,11 @@ impl From<IndexExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<RangeExpr> for Expr {
+    fn from(n: RangeExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<RecordLit> for Expr {
     fn from(n: RecordLit) -> Expr {
         Expr { syntax: n.syntax }
@@ -502,6 +625,7 @@ impl Expr {
             PATH_EXPR => ExprKind::PathExpr(PathExpr::cast(self.syntax.clone()).unwrap()),
             BIN_EXPR => ExprKind::BinExpr(BinExpr::cast(self.syntax.clone()).unwrap()),
             PAREN_EXPR => ExprKind::ParenExpr(ParenExpr::cast(self.syntax.clone()).unwrap()),
+            TUPLE_EXPR => ExprKind::TupleExpr(TupleExpr::cast(self.syntax.clone()).unwrap()),
             CALL_EXPR => ExprKind::CallExpr(CallExpr::cast(self.syntax.clone()).unwrap()),
             METHOD_CALL_EXPR => {
                 ExprKind::MethodCallExpr(MethodCallExpr::cast(self.syntax.clone()).unwrap())
@@ -510,11 +634,16 @@ impl Expr {
             IF_EXPR => ExprKind::IfExpr(IfExpr::cast(self.syntax.clone()).unwrap()),
             LOOP_EXPR => ExprKind::LoopExpr(LoopExpr::cast(self.syntax.clone()).unwrap()),
             WHILE_EXPR => ExprKind::WhileExpr(WhileExpr::cast(self.syntax.clone()).unwrap()),
+            FOR_EXPR => ExprKind::ForExpr(ForExpr::cast(self.syntax.clone()).unwrap()),
             RETURN_EXPR => ExprKind::ReturnExpr(ReturnExpr::cast(self.syntax.clone()).unwrap()),
             BREAK_EXPR => ExprKind::BreakExpr(BreakExpr::cast(self.syntax.clone()).unwrap()),
+            CONTINUE_EXPR => {
+                ExprKind::ContinueExpr(ContinueExpr::cast(self.syntax.clone()).unwrap())
+            }
             BLOCK_EXPR => ExprKind::BlockExpr(BlockExpr::cast(self.syntax.clone()).unwrap()),
             ARRAY_EXPR => ExprKind::ArrayExpr(ArrayExpr::cast(self.syntax.clone()).unwrap()),
             INDEX_EXPR => ExprKind::IndexExpr(IndexExpr::cast(self.syntax.clone()).unwrap()),
+            RANGE_EXPR => ExprKind::RangeExpr(RangeExpr::cast(self.syntax.clone()).unwrap()),
             RECORD_LIT => ExprKind::RecordLit(RecordLit::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
@@ -583,6 +712,35 @@ impl FieldExpr {
     }
 }
 
+// ForExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ForExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, FOR_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ForExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for ForExpr {}
+impl ForExpr {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
 // FunctionDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -608,6 +766,7 @@ impl AstNode for FunctionDef {
 impl ast::NameOwner for FunctionDef {}
 impl ast::VisibilityOwner for FunctionDef {}
 impl ast::DocCommentsOwner for FunctionDef {}
+impl ast::AttrsOwner for FunctionDef {}
 impl ast::ExternOwner for FunctionDef {}
 impl FunctionDef {
     pub fn param_list(&self) -> Option<ParamList> {
@@ -675,6 +834,7 @@ impl AstNode for Impl {
 }
 impl ast::VisibilityOwner for Impl {}
 impl ast::DocCommentsOwner for Impl {}
+impl ast::AttrsOwner for Impl {}
 impl Impl {
     pub fn associated_item_list(&self) -> Option<AssociatedItemList> {
         super::child_opt(self)
@@ -709,6 +869,30 @@ impl AstNode for IndexExpr {
 }
 impl IndexExpr {}
 
+// Label
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Label {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, LABEL)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Label { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Label {}
+
 // LetStmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -859,7 +1043,7 @@ impl AstNode for ModuleItem {
     fn can_cast(kind: SyntaxKind) -> bool {
         matches!(
             kind,
-            USE | FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF | IMPL
+            USE | FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF | CONST_DEF | STATIC_DEF | IMPL
         )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
@@ -879,6 +1063,8 @@ pub enum ModuleItemKind {
     FunctionDef(FunctionDef),
     StructDef(StructDef),
     TypeAliasDef(TypeAliasDef),
+    ConstDef(ConstDef),
+    StaticDef(StaticDef),
     Impl(Impl),
 }
 impl From<Use> for ModuleItem {
@@ -901,6 +1087,16 @@ impl From<TypeAliasDef> for ModuleItem {
         ModuleItem { syntax: n.syntax }
     }
 }
+impl From<ConstDef> for ModuleItem {
+    fn from(n: ConstDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<StaticDef> for ModuleItem {
+    fn from(n: StaticDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
 impl From<Impl> for ModuleItem {
     fn from(n: Impl) -> ModuleItem {
         ModuleItem { syntax: n.syntax }
@@ -918,12 +1114,16 @@ impl ModuleItem {
             TYPE_ALIAS_DEF => {
                 ModuleItemKind::TypeAliasDef(TypeAliasDef::cast(self.syntax.clone()).unwrap())
             }
+            CONST_DEF => ModuleItemKind::ConstDef(ConstDef::cast(self.syntax.clone()).unwrap()),
+            STATIC_DEF => ModuleItemKind::StaticDef(StaticDef::cast(self.syntax.clone()).unwrap()),
             IMPL => ModuleItemKind::Impl(Impl::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
 }
 
+impl ast::DocCommentsOwner for ModuleItem {}
+impl ast::AttrsOwner for ModuleItem {}
 impl ModuleItem {}
 
 // Name
@@ -1087,6 +1287,34 @@ impl ParenExpr {
     }
 }
 
+// ParenPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParenPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ParenPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, PAREN_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ParenPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ParenPat {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
 // Pat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1096,7 +1324,10 @@ pub struct Pat {
 
 impl AstNode for Pat {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, BIND_PAT | PLACEHOLDER_PAT)
+        matches!(
+            kind,
+            BIND_PAT | PLACEHOLDER_PAT | RECORD_PAT | TUPLE_STRUCT_PAT | TUPLE_PAT | PAREN_PAT
+        )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -1113,6 +1344,10 @@ impl AstNode for Pat {
 pub enum PatKind {
     BindPat(BindPat),
     PlaceholderPat(PlaceholderPat),
+    RecordPat(RecordPat),
+    TupleStructPat(TupleStructPat),
+    TuplePat(TuplePat),
+    ParenPat(ParenPat),
 }
 impl From<BindPat> for Pat {
     fn from(n: BindPat) -> Pat {
@@ -1124,6 +1359,26 @@ impl From<PlaceholderPat> for Pat {
         Pat { syntax: n.syntax }
     }
 }
+impl From<RecordPat> for Pat {
+    fn from(n: RecordPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<TupleStructPat> for Pat {
+    fn from(n: TupleStructPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<TuplePat> for Pat {
+    fn from(n: TuplePat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<ParenPat> for Pat {
+    fn from(n: ParenPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
 
 impl Pat {
     pub fn kind(&self) -> PatKind {
@@ -1132,6 +1387,12 @@ impl Pat {
             PLACEHOLDER_PAT => {
                 PatKind::PlaceholderPat(PlaceholderPat::cast(self.syntax.clone()).unwrap())
             }
+            RECORD_PAT => PatKind::RecordPat(RecordPat::cast(self.syntax.clone()).unwrap()),
+            TUPLE_STRUCT_PAT => {
+                PatKind::TupleStructPat(TupleStructPat::cast(self.syntax.clone()).unwrap())
+            }
+            TUPLE_PAT => PatKind::TuplePat(TuplePat::cast(self.syntax.clone()).unwrap()),
+            PAREN_PAT => PatKind::ParenPat(ParenPat::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -1307,6 +1568,30 @@ impl PrefixExpr {
     }
 }
 
+// RangeExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RangeExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RANGE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RangeExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RangeExpr {}
+
 // RecordField
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1427,6 +1712,66 @@ impl RecordFieldList {
     }
 }
 
+// RecordFieldPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_FIELD_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldPat {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldPatList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldPatList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldPatList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_FIELD_PAT_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldPatList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldPatList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordFieldPat> {
+        super::children(self)
+    }
+}
+
 // RecordLit
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1459,6 +1804,38 @@ impl RecordLit {
     }
 }
 
+// RecordPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, RECORD_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordPat {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+
+    pub fn record_field_pat_list(&self) -> Option<RecordFieldPatList> {
+        super::child_opt(self)
+    }
+}
+
 // Rename
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1591,6 +1968,39 @@ impl ast::ModuleItemOwner for SourceFile {}
 impl ast::FunctionDefOwner for SourceFile {}
 impl SourceFile {}
 
+// StaticDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StaticDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for StaticDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, STATIC_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(StaticDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for StaticDef {}
+impl ast::VisibilityOwner for StaticDef {}
+impl ast::DocCommentsOwner for StaticDef {}
+impl ast::AttrsOwner for StaticDef {}
+impl ast::TypeAscriptionOwner for StaticDef {}
+impl StaticDef {
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
 // Stmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1666,12 +2076,65 @@ impl AstNode for StructDef {
 impl ast::NameOwner for StructDef {}
 impl ast::VisibilityOwner for StructDef {}
 impl ast::DocCommentsOwner for StructDef {}
+impl ast::AttrsOwner for StructDef {}
 impl StructDef {
     pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
+// TokenTree
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenTree {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TokenTree {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TOKEN_TREE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TokenTree { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TokenTree {}
+
+// TupleExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleExpr {
+    pub fn exprs(&self) -> impl Iterator<Item = Expr> {
+        super::children(self)
+    }
+}
+
 // TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1729,6 +2192,66 @@ impl TupleFieldDefList {
     }
 }
 
+// TuplePat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TuplePat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TuplePat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TuplePat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TuplePat {
+    pub fn args(&self) -> impl Iterator<Item = Pat> {
+        super::children(self)
+    }
+}
+
+// TupleStructPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleStructPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleStructPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_STRUCT_PAT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleStructPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleStructPat {
+    pub fn args(&self) -> impl Iterator<Item = Pat> {
+        super::children(self)
+    }
+
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
 // TypeAliasDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1754,6 +2277,7 @@ impl AstNode for TypeAliasDef {
 impl ast::NameOwner for TypeAliasDef {}
 impl ast::VisibilityOwner for TypeAliasDef {}
 impl ast::DocCommentsOwner for TypeAliasDef {}
+impl ast::AttrsOwner for TypeAliasDef {}
 impl TypeAliasDef {
     pub fn type_ref(&self) -> Option<TypeRef> {
         super::child_opt(self)
@@ -1840,6 +2364,8 @@ impl AstNode for Use {
     }
 }
 impl ast::VisibilityOwner for Use {}
+impl ast::DocCommentsOwner for Use {}
+impl ast::AttrsOwner for Use {}
 impl Use {
     pub fn use_tree(&self) -> Option<UseTree> {
         super::child_opt(self)
@@ -18,6 +18,43 @@ impl ast::FloatNumber {
     }
 }
 
+impl ast::String {
+    /// Returns the value of the string literal: the text between the quotes
+    /// with all escape sequences replaced by the characters they represent.
+    pub fn value(&self) -> std::string::String {
+        let text = self.text();
+        let mut chars = text.chars();
+        let quote = chars.next();
+        let text = chars.as_str();
+        let text = match quote {
+            Some(quote) => text.strip_suffix(quote).unwrap_or(text),
+            None => text,
+        };
+
+        let mut value = std::string::String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('0') => value.push('\0'),
+                Some(c @ ('\\' | '"' | '\'')) => value.push(c),
+                Some(c) => {
+                    value.push('\\');
+                    value.push(c);
+                }
+                None => value.push('\\'),
+            }
+        }
+        value
+    }
+}
+
 /// Given a string containing an integer literal (e.g `0x123` or `1234u32`),
 /// splits the string in the value part and the suffix part.
 fn split_int_text_and_suffix(text: &str) -> (&str, Option<&str>) {
@@ -98,6 +135,23 @@ fn split_float_text_and_suffix(text: &str) -> (&str, Option<&str>) {
 #[cfg(test)]
 mod tests {
     use super::{split_float_text_and_suffix, split_int_text_and_suffix};
+    use crate::{
+        ast::{self, AstToken},
+        AstNode, SourceFile,
+    };
+
+    #[test]
+    fn string_value() {
+        let file = SourceFile::parse(r#"fn main() { "a\n\"b\"\\"; 'c\''; "\q" }"#).tree();
+        let values: Vec<_> = file
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(rowan::NodeOrToken::into_token)
+            .filter_map(ast::String::cast)
+            .map(|it| it.value())
+            .collect();
+        assert_eq!(values, vec!["a\n\"b\"\\", "c'", "\\q"]);
+    }
 
     #[test]
     fn split_int_and_suffix() {
@@ -29,12 +29,29 @@ impl AstToken for Comment {
 
 impl Comment {
     pub fn kind(&self) -> CommentKind {
-        kind_by_prefix(self.text())
+        CommentKind::from_text(self.text())
     }
 
     pub fn prefix(&self) -> &'static str {
         prefix_by_kind(self.kind())
     }
+
+    /// Returns the text of an outer doc comment without the comment markers and
+    /// the space that usually follows them, or `None` if this is not an outer
+    /// doc comment.
+    pub fn doc_text(&self) -> Option<&str> {
+        let kind = self.kind();
+        if kind.doc != Some(CommentPlacement::Outer) {
+            return None;
+        }
+
+        let text = &self.text()[prefix_by_kind(kind).len()..];
+        let text = match kind.shape {
+            CommentShape::Line => text,
+            CommentShape::Block => text.strip_suffix("*/").unwrap_or(text).trim(),
+        };
+        Some(text.strip_prefix(' ').unwrap_or(text))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -43,6 +60,13 @@ pub struct CommentKind {
     pub doc: Option<CommentPlacement>,
 }
 
+impl CommentKind {
+    /// Determines the kind of a comment from its text.
+    pub fn from_text(text: &str) -> CommentKind {
+        kind_by_prefix(text)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CommentShape {
     Line,
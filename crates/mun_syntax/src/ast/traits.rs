@@ -38,6 +38,10 @@ pub trait LoopBodyOwner: AstNode {
     fn loop_body(&self) -> Option<ast::BlockExpr> {
         child_opt(self)
     }
+
+    fn label(&self) -> Option<ast::Label> {
+        child_opt(self)
+    }
 }
 
 pub trait ArgListOwner: AstNode {
@@ -46,12 +50,28 @@ pub trait ArgListOwner: AstNode {
     }
 }
 
+pub trait AttrsOwner: AstNode {
+    fn attrs(&self) -> AstChildren<ast::Attr> {
+        children(self)
+    }
+}
+
 pub trait DocCommentsOwner: AstNode {
     fn doc_comments(&self) -> CommentIter {
         CommentIter {
             iter: self.syntax().children_with_tokens(),
         }
     }
+
+    /// Returns the text of the outer doc comments (`///` and `/** */`), without
+    /// the comment markers, or `None` if there are no doc comments.
+    fn doc_comment_text(&self) -> Option<String> {
+        let lines = self
+            .doc_comments()
+            .filter_map(|comment| comment.doc_text().map(ToOwned::to_owned))
+            .collect::<Vec<_>>();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
 }
 
 pub struct CommentIter {
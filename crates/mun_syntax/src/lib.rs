@@ -18,10 +18,12 @@ mod parsing;
 mod ptr;
 mod syntax_error;
 mod syntax_node;
+pub mod ted;
 mod token_text;
 
 #[cfg(test)]
 mod tests;
+pub mod trivia;
 pub mod utils;
 mod validation;
 
@@ -127,9 +129,26 @@ impl Parse<SourceFile> {
     }
 
     /// Parses the `SourceFile` again but with the given modification applied.
+    /// Only the token or block that contains the modification is parsed
+    /// again if possible, the rest of the tree is reused.
     pub fn reparse(&self, indel: &Indel) -> Parse<SourceFile> {
-        // TODO: Implement something smarter here.
-        self.full_reparse(indel)
+        self.incremental_reparse(indel)
+            .unwrap_or_else(|| self.full_reparse(indel))
+    }
+
+    /// Tries to parse only the token or block that contains the specified
+    /// modification again.
+    fn incremental_reparse(&self, indel: &Indel) -> Option<Parse<SourceFile>> {
+        let root = self.syntax_node();
+
+        // The validation errors are appended to the parse errors. Validation is
+        // performed again for the new tree, so only the parse errors are kept.
+        let num_validation_errors = validation::validate(&root).len();
+        let parse_errors = self.errors[..self.errors.len() - num_validation_errors].to_vec();
+
+        let (green, mut errors) = parsing::incremental_reparse(&root, indel, parse_errors)?;
+        errors.extend(validation::validate(&SyntaxNode::new_root(green.clone())));
+        Some(Parse::new(green, errors))
     }
 
     /// Performs a "reparse" of the `SourceFile` after applying the specified
@@ -214,6 +233,8 @@ fn api_walkthrough() {
             ast::ModuleItemKind::FunctionDef(f) => func = Some(f),
             ast::ModuleItemKind::StructDef(_)
             | ast::ModuleItemKind::TypeAliasDef(_)
+            | ast::ModuleItemKind::ConstDef(_)
+            | ast::ModuleItemKind::StaticDef(_)
             | ast::ModuleItemKind::Use(_)
             | ast::ModuleItemKind::Impl(_) => (),
         }
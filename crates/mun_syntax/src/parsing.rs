@@ -7,10 +7,12 @@ mod event;
 mod grammar;
 pub mod lexer;
 mod parser;
+mod reparsing;
 mod text_token_source;
 mod text_tree_sink;
 
 pub use lexer::tokenize;
+pub(crate) use reparsing::incremental_reparse;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ParseError(pub String);
@@ -1,4 +1,5 @@
 mod adt;
+mod attributes;
 mod declarations;
 mod expressions;
 mod params;
@@ -11,15 +12,17 @@ use super::{
     parser::{CompletedMarker, Marker, Parser},
     token_set::TokenSet,
     SyntaxKind::{
-        self, ARG_LIST, ARRAY_EXPR, ARRAY_TYPE, BIND_PAT, BIN_EXPR, BLOCK_EXPR, BREAK_EXPR,
-        CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, EXTERN, FIELD_EXPR, FLOAT_NUMBER,
-        FUNCTION_DEF, GC_KW, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL,
-        LOOP_EXPR, MEMORY_TYPE_SPECIFIER, NAME, NAME_REF, NEVER_TYPE, PARAM, PARAM_LIST,
-        PAREN_EXPR, PATH, PATH_EXPR, PATH_SEGMENT, PATH_TYPE, PLACEHOLDER_PAT, PREFIX_EXPR,
-        RECORD_FIELD, RECORD_FIELD_DEF, RECORD_FIELD_DEF_LIST, RECORD_FIELD_LIST, RECORD_LIT,
-        RENAME, RETURN_EXPR, RET_TYPE, SELF_PARAM, SOURCE_FILE, STRING, STRUCT_DEF,
-        TUPLE_FIELD_DEF, TUPLE_FIELD_DEF_LIST, TYPE_ALIAS_DEF, USE, USE_TREE, USE_TREE_LIST,
-        VALUE_KW, VISIBILITY, WHILE_EXPR,
+        self, ARG_LIST, ARRAY_EXPR, ARRAY_TYPE, ASSOCIATED_ITEM_LIST, ATTR, BIND_PAT, BIN_EXPR,
+        BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, CONST_DEF, CONTINUE_EXPR, EOF, ERROR,
+        EXPR_STMT, EXTERN, FIELD_EXPR, FLOAT_NUMBER, FOR_EXPR, FUNCTION_DEF, GC_KW, IDENT, IF_EXPR,
+        INDEX, INDEX_EXPR, INT_NUMBER, LABEL, LABEL_NAME, LET_STMT, LITERAL, LOOP_EXPR,
+        MEMORY_TYPE_SPECIFIER, NAME, NAME_REF, NEVER_TYPE, PARAM, PARAM_LIST, PAREN_EXPR,
+        PAREN_PAT, PATH, PATH_EXPR, PATH_SEGMENT, PATH_TYPE, PLACEHOLDER_PAT, PREFIX_EXPR,
+        RANGE_EXPR, RECORD_FIELD, RECORD_FIELD_DEF, RECORD_FIELD_DEF_LIST, RECORD_FIELD_LIST,
+        RECORD_FIELD_PAT, RECORD_FIELD_PAT_LIST, RECORD_LIT, RECORD_PAT, RENAME, RETURN_EXPR,
+        RET_TYPE, SELF_PARAM, SOURCE_FILE, STATIC_DEF, STRING, STRUCT_DEF, TOKEN_TREE, TUPLE_EXPR,
+        TUPLE_FIELD_DEF, TUPLE_FIELD_DEF_LIST, TUPLE_PAT, TUPLE_STRUCT_PAT, TYPE_ALIAS_DEF, USE,
+        USE_TREE, USE_TREE_LIST, VALUE_KW, VISIBILITY, WHILE_EXPR,
     },
 };
 
@@ -43,6 +46,30 @@ pub(crate) fn root(p: &mut Parser<'_>) {
     m.complete(p, SOURCE_FILE);
 }
 
+/// Parses a node that can be reparsed in isolation, without the tokens that
+/// surround it.
+#[derive(Clone, Copy)]
+pub(crate) struct Reparser(fn(&mut Parser<'_>));
+
+impl Reparser {
+    /// Returns the `Reparser` for a node of the specified kind, or `None` if
+    /// such a node cannot be reparsed in isolation.
+    pub(crate) fn for_node(kind: SyntaxKind) -> Option<Reparser> {
+        let parser = match kind {
+            BLOCK_EXPR => expressions::block,
+            RECORD_FIELD_DEF_LIST => adt::record_field_def_list,
+            ASSOCIATED_ITEM_LIST => traits::associated_item_list,
+            _ => return None,
+        };
+        Some(Reparser(parser))
+    }
+
+    /// Parses the node.
+    pub(crate) fn parse(self, p: &mut Parser<'_>) {
+        (self.0)(p);
+    }
+}
+
 //pub(crate) fn pattern(p: &mut Parser<'_>) {
 //    patterns::pattern(p)
 //}
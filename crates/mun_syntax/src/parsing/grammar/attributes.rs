@@ -0,0 +1,59 @@
+use super::{expressions, paths, Parser, ATTR, EOF, TOKEN_TREE};
+
+/// Parses the attributes that precede an item, e.g. `#[inline]`.
+pub(super) fn outer_attrs(p: &mut Parser<'_>) {
+    while p.at(T![#]) {
+        attr(p);
+    }
+}
+
+/// Parses a single attribute. An attribute consists of a path that is
+/// optionally followed by either a literal value (e.g.
+/// `#[export_name = "foo"]`) or a token tree (e.g. `#[inline(always)]`).
+fn attr(p: &mut Parser<'_>) {
+    assert!(p.at(T![#]));
+    let m = p.start();
+    p.bump(T![#]);
+    if p.expect(T!['[']) {
+        if paths::is_path_start(p) {
+            paths::expr_path(p);
+            match p.current() {
+                T![=] => {
+                    p.bump(T![=]);
+                    if expressions::literal(p).is_none() {
+                        p.error("expected a literal");
+                    }
+                }
+                T!['('] => token_tree(p),
+                _ => (),
+            }
+        } else {
+            p.error("expected an attribute name");
+        }
+        p.expect(T![']']);
+    }
+    m.complete(p, ATTR);
+}
+
+/// Parses a parenthesized sequence of arbitrary tokens with balanced
+/// parentheses.
+fn token_tree(p: &mut Parser<'_>) {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    loop {
+        match p.current() {
+            T!['('] => token_tree(p),
+            T![')'] => {
+                p.bump(T![')']);
+                break;
+            }
+            EOF | T![']'] => {
+                p.error("expected `)`");
+                break;
+            }
+            _ => p.bump_any(),
+        }
+    }
+    m.complete(p, TOKEN_TREE);
+}
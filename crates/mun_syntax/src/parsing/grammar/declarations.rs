@@ -1,12 +1,36 @@
 use super::{
-    adt, error_block, expressions, name, name_recovery, opt_visibility, params, paths, traits,
-    types, Marker, Parser, TokenSet, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME, RET_TYPE, USE,
-    USE_TREE, USE_TREE_LIST,
+    adt, attributes, error_block, expressions, name, name_recovery, opt_visibility, params, paths,
+    traits, types, Marker, Parser, TokenSet, CONST_DEF, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME,
+    RET_TYPE, STATIC_DEF, USE, USE_TREE, USE_TREE_LIST,
 };
 use crate::{parsing::grammar::paths::is_use_path_start, T};
 
-pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![fn], T![pub], T![struct], T![use], T![;], T![impl]]);
+pub(super) const DECLARATION_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![#],
+    T![fn],
+    T![pub],
+    T![struct],
+    T![use],
+    T![;],
+    T![impl],
+    T![const],
+    T![static],
+]);
+
+/// Tokens that can only occur at the start of a declaration. When encountered
+/// inside a block, the block was most likely never closed.
+pub(super) const DECLARATION_FIRST: TokenSet = TokenSet::new(&[
+    T![#],
+    T![fn],
+    T![pub],
+    T![struct],
+    T![use],
+    T![impl],
+    T![type],
+    T![extern],
+    T![const],
+    T![static],
+]);
 
 pub(super) fn mod_contents(p: &mut Parser<'_>) {
     while !p.at(EOF) {
@@ -16,6 +40,7 @@ pub(super) fn mod_contents(p: &mut Parser<'_>) {
 
 pub(super) fn declaration(p: &mut Parser<'_>, stop_on_r_curly: bool) {
     let m = p.start();
+    attributes::outer_attrs(p);
     let m = match maybe_declaration(p, m) {
         Ok(()) => return,
         Err(m) => m,
@@ -78,6 +103,12 @@ fn declarations_without_modifiers(p: &mut Parser<'_>, m: Marker) -> Result<(), M
         T![impl] => {
             traits::impl_(p, m);
         }
+        T![const] => {
+            const_def(p, m);
+        }
+        T![static] => {
+            static_def(p, m);
+        }
         _ => return Err(m),
     };
     Ok(())
@@ -104,6 +135,33 @@ pub(super) fn fn_def(p: &mut Parser<'_>) {
     }
 }
 
+fn const_def(p: &mut Parser<'_>, m: Marker) {
+    assert!(p.at(T![const]));
+    p.bump(T![const]);
+    const_or_static_body(p, "const");
+    m.complete(p, CONST_DEF);
+}
+
+fn static_def(p: &mut Parser<'_>, m: Marker) {
+    assert!(p.at(T![static]));
+    p.bump(T![static]);
+    const_or_static_body(p, "static");
+    m.complete(p, STATIC_DEF);
+}
+
+fn const_or_static_body(p: &mut Parser<'_>, kw: &str) {
+    name_recovery(p, DECLARATION_RECOVERY_SET.union(TokenSet::new(&[T![=]])));
+    if p.at(T![:]) {
+        types::ascription(p);
+    } else {
+        p.error(format!("missing type for `{kw}` item"));
+    }
+    if p.expect(T![=]) {
+        expressions::expr(p);
+    }
+    p.expect(T![;]);
+}
+
 fn opt_fn_ret_type(p: &mut Parser<'_>) -> bool {
     if p.at(T![->]) {
         let m = p.start();
@@ -1,17 +1,19 @@
 use super::{
-    error_block, expressions, name_ref, name_ref_or_index, paths, patterns, types, BlockLike,
-    CompletedMarker, Marker, Parser, SyntaxKind, TokenSet, ARG_LIST, ARRAY_EXPR, BIN_EXPR,
-    BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, FIELD_EXPR, FLOAT_NUMBER,
-    IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL, LOOP_EXPR, PAREN_EXPR,
-    PATH_EXPR, PATH_TYPE, PREFIX_EXPR, RECORD_FIELD, RECORD_FIELD_LIST, RECORD_LIT, RETURN_EXPR,
-    STRING, WHILE_EXPR,
+    declarations, error_block, expressions, name_ref, name_ref_or_index, paths, patterns, types,
+    BlockLike, CompletedMarker, Marker, Parser, SyntaxKind, TokenSet, ARG_LIST, ARRAY_EXPR,
+    BIN_EXPR, BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, CONTINUE_EXPR, EOF, ERROR, EXPR_STMT,
+    FIELD_EXPR, FLOAT_NUMBER, FOR_EXPR, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LABEL,
+    LABEL_NAME, LET_STMT, LITERAL, LOOP_EXPR, PAREN_EXPR, PATH_EXPR, PATH_TYPE, PREFIX_EXPR,
+    RANGE_EXPR, RECORD_FIELD, RECORD_FIELD_LIST, RECORD_LIT, RETURN_EXPR, STRING, TUPLE_EXPR,
+    WHILE_EXPR,
 };
 use crate::{parsing::grammar::paths::PATH_FIRST, SyntaxKind::METHOD_CALL_EXPR};
 
 pub(crate) const LITERAL_FIRST: TokenSet =
     TokenSet::new(&[T![true], T![false], INT_NUMBER, FLOAT_NUMBER, STRING]);
 
-const EXPR_RECOVERY_SET: TokenSet = TokenSet::new(&[T![let]]);
+const EXPR_RECOVERY_SET: TokenSet =
+    TokenSet::new(&[T![let]]).union(declarations::DECLARATION_FIRST);
 
 const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(TokenSet::new(&[
     IDENT,
@@ -22,7 +24,10 @@ const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(TokenSet
     T![loop],
     T![return],
     T![break],
+    T![continue],
     T![while],
+    T![for],
+    LABEL_NAME,
 ]));
 
 const LHS_FIRST: TokenSet = ATOM_EXPR_FIRST.union(TokenSet::new(&[T![!], T![-]]));
@@ -47,6 +52,13 @@ struct Restrictions {
 
 pub(crate) fn expr_block_contents(p: &mut Parser<'_>) {
     while !p.at(EOF) && !p.at(T!['}']) {
+        // A declaration cannot occur inside a block. If we encounter one, the block was most
+        // likely never closed. Stop parsing the block so the declaration can be parsed as part
+        // of the enclosing module instead of producing errors for the rest of the file.
+        if p.at_ts(declarations::DECLARATION_FIRST) {
+            break;
+        }
+
         if p.eat(T![;]) {
             continue;
         }
@@ -69,7 +81,9 @@ fn block_expr(p: &mut Parser<'_>) -> CompletedMarker {
     let m = p.start();
     p.bump(T!['{']);
     expr_block_contents(p);
-    p.expect(T!['}']);
+
+    // If the block is not closed, the unmatched `{` is reported during validation.
+    p.eat(T!['}']);
     m.complete(p, BLOCK_EXPR)
 }
 
@@ -157,7 +171,14 @@ fn expr_bp(p: &mut Parser<'_>, r: Restrictions, bp: u8) -> (Option<CompletedMark
         p.bump(op);
 
         expr_bp(p, r, op_bp + 1);
-        lhs = m.complete(p, BIN_EXPR);
+        lhs = m.complete(
+            p,
+            if matches!(op, T![..] | T![..=]) {
+                RANGE_EXPR
+            } else {
+                BIN_EXPR
+            },
+        );
     }
 
     (Some(lhs), BlockLike::NotBlock)
@@ -194,6 +215,8 @@ fn current_op(p: &Parser<'_>) -> (u8, SyntaxKind) {
         T![<] if p.at(T![<<=]) => (1, T![<<=]),
         T![<] if p.at(T![<<]) => (9, T![<<]),
         T![<] => (5, T![<]),
+        T![.] if p.at(T![..=]) => (2, T![..=]),
+        T![.] if p.at(T![..]) => (2, T![..]),
         _ => (0, T![_]),
     }
 }
@@ -228,7 +251,7 @@ fn postfix_expr(
         lhs = match p.current() {
             T!['('] if allow_calls => call_expr(p, lhs),
             T!['['] if allow_calls => index_expr(p, lhs),
-            T![.] => postfix_dot_expr(p, lhs),
+            T![.] if !p.at(T![..]) => postfix_dot_expr(p, lhs),
             INDEX => field_expr(p, lhs),
             _ => break,
         };
@@ -323,21 +346,37 @@ fn atom_expr(p: &mut Parser<'_>, r: Restrictions) -> Option<(CompletedMarker, Bl
     }
 
     let marker = match p.current() {
-        T!['('] => paren_expr(p),
+        T!['('] => paren_or_tuple_expr(p),
         T!['{'] => block_expr(p),
         T!['['] => array_expr(p),
         T![if] => if_expr(p),
-        T![loop] => loop_expr(p),
+        T![loop] => loop_expr(p, None),
         T![return] => ret_expr(p),
-        T![while] => while_expr(p),
+        T![while] => while_expr(p, None),
+        T![for] => for_expr(p, None),
         T![break] => break_expr(p, r),
+        T![continue] => continue_expr(p),
+        LABEL_NAME => {
+            let m = p.start();
+            label(p);
+            match p.current() {
+                T![loop] => loop_expr(p, Some(m)),
+                T![while] => while_expr(p, Some(m)),
+                T![for] => for_expr(p, Some(m)),
+                _ => {
+                    // A label can only be placed in front of a loop
+                    p.error("expected a loop");
+                    m.complete(p, ERROR)
+                }
+            }
+        }
         _ => {
             p.error_recover("expected expression", EXPR_RECOVERY_SET);
             return None;
         }
     };
     let blocklike = match marker.kind() {
-        IF_EXPR | WHILE_EXPR | LOOP_EXPR | BLOCK_EXPR => BlockLike::Block,
+        IF_EXPR | WHILE_EXPR | FOR_EXPR | LOOP_EXPR | BLOCK_EXPR => BlockLike::Block,
         _ => BlockLike::NotBlock,
     };
     Some((marker, blocklike))
@@ -357,7 +396,7 @@ fn path_expr(p: &mut Parser<'_>, r: Restrictions) -> (CompletedMarker, BlockLike
     }
 }
 
-fn literal(p: &mut Parser<'_>) -> Option<CompletedMarker> {
+pub(super) fn literal(p: &mut Parser<'_>) -> Option<CompletedMarker> {
     if !p.at_ts(LITERAL_FIRST) {
         return None;
     }
@@ -366,13 +405,36 @@ fn literal(p: &mut Parser<'_>) -> Option<CompletedMarker> {
     Some(m.complete(p, LITERAL))
 }
 
-fn paren_expr(p: &mut Parser<'_>) -> CompletedMarker {
+/// Parses a parenthesized expression, e.g. `(a + b)`, or a tuple, e.g. `()`,
+/// `(a,)` or `(a, b)`.
+fn paren_or_tuple_expr(p: &mut Parser<'_>) -> CompletedMarker {
     assert!(p.at(T!['(']));
     let m = p.start();
     p.bump(T!['(']);
-    expr(p);
+
+    let mut saw_expr = false;
+    let mut saw_comma = false;
+    while !p.at(EOF) && !p.at(T![')']) {
+        saw_expr = true;
+        expr(p);
+
+        if !p.at(T![')']) {
+            if !p.expect(T![,]) {
+                break;
+            }
+            saw_comma = true;
+        }
+    }
     p.expect(T![')']);
-    m.complete(p, PAREN_EXPR)
+
+    m.complete(
+        p,
+        if saw_expr && !saw_comma {
+            PAREN_EXPR
+        } else {
+            TUPLE_EXPR
+        },
+    )
 }
 
 fn if_expr(p: &mut Parser<'_>) -> CompletedMarker {
@@ -392,9 +454,17 @@ fn if_expr(p: &mut Parser<'_>) -> CompletedMarker {
     m.complete(p, IF_EXPR)
 }
 
-fn loop_expr(p: &mut Parser<'_>) -> CompletedMarker {
-    assert!(p.at(T![loop]));
+fn label(p: &mut Parser<'_>) {
+    assert!(p.at(LABEL_NAME));
     let m = p.start();
+    p.bump(LABEL_NAME);
+    p.expect(T![:]);
+    m.complete(p, LABEL);
+}
+
+fn loop_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![loop]));
+    let m = m.unwrap_or_else(|| p.start());
     p.bump(T![loop]);
     block(p);
     m.complete(p, LOOP_EXPR)
@@ -420,21 +490,41 @@ fn break_expr(p: &mut Parser<'_>, r: Restrictions) -> CompletedMarker {
     assert!(p.at(T![break]));
     let m = p.start();
     p.bump(T![break]);
+    p.eat(LABEL_NAME);
     if p.at_ts(EXPR_FIRST) && !(r.forbid_structs && p.at(T!['{'])) {
         expr(p);
     }
     m.complete(p, BREAK_EXPR)
 }
 
-fn while_expr(p: &mut Parser<'_>) -> CompletedMarker {
-    assert!(p.at(T![while]));
+fn continue_expr(p: &mut Parser<'_>) -> CompletedMarker {
+    assert!(p.at(T![continue]));
     let m = p.start();
+    p.bump(T![continue]);
+    p.eat(LABEL_NAME);
+    m.complete(p, CONTINUE_EXPR)
+}
+
+fn while_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![while]));
+    let m = m.unwrap_or_else(|| p.start());
     p.bump(T![while]);
     cond(p);
     block(p);
     m.complete(p, WHILE_EXPR)
 }
 
+fn for_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![for]));
+    let m = m.unwrap_or_else(|| p.start());
+    p.bump(T![for]);
+    patterns::pattern(p);
+    p.expect(T![in]);
+    expr_no_struct(p);
+    block(p);
+    m.complete(p, FOR_EXPR)
+}
+
 fn record_field_list(p: &mut Parser<'_>) {
     assert!(p.at(T!['{']));
     let m = p.start();
@@ -36,8 +36,9 @@ fn param(p: &mut Parser<'_>) {
 }
 
 fn opt_self_param(p: &mut Parser<'_>) {
-    if p.at(T![self]) {
+    if p.at(T![self]) || (p.at(T![mut]) && p.nth(1) == T![self]) {
         let m = p.start();
+        p.eat(T![mut]);
         self_as_name(p);
         m.complete(p, SELF_PARAM);
 
@@ -1,10 +1,12 @@
 use super::{
-    expressions, name, paths, CompletedMarker, Parser, TokenSet, BIND_PAT, IDENT, PLACEHOLDER_PAT,
+    expressions, name, name_ref, paths, CompletedMarker, Parser, TokenSet, BIND_PAT, EOF, IDENT,
+    PAREN_PAT, PLACEHOLDER_PAT, RECORD_FIELD_PAT, RECORD_FIELD_PAT_LIST, RECORD_PAT, TUPLE_PAT,
+    TUPLE_STRUCT_PAT,
 };
 
 pub(super) const PATTERN_FIRST: TokenSet = expressions::LITERAL_FIRST
     .union(paths::PATH_FIRST)
-    .union(TokenSet::new(&[T![-], T![_]]));
+    .union(TokenSet::new(&[T![-], T![_], T![mut], T!['(']]));
 
 pub(super) fn pattern(p: &mut Parser<'_>) {
     pattern_r(p, PATTERN_FIRST);
@@ -16,13 +18,22 @@ pub(super) fn pattern_r(p: &mut Parser<'_>, recovery_set: TokenSet) {
 
 fn atom_pat(p: &mut Parser<'_>, recovery_set: TokenSet) -> Option<CompletedMarker> {
     let t1 = p.nth(0);
-    if t1 == IDENT {
+    if t1 == IDENT && !matches!(p.nth(1), T![::] | T!['{'] | T!['(']) {
         return Some(bind_pat(p));
     }
 
+    if t1 == T![mut] {
+        return Some(bind_pat(p));
+    }
+
+    if paths::is_path_start(p) {
+        return Some(path_pat(p));
+    }
+
     #[allow(clippy::single_match_else)]
     let m = match t1 {
         T![_] => placeholder_pat(p),
+        T!['('] => paren_or_tuple_pat(p),
         _ => {
             p.error_recover("expected pattern", recovery_set);
             return None;
@@ -31,6 +42,121 @@ fn atom_pat(p: &mut Parser<'_>, recovery_set: TokenSet) -> Option<CompletedMarke
     Some(m)
 }
 
+/// Parses a pattern that starts with a path, e.g. `Foo { a, b }` or `Foo(a, b)`
+fn path_pat(p: &mut Parser<'_>) -> CompletedMarker {
+    assert!(paths::is_path_start(p));
+    let m = p.start();
+    paths::expr_path(p);
+    match p.current() {
+        T!['{'] => {
+            record_field_pat_list(p);
+            m.complete(p, RECORD_PAT)
+        }
+        T!['('] => {
+            tuple_pat_fields(p);
+            m.complete(p, TUPLE_STRUCT_PAT)
+        }
+        _ => {
+            p.error("expected `{` or `(`");
+            m.complete(p, RECORD_PAT)
+        }
+    }
+}
+
+fn record_field_pat_list(p: &mut Parser<'_>) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        match p.current() {
+            _ if p.at(T![..]) => {
+                p.bump(T![..]);
+                if !p.at(T!['}']) {
+                    p.error("`..` must be at the end of the field list");
+                }
+            }
+            IDENT if p.nth(1) == T![:] => {
+                let m = p.start();
+                name_ref(p);
+                p.bump(T![:]);
+                pattern(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            IDENT | T![mut] => {
+                let m = p.start();
+                bind_pat(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            _ => p.error_and_bump("expected an identifier"),
+        }
+        if !p.at(T!['}']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, RECORD_FIELD_PAT_LIST);
+}
+
+fn tuple_pat_fields(p: &mut Parser<'_>) {
+    assert!(p.at(T!['(']));
+    p.bump(T!['(']);
+    while !p.at(EOF) && !p.at(T![')']) {
+        if p.at(T![..]) {
+            p.bump(T![..]);
+            if !p.at(T![')']) {
+                p.error("`..` must be at the end of the field list");
+            }
+        } else {
+            pattern(p);
+        }
+        if !p.at(T![')']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![')']);
+}
+
+/// Parses a parenthesized pattern, e.g. `(a)`, or a tuple pattern, e.g. `()`,
+/// `(a,)` or `(a, b, ..)`
+fn paren_or_tuple_pat(p: &mut Parser<'_>) -> CompletedMarker {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+
+    let mut saw_pat = false;
+    let mut saw_comma = false;
+    let mut saw_rest = false;
+    while !p.at(EOF) && !p.at(T![')']) {
+        saw_pat = true;
+        if p.at(T![..]) {
+            saw_rest = true;
+            p.bump(T![..]);
+            if !p.at(T![')']) {
+                p.error("`..` must be at the end of the field list");
+            }
+        } else {
+            pattern(p);
+        }
+
+        if !p.at(T![')']) {
+            if !p.expect(T![,]) {
+                break;
+            }
+            saw_comma = true;
+        }
+    }
+    p.expect(T![')']);
+
+    m.complete(
+        p,
+        if saw_pat && !saw_comma && !saw_rest {
+            PAREN_PAT
+        } else {
+            TUPLE_PAT
+        },
+    )
+}
+
 fn placeholder_pat(p: &mut Parser<'_>) -> CompletedMarker {
     assert!(p.at(T![_]));
     let m = p.start();
@@ -38,8 +164,10 @@ fn placeholder_pat(p: &mut Parser<'_>) -> CompletedMarker {
     m.complete(p, PLACEHOLDER_PAT)
 }
 
+/// Parses a binding, e.g. `a` or `mut a`
 fn bind_pat(p: &mut Parser<'_>) -> CompletedMarker {
     let m = p.start();
+    p.eat(T![mut]);
     name(p);
     m.complete(p, BIND_PAT)
 }
@@ -15,7 +15,7 @@ pub(super) fn impl_(p: &mut Parser<'_>, m: Marker) {
     m.complete(p, IMPL);
 }
 
-fn associated_item_list(p: &mut Parser<'_>) {
+pub(super) fn associated_item_list(p: &mut Parser<'_>) {
     assert!(p.at(T!['{']));
     let m = p.start();
     p.bump(T!['{']);
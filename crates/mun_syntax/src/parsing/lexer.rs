@@ -12,8 +12,8 @@ use self::{
     strings::scan_string,
 };
 use crate::{
-    SyntaxKind::{self, ERROR, IDENT, NEQ, STRING, UNDERSCORE, WHITESPACE},
-    TextSize,
+    SyntaxKind::{self, DOT, ERROR, IDENT, LABEL_NAME, NEQ, STRING, UNDERSCORE, WHITESPACE},
+    TextSize, T,
 };
 
 /// A token of Mun source
@@ -29,9 +29,23 @@ pub struct Token {
 /// Break a string up into its component tokens
 pub fn tokenize(text: &str) -> Vec<Token> {
     let mut text = text;
-    let mut result = Vec::new();
+    let mut result: Vec<Token> = Vec::new();
     while !text.is_empty() {
-        let token = next_token(text);
+        // A dot directly following another dot is part of a range operator (e.g. `0..10`),
+        // it never starts a tuple index.
+        let token = if text.starts_with('.') && result.last().is_some_and(|t| t.kind == DOT) {
+            Token {
+                kind: DOT,
+                len: TextSize::from(1),
+            }
+        } else if let Some(len) = scan_label(text, follows_break_or_continue(&result)) {
+            Token {
+                kind: LABEL_NAME,
+                len,
+            }
+        } else {
+            next_token(text)
+        };
         result.push(token);
         let len: u32 = token.len.into();
         text = &text[len as usize..];
@@ -39,6 +53,45 @@ pub fn tokenize(text: &str) -> Vec<Token> {
     result
 }
 
+/// Returns true if the last significant token is the `break` or `continue`
+/// keyword.
+fn follows_break_or_continue(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .rev()
+        .find(|t| !t.kind.is_trivia())
+        .is_some_and(|t| matches!(t.kind, T![break] | T![continue]))
+}
+
+/// Scans the name of a loop label (e.g. `'outer`) at the start of `text` and
+/// returns its length. Since strings can also be single-quoted, a label is
+/// only recognized directly after a `break` or `continue`, or when it is followed by a colon
+/// and a loop.
+fn scan_label(text: &str, after_jump: bool) -> Option<TextSize> {
+    let name = text.strip_prefix('\'')?;
+    if !name.starts_with(is_ident_start) {
+        return None;
+    }
+    let rest = name.trim_start_matches(is_ident_continue);
+    if rest.starts_with('\'') {
+        return None;
+    }
+
+    let is_label = after_jump || {
+        rest.strip_prefix(':')
+            .filter(|rest| !rest.starts_with(':'))
+            .map(|rest| rest.trim_start_matches(is_whitespace))
+            .is_some_and(|rest| {
+                ["loop", "while", "for"].iter().any(|keyword| {
+                    rest.strip_prefix(keyword)
+                        .is_some_and(|rest| !rest.starts_with(is_ident_continue))
+                })
+            })
+    };
+
+    is_label.then(|| TextSize::of(text) - TextSize::of(rest))
+}
+
 /// Get the next token from a string
 pub fn next_token(text: &str) -> Token {
     assert!(!text.is_empty());
@@ -1,7 +1,10 @@
 use unicode_xid::UnicodeXID;
 
 pub fn is_whitespace(c: char) -> bool {
-    c.is_whitespace()
+    match c {
+        ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c' => true,
+        c => c > '\x7f' && c.is_whitespace(),
+    }
 }
 
 pub fn is_ident_start(c: char) -> bool {
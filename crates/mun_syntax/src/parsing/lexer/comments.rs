@@ -5,7 +5,7 @@ use crate::{
 
 pub(crate) fn scan_comment(cursor: &mut Cursor<'_>) -> Option<SyntaxKind> {
     if cursor.matches('/') {
-        bump_until_eol(cursor);
+        cursor.bump_until_eol();
         Some(COMMENT)
     } else {
         scan_block_comment(cursor)
@@ -16,7 +16,8 @@ fn scan_block_comment(cursor: &mut Cursor<'_>) -> Option<SyntaxKind> {
     if cursor.matches('*') {
         cursor.bump();
         let mut depth: u32 = 1;
-        while depth > 0 {
+        // Skip directly to the next character that could open or close a nested comment
+        while depth > 0 && cursor.bump_until_either(b'*', b'/') {
             if cursor.matches_str("*/") {
                 depth -= 1;
                 cursor.bump();
@@ -25,8 +26,8 @@ fn scan_block_comment(cursor: &mut Cursor<'_>) -> Option<SyntaxKind> {
                 depth += 1;
                 cursor.bump();
                 cursor.bump();
-            } else if cursor.bump().is_none() {
-                break;
+            } else {
+                cursor.bump();
             }
         }
         Some(COMMENT)
@@ -34,14 +35,3 @@ fn scan_block_comment(cursor: &mut Cursor<'_>) -> Option<SyntaxKind> {
         None
     }
 }
-
-fn bump_until_eol(cursor: &mut Cursor<'_>) {
-    loop {
-        if cursor.matches('\n') || cursor.matches_str("\r\n") {
-            return;
-        }
-        if cursor.bump().is_none() {
-            return;
-        }
-    }
-}
@@ -3,28 +3,32 @@ use std::str::Chars;
 use crate::TextSize;
 
 /// A simple view into the characters of a string.
+///
+/// Most Mun source code is ASCII, so the methods of the cursor check for an
+/// ASCII byte before falling back to UTF-8 decoding.
 pub(crate) struct Cursor<'s> {
     text: &'s str,
-    len: TextSize,
+    pos: usize,
 }
 
 impl<'s> Cursor<'s> {
     /// Creates a new `Cursor` from a string.
     pub fn new(text: &'s str) -> Cursor<'s> {
-        Cursor {
-            text,
-            len: 0.into(),
-        }
+        Cursor { text, pos: 0 }
     }
 
     /// Gets the length of the remaining string.
     pub fn into_len(self) -> TextSize {
-        self.len
+        TextSize::try_from(self.pos).expect("token length does not fit in a `TextSize`")
     }
 
     /// Gets the current character, if one exists
     pub fn current(&self) -> Option<char> {
-        self.chars().next()
+        match self.rest().first() {
+            Some(&b) if b.is_ascii() => Some(b as char),
+            Some(_) => self.chars().next(),
+            None => None,
+        }
     }
 
     /// Gets the nth character from the current offset. For example, 0 will
@@ -35,13 +39,16 @@ impl<'s> Cursor<'s> {
 
     /// Checks whether the current character is the specified character.
     pub fn matches(&self, c: char) -> bool {
-        self.current() == Some(c)
+        if c.is_ascii() {
+            self.rest().first() == Some(&(c as u8))
+        } else {
+            self.current() == Some(c)
+        }
     }
 
     /// Checks whether the current characters match the specified string.
     pub fn matches_str(&self, s: &str) -> bool {
-        let chars = self.chars();
-        chars.as_str().starts_with(s)
+        self.rest().starts_with(s.as_bytes())
     }
 
     //    /// Checks whether the current character satisfies the specified predicate
@@ -56,33 +63,75 @@ impl<'s> Cursor<'s> {
 
     /// Move to the next character
     pub fn bump(&mut self) -> Option<char> {
-        let ch = self.chars().next()?;
-        self.len += TextSize::of(ch);
+        let ch = self.current()?;
+        self.pos += ch.len_utf8();
         Some(ch)
     }
 
     /// Moves to the next character as long as `predicate` is satisfied.
     pub fn bump_while<F: Fn(char) -> bool>(&mut self, predicate: F) {
-        loop {
-            match self.current() {
-                Some(c) if predicate(c) => {
-                    self.bump();
-                }
-                _ => return,
+        while let Some(c) = self.current() {
+            if !predicate(c) {
+                return;
             }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Moves to the first occurrence of `byte`, or to the end of the text if
+    /// there is none. Returns true if `byte` was found.
+    ///
+    /// `byte` must be an ASCII character, which guarantees that the cursor
+    /// ends up on a character boundary.
+    pub fn bump_until(&mut self, byte: u8) -> bool {
+        debug_assert!(byte.is_ascii());
+        self.bump_to(memchr::memchr(byte, self.rest()))
+    }
+
+    /// Moves to the first occurrence of either `byte1` or `byte2`, or to the
+    /// end of the text if there is none. Returns true if one of the bytes was
+    /// found.
+    ///
+    /// Both bytes must be ASCII characters, which guarantees that the cursor
+    /// ends up on a character boundary.
+    pub fn bump_until_either(&mut self, byte1: u8, byte2: u8) -> bool {
+        debug_assert!(byte1.is_ascii() && byte2.is_ascii());
+        self.bump_to(memchr::memchr2(byte1, byte2, self.rest()))
+    }
+
+    /// Moves to the end of the current line, stopping before a `\n` or `\r\n`
+    /// line terminator.
+    pub fn bump_until_eol(&mut self) {
+        if self.bump_until(b'\n') && self.text.as_bytes()[..self.pos].ends_with(b"\r") {
+            self.pos -= 1;
         }
     }
 
     /// Returns the text up to the current point.
     pub fn current_token_text(&self) -> &str {
-        let len: u32 = self.len.into();
-        &self.text[..len as usize]
+        &self.text[..self.pos]
+    }
+
+    /// Moves the cursor `offset` bytes ahead, or to the end of the text if
+    /// `offset` is `None`.
+    fn bump_to(&mut self, offset: Option<usize>) -> bool {
+        if let Some(offset) = offset {
+            self.pos += offset;
+            true
+        } else {
+            self.pos = self.text.len();
+            false
+        }
+    }
+
+    /// Returns the remaining bytes.
+    fn rest(&self) -> &'s [u8] {
+        &self.text.as_bytes()[self.pos..]
     }
 
     /// Returns an iterator over the remaining characters.
     fn chars(&self) -> Chars<'_> {
-        let len: u32 = self.len.into();
-        self.text[len as usize..].chars()
+        self.text[self.pos..].chars()
     }
 }
 
@@ -165,4 +214,40 @@ mod tests {
         cursor.bump_while(|c| c != 'o');
         assert_eq!(cursor.current(), Some('o'));
     }
+
+    #[test]
+    fn test_non_ascii() {
+        let mut cursor = Cursor::new("héllo");
+        assert_eq!(cursor.bump(), Some('h'));
+        assert!(cursor.matches('é'));
+        assert_eq!(cursor.bump(), Some('é'));
+        assert_eq!(cursor.current(), Some('l'));
+        assert_eq!(cursor.into_len(), TextSize::from(3));
+    }
+
+    #[test]
+    fn test_bump_until() {
+        let mut cursor = Cursor::new("héllo\nworld");
+        assert!(cursor.bump_until(b'\n'));
+        assert_eq!(cursor.current_token_text(), "héllo");
+        assert!(!cursor.bump_until(b'x'));
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn test_bump_until_eol() {
+        let mut cursor = Cursor::new("a\rb\r\nc");
+        cursor.bump_until_eol();
+        assert_eq!(cursor.current_token_text(), "a\rb");
+    }
+
+    #[test]
+    fn test_bump_until_either() {
+        let mut cursor = Cursor::new("a é * b / c");
+        assert!(cursor.bump_until_either(b'/', b'*'));
+        assert_eq!(cursor.current(), Some('*'));
+        cursor.bump();
+        assert!(cursor.bump_until_either(b'/', b'*'));
+        assert_eq!(cursor.current(), Some('/'));
+    }
 }
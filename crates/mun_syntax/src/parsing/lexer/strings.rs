@@ -1,22 +1,17 @@
 use crate::parsing::lexer::cursor::Cursor;
 
 pub(crate) fn scan_string(c: char, cursor: &mut Cursor<'_>) {
-    let quote_type = c;
-    while let Some(c) = cursor.current() {
-        match c {
-            '\\' => {
-                cursor.bump();
-                if cursor.matches('\\') || cursor.matches(quote_type) {
-                    cursor.bump();
-                }
-            }
-            c if c == quote_type => {
-                cursor.bump();
-                return;
-            }
-            _ => {
+    let quote_type = c as u8;
+    // Skip directly to the next escape sequence or the closing quote
+    while cursor.bump_until_either(b'\\', quote_type) {
+        if cursor.matches('\\') {
+            cursor.bump();
+            if cursor.matches('\\') || cursor.matches(c) {
                 cursor.bump();
             }
+        } else {
+            cursor.bump();
+            return;
         }
     }
 }
@@ -80,7 +80,7 @@ impl<'t> Parser<'t> {
             T![|=] => self.at_composite2(n, T![|], T![=]),
             T![||] => self.at_composite2(n, T![|], T![|]),
             T![...] => self.at_composite3(n, T![.], T![.], T![.]),
-            //T![..=] => self.at_composite3(n, T![.], T![.], T![=]),
+            T![..=] => self.at_composite3(n, T![.], T![.], T![=]),
             T![<<=] => self.at_composite3(n, T![<], T![<], T![=]),
             T![>>=] => self.at_composite3(n, T![>], T![>], T![=]),
             _ => self.token_source.lookahead_nth(n).kind == kind,
@@ -212,7 +212,7 @@ impl<'t> Parser<'t> {
             => 2,
 
             T![...]
-            //| T![..=]
+            | T![..=]
             | T![<<=]
             | T![>>=]
             => 3,
@@ -0,0 +1,241 @@
+//! Implements incremental reparsing of a syntax tree.
+//!
+//! Instead of parsing an entire file again after an edit, only the smallest
+//! token or block that contains the edit is reparsed. The green nodes of the
+//! rest of the tree are reused. If the edit cannot be contained, e.g. because
+//! it unbalances the braces of a block, `None` is returned and the file must be
+//! parsed again entirely.
+
+use ra_ap_text_edit::Indel;
+use rowan::GreenToken;
+
+use super::{
+    grammar::Reparser,
+    lexer::{next_token, tokenize},
+    parse_from_tokens,
+    text_token_source::TextTokenSource,
+    text_tree_sink::TextTreeSink,
+    TokenSource,
+};
+use crate::{
+    ast,
+    syntax_node::GreenNode,
+    SyntaxElement, SyntaxError,
+    SyntaxKind::{self, COMMENT, EOF, IDENT, STRING, WHITESPACE},
+    SyntaxNode, TextRange, TextSize, T,
+};
+
+/// Reparses the smallest token or block of `root` that contains the `edit`.
+/// Returns the green node of the new root and the parse errors of the new
+/// tree. `errors` are the parse errors of `root`.
+pub(crate) fn incremental_reparse(
+    root: &SyntaxNode,
+    edit: &Indel,
+    errors: Vec<SyntaxError>,
+) -> Option<(GreenNode, Vec<SyntaxError>)> {
+    let (green, new_errors, old_range) =
+        reparse_token(root, edit).or_else(|| reparse_block(root, edit))?;
+    Some((green, merge_errors(errors, new_errors, old_range, edit)))
+}
+
+/// Reparses a single token if the edit is contained within it and the edited
+/// text still forms a single token of the same kind.
+fn reparse_token(
+    root: &SyntaxNode,
+    edit: &Indel,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
+    let prev_token = root.covering_element(edit.delete).into_token()?;
+    let prev_token_kind = prev_token.kind();
+    if !matches!(prev_token_kind, WHITESPACE | COMMENT | IDENT | STRING) {
+        return None;
+    }
+
+    // Newlines in whitespace and comments determine to which node comments are
+    // attached
+    if matches!(prev_token_kind, WHITESPACE | COMMENT) {
+        let deleted_range = edit.delete - prev_token.text_range().start();
+        if prev_token.text()[deleted_range].contains('\n') || edit.insert.contains('\n') {
+            return None;
+        }
+    }
+
+    let new_text = text_after_edit(prev_token.clone().into(), edit);
+    if lex_single_token(&new_text)? != prev_token_kind {
+        return None;
+    }
+
+    // Contextual keywords are lexed as identifiers, but the parser treats them
+    // differently
+    if prev_token_kind == IDENT
+        && (is_contextual_kw(prev_token.text()) || is_contextual_kw(&new_text))
+    {
+        return None;
+    }
+
+    // Doc comments are attached to different nodes than regular comments
+    if prev_token_kind == COMMENT
+        && ast::CommentKind::from_text(prev_token.text()) != ast::CommentKind::from_text(&new_text)
+    {
+        return None;
+    }
+
+    // Make sure that the edited token does not merge with its neighbours, e.g.
+    // removing the closing quote of a string
+    let next_char = prev_token
+        .next_token()
+        .and_then(|token| token.text().chars().next());
+    if next_char.is_some_and(|c| lex_single_token(&format!("{new_text}{c}")).is_some()) {
+        return None;
+    }
+    let prev_char = prev_token
+        .prev_token()
+        .and_then(|token| token.text().chars().next_back());
+    if prev_char.is_some_and(|c| lex_single_token(&format!("{c}{new_text}")).is_some()) {
+        return None;
+    }
+
+    let new_token = GreenToken::new(rowan::SyntaxKind(prev_token_kind.into()), &new_text);
+    Some((
+        prev_token.replace_with(new_token),
+        Vec::new(),
+        prev_token.text_range(),
+    ))
+}
+
+/// Reparses the smallest block-like node that contains the edit, if the
+/// braces of the node remain balanced.
+fn reparse_block(
+    root: &SyntaxNode,
+    edit: &Indel,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
+    let (node, reparser) = find_reparsable_node(root, edit.delete)?;
+    let text = text_after_edit(node.clone().into(), edit);
+
+    let tokens = tokenize(&text);
+    if !is_balanced(&tokens) {
+        return None;
+    }
+
+    let mut token_source = TextTokenSource::new(&text, &tokens);
+    let mut tree_sink = TextTreeSink::new(&text, &tokens);
+    parse_from_tokens(&mut token_source, &mut tree_sink, |p| reparser.parse(p));
+
+    // The parser may stop before the end of the block, e.g. when it encounters
+    // a declaration. In that case the surrounding tree is affected too.
+    if token_source.lookahead_nth(0).kind != EOF {
+        return None;
+    }
+
+    let (green, new_errors) = tree_sink.finish();
+    if green.kind() != rowan::SyntaxKind(node.kind().into()) {
+        return None;
+    }
+
+    Some((node.replace_with(green), new_errors, node.text_range()))
+}
+
+/// Returns the text of `element` after applying the `edit`.
+fn text_after_edit(element: SyntaxElement, edit: &Indel) -> String {
+    let edit = Indel::replace(
+        edit.delete - element.text_range().start(),
+        edit.insert.clone(),
+    );
+    let mut text = match element {
+        SyntaxElement::Token(token) => token.text().to_string(),
+        SyntaxElement::Node(node) => node.text().to_string(),
+    };
+    edit.apply(&mut text);
+    text
+}
+
+/// Returns the kind of the token if `text` consists of a single token.
+fn lex_single_token(text: &str) -> Option<SyntaxKind> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let token = next_token(text);
+    (token.len == TextSize::of(text)).then_some(token.kind)
+}
+
+/// Returns true if `text` is a keyword that is only treated as such in certain
+/// contexts.
+fn is_contextual_kw(text: &str) -> bool {
+    matches!(text, "gc" | "value")
+}
+
+/// Returns the closest ancestor of the `range` that can be reparsed in
+/// isolation.
+fn find_reparsable_node(root: &SyntaxNode, range: TextRange) -> Option<(SyntaxNode, Reparser)> {
+    let node = match root.covering_element(range) {
+        SyntaxElement::Node(node) => node,
+        SyntaxElement::Token(token) => token.parent()?,
+    };
+    node.ancestors()
+        .find_map(|node| Reparser::for_node(node.kind()).map(|reparser| (node, reparser)))
+}
+
+/// Returns true if the `tokens` start with a `{`, end with the matching `}`,
+/// and all other braces in between are balanced.
+fn is_balanced(tokens: &[super::lexer::Token]) -> bool {
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return false;
+    };
+    if tokens.len() < 2 || first.kind != T!['{'] || last.kind != T!['}'] {
+        return false;
+    }
+
+    let mut balance = 0usize;
+    for token in &tokens[1..tokens.len() - 1] {
+        match token.kind {
+            T!['{'] => balance += 1,
+            T!['}'] => match balance.checked_sub(1) {
+                Some(b) => balance = b,
+                None => return false,
+            },
+            _ => (),
+        }
+    }
+    balance == 0
+}
+
+/// Combines the errors of the old tree outside of the reparsed range with the
+/// errors of the reparsed range.
+fn merge_errors(
+    old_errors: Vec<SyntaxError>,
+    new_errors: Vec<SyntaxError>,
+    range_before_reparse: TextRange,
+    edit: &Indel,
+) -> Vec<SyntaxError> {
+    let inserted_len = TextSize::of(&edit.insert);
+    let deleted_len = edit.delete.len();
+
+    let mut errors: Vec<SyntaxError> = old_errors
+        .into_iter()
+        .filter_map(|error| {
+            let location = error.location();
+            if location.end_offset() <= range_before_reparse.start() {
+                Some(error)
+            } else if location.offset() >= range_before_reparse.end() {
+                Some(SyntaxError::new(
+                    error.kind(),
+                    location.add_offset(inserted_len, deleted_len),
+                ))
+            } else {
+                None
+            }
+        })
+        .chain(new_errors.into_iter().map(|error| {
+            SyntaxError::new(
+                error.kind(),
+                error
+                    .location()
+                    .add_offset(range_before_reparse.start(), 0.into()),
+            )
+        }))
+        .collect();
+
+    // Keep the errors in the same order as a full parse
+    errors.sort_by_key(|error| error.location().offset());
+    errors
+}
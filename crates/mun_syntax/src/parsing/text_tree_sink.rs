@@ -1,10 +1,14 @@
 use std::mem;
 
 use crate::{
+    ast,
     parsing::{lexer::Token, ParseError, TreeSink},
     syntax_node::GreenNode,
     SyntaxError,
-    SyntaxKind::{self, COMMENT, FUNCTION_DEF, WHITESPACE},
+    SyntaxKind::{
+        self, COMMENT, CONST_DEF, FUNCTION_DEF, IMPL, RECORD_FIELD_DEF, STATIC_DEF, STRUCT_DEF,
+        TUPLE_FIELD_DEF, TYPE_ALIAS_DEF, USE, WHITESPACE,
+    },
     SyntaxTreeBuilder, TextRange, TextSize,
 };
 
@@ -58,13 +62,32 @@ impl TreeSink for TextTreeSink<'_> {
         let mut trivia_end =
             self.text_pos + leading_trivias.iter().map(|it| it.len).sum::<TextSize>();
 
+        // Trivias on the same line as the previous token (e.g. a trailing comment) belong to
+        // that token and are never attached to the node.
+        let n_same_line_trivias = if self.text_pos == 0.into() {
+            0
+        } else {
+            let mut trivia_start = self.text_pos;
+            leading_trivias
+                .iter()
+                .take_while(|it| {
+                    let range = TextRange::at(trivia_start, it.len);
+                    trivia_start += it.len;
+                    !(it.kind == WHITESPACE && self.text[range].contains('\n'))
+                })
+                .count()
+        };
+
         let n_attached_trivias = {
-            let leading_trivias = leading_trivias.iter().rev().map(|it| {
-                let next_end = trivia_end - it.len;
-                let range = TextRange::new(next_end, trivia_end);
-                trivia_end = next_end;
-                (it.kind, &self.text[range])
-            });
+            let leading_trivias = leading_trivias[n_same_line_trivias..]
+                .iter()
+                .rev()
+                .map(|it| {
+                    let next_end = trivia_end - it.len;
+                    let range = TextRange::new(next_end, trivia_end);
+                    trivia_end = next_end;
+                    (it.kind, &self.text[range])
+                });
             n_attached_trivias(kind, leading_trivias)
         };
         self.eat_n_trivias(n_trivias - n_attached_trivias);
@@ -136,19 +159,47 @@ impl<'a> TextTreeSink<'a> {
 }
 
 /// This method counts the number of preceding trivias that should be attached
-/// to the to node of the given kind.
+/// to the to node of the given kind. The trivias are iterated from the node
+/// backwards.
+///
+/// Comments directly preceding a declaration are attached to it. A blank line
+/// separates comments from the declaration, unless the comment is a doc
+/// comment. Inner doc comments (e.g. `//!`) document the enclosing item and
+/// are never attached.
 fn n_attached_trivias<'a>(
-    kind: SyntaxKind,
+    node_kind: SyntaxKind,
     trivias: impl Iterator<Item = (SyntaxKind, &'a str)>,
 ) -> usize {
-    match kind {
-        FUNCTION_DEF => trivias
-            .take_while(|(kind, text)| match kind {
-                WHITESPACE => !text.contains("\n\n"),
-                COMMENT => true,
-                _ => unreachable!(),
-            })
-            .count(),
+    match node_kind {
+        FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF | CONST_DEF | STATIC_DEF | IMPL | USE
+        | RECORD_FIELD_DEF | TUPLE_FIELD_DEF => {
+            let mut result = 0;
+            let mut trivias = trivias.enumerate().peekable();
+            while let Some((idx, (kind, text))) = trivias.next() {
+                match kind {
+                    WHITESPACE if text.contains("\n\n") => {
+                        // Doc comments separated by a blank line are still attached
+                        match trivias.peek() {
+                            Some((_, (COMMENT, text)))
+                                if ast::CommentKind::from_text(text).doc
+                                    == Some(ast::CommentPlacement::Outer) => {}
+                            _ => break,
+                        }
+                    }
+                    COMMENT
+                        if ast::CommentKind::from_text(text).doc
+                            == Some(ast::CommentPlacement::Inner) =>
+                    {
+                        break
+                    }
+                    COMMENT => result = idx + 1,
+                    // Functions have always included their leading whitespace
+                    _ if node_kind == FUNCTION_DEF => result = idx + 1,
+                    _ => (),
+                }
+            }
+            result
+        }
         _ => 0,
     }
 }
@@ -86,6 +86,7 @@ pub enum SyntaxKind {
     TRUE_KW,
     WHILE_KW,
     LOOP_KW,
+    CONTINUE_KW,
     LET_KW,
     MUT_KW,
     CLASS_KW,
@@ -98,12 +99,15 @@ pub enum SyntaxKind {
     SELF_KW,
     EXTERN_KW,
     IMPL_KW,
+    CONST_KW,
+    STATIC_KW,
     INT_NUMBER,
     FLOAT_NUMBER,
     STRING,
     ERROR,
     IDENT,
     INDEX,
+    LABEL_NAME,
     WHITESPACE,
     COMMENT,
     GC_KW,
@@ -118,6 +122,8 @@ pub enum SyntaxKind {
     SELF_PARAM,
     STRUCT_DEF,
     TYPE_ALIAS_DEF,
+    CONST_DEF,
+    STATIC_DEF,
     MEMORY_TYPE_SPECIFIER,
     RECORD_FIELD_DEF_LIST,
     RECORD_FIELD_DEF,
@@ -133,6 +139,7 @@ pub enum SyntaxKind {
     LITERAL,
     BIN_EXPR,
     PAREN_EXPR,
+    TUPLE_EXPR,
     CALL_EXPR,
     METHOD_CALL_EXPR,
     FIELD_EXPR,
@@ -142,11 +149,21 @@ pub enum SyntaxKind {
     RETURN_EXPR,
     WHILE_EXPR,
     LOOP_EXPR,
+    FOR_EXPR,
     BREAK_EXPR,
+    CONTINUE_EXPR,
+    LABEL,
     ARRAY_EXPR,
+    RANGE_EXPR,
     CONDITION,
     BIND_PAT,
     PLACEHOLDER_PAT,
+    RECORD_PAT,
+    RECORD_FIELD_PAT_LIST,
+    RECORD_FIELD_PAT,
+    TUPLE_STRUCT_PAT,
+    TUPLE_PAT,
+    PAREN_PAT,
     ARG_LIST,
     NAME,
     NAME_REF,
@@ -155,6 +172,8 @@ pub enum SyntaxKind {
     RECORD_LIT,
     RECORD_FIELD_LIST,
     RECORD_FIELD,
+    ATTR,
+    TOKEN_TREE,
     USE,
     USE_TREE,
     USE_TREE_LIST,
@@ -356,6 +375,9 @@ macro_rules! T {
     (loop) => {
         $crate::SyntaxKind::LOOP_KW
     };
+    (continue) => {
+        $crate::SyntaxKind::CONTINUE_KW
+    };
     (let) => {
         $crate::SyntaxKind::LET_KW
     };
@@ -392,6 +414,12 @@ macro_rules! T {
     (impl) => {
         $crate::SyntaxKind::IMPL_KW
     };
+    (const) => {
+        $crate::SyntaxKind::CONST_KW
+    };
+    (static) => {
+        $crate::SyntaxKind::STATIC_KW
+    };
 }
 
 impl From<u16> for SyntaxKind {
@@ -426,6 +454,7 @@ impl SyntaxKind {
         | TRUE_KW
         | WHILE_KW
         | LOOP_KW
+        | CONTINUE_KW
         | LET_KW
         | MUT_KW
         | CLASS_KW
@@ -438,6 +467,8 @@ impl SyntaxKind {
         | SELF_KW
         | EXTERN_KW
         | IMPL_KW
+        | CONST_KW
+        | STATIC_KW
         )
     }
 
@@ -568,6 +599,7 @@ impl SyntaxKind {
             TRUE_KW => &SyntaxInfo { name: "TRUE_KW" },
             WHILE_KW => &SyntaxInfo { name: "WHILE_KW" },
             LOOP_KW => &SyntaxInfo { name: "LOOP_KW" },
+            CONTINUE_KW => &SyntaxInfo { name: "CONTINUE_KW" },
             LET_KW => &SyntaxInfo { name: "LET_KW" },
             MUT_KW => &SyntaxInfo { name: "MUT_KW" },
             CLASS_KW => &SyntaxInfo { name: "CLASS_KW" },
@@ -580,12 +612,15 @@ impl SyntaxKind {
             SELF_KW => &SyntaxInfo { name: "SELF_KW" },
             EXTERN_KW => &SyntaxInfo { name: "EXTERN_KW" },
             IMPL_KW => &SyntaxInfo { name: "IMPL_KW" },
+            CONST_KW => &SyntaxInfo { name: "CONST_KW" },
+            STATIC_KW => &SyntaxInfo { name: "STATIC_KW" },
             INT_NUMBER => &SyntaxInfo { name: "INT_NUMBER" },
             FLOAT_NUMBER => &SyntaxInfo { name: "FLOAT_NUMBER" },
             STRING => &SyntaxInfo { name: "STRING" },
             ERROR => &SyntaxInfo { name: "ERROR" },
             IDENT => &SyntaxInfo { name: "IDENT" },
             INDEX => &SyntaxInfo { name: "INDEX" },
+            LABEL_NAME => &SyntaxInfo { name: "LABEL_NAME" },
             WHITESPACE => &SyntaxInfo { name: "WHITESPACE" },
             COMMENT => &SyntaxInfo { name: "COMMENT" },
             GC_KW => &SyntaxInfo { name: "GC_KW" },
@@ -600,6 +635,8 @@ impl SyntaxKind {
             SELF_PARAM => &SyntaxInfo { name: "SELF_PARAM" },
             STRUCT_DEF => &SyntaxInfo { name: "STRUCT_DEF" },
             TYPE_ALIAS_DEF => &SyntaxInfo { name: "TYPE_ALIAS_DEF" },
+            CONST_DEF => &SyntaxInfo { name: "CONST_DEF" },
+            STATIC_DEF => &SyntaxInfo { name: "STATIC_DEF" },
             MEMORY_TYPE_SPECIFIER => &SyntaxInfo { name: "MEMORY_TYPE_SPECIFIER" },
             RECORD_FIELD_DEF_LIST => &SyntaxInfo { name: "RECORD_FIELD_DEF_LIST" },
             RECORD_FIELD_DEF => &SyntaxInfo { name: "RECORD_FIELD_DEF" },
@@ -615,6 +652,7 @@ impl SyntaxKind {
             LITERAL => &SyntaxInfo { name: "LITERAL" },
             BIN_EXPR => &SyntaxInfo { name: "BIN_EXPR" },
             PAREN_EXPR => &SyntaxInfo { name: "PAREN_EXPR" },
+            TUPLE_EXPR => &SyntaxInfo { name: "TUPLE_EXPR" },
             CALL_EXPR => &SyntaxInfo { name: "CALL_EXPR" },
             METHOD_CALL_EXPR => &SyntaxInfo { name: "METHOD_CALL_EXPR" },
             FIELD_EXPR => &SyntaxInfo { name: "FIELD_EXPR" },
@@ -624,11 +662,21 @@ impl SyntaxKind {
             RETURN_EXPR => &SyntaxInfo { name: "RETURN_EXPR" },
             WHILE_EXPR => &SyntaxInfo { name: "WHILE_EXPR" },
             LOOP_EXPR => &SyntaxInfo { name: "LOOP_EXPR" },
+            FOR_EXPR => &SyntaxInfo { name: "FOR_EXPR" },
             BREAK_EXPR => &SyntaxInfo { name: "BREAK_EXPR" },
+            CONTINUE_EXPR => &SyntaxInfo { name: "CONTINUE_EXPR" },
+            LABEL => &SyntaxInfo { name: "LABEL" },
             ARRAY_EXPR => &SyntaxInfo { name: "ARRAY_EXPR" },
+            RANGE_EXPR => &SyntaxInfo { name: "RANGE_EXPR" },
             CONDITION => &SyntaxInfo { name: "CONDITION" },
             BIND_PAT => &SyntaxInfo { name: "BIND_PAT" },
             PLACEHOLDER_PAT => &SyntaxInfo { name: "PLACEHOLDER_PAT" },
+            RECORD_PAT => &SyntaxInfo { name: "RECORD_PAT" },
+            RECORD_FIELD_PAT_LIST => &SyntaxInfo { name: "RECORD_FIELD_PAT_LIST" },
+            RECORD_FIELD_PAT => &SyntaxInfo { name: "RECORD_FIELD_PAT" },
+            TUPLE_STRUCT_PAT => &SyntaxInfo { name: "TUPLE_STRUCT_PAT" },
+            TUPLE_PAT => &SyntaxInfo { name: "TUPLE_PAT" },
+            PAREN_PAT => &SyntaxInfo { name: "PAREN_PAT" },
             ARG_LIST => &SyntaxInfo { name: "ARG_LIST" },
             NAME => &SyntaxInfo { name: "NAME" },
             NAME_REF => &SyntaxInfo { name: "NAME_REF" },
@@ -637,6 +685,8 @@ impl SyntaxKind {
             RECORD_LIT => &SyntaxInfo { name: "RECORD_LIT" },
             RECORD_FIELD_LIST => &SyntaxInfo { name: "RECORD_FIELD_LIST" },
             RECORD_FIELD => &SyntaxInfo { name: "RECORD_FIELD" },
+            ATTR => &SyntaxInfo { name: "ATTR" },
+            TOKEN_TREE => &SyntaxInfo { name: "TOKEN_TREE" },
             USE => &SyntaxInfo { name: "USE" },
             USE_TREE => &SyntaxInfo { name: "USE_TREE" },
             USE_TREE_LIST => &SyntaxInfo { name: "USE_TREE_LIST" },
@@ -667,6 +717,7 @@ impl SyntaxKind {
             "true" => TRUE_KW,
             "while" => WHILE_KW,
             "loop" => LOOP_KW,
+            "continue" => CONTINUE_KW,
             "let" => LET_KW,
             "mut" => MUT_KW,
             "class" => CLASS_KW,
@@ -679,6 +730,8 @@ impl SyntaxKind {
             "self" => SELF_KW,
             "extern" => EXTERN_KW,
             "impl" => IMPL_KW,
+            "const" => CONST_KW,
+            "static" => STATIC_KW,
             _ => return None,
         };
         Some(kw)
@@ -0,0 +1,354 @@
+//! Primitive tree editor, ted for short.
+//!
+//! Rowan syntax trees are immutable by default, but a tree can be made
+//! mutable by calling [`SyntaxNode::clone_for_update`] (or
+//! [`crate::AstNode::clone_for_update`]) on it. The functions in this module edit
+//! such a mutable tree in place. Code actions, refactorings and the formatter
+//! use them to produce edits without manipulating source text directly.
+//!
+//! The functions without a `_raw` suffix keep the result readable: they insert
+//! whitespace between elements that would otherwise be glued together, put
+//! items and statements on their own line with the indentation of their
+//! surroundings, and remove the whitespace that a removed element leaves
+//! behind.
+
+use std::ops::RangeInclusive;
+
+use crate::{SourceFile, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, T};
+
+/// The number of spaces that a nested block is indented by.
+const INDENT: &str = "    ";
+
+/// Something that can be inserted into or removed from a syntax tree.
+pub trait Element {
+    fn syntax_element(self) -> SyntaxElement;
+}
+
+impl<E: Element + Clone> Element for &'_ E {
+    fn syntax_element(self) -> SyntaxElement {
+        self.clone().syntax_element()
+    }
+}
+
+impl Element for SyntaxElement {
+    fn syntax_element(self) -> SyntaxElement {
+        self
+    }
+}
+
+impl Element for SyntaxNode {
+    fn syntax_element(self) -> SyntaxElement {
+        self.into()
+    }
+}
+
+impl Element for SyntaxToken {
+    fn syntax_element(self) -> SyntaxElement {
+        self.into()
+    }
+}
+
+/// A location in a syntax tree at which elements can be inserted.
+#[derive(Debug)]
+pub struct Position {
+    repr: PositionRepr,
+}
+
+#[derive(Debug)]
+enum PositionRepr {
+    FirstChild(SyntaxNode),
+    After(SyntaxElement),
+}
+
+impl Position {
+    /// The position directly after `elem`.
+    pub fn after(elem: impl Element) -> Position {
+        let repr = PositionRepr::After(elem.syntax_element());
+        Position { repr }
+    }
+
+    /// The position directly before `elem`.
+    pub fn before(elem: impl Element) -> Position {
+        let elem = elem.syntax_element();
+        let repr = match elem.prev_sibling_or_token() {
+            Some(it) => PositionRepr::After(it),
+            None => PositionRepr::FirstChild(elem.parent().unwrap()),
+        };
+        Position { repr }
+    }
+
+    /// The position before the first child of `node`.
+    pub fn first_child_of(node: &(impl Into<SyntaxNode> + Clone)) -> Position {
+        let repr = PositionRepr::FirstChild(node.clone().into());
+        Position { repr }
+    }
+
+    /// The position after the last child of `node`.
+    pub fn last_child_of(node: &(impl Into<SyntaxNode> + Clone)) -> Position {
+        let node = node.clone().into();
+        let repr = match node.last_child_or_token() {
+            Some(it) => PositionRepr::After(it),
+            None => PositionRepr::FirstChild(node),
+        };
+        Position { repr }
+    }
+
+    /// Returns the element that precedes the position, if any.
+    fn prev(&self) -> Option<SyntaxElement> {
+        match &self.repr {
+            PositionRepr::FirstChild(_) => None,
+            PositionRepr::After(it) => Some(it.clone()),
+        }
+    }
+
+    /// Returns the element that follows the position, if any.
+    fn next(&self) -> Option<SyntaxElement> {
+        match &self.repr {
+            PositionRepr::FirstChild(parent) => parent.first_child_or_token(),
+            PositionRepr::After(it) => it.next_sibling_or_token(),
+        }
+    }
+}
+
+/// Inserts `elem` at `position`, adding whitespace around it if required.
+pub fn insert(position: Position, elem: impl Element) {
+    insert_all(position, vec![elem.syntax_element()]);
+}
+
+/// Inserts `elem` at `position` as is.
+pub fn insert_raw(position: Position, elem: impl Element) {
+    insert_all_raw(position, vec![elem.syntax_element()]);
+}
+
+/// Inserts `elements` at `position`, adding whitespace before the first and
+/// after the last element if required.
+pub fn insert_all(position: Position, mut elements: Vec<SyntaxElement>) {
+    if let Some(first) = elements.first() {
+        if let Some(ws) = position.prev().and_then(|prev| ws_between(&prev, first)) {
+            elements.insert(0, ws.into());
+        }
+    }
+    if let Some(last) = elements.last() {
+        if let Some(ws) = position.next().and_then(|next| ws_between(last, &next)) {
+            elements.push(ws.into());
+        }
+    }
+    insert_all_raw(position, elements);
+}
+
+/// Inserts `elements` at `position` as is.
+pub fn insert_all_raw(position: Position, elements: Vec<SyntaxElement>) {
+    let (parent, index) = match position.repr {
+        PositionRepr::FirstChild(parent) => (parent, 0),
+        PositionRepr::After(child) => (child.parent().unwrap(), child.index() + 1),
+    };
+    parent.splice_children(index..index, elements);
+}
+
+/// Removes `elem` from the tree, together with the whitespace that would
+/// otherwise be left behind.
+pub fn remove(elem: impl Element) {
+    let elem = elem.syntax_element();
+    remove_all(elem.clone()..=elem);
+}
+
+/// Removes all siblings from the first to the last element of `range`, together
+/// with the whitespace that would otherwise be left behind.
+pub fn remove_all(range: RangeInclusive<SyntaxElement>) {
+    let (first, last) = range.into_inner();
+    let prev = first.prev_sibling_or_token();
+    let next = last.next_sibling_or_token();
+    remove_all_raw(first..=last);
+
+    match (prev, next) {
+        // Keep the whitespace that follows, it has the correct indentation for the
+        // next element. Unless it only separates the delimiters of an empty list.
+        (Some(prev), Some(next)) if is_whitespace(&prev) && is_whitespace(&next) => {
+            remove_raw(prev);
+            let is_empty_list = next
+                .prev_sibling_or_token()
+                .is_some_and(|it| is_opening_delimiter(&it))
+                && next
+                    .next_sibling_or_token()
+                    .is_some_and(|it| is_closing_delimiter(&it));
+            if is_empty_list {
+                remove_raw(next);
+            }
+        }
+        (prev, Some(next))
+            if is_whitespace(&next) && prev.as_ref().map_or(true, is_opening_delimiter) =>
+        {
+            remove_raw(next);
+        }
+        (Some(prev), next)
+            if is_whitespace(&prev) && next.as_ref().map_or(true, is_closing_delimiter) =>
+        {
+            remove_raw(prev);
+        }
+        _ => (),
+    }
+}
+
+/// Removes `elem` from the tree as is.
+pub fn remove_raw(elem: impl Element) {
+    elem.syntax_element().detach();
+}
+
+/// Removes all siblings from the first to the last element of `range` as is.
+pub fn remove_all_raw(range: RangeInclusive<SyntaxElement>) {
+    replace_all(range, Vec::new());
+}
+
+/// Removes all elements of `iter`, together with the whitespace that would
+/// otherwise be left behind.
+pub fn remove_all_iter(iter: impl IntoIterator<Item = impl Element>) {
+    let elements: Vec<_> = iter.into_iter().map(Element::syntax_element).collect();
+    for elem in elements {
+        remove(elem);
+    }
+}
+
+/// Replaces `old` with `new`.
+pub fn replace(old: impl Element, new: impl Element) {
+    replace_with_many(old, vec![new.syntax_element()]);
+}
+
+/// Replaces `old` with all elements of `new`.
+pub fn replace_with_many(old: impl Element, new: Vec<SyntaxElement>) {
+    let old = old.syntax_element();
+    replace_all(old.clone()..=old, new);
+}
+
+/// Replaces all siblings from the first to the last element of `range` with
+/// `new`.
+pub fn replace_all(range: RangeInclusive<SyntaxElement>, new: Vec<SyntaxElement>) {
+    let start = range.start().index();
+    let end = range.end().index();
+    let parent = range.start().parent().unwrap();
+    parent.splice_children(start..end + 1, new);
+}
+
+/// Appends `child` to the children of `node`, adding whitespace before it if
+/// required.
+pub fn append_child(node: &(impl Into<SyntaxNode> + Clone), child: impl Element) {
+    insert(Position::last_child_of(node), child);
+}
+
+/// Appends `child` to the children of `node` as is.
+pub fn append_child_raw(node: &(impl Into<SyntaxNode> + Clone), child: impl Element) {
+    insert_raw(Position::last_child_of(node), child);
+}
+
+/// Prepends `child` to the children of `node`, adding whitespace after it if
+/// required.
+pub fn prepend_child(node: &(impl Into<SyntaxNode> + Clone), child: impl Element) {
+    insert(Position::first_child_of(node), child);
+}
+
+/// Returns a new, mutable whitespace token with the specified text.
+pub fn whitespace(text: &str) -> SyntaxToken {
+    assert!(
+        !text.is_empty() && text.trim().is_empty(),
+        "`{text}` is not whitespace"
+    );
+    SourceFile::parse(text)
+        .syntax_node()
+        .clone_for_update()
+        .first_token()
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+        .unwrap()
+}
+
+/// Returns the whitespace that should separate `left` and `right`, if any.
+fn ws_between(left: &SyntaxElement, right: &SyntaxElement) -> Option<SyntaxToken> {
+    if is_whitespace(left) || is_whitespace(right) {
+        return None;
+    }
+
+    // Items and statements are placed on their own line
+    if is_line_element(right.kind()) {
+        let indent = if left.kind() == T!['{'] {
+            format!("{}{INDENT}", indent_of_line(left))
+        } else {
+            indent_of_line(left)
+        };
+        let separator = if is_item(left.kind()) && is_item(right.kind()) {
+            "\n\n"
+        } else {
+            "\n"
+        };
+        return Some(whitespace(&format!("{separator}{indent}")));
+    }
+    if is_line_element(left.kind()) {
+        let indent = if right.kind() == T!['}'] {
+            indent_of_line(right)
+        } else {
+            indent_of_line(left)
+        };
+        return Some(whitespace(&format!("\n{indent}")));
+    }
+
+    if matches!(left.kind(), T!['('] | T!['['] | T![.] | T![::])
+        || matches!(
+            right.kind(),
+            T![')'] | T![']'] | T![.] | T![::] | T![,] | T![;] | T![:]
+        )
+    {
+        return None;
+    }
+
+    Some(whitespace(" "))
+}
+
+/// Returns the indentation of the line on which `elem` starts.
+fn indent_of_line(elem: &SyntaxElement) -> String {
+    let first_token = match elem {
+        SyntaxElement::Node(node) => node.first_token(),
+        SyntaxElement::Token(token) => Some(token.clone()),
+    };
+    let mut token = first_token.and_then(|it| it.prev_token());
+    while let Some(current) = token {
+        if current.kind() == SyntaxKind::WHITESPACE {
+            if let Some((_, indent)) = current.text().rsplit_once('\n') {
+                return indent.to_owned();
+            }
+        }
+        token = current.prev_token();
+    }
+    String::new()
+}
+
+/// Returns true if `kind` is placed on a line of its own.
+fn is_line_element(kind: SyntaxKind) -> bool {
+    is_item(kind)
+        || matches!(
+            kind,
+            SyntaxKind::USE | SyntaxKind::LET_STMT | SyntaxKind::EXPR_STMT
+        )
+}
+
+/// Returns true if `kind` is an item that is separated from other items by an
+/// empty line.
+fn is_item(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::FUNCTION_DEF
+            | SyntaxKind::STRUCT_DEF
+            | SyntaxKind::TYPE_ALIAS_DEF
+            | SyntaxKind::CONST_DEF
+            | SyntaxKind::STATIC_DEF
+            | SyntaxKind::IMPL
+    )
+}
+
+fn is_whitespace(elem: &SyntaxElement) -> bool {
+    elem.kind() == SyntaxKind::WHITESPACE
+}
+
+fn is_opening_delimiter(elem: &SyntaxElement) -> bool {
+    matches!(elem.kind(), T!['('] | T!['['] | T!['{'])
+}
+
+fn is_closing_delimiter(elem: &SyntaxElement) -> bool {
+    matches!(elem.kind(), T![')'] | T![']'] | T!['}'])
+}
@@ -1,2 +1,5 @@
 mod lexer;
 mod parser;
+mod reparsing;
+mod ted;
+mod trivia;
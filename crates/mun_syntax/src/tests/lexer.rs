@@ -73,6 +73,22 @@ fn comments() {
     "#);
 }
 
+#[test]
+fn non_ascii() {
+    insta::assert_snapshot!(dump_text_tokens(
+        "// ünïcödé comment\r\n/* ∑ /* ∏ */ */ \"π ≈ 3.14\" ünïcödé\u{a0}x"), @r#"
+    COMMENT 22 "// ünïcödé comment"
+    WHITESPACE 2 "\r\n"
+    COMMENT 19 "/* ∑ /* ∏ */ */"
+    WHITESPACE 1 " "
+    STRING 13 "\"π ≈ 3.14\""
+    WHITESPACE 1 " "
+    IDENT 11 "ünïcödé"
+    WHITESPACE 2 "\u{a0}"
+    IDENT 1 "x"
+    "#);
+}
+
 #[test]
 fn whitespace() {
     insta::assert_snapshot!(dump_text_tokens(
@@ -403,3 +419,82 @@ fn binary_cmp() {
     WHITESPACE 5 "\n    "
     "#);
 }
+
+#[test]
+fn ranges() {
+    insta::assert_snapshot!(dump_text_tokens(
+        r#"
+    0..10
+    a..=b
+    a.0..a.1"#), @r#"
+    WHITESPACE 5 "\n    "
+    INT_NUMBER 1 "0"
+    DOT 1 "."
+    DOT 1 "."
+    INT_NUMBER 2 "10"
+    WHITESPACE 5 "\n    "
+    IDENT 1 "a"
+    DOT 1 "."
+    DOT 1 "."
+    EQ 1 "="
+    IDENT 1 "b"
+    WHITESPACE 5 "\n    "
+    IDENT 1 "a"
+    INDEX 2 ".0"
+    DOT 1 "."
+    DOT 1 "."
+    IDENT 1 "a"
+    INDEX 2 ".1"
+    "#);
+}
+
+#[test]
+fn continue_label() {
+    insta::assert_snapshot!(dump_text_tokens(
+        r#"
+    continue 'outer;
+    "#), @r#"
+    WHITESPACE 5 "\n    "
+    CONTINUE_KW 8 "continue"
+    WHITESPACE 1 " "
+    LABEL_NAME 6 "'outer"
+    SEMI 1 ";"
+    WHITESPACE 5 "\n    "
+    "#);
+}
+
+#[test]
+fn labels() {
+    insta::assert_snapshot!(dump_text_tokens(
+        r#"
+    'outer: loop { break 'outer; }
+    'a: while
+    'note: hello'
+    'a'
+    "#), @r#"
+    WHITESPACE 5 "\n    "
+    LABEL_NAME 6 "'outer"
+    COLON 1 ":"
+    WHITESPACE 1 " "
+    LOOP_KW 4 "loop"
+    WHITESPACE 1 " "
+    L_CURLY 1 "{"
+    WHITESPACE 1 " "
+    BREAK_KW 5 "break"
+    WHITESPACE 1 " "
+    LABEL_NAME 6 "'outer"
+    SEMI 1 ";"
+    WHITESPACE 1 " "
+    R_CURLY 1 "}"
+    WHITESPACE 5 "\n    "
+    LABEL_NAME 2 "'a"
+    COLON 1 ":"
+    WHITESPACE 1 " "
+    WHILE_KW 5 "while"
+    WHITESPACE 5 "\n    "
+    STRING 13 "'note: hello'"
+    WHITESPACE 5 "\n    "
+    STRING 3 "'a'"
+    WHITESPACE 5 "\n    "
+    "#);
+}
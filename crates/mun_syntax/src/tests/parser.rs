@@ -109,6 +109,59 @@ fn impl_block() {
     "###);
 }
 
+#[test]
+fn prefixed_int_literals() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        fn main() {
+            0xFF;
+            0o77;
+            0b1010;
+            0b1111_0000_u8;
+            0xdead_beef_u32;
+            0b102;
+            0o78;
+            0x;
+            0b_u8;
+        }
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn unclosed_block() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        fn foo() {
+            let a = 3;
+            if a {
+                bar();
+
+        fn baz() -> i32 {
+            5
+        }
+
+        struct Foo { a: i32 }
+        }
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn unclosed_let_initializer() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        fn foo() {
+            let a =
+
+        pub fn bar() {}
+        "#
+    )
+    .debug_dump());
+}
+
 #[test]
 fn array_type() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -2631,6 +2684,202 @@ fn while_expr() {
     "#);
 }
 
+#[test]
+fn for_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        for i in 0..10 {};
+        for i in 0..=a {};
+        for _ in array {};
+        for a in Foo {};
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..132
+      FUNCTION_DEF@0..127
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..127
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          EXPR_STMT@24..42
+            FOR_EXPR@24..41
+              FOR_KW@24..27 "for"
+              WHITESPACE@27..28 " "
+              BIND_PAT@28..29
+                NAME@28..29
+                  IDENT@28..29 "i"
+              WHITESPACE@29..30 " "
+              IN_KW@30..32 "in"
+              WHITESPACE@32..33 " "
+              RANGE_EXPR@33..38
+                LITERAL@33..34
+                  INT_NUMBER@33..34 "0"
+                DOTDOT@34..36 ".."
+                LITERAL@36..38
+                  INT_NUMBER@36..38 "10"
+              WHITESPACE@38..39 " "
+              BLOCK_EXPR@39..41
+                L_CURLY@39..40 "{"
+                R_CURLY@40..41 "}"
+            SEMI@41..42 ";"
+          WHITESPACE@42..51 "\n        "
+          EXPR_STMT@51..69
+            FOR_EXPR@51..68
+              FOR_KW@51..54 "for"
+              WHITESPACE@54..55 " "
+              BIND_PAT@55..56
+                NAME@55..56
+                  IDENT@55..56 "i"
+              WHITESPACE@56..57 " "
+              IN_KW@57..59 "in"
+              WHITESPACE@59..60 " "
+              RANGE_EXPR@60..65
+                LITERAL@60..61
+                  INT_NUMBER@60..61 "0"
+                DOTDOTEQ@61..64 "..="
+                PATH_EXPR@64..65
+                  PATH@64..65
+                    PATH_SEGMENT@64..65
+                      NAME_REF@64..65
+                        IDENT@64..65 "a"
+              WHITESPACE@65..66 " "
+              BLOCK_EXPR@66..68
+                L_CURLY@66..67 "{"
+                R_CURLY@67..68 "}"
+            SEMI@68..69 ";"
+          WHITESPACE@69..78 "\n        "
+          EXPR_STMT@78..96
+            FOR_EXPR@78..95
+              FOR_KW@78..81 "for"
+              WHITESPACE@81..82 " "
+              PLACEHOLDER_PAT@82..83
+                UNDERSCORE@82..83 "_"
+              WHITESPACE@83..84 " "
+              IN_KW@84..86 "in"
+              WHITESPACE@86..87 " "
+              PATH_EXPR@87..92
+                PATH@87..92
+                  PATH_SEGMENT@87..92
+                    NAME_REF@87..92
+                      IDENT@87..92 "array"
+              WHITESPACE@92..93 " "
+              BLOCK_EXPR@93..95
+                L_CURLY@93..94 "{"
+                R_CURLY@94..95 "}"
+            SEMI@95..96 ";"
+          WHITESPACE@96..105 "\n        "
+          EXPR_STMT@105..121
+            FOR_EXPR@105..120
+              FOR_KW@105..108 "for"
+              WHITESPACE@108..109 " "
+              BIND_PAT@109..110
+                NAME@109..110
+                  IDENT@109..110 "a"
+              WHITESPACE@110..111 " "
+              IN_KW@111..113 "in"
+              WHITESPACE@113..114 " "
+              PATH_EXPR@114..117
+                PATH@114..117
+                  PATH_SEGMENT@114..117
+                    NAME_REF@114..117
+                      IDENT@114..117 "Foo"
+              WHITESPACE@117..118 " "
+              BLOCK_EXPR@118..120
+                L_CURLY@118..119 "{"
+                R_CURLY@119..120 "}"
+            SEMI@120..121 ";"
+          WHITESPACE@121..126 "\n    "
+          R_CURLY@126..127 "}"
+      WHITESPACE@127..132 "\n    "
+    "#);
+}
+
+#[test]
+fn range_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        let a = 0..10;
+        let b = a+1..=b*2;
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..76
+      FUNCTION_DEF@0..71
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..71
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LET_STMT@24..38
+            LET_KW@24..27 "let"
+            WHITESPACE@27..28 " "
+            BIND_PAT@28..29
+              NAME@28..29
+                IDENT@28..29 "a"
+            WHITESPACE@29..30 " "
+            EQ@30..31 "="
+            WHITESPACE@31..32 " "
+            RANGE_EXPR@32..37
+              LITERAL@32..33
+                INT_NUMBER@32..33 "0"
+              DOTDOT@33..35 ".."
+              LITERAL@35..37
+                INT_NUMBER@35..37 "10"
+            SEMI@37..38 ";"
+          WHITESPACE@38..47 "\n        "
+          LET_STMT@47..65
+            LET_KW@47..50 "let"
+            WHITESPACE@50..51 " "
+            BIND_PAT@51..52
+              NAME@51..52
+                IDENT@51..52 "b"
+            WHITESPACE@52..53 " "
+            EQ@53..54 "="
+            WHITESPACE@54..55 " "
+            RANGE_EXPR@55..64
+              BIN_EXPR@55..58
+                PATH_EXPR@55..56
+                  PATH@55..56
+                    PATH_SEGMENT@55..56
+                      NAME_REF@55..56
+                        IDENT@55..56 "a"
+                PLUS@56..57 "+"
+                LITERAL@57..58
+                  INT_NUMBER@57..58 "1"
+              DOTDOTEQ@58..61 "..="
+              BIN_EXPR@61..64
+                PATH_EXPR@61..62
+                  PATH@61..62
+                    PATH_SEGMENT@61..62
+                      NAME_REF@61..62
+                        IDENT@61..62 "b"
+                STAR@62..63 "*"
+                LITERAL@63..64
+                  INT_NUMBER@63..64 "2"
+            SEMI@64..65 ";"
+          WHITESPACE@65..70 "\n    "
+          R_CURLY@70..71 "}"
+      WHITESPACE@71..76 "\n    "
+    "#);
+}
+
 #[test]
 fn struct_lit() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -3357,9 +3606,9 @@ fn use_() {
     ).debug_dump(), @r#"
     SOURCE_FILE@0..726
       WHITESPACE@0..9 "\n        "
-      COMMENT@9..24 "// Simple paths"
-      WHITESPACE@24..33 "\n        "
-      USE@33..50
+      USE@9..50
+        COMMENT@9..24 "// Simple paths"
+        WHITESPACE@24..33 "\n        "
         USE_KW@33..36 "use"
         WHITESPACE@36..37 " "
         USE_TREE@37..49
@@ -3449,9 +3698,9 @@ fn use_() {
                 IDENT@212..221 "some_item"
         SEMI@221..222 ";"
       WHITESPACE@222..232 "\n\n        "
-      COMMENT@232..248 "// Use tree list"
-      WHITESPACE@248..257 "\n        "
-      USE@257..275
+      USE@232..275
+        COMMENT@232..248 "// Use tree list"
+        WHITESPACE@248..257 "\n        "
         USE_KW@257..260 "use"
         WHITESPACE@260..261 " "
         USE_TREE@261..274
@@ -3488,9 +3737,9 @@ fn use_() {
             R_CURLY@299..300 "}"
         SEMI@300..301 ";"
       WHITESPACE@301..311 "\n\n        "
-      COMMENT@311..329 "// Wildcard import"
-      WHITESPACE@329..338 "\n        "
-      USE@338..344
+      USE@311..344
+        COMMENT@311..329 "// Wildcard import"
+        WHITESPACE@329..338 "\n        "
         USE_KW@338..341 "use"
         WHITESPACE@341..342 " "
         ERROR@342..343
@@ -3541,9 +3790,9 @@ fn use_() {
             R_CURLY@423..424 "}"
         SEMI@424..425 ";"
       WHITESPACE@425..435 "\n\n        "
-      COMMENT@435..445 "// Renames"
-      WHITESPACE@445..454 "\n        "
-      USE@454..482
+      USE@435..482
+        COMMENT@435..445 "// Renames"
+        WHITESPACE@445..454 "\n        "
         USE_KW@454..457 "use"
         WHITESPACE@457..458 " "
         USE_TREE@458..481
@@ -3688,3 +3937,600 @@ fn use_() {
     error Offset(369): expected a declaration
     "#);
 }
+
+#[test]
+fn comment_attachment() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        struct Foo; // trailing
+
+        // detached
+
+        /// doc
+        struct Bar {
+            // field
+            a: i32,
+        }
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn const_def() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        /// The maximum speed
+        pub const MAX_SPEED: f32 = 5.0;
+        const TWICE: i32 = 2 * 21;
+        const NO_TYPE = 1;
+        const NO_VALUE: i32;
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn static_def() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        /// Number of frames rendered so far
+        pub static FRAME_COUNT: u64 = 0;
+        static SPEED: f32 = 2.0 * 1.5;
+        static NO_TYPE = 1;
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn destructuring_patterns() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo(Vec2 { x, y }: Vec2, Pair(a, _): Pair) {
+        let Foo { a, b: Bar(c, ..), .. } = foo;
+        let package::Baz(d) = baz;
+        let Foo = foo;
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..169
+      FUNCTION_DEF@0..164
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..50
+          L_PAREN@11..12 "("
+          PARAM@12..31
+            RECORD_PAT@12..25
+              PATH@12..16
+                PATH_SEGMENT@12..16
+                  NAME_REF@12..16
+                    IDENT@12..16 "Vec2"
+              WHITESPACE@16..17 " "
+              RECORD_FIELD_PAT_LIST@17..25
+                L_CURLY@17..18 "{"
+                WHITESPACE@18..19 " "
+                RECORD_FIELD_PAT@19..20
+                  BIND_PAT@19..20
+                    NAME@19..20
+                      IDENT@19..20 "x"
+                COMMA@20..21 ","
+                WHITESPACE@21..22 " "
+                RECORD_FIELD_PAT@22..23
+                  BIND_PAT@22..23
+                    NAME@22..23
+                      IDENT@22..23 "y"
+                WHITESPACE@23..24 " "
+                R_CURLY@24..25 "}"
+            COLON@25..26 ":"
+            WHITESPACE@26..27 " "
+            PATH_TYPE@27..31
+              PATH@27..31
+                PATH_SEGMENT@27..31
+                  NAME_REF@27..31
+                    IDENT@27..31 "Vec2"
+          COMMA@31..32 ","
+          WHITESPACE@32..33 " "
+          PARAM@33..49
+            TUPLE_STRUCT_PAT@33..43
+              PATH@33..37
+                PATH_SEGMENT@33..37
+                  NAME_REF@33..37
+                    IDENT@33..37 "Pair"
+              L_PAREN@37..38 "("
+              BIND_PAT@38..39
+                NAME@38..39
+                  IDENT@38..39 "a"
+              COMMA@39..40 ","
+              WHITESPACE@40..41 " "
+              PLACEHOLDER_PAT@41..42
+                UNDERSCORE@41..42 "_"
+              R_PAREN@42..43 ")"
+            COLON@43..44 ":"
+            WHITESPACE@44..45 " "
+            PATH_TYPE@45..49
+              PATH@45..49
+                PATH_SEGMENT@45..49
+                  NAME_REF@45..49
+                    IDENT@45..49 "Pair"
+          R_PAREN@49..50 ")"
+        WHITESPACE@50..51 " "
+        BLOCK_EXPR@51..164
+          L_CURLY@51..52 "{"
+          WHITESPACE@52..61 "\n        "
+          LET_STMT@61..100
+            LET_KW@61..64 "let"
+            WHITESPACE@64..65 " "
+            RECORD_PAT@65..93
+              PATH@65..68
+                PATH_SEGMENT@65..68
+                  NAME_REF@65..68
+                    IDENT@65..68 "Foo"
+              WHITESPACE@68..69 " "
+              RECORD_FIELD_PAT_LIST@69..93
+                L_CURLY@69..70 "{"
+                WHITESPACE@70..71 " "
+                RECORD_FIELD_PAT@71..72
+                  BIND_PAT@71..72
+                    NAME@71..72
+                      IDENT@71..72 "a"
+                COMMA@72..73 ","
+                WHITESPACE@73..74 " "
+                RECORD_FIELD_PAT@74..87
+                  NAME_REF@74..75
+                    IDENT@74..75 "b"
+                  COLON@75..76 ":"
+                  WHITESPACE@76..77 " "
+                  TUPLE_STRUCT_PAT@77..87
+                    PATH@77..80
+                      PATH_SEGMENT@77..80
+                        NAME_REF@77..80
+                          IDENT@77..80 "Bar"
+                    L_PAREN@80..81 "("
+                    BIND_PAT@81..82
+                      NAME@81..82
+                        IDENT@81..82 "c"
+                    COMMA@82..83 ","
+                    WHITESPACE@83..84 " "
+                    DOTDOT@84..86 ".."
+                    R_PAREN@86..87 ")"
+                COMMA@87..88 ","
+                WHITESPACE@88..89 " "
+                DOTDOT@89..91 ".."
+                WHITESPACE@91..92 " "
+                R_CURLY@92..93 "}"
+            WHITESPACE@93..94 " "
+            EQ@94..95 "="
+            WHITESPACE@95..96 " "
+            PATH_EXPR@96..99
+              PATH@96..99
+                PATH_SEGMENT@96..99
+                  NAME_REF@96..99
+                    IDENT@96..99 "foo"
+            SEMI@99..100 ";"
+          WHITESPACE@100..109 "\n        "
+          LET_STMT@109..135
+            LET_KW@109..112 "let"
+            WHITESPACE@112..113 " "
+            TUPLE_STRUCT_PAT@113..128
+              PATH@113..125
+                PATH@113..120
+                  PATH_SEGMENT@113..120
+                    PACKAGE_KW@113..120 "package"
+                COLONCOLON@120..122 "::"
+                PATH_SEGMENT@122..125
+                  NAME_REF@122..125
+                    IDENT@122..125 "Baz"
+              L_PAREN@125..126 "("
+              BIND_PAT@126..127
+                NAME@126..127
+                  IDENT@126..127 "d"
+              R_PAREN@127..128 ")"
+            WHITESPACE@128..129 " "
+            EQ@129..130 "="
+            WHITESPACE@130..131 " "
+            PATH_EXPR@131..134
+              PATH@131..134
+                PATH_SEGMENT@131..134
+                  NAME_REF@131..134
+                    IDENT@131..134 "baz"
+            SEMI@134..135 ";"
+          WHITESPACE@135..144 "\n        "
+          LET_STMT@144..158
+            LET_KW@144..147 "let"
+            WHITESPACE@147..148 " "
+            BIND_PAT@148..151
+              NAME@148..151
+                IDENT@148..151 "Foo"
+            WHITESPACE@151..152 " "
+            EQ@152..153 "="
+            WHITESPACE@153..154 " "
+            PATH_EXPR@154..157
+              PATH@154..157
+                PATH_SEGMENT@154..157
+                  NAME_REF@154..157
+                    IDENT@154..157 "foo"
+            SEMI@157..158 ";"
+          WHITESPACE@158..163 "\n    "
+          R_CURLY@163..164 "}"
+      WHITESPACE@164..169 "\n    "
+    "#);
+}
+
+#[test]
+fn tuples() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        let a = ();
+        let (b,) = (1,);
+        let (c, (d, _), ..) = (a, (2.0, true), 3);
+        let (e) = (c + 1);
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..149
+      FUNCTION_DEF@0..144
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..144
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LET_STMT@24..35
+            LET_KW@24..27 "let"
+            WHITESPACE@27..28 " "
+            BIND_PAT@28..29
+              NAME@28..29
+                IDENT@28..29 "a"
+            WHITESPACE@29..30 " "
+            EQ@30..31 "="
+            WHITESPACE@31..32 " "
+            TUPLE_EXPR@32..34
+              L_PAREN@32..33 "("
+              R_PAREN@33..34 ")"
+            SEMI@34..35 ";"
+          WHITESPACE@35..44 "\n        "
+          LET_STMT@44..60
+            LET_KW@44..47 "let"
+            WHITESPACE@47..48 " "
+            TUPLE_PAT@48..52
+              L_PAREN@48..49 "("
+              BIND_PAT@49..50
+                NAME@49..50
+                  IDENT@49..50 "b"
+              COMMA@50..51 ","
+              R_PAREN@51..52 ")"
+            WHITESPACE@52..53 " "
+            EQ@53..54 "="
+            WHITESPACE@54..55 " "
+            TUPLE_EXPR@55..59
+              L_PAREN@55..56 "("
+              LITERAL@56..57
+                INT_NUMBER@56..57 "1"
+              COMMA@57..58 ","
+              R_PAREN@58..59 ")"
+            SEMI@59..60 ";"
+          WHITESPACE@60..69 "\n        "
+          LET_STMT@69..111
+            LET_KW@69..72 "let"
+            WHITESPACE@72..73 " "
+            TUPLE_PAT@73..88
+              L_PAREN@73..74 "("
+              BIND_PAT@74..75
+                NAME@74..75
+                  IDENT@74..75 "c"
+              COMMA@75..76 ","
+              WHITESPACE@76..77 " "
+              TUPLE_PAT@77..83
+                L_PAREN@77..78 "("
+                BIND_PAT@78..79
+                  NAME@78..79
+                    IDENT@78..79 "d"
+                COMMA@79..80 ","
+                WHITESPACE@80..81 " "
+                PLACEHOLDER_PAT@81..82
+                  UNDERSCORE@81..82 "_"
+                R_PAREN@82..83 ")"
+              COMMA@83..84 ","
+              WHITESPACE@84..85 " "
+              DOTDOT@85..87 ".."
+              R_PAREN@87..88 ")"
+            WHITESPACE@88..89 " "
+            EQ@89..90 "="
+            WHITESPACE@90..91 " "
+            TUPLE_EXPR@91..110
+              L_PAREN@91..92 "("
+              PATH_EXPR@92..93
+                PATH@92..93
+                  PATH_SEGMENT@92..93
+                    NAME_REF@92..93
+                      IDENT@92..93 "a"
+              COMMA@93..94 ","
+              WHITESPACE@94..95 " "
+              TUPLE_EXPR@95..106
+                L_PAREN@95..96 "("
+                LITERAL@96..99
+                  FLOAT_NUMBER@96..99 "2.0"
+                COMMA@99..100 ","
+                WHITESPACE@100..101 " "
+                LITERAL@101..105
+                  TRUE_KW@101..105 "true"
+                R_PAREN@105..106 ")"
+              COMMA@106..107 ","
+              WHITESPACE@107..108 " "
+              LITERAL@108..109
+                INT_NUMBER@108..109 "3"
+              R_PAREN@109..110 ")"
+            SEMI@110..111 ";"
+          WHITESPACE@111..120 "\n        "
+          LET_STMT@120..138
+            LET_KW@120..123 "let"
+            WHITESPACE@123..124 " "
+            PAREN_PAT@124..127
+              L_PAREN@124..125 "("
+              BIND_PAT@125..126
+                NAME@125..126
+                  IDENT@125..126 "e"
+              R_PAREN@126..127 ")"
+            WHITESPACE@127..128 " "
+            EQ@128..129 "="
+            WHITESPACE@129..130 " "
+            PAREN_EXPR@130..137
+              L_PAREN@130..131 "("
+              BIN_EXPR@131..136
+                PATH_EXPR@131..132
+                  PATH@131..132
+                    PATH_SEGMENT@131..132
+                      NAME_REF@131..132
+                        IDENT@131..132 "c"
+                WHITESPACE@132..133 " "
+                PLUS@133..134 "+"
+                WHITESPACE@134..135 " "
+                LITERAL@135..136
+                  INT_NUMBER@135..136 "1"
+              R_PAREN@136..137 ")"
+            SEMI@137..138 ";"
+          WHITESPACE@138..143 "\n    "
+          R_CURLY@143..144 "}"
+      WHITESPACE@144..149 "\n    "
+    "#);
+}
+
+#[test]
+fn continue_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        'outer: for i in 0..10 {
+            while true {
+                continue 'outer;
+            }
+            continue;
+        }
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..163
+      FUNCTION_DEF@0..158
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..158
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          FOR_EXPR@24..152
+            LABEL@24..31
+              LABEL_NAME@24..30 "'outer"
+              COLON@30..31 ":"
+            WHITESPACE@31..32 " "
+            FOR_KW@32..35 "for"
+            WHITESPACE@35..36 " "
+            BIND_PAT@36..37
+              NAME@36..37
+                IDENT@36..37 "i"
+            WHITESPACE@37..38 " "
+            IN_KW@38..40 "in"
+            WHITESPACE@40..41 " "
+            RANGE_EXPR@41..46
+              LITERAL@41..42
+                INT_NUMBER@41..42 "0"
+              DOTDOT@42..44 ".."
+              LITERAL@44..46
+                INT_NUMBER@44..46 "10"
+            WHITESPACE@46..47 " "
+            BLOCK_EXPR@47..152
+              L_CURLY@47..48 "{"
+              WHITESPACE@48..61 "\n            "
+              EXPR_STMT@61..120
+                WHILE_EXPR@61..120
+                  WHILE_KW@61..66 "while"
+                  WHITESPACE@66..67 " "
+                  CONDITION@67..71
+                    LITERAL@67..71
+                      TRUE_KW@67..71 "true"
+                  WHITESPACE@71..72 " "
+                  BLOCK_EXPR@72..120
+                    L_CURLY@72..73 "{"
+                    WHITESPACE@73..90 "\n                "
+                    EXPR_STMT@90..106
+                      CONTINUE_EXPR@90..105
+                        CONTINUE_KW@90..98 "continue"
+                        WHITESPACE@98..99 " "
+                        LABEL_NAME@99..105 "'outer"
+                      SEMI@105..106 ";"
+                    WHITESPACE@106..119 "\n            "
+                    R_CURLY@119..120 "}"
+              WHITESPACE@120..133 "\n            "
+              EXPR_STMT@133..142
+                CONTINUE_EXPR@133..141
+                  CONTINUE_KW@133..141 "continue"
+                SEMI@141..142 ";"
+              WHITESPACE@142..151 "\n        "
+              R_CURLY@151..152 "}"
+          WHITESPACE@152..157 "\n    "
+          R_CURLY@157..158 "}"
+      WHITESPACE@158..163 "\n    "
+    "#);
+}
+
+#[test]
+fn labeled_loops() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        'outer: loop {
+            'inner: while true {
+                break 'outer 5;
+            }
+            for i in 0..10 {
+                break 'outer;
+            }
+        }
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..211
+      FUNCTION_DEF@0..206
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..206
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LOOP_EXPR@24..200
+            LABEL@24..31
+              LABEL_NAME@24..30 "'outer"
+              COLON@30..31 ":"
+            WHITESPACE@31..32 " "
+            LOOP_KW@32..36 "loop"
+            WHITESPACE@36..37 " "
+            BLOCK_EXPR@37..200
+              L_CURLY@37..38 "{"
+              WHITESPACE@38..51 "\n            "
+              EXPR_STMT@51..117
+                WHILE_EXPR@51..117
+                  LABEL@51..58
+                    LABEL_NAME@51..57 "'inner"
+                    COLON@57..58 ":"
+                  WHITESPACE@58..59 " "
+                  WHILE_KW@59..64 "while"
+                  WHITESPACE@64..65 " "
+                  CONDITION@65..69
+                    LITERAL@65..69
+                      TRUE_KW@65..69 "true"
+                  WHITESPACE@69..70 " "
+                  BLOCK_EXPR@70..117
+                    L_CURLY@70..71 "{"
+                    WHITESPACE@71..88 "\n                "
+                    EXPR_STMT@88..103
+                      BREAK_EXPR@88..102
+                        BREAK_KW@88..93 "break"
+                        WHITESPACE@93..94 " "
+                        LABEL_NAME@94..100 "'outer"
+                        WHITESPACE@100..101 " "
+                        LITERAL@101..102
+                          INT_NUMBER@101..102 "5"
+                      SEMI@102..103 ";"
+                    WHITESPACE@103..116 "\n            "
+                    R_CURLY@116..117 "}"
+              WHITESPACE@117..130 "\n            "
+              FOR_EXPR@130..190
+                FOR_KW@130..133 "for"
+                WHITESPACE@133..134 " "
+                BIND_PAT@134..135
+                  NAME@134..135
+                    IDENT@134..135 "i"
+                WHITESPACE@135..136 " "
+                IN_KW@136..138 "in"
+                WHITESPACE@138..139 " "
+                RANGE_EXPR@139..144
+                  LITERAL@139..140
+                    INT_NUMBER@139..140 "0"
+                  DOTDOT@140..142 ".."
+                  LITERAL@142..144
+                    INT_NUMBER@142..144 "10"
+                WHITESPACE@144..145 " "
+                BLOCK_EXPR@145..190
+                  L_CURLY@145..146 "{"
+                  WHITESPACE@146..163 "\n                "
+                  EXPR_STMT@163..176
+                    BREAK_EXPR@163..175
+                      BREAK_KW@163..168 "break"
+                      WHITESPACE@168..169 " "
+                      LABEL_NAME@169..175 "'outer"
+                    SEMI@175..176 ";"
+                  WHITESPACE@176..189 "\n            "
+                  R_CURLY@189..190 "}"
+              WHITESPACE@190..199 "\n        "
+              R_CURLY@199..200 "}"
+          WHITESPACE@200..205 "\n    "
+          R_CURLY@205..206 "}"
+      WHITESPACE@206..211 "\n    "
+    "#);
+}
+
+#[test]
+fn attributes() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        #[inline]
+        pub fn foo() {}
+
+        #[inline(always)]
+        #[export_name = "bar_export"]
+        fn bar() {}
+
+        #[deprecated = "use `Bar` instead"]
+        struct Foo;
+
+        #[inline(]
+        fn baz() {}
+
+        #[= "missing name"]
+        fn qux() {}
+        "#
+    )
+    .debug_dump());
+}
+
+#[test]
+fn mut_bindings() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+        struct Foo { a: i32 }
+
+        impl Foo {
+            fn bar(mut self, mut b: i32) {}
+        }
+
+        fn main() {
+            let mut a = 5;
+            let Foo { mut a } = foo;
+            let mut = 5;
+        }
+        "#
+    )
+    .debug_dump());
+}
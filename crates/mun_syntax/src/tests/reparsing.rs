@@ -0,0 +1,90 @@
+use ra_ap_text_edit::Indel;
+
+use crate::{SourceFile, TextRange, TextSize};
+
+/// Applies an edit that replaces the text between the two `$0` markers in
+/// `before` with `replace_with`. Asserts that the edit is reparsed
+/// incrementally if `incremental` is true, and that the result is the same as
+/// parsing the edited text from scratch.
+fn check(before: &str, replace_with: &str, incremental: bool) {
+    let (text, range) = extract_range(before);
+    let edit = Indel::replace(range, replace_with.to_owned());
+
+    let before = SourceFile::parse(&text);
+    let incrementally_reparsed = before.incremental_reparse(&edit);
+    assert_eq!(
+        incrementally_reparsed.is_some(),
+        incremental,
+        "unexpected reparse of {text:?} with {replace_with:?}"
+    );
+
+    let mut after = text.clone();
+    edit.apply(&mut after);
+    let fully_reparsed = SourceFile::parse(&after);
+    let reparsed = before.reparse(&edit);
+    assert_eq!(reparsed.debug_dump(), fully_reparsed.debug_dump());
+}
+
+/// Removes the two `$0` markers from `text` and returns the range between them.
+fn extract_range(text: &str) -> (String, TextRange) {
+    let start = text.find("$0").expect("missing start marker");
+    let text = text.replacen("$0", "", 1);
+    let end = text.find("$0").expect("missing end marker");
+    let text = text.replacen("$0", "", 1);
+    let range = TextRange::new(
+        TextSize::try_from(start).unwrap(),
+        TextSize::try_from(end).unwrap(),
+    );
+    (text, range)
+}
+
+#[test]
+fn reparse_token() {
+    check("fn foo() { let $0bar$0 = 1; }", "baz", true);
+    check("fn foo() { let bar =$0 $0 1; }", "   ", true);
+    check("// a $0comment$0\nfn foo() {}", "remark", true);
+    check(r#"fn foo() { let a = "hel$0$0lo"; }"#, "l", true);
+
+    // Keywords are not identifiers
+    check("fn $0foo$0() {}", "loop", false);
+    // Contextual keywords are treated differently by the parser
+    check("struct($0val$0) Foo;", "value", false);
+    // A newline changes to which node comments are attached
+    check("// a comment$0 $0fn foo() {}", "\n", false);
+    // A doc comment is attached to different nodes than a regular comment
+    check("$0//$0 a comment\nfn foo() {}", "///", false);
+    // The token is merged with the next token
+    check(r#"fn foo() { let a = "hello$0"$0; }"#, "", false);
+}
+
+#[test]
+fn reparse_block() {
+    check(
+        "fn foo() {\n    let a = 1;\n}\n\nfn bar() {\n    $0a$0 + 1\n}",
+        "a +",
+        true,
+    );
+    check(
+        "fn foo() {\n    let a = 1;\n}\n\nfn bar() {\n    if true $0{ 1 }$0\n}",
+        "{ let b = 5; b }",
+        true,
+    );
+    check("struct Foo {\n    a: i32,$0$0\n}", "\n    b: f32,", true);
+    check(
+        "impl Foo {\n    fn foo() {}$0\n$0}",
+        "\n    fn bar() {}\n",
+        true,
+    );
+
+    // Errors outside of the reparsed block are kept
+    check(
+        "fn foo( {}\n\nfn bar() {\n    $0a$0\n}\n\nfn baz( {}",
+        "let",
+        true,
+    );
+
+    // Unbalanced braces affect the rest of the file
+    check("fn foo() {\n    $0$0\n}\n\nfn bar() {}", "{", false);
+    // A declaration ends the block
+    check("fn foo() {\n    $0$0\n}", "fn bar() {}", false);
+}
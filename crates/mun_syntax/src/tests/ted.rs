@@ -0,0 +1,115 @@
+use crate::{
+    ast::{self, AstNode},
+    ted::{self, Position},
+    SourceFile, SyntaxKind, SyntaxNode, T,
+};
+
+/// Parses `text` into a mutable tree.
+fn parse(text: &str) -> SyntaxNode {
+    SourceFile::parse(text).syntax_node().clone_for_update()
+}
+
+/// Parses `text` and returns the first node of type `N` as part of a new,
+/// mutable tree.
+fn make<N: AstNode>(text: &str) -> N {
+    parse(text).descendants().find_map(N::cast).unwrap()
+}
+
+fn first<N: AstNode>(root: &SyntaxNode) -> N {
+    root.descendants().find_map(N::cast).unwrap()
+}
+
+fn nth_stmt(root: &SyntaxNode, n: usize) -> SyntaxNode {
+    root.descendants()
+        .filter(|it| matches!(it.kind(), SyntaxKind::LET_STMT | SyntaxKind::EXPR_STMT))
+        .nth(n)
+        .unwrap()
+}
+
+#[test]
+fn append_statement_to_block() {
+    let root = parse("fn main() {\n    let a = 1;\n}\n");
+    let stmt = make::<ast::LetStmt>("fn f() { let b = 2; }");
+    ted::insert(Position::after(nth_stmt(&root, 0)), stmt.syntax());
+    insta::assert_snapshot!(root.to_string(), @r###"
+    fn main() {
+        let a = 1;
+        let b = 2;
+    }
+    "###);
+}
+
+#[test]
+fn insert_statement_into_empty_block() {
+    let root = parse("fn main() {\n    if true {}\n}\n");
+    let block = root
+        .descendants()
+        .filter_map(ast::BlockExpr::cast)
+        .nth(1)
+        .unwrap();
+    let l_curly = block
+        .syntax()
+        .children_with_tokens()
+        .find(|it| it.kind() == T!['{'])
+        .unwrap();
+    let stmt = make::<ast::LetStmt>("fn f() { let b = 2; }");
+    ted::insert(Position::after(l_curly), stmt.syntax());
+    insta::assert_snapshot!(root.to_string(), @r###"
+    fn main() {
+        if true {
+            let b = 2;
+        }
+    }
+    "###);
+}
+
+#[test]
+fn append_item_to_file() {
+    let root = parse("fn foo() {}");
+    let item = make::<ast::FunctionDef>("fn bar() {}");
+    ted::append_child(&root, item.syntax());
+    insta::assert_snapshot!(root.to_string(), @r###"
+    fn foo() {}
+
+    fn bar() {}
+    "###);
+}
+
+#[test]
+fn remove_statements() {
+    let root = parse("fn main() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n}\n");
+    ted::remove(nth_stmt(&root, 1));
+    insta::assert_snapshot!(root.to_string(), @r###"
+    fn main() {
+        let a = 1;
+        let c = 3;
+    }
+    "###);
+
+    ted::remove(nth_stmt(&root, 1));
+    ted::remove(nth_stmt(&root, 0));
+    insta::assert_snapshot!(root.to_string(), @"fn main() {}");
+}
+
+#[test]
+fn insert_and_remove_mut() {
+    let root = parse("fn main() { let a = 1; }");
+    let bind_pat = first::<ast::BindPat>(&root);
+    let mut_token = make::<ast::BindPat>("fn f() { let mut b = 1; }")
+        .mut_token()
+        .unwrap();
+    ted::prepend_child(bind_pat.syntax(), mut_token);
+    insta::assert_snapshot!(root.to_string(), @"fn main() { let mut a = 1; }");
+
+    ted::remove(bind_pat.mut_token().unwrap());
+    insta::assert_snapshot!(root.to_string(), @"fn main() { let a = 1; }");
+}
+
+#[test]
+fn replace_expression() {
+    let root = parse("fn main() -> i32 { 1 + 2 }");
+    let expr = first::<ast::BinExpr>(&root);
+    let new_expr = make::<ast::CallExpr>("fn f() { foo(3) }");
+    ted::replace(expr.syntax(), new_expr.syntax());
+    insta::assert_snapshot!(root.to_string(), @"fn main() -> i32 { foo(3) }");
+}
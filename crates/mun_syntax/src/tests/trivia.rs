@@ -0,0 +1,143 @@
+use crate::{
+    ast::{self, AstNode, DocCommentsOwner, ModuleItemOwner},
+    trivia::attached_comments,
+    AstToken, SourceFile, SyntaxNode,
+};
+
+fn comments(node: &SyntaxNode) -> String {
+    let comments = attached_comments(node);
+    let leading = comments
+        .leading
+        .iter()
+        .map(|it| it.text().to_string())
+        .collect::<Vec<_>>();
+    format!(
+        "leading: {:?}, trailing: {:?}",
+        leading,
+        comments.trailing.as_ref().map(|it| it.text().to_string())
+    )
+}
+
+fn item_comments(text: &str) -> String {
+    SourceFile::parse(text)
+        .tree()
+        .items()
+        .map(|item| comments(item.syntax()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn leading_and_trailing_comments() {
+    insta::assert_snapshot!(item_comments(
+        r#"
+// foo
+fn foo() {} // after foo
+
+/// Documents `Bar`
+
+/// more docs
+struct Bar; // after bar
+// not a doc comment
+
+fn baz() {}
+"#,
+    ), @r###"
+    leading: ["// foo"], trailing: Some("// after foo")
+    leading: ["/// Documents `Bar`", "/// more docs"], trailing: Some("// after bar")
+    leading: [], trailing: None
+    "###);
+}
+
+#[test]
+fn inner_doc_comments_are_not_attached() {
+    insta::assert_snapshot!(item_comments(
+        r#"//! module docs
+fn foo() {}
+"#,
+    ), @r###"leading: [], trailing: None"###);
+}
+
+#[test]
+fn record_field_comments() {
+    let file = SourceFile::parse(
+        r#"
+struct Foo {
+    // leading a
+    a: i32, // trailing a
+    /// leading b
+    b: f64,
+}
+"#,
+    )
+    .tree();
+    let fields = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::RecordFieldDef::cast)
+        .map(|field| comments(field.syntax()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    insta::assert_snapshot!(fields, @r###"
+    leading: ["// leading a"], trailing: Some("// trailing a")
+    leading: ["/// leading b"], trailing: None
+    "###);
+}
+
+#[test]
+fn statement_comments() {
+    let file = SourceFile::parse(
+        r#"
+fn foo() {
+    let a = 1; // one
+
+    // two
+    let b = 2;
+}
+"#,
+    )
+    .tree();
+    let statements = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::LetStmt::cast)
+        .map(|stmt| comments(stmt.syntax()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    insta::assert_snapshot!(statements, @r###"
+    leading: [], trailing: Some("// one")
+    leading: ["// two"], trailing: None
+    "###);
+}
+
+#[test]
+fn doc_comment_text() {
+    let file = SourceFile::parse(
+        r#"
+/// Adds two numbers.
+///
+///   Indentation is preserved.
+#[inline]
+fn add() {}
+
+/** Block docs */
+struct Foo;
+
+// not a doc comment
+fn bar() {}
+"#,
+    )
+    .tree();
+    let docs = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::ModuleItem::cast)
+        .map(|item| format!("{:?}", item.doc_comment_text()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    insta::assert_snapshot!(docs, @r###"
+    Some("Adds two numbers.\n\n  Indentation is preserved.")
+    Some("Block docs")
+    None
+    "###);
+}
@@ -0,0 +1,132 @@
+//! Associates trivia, and comments in particular, with the syntax nodes they
+//! belong to.
+//!
+//! Comments are not part of the grammar, but tools that rewrite or inspect
+//! source code (e.g. a formatter, documentation extraction or structured
+//! edits) must not lose them. A comment is associated with a node by the
+//! following rules:
+//!
+//! * A *leading* comment precedes the node on its own line(s). The parser
+//!   already attaches these comments to declarations, struct fields and
+//!   `use` items, in which case they are the first children of the node. For
+//!   other nodes the comments are searched in the preceding tokens. A blank
+//!   line ends a group of leading comments, unless the comment before it is a
+//!   doc comment. Inner doc comments (`//!`) document the enclosing item and
+//!   are never leading comments.
+//! * A *trailing* comment starts on the same line as the last token of the
+//!   node, optionally after a separating `,` or `;`.
+//!
+//! A comment that trails a node is never a leading comment of the next node.
+
+use crate::{
+    ast::{self, AstToken, CommentPlacement},
+    SyntaxKind, SyntaxNode, SyntaxToken,
+};
+
+/// Returns the trivia tokens directly preceding `token`, starting with the
+/// token closest to it.
+pub fn leading_trivia(token: &SyntaxToken) -> impl Iterator<Item = SyntaxToken> {
+    std::iter::successors(token.prev_token(), SyntaxToken::prev_token)
+        .take_while(|it| it.kind().is_trivia())
+}
+
+/// Returns the trivia tokens directly following `token`, starting with the
+/// token closest to it.
+pub fn trailing_trivia(token: &SyntaxToken) -> impl Iterator<Item = SyntaxToken> {
+    std::iter::successors(token.next_token(), SyntaxToken::next_token)
+        .take_while(|it| it.kind().is_trivia())
+}
+
+/// The comments associated with a syntax node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AttachedComments {
+    /// The comments that precede the node, in source order.
+    pub leading: Vec<ast::Comment>,
+
+    /// The comment on the same line as the end of the node.
+    pub trailing: Option<ast::Comment>,
+}
+
+/// Returns the comments associated with `node`.
+pub fn attached_comments(node: &SyntaxNode) -> AttachedComments {
+    AttachedComments {
+        leading: leading_comments(node),
+        trailing: trailing_comment(node),
+    }
+}
+
+/// Returns the comments that precede `node`, in source order.
+pub fn leading_comments(node: &SyntaxNode) -> Vec<ast::Comment> {
+    // Comments that were attached by the parser are children of the node
+    let attached: Vec<_> = node
+        .children_with_tokens()
+        .map_while(|it| it.into_token().filter(|it| it.kind().is_trivia()))
+        .filter_map(ast::Comment::cast)
+        .collect();
+    if !attached.is_empty() {
+        return attached;
+    }
+
+    let Some(first_token) = node.first_token() else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    for token in leading_trivia(&first_token) {
+        match ast::Comment::cast(token.clone()) {
+            Some(comment) => {
+                if comment.kind().doc == Some(CommentPlacement::Inner)
+                    || !starts_line(comment.syntax())
+                {
+                    break;
+                }
+                result.push(comment);
+            }
+            None if token.text().contains("\n\n") => {
+                // Doc comments separated by a blank line are still attached
+                let prev_is_outer_doc = token
+                    .prev_token()
+                    .and_then(ast::Comment::cast)
+                    .is_some_and(|it| it.kind().doc == Some(CommentPlacement::Outer));
+                if !prev_is_outer_doc {
+                    break;
+                }
+            }
+            None => (),
+        }
+    }
+
+    result.reverse();
+    result
+}
+
+/// Returns the comment on the same line as the end of `node`, if any.
+pub fn trailing_comment(node: &SyntaxNode) -> Option<ast::Comment> {
+    let mut token = node.last_token()?;
+    if let Some(next) = next_non_whitespace_on_line(&token) {
+        if matches!(next.kind(), SyntaxKind::COMMA | SyntaxKind::SEMI) {
+            token = next;
+        }
+    }
+    next_non_whitespace_on_line(&token).and_then(ast::Comment::cast)
+}
+
+/// Returns the first token after `token` that is not whitespace, provided
+/// there is no line break in between.
+fn next_non_whitespace_on_line(token: &SyntaxToken) -> Option<SyntaxToken> {
+    std::iter::successors(token.next_token(), SyntaxToken::next_token)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE || it.text().contains('\n'))
+        .filter(|it| it.kind() != SyntaxKind::WHITESPACE)
+}
+
+/// Returns true if there are no non-whitespace tokens before `token` on the
+/// same line.
+fn starts_line(token: &SyntaxToken) -> bool {
+    match token.prev_token() {
+        None => true,
+        Some(prev) => {
+            prev.kind() == SyntaxKind::WHITESPACE
+                && (prev.text().contains('\n') || prev.prev_token().is_none())
+        }
+    }
+}
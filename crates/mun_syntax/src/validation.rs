@@ -3,8 +3,8 @@
 
 use crate::{
     ast,
-    ast::{AstNode, VisibilityOwner},
-    match_ast, SyntaxError, SyntaxNode,
+    ast::{AstNode, AstToken, VisibilityOwner},
+    match_ast, SyntaxError, SyntaxNode, TextRange, TextSize, T,
 };
 
 /// A validation pass that checks that the AST is valid.
@@ -18,6 +18,8 @@ pub(crate) fn validate(root: &SyntaxNode) -> Vec<SyntaxError> {
         match_ast! {
             match node {
                 ast::Impl(it) => validate_impl(it, &mut errors),
+                ast::Literal(it) => validate_literal(it, &mut errors),
+                ast::BlockExpr(it) => validate_block_expr(it, &mut errors),
                 _ => (),
             }
         }
@@ -58,3 +60,58 @@ fn validate_impl_associated_items(node: ast::Impl, errors: &mut Vec<SyntaxError>
         }
     }
 }
+
+/// Validates that a block is closed. The parser stops parsing a block when it
+/// encounters the start of a declaration, so an unclosed block does not
+/// swallow the rest of the file. Report the originating `{` so the user knows
+/// which block is missing its closing brace.
+fn validate_block_expr(node: ast::BlockExpr, errors: &mut Vec<SyntaxError>) {
+    let mut tokens = node
+        .syntax()
+        .children_with_tokens()
+        .filter_map(rowan::NodeOrToken::into_token);
+    let Some(l_curly) = tokens.find(|token| token.kind() == T!['{']) else {
+        return;
+    };
+    if !tokens.any(|token| token.kind() == T!['}']) {
+        errors.push(SyntaxError::parse_error(
+            "unclosed delimiter, this `{` is never closed",
+            l_curly.text_range(),
+        ));
+    }
+}
+
+/// Validates that the digits of an integer literal with a base prefix (e.g.
+/// `0x`, `0o` or `0b`) are valid for that base. The lexer accepts any decimal
+/// digit for all bases, so `0b102` is lexed as a single token.
+fn validate_literal(node: ast::Literal, errors: &mut Vec<SyntaxError>) {
+    let ast::LiteralKind::IntNumber(token) = node.kind() else {
+        return;
+    };
+
+    let (text, _suffix) = token.split_into_parts();
+    let (base, name) = match text.as_bytes() {
+        [b'0', b'x', ..] => (16, "hexadecimal"),
+        [b'0', b'o', ..] => (8, "octal"),
+        [b'0', b'b', ..] => (2, "binary"),
+        _ => return,
+    };
+
+    let range = token.syntax().text_range();
+    let digits = &text[2..];
+    if digits.chars().all(|c| c == '_') {
+        errors.push(SyntaxError::parse_error(
+            format!("missing digits after the {name} literal prefix"),
+            range,
+        ));
+    } else if let Some((offset, c)) = digits
+        .char_indices()
+        .find(|(_, c)| *c != '_' && !c.is_digit(base))
+    {
+        let start = range.start() + TextSize::of(&text[..2]) + TextSize::of(&digits[..offset]);
+        errors.push(SyntaxError::parse_error(
+            format!("invalid digit `{c}` in {name} literal"),
+            TextRange::at(start, TextSize::of(c)),
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use mun_compiler::{Config, Driver, PathOrInline};
+use mun_hir::{diagnostics::Severity, AstDatabase, DiagnosticSink, HirDatabase, Package};
+use mun_hir_input::{check_annotations, Fixture, SourceDatabase};
+use mun_syntax::{Location, TextRange};
+
+/// Asserts that the diagnostics emitted by the compiler for the files of the
+/// `fixture` match the inline annotations of the fixture, e.g.:
+///
+/// ```
+/// mun_test::check_diagnostics(
+///     r#"
+///     pub fn main() -> i32 {
+///         true
+///       //^^^^ error: mismatched type
+///     }
+///     "#,
+/// );
+/// ```
+///
+/// Syntax errors are annotated with `error:`. HIR diagnostics are annotated
+/// with their severity, e.g. `error:` or `warning:`.
+pub fn check_diagnostics(fixture: &str) {
+    let fixtures = Fixture::parse(fixture);
+    let (first, rest) = fixtures
+        .split_first()
+        .expect("a fixture contains at least one file");
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = Config {
+        out_dir: Some(temp_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let input = PathOrInline::Inline {
+        rel_path: first.relative_path.clone(),
+        contents: first.text.clone(),
+    };
+    let (mut driver, _) = Driver::with_file(config, input).unwrap();
+    for entry in rest {
+        driver.add_file(&entry.relative_path, entry.text.clone());
+    }
+
+    let db = driver.database();
+    let hir_db: &dyn HirDatabase = db;
+    let mut diagnostics = Vec::new();
+    for module in Package::all(hir_db)
+        .into_iter()
+        .flat_map(|package| package.modules(hir_db))
+    {
+        let Some(file_id) = module.file_id(hir_db) else {
+            continue;
+        };
+        let path = db.file_relative_path(file_id);
+        for error in db.parse(file_id).errors() {
+            // An offset is marked by a single caret
+            let range = match error.location() {
+                Location::Offset(offset) => TextRange::at(offset, 1.into()),
+                Location::Range(range) => range,
+            };
+            diagnostics.push((path.clone(), range, format!("error: {error}")));
+        }
+
+        module.diagnostics(
+            hir_db,
+            &mut DiagnosticSink::new(|diagnostic| {
+                let severity = match diagnostic.severity() {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                diagnostics.push((
+                    path.clone(),
+                    diagnostic.highlight_range(),
+                    format!("{severity}: {}", diagnostic.message()),
+                ));
+            }),
+        );
+    }
+
+    check_annotations(&fixtures, diagnostics);
+}
@@ -65,10 +65,17 @@ impl CompileTestDriver {
 
     /// Constructs a new `CompileTestDriver` from a single Mun source.
     pub fn from_file(text: &str) -> Self {
+        Self::from_file_with_config(text, Config::default())
+    }
+
+    /// Constructs a new `CompileTestDriver` from a single Mun source that is
+    /// compiled with the specified `config`. The output directory of the
+    /// `config` is ignored.
+    pub fn from_file_with_config(text: &str, config: Config) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let config = Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
-            ..Config::default()
+            ..config
         };
         let input = PathOrInline::Inline {
             rel_path: RelativePathBuf::from("mod.mun"),
@@ -166,7 +173,18 @@ impl CompileAndRunTestDriver {
         text: &str,
         config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
     ) -> Result<Self, InitError> {
-        let driver = CompileTestDriver::from_file(text);
+        Self::with_compiler_config(text, Config::default(), config_fn)
+    }
+
+    /// Constructs a `CompileAndRunTestDriver` from a single Mun source file
+    /// that is compiled with the specified compiler `config`, and a
+    /// `config_fn` that allows modification of a [`RuntimeBuilder`].
+    pub fn with_compiler_config(
+        text: &str,
+        config: Config,
+        config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+    ) -> Result<Self, InitError> {
+        let driver = CompileTestDriver::from_file_with_config(text, config);
         let builder = Runtime::builder(driver.lib_path());
 
         // Safety: We compiled the library ourselves, therefor loading the munlib is
@@ -199,4 +217,15 @@ impl CompileAndRunTestDriver {
             }
         }
     }
+
+    /// Updates the text of the Mun source and ensures that the generated
+    /// assembly has been recompiled, without reloading it in the runtime.
+    pub fn recompile_file(&mut self, path: impl AsRef<mun_paths::RelativePath>, text: &str) {
+        self.driver.update_file(path, text);
+    }
+
+    /// Returns the path to the generated `*.munlib` library.
+    pub fn lib_path(&self) -> &Path {
+        self.driver.lib_path()
+    }
 }
@@ -3,6 +3,8 @@
 //! Mun Test contains shared functionality for testing Mun crates.
 #![warn(missing_docs)]
 
+pub use diagnostics::*;
 pub use driver::*;
 
+mod diagnostics;
 mod driver;
@@ -3,7 +3,7 @@
 
 mod notify_monitor;
 
-use std::fmt;
+use std::{fmt, io};
 
 pub use notify_monitor::NotifyMonitor;
 
@@ -101,6 +101,29 @@ impl MonitorDirectories {
         self.includes_path(path)
     }
 
+    /// Returns true if the file at the given `path` is contained in this set
+    /// after resolving all symbolic links, of both the `path` and the
+    /// `include` and `exclude` paths. Unlike
+    /// [`MonitorDirectories::contains_file`] this accesses the filesystem,
+    /// which makes it possible to refuse files that are linked from outside
+    /// of the set.
+    pub fn contains_resolved_file(&self, path: impl AsRef<AbsPath>) -> io::Result<bool> {
+        let resolved = MonitorDirectories {
+            extensions: self.extensions.clone(),
+            include: self
+                .include
+                .iter()
+                .filter_map(|path| canonicalize(path).ok())
+                .collect(),
+            exclude: self
+                .exclude
+                .iter()
+                .filter_map(|path| canonicalize(path).ok())
+                .collect(),
+        };
+        Ok(resolved.contains_file(canonicalize(path.as_ref())?))
+    }
+
     /// Returns true if the given path is considered part of this set.
     fn includes_path(&self, path: impl AsRef<AbsPath>) -> bool {
         let path = path.as_ref();
@@ -156,6 +179,13 @@ impl MonitorEntry {
     }
 }
 
+/// Returns the canonical form of `path`, with all symbolic links resolved.
+fn canonicalize(path: &AbsPath) -> io::Result<AbsPathBuf> {
+    let canonical_path = path.canonicalize()?;
+    // A canonical path is always absolute
+    Ok(AbsPathBuf::try_from(canonical_path).unwrap())
+}
+
 impl fmt::Debug for MonitorMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -209,4 +239,34 @@ mod tests {
         assert!(config.contains_file(abs_manifest_dir.join("src/.git/special_case/mod.mun")));
         assert!(config.contains_dir(abs_manifest_dir.join("src")));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolved_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root: AbsPathBuf = dir.path().canonicalize().unwrap().try_into().unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::create_dir(root.join("outside")).unwrap();
+        std::fs::write(root.join("src/mod.mun"), "").unwrap();
+        std::fs::write(root.join("outside/mod.mun"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("outside/mod.mun"), root.join("src/linked.mun"))
+            .unwrap();
+
+        let config = MonitorDirectories {
+            extensions: vec!["mun".to_owned()],
+            include: vec![root.join("src")],
+            exclude: vec![],
+        };
+
+        assert!(config.contains_file(root.join("src/linked.mun")));
+        assert!(config
+            .contains_resolved_file(root.join("src/mod.mun"))
+            .unwrap());
+        assert!(!config
+            .contains_resolved_file(root.join("src/linked.mun"))
+            .unwrap());
+        assert!(config
+            .contains_resolved_file(root.join("src/missing.mun"))
+            .is_err());
+    }
 }
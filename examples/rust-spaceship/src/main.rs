@@ -12,14 +12,6 @@ use mun_runtime::{RootedStruct, Runtime, StructRef};
 
 use rand::prelude::*;
 
-extern "C" fn sin(number: f32) -> f32 {
-    number.sin()
-}
-
-extern "C" fn cos(number: f32) -> f32 {
-    number.cos()
-}
-
 extern "C" fn dbg(number: f32) {
     dbg!(number);
 }
@@ -28,10 +20,6 @@ extern "C" fn degrees_to_radians(degrees: f32) -> f32 {
     degrees.to_radians()
 }
 
-extern "C" fn sqrt(value: f32) -> f32 {
-    value.sqrt()
-}
-
 extern "C" fn game_area_width() -> f32 {
     128.0 * 5.0
 }
@@ -431,14 +419,11 @@ fn new_asteroids(runtime: &mun_runtime::Runtime) -> Vec<RootedStruct> {
 
 fn main() -> tetra::Result {
     let runtime = Runtime::builder("mun/target/mod.munlib")
-        .insert_fn("sin", sin as extern "C" fn(number: f32) -> f32)
-        .insert_fn("cos", cos as extern "C" fn(number: f32) -> f32)
         .insert_fn("dbg", dbg as extern "C" fn(number: f32))
         .insert_fn(
             "degrees_to_radians",
             degrees_to_radians as extern "C" fn(degrees: f32) -> f32,
         )
-        .insert_fn("sqrt", sqrt as extern "C" fn(value: f32) -> f32)
         .insert_fn("game_area_width", game_area_width as extern "C" fn() -> f32)
         .insert_fn(
             "game_area_height",